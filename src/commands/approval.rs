@@ -7,7 +7,11 @@ use std::sync::Mutex;
 use tauri::{command, State};
 use serde::{Serialize, Deserialize};
 use crate::database::approval::{ApprovalManager, Approval, ApprovalStatus};
+use crate::database::approval_policy::{ApprovalPolicyManager, ApprovalPolicy, ApprovalPolicyGroup, ApprovalEvaluation};
+use crate::database::group::{GroupManager, Group};
+use crate::database::signature::{SignatureManager, ElectronicSignature, SignatureVerification};
 use crate::database::connection_manager::ConnectionManager;
+use crate::commands::parts::DatabaseState;
 use std::time::UNIX_EPOCH;
 use chrono;
 
@@ -48,6 +52,123 @@ pub struct ApprovalUpdateData {
     pub comments: Option<String>,
 }
 
+/// Approval policy data structure for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalPolicyDto {
+    /// Policy ID
+    pub policy_id: i64,
+    /// Policy name
+    pub name: String,
+    /// Category the policy applies to, if any
+    pub category_id: Option<i64>,
+    /// Transition the policy applies to, if any
+    pub transition_id: Option<i64>,
+    /// Number of approvals needed
+    pub quorum: i64,
+    /// Number of rejections that reject the review
+    pub rejection_threshold: i64,
+    /// Whether the policy is active
+    pub active: bool,
+}
+
+/// Approval policy creation data from the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalPolicyCreationData {
+    /// Policy name
+    pub name: String,
+    /// Category the policy applies to (optional)
+    pub category_id: Option<i64>,
+    /// Transition the policy applies to (optional)
+    pub transition_id: Option<i64>,
+    /// Number of approvals needed
+    pub quorum: i64,
+    /// Number of rejections that reject the review
+    pub rejection_threshold: i64,
+}
+
+/// Approval policy group data structure for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalPolicyGroupDto {
+    /// Policy ID
+    pub policy_id: i64,
+    /// Group ID
+    pub group_id: i64,
+    /// Group name
+    pub group_name: String,
+    /// Number of approvals needed from the group
+    pub min_approvals: i64,
+}
+
+/// Approval evaluation data structure for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalEvaluationDto {
+    /// Policy that applies, if any
+    pub policy: Option<ApprovalPolicyDto>,
+    /// Decision of the review (Pending, Approved, Rejected)
+    pub decision: String,
+    /// Number of counted approvals
+    pub approvals: i64,
+    /// Number of counted rejections
+    pub rejections: i64,
+    /// Number of approvals needed
+    pub quorum: i64,
+    /// Required groups that have not given enough approvals
+    pub missing_groups: Vec<String>,
+}
+
+/// Group data structure for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupDto {
+    /// Group ID
+    pub group_id: i64,
+    /// Group name
+    pub name: String,
+    /// Description of the group
+    pub description: Option<String>,
+}
+
+/// Electronic signature data structure for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureDto {
+    /// Signature ID
+    pub signature_id: i64,
+    /// Revision ID the signature is for
+    pub revision_id: i64,
+    /// Approval ID the signature belongs to
+    pub approval_id: Option<i64>,
+    /// Signer username
+    pub signer: String,
+    /// Signer email address
+    pub signer_email: Option<String>,
+    /// Meaning of the signature (Review, Approve, Release)
+    pub meaning: String,
+    /// Date of the signature (ISO 8601 format)
+    pub timestamp: String,
+    /// Signed commit hash, approved for release
+    pub commit_hash: Option<String>,
+    /// Commit hash the revision was released with, for release signatures
+    pub released_commit_hash: Option<String>,
+    /// Digest of the signed revision content
+    pub content_digest: String,
+    /// Public key of the signer (hex)
+    pub public_key: String,
+}
+
+/// Result of verifying a signature for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureVerificationDto {
+    /// The verified signature
+    pub signature: SignatureDto,
+    /// Whether the signature holds
+    pub valid: bool,
+    /// Whether the signature was made with the registered key of the signer
+    pub authentic: bool,
+    /// Whether the revision metadata still matches the signed digest
+    pub content_matches: bool,
+    /// Whether the signed content is exactly the released content
+    pub covers_release: Option<bool>,
+}
+
 /// Approval state for the Tauri application
 pub struct ApprovalState {
     /// Connection manager for the database
@@ -83,6 +204,87 @@ impl From<Approval> for ApprovalDto {
     }
 }
 
+impl From<ElectronicSignature> for SignatureDto {
+    fn from(signature: ElectronicSignature) -> Self {
+        let secs = signature.timestamp.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        Self {
+            signature_id: signature.signature_id.unwrap_or_default(),
+            revision_id: signature.revision_id,
+            approval_id: signature.approval_id,
+            signer: signature.signer,
+            signer_email: signature.signer_email,
+            meaning: signature.meaning.to_str().to_string(),
+            timestamp: chrono::DateTime::<chrono::Utc>::from_timestamp(secs as i64, 0)
+                .map(|dt| dt.to_rfc3339())
+                .unwrap_or_else(|| String::from("Invalid date")),
+            commit_hash: signature.commit_hash,
+            released_commit_hash: signature.released_commit_hash,
+            content_digest: signature.content_digest,
+            public_key: signature.public_key,
+        }
+    }
+}
+
+impl From<SignatureVerification> for SignatureVerificationDto {
+    fn from(verification: SignatureVerification) -> Self {
+        Self {
+            valid: verification.is_valid(),
+            authentic: verification.authentic,
+            content_matches: verification.content_matches,
+            covers_release: verification.covers_release,
+            signature: SignatureDto::from(verification.signature),
+        }
+    }
+}
+
+impl From<ApprovalPolicy> for ApprovalPolicyDto {
+    fn from(policy: ApprovalPolicy) -> Self {
+        Self {
+            policy_id: policy.policy_id.unwrap_or_default(),
+            name: policy.name,
+            category_id: policy.category_id,
+            transition_id: policy.transition_id,
+            quorum: policy.quorum,
+            rejection_threshold: policy.rejection_threshold,
+            active: policy.active,
+        }
+    }
+}
+
+impl From<ApprovalPolicyGroup> for ApprovalPolicyGroupDto {
+    fn from(group: ApprovalPolicyGroup) -> Self {
+        Self {
+            policy_id: group.policy_id,
+            group_id: group.group_id,
+            group_name: group.group_name,
+            min_approvals: group.min_approvals,
+        }
+    }
+}
+
+impl From<ApprovalEvaluation> for ApprovalEvaluationDto {
+    fn from(evaluation: ApprovalEvaluation) -> Self {
+        Self {
+            policy: evaluation.policy.map(ApprovalPolicyDto::from),
+            decision: evaluation.decision.to_str().to_string(),
+            approvals: evaluation.approvals,
+            rejections: evaluation.rejections,
+            quorum: evaluation.quorum,
+            missing_groups: evaluation.missing_groups,
+        }
+    }
+}
+
+impl From<Group> for GroupDto {
+    fn from(group: Group) -> Self {
+        Self {
+            group_id: group.group_id.unwrap_or_default(),
+            name: group.name,
+            description: group.description,
+        }
+    }
+}
+
 /// Initialize the approval state
 pub fn init_approval_state(connection_manager: ConnectionManager) -> ApprovalState {
     // Create an approval manager with 'static lifetime using a leak (safe in this context)
@@ -153,8 +355,11 @@ pub async fn get_approval_for_revision_and_approver(
 pub async fn create_approval(
     approval_data: ApprovalCreationData,
     approval_state: State<'_, ApprovalState>,
+    db_state: State<'_, DatabaseState>,
 ) -> Result<ApprovalDto, String> {
-    let approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    let actor = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+    let approval_manager = ApprovalManager::new(&approval_state.connection_manager).with_actor(&actor);
     
     // Create a new approval
     let approval = Approval::new(
@@ -182,8 +387,11 @@ pub async fn update_approval_status(
     approval_id: i64,
     update_data: ApprovalUpdateData,
     approval_state: State<'_, ApprovalState>,
+    db_state: State<'_, DatabaseState>,
 ) -> Result<ApprovalDto, String> {
-    let approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    let actor = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+    let approval_manager = ApprovalManager::new(&approval_state.connection_manager).with_actor(&actor);
     
     // Convert the status string to ApprovalStatus
     let status = match update_data.status.as_str() {
@@ -210,8 +418,11 @@ pub async fn update_approval_status(
 pub async fn delete_approval(
     approval_id: i64,
     approval_state: State<'_, ApprovalState>,
+    db_state: State<'_, DatabaseState>,
 ) -> Result<(), String> {
-    let approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    let actor = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+    let approval_manager = ApprovalManager::new(&approval_state.connection_manager).with_actor(&actor);
     
     // Delete the approval
     approval_manager.delete_approval(approval_id)
@@ -241,8 +452,11 @@ pub async fn submit_for_approval(
     revision_id: i64,
     approvers: Vec<String>,
     approval_state: State<'_, ApprovalState>,
+    db_state: State<'_, DatabaseState>,
 ) -> Result<Vec<ApprovalDto>, String> {
-    let approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    let actor = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+    let approval_manager = ApprovalManager::new(&approval_state.connection_manager).with_actor(&actor);
     let mut approval_dtos = Vec::new();
     
     // Create an approval for each approver
@@ -273,8 +487,11 @@ pub async fn approve_revision(
     approver: String,
     comments: Option<String>,
     approval_state: State<'_, ApprovalState>,
+    db_state: State<'_, DatabaseState>,
 ) -> Result<ApprovalDto, String> {
-    let approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    let actor = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+    let approval_manager = ApprovalManager::new(&approval_state.connection_manager).with_actor(&actor);
     
     // Get the approval for this revision and approver
     let approval = approval_manager.get_approval_for_revision_and_approver(revision_id, &approver)
@@ -302,8 +519,11 @@ pub async fn reject_revision(
     approver: String,
     comments: Option<String>,
     approval_state: State<'_, ApprovalState>,
+    db_state: State<'_, DatabaseState>,
 ) -> Result<ApprovalDto, String> {
-    let approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    let actor = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+    let approval_manager = ApprovalManager::new(&approval_state.connection_manager).with_actor(&actor);
     
     // Get the approval for this revision and approver
     let approval = approval_manager.get_approval_for_revision_and_approver(revision_id, &approver)
@@ -322,4 +542,245 @@ pub async fn reject_revision(
     
     // Convert to DTO
     Ok(ApprovalDto::from(updated_approval))
-}
\ No newline at end of file
+}
+
+/// Get all approval policies
+#[command]
+pub async fn get_approval_policies(
+    approval_state: State<'_, ApprovalState>,
+) -> Result<Vec<ApprovalPolicyDto>, String> {
+    let _approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Get all policies
+    let policy_manager = ApprovalPolicyManager::new(&approval_state.connection_manager);
+    let policies = policy_manager.get_all_policies()
+        .map_err(|e| e.to_string())?;
+    
+    // Convert to DTOs
+    Ok(policies.into_iter().map(ApprovalPolicyDto::from).collect())
+}
+
+/// Create a new approval policy
+#[command]
+pub async fn create_approval_policy(
+    policy_data: ApprovalPolicyCreationData,
+    approval_state: State<'_, ApprovalState>,
+) -> Result<ApprovalPolicyDto, String> {
+    let _approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Create the policy
+    let policy_manager = ApprovalPolicyManager::new(&approval_state.connection_manager);
+    let policy = ApprovalPolicy::new(
+        policy_data.name,
+        policy_data.category_id,
+        policy_data.transition_id,
+        policy_data.quorum,
+        policy_data.rejection_threshold,
+    );
+    let policy_id = policy_manager.create_policy(&policy)
+        .map_err(|e| e.to_string())?;
+    
+    // Get the created policy
+    let created_policy = policy_manager.get_policy(policy_id)
+        .map_err(|e| e.to_string())?;
+    
+    // Convert to DTO
+    Ok(ApprovalPolicyDto::from(created_policy))
+}
+
+/// Delete an approval policy
+#[command]
+pub async fn delete_approval_policy(
+    policy_id: i64,
+    approval_state: State<'_, ApprovalState>,
+) -> Result<(), String> {
+    let _approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Delete the policy
+    ApprovalPolicyManager::new(&approval_state.connection_manager).delete_policy(policy_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Get the groups of an approval policy
+#[command]
+pub async fn get_approval_policy_groups(
+    policy_id: i64,
+    approval_state: State<'_, ApprovalState>,
+) -> Result<Vec<ApprovalPolicyGroupDto>, String> {
+    let _approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Get the groups
+    let groups = ApprovalPolicyManager::new(&approval_state.connection_manager).get_policy_groups(policy_id)
+        .map_err(|e| e.to_string())?;
+    
+    // Convert to DTOs
+    Ok(groups.into_iter().map(ApprovalPolicyGroupDto::from).collect())
+}
+
+/// Add a group to an approval policy, or change how many approvals it needs
+#[command]
+pub async fn set_approval_policy_group(
+    policy_id: i64,
+    group_id: i64,
+    min_approvals: i64,
+    approval_state: State<'_, ApprovalState>,
+) -> Result<(), String> {
+    let _approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Set the group
+    ApprovalPolicyManager::new(&approval_state.connection_manager).set_policy_group(policy_id, group_id, min_approvals)
+        .map_err(|e| e.to_string())
+}
+
+/// Remove a group from an approval policy
+#[command]
+pub async fn remove_approval_policy_group(
+    policy_id: i64,
+    group_id: i64,
+    approval_state: State<'_, ApprovalState>,
+) -> Result<(), String> {
+    let _approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Remove the group
+    ApprovalPolicyManager::new(&approval_state.connection_manager).remove_policy_group(policy_id, group_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Evaluate the approvals of a revision against its approval policy
+#[command]
+pub async fn evaluate_revision_approval(
+    revision_id: i64,
+    approval_state: State<'_, ApprovalState>,
+) -> Result<ApprovalEvaluationDto, String> {
+    let _approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Evaluate the review
+    let evaluation = ApprovalPolicyManager::new(&approval_state.connection_manager).evaluate(revision_id, None)
+        .map_err(|e| e.to_string())?;
+    
+    // Convert to DTO
+    Ok(ApprovalEvaluationDto::from(evaluation))
+}
+
+/// Get the electronic signatures on a revision
+#[command]
+pub async fn get_revision_signatures(
+    revision_id: i64,
+    approval_state: State<'_, ApprovalState>,
+) -> Result<Vec<SignatureDto>, String> {
+    let _approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Get the signatures
+    let signatures = SignatureManager::new(&approval_state.connection_manager).get_signatures(revision_id)
+        .map_err(|e| e.to_string())?;
+    
+    // Convert to DTOs
+    Ok(signatures.into_iter().map(SignatureDto::from).collect())
+}
+
+/// Verify that the electronic signatures on a revision cover exactly what was released
+#[command]
+pub async fn verify_revision_signatures(
+    revision_id: i64,
+    approval_state: State<'_, ApprovalState>,
+) -> Result<Vec<SignatureVerificationDto>, String> {
+    let _approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Verify the signatures
+    let verifications = SignatureManager::new(&approval_state.connection_manager).verify(revision_id)
+        .map_err(|e| e.to_string())?;
+    
+    // Convert to DTOs
+    Ok(verifications.into_iter().map(SignatureVerificationDto::from).collect())
+}
+
+/// Get all groups
+#[command]
+pub async fn get_groups(
+    approval_state: State<'_, ApprovalState>,
+) -> Result<Vec<GroupDto>, String> {
+    let _approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Get all groups
+    let groups = GroupManager::new(&approval_state.connection_manager).get_all_groups()
+        .map_err(|e| e.to_string())?;
+    
+    // Convert to DTOs
+    Ok(groups.into_iter().map(GroupDto::from).collect())
+}
+
+/// Create a new group
+#[command]
+pub async fn create_group(
+    name: String,
+    description: Option<String>,
+    approval_state: State<'_, ApprovalState>,
+) -> Result<GroupDto, String> {
+    let _approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Create the group
+    let group_manager = GroupManager::new(&approval_state.connection_manager);
+    let group_id = group_manager.create_group(&Group::new(name, description))
+        .map_err(|e| e.to_string())?;
+    
+    // Get the created group
+    let created_group = group_manager.get_group(group_id)
+        .map_err(|e| e.to_string())?;
+    
+    // Convert to DTO
+    Ok(GroupDto::from(created_group))
+}
+
+/// Delete a group
+#[command]
+pub async fn delete_group(
+    group_id: i64,
+    approval_state: State<'_, ApprovalState>,
+) -> Result<(), String> {
+    let _approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Delete the group
+    GroupManager::new(&approval_state.connection_manager).delete_group(group_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Get the members of a group
+#[command]
+pub async fn get_group_members(
+    group_id: i64,
+    approval_state: State<'_, ApprovalState>,
+) -> Result<Vec<String>, String> {
+    let _approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Get the members
+    GroupManager::new(&approval_state.connection_manager).get_members(group_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Add a member to a group
+#[command]
+pub async fn add_group_member(
+    group_id: i64,
+    member: String,
+    approval_state: State<'_, ApprovalState>,
+) -> Result<(), String> {
+    let _approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Add the member
+    GroupManager::new(&approval_state.connection_manager).add_member(group_id, &member)
+        .map_err(|e| e.to_string())
+}
+
+/// Remove a member from a group
+#[command]
+pub async fn remove_group_member(
+    group_id: i64,
+    member: String,
+    approval_state: State<'_, ApprovalState>,
+) -> Result<(), String> {
+    let _approval_manager = approval_state.approval_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Remove the member
+    GroupManager::new(&approval_state.connection_manager).remove_member(group_id, &member)
+        .map_err(|e| e.to_string())
+}
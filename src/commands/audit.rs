@@ -0,0 +1,150 @@
+//! Tauri command handlers for audit log operations
+//!
+//! This module contains the command handlers for the audit log in the Tauri application.
+//! These commands are exposed to the frontend and allow it to browse the history of metadata
+//! changes and to verify that the history was not tampered with.
+
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{command, State};
+use serde::{Serialize, Deserialize};
+use crate::database::audit::{AuditManager, AuditEntry, AuditFilter, AuditVerification};
+use crate::database::connection_manager::ConnectionManager;
+
+/// Audit log entry for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntryDto {
+    /// Position of the entry in the chain
+    pub entry_id: i64,
+    /// Date of the change (ISO 8601 format)
+    pub timestamp: String,
+    /// User who made the change
+    pub user: String,
+    /// Type of the changed entity (Part, Revision, Relationship, Property, Approval)
+    pub entity_type: String,
+    /// ID of the changed entity
+    pub entity_id: i64,
+    /// Kind of change (Create, Update, Delete)
+    pub action: String,
+    /// JSON description of the change
+    pub details: Option<String>,
+    /// Hash of this entry
+    pub hash: String,
+}
+
+/// Audit log filter from the frontend
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditFilterDto {
+    /// Type of the changed entity (optional)
+    pub entity_type: Option<String>,
+    /// ID of the changed entity (optional)
+    pub entity_id: Option<i64>,
+    /// User who made the change (optional)
+    pub user: Option<String>,
+    /// Earliest date of the change in seconds since UNIX epoch (optional)
+    pub from: Option<i64>,
+    /// Latest date of the change in seconds since UNIX epoch (optional)
+    pub to: Option<i64>,
+}
+
+/// Result of verifying the audit log for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditVerificationDto {
+    /// Whether the chain is intact
+    pub valid: bool,
+    /// Number of entries checked
+    pub entries_checked: i64,
+    /// ID of the first entry that breaks the chain
+    pub first_invalid_entry: Option<i64>,
+}
+
+/// Audit state for the Tauri application
+pub struct AuditState {
+    /// Connection manager for the database
+    pub connection_manager: ConnectionManager,
+    /// Audit manager for audit log operations
+    pub audit_manager: Mutex<AuditManager<'static>>,
+}
+
+impl From<AuditEntry> for AuditEntryDto {
+    fn from(entry: AuditEntry) -> Self {
+        let secs = entry.timestamp.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        Self {
+            entry_id: entry.entry_id,
+            timestamp: chrono::DateTime::<chrono::Utc>::from_timestamp(secs as i64, 0)
+                .map(|dt| dt.to_rfc3339())
+                .unwrap_or_else(|| String::from("Invalid date")),
+            user: entry.user,
+            entity_type: entry.entity_type,
+            entity_id: entry.entity_id,
+            action: entry.action.to_str().to_string(),
+            details: entry.details,
+            hash: entry.hash,
+        }
+    }
+}
+
+impl From<AuditFilterDto> for AuditFilter {
+    fn from(filter: AuditFilterDto) -> Self {
+        let from_secs = |secs: i64| -> SystemTime { UNIX_EPOCH + Duration::from_secs(secs.max(0) as u64) };
+        Self {
+            entity_type: filter.entity_type,
+            entity_id: filter.entity_id,
+            user: filter.user,
+            from: filter.from.map(from_secs),
+            to: filter.to.map(from_secs),
+        }
+    }
+}
+
+impl From<AuditVerification> for AuditVerificationDto {
+    fn from(verification: AuditVerification) -> Self {
+        Self {
+            valid: verification.is_valid(),
+            entries_checked: verification.entries_checked,
+            first_invalid_entry: verification.first_invalid_entry,
+        }
+    }
+}
+
+/// Initialize the audit state
+pub fn init_audit_state(connection_manager: ConnectionManager) -> AuditState {
+    // Create an audit manager with 'static lifetime using a leak (safe in this context)
+    let static_connection_manager: &'static ConnectionManager = Box::leak(Box::new(connection_manager.clone()));
+    let audit_manager = AuditManager::new(static_connection_manager);
+
+    AuditState {
+        connection_manager,
+        audit_manager: Mutex::new(audit_manager),
+    }
+}
+
+/// Query the audit log by entity, user and time range
+#[command]
+pub async fn get_audit_log(
+    filter: Option<AuditFilterDto>,
+    audit_state: State<'_, AuditState>,
+) -> Result<Vec<AuditEntryDto>, String> {
+    let audit_manager = audit_state.audit_manager.lock().map_err(|e| e.to_string())?;
+
+    // Query the entries
+    let entries = audit_manager.query(&AuditFilter::from(filter.unwrap_or_default()))
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTOs
+    Ok(entries.into_iter().map(AuditEntryDto::from).collect())
+}
+
+/// Verify that the audit log was not tampered with
+#[command]
+pub async fn verify_audit_log(
+    audit_state: State<'_, AuditState>,
+) -> Result<AuditVerificationDto, String> {
+    let audit_manager = audit_state.audit_manager.lock().map_err(|e| e.to_string())?;
+
+    // Check the hash chain
+    let verification = audit_manager.verify()
+        .map_err(|e| e.to_string())?;
+
+    Ok(AuditVerificationDto::from(verification))
+}
@@ -0,0 +1,186 @@
+//! Tauri command handlers for change impact operations
+//!
+//! This module contains the command handlers for change impact analysis in the Tauri application.
+//! These commands are exposed to the frontend and allow it to record the form-fit-function flags
+//! of a revision and to show approvers the impact report attached to its review.
+
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{command, State};
+use serde::{Serialize, Deserialize};
+use crate::database::change_impact::{ChangeImpactManager, ChangeImpactReport, ChangeFlags, WhereUsedEntry};
+use crate::database::connection_manager::ConnectionManager;
+use crate::commands::parts::DatabaseState;
+
+/// Form-fit-function flags data structure for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeFlagsDto {
+    /// Whether the form of the part changes
+    pub form_changed: bool,
+    /// Whether the fit of the part changes
+    pub fit_changed: bool,
+    /// Whether the function of the part changes
+    pub function_changed: bool,
+    /// Notes describing the change
+    pub notes: Option<String>,
+}
+
+/// Change impact report data structure for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeImpactReportDto {
+    /// ID of the proposed revision
+    pub revision_id: i64,
+    /// ID of the changed part
+    pub part_id: i64,
+    /// Display part number of the changed part
+    pub part_number: String,
+    /// Version of the proposed revision
+    pub version: String,
+    /// Form-fit-function flags of the revision
+    pub flags: ChangeFlagsDto,
+    /// Recommendation (New Revision, New Part Number)
+    pub recommendation: String,
+    /// Reasons for the recommendation
+    pub reasons: Vec<String>,
+    /// Non-obsolete assembly revisions that use the part, nearest first
+    pub where_used: Vec<WhereUsedEntry>,
+    /// Number of the open change order that contains the revision
+    pub open_change_order: Option<String>,
+    /// User the report was generated for
+    pub generated_by: String,
+    /// Generation date (ISO 8601 format)
+    pub generated_date: String,
+}
+
+/// Change impact state for the Tauri application
+pub struct ChangeImpactState {
+    /// Connection manager for the database
+    pub connection_manager: ConnectionManager,
+    /// Change impact manager for change impact operations
+    pub change_impact_manager: Mutex<ChangeImpactManager<'static>>,
+}
+
+/// Format a timestamp in ISO 8601 format
+fn format_date(date: SystemTime) -> String {
+    let secs = date.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    chrono::DateTime::<chrono::Utc>::from_timestamp(secs as i64, 0)
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_else(|| String::from("Invalid date"))
+}
+
+impl From<ChangeFlags> for ChangeFlagsDto {
+    fn from(flags: ChangeFlags) -> Self {
+        Self {
+            form_changed: flags.form_changed,
+            fit_changed: flags.fit_changed,
+            function_changed: flags.function_changed,
+            notes: flags.notes,
+        }
+    }
+}
+
+impl From<ChangeFlagsDto> for ChangeFlags {
+    fn from(flags: ChangeFlagsDto) -> Self {
+        Self {
+            form_changed: flags.form_changed,
+            fit_changed: flags.fit_changed,
+            function_changed: flags.function_changed,
+            notes: flags.notes,
+        }
+    }
+}
+
+impl From<ChangeImpactReport> for ChangeImpactReportDto {
+    fn from(report: ChangeImpactReport) -> Self {
+        Self {
+            revision_id: report.revision_id,
+            part_id: report.part_id,
+            part_number: report.part_number,
+            version: report.version,
+            flags: ChangeFlagsDto::from(report.flags),
+            recommendation: report.recommendation.to_str().to_string(),
+            reasons: report.reasons,
+            where_used: report.where_used,
+            open_change_order: report.open_change_order,
+            generated_by: report.generated_by,
+            generated_date: format_date(report.generated_date),
+        }
+    }
+}
+
+/// Initialize the change impact state
+pub fn init_change_impact_state(connection_manager: ConnectionManager) -> ChangeImpactState {
+    // Create a change impact manager with 'static lifetime using a leak (safe in this context)
+    let static_connection_manager: &'static ConnectionManager = Box::leak(Box::new(connection_manager.clone()));
+    let change_impact_manager = ChangeImpactManager::new(static_connection_manager);
+
+    ChangeImpactState {
+        connection_manager,
+        change_impact_manager: Mutex::new(change_impact_manager),
+    }
+}
+
+/// Get the form-fit-function flags of a revision
+#[command]
+pub async fn get_change_flags(
+    revision_id: i64,
+    change_impact_state: State<'_, ChangeImpactState>,
+) -> Result<ChangeFlagsDto, String> {
+    let change_impact_manager = change_impact_state.change_impact_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get the flags
+    let flags = change_impact_manager.get_flags(revision_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(ChangeFlagsDto::from(flags))
+}
+
+/// Set the form-fit-function flags of a draft revision
+#[command]
+pub async fn set_change_flags(
+    revision_id: i64,
+    flags: ChangeFlagsDto,
+    change_impact_state: State<'_, ChangeImpactState>,
+) -> Result<(), String> {
+    let change_impact_manager = change_impact_state.change_impact_manager.lock().map_err(|e| e.to_string())?;
+
+    // Store the flags
+    change_impact_manager.set_flags(revision_id, &ChangeFlags::from(flags))
+        .map_err(|e| e.to_string())
+}
+
+/// Analyze the impact of a revision as it stands now
+#[command]
+pub async fn analyze_change_impact(
+    revision_id: i64,
+    change_impact_state: State<'_, ChangeImpactState>,
+    db_state: State<'_, DatabaseState>,
+) -> Result<ChangeImpactReportDto, String> {
+    let change_impact_manager = change_impact_state.change_impact_manager.lock().map_err(|e| e.to_string())?;
+    let generated_by = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+
+    // Generate the report
+    let report = change_impact_manager.analyze(revision_id, &generated_by)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(ChangeImpactReportDto::from(report))
+}
+
+/// Get the impact report attached to the latest review of a revision
+#[command]
+pub async fn get_review_impact_report(
+    revision_id: i64,
+    change_impact_state: State<'_, ChangeImpactState>,
+) -> Result<Option<ChangeImpactReportDto>, String> {
+    let change_impact_manager = change_impact_state.change_impact_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get the attached report
+    let report = change_impact_manager.get_attached_report(revision_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(report.map(ChangeImpactReportDto::from))
+}
@@ -0,0 +1,310 @@
+//! Tauri command handlers for change order operations
+//!
+//! This module contains the command handlers for engineering change orders in the Tauri application.
+//! These commands are exposed to the frontend and allow it to group revisions into change orders
+//! and review, approve and release them as a unit.
+
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{command, State};
+use serde::{Serialize, Deserialize};
+use crate::database::change_order::{ChangeOrderManager, ChangeOrder};
+use crate::database::connection_manager::ConnectionManager;
+use crate::commands::parts::DatabaseState;
+use crate::commands::revision::RevisionDto;
+
+/// Change order data structure for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeOrderDto {
+    /// Change order ID
+    pub eco_id: i64,
+    /// Change order number, e.g. ECO-00042
+    pub eco_number: String,
+    /// Short title of the change
+    pub title: String,
+    /// Reason for the change
+    pub reason: String,
+    /// Impact of the change
+    pub impact: Option<String>,
+    /// Status (Draft, In Review, Released, Cancelled)
+    pub status: String,
+    /// User who created the change order
+    pub created_by: String,
+    /// Creation date (ISO 8601 format)
+    pub created_date: String,
+    /// Release date (ISO 8601 format)
+    pub released_date: Option<String>,
+    /// Git commit hash of the release
+    pub commit_hash: Option<String>,
+}
+
+/// Change order creation and update data from the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeOrderData {
+    /// Short title of the change
+    pub title: String,
+    /// Reason for the change
+    pub reason: String,
+    /// Impact of the change (optional)
+    pub impact: Option<String>,
+}
+
+/// Change order state for the Tauri application
+pub struct ChangeOrderState {
+    /// Connection manager for the database
+    pub connection_manager: ConnectionManager,
+    /// Change order manager for change order operations
+    pub change_order_manager: Mutex<ChangeOrderManager<'static>>,
+}
+
+/// Format a timestamp in ISO 8601 format
+fn format_date(date: SystemTime) -> String {
+    let secs = date.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    chrono::DateTime::<chrono::Utc>::from_timestamp(secs as i64, 0)
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_else(|| String::from("Invalid date"))
+}
+
+impl From<ChangeOrder> for ChangeOrderDto {
+    fn from(change_order: ChangeOrder) -> Self {
+        Self {
+            eco_id: change_order.eco_id.unwrap_or_default(),
+            eco_number: change_order.eco_number,
+            title: change_order.title,
+            reason: change_order.reason,
+            impact: change_order.impact,
+            status: change_order.status.to_str().to_string(),
+            created_by: change_order.created_by,
+            created_date: format_date(change_order.created_date),
+            released_date: change_order.released_date.map(format_date),
+            commit_hash: change_order.commit_hash,
+        }
+    }
+}
+
+/// Initialize the change order state
+pub fn init_change_order_state(connection_manager: ConnectionManager) -> ChangeOrderState {
+    // Create a change order manager with 'static lifetime using a leak (safe in this context)
+    let static_connection_manager: &'static ConnectionManager = Box::leak(Box::new(connection_manager.clone()));
+    let change_order_manager = ChangeOrderManager::new(static_connection_manager);
+
+    ChangeOrderState {
+        connection_manager,
+        change_order_manager: Mutex::new(change_order_manager),
+    }
+}
+
+/// Get all change orders
+#[command]
+pub async fn get_change_orders(
+    change_order_state: State<'_, ChangeOrderState>,
+) -> Result<Vec<ChangeOrderDto>, String> {
+    let change_order_manager = change_order_state.change_order_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get all change orders
+    let change_orders = change_order_manager.get_all_change_orders()
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTOs
+    Ok(change_orders.into_iter().map(ChangeOrderDto::from).collect())
+}
+
+/// Get a change order by ID
+#[command]
+pub async fn get_change_order(
+    eco_id: i64,
+    change_order_state: State<'_, ChangeOrderState>,
+) -> Result<ChangeOrderDto, String> {
+    let change_order_manager = change_order_state.change_order_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get the change order
+    let change_order = change_order_manager.get_change_order(eco_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(ChangeOrderDto::from(change_order))
+}
+
+/// Create a new change order on behalf of the current user
+#[command]
+pub async fn create_change_order(
+    change_order_data: ChangeOrderData,
+    change_order_state: State<'_, ChangeOrderState>,
+    db_state: State<'_, DatabaseState>,
+) -> Result<ChangeOrderDto, String> {
+    let change_order_manager = change_order_state.change_order_manager.lock().map_err(|e| e.to_string())?;
+    let created_by = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+
+    // Create the change order
+    let change_order = ChangeOrder::new(
+        change_order_data.title,
+        change_order_data.reason,
+        change_order_data.impact,
+        created_by,
+    );
+    let eco_id = change_order_manager.create_change_order(&change_order)
+        .map_err(|e| e.to_string())?;
+
+    // Get the created change order
+    let created_change_order = change_order_manager.get_change_order(eco_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(ChangeOrderDto::from(created_change_order))
+}
+
+/// Update the title, reason and impact of a draft change order
+#[command]
+pub async fn update_change_order(
+    eco_id: i64,
+    change_order_data: ChangeOrderData,
+    change_order_state: State<'_, ChangeOrderState>,
+) -> Result<ChangeOrderDto, String> {
+    let change_order_manager = change_order_state.change_order_manager.lock().map_err(|e| e.to_string())?;
+
+    // Update the change order
+    let mut change_order = change_order_manager.get_change_order(eco_id)
+        .map_err(|e| e.to_string())?;
+    change_order.title = change_order_data.title;
+    change_order.reason = change_order_data.reason;
+    change_order.impact = change_order_data.impact;
+    change_order_manager.update_change_order(&change_order)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(ChangeOrderDto::from(change_order))
+}
+
+/// Get the revisions of a change order
+#[command]
+pub async fn get_change_order_revisions(
+    eco_id: i64,
+    change_order_state: State<'_, ChangeOrderState>,
+) -> Result<Vec<RevisionDto>, String> {
+    let change_order_manager = change_order_state.change_order_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get the revisions
+    let revisions = change_order_manager.get_revisions(eco_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTOs
+    Ok(revisions.into_iter().map(RevisionDto::from).collect())
+}
+
+/// Add a draft revision to a change order
+#[command]
+pub async fn add_revision_to_change_order(
+    eco_id: i64,
+    revision_id: i64,
+    change_order_state: State<'_, ChangeOrderState>,
+) -> Result<(), String> {
+    let change_order_manager = change_order_state.change_order_manager.lock().map_err(|e| e.to_string())?;
+
+    // Add the revision
+    change_order_manager.add_revision(eco_id, revision_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Remove a revision from a change order
+#[command]
+pub async fn remove_revision_from_change_order(
+    eco_id: i64,
+    revision_id: i64,
+    change_order_state: State<'_, ChangeOrderState>,
+) -> Result<(), String> {
+    let change_order_manager = change_order_state.change_order_manager.lock().map_err(|e| e.to_string())?;
+
+    // Remove the revision
+    change_order_manager.remove_revision(eco_id, revision_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Get the combined review decision of a change order (Pending, Approved, Rejected)
+#[command]
+pub async fn evaluate_change_order(
+    eco_id: i64,
+    change_order_state: State<'_, ChangeOrderState>,
+) -> Result<String, String> {
+    let change_order_manager = change_order_state.change_order_manager.lock().map_err(|e| e.to_string())?;
+
+    // Evaluate the reviews of the revisions
+    let decision = change_order_manager.evaluate(eco_id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(decision.to_str().to_string())
+}
+
+/// Submit a change order for review
+#[command]
+pub async fn submit_change_order(
+    eco_id: i64,
+    reviewers: Vec<String>,
+    db_state: State<'_, DatabaseState>,
+) -> Result<(), String> {
+    let part_manager = db_state.part_manager.lock().map_err(|e| e.to_string())?;
+
+    // The change order branch is created in the repository in the current directory
+    let repo_path = std::env::current_dir().map_err(|e| e.to_string())?;
+
+    // Submit all revisions for review
+    part_manager.submit_change_order_for_review(eco_id, &repo_path, reviewers)
+        .map_err(|e| e.to_string())
+}
+
+/// Approve a change order as the current user
+#[command]
+pub async fn approve_change_order(
+    eco_id: i64,
+    comments: Option<String>,
+    db_state: State<'_, DatabaseState>,
+) -> Result<(), String> {
+    let part_manager = db_state.part_manager.lock().map_err(|e| e.to_string())?;
+
+    // Approve all revisions the user reviews
+    part_manager.approve_change_order(eco_id, comments)
+        .map_err(|e| e.to_string())
+}
+
+/// Reject a change order as the current user
+#[command]
+pub async fn reject_change_order(
+    eco_id: i64,
+    comments: Option<String>,
+    db_state: State<'_, DatabaseState>,
+) -> Result<(), String> {
+    let part_manager = db_state.part_manager.lock().map_err(|e| e.to_string())?;
+
+    // Reject all revisions the user reviews
+    part_manager.reject_change_order(eco_id, comments)
+        .map_err(|e| e.to_string())
+}
+
+/// Release a change order and return the commit hash of the release
+#[command]
+pub async fn release_change_order(
+    eco_id: i64,
+    db_state: State<'_, DatabaseState>,
+) -> Result<String, String> {
+    let part_manager = db_state.part_manager.lock().map_err(|e| e.to_string())?;
+
+    // The change order is merged into main of the repository in the current directory
+    let repo_path = std::env::current_dir().map_err(|e| e.to_string())?;
+
+    // Release all revisions with a single merge and tag
+    part_manager.release_change_order(eco_id, &repo_path)
+        .map_err(|e| e.to_string())
+}
+
+/// Cancel a change order
+#[command]
+pub async fn cancel_change_order(
+    eco_id: i64,
+    db_state: State<'_, DatabaseState>,
+) -> Result<(), String> {
+    let part_manager = db_state.part_manager.lock().map_err(|e| e.to_string())?;
+
+    // Cancel the change order and withdraw its revisions from review
+    part_manager.cancel_change_order(eco_id)
+        .map_err(|e| e.to_string())
+}
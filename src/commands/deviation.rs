@@ -0,0 +1,319 @@
+//! Tauri command handlers for deviation operations
+//!
+//! This module contains the command handlers for deviations and waivers in the Tauri application.
+//! These commands are exposed to the frontend and allow it to request, approve and track
+//! time-limited or quantity-limited deviations, and to export BOMs that list them.
+
+use std::fs::File;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{command, State};
+use serde::{Serialize, Deserialize};
+use crate::database::deviation::{DeviationManager, Deviation, DeviationType, DeviationItem, DeviationNotice};
+use crate::database::bom_export::BomExportManager;
+use crate::database::connection_manager::ConnectionManager;
+use crate::commands::parts::DatabaseState;
+
+/// Deviation data structure for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviationDto {
+    /// Deviation ID
+    pub deviation_id: i64,
+    /// Deviation number, e.g. DEV-00042
+    pub deviation_number: String,
+    /// Type (Deviation, Waiver)
+    pub deviation_type: String,
+    /// Short title
+    pub title: String,
+    /// Description
+    pub description: Option<String>,
+    /// ID of the replaced manufacturer part
+    pub original_mpn_id: Option<i64>,
+    /// ID of the substitute manufacturer part
+    pub substitute_mpn_id: Option<i64>,
+    /// Number of units the deviation applies to
+    pub quantity_limit: Option<i64>,
+    /// Number of units built under the deviation
+    pub quantity_used: i64,
+    /// Expiry date (seconds since UNIX epoch)
+    pub expiry_date: Option<i64>,
+    /// Status (Pending, Approved, Rejected, Closed)
+    pub status: String,
+    /// Whether the deviation ran out of time or quantity
+    pub expired: bool,
+    /// User who requested the deviation
+    pub created_by: String,
+    /// User who approved or rejected the deviation
+    pub approved_by: Option<String>,
+}
+
+/// Deviation creation data from the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviationCreationData {
+    /// Type (Deviation, Waiver)
+    pub deviation_type: String,
+    /// Short title
+    pub title: String,
+    /// Description (optional)
+    pub description: Option<String>,
+    /// ID of the replaced manufacturer part (optional)
+    pub original_mpn_id: Option<i64>,
+    /// ID of the substitute manufacturer part (optional)
+    pub substitute_mpn_id: Option<i64>,
+    /// Number of units the deviation applies to (optional)
+    pub quantity_limit: Option<i64>,
+    /// Expiry date in seconds since UNIX epoch (optional)
+    pub expiry_date: Option<i64>,
+}
+
+/// Affected part or revision of a deviation for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviationItemDto {
+    /// Deviation ID
+    pub deviation_id: i64,
+    /// Affected part ID
+    pub part_id: i64,
+    /// Affected revision ID; null if every revision of the part is affected
+    pub revision_id: Option<i64>,
+}
+
+/// Deviation that applies to a BOM line for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviationNoticeDto {
+    /// Part ID the deviation applies to
+    pub part_id: i64,
+    /// The deviation
+    pub deviation: DeviationDto,
+}
+
+/// Deviation state for the Tauri application
+pub struct DeviationState {
+    /// Connection manager for the database
+    pub connection_manager: ConnectionManager,
+    /// Deviation manager for deviation operations
+    pub deviation_manager: Mutex<DeviationManager<'static>>,
+}
+
+impl From<Deviation> for DeviationDto {
+    fn from(deviation: Deviation) -> Self {
+        let expired = deviation.is_expired(SystemTime::now());
+        Self {
+            deviation_id: deviation.deviation_id.unwrap_or_default(),
+            deviation_number: deviation.deviation_number,
+            deviation_type: deviation.deviation_type.to_str().to_string(),
+            title: deviation.title,
+            description: deviation.description,
+            original_mpn_id: deviation.original_mpn_id,
+            substitute_mpn_id: deviation.substitute_mpn_id,
+            quantity_limit: deviation.quantity_limit,
+            quantity_used: deviation.quantity_used,
+            expiry_date: deviation.expiry_date
+                .map(|date| date.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64),
+            status: deviation.status.to_str().to_string(),
+            expired,
+            created_by: deviation.created_by,
+            approved_by: deviation.approved_by,
+        }
+    }
+}
+
+impl From<DeviationItem> for DeviationItemDto {
+    fn from(item: DeviationItem) -> Self {
+        Self {
+            deviation_id: item.deviation_id,
+            part_id: item.part_id,
+            revision_id: item.revision_id,
+        }
+    }
+}
+
+impl From<DeviationNotice> for DeviationNoticeDto {
+    fn from(notice: DeviationNotice) -> Self {
+        let mut deviation = DeviationDto::from(notice.deviation);
+        deviation.expired = notice.expired;
+        Self {
+            part_id: notice.part_id,
+            deviation,
+        }
+    }
+}
+
+/// Initialize the deviation state
+pub fn init_deviation_state(connection_manager: ConnectionManager) -> DeviationState {
+    // Create a deviation manager with 'static lifetime using a leak (safe in this context)
+    let static_connection_manager: &'static ConnectionManager = Box::leak(Box::new(connection_manager.clone()));
+    let deviation_manager = DeviationManager::new(static_connection_manager);
+
+    DeviationState {
+        connection_manager,
+        deviation_manager: Mutex::new(deviation_manager),
+    }
+}
+
+/// Get all deviations
+#[command]
+pub async fn get_deviations(
+    deviation_state: State<'_, DeviationState>,
+) -> Result<Vec<DeviationDto>, String> {
+    let deviation_manager = deviation_state.deviation_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get all deviations
+    let deviations = deviation_manager.get_all_deviations()
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTOs
+    Ok(deviations.into_iter().map(DeviationDto::from).collect())
+}
+
+/// Request a new deviation on behalf of the current user
+#[command]
+pub async fn create_deviation(
+    deviation_data: DeviationCreationData,
+    deviation_state: State<'_, DeviationState>,
+    db_state: State<'_, DatabaseState>,
+) -> Result<DeviationDto, String> {
+    let deviation_manager = deviation_state.deviation_manager.lock().map_err(|e| e.to_string())?;
+    let created_by = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+
+    // Convert the type string to DeviationType
+    let deviation_type = DeviationType::from_str(&deviation_data.deviation_type)
+        .ok_or_else(|| format!("Invalid deviation type: {}", deviation_data.deviation_type))?;
+
+    // Create the deviation
+    let mut deviation = Deviation::new(
+        deviation_type,
+        deviation_data.title,
+        deviation_data.quantity_limit,
+        deviation_data.expiry_date.map(|secs| UNIX_EPOCH + Duration::from_secs(secs.max(0) as u64)),
+        created_by,
+    );
+    deviation.description = deviation_data.description;
+    deviation.original_mpn_id = deviation_data.original_mpn_id;
+    deviation.substitute_mpn_id = deviation_data.substitute_mpn_id;
+    let deviation_id = deviation_manager.create_deviation(&deviation)
+        .map_err(|e| e.to_string())?;
+
+    // Get the created deviation
+    let created_deviation = deviation_manager.get_deviation(deviation_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(DeviationDto::from(created_deviation))
+}
+
+/// Get the parts and revisions affected by a deviation
+#[command]
+pub async fn get_deviation_items(
+    deviation_id: i64,
+    deviation_state: State<'_, DeviationState>,
+) -> Result<Vec<DeviationItemDto>, String> {
+    let deviation_manager = deviation_state.deviation_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get the items
+    let items = deviation_manager.get_items(deviation_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTOs
+    Ok(items.into_iter().map(DeviationItemDto::from).collect())
+}
+
+/// Link a part or revision to a pending deviation
+#[command]
+pub async fn add_deviation_item(
+    deviation_id: i64,
+    part_id: i64,
+    revision_id: Option<i64>,
+    deviation_state: State<'_, DeviationState>,
+) -> Result<(), String> {
+    let deviation_manager = deviation_state.deviation_manager.lock().map_err(|e| e.to_string())?;
+
+    // Add the item
+    deviation_manager.add_item(deviation_id, part_id, revision_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Remove a part or revision from a pending deviation
+#[command]
+pub async fn remove_deviation_item(
+    deviation_id: i64,
+    part_id: i64,
+    revision_id: Option<i64>,
+    deviation_state: State<'_, DeviationState>,
+) -> Result<(), String> {
+    let deviation_manager = deviation_state.deviation_manager.lock().map_err(|e| e.to_string())?;
+
+    // Remove the item
+    deviation_manager.remove_item(deviation_id, part_id, revision_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Approve or reject a deviation as the current user
+#[command]
+pub async fn decide_deviation(
+    deviation_id: i64,
+    approved: bool,
+    deviation_state: State<'_, DeviationState>,
+    db_state: State<'_, DatabaseState>,
+) -> Result<DeviationDto, String> {
+    let deviation_manager = deviation_state.deviation_manager.lock().map_err(|e| e.to_string())?;
+    let approver = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().clone();
+
+    // Record the decision
+    deviation_manager.decide(deviation_id, &approver, approved)
+        .map_err(|e| e.to_string())?;
+
+    // Get the updated deviation
+    let deviation = deviation_manager.get_deviation(deviation_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(DeviationDto::from(deviation))
+}
+
+/// Close a deviation before it expires
+#[command]
+pub async fn close_deviation(
+    deviation_id: i64,
+    deviation_state: State<'_, DeviationState>,
+) -> Result<(), String> {
+    let deviation_manager = deviation_state.deviation_manager.lock().map_err(|e| e.to_string())?;
+
+    // Close the deviation
+    deviation_manager.close_deviation(deviation_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Record units built under a deviation
+#[command]
+pub async fn record_deviation_usage(
+    deviation_id: i64,
+    quantity: i64,
+    deviation_state: State<'_, DeviationState>,
+) -> Result<DeviationDto, String> {
+    let deviation_manager = deviation_state.deviation_manager.lock().map_err(|e| e.to_string())?;
+
+    // Record the usage
+    let deviation = deviation_manager.record_usage(deviation_id, quantity)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(DeviationDto::from(deviation))
+}
+
+/// Export the BOM of an assembly revision with its deviations as CSV
+#[command]
+pub async fn export_bom_csv(
+    revision_id: i64,
+    file_path: String,
+    deviation_state: State<'_, DeviationState>,
+) -> Result<(), String> {
+    let _deviation_manager = deviation_state.deviation_manager.lock().map_err(|e| e.to_string())?;
+
+    // Write the BOM to the file
+    let file = File::create(Path::new(&file_path)).map_err(|e| e.to_string())?;
+    BomExportManager::new(&deviation_state.connection_manager).export_csv(revision_id, file)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,478 @@
+//! Tauri command handlers for inventory operations
+//!
+//! This module contains the command handlers for inventory-related operations in the Tauri application.
+//! These commands are exposed to the frontend and allow it to manage stock locations, stock levels
+//! and inventory transactions.
+
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+use tauri::{command, State};
+use serde::{Serialize, Deserialize};
+use crate::database::inventory::{
+    InventoryManager, InventoryTransaction, InventoryTransactionType, StockLocation, StockRecord, StockLevel,
+};
+use crate::database::clear_to_build::{ClearToBuildManager, ClearToBuildReport, ClearToBuildLine, Shortage};
+use crate::database::connection_manager::ConnectionManager;
+use crate::commands::deviation::DeviationNoticeDto;
+
+/// Stock location information for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StockLocationDto {
+    /// Location ID
+    pub location_id: i64,
+    /// Location name
+    pub name: String,
+    /// Location description
+    pub description: Option<String>,
+}
+
+/// Stock record information for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StockRecordDto {
+    /// Stock record ID
+    pub stock_id: i64,
+    /// Part ID
+    pub part_id: i64,
+    /// Location ID
+    pub location_id: i64,
+    /// Lot number
+    pub lot_number: Option<String>,
+    /// Quantity on hand
+    pub quantity_on_hand: i64,
+    /// Quantity reserved
+    pub quantity_reserved: i64,
+    /// Quantity available
+    pub quantity_available: i64,
+}
+
+/// Stock level information for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StockLevelDto {
+    /// Quantity on hand across all locations
+    pub quantity_on_hand: i64,
+    /// Quantity reserved across all locations
+    pub quantity_reserved: i64,
+    /// Quantity available across all locations
+    pub quantity_available: i64,
+}
+
+/// Inventory transaction information for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryTransactionDto {
+    /// Transaction ID
+    pub transaction_id: i64,
+    /// Part ID
+    pub part_id: i64,
+    /// Transaction type (Receive, Issue, Adjust, Transfer)
+    pub transaction_type: String,
+    /// Quantity; signed for adjustments
+    pub quantity: i64,
+    /// Source location ID
+    pub from_location_id: Option<i64>,
+    /// Destination location ID
+    pub to_location_id: Option<i64>,
+    /// Lot number
+    pub lot_number: Option<String>,
+    /// External reference
+    pub reference: Option<String>,
+    /// Notes
+    pub notes: Option<String>,
+    /// User who performed the transaction
+    pub performed_by: String,
+    /// Date of the transaction (seconds since UNIX epoch)
+    pub date: i64,
+}
+
+/// Inventory transaction data from the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryTransactionData {
+    /// Part ID
+    pub part_id: i64,
+    /// Transaction type (Receive, Issue, Adjust, Transfer)
+    pub transaction_type: String,
+    /// Quantity; signed for adjustments
+    pub quantity: i64,
+    /// Source location ID (Issue, Transfer)
+    pub from_location_id: Option<i64>,
+    /// Destination or counted location ID (Receive, Adjust, Transfer)
+    pub to_location_id: Option<i64>,
+    /// Lot number
+    pub lot_number: Option<String>,
+    /// External reference
+    pub reference: Option<String>,
+    /// Notes
+    pub notes: Option<String>,
+    /// User performing the transaction
+    pub performed_by: String,
+}
+
+/// Alternate part allocation for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlternateAllocationDto {
+    /// Alternate part ID
+    pub part_id: i64,
+    /// Quantity allocated from the alternate part
+    pub quantity: i64,
+}
+
+/// Clear-to-build line information for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClearToBuildLineDto {
+    /// Depth in the BOM, 1 for direct children
+    pub level: usize,
+    /// Parent part ID
+    pub parent_part_id: i64,
+    /// Child part ID
+    pub part_id: i64,
+    /// Quantity per parent
+    pub quantity_per: i64,
+    /// Attrition/scrap factor
+    pub attrition: f64,
+    /// Quantity needed including attrition
+    pub gross_quantity: i64,
+    /// Quantity allocated from stock
+    pub from_stock: i64,
+    /// Quantities allocated from alternate parts
+    pub alternates: Vec<AlternateAllocationDto>,
+    /// Quantity of a sub-assembly to build
+    pub to_build: i64,
+    /// Quantity of a purchased part not covered by stock
+    pub shortage: i64,
+}
+
+/// Shortage information for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortageDto {
+    /// Part ID
+    pub part_id: i64,
+    /// Quantity required across the build
+    pub required: i64,
+    /// Quantity available before the build
+    pub available: i64,
+    /// Quantity missing
+    pub shortage: i64,
+}
+
+/// Clear-to-build report for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClearToBuildReportDto {
+    /// Assembly revision ID
+    pub revision_id: i64,
+    /// Assembly part ID
+    pub part_id: i64,
+    /// Assembly revision version
+    pub version: String,
+    /// Number of assemblies to build
+    pub build_quantity: i64,
+    /// Whether the build is fully covered by stock
+    pub clear_to_build: bool,
+    /// BOM line allocations
+    pub lines: Vec<ClearToBuildLineDto>,
+    /// Shortages of purchased parts
+    pub shortages: Vec<ShortageDto>,
+    /// Maximum buildable quantity; null if not limited by stock
+    pub max_buildable: Option<i64>,
+    /// Approved deviations of the assembly and its BOM parts, with expired ones flagged
+    pub deviations: Vec<DeviationNoticeDto>,
+}
+
+/// Inventory state for the application
+pub struct InventoryState {
+    /// Connection manager for the database
+    pub connection_manager: ConnectionManager,
+    /// Inventory manager for inventory operations
+    pub inventory_manager: Mutex<InventoryManager<'static>>,
+}
+
+impl From<StockLocation> for StockLocationDto {
+    fn from(location: StockLocation) -> Self {
+        Self {
+            location_id: location.location_id.unwrap_or_default(),
+            name: location.name,
+            description: location.description,
+        }
+    }
+}
+
+impl From<StockRecord> for StockRecordDto {
+    fn from(record: StockRecord) -> Self {
+        Self {
+            quantity_available: record.quantity_available(),
+            stock_id: record.stock_id,
+            part_id: record.part_id,
+            location_id: record.location_id,
+            lot_number: record.lot_number,
+            quantity_on_hand: record.quantity_on_hand,
+            quantity_reserved: record.quantity_reserved,
+        }
+    }
+}
+
+impl From<StockLevel> for StockLevelDto {
+    fn from(level: StockLevel) -> Self {
+        Self {
+            quantity_available: level.quantity_available(),
+            quantity_on_hand: level.quantity_on_hand,
+            quantity_reserved: level.quantity_reserved,
+        }
+    }
+}
+
+impl From<InventoryTransaction> for InventoryTransactionDto {
+    fn from(transaction: InventoryTransaction) -> Self {
+        Self {
+            transaction_id: transaction.transaction_id.unwrap_or_default(),
+            part_id: transaction.part_id,
+            transaction_type: transaction.transaction_type.to_str().to_string(),
+            quantity: transaction.quantity,
+            from_location_id: transaction.from_location_id,
+            to_location_id: transaction.to_location_id,
+            lot_number: transaction.lot_number,
+            reference: transaction.reference,
+            notes: transaction.notes,
+            performed_by: transaction.performed_by,
+            date: transaction.date.duration_since(UNIX_EPOCH).map(|d| d.as_secs() as i64).unwrap_or_default(),
+        }
+    }
+}
+
+impl From<ClearToBuildLine> for ClearToBuildLineDto {
+    fn from(line: ClearToBuildLine) -> Self {
+        Self {
+            level: line.level,
+            parent_part_id: line.parent_part_id,
+            part_id: line.part_id,
+            quantity_per: line.quantity_per,
+            attrition: line.attrition,
+            gross_quantity: line.gross_quantity,
+            from_stock: line.from_stock,
+            alternates: line.alternates
+                .into_iter()
+                .map(|allocation| AlternateAllocationDto {
+                    part_id: allocation.part_id,
+                    quantity: allocation.quantity,
+                })
+                .collect(),
+            to_build: line.to_build,
+            shortage: line.shortage,
+        }
+    }
+}
+
+impl From<Shortage> for ShortageDto {
+    fn from(shortage: Shortage) -> Self {
+        Self {
+            part_id: shortage.part_id,
+            required: shortage.required,
+            available: shortage.available,
+            shortage: shortage.shortage,
+        }
+    }
+}
+
+impl From<ClearToBuildReport> for ClearToBuildReportDto {
+    fn from(report: ClearToBuildReport) -> Self {
+        Self {
+            revision_id: report.revision_id,
+            part_id: report.part_id,
+            version: report.version,
+            build_quantity: report.build_quantity,
+            clear_to_build: report.clear_to_build,
+            lines: report.lines.into_iter().map(ClearToBuildLineDto::from).collect(),
+            shortages: report.shortages.into_iter().map(ShortageDto::from).collect(),
+            max_buildable: report.max_buildable,
+            deviations: report.deviations.into_iter().map(DeviationNoticeDto::from).collect(),
+        }
+    }
+}
+
+/// Initialize the inventory state
+pub fn init_inventory_state(connection_manager: ConnectionManager) -> InventoryState {
+    // Create an inventory manager with 'static lifetime using a leak (safe in this context)
+    let static_connection_manager: &'static ConnectionManager = Box::leak(Box::new(connection_manager.clone()));
+    let inventory_manager = InventoryManager::new(static_connection_manager);
+
+    InventoryState {
+        connection_manager,
+        inventory_manager: Mutex::new(inventory_manager),
+    }
+}
+
+/// Get all stock locations
+#[command]
+pub async fn get_stock_locations(
+    inventory_state: State<'_, InventoryState>,
+) -> Result<Vec<StockLocationDto>, String> {
+    let inventory_manager = inventory_state.inventory_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get all locations
+    let locations = inventory_manager.get_all_locations()
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTOs
+    Ok(locations.into_iter().map(StockLocationDto::from).collect())
+}
+
+/// Create a new stock location
+#[command]
+pub async fn create_stock_location(
+    name: String,
+    description: Option<String>,
+    inventory_state: State<'_, InventoryState>,
+) -> Result<StockLocationDto, String> {
+    let inventory_manager = inventory_state.inventory_manager.lock().map_err(|e| e.to_string())?;
+
+    // Create the location
+    let location_id = inventory_manager.create_location(&StockLocation::new(name, description))
+        .map_err(|e| e.to_string())?;
+
+    // Get the created location
+    let location = inventory_manager.get_location(location_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(StockLocationDto::from(location))
+}
+
+/// Get the stock records of a part
+#[command]
+pub async fn get_part_stock(
+    part_id: i64,
+    inventory_state: State<'_, InventoryState>,
+) -> Result<Vec<StockRecordDto>, String> {
+    let inventory_manager = inventory_state.inventory_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get the stock records
+    let records = inventory_manager.get_stock_for_part(part_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTOs
+    Ok(records.into_iter().map(StockRecordDto::from).collect())
+}
+
+/// Get the stock totals of a part
+#[command]
+pub async fn get_part_stock_level(
+    part_id: i64,
+    inventory_state: State<'_, InventoryState>,
+) -> Result<StockLevelDto, String> {
+    let inventory_manager = inventory_state.inventory_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get the stock level
+    let level = inventory_manager.get_stock_level(part_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(StockLevelDto::from(level))
+}
+
+/// Get the stock records at a location
+#[command]
+pub async fn get_location_stock(
+    location_id: i64,
+    inventory_state: State<'_, InventoryState>,
+) -> Result<Vec<StockRecordDto>, String> {
+    let inventory_manager = inventory_state.inventory_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get the stock records
+    let records = inventory_manager.get_stock_at_location(location_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTOs
+    Ok(records.into_iter().map(StockRecordDto::from).collect())
+}
+
+/// Record an inventory transaction
+#[command]
+pub async fn record_inventory_transaction(
+    transaction_data: InventoryTransactionData,
+    inventory_state: State<'_, InventoryState>,
+) -> Result<i64, String> {
+    let inventory_manager = inventory_state.inventory_manager.lock().map_err(|e| e.to_string())?;
+
+    // Convert type string to InventoryTransactionType
+    let transaction_type = InventoryTransactionType::from_str(&transaction_data.transaction_type)
+        .ok_or_else(|| format!("Invalid inventory transaction type: {}", transaction_data.transaction_type))?;
+
+    // Create the transaction
+    let mut transaction = InventoryTransaction::new(
+        transaction_data.part_id,
+        transaction_type,
+        transaction_data.quantity,
+        transaction_data.from_location_id,
+        transaction_data.to_location_id,
+        transaction_data.performed_by,
+    );
+    transaction.lot_number = transaction_data.lot_number;
+    transaction.reference = transaction_data.reference;
+    transaction.notes = transaction_data.notes;
+
+    // Record the transaction
+    inventory_manager.record_transaction(&transaction)
+        .map_err(|e| e.to_string())
+}
+
+/// Get the inventory transaction history of a part
+#[command]
+pub async fn get_part_inventory_transactions(
+    part_id: i64,
+    inventory_state: State<'_, InventoryState>,
+) -> Result<Vec<InventoryTransactionDto>, String> {
+    let inventory_manager = inventory_state.inventory_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get the transactions
+    let transactions = inventory_manager.get_transactions_for_part(part_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTOs
+    Ok(transactions.into_iter().map(InventoryTransactionDto::from).collect())
+}
+
+/// Reserve stock at a location
+#[command]
+pub async fn reserve_stock(
+    part_id: i64,
+    location_id: i64,
+    quantity: i64,
+    lot_number: Option<String>,
+    inventory_state: State<'_, InventoryState>,
+) -> Result<(), String> {
+    let inventory_manager = inventory_state.inventory_manager.lock().map_err(|e| e.to_string())?;
+
+    // Reserve the stock
+    inventory_manager.reserve(part_id, location_id, quantity, lot_number.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// Release reserved stock at a location
+#[command]
+pub async fn release_stock_reservation(
+    part_id: i64,
+    location_id: i64,
+    quantity: i64,
+    lot_number: Option<String>,
+    inventory_state: State<'_, InventoryState>,
+) -> Result<(), String> {
+    let inventory_manager = inventory_state.inventory_manager.lock().map_err(|e| e.to_string())?;
+
+    // Release the reservation
+    inventory_manager.release_reservation(part_id, location_id, quantity, lot_number.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// Check whether an assembly revision can be built from available stock
+#[command]
+pub async fn get_clear_to_build(
+    revision_id: i64,
+    build_quantity: i64,
+    inventory_state: State<'_, InventoryState>,
+) -> Result<ClearToBuildReportDto, String> {
+    let clear_to_build_manager = ClearToBuildManager::new(&inventory_state.connection_manager);
+
+    // Run the analysis
+    let report = clear_to_build_manager.get_clear_to_build(revision_id, build_quantity)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(ClearToBuildReportDto::from(report))
+}
@@ -4,9 +4,11 @@
 //! These commands are exposed to the frontend and allow it to interact with the manufacturer part management system.
 
 use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tauri::{command, State};
 use serde::{Serialize, Deserialize};
 use crate::database::manufacturer_part::{ManufacturerPartManager, ManufacturerPart, ManufacturerPartStatus};
+use crate::database::obsolescence::{ObsolescenceManager, ObsolescenceReport, AtRiskPart, AffectedAssembly};
 use crate::database::connection_manager::ConnectionManager;
 
 /// Manufacturer part information for the frontend
@@ -24,6 +26,12 @@ pub struct ManufacturerPartDto {
     pub description: Option<String>,
     /// Status of the manufacturer part (Active, Preferred, Alternate, Obsolete)
     pub status: String,
+    /// NRND date in ISO 8601 format
+    pub nrnd_date: Option<String>,
+    /// Last-time-buy date in ISO 8601 format
+    pub last_time_buy_date: Option<String>,
+    /// End-of-life date in ISO 8601 format
+    pub eol_date: Option<String>,
 }
 
 /// Manufacturer part creation data from the frontend
@@ -39,6 +47,54 @@ pub struct ManufacturerPartCreationData {
     pub description: Option<String>,
     /// Status of the manufacturer part (Active, Preferred, Alternate, Obsolete)
     pub status: String,
+    /// NRND date (YYYY-MM-DD or RFC 3339)
+    #[serde(default)]
+    pub nrnd_date: Option<String>,
+    /// Last-time-buy date (YYYY-MM-DD or RFC 3339)
+    #[serde(default)]
+    pub last_time_buy_date: Option<String>,
+    /// End-of-life date (YYYY-MM-DD or RFC 3339)
+    #[serde(default)]
+    pub eol_date: Option<String>,
+}
+
+/// At-risk part information for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AtRiskPartDto {
+    /// ID of the internal part
+    pub part_id: i64,
+    /// Risk level (NRND, Last Time Buy, No Source)
+    pub risk: String,
+    /// Manufacturer parts registered for the internal part
+    pub manufacturer_parts: Vec<ManufacturerPartDto>,
+}
+
+/// Affected assembly information for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AffectedAssemblyDto {
+    /// ID of the assembly part
+    pub part_id: i64,
+    /// ID of the released revision
+    pub revision_id: i64,
+    /// Version of the released revision
+    pub version: String,
+    /// ID of the at-risk part used by the assembly
+    pub at_risk_part_id: i64,
+    /// Risk level of the at-risk part
+    pub risk: String,
+    /// Part IDs from the at-risk part up to the assembly
+    pub path: Vec<i64>,
+}
+
+/// Obsolescence report for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObsolescenceReportDto {
+    /// Evaluation date in ISO 8601 format
+    pub as_of: String,
+    /// Internal parts at risk
+    pub at_risk_parts: Vec<AtRiskPartDto>,
+    /// Released assemblies affected by the at-risk parts
+    pub affected_assemblies: Vec<AffectedAssemblyDto>,
 }
 
 /// Manufacturer part state for the application
@@ -58,10 +114,78 @@ impl From<ManufacturerPart> for ManufacturerPartDto {
             mpn: mpn.mpn,
             description: mpn.description,
             status: mpn.status.to_str().to_string(),
+            nrnd_date: mpn.nrnd_date.map(format_date),
+            last_time_buy_date: mpn.last_time_buy_date.map(format_date),
+            eol_date: mpn.eol_date.map(format_date),
+        }
+    }
+}
+
+impl From<AtRiskPart> for AtRiskPartDto {
+    fn from(part: AtRiskPart) -> Self {
+        Self {
+            part_id: part.part_id,
+            risk: part.risk.to_str().to_string(),
+            manufacturer_parts: part.manufacturer_parts.into_iter().map(ManufacturerPartDto::from).collect(),
+        }
+    }
+}
+
+impl From<AffectedAssembly> for AffectedAssemblyDto {
+    fn from(assembly: AffectedAssembly) -> Self {
+        Self {
+            part_id: assembly.part_id,
+            revision_id: assembly.revision_id,
+            version: assembly.version,
+            at_risk_part_id: assembly.at_risk_part_id,
+            risk: assembly.risk.to_str().to_string(),
+            path: assembly.path,
+        }
+    }
+}
+
+impl From<ObsolescenceReport> for ObsolescenceReportDto {
+    fn from(report: ObsolescenceReport) -> Self {
+        Self {
+            as_of: format_date(report.as_of),
+            at_risk_parts: report.at_risk_parts.into_iter().map(AtRiskPartDto::from).collect(),
+            affected_assemblies: report.affected_assemblies.into_iter().map(AffectedAssemblyDto::from).collect(),
         }
     }
 }
 
+/// Format a SystemTime as an ISO 8601 string
+fn format_date(date: SystemTime) -> String {
+    date.duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|d| chrono::DateTime::<chrono::Utc>::from_timestamp(d.as_secs() as i64, 0))
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_else(|| String::from("Invalid date"))
+}
+
+/// Parse an optional date from the frontend (YYYY-MM-DD or RFC 3339)
+fn parse_date(date: Option<&str>) -> Result<Option<SystemTime>, String> {
+    let date = match date {
+        Some(date) if !date.trim().is_empty() => date.trim(),
+        _ => return Ok(None),
+    };
+
+    let secs = if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(date) {
+        dt.timestamp()
+    } else {
+        chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|e| format!("Invalid date '{}': {}", date, e))?
+            .and_hms_opt(0, 0, 0)
+            .map(|dt| dt.and_utc().timestamp())
+            .ok_or_else(|| format!("Invalid date '{}'", date))?
+    };
+
+    if secs < 0 {
+        return Err(format!("Date before 1970 is not supported: {}", date));
+    }
+    Ok(Some(UNIX_EPOCH + Duration::from_secs(secs as u64)))
+}
+
 /// Initialize the manufacturer part state
 pub fn init_manufacturer_part_state(connection_manager: ConnectionManager) -> ManufacturerPartState {
     // Create a manufacturer part manager with 'static lifetime using a leak (safe in this context)
@@ -149,13 +273,16 @@ pub async fn create_manufacturer_part(
     };
     
     // Create a new manufacturer part
-    let mpn = ManufacturerPart::new(
+    let mut mpn = ManufacturerPart::new(
         manufacturer_part_data.part_id,
         manufacturer_part_data.manufacturer,
         manufacturer_part_data.mpn,
         manufacturer_part_data.description,
         status,
     );
+    mpn.nrnd_date = parse_date(manufacturer_part_data.nrnd_date.as_deref())?;
+    mpn.last_time_buy_date = parse_date(manufacturer_part_data.last_time_buy_date.as_deref())?;
+    mpn.eol_date = parse_date(manufacturer_part_data.eol_date.as_deref())?;
     
     // Save the manufacturer part
     let mpn_id = manufacturer_part_manager.create_manufacturer_part(&mpn)
@@ -196,6 +323,9 @@ pub async fn update_manufacturer_part(
         status,
     );
     mpn.mpn_id = Some(mpn_id);
+    mpn.nrnd_date = parse_date(manufacturer_part_data.nrnd_date.as_deref())?;
+    mpn.last_time_buy_date = parse_date(manufacturer_part_data.last_time_buy_date.as_deref())?;
+    mpn.eol_date = parse_date(manufacturer_part_data.eol_date.as_deref())?;
     
     // Update the manufacturer part
     manufacturer_part_manager.update_manufacturer_part(&mpn)
@@ -242,4 +372,23 @@ pub async fn search_manufacturer_parts(
         .collect();
     
     Ok(mpn_dtos)
-}
\ No newline at end of file
+}
+
+/// Get the obsolescence risk report for released assemblies
+#[command]
+pub async fn get_obsolescence_report(
+    as_of: Option<String>,
+    manufacturer_part_state: State<'_, ManufacturerPartState>,
+) -> Result<ObsolescenceReportDto, String> {
+    let obsolescence_manager = ObsolescenceManager::new(&manufacturer_part_state.connection_manager);
+
+    // Default to evaluating lifecycle dates as of now
+    let as_of = parse_date(as_of.as_deref())?.unwrap_or_else(SystemTime::now);
+
+    // Generate the report
+    let report = obsolescence_manager.get_obsolescence_report(as_of)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(ObsolescenceReportDto::from(report))
+}
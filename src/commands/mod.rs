@@ -14,6 +14,14 @@ pub mod property;
 pub mod file;
 pub mod relationship;
 pub mod revision;
+pub mod supplier;
+pub mod inventory;
+pub mod serialized_unit;
+pub mod user;
+pub mod change_order;
+pub mod deviation;
+pub mod change_impact;
+pub mod audit;
 
 // Re-export common types from command modules
 pub use repository::{
@@ -29,25 +37,34 @@ pub use repository::{
 pub use parts::{
     DatabaseState,
     PartDto,
+    SignatureStatusDto,
+    ReleaseVerificationDto,
     get_parts,
     get_part,
     create_part,
     update_part,
     change_part_status,
     delete_part,
+    execute_revision_transition,
+    get_part_working_path,
+    verify_release_signatures,
     init_database_state,
 };
 
 pub use workspace::{
     WorkspaceState,
     WorkspaceDto,
+    WorkspacePartDto,
     get_workspaces,
     get_workspace,
     create_workspace,
     update_workspace,
     delete_workspace,
     add_part_to_workspace,
+    set_workspace_part_revision,
     remove_part_from_workspace,
+    activate_workspace,
+    deactivate_workspace,
     init_workspace_state,
 };
 
@@ -76,12 +93,32 @@ pub use workflow::{
     update_workflow_transition,
     delete_workflow_transition,
     create_default_part_workflow,
+    assign_category_workflow,
+    get_revision_workflow_state,
+    get_available_revision_transitions,
+    RevisionWorkflowStateDto,
+    get_transition_guards,
+    create_transition_guard,
+    delete_transition_guard,
+    get_transition_actions,
+    create_transition_action,
+    delete_transition_action,
+    check_revision_transition,
+    get_revision_workflow_events,
+    load_workflow_files,
+    export_workflow_files,
+    TransitionGuardDto,
+    TransitionActionDto,
+    GuardFailureDto,
+    WorkflowEventDto,
     init_workflow_state,
 };
 
 pub use approval::{
     ApprovalState,
     ApprovalDto,
+    SignatureDto,
+    SignatureVerificationDto,
     get_approval,
     get_approvals_for_revision,
     get_approval_for_revision_and_approver,
@@ -90,6 +127,21 @@ pub use approval::{
     delete_approval,
     is_revision_approved,
     submit_for_approval,
+    get_approval_policies,
+    create_approval_policy,
+    delete_approval_policy,
+    get_approval_policy_groups,
+    set_approval_policy_group,
+    remove_approval_policy_group,
+    evaluate_revision_approval,
+    get_revision_signatures,
+    verify_revision_signatures,
+    get_groups,
+    create_group,
+    delete_group,
+    get_group_members,
+    add_group_member,
+    remove_group_member,
     approve_revision,
     reject_revision,
     init_approval_state,
@@ -98,6 +150,9 @@ pub use approval::{
 pub use manufacturer_part::{
     ManufacturerPartState,
     ManufacturerPartDto,
+    ObsolescenceReportDto,
+    AtRiskPartDto,
+    AffectedAssemblyDto,
     get_manufacturer_part,
     get_manufacturer_parts_for_part,
     get_manufacturer_parts_by_mpn,
@@ -105,6 +160,7 @@ pub use manufacturer_part::{
     update_manufacturer_part,
     delete_manufacturer_part,
     search_manufacturer_parts,
+    get_obsolescence_report,
     init_manufacturer_part_state,
 };
 
@@ -151,6 +207,7 @@ pub use relationship::{
 pub use revision::{
     RevisionState,
     RevisionDto,
+    EffectivityDto,
     get_revision,
     get_part_revisions,
     get_latest_revision,
@@ -159,5 +216,128 @@ pub use revision::{
     update_revision,
     update_revision_status,
     delete_revision,
+    get_revision_effectivity,
+    set_revision_effectivity,
+    get_effective_revision,
     init_revision_state,
-};
\ No newline at end of file
+};
+
+pub use supplier::{
+    SupplierState,
+    SupplierDto,
+    SupplierOfferDto,
+    OfferImportResultDto,
+    CostRollupDto,
+    get_suppliers,
+    get_supplier_offers,
+    create_supplier_offer,
+    delete_supplier_offer,
+    import_supplier_offers_csv,
+    get_bom_cost_rollup,
+    init_supplier_state,
+};
+
+pub use inventory::{
+    InventoryState,
+    StockLocationDto,
+    StockRecordDto,
+    StockLevelDto,
+    InventoryTransactionDto,
+    ClearToBuildReportDto,
+    get_stock_locations,
+    create_stock_location,
+    get_part_stock,
+    get_part_stock_level,
+    get_location_stock,
+    record_inventory_transaction,
+    get_part_inventory_transactions,
+    reserve_stock,
+    release_stock_reservation,
+    get_clear_to_build,
+    init_inventory_state,
+};
+
+pub use serialized_unit::{
+    SerializedUnitState,
+    SerializedUnitDto,
+    AsBuiltComponentDto,
+    create_serialized_unit,
+    get_serialized_unit,
+    get_unit_as_built,
+    add_as_built_component,
+    get_revision_units,
+    get_units_containing_lot,
+    init_serialized_unit_state,
+};
+
+pub use user::{
+    UserState,
+    UserDto,
+    PermissionDto,
+    get_users,
+    create_user,
+    update_user,
+    delete_user,
+    get_current_user,
+    use_git_identity,
+    get_permissions,
+    grant_permission,
+    revoke_permission,
+    init_user_state,
+};
+
+pub use change_order::{
+    ChangeOrderState,
+    ChangeOrderDto,
+    get_change_orders,
+    get_change_order,
+    create_change_order,
+    update_change_order,
+    get_change_order_revisions,
+    add_revision_to_change_order,
+    remove_revision_from_change_order,
+    evaluate_change_order,
+    submit_change_order,
+    approve_change_order,
+    reject_change_order,
+    release_change_order,
+    cancel_change_order,
+    init_change_order_state,
+};
+
+pub use deviation::{
+    DeviationState,
+    DeviationDto,
+    DeviationNoticeDto,
+    get_deviations,
+    create_deviation,
+    get_deviation_items,
+    add_deviation_item,
+    remove_deviation_item,
+    decide_deviation,
+    close_deviation,
+    record_deviation_usage,
+    export_bom_csv,
+    init_deviation_state,
+};
+
+pub use change_impact::{
+    ChangeImpactState,
+    ChangeFlagsDto,
+    ChangeImpactReportDto,
+    get_change_flags,
+    set_change_flags,
+    analyze_change_impact,
+    get_review_impact_report,
+    init_change_impact_state,
+};
+
+pub use audit::{
+    AuditState,
+    AuditEntryDto,
+    AuditFilterDto,
+    AuditVerificationDto,
+    get_audit_log,
+    verify_audit_log,
+    init_audit_state,
+};
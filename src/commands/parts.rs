@@ -6,7 +6,8 @@
 use std::sync::Mutex;
 use tauri::{command, State};
 use serde::{Serialize, Deserialize};
-use crate::database::part_management::PartManagementManager;
+use crate::database::part_management::{PartManagementManager, ReleaseVerification};
+use crate::git_backend::signing::SignatureStatus;
 use crate::database::part::Part;
 use crate::database::connection_manager::ConnectionManager;
 
@@ -42,6 +43,34 @@ pub struct PartCreateDto {
     pub subcategory: String,
 }
 
+/// Signature status of a commit or tag for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureStatusDto {
+    /// Status (Unsigned, Valid, Untrusted or Invalid)
+    pub status: String,
+    /// Signature format (ssh or gpg), if recognized
+    pub format: Option<String>,
+    /// Identity of the signer, if it could be established
+    pub signer: Option<String>,
+    /// Reason the signature is not valid
+    pub reason: Option<String>,
+}
+
+/// Signatures of a released revision for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseVerificationDto {
+    /// Hash of the released commit
+    pub commit_hash: String,
+    /// Signature of the released commit
+    pub commit: SignatureStatusDto,
+    /// Name of the release tag
+    pub tag_name: String,
+    /// Signature of the release tag, if the tag exists
+    pub tag: Option<SignatureStatusDto>,
+    /// Whether the commit or the tag carries a valid signature
+    pub signed: bool,
+}
+
 /// Database state for part operations
 pub struct DatabaseState {
     /// Connection manager
@@ -71,6 +100,49 @@ impl From<Part> for PartDto {
     }
 }
 
+impl From<SignatureStatus> for SignatureStatusDto {
+    fn from(status: SignatureStatus) -> Self {
+        match status {
+            SignatureStatus::Unsigned => Self {
+                status: "Unsigned".to_string(),
+                format: None,
+                signer: None,
+                reason: None,
+            },
+            SignatureStatus::Valid { format, signer } => Self {
+                status: "Valid".to_string(),
+                format: Some(format.to_str().to_string()),
+                signer,
+                reason: None,
+            },
+            SignatureStatus::Untrusted { format, signer, reason } => Self {
+                status: "Untrusted".to_string(),
+                format: Some(format.to_str().to_string()),
+                signer,
+                reason: Some(reason),
+            },
+            SignatureStatus::Invalid { format, reason } => Self {
+                status: "Invalid".to_string(),
+                format: format.map(|format| format.to_str().to_string()),
+                signer: None,
+                reason: Some(reason),
+            },
+        }
+    }
+}
+
+impl From<ReleaseVerification> for ReleaseVerificationDto {
+    fn from(verification: ReleaseVerification) -> Self {
+        Self {
+            signed: verification.is_signed(),
+            commit_hash: verification.commit_hash,
+            commit: SignatureStatusDto::from(verification.commit),
+            tag_name: verification.tag_name,
+            tag: verification.tag.map(SignatureStatusDto::from),
+        }
+    }
+}
+
 /// Get all parts
 #[command]
 pub async fn get_parts(
@@ -147,10 +219,11 @@ pub async fn update_part(
     part_data: PartCreateDto,
     db_state: State<'_, DatabaseState>,
 ) -> Result<PartDto, String> {
-    let _part_manager = db_state.part_manager.lock().map_err(|e| e.to_string())?;
+    let part_manager = db_state.part_manager.lock().map_err(|e| e.to_string())?;
     
     // We need to use the PartManager directly for update operations
-    let part_db_manager = crate::database::part::PartManager::new(&db_state.connection_manager);
+    let part_db_manager = crate::database::part::PartManager::new(&db_state.connection_manager)
+        .with_actor(&part_manager.current_user().username);
     
     // Get the current part
     let part = part_db_manager.get_part(part_id)
@@ -198,16 +271,68 @@ pub async fn change_part_status(
     Ok(())
 }
 
+/// Execute a workflow transition on a revision
+#[command]
+pub async fn execute_revision_transition(
+    revision_id: i64,
+    transition_id: i64,
+    db_state: State<'_, DatabaseState>,
+) -> Result<String, String> {
+    let part_manager = db_state.part_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Transition actions run against the repository in the current directory
+    let repo_path = std::env::current_dir().map_err(|e| e.to_string())?;
+    
+    // Execute the transition and return the name of the new state
+    let state = part_manager.execute_transition(revision_id, transition_id, &repo_path)
+        .map_err(|e| e.to_string())?;
+    
+    Ok(state.name)
+}
+
+/// Get the directory a revision's files are worked on in
+#[command]
+pub async fn get_part_working_path(
+    revision_id: i64,
+    db_state: State<'_, DatabaseState>,
+) -> Result<String, String> {
+    let part_manager = db_state.part_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Drafts and reviews are resolved to the worktree of their branch in the current repository
+    let repo_path = std::env::current_dir().map_err(|e| e.to_string())?;
+    let working_path = part_manager.get_working_path(revision_id, &repo_path)
+        .map_err(|e| e.to_string())?;
+    
+    Ok(working_path.to_string_lossy().into_owned())
+}
+
+/// Verify the signatures of the commit and tag a revision was released with
+#[command]
+pub async fn verify_release_signatures(
+    revision_id: i64,
+    db_state: State<'_, DatabaseState>,
+) -> Result<ReleaseVerificationDto, String> {
+    let part_manager = db_state.part_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Releases are verified against the repository in the current directory
+    let repo_path = std::env::current_dir().map_err(|e| e.to_string())?;
+    let verification = part_manager.verify_release(revision_id, &repo_path)
+        .map_err(|e| e.to_string())?;
+    
+    Ok(ReleaseVerificationDto::from(verification))
+}
+
 /// Delete a part
 #[command]
 pub async fn delete_part(
     part_id: i64,
     db_state: State<'_, DatabaseState>,
 ) -> Result<(), String> {
-    let _part_manager = db_state.part_manager.lock().map_err(|e| e.to_string())?;
+    let part_manager = db_state.part_manager.lock().map_err(|e| e.to_string())?;
     
     // We need to use the PartManager directly for delete operations
-    let part_db_manager = crate::database::part::PartManager::new(&db_state.connection_manager);
+    let part_db_manager = crate::database::part::PartManager::new(&db_state.connection_manager)
+        .with_actor(&part_manager.current_user().username);
     
     // Delete the part
     part_db_manager.delete_part(part_id)
@@ -233,19 +358,32 @@ pub fn init_database_state() -> DatabaseState {
     let git_manager = GitBackendManager::new(git_config, auth_config)
         .expect("Failed to create git backend manager");
     
-    // Create a default user for system operations
+    // The current user is the stored user of the git identity of this machine, or a system user
+    // without one; new identities are designers until an administrator grants them more
     use crate::database::part_management::{User, UserRole};
-    let system_user = User::new("system".to_string(), UserRole::Admin);
+    use crate::database::user::UserManager;
+    let system_user = match git2::Config::open_default()
+        .ok()
+        .and_then(|config| User::from_git_config(&config, UserRole::Designer))
+    {
+        Some(identity) => UserManager::new(&connection_manager)
+            .resolve_git_identity(&identity.username, identity.email.as_deref())
+            .unwrap_or(identity),
+        None => User::new("system".to_string(), UserRole::Designer),
+    };
     
     // Create the part manager with 'static lifetime
     let static_connection_manager: &'static ConnectionManager = Box::leak(Box::new(connection_manager.clone()));
     let static_git_manager: &'static GitBackendManager = Box::leak(Box::new(git_manager));
-    let part_manager = PartManagementManager::new(
+    let mut part_manager = PartManagementManager::new(
         static_connection_manager,
         static_git_manager,
         system_user
     );
     
+    // Sign approvals and releases with the keys kept in the home directory of the local user
+    part_manager.set_key_store(crate::database::signature::SigningKeyStore::in_home_directory());
+    
     DatabaseState {
         connection_manager,
         part_manager: Mutex::new(part_manager),
@@ -8,6 +8,7 @@ use tauri::{command, State};
 use serde::{Serialize, Deserialize};
 use crate::database::property::{PropertyManager, Property, PropertyType};
 use crate::database::connection_manager::ConnectionManager;
+use crate::commands::parts::DatabaseState;
 
 /// Property information for the frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -169,8 +170,11 @@ pub async fn get_revision_property(
 pub async fn create_property(
     property_data: PropertyCreationData,
     property_state: State<'_, PropertyState>,
+    db_state: State<'_, DatabaseState>,
 ) -> Result<PropertyDto, String> {
-    let property_manager = property_state.property_manager.lock().map_err(|e| e.to_string())?;
+    let actor = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+    let property_manager = PropertyManager::new(&property_state.connection_manager).with_actor(&actor);
     
     // Convert property type string to PropertyType
     let property_type = PropertyType::from_str(&property_data.property_type)
@@ -213,8 +217,11 @@ pub async fn update_property(
     property_id: i64,
     property_data: PropertyCreationData,
     property_state: State<'_, PropertyState>,
+    db_state: State<'_, DatabaseState>,
 ) -> Result<PropertyDto, String> {
-    let property_manager = property_state.property_manager.lock().map_err(|e| e.to_string())?;
+    let actor = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+    let property_manager = PropertyManager::new(&property_state.connection_manager).with_actor(&actor);
     
     // Get the existing property
     let mut property = property_manager.get_property(property_id)
@@ -248,8 +255,11 @@ pub async fn update_property(
 pub async fn delete_property(
     property_id: i64,
     property_state: State<'_, PropertyState>,
+    db_state: State<'_, DatabaseState>,
 ) -> Result<(), String> {
-    let property_manager = property_state.property_manager.lock().map_err(|e| e.to_string())?;
+    let actor = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+    let property_manager = PropertyManager::new(&property_state.connection_manager).with_actor(&actor);
     
     // Delete the property
     property_manager.delete_property(property_id)
@@ -8,6 +8,7 @@ use tauri::{command, State};
 use serde::{Serialize, Deserialize};
 use crate::database::relationship::{RelationshipManager, Relationship, RelationshipType};
 use crate::database::connection_manager::ConnectionManager;
+use crate::commands::parts::DatabaseState;
 
 /// Relationship information for the frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +23,8 @@ pub struct RelationshipDto {
     pub relationship_type: String,
     /// Quantity of child parts in the relationship
     pub quantity: i64,
+    /// Attrition/scrap factor as a fraction (0.02 = 2% extra)
+    pub attrition: f64,
     /// Units for the quantity
     pub unit: Option<String>,
     /// Description of the relationship
@@ -39,6 +42,9 @@ pub struct RelationshipCreationData {
     pub relationship_type: String,
     /// Quantity of child parts in the relationship
     pub quantity: i64,
+    /// Attrition/scrap factor as a fraction (0.02 = 2% extra)
+    #[serde(default)]
+    pub attrition: f64,
     /// Units for the quantity
     pub unit: Option<String>,
     /// Description of the relationship
@@ -61,6 +67,7 @@ impl From<Relationship> for RelationshipDto {
             child_id: relationship.child_part_id,
             relationship_type: relationship.relationship_type.to_str(),
             quantity: relationship.quantity,
+            attrition: relationship.attrition,
             unit: None, // Not in Relationship struct but needed for DTO
             description: None, // Not in Relationship struct but needed for DTO
         }
@@ -140,8 +147,11 @@ pub async fn get_child_relationships(
 pub async fn create_relationship(
     relationship_data: RelationshipCreationData,
     relationship_state: State<'_, RelationshipState>,
+    db_state: State<'_, DatabaseState>,
 ) -> Result<RelationshipDto, String> {
-    let relationship_manager = relationship_state.relationship_manager.lock().map_err(|e| e.to_string())?;
+    let actor = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+    let relationship_manager = RelationshipManager::new(&relationship_state.connection_manager).with_actor(&actor);
     
     // Convert relationship type string to RelationshipType
     let relationship_type = match relationship_data.relationship_type.as_str() {
@@ -152,12 +162,13 @@ pub async fn create_relationship(
     };
     
     // Create a new relationship
-    let relationship = Relationship::new(
+    let mut relationship = Relationship::new(
         relationship_data.parent_id,
         relationship_data.child_id,
         relationship_type,
         relationship_data.quantity
     );
+    relationship.attrition = relationship_data.attrition;
     
     // Save the relationship
     let relationship_id = relationship_manager.create_relationship(&relationship)
@@ -177,8 +188,11 @@ pub async fn update_relationship(
     relationship_id: i64,
     relationship_data: RelationshipCreationData,
     relationship_state: State<'_, RelationshipState>,
+    db_state: State<'_, DatabaseState>,
 ) -> Result<RelationshipDto, String> {
-    let relationship_manager = relationship_state.relationship_manager.lock().map_err(|e| e.to_string())?;
+    let actor = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+    let relationship_manager = RelationshipManager::new(&relationship_state.connection_manager).with_actor(&actor);
     
     // Convert relationship type string to RelationshipType
     let relationship_type = match relationship_data.relationship_type.as_str() {
@@ -196,6 +210,7 @@ pub async fn update_relationship(
         relationship_data.quantity
     );
     relationship.relationship_id = Some(relationship_id);
+    relationship.attrition = relationship_data.attrition;
     
     // Update the relationship
     relationship_manager.update_relationship(&relationship)
@@ -214,8 +229,11 @@ pub async fn update_relationship(
 pub async fn delete_relationship(
     relationship_id: i64,
     relationship_state: State<'_, RelationshipState>,
+    db_state: State<'_, DatabaseState>,
 ) -> Result<(), String> {
-    let relationship_manager = relationship_state.relationship_manager.lock().map_err(|e| e.to_string())?;
+    let actor = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+    let relationship_manager = RelationshipManager::new(&relationship_state.connection_manager).with_actor(&actor);
     
     // Delete the relationship
     relationship_manager.delete_relationship(relationship_id)
@@ -54,6 +54,9 @@ pub struct GitBackendState {
 
 use crate::commands::parts::DatabaseState;
 use crate::database::connection_manager::ConnectionManager;
+use crate::database::schema::DatabaseManager;
+use crate::database::operation_journal::OperationJournalManager;
+use crate::database::workflow_definition::WorkflowDefinitionManager;
 
 /// Create a new repository
 #[command]
@@ -109,7 +112,7 @@ pub async fn create_repository(
 pub async fn open_repository(
     path: String,
     git_state: State<'_, GitBackendState>,
-    _db_state: State<'_, DatabaseState>,
+    db_state: State<'_, DatabaseState>,
 ) -> Result<RepositoryDto, String> {
     let manager = git_state.manager.lock().map_err(|e| e.to_string())?;
     
@@ -128,9 +131,6 @@ pub async fn open_repository(
     
     if db_path.exists() {
         println!("Using existing repository database at: {}", db_path.display());
-        // TODO: We should update the DatabaseState to use this existing connection
-        let _connection_manager = ConnectionManager::new(&db_path)
-            .map_err(|e| format!("Failed to open repository database: {}", e))?;
     } else {
         // Create config directory if it doesn't exist
         if !config_dir.exists() {
@@ -138,12 +138,40 @@ pub async fn open_repository(
                 .map_err(|e| format!("Failed to create config directory: {}", e))?;
         }
         
-        // Create a new repository database
         println!("Creating repository database at: {}", db_path.display());
-        let _connection_manager = ConnectionManager::new(&db_path)
-            .map_err(|e| format!("Failed to create repository database: {}", e))?;
     }
     
+    // Point the shared connection at the repository database, so that part operations are
+    // journaled on the same connection recovery checks
+    db_state.connection_manager.reopen(&db_path)
+        .map_err(|e| format!("Failed to open repository database: {}", e))?;
+    let db_manager = DatabaseManager::from_connection_manager(db_state.connection_manager.clone());
+    db_manager.initialize_schema()
+        .map_err(|e| format!("Failed to initialize repository database: {}", e))?;
+    
+    // Resolve operations interrupted by a crash before anything else touches the repository
+    let recovered = OperationJournalManager::new(db_manager.connection_manager(), &manager)
+        .recover(Path::new(&path))
+        .map_err(|e| format!("Failed to recover interrupted operations: {}", e))?;
+    for operation in recovered {
+        println!(
+            "Recovered interrupted operation {} ({})",
+            operation.entry.operation,
+            if operation.committed {
+                "committed"
+            } else if operation.rolled_back {
+                "rolled back"
+            } else {
+                "no repository changes to roll back"
+            },
+        );
+    }
+    
+    // Load the workflow definitions kept in the repository, rejecting invalid ones
+    WorkflowDefinitionManager::new(db_manager.connection_manager())
+        .load_directory(&config_dir.join("workflows"))
+        .map_err(|e| format!("Failed to load workflow definitions: {}", e))?;
+    
     Ok(RepositoryDto::from(info))
 }
 
@@ -4,11 +4,15 @@
 //! These commands are exposed to the frontend and allow it to interact with the revision management system.
 
 use std::sync::Mutex;
+use std::time::{Duration, UNIX_EPOCH};
 use tauri::{command, State};
 use serde::{Serialize, Deserialize};
 use crate::database::revision::{RevisionManager, Revision, RevisionStatus};
+use crate::database::effectivity::{Effectivity, EffectivityManager};
+use crate::database::audit::{AuditAction, AuditManager};
 use crate::database::connection_manager::ConnectionManager;
 use crate::database::schema::DatabaseError;
+use crate::commands::parts::DatabaseState;
 use rusqlite::params;
 
 /// Revision information for the frontend
@@ -43,6 +47,21 @@ pub struct RevisionCreationData {
     pub commit_hash: Option<String>,
 }
 
+/// Revision effectivity data for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EffectivityDto {
+    /// Revision ID
+    pub revision_id: i64,
+    /// Build date the revision takes effect at (seconds since UNIX epoch)
+    pub from_date: Option<i64>,
+    /// Serial number the revision takes effect at
+    pub from_serial: Option<String>,
+    /// Build date the revision is superseded at (seconds since UNIX epoch)
+    pub superseded_date: Option<i64>,
+    /// Serial number the revision is superseded at
+    pub superseded_serial: Option<String>,
+}
+
 /// Revision state for the application
 pub struct RevisionState {
     /// Connection manager for the database
@@ -64,6 +83,32 @@ impl From<Revision> for RevisionDto {
     }
 }
 
+impl From<Effectivity> for EffectivityDto {
+    fn from(effectivity: Effectivity) -> Self {
+        let to_secs = |date: std::time::SystemTime| date.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64;
+        Self {
+            revision_id: effectivity.revision_id,
+            from_date: effectivity.from_date.map(to_secs),
+            from_serial: effectivity.from_serial,
+            superseded_date: effectivity.superseded_date.map(to_secs),
+            superseded_serial: effectivity.superseded_serial,
+        }
+    }
+}
+
+impl From<EffectivityDto> for Effectivity {
+    fn from(effectivity: EffectivityDto) -> Self {
+        let from_secs = |secs: i64| UNIX_EPOCH + Duration::from_secs(secs.max(0) as u64);
+        Self {
+            revision_id: effectivity.revision_id,
+            from_date: effectivity.from_date.map(from_secs),
+            from_serial: effectivity.from_serial,
+            superseded_date: effectivity.superseded_date.map(from_secs),
+            superseded_serial: effectivity.superseded_serial,
+        }
+    }
+}
+
 /// Initialize the revision state
 pub fn init_revision_state(connection_manager: ConnectionManager) -> RevisionState {
     // Create a revision manager with 'static lifetime using a leak (safe in this context)
@@ -134,45 +179,14 @@ pub async fn get_latest_released_revision(
     part_id: i64,
     revision_state: State<'_, RevisionState>,
 ) -> Result<RevisionDto, String> {
-    // Get the latest released revision for the part by using a custom query
-    // since there's no direct method for this in the RevisionManager
-    let revision = revision_state.connection_manager.execute::<_, _, DatabaseError>(|conn| {
-        let result = conn.query_row(
-            "SELECT revision_id, part_id, version, status, created_date, created_by, commit_hash
-             FROM Revisions
-             WHERE part_id = ?1 AND status = 'Released'
-             ORDER BY created_date DESC
-             LIMIT 1",
-            params![part_id],
-            |row| {
-                let status_str: String = row.get(3)?;
-                let status = RevisionStatus::from_str(&status_str)
-                    .unwrap_or(RevisionStatus::Draft);
-                
-                // Convert SQLite timestamp to SystemTime
-                let created_secs: i64 = row.get(4)?;
-                let created_date = std::time::SystemTime::UNIX_EPOCH +
-                    std::time::Duration::from_secs(created_secs as u64);
-
-                Ok(Revision {
-                    revision_id: Some(row.get(0)?),
-                    part_id: row.get(1)?,
-                    version: row.get(2)?,
-                    status,
-                    created_date,
-                    created_by: row.get(5)?,
-                    commit_hash: row.get(6)?,
-                })
-            },
-        );
-        
-        // Converting the result to the expected type
-        match result {
-            Ok(revision) => Ok(revision),
-            Err(e) => Err(DatabaseError::from(e)),
-        }
-    })
-    .map_err(|e| e.to_string())?;
+    let revision_manager = revision_state.revision_manager.lock().map_err(|e| e.to_string())?;
+    
+    // The revisions are ordered by the revision scheme of the part, newest first
+    let revision = revision_manager.get_revisions_for_part(part_id)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|revision| revision.status == RevisionStatus::Released)
+        .ok_or_else(|| format!("Part {} has no released revision", part_id))?;
     
     // Convert to DTO
     Ok(RevisionDto::from(revision))
@@ -183,8 +197,11 @@ pub async fn get_latest_released_revision(
 pub async fn create_revision(
     revision_data: RevisionCreationData,
     revision_state: State<'_, RevisionState>,
+    db_state: State<'_, DatabaseState>,
 ) -> Result<RevisionDto, String> {
-    let revision_manager = revision_state.revision_manager.lock().map_err(|e| e.to_string())?;
+    let actor = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+    let revision_manager = RevisionManager::new(&revision_state.connection_manager).with_actor(&actor);
     
     // Convert status string to RevisionStatus
     let status = match revision_data.status.as_str() {
@@ -222,8 +239,11 @@ pub async fn update_revision(
     revision_id: i64,
     revision_data: RevisionCreationData,
     revision_state: State<'_, RevisionState>,
+    db_state: State<'_, DatabaseState>,
 ) -> Result<RevisionDto, String> {
-    let revision_manager = revision_state.revision_manager.lock().map_err(|e| e.to_string())?;
+    let actor = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+    let revision_manager = RevisionManager::new(&revision_state.connection_manager).with_actor(&actor);
     
     // Convert status string to RevisionStatus
     let status = match revision_data.status.as_str() {
@@ -270,8 +290,11 @@ pub async fn update_revision_status(
     revision_id: i64,
     status: String,
     revision_state: State<'_, RevisionState>,
+    db_state: State<'_, DatabaseState>,
 ) -> Result<RevisionDto, String> {
-    let revision_manager = revision_state.revision_manager.lock().map_err(|e| e.to_string())?;
+    let actor = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+    let revision_manager = RevisionManager::new(&revision_state.connection_manager).with_actor(&actor);
     
     // Convert status string to RevisionStatus
     let status_enum = match status.as_str() {
@@ -299,16 +322,92 @@ pub async fn update_revision_status(
 pub async fn delete_revision(
     revision_id: i64,
     revision_state: State<'_, RevisionState>,
+    db_state: State<'_, DatabaseState>,
 ) -> Result<(), String> {
+    let actor = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+    
     // There's no direct delete_revision method, so we need to implement a custom solution
     // Using a transaction to execute a DELETE statement
     revision_state.connection_manager.execute_mut::<_, _, DatabaseError>(|conn| {
+        conn.execute(
+            "DELETE FROM ChangeOrderRevisions WHERE revision_id = ?1",
+            params![revision_id],
+        )?;
+        conn.execute(
+            "DELETE FROM RevisionChangeFlags WHERE revision_id = ?1",
+            params![revision_id],
+        )?;
+        conn.execute(
+            "DELETE FROM ChangeImpactReports WHERE revision_id = ?1",
+            params![revision_id],
+        )?;
+        conn.execute(
+            "DELETE FROM RevisionEffectivity WHERE revision_id = ?1",
+            params![revision_id],
+        )?;
+        conn.execute(
+            "DELETE FROM Signatures WHERE revision_id = ?1",
+            params![revision_id],
+        )?;
+        conn.execute(
+            "UPDATE WorkspaceParts SET revision_id = NULL WHERE revision_id = ?1",
+            params![revision_id],
+        )?;
         conn.execute(
             "DELETE FROM Revisions WHERE revision_id = ?1",
             params![revision_id],
         )?;
+        AuditManager::new(&revision_state.connection_manager)
+            .record(conn, &actor, "Revision", revision_id, AuditAction::Delete, None)?;
         Ok::<(), DatabaseError>(())
     }).map_err(|e| e.to_string())?;
     
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Get the effectivity of a revision
+#[command]
+pub async fn get_revision_effectivity(
+    revision_id: i64,
+    revision_state: State<'_, RevisionState>,
+) -> Result<Option<EffectivityDto>, String> {
+    // Get the effectivity
+    let effectivity = EffectivityManager::new(&revision_state.connection_manager).get_effectivity(revision_id)
+        .map_err(|e| e.to_string())?;
+    
+    // Convert to DTO
+    Ok(effectivity.map(EffectivityDto::from))
+}
+
+/// Set the effectivity of a released revision
+#[command]
+pub async fn set_revision_effectivity(
+    effectivity: EffectivityDto,
+    revision_state: State<'_, RevisionState>,
+) -> Result<(), String> {
+    // Store the effectivity; open ranges of earlier revisions are superseded
+    EffectivityManager::new(&revision_state.connection_manager).set_effectivity(&Effectivity::from(effectivity))
+        .map_err(|e| e.to_string())
+}
+
+/// Get the revision of a part that applies to a build date or a serial number
+#[command]
+pub async fn get_effective_revision(
+    part_id: i64,
+    build_date: Option<i64>,
+    serial_number: Option<String>,
+    revision_state: State<'_, RevisionState>,
+) -> Result<Option<RevisionDto>, String> {
+    let effectivity_manager = EffectivityManager::new(&revision_state.connection_manager);
+    
+    // Serial numbers take precedence over build dates
+    let revision = match (serial_number, build_date) {
+        (Some(serial_number), _) => effectivity_manager.get_revision_for_serial(part_id, &serial_number),
+        (None, Some(build_date)) => effectivity_manager.get_revision_for_date(part_id, UNIX_EPOCH + Duration::from_secs(build_date.max(0) as u64)),
+        (None, None) => return Err("A build date or a serial number is required".to_string()),
+    }.map_err(|e| e.to_string())?;
+    
+    // Convert to DTO
+    Ok(revision.map(RevisionDto::from))
+}
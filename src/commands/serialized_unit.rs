@@ -0,0 +1,264 @@
+//! Tauri command handlers for serialized unit operations
+//!
+//! This module contains the command handlers for serialized unit operations in the Tauri application.
+//! These commands are exposed to the frontend and allow it to record built units with their as-built
+//! configuration and to trace lots and sub-assemblies through built units.
+
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+use tauri::{command, State};
+use serde::{Serialize, Deserialize};
+use crate::database::serialized_unit::{SerializedUnitManager, SerializedUnit, AsBuiltComponent};
+use crate::database::connection_manager::ConnectionManager;
+
+/// Serialized unit information for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SerializedUnitDto {
+    /// Unit ID
+    pub unit_id: i64,
+    /// Serial number
+    pub serial_number: String,
+    /// Assembly part ID
+    pub part_id: i64,
+    /// Assembly revision ID the unit was built to
+    pub revision_id: i64,
+    /// Build date (seconds since UNIX epoch)
+    pub build_date: i64,
+    /// User who built the unit
+    pub built_by: String,
+    /// Notes
+    pub notes: Option<String>,
+}
+
+/// As-built component information for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AsBuiltComponentDto {
+    /// Component record ID
+    pub component_id: i64,
+    /// Position, e.g. reference designator
+    pub position: String,
+    /// Installed part ID
+    pub part_id: i64,
+    /// Installed quantity
+    pub quantity: i64,
+    /// Installed manufacturer part ID
+    pub mpn_id: Option<i64>,
+    /// Lot number
+    pub lot_number: Option<String>,
+    /// Installed serialized sub-assembly unit ID
+    pub installed_unit_id: Option<i64>,
+}
+
+/// As-built component data from the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AsBuiltComponentData {
+    /// Position, e.g. reference designator
+    pub position: String,
+    /// Installed part ID
+    pub part_id: i64,
+    /// Installed quantity
+    pub quantity: i64,
+    /// Installed manufacturer part ID
+    pub mpn_id: Option<i64>,
+    /// Lot number
+    pub lot_number: Option<String>,
+    /// Serial number of an installed sub-assembly
+    pub installed_serial_number: Option<String>,
+}
+
+/// Serialized unit creation data from the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SerializedUnitCreationData {
+    /// Serial number
+    pub serial_number: String,
+    /// Assembly part ID
+    pub part_id: i64,
+    /// Assembly revision ID the unit was built to
+    pub revision_id: i64,
+    /// User who built the unit
+    pub built_by: String,
+    /// Notes
+    pub notes: Option<String>,
+    /// Installed components
+    #[serde(default)]
+    pub components: Vec<AsBuiltComponentData>,
+}
+
+/// Serialized unit state for the application
+pub struct SerializedUnitState {
+    /// Connection manager for the database
+    pub connection_manager: ConnectionManager,
+    /// Serialized unit manager for serialized unit operations
+    pub serialized_unit_manager: Mutex<SerializedUnitManager<'static>>,
+}
+
+impl From<SerializedUnit> for SerializedUnitDto {
+    fn from(unit: SerializedUnit) -> Self {
+        Self {
+            unit_id: unit.unit_id.unwrap_or_default(),
+            serial_number: unit.serial_number,
+            part_id: unit.part_id,
+            revision_id: unit.revision_id,
+            build_date: unit.build_date.duration_since(UNIX_EPOCH).map(|d| d.as_secs() as i64).unwrap_or_default(),
+            built_by: unit.built_by,
+            notes: unit.notes,
+        }
+    }
+}
+
+impl From<AsBuiltComponent> for AsBuiltComponentDto {
+    fn from(component: AsBuiltComponent) -> Self {
+        Self {
+            component_id: component.component_id.unwrap_or_default(),
+            position: component.position,
+            part_id: component.part_id,
+            quantity: component.quantity,
+            mpn_id: component.mpn_id,
+            lot_number: component.lot_number,
+            installed_unit_id: component.installed_unit_id,
+        }
+    }
+}
+
+/// Initialize the serialized unit state
+pub fn init_serialized_unit_state(connection_manager: ConnectionManager) -> SerializedUnitState {
+    // Create a serialized unit manager with 'static lifetime using a leak (safe in this context)
+    let static_connection_manager: &'static ConnectionManager = Box::leak(Box::new(connection_manager.clone()));
+    let serialized_unit_manager = SerializedUnitManager::new(static_connection_manager);
+
+    SerializedUnitState {
+        connection_manager,
+        serialized_unit_manager: Mutex::new(serialized_unit_manager),
+    }
+}
+
+/// Resolve a serial number to a unit
+fn find_unit(serialized_unit_manager: &SerializedUnitManager, serial_number: &str) -> Result<SerializedUnit, String> {
+    serialized_unit_manager.get_unit_by_serial(serial_number)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Unknown serial number: {}", serial_number))
+}
+
+/// Convert component data from the frontend to an as-built component
+fn to_component(serialized_unit_manager: &SerializedUnitManager, data: AsBuiltComponentData) -> Result<AsBuiltComponent, String> {
+    let mut component = AsBuiltComponent::new(data.position, data.part_id, data.quantity);
+    component.mpn_id = data.mpn_id;
+    component.lot_number = data.lot_number;
+    if let Some(serial_number) = data.installed_serial_number {
+        component.installed_unit_id = find_unit(serialized_unit_manager, &serial_number)?.unit_id;
+    }
+    Ok(component)
+}
+
+/// Record a built unit with its as-built components
+#[command]
+pub async fn create_serialized_unit(
+    unit_data: SerializedUnitCreationData,
+    serialized_unit_state: State<'_, SerializedUnitState>,
+) -> Result<SerializedUnitDto, String> {
+    let serialized_unit_manager = serialized_unit_state.serialized_unit_manager.lock().map_err(|e| e.to_string())?;
+
+    // Convert the components, resolving installed sub-assemblies by serial number
+    let components = unit_data.components
+        .into_iter()
+        .map(|data| to_component(&serialized_unit_manager, data))
+        .collect::<Result<Vec<_>, String>>()?;
+
+    // Create the unit
+    let mut unit = SerializedUnit::new(unit_data.serial_number, unit_data.part_id, unit_data.revision_id, unit_data.built_by);
+    unit.notes = unit_data.notes;
+    let unit_id = serialized_unit_manager.create_unit(&unit, &components)
+        .map_err(|e| e.to_string())?;
+
+    // Get the created unit
+    let created_unit = serialized_unit_manager.get_unit(unit_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(SerializedUnitDto::from(created_unit))
+}
+
+/// Get a unit by serial number
+#[command]
+pub async fn get_serialized_unit(
+    serial_number: String,
+    serialized_unit_state: State<'_, SerializedUnitState>,
+) -> Result<SerializedUnitDto, String> {
+    let serialized_unit_manager = serialized_unit_state.serialized_unit_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get the unit
+    let unit = find_unit(&serialized_unit_manager, &serial_number)?;
+
+    // Convert to DTO
+    Ok(SerializedUnitDto::from(unit))
+}
+
+/// Get the as-built components of a unit
+#[command]
+pub async fn get_unit_as_built(
+    serial_number: String,
+    serialized_unit_state: State<'_, SerializedUnitState>,
+) -> Result<Vec<AsBuiltComponentDto>, String> {
+    let serialized_unit_manager = serialized_unit_state.serialized_unit_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get the unit
+    let unit = find_unit(&serialized_unit_manager, &serial_number)?;
+
+    // Get the components
+    let components = serialized_unit_manager.get_components(unit.unit_id.unwrap_or_default())
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTOs
+    Ok(components.into_iter().map(AsBuiltComponentDto::from).collect())
+}
+
+/// Add a component to the as-built record of a unit
+#[command]
+pub async fn add_as_built_component(
+    serial_number: String,
+    component_data: AsBuiltComponentData,
+    serialized_unit_state: State<'_, SerializedUnitState>,
+) -> Result<i64, String> {
+    let serialized_unit_manager = serialized_unit_state.serialized_unit_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get the unit
+    let unit = find_unit(&serialized_unit_manager, &serial_number)?;
+
+    // Add the component
+    let component = to_component(&serialized_unit_manager, component_data)?;
+    serialized_unit_manager.add_component(unit.unit_id.unwrap_or_default(), &component)
+        .map_err(|e| e.to_string())
+}
+
+/// Get all units built to a revision
+#[command]
+pub async fn get_revision_units(
+    revision_id: i64,
+    serialized_unit_state: State<'_, SerializedUnitState>,
+) -> Result<Vec<SerializedUnitDto>, String> {
+    let serialized_unit_manager = serialized_unit_state.serialized_unit_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get the units
+    let units = serialized_unit_manager.get_units_for_revision(revision_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTOs
+    Ok(units.into_iter().map(SerializedUnitDto::from).collect())
+}
+
+/// Get all units containing a lot, directly or through installed sub-assemblies
+#[command]
+pub async fn get_units_containing_lot(
+    lot_number: String,
+    part_id: Option<i64>,
+    serialized_unit_state: State<'_, SerializedUnitState>,
+) -> Result<Vec<SerializedUnitDto>, String> {
+    let serialized_unit_manager = serialized_unit_state.serialized_unit_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get the units
+    let units = serialized_unit_manager.get_units_containing_lot(&lot_number, part_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTOs
+    Ok(units.into_iter().map(SerializedUnitDto::from).collect())
+}
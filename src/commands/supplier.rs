@@ -0,0 +1,366 @@
+//! Tauri command handlers for supplier and offer operations
+//!
+//! This module contains the command handlers for supplier-related operations in the Tauri application.
+//! These commands are exposed to the frontend and allow it to manage supplier offers, import
+//! distributor CSV exports and compute BOM cost rollups.
+
+use std::sync::Mutex;
+use tauri::{command, State};
+use serde::{Serialize, Deserialize};
+use crate::database::supplier::{SupplierManager, Supplier, SupplierOffer, PriceBreak, OfferCsvMapping, OfferImportResult};
+use crate::database::cost_rollup::{CostRollupManager, CostRollup, CostRollupLine, CostRollupNode};
+use crate::database::connection_manager::ConnectionManager;
+
+/// Supplier information for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupplierDto {
+    /// Supplier ID
+    pub supplier_id: i64,
+    /// Supplier name
+    pub name: String,
+    /// Supplier website
+    pub website: Option<String>,
+}
+
+/// Price break information for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceBreakDto {
+    /// Minimum order quantity for this price
+    pub quantity: i64,
+    /// Unit price at this quantity
+    pub unit_price: f64,
+}
+
+/// Supplier offer information for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupplierOfferDto {
+    /// Offer ID
+    pub offer_id: i64,
+    /// Manufacturer part ID
+    pub mpn_id: i64,
+    /// Supplier ID
+    pub supplier_id: i64,
+    /// Supplier SKU
+    pub sku: String,
+    /// Minimum order quantity
+    pub moq: i64,
+    /// Currency code
+    pub currency: String,
+    /// Lead time in days
+    pub lead_time_days: Option<i64>,
+    /// Quantity price breaks
+    pub price_breaks: Vec<PriceBreakDto>,
+}
+
+/// Supplier offer creation data from the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SupplierOfferCreationData {
+    /// Manufacturer part ID
+    pub mpn_id: i64,
+    /// Supplier name; the supplier is created if it does not exist
+    pub supplier_name: String,
+    /// Supplier SKU
+    pub sku: String,
+    /// Minimum order quantity
+    pub moq: i64,
+    /// Currency code
+    pub currency: String,
+    /// Lead time in days
+    pub lead_time_days: Option<i64>,
+    /// Quantity price breaks
+    pub price_breaks: Vec<PriceBreakDto>,
+}
+
+/// Offer CSV import summary for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfferImportResultDto {
+    /// Number of offers created
+    pub created: usize,
+    /// Number of existing offers updated
+    pub updated: usize,
+    /// Skipped rows as "line N: reason" messages
+    pub skipped: Vec<String>,
+}
+
+/// Cost rollup line information for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostRollupLineDto {
+    /// Purchased part ID
+    pub part_id: i64,
+    /// Quantity required for the whole build
+    pub required_quantity: i64,
+    /// Chosen offer, if any
+    pub offer: Option<SupplierOfferDto>,
+    /// Quantity to order
+    pub order_quantity: Option<i64>,
+    /// Unit price at the order quantity
+    pub unit_price: Option<f64>,
+    /// Total price of the order
+    pub extended_price: Option<f64>,
+}
+
+/// Cost rollup node information for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostRollupNodeDto {
+    /// Depth in the BOM, 0 for the top-level part
+    pub level: usize,
+    /// Part ID
+    pub part_id: i64,
+    /// Quantity across the whole build
+    pub quantity: i64,
+    /// Cost across the whole build
+    pub extended_cost: f64,
+    /// Whether any purchased part below this node could not be costed
+    pub incomplete: bool,
+}
+
+/// Cost rollup information for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CostRollupDto {
+    /// Top-level part ID
+    pub part_id: i64,
+    /// Number of top-level units being built
+    pub build_quantity: i64,
+    /// Currency of the rollup
+    pub currency: String,
+    /// Purchased part lines
+    pub lines: Vec<CostRollupLineDto>,
+    /// BOM tree in depth-first order
+    pub nodes: Vec<CostRollupNodeDto>,
+    /// Total cost of the build
+    pub total_cost: f64,
+    /// Cost per top-level unit
+    pub unit_cost: f64,
+    /// Purchased parts without a usable offer
+    pub uncosted_parts: Vec<i64>,
+}
+
+/// Supplier state for the application
+pub struct SupplierState {
+    /// Connection manager for the database
+    pub connection_manager: ConnectionManager,
+    /// Supplier manager for supplier operations
+    pub supplier_manager: Mutex<SupplierManager<'static>>,
+}
+
+impl From<Supplier> for SupplierDto {
+    fn from(supplier: Supplier) -> Self {
+        Self {
+            supplier_id: supplier.supplier_id.unwrap_or_default(),
+            name: supplier.name,
+            website: supplier.website,
+        }
+    }
+}
+
+impl From<SupplierOffer> for SupplierOfferDto {
+    fn from(offer: SupplierOffer) -> Self {
+        Self {
+            offer_id: offer.offer_id.unwrap_or_default(),
+            mpn_id: offer.mpn_id,
+            supplier_id: offer.supplier_id,
+            sku: offer.sku,
+            moq: offer.moq,
+            currency: offer.currency,
+            lead_time_days: offer.lead_time_days,
+            price_breaks: offer.price_breaks
+                .into_iter()
+                .map(|price_break| PriceBreakDto {
+                    quantity: price_break.quantity,
+                    unit_price: price_break.unit_price,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<OfferImportResult> for OfferImportResultDto {
+    fn from(result: OfferImportResult) -> Self {
+        Self {
+            created: result.created,
+            updated: result.updated,
+            skipped: result.skipped
+                .into_iter()
+                .map(|(line, reason)| format!("line {}: {}", line, reason))
+                .collect(),
+        }
+    }
+}
+
+impl From<CostRollupLine> for CostRollupLineDto {
+    fn from(line: CostRollupLine) -> Self {
+        Self {
+            part_id: line.part_id,
+            required_quantity: line.required_quantity,
+            offer: line.offer.map(SupplierOfferDto::from),
+            order_quantity: line.quote.as_ref().map(|quote| quote.order_quantity),
+            unit_price: line.quote.as_ref().map(|quote| quote.unit_price),
+            extended_price: line.quote.as_ref().map(|quote| quote.extended_price),
+        }
+    }
+}
+
+impl From<CostRollupNode> for CostRollupNodeDto {
+    fn from(node: CostRollupNode) -> Self {
+        Self {
+            level: node.level,
+            part_id: node.part_id,
+            quantity: node.quantity,
+            extended_cost: node.extended_cost,
+            incomplete: node.incomplete,
+        }
+    }
+}
+
+impl From<CostRollup> for CostRollupDto {
+    fn from(rollup: CostRollup) -> Self {
+        Self {
+            part_id: rollup.part_id,
+            build_quantity: rollup.build_quantity,
+            currency: rollup.currency,
+            lines: rollup.lines.into_iter().map(CostRollupLineDto::from).collect(),
+            nodes: rollup.nodes.into_iter().map(CostRollupNodeDto::from).collect(),
+            total_cost: rollup.total_cost,
+            unit_cost: rollup.unit_cost,
+            uncosted_parts: rollup.uncosted_parts,
+        }
+    }
+}
+
+/// Initialize the supplier state
+pub fn init_supplier_state(connection_manager: ConnectionManager) -> SupplierState {
+    // Create a supplier manager with 'static lifetime using a leak (safe in this context)
+    let static_connection_manager: &'static ConnectionManager = Box::leak(Box::new(connection_manager.clone()));
+    let supplier_manager = SupplierManager::new(static_connection_manager);
+
+    SupplierState {
+        connection_manager,
+        supplier_manager: Mutex::new(supplier_manager),
+    }
+}
+
+/// Get all suppliers
+#[command]
+pub async fn get_suppliers(
+    supplier_state: State<'_, SupplierState>,
+) -> Result<Vec<SupplierDto>, String> {
+    let supplier_manager = supplier_state.supplier_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get all suppliers
+    let suppliers = supplier_manager.get_all_suppliers()
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTOs
+    Ok(suppliers.into_iter().map(SupplierDto::from).collect())
+}
+
+/// Get all offers for a manufacturer part
+#[command]
+pub async fn get_supplier_offers(
+    mpn_id: i64,
+    supplier_state: State<'_, SupplierState>,
+) -> Result<Vec<SupplierOfferDto>, String> {
+    let supplier_manager = supplier_state.supplier_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get the offers
+    let offers = supplier_manager.get_offers_for_manufacturer_part(mpn_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTOs
+    Ok(offers.into_iter().map(SupplierOfferDto::from).collect())
+}
+
+/// Create a new supplier offer
+#[command]
+pub async fn create_supplier_offer(
+    offer_data: SupplierOfferCreationData,
+    supplier_state: State<'_, SupplierState>,
+) -> Result<SupplierOfferDto, String> {
+    let supplier_manager = supplier_state.supplier_manager.lock().map_err(|e| e.to_string())?;
+
+    // Resolve the supplier by name, creating it if needed
+    let supplier_id = match supplier_manager.get_supplier_by_name(&offer_data.supplier_name).map_err(|e| e.to_string())? {
+        Some(supplier) => supplier.supplier_id.unwrap_or_default(),
+        None => supplier_manager.create_supplier(&Supplier::new(offer_data.supplier_name, None))
+            .map_err(|e| e.to_string())?,
+    };
+
+    // Create the offer
+    let offer = SupplierOffer::new(
+        offer_data.mpn_id,
+        supplier_id,
+        offer_data.sku,
+        offer_data.moq,
+        offer_data.currency,
+        offer_data.lead_time_days,
+        offer_data.price_breaks
+            .into_iter()
+            .map(|price_break| PriceBreak::new(price_break.quantity, price_break.unit_price))
+            .collect(),
+    );
+    let offer_id = supplier_manager.create_offer(&offer)
+        .map_err(|e| e.to_string())?;
+
+    // Get the created offer
+    let created_offer = supplier_manager.get_offer(offer_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(SupplierOfferDto::from(created_offer))
+}
+
+/// Delete a supplier offer
+#[command]
+pub async fn delete_supplier_offer(
+    offer_id: i64,
+    supplier_state: State<'_, SupplierState>,
+) -> Result<(), String> {
+    let supplier_manager = supplier_state.supplier_manager.lock().map_err(|e| e.to_string())?;
+
+    // Delete the offer
+    supplier_manager.delete_offer(offer_id)
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Import supplier offers from a distributor CSV export
+#[command]
+pub async fn import_supplier_offers_csv(
+    csv_content: String,
+    supplier_name: Option<String>,
+    supplier_state: State<'_, SupplierState>,
+) -> Result<OfferImportResultDto, String> {
+    let supplier_manager = supplier_state.supplier_manager.lock().map_err(|e| e.to_string())?;
+
+    // Use the default column layout, with a fixed supplier for single-distributor exports
+    let mapping = OfferCsvMapping {
+        supplier_name,
+        ..OfferCsvMapping::default()
+    };
+
+    // Import the offers
+    let result = supplier_manager.import_offers_csv(csv_content.as_bytes(), &mapping)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(OfferImportResultDto::from(result))
+}
+
+/// Get the multi-level BOM cost rollup of a part for a build quantity
+#[command]
+pub async fn get_bom_cost_rollup(
+    part_id: i64,
+    build_quantity: i64,
+    currency: String,
+    supplier_state: State<'_, SupplierState>,
+) -> Result<CostRollupDto, String> {
+    let cost_rollup_manager = CostRollupManager::new(&supplier_state.connection_manager);
+
+    // Compute the rollup
+    let rollup = cost_rollup_manager.get_cost_rollup(part_id, build_quantity, &currency)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(CostRollupDto::from(rollup))
+}
@@ -0,0 +1,327 @@
+//! Tauri command handlers for user and permission operations
+//!
+//! This module contains the command handlers for user-related operations in the Tauri application.
+//! These commands are exposed to the frontend and allow it to manage users, their permissions,
+//! and the identity the application acts as.
+
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::{command, State};
+use serde::{Serialize, Deserialize};
+use crate::database::user::{UserManager, User, UserRole};
+use crate::database::permission::{PermissionManager, Permission, PermissionAction, GranteeType};
+use crate::database::connection_manager::ConnectionManager;
+use crate::commands::parts::DatabaseState;
+
+/// User data structure for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserDto {
+    /// User ID, if the user is stored in the database
+    pub user_id: Option<i64>,
+    /// Username
+    pub username: String,
+    /// Display name
+    pub display_name: Option<String>,
+    /// Email address
+    pub email: Option<String>,
+    /// Role (Designer, Viewer, Admin)
+    pub role: String,
+    /// Whether the user is active
+    pub active: bool,
+}
+
+/// User creation data from the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserCreationData {
+    /// Username
+    pub username: String,
+    /// Display name (optional)
+    pub display_name: Option<String>,
+    /// Email address (optional)
+    pub email: Option<String>,
+    /// Role (Designer, Viewer, Admin)
+    pub role: String,
+}
+
+/// User update data from the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserUpdateData {
+    /// Display name (optional)
+    pub display_name: Option<String>,
+    /// Email address (optional)
+    pub email: Option<String>,
+    /// Role (Designer, Viewer, Admin)
+    pub role: String,
+    /// Whether the user is active
+    pub active: bool,
+}
+
+/// Permission data structure for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionDto {
+    /// Permission ID
+    pub permission_id: i64,
+    /// Action (Create, Edit, Approve, Release, Obsolete)
+    pub action: String,
+    /// Kind of grantee (User, Group, Role)
+    pub grantee_type: String,
+    /// Username, group name or role name of the grantee
+    pub grantee: String,
+    /// Category the permission is limited to, if any
+    pub category_id: Option<i64>,
+}
+
+/// Permission creation data from the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionCreationData {
+    /// Action (Create, Edit, Approve, Release, Obsolete)
+    pub action: String,
+    /// Kind of grantee (User, Group, Role)
+    pub grantee_type: String,
+    /// Username, group name or role name of the grantee
+    pub grantee: String,
+    /// Category the permission is limited to (optional)
+    pub category_id: Option<i64>,
+}
+
+/// User state for the Tauri application
+pub struct UserState {
+    /// Connection manager for the database
+    pub connection_manager: ConnectionManager,
+    /// User manager for user operations
+    pub user_manager: Mutex<UserManager<'static>>,
+}
+
+impl From<User> for UserDto {
+    fn from(user: User) -> Self {
+        Self {
+            user_id: user.user_id,
+            username: user.username,
+            display_name: user.display_name,
+            email: user.email,
+            role: user.role.to_str().to_string(),
+            active: user.active,
+        }
+    }
+}
+
+impl From<Permission> for PermissionDto {
+    fn from(permission: Permission) -> Self {
+        Self {
+            permission_id: permission.permission_id.unwrap_or_default(),
+            action: permission.action.to_str().to_string(),
+            grantee_type: permission.grantee_type.to_str().to_string(),
+            grantee: permission.grantee,
+            category_id: permission.category_id,
+        }
+    }
+}
+
+/// Initialize the user state
+pub fn init_user_state(connection_manager: ConnectionManager) -> UserState {
+    // Create a user manager with 'static lifetime using a leak (safe in this context)
+    let static_connection_manager: &'static ConnectionManager = Box::leak(Box::new(connection_manager.clone()));
+    let user_manager = UserManager::new(static_connection_manager);
+
+    UserState {
+        connection_manager,
+        user_manager: Mutex::new(user_manager),
+    }
+}
+
+/// Check that the user the application acts as may manage users and permissions
+fn require_administrator(user_manager: &UserManager, db_state: &DatabaseState) -> Result<(), String> {
+    let part_manager = db_state.part_manager.lock().map_err(|e| e.to_string())?;
+    if !user_manager.is_administrator(part_manager.current_user()).map_err(|e| e.to_string())? {
+        return Err(format!("User {} is not an administrator", part_manager.current_user().username));
+    }
+    Ok(())
+}
+
+/// Get all users
+#[command]
+pub async fn get_users(
+    user_state: State<'_, UserState>,
+) -> Result<Vec<UserDto>, String> {
+    let user_manager = user_state.user_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get all users
+    let users = user_manager.get_all_users()
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTOs
+    Ok(users.into_iter().map(UserDto::from).collect())
+}
+
+/// Create a new user
+#[command]
+pub async fn create_user(
+    user_data: UserCreationData,
+    user_state: State<'_, UserState>,
+    db_state: State<'_, DatabaseState>,
+) -> Result<UserDto, String> {
+    let user_manager = user_state.user_manager.lock().map_err(|e| e.to_string())?;
+    require_administrator(&user_manager, &db_state)?;
+
+    // Convert the role string to UserRole
+    let role = UserRole::from_str(&user_data.role)
+        .ok_or_else(|| format!("Invalid role: {}", user_data.role))?;
+
+    // Create the user
+    let mut user = User::new(user_data.username, role);
+    user.display_name = user_data.display_name;
+    user.email = user_data.email;
+    let user_id = user_manager.create_user(&user)
+        .map_err(|e| e.to_string())?;
+
+    // Get the created user
+    let created_user = user_manager.get_user(user_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(UserDto::from(created_user))
+}
+
+/// Update a user
+#[command]
+pub async fn update_user(
+    user_id: i64,
+    update_data: UserUpdateData,
+    user_state: State<'_, UserState>,
+    db_state: State<'_, DatabaseState>,
+) -> Result<UserDto, String> {
+    let user_manager = user_state.user_manager.lock().map_err(|e| e.to_string())?;
+    require_administrator(&user_manager, &db_state)?;
+
+    // Convert the role string to UserRole
+    let role = UserRole::from_str(&update_data.role)
+        .ok_or_else(|| format!("Invalid role: {}", update_data.role))?;
+
+    // Update the user
+    let mut user = user_manager.get_user(user_id)
+        .map_err(|e| e.to_string())?;
+    user.display_name = update_data.display_name;
+    user.email = update_data.email;
+    user.role = role;
+    user.active = update_data.active;
+    user_manager.update_user(&user)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(UserDto::from(user))
+}
+
+/// Delete a user
+#[command]
+pub async fn delete_user(
+    user_id: i64,
+    user_state: State<'_, UserState>,
+    db_state: State<'_, DatabaseState>,
+) -> Result<(), String> {
+    let user_manager = user_state.user_manager.lock().map_err(|e| e.to_string())?;
+    require_administrator(&user_manager, &db_state)?;
+
+    // Delete the user
+    user_manager.delete_user(user_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Get the user the application acts as
+#[command]
+pub async fn get_current_user(
+    db_state: State<'_, DatabaseState>,
+) -> Result<UserDto, String> {
+    let part_manager = db_state.part_manager.lock().map_err(|e| e.to_string())?;
+
+    Ok(UserDto::from(part_manager.current_user().clone()))
+}
+
+/// Act as the user of the git identity configured for a repository
+///
+/// Falls back to the global git configuration if no repository path is given.
+#[command]
+pub async fn use_git_identity(
+    repo_path: Option<String>,
+    user_state: State<'_, UserState>,
+    db_state: State<'_, DatabaseState>,
+) -> Result<UserDto, String> {
+    let user_manager = user_state.user_manager.lock().map_err(|e| e.to_string())?;
+
+    // Read the git identity
+    let config = match repo_path {
+        Some(path) => git2::Repository::open(Path::new(&path))
+            .and_then(|repo| repo.config())
+            .map_err(|e| e.to_string())?,
+        None => git2::Config::open_default().map_err(|e| e.to_string())?,
+    };
+    let identity = User::from_git_config(&config, UserRole::Designer)
+        .ok_or_else(|| "Git user.name is not configured".to_string())?;
+
+    // Find or register the user and act as them
+    let user = user_manager.resolve_git_identity(&identity.username, identity.email.as_deref())
+        .map_err(|e| e.to_string())?;
+    let mut part_manager = db_state.part_manager.lock().map_err(|e| e.to_string())?;
+    part_manager.set_current_user(user.clone());
+
+    Ok(UserDto::from(user))
+}
+
+/// Get all permissions
+#[command]
+pub async fn get_permissions(
+    user_state: State<'_, UserState>,
+) -> Result<Vec<PermissionDto>, String> {
+    let _user_manager = user_state.user_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get all permissions
+    let permissions = PermissionManager::new(&user_state.connection_manager).get_all_permissions()
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTOs
+    Ok(permissions.into_iter().map(PermissionDto::from).collect())
+}
+
+/// Grant a permission
+#[command]
+pub async fn grant_permission(
+    permission_data: PermissionCreationData,
+    user_state: State<'_, UserState>,
+    db_state: State<'_, DatabaseState>,
+) -> Result<PermissionDto, String> {
+    let user_manager = user_state.user_manager.lock().map_err(|e| e.to_string())?;
+    require_administrator(&user_manager, &db_state)?;
+
+    // Convert the action and grantee type strings
+    let action = PermissionAction::from_str(&permission_data.action)
+        .ok_or_else(|| format!("Invalid permission action: {}", permission_data.action))?;
+    let grantee_type = GranteeType::from_str(&permission_data.grantee_type)
+        .ok_or_else(|| format!("Invalid grantee type: {}", permission_data.grantee_type))?;
+
+    // Grant the permission
+    let permission_manager = PermissionManager::new(&user_state.connection_manager);
+    let permission = Permission::new(action, grantee_type, permission_data.grantee, permission_data.category_id);
+    let permission_id = permission_manager.grant_permission(&permission)
+        .map_err(|e| e.to_string())?;
+
+    // Get the granted permission
+    let granted_permission = permission_manager.get_permission(permission_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    Ok(PermissionDto::from(granted_permission))
+}
+
+/// Revoke a permission
+#[command]
+pub async fn revoke_permission(
+    permission_id: i64,
+    user_state: State<'_, UserState>,
+    db_state: State<'_, DatabaseState>,
+) -> Result<(), String> {
+    let user_manager = user_state.user_manager.lock().map_err(|e| e.to_string())?;
+    require_administrator(&user_manager, &db_state)?;
+
+    // Revoke the permission
+    PermissionManager::new(&user_state.connection_manager).revoke_permission(permission_id)
+        .map_err(|e| e.to_string())
+}
@@ -3,10 +3,17 @@
 //! This module contains the command handlers for workflow-related operations in the Tauri application.
 //! These commands are exposed to the frontend and allow it to interact with the workflow management system.
 
+use std::path::Path;
 use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
 use tauri::{command, State};
 use serde::{Serialize, Deserialize};
-use crate::database::workflow::{WorkflowManager, Workflow, WorkflowState as DbWorkflowState, WorkflowTransition};
+use crate::database::workflow::{
+    WorkflowManager, Workflow, WorkflowState as DbWorkflowState, WorkflowTransition,
+    TransitionGuard, TransitionGuardType, TransitionAction, TransitionActionType,
+};
+use crate::database::workflow_engine::{WorkflowEngine, RevisionWorkflowState, GuardFailure, WorkflowEvent};
+use crate::database::workflow_definition::WorkflowDefinitionManager;
 use crate::database::connection_manager::ConnectionManager;
 
 /// Workflow data structure for the frontend
@@ -60,6 +67,79 @@ pub struct WorkflowTransitionDto {
     pub requires_approval: bool,
 }
 
+/// Current workflow state of a revision for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevisionWorkflowStateDto {
+    /// Revision ID
+    pub revision_id: i64,
+    /// Workflow governing the revision
+    pub workflow: WorkflowDto,
+    /// Current state of the revision
+    pub state: WorkflowStateDto,
+    /// User who moved the revision into the current state
+    pub updated_by: String,
+    /// Date the revision entered the current state (seconds since UNIX epoch)
+    pub updated_date: i64,
+}
+
+/// Transition guard data structure for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionGuardDto {
+    /// Guard ID
+    pub guard_id: i64,
+    /// Transition ID this guard belongs to
+    pub transition_id: i64,
+    /// Guard type (ChildrenReleased, FileAttached, PropertiesPresent, NoOpenDeviations or SignedRelease)
+    pub guard_type: String,
+    /// Guard parameter, e.g. a file type or comma-separated property names
+    pub parameter: Option<String>,
+}
+
+/// Transition action data structure for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionActionDto {
+    /// Action ID
+    pub action_id: i64,
+    /// Transition ID this action belongs to
+    pub transition_id: i64,
+    /// Action type (CreateTag, MergeBranch, RegenerateReadme or EmitEvent)
+    pub action_type: String,
+    /// Action parameter, e.g. a tag name or event name
+    pub parameter: Option<String>,
+    /// Position of the action in the execution order
+    pub sequence: i64,
+}
+
+/// Reason a transition guard did not pass, for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuardFailureDto {
+    /// Guard ID
+    pub guard_id: i64,
+    /// Guard type
+    pub guard_type: String,
+    /// Part the failure refers to
+    pub part_id: Option<i64>,
+    /// Human-readable reason
+    pub message: String,
+}
+
+/// Workflow event data structure for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowEventDto {
+    /// Event ID
+    pub event_id: i64,
+    /// Event name
+    pub name: String,
+    /// Revision ID
+    pub revision_id: i64,
+    /// Transition ID
+    pub transition_id: i64,
+    /// User who executed the transition
+    pub performed_by: String,
+    /// Date of the event (seconds since UNIX epoch)
+    pub created_date: i64,
+}
+
 /// Workflow creation data from the frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowCreationData {
@@ -73,6 +153,30 @@ pub struct WorkflowCreationData {
     pub is_active: bool,
 }
 
+/// Transition guard creation data from the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionGuardCreationData {
+    /// Transition ID this guard belongs to
+    pub transition_id: i64,
+    /// Guard type
+    pub guard_type: String,
+    /// Guard parameter
+    pub parameter: Option<String>,
+}
+
+/// Transition action creation data from the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitionActionCreationData {
+    /// Transition ID this action belongs to
+    pub transition_id: i64,
+    /// Action type
+    pub action_type: String,
+    /// Action parameter
+    pub parameter: Option<String>,
+    /// Position of the action in the execution order
+    pub sequence: i64,
+}
+
 /// Workflow state creation data from the frontend
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkflowStateCreationData {
@@ -152,6 +256,65 @@ impl From<WorkflowTransition> for WorkflowTransitionDto {
     }
 }
 
+impl From<TransitionGuard> for TransitionGuardDto {
+    fn from(guard: TransitionGuard) -> Self {
+        Self {
+            guard_id: guard.guard_id.unwrap_or_default(),
+            transition_id: guard.transition_id,
+            guard_type: guard.guard_type.to_str().to_string(),
+            parameter: guard.parameter,
+        }
+    }
+}
+
+impl From<TransitionAction> for TransitionActionDto {
+    fn from(action: TransitionAction) -> Self {
+        Self {
+            action_id: action.action_id.unwrap_or_default(),
+            transition_id: action.transition_id,
+            action_type: action.action_type.to_str().to_string(),
+            parameter: action.parameter,
+            sequence: action.sequence,
+        }
+    }
+}
+
+impl From<GuardFailure> for GuardFailureDto {
+    fn from(failure: GuardFailure) -> Self {
+        Self {
+            guard_id: failure.guard_id,
+            guard_type: failure.guard_type.to_str().to_string(),
+            part_id: failure.part_id,
+            message: failure.message,
+        }
+    }
+}
+
+impl From<WorkflowEvent> for WorkflowEventDto {
+    fn from(event: WorkflowEvent) -> Self {
+        Self {
+            event_id: event.event_id.unwrap_or_default(),
+            name: event.name,
+            revision_id: event.revision_id,
+            transition_id: event.transition_id,
+            performed_by: event.performed_by,
+            created_date: event.created_date.duration_since(UNIX_EPOCH).map(|d| d.as_secs() as i64).unwrap_or_default(),
+        }
+    }
+}
+
+impl From<RevisionWorkflowState> for RevisionWorkflowStateDto {
+    fn from(state: RevisionWorkflowState) -> Self {
+        Self {
+            revision_id: state.revision_id,
+            workflow: WorkflowDto::from(state.workflow),
+            state: WorkflowStateDto::from(state.state),
+            updated_by: state.updated_by,
+            updated_date: state.updated_date.duration_since(UNIX_EPOCH).map(|d| d.as_secs() as i64).unwrap_or_default(),
+        }
+    }
+}
+
 /// Initialize the workflow state
 pub fn init_workflow_state(connection_manager: ConnectionManager) -> WorkflowState {
     // Create a workflow manager with 'static lifetime using a leak (safe in this context)
@@ -585,4 +748,211 @@ pub async fn create_default_part_workflow(
     
     // Convert to DTO
     Ok(WorkflowDto::from(workflow))
-}
\ No newline at end of file
+}
+
+/// Assign a workflow to a category, or reset it to the default part workflow
+#[command]
+pub async fn assign_category_workflow(
+    category_id: i64,
+    workflow_id: Option<i64>,
+    workflow_state: State<'_, WorkflowState>,
+) -> Result<(), String> {
+    let _workflow_manager = workflow_state.workflow_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Assign the workflow
+    let workflow_engine = WorkflowEngine::new(&workflow_state.connection_manager);
+    workflow_engine.assign_category_workflow(category_id, workflow_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Get the current workflow state of a revision
+#[command]
+pub async fn get_revision_workflow_state(
+    revision_id: i64,
+    workflow_state: State<'_, WorkflowState>,
+) -> Result<RevisionWorkflowStateDto, String> {
+    let _workflow_manager = workflow_state.workflow_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Get the state
+    let workflow_engine = WorkflowEngine::new(&workflow_state.connection_manager);
+    let state = workflow_engine.get_revision_state(revision_id)
+        .map_err(|e| e.to_string())?;
+    
+    // Convert to DTO
+    Ok(RevisionWorkflowStateDto::from(state))
+}
+
+/// Get the transitions available from the current workflow state of a revision
+#[command]
+pub async fn get_available_revision_transitions(
+    revision_id: i64,
+    workflow_state: State<'_, WorkflowState>,
+) -> Result<Vec<WorkflowTransitionDto>, String> {
+    let _workflow_manager = workflow_state.workflow_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Get the transitions
+    let workflow_engine = WorkflowEngine::new(&workflow_state.connection_manager);
+    let transitions = workflow_engine.get_available_transitions(revision_id)
+        .map_err(|e| e.to_string())?;
+    
+    // Convert to DTOs
+    Ok(transitions.into_iter().map(WorkflowTransitionDto::from).collect())
+}
+
+/// Get the guards of a workflow transition
+#[command]
+pub async fn get_transition_guards(
+    transition_id: i64,
+    workflow_state: State<'_, WorkflowState>,
+) -> Result<Vec<TransitionGuardDto>, String> {
+    let workflow_manager = workflow_state.workflow_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Get the guards
+    let guards = workflow_manager.get_transition_guards(transition_id)
+        .map_err(|e| e.to_string())?;
+    
+    // Convert to DTOs
+    Ok(guards.into_iter().map(TransitionGuardDto::from).collect())
+}
+
+/// Add a guard to a workflow transition
+#[command]
+pub async fn create_transition_guard(
+    guard_data: TransitionGuardCreationData,
+    workflow_state: State<'_, WorkflowState>,
+) -> Result<i64, String> {
+    let workflow_manager = workflow_state.workflow_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Parse the guard type
+    let guard_type = TransitionGuardType::from_str(&guard_data.guard_type)
+        .ok_or_else(|| format!("Unknown guard type: {}", guard_data.guard_type))?;
+    
+    // Create the guard
+    let guard = TransitionGuard::new(guard_data.transition_id, guard_type, guard_data.parameter);
+    workflow_manager.create_transition_guard(&guard)
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a transition guard
+#[command]
+pub async fn delete_transition_guard(
+    guard_id: i64,
+    workflow_state: State<'_, WorkflowState>,
+) -> Result<(), String> {
+    let workflow_manager = workflow_state.workflow_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Delete the guard
+    workflow_manager.delete_transition_guard(guard_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Get the actions of a workflow transition
+#[command]
+pub async fn get_transition_actions(
+    transition_id: i64,
+    workflow_state: State<'_, WorkflowState>,
+) -> Result<Vec<TransitionActionDto>, String> {
+    let workflow_manager = workflow_state.workflow_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Get the actions
+    let actions = workflow_manager.get_transition_actions(transition_id)
+        .map_err(|e| e.to_string())?;
+    
+    // Convert to DTOs
+    Ok(actions.into_iter().map(TransitionActionDto::from).collect())
+}
+
+/// Add an action to a workflow transition
+#[command]
+pub async fn create_transition_action(
+    action_data: TransitionActionCreationData,
+    workflow_state: State<'_, WorkflowState>,
+) -> Result<i64, String> {
+    let workflow_manager = workflow_state.workflow_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Parse the action type
+    let action_type = TransitionActionType::from_str(&action_data.action_type)
+        .ok_or_else(|| format!("Unknown action type: {}", action_data.action_type))?;
+    
+    // Create the action
+    let action = TransitionAction::new(action_data.transition_id, action_type, action_data.parameter, action_data.sequence);
+    workflow_manager.create_transition_action(&action)
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a transition action
+#[command]
+pub async fn delete_transition_action(
+    action_id: i64,
+    workflow_state: State<'_, WorkflowState>,
+) -> Result<(), String> {
+    let workflow_manager = workflow_state.workflow_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Delete the action
+    workflow_manager.delete_transition_action(action_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Check the guards of a transition for a revision without executing it
+#[command]
+pub async fn check_revision_transition(
+    revision_id: i64,
+    transition_id: i64,
+    workflow_state: State<'_, WorkflowState>,
+) -> Result<Vec<GuardFailureDto>, String> {
+    let _workflow_manager = workflow_state.workflow_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Check the guards
+    let workflow_engine = WorkflowEngine::new(&workflow_state.connection_manager);
+    let failures = workflow_engine.check_transition(revision_id, transition_id)
+        .map_err(|e| e.to_string())?;
+    
+    // Convert to DTOs
+    Ok(failures.into_iter().map(GuardFailureDto::from).collect())
+}
+
+/// Get the events emitted by transitions of a revision
+#[command]
+pub async fn get_revision_workflow_events(
+    revision_id: i64,
+    workflow_state: State<'_, WorkflowState>,
+) -> Result<Vec<WorkflowEventDto>, String> {
+    let _workflow_manager = workflow_state.workflow_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Get the events
+    let workflow_engine = WorkflowEngine::new(&workflow_state.connection_manager);
+    let events = workflow_engine.get_events_for_revision(revision_id)
+        .map_err(|e| e.to_string())?;
+    
+    // Convert to DTOs
+    Ok(events.into_iter().map(WorkflowEventDto::from).collect())
+}
+/// Load the workflow definition files in a directory
+#[command]
+pub async fn load_workflow_files(
+    path: String,
+    workflow_state: State<'_, WorkflowState>,
+) -> Result<Vec<i64>, String> {
+    let _workflow_manager = workflow_state.workflow_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Validate and load the definitions
+    let definition_manager = WorkflowDefinitionManager::new(&workflow_state.connection_manager);
+    definition_manager.load_directory(Path::new(&path))
+        .map_err(|e| e.to_string())
+}
+
+/// Export all workflows to definition files in a directory
+#[command]
+pub async fn export_workflow_files(
+    path: String,
+    workflow_state: State<'_, WorkflowState>,
+) -> Result<Vec<String>, String> {
+    let _workflow_manager = workflow_state.workflow_manager.lock().map_err(|e| e.to_string())?;
+    
+    // Write the definitions
+    let definition_manager = WorkflowDefinitionManager::new(&workflow_state.connection_manager);
+    let paths = definition_manager.export_directory(Path::new(&path))
+        .map_err(|e| e.to_string())?;
+    
+    Ok(paths.into_iter().map(|path| path.to_string_lossy().to_string()).collect())
+}
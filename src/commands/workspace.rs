@@ -4,20 +4,29 @@
 //! These commands are exposed to the frontend and allow it to interact with the workspace management system.
 
 use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tauri::{command, State};
 use serde::{Serialize, Deserialize};
-use chrono::Utc;
-use std::collections::HashMap;
+use crate::database::workspace::{Workspace, WorkspaceMember, WorkspaceManager};
+use crate::database::part::PartManager;
+use crate::database::revision::RevisionManager;
+use crate::database::connection_manager::ConnectionManager;
+use crate::commands::parts::DatabaseState;
+use crate::commands::repository::GitBackendState;
 
 /// Workspace data structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkspaceDto {
     /// Workspace ID
-    pub id: String,
+    pub id: i64,
     /// Workspace name
     pub name: String,
     /// Workspace description
     pub description: String,
+    /// Whether the sparse checkout includes the BOM trees of the parts
+    pub include_bom: bool,
+    /// User who created the workspace
+    pub created_by: String,
     /// Creation timestamp
     pub created_at: String,
     /// Last update timestamp
@@ -27,16 +36,27 @@ pub struct WorkspaceDto {
 }
 
 /// Part reference in a workspace
+///
+/// The part number, name, version and status are read from the part and its revision each time
+/// the workspace is loaded.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkspacePartDto {
     /// Part ID
     pub id: i64,
+    /// ID of the pinned revision; None when the workspace follows the latest revision
+    pub pinned_revision_id: Option<i64>,
+    /// ID of the revision in the workspace
+    pub revision_id: Option<i64>,
     /// Part number
     pub part_number: String,
     /// Part name
     pub name: String,
-    /// Part status
-    pub status: String,
+    /// Version of the revision in the workspace
+    pub version: Option<String>,
+    /// Status of the revision in the workspace
+    pub status: Option<String>,
+    /// Date the part was added to the workspace
+    pub added_at: String,
 }
 
 /// Workspace creation data from the frontend
@@ -46,6 +66,8 @@ pub struct WorkspaceCreationData {
     pub name: String,
     /// Workspace description
     pub description: String,
+    /// Whether the sparse checkout includes the BOM trees of the parts (optional)
+    pub include_bom: Option<bool>,
 }
 
 /// Workspace update data from the frontend
@@ -55,33 +77,71 @@ pub struct WorkspaceUpdateData {
     pub name: Option<String>,
     /// Workspace description (optional)
     pub description: Option<String>,
+    /// Whether the sparse checkout includes the BOM trees of the parts (optional)
+    pub include_bom: Option<bool>,
 }
 
-/// Workspace state for in-memory storage
-/// Note: In a real implementation, this would use the database
+/// Workspace state for the Tauri application
 pub struct WorkspaceState {
-    /// Map of workspace ID to workspace data
-    pub workspaces: Mutex<HashMap<String, WorkspaceDto>>,
-    /// Next workspace ID counter
-    pub next_id: Mutex<u64>,
-}
-
-impl WorkspaceState {
-    /// Create a new workspace state
-    pub fn new() -> Self {
-        Self {
-            workspaces: Mutex::new(HashMap::new()),
-            next_id: Mutex::new(1),
-        }
-    }
+    /// Connection manager for the database
+    pub connection_manager: ConnectionManager,
+    /// Workspace manager for workspace operations
+    pub workspace_manager: Mutex<WorkspaceManager<'static>>,
+}
 
-    /// Get the next workspace ID
-    fn get_next_id(&self) -> String {
-        let mut id = self.next_id.lock().unwrap();
-        let current = *id;
-        *id += 1;
-        format!("ws-{}", current)
-    }
+/// Format a timestamp in ISO 8601 format
+fn format_date(date: SystemTime) -> String {
+    let secs = date.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    chrono::DateTime::<chrono::Utc>::from_timestamp(secs as i64, 0)
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_else(|| String::from("Invalid date"))
+}
+
+/// Resolve a workspace member against its part and revision
+fn member_to_dto(member: WorkspaceMember, connection_manager: &ConnectionManager) -> Result<WorkspacePartDto, String> {
+    let part = PartManager::new(connection_manager).get_part(member.part_id)
+        .map_err(|e| e.to_string())?;
+    let revision_manager = RevisionManager::new(connection_manager);
+    let revision = match member.revision_id {
+        Some(revision_id) => Some(revision_manager.get_revision(revision_id).map_err(|e| e.to_string())?),
+        None => revision_manager.get_latest_revision(member.part_id).ok(),
+    };
+
+    Ok(WorkspacePartDto {
+        id: member.part_id,
+        pinned_revision_id: member.revision_id,
+        revision_id: revision.as_ref().and_then(|revision| revision.revision_id),
+        part_number: part.display_part_number(connection_manager),
+        name: part.name,
+        version: revision.as_ref().map(|revision| revision.version.clone()),
+        status: revision.map(|revision| revision.status.to_str().to_string()),
+        added_at: format_date(member.added_date),
+    })
+}
+
+/// Load the members of a workspace and convert it to a DTO
+fn workspace_to_dto(
+    workspace: Workspace,
+    workspace_manager: &WorkspaceManager,
+    connection_manager: &ConnectionManager,
+) -> Result<WorkspaceDto, String> {
+    let workspace_id = workspace.workspace_id.unwrap_or_default();
+    let parts = workspace_manager.get_members(workspace_id)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|member| member_to_dto(member, connection_manager))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(WorkspaceDto {
+        id: workspace_id,
+        name: workspace.name,
+        description: workspace.description.unwrap_or_default(),
+        include_bom: workspace.include_bom,
+        created_by: workspace.created_by,
+        created_at: format_date(workspace.created_date),
+        updated_at: format_date(workspace.updated_date),
+        parts,
+    })
 }
 
 /// Get all workspaces
@@ -89,21 +149,32 @@ impl WorkspaceState {
 pub async fn get_workspaces(
     workspace_state: State<'_, WorkspaceState>,
 ) -> Result<Vec<WorkspaceDto>, String> {
-    let workspaces = workspace_state.workspaces.lock().map_err(|e| e.to_string())?;
-    Ok(workspaces.values().cloned().collect())
+    let workspace_manager = workspace_state.workspace_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get the workspaces
+    let workspaces = workspace_manager.get_all_workspaces()
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTOs
+    workspaces.into_iter()
+        .map(|workspace| workspace_to_dto(workspace, &workspace_manager, &workspace_state.connection_manager))
+        .collect()
 }
 
 /// Get a specific workspace by ID
 #[command]
 pub async fn get_workspace(
-    workspace_id: String,
+    workspace_id: i64,
     workspace_state: State<'_, WorkspaceState>,
 ) -> Result<WorkspaceDto, String> {
-    let workspaces = workspace_state.workspaces.lock().map_err(|e| e.to_string())?;
-    
-    workspaces.get(&workspace_id)
-        .cloned()
-        .ok_or_else(|| format!("Workspace not found: {}", workspace_id))
+    let workspace_manager = workspace_state.workspace_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get the workspace
+    let workspace = workspace_manager.get_workspace(workspace_id)
+        .map_err(|_| format!("Workspace not found: {}", workspace_id))?;
+
+    // Convert to DTO
+    workspace_to_dto(workspace, &workspace_manager, &workspace_state.connection_manager)
 }
 
 /// Create a new workspace
@@ -111,123 +182,171 @@ pub async fn get_workspace(
 pub async fn create_workspace(
     workspace_data: WorkspaceCreationData,
     workspace_state: State<'_, WorkspaceState>,
+    db_state: State<'_, DatabaseState>,
 ) -> Result<WorkspaceDto, String> {
-    let id = workspace_state.get_next_id();
-    let now = Utc::now().to_rfc3339();
-    
-    let workspace = WorkspaceDto {
-        id,
-        name: workspace_data.name,
-        description: workspace_data.description,
-        created_at: now.clone(),
-        updated_at: now,
-        parts: Vec::new(),
-    };
-    
-    let mut workspaces = workspace_state.workspaces.lock().map_err(|e| e.to_string())?;
-    let workspace_clone = workspace.clone();
-    workspaces.insert(workspace.id.clone(), workspace);
-    
-    Ok(workspace_clone)
+    let workspace_manager = workspace_state.workspace_manager.lock().map_err(|e| e.to_string())?;
+    let created_by = db_state.part_manager.lock().map_err(|e| e.to_string())?
+        .current_user().username.clone();
+
+    // Create the workspace
+    let description = Some(workspace_data.description).filter(|description| !description.is_empty());
+    let mut workspace = Workspace::new(workspace_data.name, description, created_by);
+    workspace.include_bom = workspace_data.include_bom.unwrap_or_default();
+    let workspace_id = workspace_manager.create_workspace(&workspace)
+        .map_err(|e| e.to_string())?;
+
+    // Get the created workspace
+    let workspace = workspace_manager.get_workspace(workspace_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    workspace_to_dto(workspace, &workspace_manager, &workspace_state.connection_manager)
 }
 
 /// Update an existing workspace
 #[command]
 pub async fn update_workspace(
-    workspace_id: String,
+    workspace_id: i64,
     workspace_data: WorkspaceUpdateData,
     workspace_state: State<'_, WorkspaceState>,
 ) -> Result<WorkspaceDto, String> {
-    let mut workspaces = workspace_state.workspaces.lock().map_err(|e| e.to_string())?;
-    
-    let workspace = workspaces.get_mut(&workspace_id)
-        .ok_or_else(|| format!("Workspace not found: {}", workspace_id))?;
-    
+    let workspace_manager = workspace_state.workspace_manager.lock().map_err(|e| e.to_string())?;
+
+    // Get the workspace
+    let mut workspace = workspace_manager.get_workspace(workspace_id)
+        .map_err(|_| format!("Workspace not found: {}", workspace_id))?;
+
     if let Some(name) = workspace_data.name {
         workspace.name = name;
     }
-    
+
     if let Some(description) = workspace_data.description {
-        workspace.description = description;
+        workspace.description = Some(description).filter(|description| !description.is_empty());
+    }
+
+    if let Some(include_bom) = workspace_data.include_bom {
+        workspace.include_bom = include_bom;
     }
-    
-    workspace.updated_at = Utc::now().to_rfc3339();
-    
-    Ok(workspace.clone())
+
+    // Update the workspace
+    workspace_manager.update_workspace(&workspace)
+        .map_err(|e| e.to_string())?;
+
+    // Get the updated workspace
+    let workspace = workspace_manager.get_workspace(workspace_id)
+        .map_err(|e| e.to_string())?;
+
+    // Convert to DTO
+    workspace_to_dto(workspace, &workspace_manager, &workspace_state.connection_manager)
 }
 
 /// Delete a workspace
 #[command]
 pub async fn delete_workspace(
-    workspace_id: String,
+    workspace_id: i64,
     workspace_state: State<'_, WorkspaceState>,
 ) -> Result<(), String> {
-    let mut workspaces = workspace_state.workspaces.lock().map_err(|e| e.to_string())?;
-    
-    if workspaces.remove(&workspace_id).is_none() {
-        return Err(format!("Workspace not found: {}", workspace_id));
-    }
-    
-    Ok(())
+    let workspace_manager = workspace_state.workspace_manager.lock().map_err(|e| e.to_string())?;
+
+    // Delete the workspace
+    workspace_manager.delete_workspace(workspace_id)
+        .map_err(|_| format!("Workspace not found: {}", workspace_id))
 }
 
-/// Add a part to a workspace
+/// Add a part to a workspace, optionally pinned to one of its revisions
 #[command]
 pub async fn add_part_to_workspace(
-    workspace_id: String,
+    workspace_id: i64,
     part_id: i64,
+    revision_id: Option<i64>,
     workspace_state: State<'_, WorkspaceState>,
-    // In a real implementation, we would use the part_state to get the part information
 ) -> Result<(), String> {
-    let mut workspaces = workspace_state.workspaces.lock().map_err(|e| e.to_string())?;
-    
-    let workspace = workspaces.get_mut(&workspace_id)
-        .ok_or_else(|| format!("Workspace not found: {}", workspace_id))?;
-    
-    // Check if the part is already in the workspace
-    if workspace.parts.iter().any(|p| p.id == part_id) {
-        return Err(format!("Part {} is already in workspace {}", part_id, workspace_id));
-    }
-    
-    // In a real implementation, we would get the part information from the database
-    // For now, we'll create a dummy part
-    let part = WorkspacePartDto {
-        id: part_id,
-        part_number: format!("PART-{}", part_id),
-        name: format!("Part {}", part_id),
-        status: "Draft".to_string(),
-    };
-    
-    workspace.parts.push(part);
-    workspace.updated_at = Utc::now().to_rfc3339();
-    
-    Ok(())
+    let workspace_manager = workspace_state.workspace_manager.lock().map_err(|e| e.to_string())?;
+
+    // Add the part
+    workspace_manager.add_part(workspace_id, part_id, revision_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Pin a part in a workspace to one of its revisions, or let it follow the latest revision
+#[command]
+pub async fn set_workspace_part_revision(
+    workspace_id: i64,
+    part_id: i64,
+    revision_id: Option<i64>,
+    workspace_state: State<'_, WorkspaceState>,
+) -> Result<(), String> {
+    let workspace_manager = workspace_state.workspace_manager.lock().map_err(|e| e.to_string())?;
+
+    // Update the membership
+    workspace_manager.set_part_revision(workspace_id, part_id, revision_id)
+        .map_err(|e| e.to_string())
 }
 
 /// Remove a part from a workspace
 #[command]
 pub async fn remove_part_from_workspace(
-    workspace_id: String,
+    workspace_id: i64,
     part_id: i64,
     workspace_state: State<'_, WorkspaceState>,
 ) -> Result<(), String> {
-    let mut workspaces = workspace_state.workspaces.lock().map_err(|e| e.to_string())?;
-    
-    let workspace = workspaces.get_mut(&workspace_id)
-        .ok_or_else(|| format!("Workspace not found: {}", workspace_id))?;
-    
-    // Find the index of the part in the workspace
-    let part_index = workspace.parts.iter().position(|p| p.id == part_id)
-        .ok_or_else(|| format!("Part {} not found in workspace {}", part_id, workspace_id))?;
-    
-    // Remove the part from the workspace
-    workspace.parts.remove(part_index);
-    workspace.updated_at = Utc::now().to_rfc3339();
-    
-    Ok(())
+    let workspace_manager = workspace_state.workspace_manager.lock().map_err(|e| e.to_string())?;
+
+    // Remove the part
+    workspace_manager.remove_part(workspace_id, part_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Activate a workspace by checking out only the part directories it needs
+///
+/// Returns the part directories of the sparse checkout.
+#[command]
+pub async fn activate_workspace(
+    workspace_id: i64,
+    workspace_state: State<'_, WorkspaceState>,
+    git_state: State<'_, GitBackendState>,
+) -> Result<Vec<String>, String> {
+    let workspace_manager = workspace_state.workspace_manager.lock().map_err(|e| e.to_string())?;
+    let git_manager = git_state.manager.lock().map_err(|e| e.to_string())?;
+
+    // Work out the part directories of the workspace
+    let patterns = workspace_manager.get_sparse_checkout_patterns(workspace_id)
+        .map_err(|e| e.to_string())?;
+
+    // Apply them to the repository
+    let repo_path = std::env::current_dir().map_err(|e| e.to_string())?;
+    let repo = git_manager.open_repository(&repo_path)
+        .map_err(|e| e.to_string())?;
+    let patterns_ref: Vec<&str> = patterns.iter().map(String::as_str).collect();
+    git_manager.repository_manager(&repo).configure_sparse_checkout(&patterns_ref)
+        .map_err(|e| e.to_string())?;
+
+    Ok(patterns)
+}
+
+/// Deactivate the active workspace by checking out the full repository again
+#[command]
+pub async fn deactivate_workspace(
+    git_state: State<'_, GitBackendState>,
+) -> Result<(), String> {
+    let git_manager = git_state.manager.lock().map_err(|e| e.to_string())?;
+
+    // Restore the full working tree
+    let repo_path = std::env::current_dir().map_err(|e| e.to_string())?;
+    let repo = git_manager.open_repository(&repo_path)
+        .map_err(|e| e.to_string())?;
+    git_manager.repository_manager(&repo).disable_sparse_checkout()
+        .map_err(|e| e.to_string())
 }
 
 /// Initialize the workspace state
-pub fn init_workspace_state() -> WorkspaceState {
-    WorkspaceState::new()
-}
\ No newline at end of file
+pub fn init_workspace_state(connection_manager: ConnectionManager) -> WorkspaceState {
+    // Create a workspace manager with 'static lifetime using a leak (safe in this context)
+    let static_connection_manager: &'static ConnectionManager = Box::leak(Box::new(connection_manager.clone()));
+    let workspace_manager = WorkspaceManager::new(static_connection_manager);
+
+    WorkspaceState {
+        connection_manager,
+        workspace_manager: Mutex::new(workspace_manager),
+    }
+}
@@ -6,6 +6,7 @@ use rusqlite::{Transaction, params, Row, Result as SqliteResult};
 use std::time::{SystemTime, UNIX_EPOCH};
 use crate::database::schema::{DatabaseResult, DatabaseError};
 use crate::database::connection_manager::ConnectionManager;
+use crate::database::audit::{AuditAction, AuditManager, DEFAULT_ACTOR};
 
 /// Status of an approval
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -28,6 +29,7 @@ impl ApprovalStatus {
     /// # Returns
     ///
     /// The corresponding ApprovalStatus
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(status: &str) -> Option<Self> {
         match status {
             "Pending" => Some(Self::Pending),
@@ -108,6 +110,8 @@ impl Approval {
 pub struct ApprovalManager<'a> {
     /// Connection manager for the SQLite database
     connection_manager: &'a ConnectionManager,
+    /// User recorded in the audit log for the changes made through this manager
+    actor: String,
 }
 
 impl<'a> ApprovalManager<'a> {
@@ -121,7 +125,24 @@ impl<'a> ApprovalManager<'a> {
     ///
     /// A new ApprovalManager instance
     pub fn new(connection_manager: &'a ConnectionManager) -> Self {
-        Self { connection_manager }
+        Self {
+            connection_manager,
+            actor: DEFAULT_ACTOR.to_string(),
+        }
+    }
+    
+    /// Set the user recorded in the audit log for the changes made through this manager
+    ///
+    /// # Arguments
+    ///
+    /// * `actor` - Username of the user making the changes
+    ///
+    /// # Returns
+    ///
+    /// The ApprovalManager recording changes as made by the user
+    pub fn with_actor(mut self, actor: &str) -> Self {
+        self.actor = actor.to_string();
+        self
     }
     
     /// Create a new ApprovalManager with a transaction
@@ -174,9 +195,11 @@ impl<'a> ApprovalManager<'a> {
                     approval.comments,
                 ],
             )?;
-            Ok::<i64, DatabaseError>(conn.last_insert_rowid())
-        }).map_err(DatabaseError::from)
-    }
+            let approval_id = conn.last_insert_rowid();
+            AuditManager::new(self.connection_manager)
+                .record(conn, &self.actor, "Approval", approval_id, AuditAction::Create, Some(Self::audit_details(approval)))?;
+            Ok::<i64, DatabaseError>(approval_id)
+        })}
     
     /// Create a new approval in the database within an existing transaction
     ///
@@ -211,7 +234,10 @@ impl<'a> ApprovalManager<'a> {
                 approval.comments,
             ],
         )?;
-        Ok(tx.last_insert_rowid())
+        let approval_id = tx.last_insert_rowid();
+        AuditManager::new(self.connection_manager)
+            .record(tx, &self.actor, "Approval", approval_id, AuditAction::Create, Some(Self::audit_details(approval)))?;
+        Ok(approval_id)
     }
 
     /// Get an approval by its ID
@@ -237,8 +263,7 @@ impl<'a> ApprovalManager<'a> {
                 |row| self.row_to_approval(row),
             )?;
             Ok::<Approval, DatabaseError>(approval)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get an approval by its ID within an existing transaction
     ///
@@ -292,8 +317,7 @@ impl<'a> ApprovalManager<'a> {
                 approvals.push(approval_result?);
             }
             Ok::<Vec<Approval>, DatabaseError>(approvals)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get all approvals for a revision within an existing transaction
     ///
@@ -348,8 +372,7 @@ impl<'a> ApprovalManager<'a> {
                 |row| self.row_to_approval(row),
             )?;
             Ok::<Approval, DatabaseError>(approval)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get an approval for a specific revision and approver within an existing transaction
     ///
@@ -418,9 +441,13 @@ impl<'a> ApprovalManager<'a> {
                     comments,
                 ],
             )?;
+            AuditManager::new(self.connection_manager)
+                .record(conn, &self.actor, "Approval", approval_id, AuditAction::Update, Some(serde_json::json!({
+                    "status": status.to_str(),
+                    "comments": comments,
+                })))?;
             Ok::<(), DatabaseError>(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
     
     /// Update the status of an approval within an existing transaction
     ///
@@ -463,6 +490,11 @@ impl<'a> ApprovalManager<'a> {
                 comments,
             ],
         )?;
+        AuditManager::new(self.connection_manager)
+            .record(tx, &self.actor, "Approval", approval_id, AuditAction::Update, Some(serde_json::json!({
+                "status": status.to_str(),
+                "comments": comments,
+            })))?;
         Ok(())
     }
 
@@ -485,9 +517,10 @@ impl<'a> ApprovalManager<'a> {
                 "DELETE FROM Approvals WHERE approval_id = ?1",
                 params![approval_id],
             )?;
+            AuditManager::new(self.connection_manager)
+                .record(conn, &self.actor, "Approval", approval_id, AuditAction::Delete, None)?;
             Ok::<(), DatabaseError>(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
     
     /// Delete an approval within an existing transaction
     ///
@@ -508,6 +541,8 @@ impl<'a> ApprovalManager<'a> {
             "DELETE FROM Approvals WHERE approval_id = ?1",
             params![approval_id],
         )?;
+        AuditManager::new(self.connection_manager)
+            .record(tx, &self.actor, "Approval", approval_id, AuditAction::Delete, None)?;
         Ok(())
     }
 
@@ -603,6 +638,16 @@ impl<'a> ApprovalManager<'a> {
             comments: row.get(5)?,
         })
     }
+
+    /// Describe an approval for the audit log
+    fn audit_details(approval: &Approval) -> serde_json::Value {
+        serde_json::json!({
+            "revision_id": approval.revision_id,
+            "approver": approval.approver,
+            "status": approval.status.to_str(),
+            "comments": approval.comments,
+        })
+    }
 }
 
 #[cfg(test)]
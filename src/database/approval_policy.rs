@@ -0,0 +1,763 @@
+//! Approval policy module for Implexa
+//!
+//! This module provides approval policies, which decide when a revision under review counts as
+//! approved or rejected. A policy requires a quorum of approvals from the reviewers of its groups,
+//! can require approvals from specific groups, and rejects the review once a number of reviewers
+//! have rejected it. Policies apply to all reviews, or to the parts of a category or a single
+//! workflow transition; the most specific active policy wins.
+
+use std::collections::BTreeSet;
+use rusqlite::{Transaction, params, Row, Result as SqliteResult, OptionalExtension};
+use crate::database::schema::{DatabaseResult, DatabaseError};
+use crate::database::connection_manager::ConnectionManager;
+use crate::database::approval::{ApprovalManager, ApprovalStatus};
+use crate::database::group::GroupManager;
+use crate::database::workflow::WorkflowManager;
+use crate::database::workflow_engine::WorkflowEngine;
+
+/// Represents an approval policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApprovalPolicy {
+    /// Unique identifier for the policy
+    pub policy_id: Option<i64>,
+    /// Name of the policy
+    pub name: String,
+    /// Category the policy applies to, or None for all categories
+    pub category_id: Option<i64>,
+    /// Transition the policy applies to, or None for all transitions requiring approval
+    pub transition_id: Option<i64>,
+    /// Number of approvals needed
+    pub quorum: i64,
+    /// Number of rejections that reject the review
+    pub rejection_threshold: i64,
+    /// Whether the policy is active
+    pub active: bool,
+}
+
+impl ApprovalPolicy {
+    /// Create a new approval policy
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the policy
+    /// * `category_id` - Category the policy applies to, or None for all categories
+    /// * `transition_id` - Transition the policy applies to, or None for all transitions
+    /// * `quorum` - Number of approvals needed
+    /// * `rejection_threshold` - Number of rejections that reject the review
+    ///
+    /// # Returns
+    ///
+    /// A new, active ApprovalPolicy instance
+    pub fn new(
+        name: String,
+        category_id: Option<i64>,
+        transition_id: Option<i64>,
+        quorum: i64,
+        rejection_threshold: i64,
+    ) -> Self {
+        Self {
+            policy_id: None,
+            name,
+            category_id,
+            transition_id,
+            quorum,
+            rejection_threshold,
+            active: true,
+        }
+    }
+}
+
+/// Group whose members review under an approval policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApprovalPolicyGroup {
+    /// ID of the policy
+    pub policy_id: i64,
+    /// ID of the group
+    pub group_id: i64,
+    /// Name of the group
+    pub group_name: String,
+    /// Number of approvals needed from members of this group; 0 if the group is optional
+    pub min_approvals: i64,
+}
+
+/// Decision of a review
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalDecision {
+    /// The review has not reached a decision yet
+    Pending,
+    /// The review approved the revision
+    Approved,
+    /// The review rejected the revision
+    Rejected,
+}
+
+impl ApprovalDecision {
+    /// Convert an ApprovalDecision to a string
+    ///
+    /// # Returns
+    ///
+    /// The string representation of the decision
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "Pending",
+            Self::Approved => "Approved",
+            Self::Rejected => "Rejected",
+        }
+    }
+}
+
+/// Result of evaluating the approvals of a revision against its policy
+#[derive(Debug, Clone)]
+pub struct ApprovalEvaluation {
+    /// The policy that applies, or None if every listed reviewer must approve
+    pub policy: Option<ApprovalPolicy>,
+    /// Decision of the review
+    pub decision: ApprovalDecision,
+    /// Number of counted approvals
+    pub approvals: i64,
+    /// Number of counted rejections
+    pub rejections: i64,
+    /// Number of approvals needed
+    pub quorum: i64,
+    /// Names of the required groups that have not given enough approvals
+    pub missing_groups: Vec<String>,
+}
+
+/// Manager for approval policy operations
+pub struct ApprovalPolicyManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+}
+
+impl<'a> ApprovalPolicyManager<'a> {
+    /// Create a new ApprovalPolicyManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new ApprovalPolicyManager instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Create a new approval policy in the database
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The policy to create
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created policy
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the policy is invalid or could not be created
+    pub fn create_policy(&self, policy: &ApprovalPolicy) -> DatabaseResult<i64> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.create_policy_in_transaction(policy, tx)
+        })
+    }
+
+    /// Create a new approval policy in the database within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The policy to create
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created policy
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the policy is invalid or could not be created
+    pub fn create_policy_in_transaction(&self, policy: &ApprovalPolicy, tx: &Transaction) -> DatabaseResult<i64> {
+        Self::validate_policy(policy)?;
+        tx.execute(
+            "INSERT INTO ApprovalPolicies (name, category_id, transition_id, quorum, rejection_threshold, active)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                policy.name,
+                policy.category_id,
+                policy.transition_id,
+                policy.quorum,
+                policy.rejection_threshold,
+                policy.active,
+            ],
+        )?;
+        Ok(tx.last_insert_rowid())
+    }
+
+    /// Get an approval policy by ID
+    ///
+    /// # Arguments
+    ///
+    /// * `policy_id` - The ID of the policy
+    ///
+    /// # Returns
+    ///
+    /// The policy
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the policy could not be retrieved
+    pub fn get_policy(&self, policy_id: i64) -> DatabaseResult<ApprovalPolicy> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let policy = conn.query_row(
+                "SELECT policy_id, name, category_id, transition_id, quorum, rejection_threshold, active
+                 FROM ApprovalPolicies
+                 WHERE policy_id = ?1",
+                params![policy_id],
+                |row| self.row_to_policy(row),
+            )?;
+            Ok(policy)
+        })
+    }
+
+    /// Get all approval policies
+    ///
+    /// # Returns
+    ///
+    /// A vector of all policies, ordered by name
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the policies could not be retrieved
+    pub fn get_all_policies(&self) -> DatabaseResult<Vec<ApprovalPolicy>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT policy_id, name, category_id, transition_id, quorum, rejection_threshold, active
+                 FROM ApprovalPolicies
+                 ORDER BY name",
+            )?;
+            let policies = stmt
+                .query_map([], |row| self.row_to_policy(row))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(policies)
+        })
+    }
+
+    /// Update an approval policy
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - The policy to update
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the policy was successfully updated
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the policy is invalid or could not be updated
+    pub fn update_policy(&self, policy: &ApprovalPolicy) -> DatabaseResult<()> {
+        let policy_id = policy.policy_id.ok_or_else(|| {
+            DatabaseError::ValidationError("Cannot update an approval policy without an ID".to_string())
+        })?;
+        Self::validate_policy(policy)?;
+
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            conn.execute(
+                "UPDATE ApprovalPolicies
+                 SET name = ?2, category_id = ?3, transition_id = ?4, quorum = ?5, rejection_threshold = ?6, active = ?7
+                 WHERE policy_id = ?1",
+                params![
+                    policy_id,
+                    policy.name,
+                    policy.category_id,
+                    policy.transition_id,
+                    policy.quorum,
+                    policy.rejection_threshold,
+                    policy.active,
+                ],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Delete an approval policy and its groups
+    ///
+    /// # Arguments
+    ///
+    /// * `policy_id` - The ID of the policy to delete
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the policy was successfully deleted
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the policy could not be deleted
+    pub fn delete_policy(&self, policy_id: i64) -> DatabaseResult<()> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            tx.execute("DELETE FROM ApprovalPolicyGroups WHERE policy_id = ?1", params![policy_id])?;
+            tx.execute("DELETE FROM ApprovalPolicies WHERE policy_id = ?1", params![policy_id])?;
+            Ok(())
+        })
+    }
+
+    /// Add a group to an approval policy, or change how many approvals it needs
+    ///
+    /// # Arguments
+    ///
+    /// * `policy_id` - The ID of the policy
+    /// * `group_id` - The ID of the group
+    /// * `min_approvals` - Number of approvals needed from the group; 0 if the group is optional
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the group was successfully set
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the group could not be set
+    pub fn set_policy_group(&self, policy_id: i64, group_id: i64, min_approvals: i64) -> DatabaseResult<()> {
+        if min_approvals < 0 {
+            return Err(DatabaseError::ValidationError("Minimum approvals cannot be negative".to_string()));
+        }
+
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            conn.execute(
+                "INSERT INTO ApprovalPolicyGroups (policy_id, group_id, min_approvals) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(policy_id, group_id) DO UPDATE SET min_approvals = excluded.min_approvals",
+                params![policy_id, group_id, min_approvals],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Remove a group from an approval policy
+    ///
+    /// # Arguments
+    ///
+    /// * `policy_id` - The ID of the policy
+    /// * `group_id` - The ID of the group
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the group was successfully removed
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the group could not be removed
+    pub fn remove_policy_group(&self, policy_id: i64, group_id: i64) -> DatabaseResult<()> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            conn.execute(
+                "DELETE FROM ApprovalPolicyGroups WHERE policy_id = ?1 AND group_id = ?2",
+                params![policy_id, group_id],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Get the groups of an approval policy
+    ///
+    /// # Arguments
+    ///
+    /// * `policy_id` - The ID of the policy
+    ///
+    /// # Returns
+    ///
+    /// The groups of the policy, ordered by name
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the groups could not be retrieved
+    pub fn get_policy_groups(&self, policy_id: i64) -> DatabaseResult<Vec<ApprovalPolicyGroup>> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.get_policy_groups_in_transaction(policy_id, tx)
+        })
+    }
+
+    /// Get the groups of an approval policy within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `policy_id` - The ID of the policy
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The groups of the policy, ordered by name
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the groups could not be retrieved
+    pub fn get_policy_groups_in_transaction(&self, policy_id: i64, tx: &Transaction) -> DatabaseResult<Vec<ApprovalPolicyGroup>> {
+        let mut stmt = tx.prepare(
+            "SELECT pg.policy_id, pg.group_id, g.name, pg.min_approvals
+             FROM ApprovalPolicyGroups pg
+             JOIN Groups g ON g.group_id = pg.group_id
+             WHERE pg.policy_id = ?1
+             ORDER BY g.name",
+        )?;
+        let groups = stmt
+            .query_map(params![policy_id], |row| {
+                Ok(ApprovalPolicyGroup {
+                    policy_id: row.get(0)?,
+                    group_id: row.get(1)?,
+                    group_name: row.get(2)?,
+                    min_approvals: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(groups)
+    }
+
+    /// Get the reviewers of an approval policy within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `policy_id` - The ID of the policy
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The members of the policy's groups, ordered by name; empty if the policy has no groups
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the reviewers could not be retrieved
+    pub fn get_reviewers_in_transaction(&self, policy_id: i64, tx: &Transaction) -> DatabaseResult<Vec<String>> {
+        let group_manager = GroupManager::new(self.connection_manager);
+        let mut reviewers = BTreeSet::new();
+        for group in self.get_policy_groups_in_transaction(policy_id, tx)? {
+            reviewers.extend(group_manager.get_members_in_transaction(group.group_id, tx)?);
+        }
+        Ok(reviewers.into_iter().collect())
+    }
+
+    /// Find the approval policy for the review of a revision within an existing transaction
+    ///
+    /// A policy for the transition wins over a policy for the category, which wins over a policy
+    /// for all reviews. Without a transition, the first transition requiring approval out of the
+    /// revision's current workflow state is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    /// * `transition_id` - The transition being approved, if known
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The policy, or None if no active policy applies
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the policy could not be determined
+    pub fn resolve_policy_in_transaction(
+        &self,
+        revision_id: i64,
+        transition_id: Option<i64>,
+        tx: &Transaction,
+    ) -> DatabaseResult<Option<ApprovalPolicy>> {
+        let transition_id = match transition_id {
+            Some(transition_id) => Some(transition_id),
+            None => {
+                let state = WorkflowEngine::new(self.connection_manager).get_revision_state_in_transaction(revision_id, tx)?;
+                WorkflowManager::new(self.connection_manager)
+                    .get_transitions_from_state_in_transaction(state.state.state_id.unwrap_or_default(), tx)?
+                    .into_iter()
+                    .filter(|transition| transition.requires_approval)
+                    .filter_map(|transition| transition.transition_id)
+                    .min()
+            }
+        };
+
+        let category_id: Option<i64> = tx.query_row(
+            "SELECT c.category_id
+             FROM Revisions r
+             JOIN Parts p ON p.part_id = r.part_id
+             JOIN Categories c ON c.name = p.category
+             WHERE r.revision_id = ?1",
+            params![revision_id],
+            |row| row.get(0),
+        ).optional()?;
+
+        let policy = tx.query_row(
+            "SELECT policy_id, name, category_id, transition_id, quorum, rejection_threshold, active
+             FROM ApprovalPolicies
+             WHERE active = 1
+             AND (category_id IS NULL OR category_id = ?1)
+             AND (transition_id IS NULL OR transition_id = ?2)
+             ORDER BY transition_id IS NULL, category_id IS NULL, policy_id
+             LIMIT 1",
+            params![category_id, transition_id],
+            |row| self.row_to_policy(row),
+        ).optional()?;
+        Ok(policy)
+    }
+
+    /// Evaluate the approvals of a revision against its policy
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    /// * `transition_id` - The transition being approved, if known
+    ///
+    /// # Returns
+    ///
+    /// The evaluation of the review
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the approvals could not be evaluated
+    pub fn evaluate(&self, revision_id: i64, transition_id: Option<i64>) -> DatabaseResult<ApprovalEvaluation> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.evaluate_in_transaction(revision_id, transition_id, tx)
+        })
+    }
+
+    /// Evaluate the approvals of a revision against its policy within an existing transaction
+    ///
+    /// Without a policy every listed reviewer must approve and a single rejection rejects the
+    /// review. With a policy, only approvals from the reviewers of its groups count, unless the
+    /// policy has no groups.
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    /// * `transition_id` - The transition being approved, if known
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The evaluation of the review
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the approvals could not be evaluated
+    pub fn evaluate_in_transaction(
+        &self,
+        revision_id: i64,
+        transition_id: Option<i64>,
+        tx: &Transaction,
+    ) -> DatabaseResult<ApprovalEvaluation> {
+        let approvals = ApprovalManager::new(self.connection_manager).get_approvals_for_revision_in_transaction(revision_id, tx)?;
+        let count = |status: ApprovalStatus, counted: &dyn Fn(&str) -> bool| {
+            approvals
+                .iter()
+                .filter(|approval| approval.status == status && counted(&approval.approver))
+                .count() as i64
+        };
+
+        let policy = match self.resolve_policy_in_transaction(revision_id, transition_id, tx)? {
+            Some(policy) => policy,
+            None => {
+                let approved = count(ApprovalStatus::Approved, &|_| true);
+                let rejected = count(ApprovalStatus::Rejected, &|_| true);
+                let decision = if rejected > 0 {
+                    ApprovalDecision::Rejected
+                } else if !approvals.is_empty() && approved == approvals.len() as i64 {
+                    ApprovalDecision::Approved
+                } else {
+                    ApprovalDecision::Pending
+                };
+
+                return Ok(ApprovalEvaluation {
+                    policy: None,
+                    decision,
+                    approvals: approved,
+                    rejections: rejected,
+                    quorum: approvals.len() as i64,
+                    missing_groups: Vec::new(),
+                });
+            }
+        };
+
+        let policy_id = policy.policy_id.unwrap_or_default();
+        let reviewers = self.get_reviewers_in_transaction(policy_id, tx)?;
+        let in_pool = |approver: &str| reviewers.is_empty() || reviewers.iter().any(|reviewer| reviewer == approver);
+        let approved = count(ApprovalStatus::Approved, &in_pool);
+        let rejected = count(ApprovalStatus::Rejected, &in_pool);
+
+        // Required groups need enough approvals from their own members
+        let group_manager = GroupManager::new(self.connection_manager);
+        let mut missing_groups = Vec::new();
+        for group in self.get_policy_groups_in_transaction(policy_id, tx)? {
+            if group.min_approvals == 0 {
+                continue;
+            }
+            let members = group_manager.get_members_in_transaction(group.group_id, tx)?;
+            if count(ApprovalStatus::Approved, &|approver| members.iter().any(|member| member == approver)) < group.min_approvals {
+                missing_groups.push(group.group_name);
+            }
+        }
+
+        let decision = if rejected >= policy.rejection_threshold {
+            ApprovalDecision::Rejected
+        } else if approved >= policy.quorum && missing_groups.is_empty() {
+            ApprovalDecision::Approved
+        } else {
+            ApprovalDecision::Pending
+        };
+
+        Ok(ApprovalEvaluation {
+            quorum: policy.quorum,
+            policy: Some(policy),
+            decision,
+            approvals: approved,
+            rejections: rejected,
+            missing_groups,
+        })
+    }
+
+    /// Check that a policy can be satisfied
+    fn validate_policy(policy: &ApprovalPolicy) -> DatabaseResult<()> {
+        if policy.quorum < 1 {
+            return Err(DatabaseError::ValidationError(format!(
+                "Approval policy {} needs a quorum of at least 1", policy.name
+            )));
+        }
+        if policy.rejection_threshold < 1 {
+            return Err(DatabaseError::ValidationError(format!(
+                "Approval policy {} needs a rejection threshold of at least 1", policy.name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Convert a database row to an ApprovalPolicy
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The database row
+    ///
+    /// # Returns
+    ///
+    /// An ApprovalPolicy instance
+    ///
+    /// # Errors
+    ///
+    /// Returns a SqliteError if the row could not be converted
+    fn row_to_policy(&self, row: &Row) -> SqliteResult<ApprovalPolicy> {
+        Ok(ApprovalPolicy {
+            policy_id: Some(row.get(0)?),
+            name: row.get(1)?,
+            category_id: row.get(2)?,
+            transition_id: row.get(3)?,
+            quorum: row.get(4)?,
+            rejection_threshold: row.get(5)?,
+            active: row.get(6)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::DatabaseManager;
+    use crate::database::part::{Part, PartManager};
+    use crate::database::revision::{Revision, RevisionStatus, RevisionManager};
+    use crate::database::approval::Approval;
+    use crate::database::category::CategoryManager;
+    use crate::database::group::Group;
+    use crate::database::part_management::PartManagementError;
+    use std::collections::HashMap;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_approval_policy_evaluation() {
+        // Create a temporary directory for the test database
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+
+        // Create a new database manager and initialize the schema
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let connection_manager = db_manager.connection_manager();
+
+        let engine = WorkflowEngine::new(connection_manager);
+        let approval_manager = ApprovalManager::new(connection_manager);
+        let policy_manager = ApprovalPolicyManager::new(connection_manager);
+
+        // Create an electronic part with a revision in review
+        WorkflowManager::new(connection_manager).create_default_part_workflow().unwrap();
+        PartManager::new(connection_manager).create_part(&Part::new(
+            10001, "Electronic".to_string(), "PCA".to_string(), "Controller".to_string(), None,
+        )).unwrap();
+        let revision_id = RevisionManager::new(connection_manager).create_revision(&Revision::new(
+            10001, "1".to_string(), RevisionStatus::Draft, "alice".to_string(), None,
+        )).unwrap();
+        connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            engine.start_revision_in_transaction(revision_id, "alice", tx)
+        }).unwrap();
+        let submit = engine.get_available_transitions(revision_id).unwrap()[0].transition_id.unwrap();
+        engine.execute_transition(revision_id, submit, "alice").unwrap();
+
+        // Create the QA and Electrical groups
+        let group_manager = GroupManager::new(connection_manager);
+        let qa = group_manager.create_group(&Group::new("QA".to_string(), None)).unwrap();
+        let electrical = group_manager.create_group(&Group::new("Electrical".to_string(), None)).unwrap();
+        group_manager.add_member(qa, "carol").unwrap();
+        group_manager.add_member(electrical, "dave").unwrap();
+        group_manager.add_member(electrical, "erin").unwrap();
+
+        // A policy for all reviews and a stricter one for electronic parts
+        policy_manager.create_policy(&ApprovalPolicy::new("Default".to_string(), None, None, 1, 1)).unwrap();
+        let category = CategoryManager::new(connection_manager).get_category_by_code("EL").unwrap();
+        let policy_id = policy_manager.create_policy(&ApprovalPolicy::new(
+            "Electronics".to_string(), category.category_id, None, 2, 2,
+        )).unwrap();
+        policy_manager.set_policy_group(policy_id, qa, 1).unwrap();
+        policy_manager.set_policy_group(policy_id, electrical, 1).unwrap();
+        assert!(policy_manager.create_policy(&ApprovalPolicy::new("Empty".to_string(), None, None, 0, 1)).is_err());
+
+        // The category policy wins and draws its reviewers from both groups
+        let policy = connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            policy_manager.resolve_policy_in_transaction(revision_id, None, tx)
+        }).unwrap().unwrap();
+        assert_eq!(policy.policy_id, Some(policy_id));
+        let reviewers = connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            policy_manager.get_reviewers_in_transaction(policy_id, tx)
+        }).unwrap();
+        assert_eq!(reviewers, vec!["carol", "dave", "erin"]);
+
+        let mut approval_ids = HashMap::new();
+        for reviewer in ["bob", "carol", "dave", "erin"] {
+            let approval = Approval::new(revision_id, reviewer.to_string(), ApprovalStatus::Pending, None);
+            approval_ids.insert(reviewer, approval_manager.create_approval(&approval).unwrap());
+        }
+        let set_status = |reviewer: &str, status: ApprovalStatus| {
+            approval_manager.update_status(approval_ids[reviewer], status, None).unwrap();
+        };
+
+        // One rejection stays below the threshold of two
+        set_status("dave", ApprovalStatus::Rejected);
+        let evaluation = policy_manager.evaluate(revision_id, None).unwrap();
+        assert_eq!(evaluation.decision, ApprovalDecision::Pending);
+        assert_eq!(evaluation.rejections, 1);
+        set_status("erin", ApprovalStatus::Rejected);
+        assert_eq!(policy_manager.evaluate(revision_id, None).unwrap().decision, ApprovalDecision::Rejected);
+
+        // Two Electrical approvals reach the quorum, but QA has not approved and bob does not count
+        set_status("dave", ApprovalStatus::Approved);
+        set_status("erin", ApprovalStatus::Approved);
+        set_status("bob", ApprovalStatus::Approved);
+        let evaluation = policy_manager.evaluate(revision_id, None).unwrap();
+        assert_eq!(evaluation.decision, ApprovalDecision::Pending);
+        assert_eq!(evaluation.approvals, 2);
+        assert_eq!(evaluation.missing_groups, vec!["QA"]);
+        let release = engine.get_available_transitions(revision_id).unwrap()
+            .into_iter()
+            .find(|transition| transition.requires_approval)
+            .unwrap();
+        assert!(matches!(
+            engine.execute_transition(revision_id, release.transition_id.unwrap(), "carol"),
+            Err(PartManagementError::ApprovalRequired(message)) if message.contains("missing approval from QA")
+        ));
+
+        // The QA approval completes the review
+        set_status("carol", ApprovalStatus::Approved);
+        let evaluation = policy_manager.evaluate(revision_id, None).unwrap();
+        assert_eq!(evaluation.decision, ApprovalDecision::Approved);
+        assert_eq!(evaluation.approvals, 3);
+        engine.execute_transition(revision_id, release.transition_id.unwrap(), "carol").unwrap();
+    }
+}
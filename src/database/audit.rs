@@ -0,0 +1,417 @@
+//! Audit module for Implexa
+//!
+//! This module provides an append-only, tamper-evident log of metadata changes. Every entry
+//! records who changed which entity and when, and carries a SHA-256 hash over its own content
+//! and the hash of the previous entry, so editing or removing history breaks the chain.
+
+use rusqlite::{Connection, OptionalExtension, params, Row, Result as SqliteResult};
+use rusqlite::types::{ToSql, Type};
+use sha2::{Digest, Sha256};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::database::schema::{DatabaseError, DatabaseResult};
+use crate::database::connection_manager::ConnectionManager;
+
+/// User recorded in the audit log for changes not made on behalf of a signed-in user
+pub const DEFAULT_ACTOR: &str = "system";
+
+/// Hash the first entry of the chain links to
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Kind of change recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    /// The entity was created
+    Create,
+    /// The entity was updated
+    Update,
+    /// The entity was deleted
+    Delete,
+}
+
+impl AuditAction {
+    /// Convert a string to an AuditAction
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The action string
+    ///
+    /// # Returns
+    ///
+    /// The corresponding AuditAction
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(action: &str) -> Option<Self> {
+        match action {
+            "Create" => Some(Self::Create),
+            "Update" => Some(Self::Update),
+            "Delete" => Some(Self::Delete),
+            _ => None,
+        }
+    }
+
+    /// Convert an AuditAction to a string
+    ///
+    /// # Returns
+    ///
+    /// The string representation of the action
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::Create => "Create",
+            Self::Update => "Update",
+            Self::Delete => "Delete",
+        }
+    }
+}
+
+/// Represents an entry of the audit log
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    /// Position of the entry in the chain
+    pub entry_id: i64,
+    /// Date of the change
+    pub timestamp: SystemTime,
+    /// User who made the change
+    pub user: String,
+    /// Type of the changed entity, e.g. Part, Revision, Relationship, Property or Approval
+    pub entity_type: String,
+    /// ID of the changed entity
+    pub entity_id: i64,
+    /// Kind of change
+    pub action: AuditAction,
+    /// JSON description of the change
+    pub details: Option<String>,
+    /// Hash of the previous entry
+    pub previous_hash: String,
+    /// Hash of this entry
+    pub hash: String,
+}
+
+impl AuditEntry {
+    /// Compute the hash of an entry from its content and the hash of the previous entry
+    fn compute_hash(
+        previous_hash: &str,
+        timestamp: i64,
+        user: &str,
+        entity_type: &str,
+        entity_id: i64,
+        action: AuditAction,
+        details: Option<&str>,
+    ) -> String {
+        let mut hasher = Sha256::new();
+        for field in [
+            previous_hash,
+            &timestamp.to_string(),
+            user,
+            entity_type,
+            &entity_id.to_string(),
+            action.to_str(),
+            details.unwrap_or_default(),
+        ] {
+            // Prefix every field with its length so field boundaries cannot be shifted
+            hasher.update((field.len() as u64).to_be_bytes());
+            hasher.update(field.as_bytes());
+        }
+        format!("{:x}", hasher.finalize())
+    }
+}
+
+/// Filter for audit log queries; unset fields match every entry
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    /// Type of the changed entity
+    pub entity_type: Option<String>,
+    /// ID of the changed entity
+    pub entity_id: Option<i64>,
+    /// User who made the change
+    pub user: Option<String>,
+    /// Earliest date of the change, inclusive
+    pub from: Option<SystemTime>,
+    /// Latest date of the change, inclusive
+    pub to: Option<SystemTime>,
+}
+
+/// Result of verifying the audit log chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditVerification {
+    /// Number of entries checked
+    pub entries_checked: i64,
+    /// ID of the first entry whose hash or link does not match, if any
+    pub first_invalid_entry: Option<i64>,
+}
+
+impl AuditVerification {
+    /// Check whether the chain is intact
+    ///
+    /// # Returns
+    ///
+    /// true if no entry was found to be modified
+    pub fn is_valid(&self) -> bool {
+        self.first_invalid_entry.is_none()
+    }
+}
+
+/// Manager for audit log operations
+pub struct AuditManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+}
+
+impl<'a> AuditManager<'a> {
+    /// Create a new AuditManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new AuditManager instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Append a change made by a user to the audit log
+    ///
+    /// Called with the connection or transaction the change itself was made on, so the entry is
+    /// written together with the change.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - Connection or transaction the change was made on
+    /// * `user` - Username of the user who made the change
+    /// * `entity_type` - Type of the changed entity
+    /// * `entity_id` - ID of the changed entity
+    /// * `action` - Kind of change
+    /// * `details` - JSON description of the change
+    ///
+    /// # Returns
+    ///
+    /// The ID of the new entry
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the entry could not be written
+    pub fn record(
+        &self,
+        conn: &Connection,
+        user: &str,
+        entity_type: &str,
+        entity_id: i64,
+        action: AuditAction,
+        details: Option<serde_json::Value>,
+    ) -> DatabaseResult<i64> {
+        let details = details.map(|details| details.to_string());
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64;
+        let previous_hash: String = conn.query_row(
+            "SELECT hash FROM AuditLog ORDER BY entry_id DESC LIMIT 1",
+            [],
+            |row| row.get(0),
+        ).optional()?.unwrap_or_else(|| GENESIS_HASH.to_string());
+        let hash = AuditEntry::compute_hash(&previous_hash, timestamp, user, entity_type, entity_id, action, details.as_deref());
+
+        conn.execute(
+            "INSERT INTO AuditLog (timestamp, user, entity_type, entity_id, action, details, previous_hash, hash)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![timestamp, user, entity_type, entity_id, action.to_str(), details, previous_hash, hash],
+        )?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Query the audit log
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - Entity, user and time range to match
+    ///
+    /// # Returns
+    ///
+    /// The matching entries, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the entries could not be retrieved
+    pub fn query(&self, filter: &AuditFilter) -> DatabaseResult<Vec<AuditEntry>> {
+        let to_timestamp = |time: SystemTime| time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64;
+        let mut conditions = Vec::new();
+        let mut values: Vec<Box<dyn ToSql>> = Vec::new();
+        if let Some(entity_type) = &filter.entity_type {
+            values.push(Box::new(entity_type.clone()));
+            conditions.push(format!("entity_type = ?{}", values.len()));
+        }
+        if let Some(entity_id) = filter.entity_id {
+            values.push(Box::new(entity_id));
+            conditions.push(format!("entity_id = ?{}", values.len()));
+        }
+        if let Some(user) = &filter.user {
+            values.push(Box::new(user.clone()));
+            conditions.push(format!("user = ?{}", values.len()));
+        }
+        if let Some(from) = filter.from {
+            values.push(Box::new(to_timestamp(from)));
+            conditions.push(format!("timestamp >= ?{}", values.len()));
+        }
+        if let Some(to) = filter.to {
+            values.push(Box::new(to_timestamp(to)));
+            conditions.push(format!("timestamp <= ?{}", values.len()));
+        }
+
+        let mut sql = String::from(
+            "SELECT entry_id, timestamp, user, entity_type, entity_id, action, details, previous_hash, hash FROM AuditLog",
+        );
+        if !conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&conditions.join(" AND "));
+        }
+        sql.push_str(" ORDER BY entry_id");
+
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(&sql)?;
+            let entries = stmt
+                .query_map(rusqlite::params_from_iter(values.iter()), |row| self.row_to_entry(row))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(entries)
+        })
+    }
+
+    /// Verify that no entry of the audit log was modified, inserted or removed
+    ///
+    /// # Returns
+    ///
+    /// The verification result, with the first entry that breaks the chain
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the entries could not be read
+    pub fn verify(&self) -> DatabaseResult<AuditVerification> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT entry_id, timestamp, user, entity_type, entity_id, action, details, previous_hash, hash
+                 FROM AuditLog ORDER BY entry_id",
+            )?;
+            let mut rows = stmt.query([])?;
+            let mut expected_previous = GENESIS_HASH.to_string();
+            let mut entries_checked = 0;
+            while let Some(row) = rows.next()? {
+                entries_checked += 1;
+                let entry_id: i64 = row.get(0)?;
+                // An unreadable action counts as tampering rather than an error
+                let intact = match self.row_to_entry(row) {
+                    Ok(entry) => {
+                        let timestamp = entry.timestamp.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64;
+                        entry.previous_hash == expected_previous
+                            && entry.hash == AuditEntry::compute_hash(
+                                &entry.previous_hash,
+                                timestamp,
+                                &entry.user,
+                                &entry.entity_type,
+                                entry.entity_id,
+                                entry.action,
+                                entry.details.as_deref(),
+                            )
+                    }
+                    Err(_) => false,
+                };
+                if !intact {
+                    return Ok(AuditVerification {
+                        entries_checked,
+                        first_invalid_entry: Some(entry_id),
+                    });
+                }
+                expected_previous = row.get(8)?;
+            }
+            Ok(AuditVerification {
+                entries_checked,
+                first_invalid_entry: None,
+            })
+        })
+    }
+
+    /// Convert a database row to an AuditEntry
+    fn row_to_entry(&self, row: &Row) -> SqliteResult<AuditEntry> {
+        let action_str: String = row.get(5)?;
+        let action = AuditAction::from_str(&action_str)
+            .ok_or_else(|| rusqlite::Error::InvalidColumnType(5, "action".to_string(), Type::Text))?;
+
+        Ok(AuditEntry {
+            entry_id: row.get(0)?,
+            timestamp: UNIX_EPOCH + Duration::from_secs(row.get::<_, i64>(1)?.max(0) as u64),
+            user: row.get(2)?,
+            entity_type: row.get(3)?,
+            entity_id: row.get(4)?,
+            action,
+            details: row.get(6)?,
+            previous_hash: row.get(7)?,
+            hash: row.get(8)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::DatabaseManager;
+    use crate::database::part::{Part, PartManager};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_audit_log_chain_and_queries() {
+        // Create a temporary database
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let connection_manager = db_manager.connection_manager();
+
+        // Changes made through the managers are recorded for the actor of each manager, even
+        // when managers of different users share the connection
+        let alice_manager = PartManager::new(connection_manager).with_actor("alice");
+        let bob_manager = PartManager::new(connection_manager).with_actor("bob");
+        let mut part = Part::new(100001, "Electronic".to_string(), "Resistor".to_string(), "10K Resistor".to_string(), None);
+        alice_manager.create_part(&part).unwrap();
+        part.name = "10K Resistor 1%".to_string();
+        bob_manager.update_part(&part).unwrap();
+        bob_manager.delete_part(100001).unwrap();
+
+        let audit_manager = AuditManager::new(connection_manager);
+        let entries = audit_manager.query(&AuditFilter {
+            entity_type: Some("Part".to_string()),
+            entity_id: Some(100001),
+            ..AuditFilter::default()
+        }).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].action, AuditAction::Create);
+        assert_eq!(entries[0].user, "alice");
+        assert_eq!(entries[2].action, AuditAction::Delete);
+        assert_eq!(entries[1].previous_hash, entries[0].hash);
+
+        let bob = audit_manager.query(&AuditFilter {
+            user: Some("bob".to_string()),
+            ..AuditFilter::default()
+        }).unwrap();
+        assert_eq!(bob.len(), 2);
+        let future = audit_manager.query(&AuditFilter {
+            from: Some(SystemTime::now() + Duration::from_secs(3600)),
+            ..AuditFilter::default()
+        }).unwrap();
+        assert!(future.is_empty());
+
+        let verification = audit_manager.verify().unwrap();
+        assert!(verification.is_valid());
+        assert_eq!(verification.entries_checked, 3);
+
+        // The log cannot be changed through SQL
+        let update = connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            conn.execute("UPDATE AuditLog SET user = 'mallory' WHERE entry_id = ?1", params![entries[0].entry_id])?;
+            Ok(())
+        });
+        assert!(update.is_err());
+
+        // Rewriting history behind the triggers breaks the chain
+        connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            conn.execute("DROP TRIGGER audit_log_no_update", [])?;
+            conn.execute("UPDATE AuditLog SET user = 'mallory' WHERE entry_id = ?1", params![entries[1].entry_id])?;
+            Ok(())
+        }).unwrap();
+        let verification = audit_manager.verify().unwrap();
+        assert_eq!(verification.first_invalid_entry, Some(entries[1].entry_id));
+    }
+}
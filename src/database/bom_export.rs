@@ -0,0 +1,111 @@
+//! BOM export module for Implexa
+//!
+//! This module provides functionality for exporting the multi-level bill of materials of an
+//! assembly revision as CSV, together with the deviations that apply to each line.
+
+use std::io::Write;
+use crate::database::schema::DatabaseResult;
+use crate::database::connection_manager::ConnectionManager;
+use crate::database::deviation::{DeviationManager, DeviationNotice};
+use crate::database::part::PartManager;
+use crate::database::relationship::RelationshipManager;
+use crate::database::revision::RevisionManager;
+
+/// Manager for BOM export operations
+pub struct BomExportManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+}
+
+impl<'a> BomExportManager<'a> {
+    /// Create a new BomExportManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new BomExportManager instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Export the indented BOM of an assembly revision as CSV
+    ///
+    /// The first row is the assembly itself at level 0. The Deviations column lists the approved
+    /// deviations of each line; deviations that ran out of time or quantity are marked expired.
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the assembly revision
+    /// * `writer` - Destination of the CSV data
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the BOM was exported
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the BOM could not be retrieved or written
+    pub fn export_csv<W: Write>(&self, revision_id: i64, writer: W) -> DatabaseResult<()> {
+        let part_manager = PartManager::new(self.connection_manager);
+        let revision = RevisionManager::new(self.connection_manager).get_revision(revision_id)?;
+        let assembly = part_manager.get_part(revision.part_id)?;
+        let bom = RelationshipManager::new(self.connection_manager).explode_bom(revision.part_id)?;
+
+        let mut part_ids: Vec<i64> = Vec::new();
+        for line in &bom {
+            if !part_ids.contains(&line.part_id) {
+                part_ids.push(line.part_id);
+            }
+        }
+        let notices = DeviationManager::new(self.connection_manager).get_bom_deviations(revision_id, &part_ids)?;
+
+        let mut csv_writer = csv::Writer::from_writer(writer);
+        csv_writer.write_record(["Level", "Part Number", "Revision", "Name", "Quantity", "Extended Quantity", "Deviations"])?;
+        csv_writer.write_record([
+            "0".to_string(),
+            assembly.display_part_number(self.connection_manager),
+            revision.version.clone(),
+            assembly.name.clone(),
+            "1".to_string(),
+            "1".to_string(),
+            Self::format_deviations(&notices, assembly.part_id),
+        ])?;
+
+        for line in &bom {
+            let part = part_manager.get_part(line.part_id)?;
+            csv_writer.write_record([
+                line.level.to_string(),
+                part.display_part_number(self.connection_manager),
+                String::new(),
+                part.name.clone(),
+                line.quantity.to_string(),
+                line.extended_quantity.to_string(),
+                Self::format_deviations(&notices, line.part_id),
+            ])?;
+        }
+
+        csv_writer.flush()?;
+        Ok(())
+    }
+
+    /// Format the deviations of a part for the Deviations column
+    fn format_deviations(notices: &[DeviationNotice], part_id: i64) -> String {
+        notices
+            .iter()
+            .filter(|notice| notice.part_id == part_id)
+            .map(|notice| {
+                let mut text = format!("{} {}", notice.deviation.deviation_number, notice.deviation.title);
+                if notice.expired {
+                    text.push_str(" (expired)");
+                } else if let Some(remaining) = notice.deviation.remaining_quantity() {
+                    text.push_str(&format!(" ({} units left)", remaining));
+                }
+                text
+            })
+            .collect::<Vec<_>>()
+            .join("; ")
+    }
+}
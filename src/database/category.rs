@@ -5,6 +5,7 @@
 use rusqlite::{params, Row, Result as SqliteResult};
 use crate::database::schema::DatabaseResult;
 use crate::database::connection_manager::ConnectionManager;
+use crate::database::revision_scheme::RevisionScheme;
 
 /// Represents a category in the system
 #[derive(Debug, Clone)]
@@ -17,6 +18,10 @@ pub struct Category {
     pub code: String,
     /// Description of the category
     pub description: Option<String>,
+    /// Revision scheme of parts in the category
+    pub revision_scheme: RevisionScheme,
+    /// Workflow governing revisions of parts in the category, or None for the default workflow
+    pub workflow_id: Option<i64>,
 }
 
 impl Category {
@@ -37,6 +42,8 @@ impl Category {
             name,
             code,
             description,
+            revision_scheme: RevisionScheme::default(),
+            workflow_id: None,
         }
     }
 }
@@ -116,12 +123,14 @@ impl<'a> CategoryManager<'a> {
     pub fn create_category(&self, category: &Category) -> DatabaseResult<i64> {
         self.connection_manager.execute_mut(|conn| {
             conn.execute(
-                "INSERT INTO Categories (name, code, description)
-                 VALUES (?1, ?2, ?3)",
+                "INSERT INTO Categories (name, code, description, revision_scheme, workflow_id)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
                 params![
                     category.name,
                     category.code,
                     category.description,
+                    category.revision_scheme.to_str(),
+                    category.workflow_id,
                 ],
             )?;
             Ok(conn.last_insert_rowid())
@@ -144,7 +153,7 @@ impl<'a> CategoryManager<'a> {
     pub fn get_category(&self, category_id: i64) -> DatabaseResult<Category> {
         self.connection_manager.execute(|conn| {
             let category = conn.query_row(
-                "SELECT category_id, name, code, description
+                "SELECT category_id, name, code, description, revision_scheme, workflow_id
                  FROM Categories
                  WHERE category_id = ?1",
                 params![category_id],
@@ -170,7 +179,7 @@ impl<'a> CategoryManager<'a> {
     pub fn get_category_by_code(&self, code: &str) -> DatabaseResult<Category> {
         self.connection_manager.execute(|conn| {
             let category = conn.query_row(
-                "SELECT category_id, name, code, description
+                "SELECT category_id, name, code, description, revision_scheme, workflow_id
                  FROM Categories
                  WHERE code = ?1",
                 params![code],
@@ -192,7 +201,7 @@ impl<'a> CategoryManager<'a> {
     pub fn get_all_categories(&self) -> DatabaseResult<Vec<Category>> {
         self.connection_manager.execute(|conn| {
             let mut stmt = conn.prepare(
-                "SELECT category_id, name, code, description
+                "SELECT category_id, name, code, description, revision_scheme, workflow_id
                  FROM Categories
                  ORDER BY name",
             )?;
@@ -226,13 +235,15 @@ impl<'a> CategoryManager<'a> {
         self.connection_manager.execute_mut(|conn| {
             conn.execute(
                 "UPDATE Categories
-                 SET name = ?2, code = ?3, description = ?4
+                 SET name = ?2, code = ?3, description = ?4, revision_scheme = ?5, workflow_id = ?6
                  WHERE category_id = ?1",
                 params![
                     category_id,
                     category.name,
                     category.code,
                     category.description,
+                    category.revision_scheme.to_str(),
+                    category.workflow_id,
                 ],
             )?;
             Ok(())
@@ -446,11 +457,17 @@ impl<'a> CategoryManager<'a> {
     ///
     /// Returns a SqliteError if the row could not be converted
     fn row_to_category(&self, row: &Row) -> SqliteResult<Category> {
+        let scheme_str: String = row.get(4)?;
+        let revision_scheme = RevisionScheme::from_str(&scheme_str)
+            .ok_or_else(|| rusqlite::Error::InvalidColumnType(4, "revision_scheme".to_string(), rusqlite::types::Type::Text))?;
+
         Ok(Category {
             category_id: Some(row.get(0)?),
             name: row.get(1)?,
             code: row.get(2)?,
             description: row.get(3)?,
+            revision_scheme,
+            workflow_id: row.get(5)?,
         })
     }
 
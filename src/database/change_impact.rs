@@ -0,0 +1,465 @@
+//! Change impact module for Implexa
+//!
+//! This module provides functionality for analyzing the impact of a proposed revision: the
+//! assembly revisions that use the part (where-used), their states, the open change orders
+//! touching them, and whether the form-fit-function changes of the revision call for a new
+//! part number rather than a new revision. The report is attached to the review of the revision.
+
+use rusqlite::{Transaction, params, OptionalExtension};
+use serde::{Serialize, Deserialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::database::schema::{DatabaseError, DatabaseResult};
+use crate::database::connection_manager::ConnectionManager;
+use crate::database::change_order::ChangeOrderManager;
+use crate::database::obsolescence::ObsolescenceManager;
+use crate::database::part::PartManager;
+use crate::database::revision::{RevisionStatus, RevisionManager};
+
+/// Form-fit-function flags of a revision
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChangeFlags {
+    /// Whether the shape, size, weight or appearance of the part changes
+    pub form_changed: bool,
+    /// Whether the way the part interfaces or mounts changes
+    pub fit_changed: bool,
+    /// Whether what the part does changes
+    pub function_changed: bool,
+    /// Notes describing the change
+    pub notes: Option<String>,
+}
+
+impl ChangeFlags {
+    /// Check whether the change breaks interchangeability with previous revisions
+    ///
+    /// # Returns
+    ///
+    /// true if form, fit or function changes
+    pub fn breaks_interchangeability(&self) -> bool {
+        self.form_changed || self.fit_changed || self.function_changed
+    }
+}
+
+/// Recommended way to release a change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChangeRecommendation {
+    /// The change is interchangeable and can be released as a new revision
+    NewRevision,
+    /// The change is not interchangeable and needs a new part number
+    NewPartNumber,
+}
+
+impl ChangeRecommendation {
+    /// Convert a ChangeRecommendation to a string
+    ///
+    /// # Returns
+    ///
+    /// The string representation of the recommendation
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::NewRevision => "New Revision",
+            Self::NewPartNumber => "New Part Number",
+        }
+    }
+}
+
+/// Assembly revision that uses the changed part
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WhereUsedEntry {
+    /// Number of assembly levels between the assembly and the changed part, 1 for direct parents
+    pub level: i64,
+    /// ID of the assembly part
+    pub part_id: i64,
+    /// Display part number of the assembly
+    pub part_number: String,
+    /// Name of the assembly
+    pub name: String,
+    /// ID of the assembly revision
+    pub revision_id: i64,
+    /// Version of the assembly revision
+    pub version: String,
+    /// Status of the assembly revision
+    pub status: String,
+    /// Number of the open change order that contains the assembly revision, if any
+    pub open_change_order: Option<String>,
+}
+
+/// Impact report of a proposed revision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeImpactReport {
+    /// ID of the proposed revision
+    pub revision_id: i64,
+    /// ID of the changed part
+    pub part_id: i64,
+    /// Display part number of the changed part
+    pub part_number: String,
+    /// Version of the proposed revision
+    pub version: String,
+    /// Form-fit-function flags of the revision
+    pub flags: ChangeFlags,
+    /// Recommended way to release the change
+    pub recommendation: ChangeRecommendation,
+    /// Reasons for the recommendation
+    pub reasons: Vec<String>,
+    /// Non-obsolete assembly revisions that use the part, nearest first
+    pub where_used: Vec<WhereUsedEntry>,
+    /// Number of the open change order that contains the proposed revision, if any
+    pub open_change_order: Option<String>,
+    /// User the report was generated for
+    pub generated_by: String,
+    /// Date the report was generated
+    pub generated_date: SystemTime,
+}
+
+/// Manager for change impact operations
+pub struct ChangeImpactManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+}
+
+impl<'a> ChangeImpactManager<'a> {
+    /// Create a new ChangeImpactManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new ChangeImpactManager instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Set the form-fit-function flags of a revision
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    /// * `flags` - The flags of the change
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the flags were stored
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the revision is no longer a draft or the flags could not be stored
+    pub fn set_flags(&self, revision_id: i64, flags: &ChangeFlags) -> DatabaseResult<()> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let revision = RevisionManager::new(self.connection_manager).get_revision_in_transaction(revision_id, tx)?;
+            if revision.status != RevisionStatus::Draft {
+                return Err(DatabaseError::ValidationError(format!(
+                    "Change flags can only be set on draft revisions; revision {} is {}",
+                    revision.version, revision.status.to_str()
+                )));
+            }
+
+            tx.execute(
+                "INSERT INTO RevisionChangeFlags (revision_id, form_changed, fit_changed, function_changed, notes)
+                 VALUES (?1, ?2, ?3, ?4, ?5)
+                 ON CONFLICT(revision_id) DO UPDATE SET
+                     form_changed = excluded.form_changed,
+                     fit_changed = excluded.fit_changed,
+                     function_changed = excluded.function_changed,
+                     notes = excluded.notes",
+                params![revision_id, flags.form_changed, flags.fit_changed, flags.function_changed, flags.notes],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Get the form-fit-function flags of a revision
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    ///
+    /// # Returns
+    ///
+    /// The flags of the revision; all false if none were set
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the flags could not be retrieved
+    pub fn get_flags(&self, revision_id: i64) -> DatabaseResult<ChangeFlags> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.get_flags_in_transaction(revision_id, tx)
+        })
+    }
+
+    /// Get the form-fit-function flags of a revision within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The flags of the revision; all false if none were set
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the flags could not be retrieved
+    pub fn get_flags_in_transaction(&self, revision_id: i64, tx: &Transaction) -> DatabaseResult<ChangeFlags> {
+        let flags = tx.query_row(
+            "SELECT form_changed, fit_changed, function_changed, notes FROM RevisionChangeFlags WHERE revision_id = ?1",
+            params![revision_id],
+            |row| {
+                Ok(ChangeFlags {
+                    form_changed: row.get(0)?,
+                    fit_changed: row.get(1)?,
+                    function_changed: row.get(2)?,
+                    notes: row.get(3)?,
+                })
+            },
+        ).optional()?;
+        Ok(flags.unwrap_or_default())
+    }
+
+    /// Analyze the impact of a revision
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the proposed revision
+    /// * `generated_by` - The user the report is generated for
+    ///
+    /// # Returns
+    ///
+    /// The impact report
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the revision or its where-used assemblies could not be retrieved
+    pub fn analyze(&self, revision_id: i64, generated_by: &str) -> DatabaseResult<ChangeImpactReport> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.analyze_in_transaction(revision_id, generated_by, tx)
+        })
+    }
+
+    /// Analyze the impact of a revision within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the proposed revision
+    /// * `generated_by` - The user the report is generated for
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The impact report
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the revision or its where-used assemblies could not be retrieved
+    pub fn analyze_in_transaction(&self, revision_id: i64, generated_by: &str, tx: &Transaction) -> DatabaseResult<ChangeImpactReport> {
+        let part_manager = PartManager::new(self.connection_manager);
+        let change_order_manager = ChangeOrderManager::new(self.connection_manager);
+        let revision = RevisionManager::new(self.connection_manager).get_revision_in_transaction(revision_id, tx)?;
+        let part = part_manager.get_part_in_transaction(revision.part_id, tx)?;
+        let flags = self.get_flags_in_transaction(revision_id, tx)?;
+
+        // Find every assembly above the part, nearest first
+        let assemblies = ObsolescenceManager::new(self.connection_manager)
+            .get_where_used_in_transaction(revision.part_id, tx)?;
+
+        let mut where_used = Vec::new();
+        let mut revision_stmt = tx.prepare(
+            "SELECT revision_id, version, status FROM Revisions
+             WHERE part_id = ?1 AND status <> ?2
+             ORDER BY revision_id",
+        )?;
+        for (assembly_id, path) in assemblies {
+            let level = path.len() as i64 - 1;
+            let assembly = part_manager.get_part_in_transaction(assembly_id, tx)?;
+            let part_number = assembly.display_part_number_in_transaction(tx);
+            let revisions = revision_stmt
+                .query_map(params![assembly_id, RevisionStatus::Obsolete.to_str()], |row| {
+                    Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            for (assembly_revision_id, version, status) in revisions {
+                let open_change_order = change_order_manager
+                    .get_open_change_order_for_revision_in_transaction(assembly_revision_id, tx)?
+                    .map(|change_order| change_order.eco_number);
+                where_used.push(WhereUsedEntry {
+                    level,
+                    part_id: assembly_id,
+                    part_number: part_number.clone(),
+                    name: assembly.name.clone(),
+                    revision_id: assembly_revision_id,
+                    version,
+                    status,
+                    open_change_order,
+                });
+            }
+        }
+
+        // Form, fit or function changes make the part non-interchangeable
+        let mut reasons = Vec::new();
+        for (changed, aspect) in [(flags.form_changed, "Form"), (flags.fit_changed, "Fit"), (flags.function_changed, "Function")] {
+            if changed {
+                reasons.push(format!("{} changes, so the part is no longer interchangeable with previous revisions", aspect));
+            }
+        }
+        let recommendation = if flags.breaks_interchangeability() {
+            let released = where_used.iter()
+                .filter(|entry| entry.status == RevisionStatus::Released.to_str())
+                .count();
+            if released > 0 {
+                reasons.push(format!("{} released assembly revisions use the part and need to be revised to pick up the new part number", released));
+            }
+            ChangeRecommendation::NewPartNumber
+        } else {
+            reasons.push("Form, fit and function are unchanged, so the part stays interchangeable".to_string());
+            ChangeRecommendation::NewRevision
+        };
+
+        let open_change_order = change_order_manager
+            .get_open_change_order_for_revision_in_transaction(revision_id, tx)?
+            .map(|change_order| change_order.eco_number);
+
+        Ok(ChangeImpactReport {
+            revision_id,
+            part_id: part.part_id,
+            part_number: part.display_part_number_in_transaction(tx),
+            version: revision.version,
+            flags,
+            recommendation,
+            reasons,
+            where_used,
+            open_change_order,
+            generated_by: generated_by.to_string(),
+            generated_date: SystemTime::now(),
+        })
+    }
+
+    /// Analyze the impact of a revision and attach the report to its review
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision under review
+    /// * `generated_by` - The user who submitted the revision for review
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The attached report
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the report could not be generated or stored
+    pub fn attach_report_in_transaction(&self, revision_id: i64, generated_by: &str, tx: &Transaction) -> DatabaseResult<ChangeImpactReport> {
+        let report = self.analyze_in_transaction(revision_id, generated_by, tx)?;
+        tx.execute(
+            "INSERT INTO ChangeImpactReports (revision_id, generated_by, generated_date, content)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                revision_id,
+                generated_by,
+                report.generated_date.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64,
+                serde_json::to_string(&report)?,
+            ],
+        )?;
+        Ok(report)
+    }
+
+    /// Get the report attached to the latest review of a revision
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    ///
+    /// # Returns
+    ///
+    /// The attached report, or None if the revision was never submitted for review
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the report could not be retrieved
+    pub fn get_attached_report(&self, revision_id: i64) -> DatabaseResult<Option<ChangeImpactReport>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let content: Option<String> = conn.query_row(
+                "SELECT content FROM ChangeImpactReports WHERE revision_id = ?1 ORDER BY report_id DESC LIMIT 1",
+                params![revision_id],
+                |row| row.get(0),
+            ).optional()?;
+            match content {
+                Some(content) => Ok(Some(serde_json::from_str(&content)?)),
+                None => Ok(None),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::DatabaseManager;
+    use crate::database::part::Part;
+    use crate::database::revision::Revision;
+    use crate::database::relationship::{Relationship, RelationshipType, RelationshipManager};
+    use crate::database::change_order::ChangeOrder;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_change_impact_report() {
+        // Create a temporary database
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let connection_manager = db_manager.connection_manager();
+
+        // Create a resistor used on a board that is used in a controller
+        let part_manager = PartManager::new(connection_manager);
+        let revision_manager = RevisionManager::new(connection_manager);
+        let mut revision_ids = Vec::new();
+        for (part_id, subcategory, name, status) in [
+            (100001, "Resistor", "10K Resistor", RevisionStatus::Draft),
+            (100002, "PCA", "Controller Board", RevisionStatus::Released),
+            (100003, "Assembly", "Controller", RevisionStatus::Released),
+        ] {
+            part_manager.create_part(&Part::new(part_id, "Electronic".to_string(), subcategory.to_string(), name.to_string(), None)).unwrap();
+            revision_ids.push(revision_manager.create_revision(&Revision::new(part_id, "1".to_string(), status, "designer".to_string(), None)).unwrap());
+        }
+        let relationship_manager = RelationshipManager::new(connection_manager);
+        relationship_manager.create_relationship(&Relationship::new(100002, 100001, RelationshipType::Assembly, 4)).unwrap();
+        relationship_manager.create_relationship(&Relationship::new(100003, 100002, RelationshipType::Assembly, 1)).unwrap();
+
+        // A draft revision of the controller is part of an open change order
+        let controller_draft = revision_manager.create_revision(&Revision::new(100003, "2".to_string(), RevisionStatus::Draft, "designer".to_string(), None)).unwrap();
+        let change_order_manager = ChangeOrderManager::new(connection_manager);
+        let eco_id = change_order_manager.create_change_order(&ChangeOrder::new(
+            "Controller update".to_string(),
+            "New enclosure".to_string(),
+            None,
+            "designer".to_string(),
+        )).unwrap();
+        change_order_manager.add_revision(eco_id, controller_draft).unwrap();
+
+        // Without form-fit-function changes a new revision is enough
+        let impact_manager = ChangeImpactManager::new(connection_manager);
+        let report = impact_manager.analyze(revision_ids[0], "designer").unwrap();
+        assert_eq!(report.recommendation, ChangeRecommendation::NewRevision);
+        assert_eq!(report.where_used.len(), 3);
+        assert_eq!(report.where_used[0].part_id, 100002);
+        assert_eq!(report.where_used[0].level, 1);
+        assert_eq!(report.where_used[2].open_change_order.as_deref(), Some("ECO-00001"));
+
+        // A fit change needs a new part number
+        impact_manager.set_flags(revision_ids[0], &ChangeFlags {
+            fit_changed: true,
+            ..ChangeFlags::default()
+        }).unwrap();
+        let report = connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            impact_manager.attach_report_in_transaction(revision_ids[0], "designer", tx)
+        }).unwrap();
+        assert_eq!(report.recommendation, ChangeRecommendation::NewPartNumber);
+
+        // The attached report can be read back for the review
+        let attached = impact_manager.get_attached_report(revision_ids[0]).unwrap().unwrap();
+        assert!(attached.flags.fit_changed);
+        assert_eq!(attached.where_used.len(), 3);
+    }
+}
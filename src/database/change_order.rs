@@ -0,0 +1,599 @@
+//! Change order module for Implexa
+//!
+//! This module provides functionality for managing engineering change orders (ECOs). An ECO
+//! groups the revisions of several parts under a single reason and impact statement so that
+//! they are reviewed, approved and released together.
+
+use rusqlite::{Transaction, params, Row, Result as SqliteResult, OptionalExtension};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::database::schema::{DatabaseError, DatabaseResult};
+use crate::database::connection_manager::ConnectionManager;
+use crate::database::revision::{Revision, RevisionStatus, RevisionManager};
+use crate::database::approval_policy::{ApprovalPolicyManager, ApprovalDecision};
+
+/// Status of a change order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeOrderStatus {
+    /// The change order is being prepared and its revisions can be changed
+    Draft,
+    /// The revisions of the change order are being reviewed
+    InReview,
+    /// The revisions of the change order have been released
+    Released,
+    /// The change order was abandoned
+    Cancelled,
+}
+
+impl ChangeOrderStatus {
+    /// Convert a string to a ChangeOrderStatus
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The string to convert
+    ///
+    /// # Returns
+    ///
+    /// The corresponding ChangeOrderStatus, or None if the string is not a valid status
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "Draft" => Some(Self::Draft),
+            "In Review" => Some(Self::InReview),
+            "Released" => Some(Self::Released),
+            "Cancelled" => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+
+    /// Convert a ChangeOrderStatus to a string
+    ///
+    /// # Returns
+    ///
+    /// The string representation of the status
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::Draft => "Draft",
+            Self::InReview => "In Review",
+            Self::Released => "Released",
+            Self::Cancelled => "Cancelled",
+        }
+    }
+
+    /// Check whether a change order with this status still claims its revisions
+    ///
+    /// # Returns
+    ///
+    /// true if the change order is neither released nor cancelled
+    pub fn is_open(&self) -> bool {
+        matches!(self, Self::Draft | Self::InReview)
+    }
+}
+
+/// Represents an engineering change order
+#[derive(Debug, Clone)]
+pub struct ChangeOrder {
+    /// Unique identifier for the change order
+    pub eco_id: Option<i64>,
+    /// Change order number, e.g. ECO-00042; assigned when the change order is created
+    pub eco_number: String,
+    /// Short title of the change
+    pub title: String,
+    /// Reason for the change
+    pub reason: String,
+    /// Impact of the change on production, stock and field units
+    pub impact: Option<String>,
+    /// Status of the change order
+    pub status: ChangeOrderStatus,
+    /// User who created the change order
+    pub created_by: String,
+    /// Date the change order was created
+    pub created_date: SystemTime,
+    /// Date the change order was released
+    pub released_date: Option<SystemTime>,
+    /// Git commit hash of the release merge
+    pub commit_hash: Option<String>,
+}
+
+impl ChangeOrder {
+    /// Create a new change order
+    ///
+    /// # Arguments
+    ///
+    /// * `title` - Short title of the change
+    /// * `reason` - Reason for the change
+    /// * `impact` - Impact of the change
+    /// * `created_by` - User who created the change order
+    ///
+    /// # Returns
+    ///
+    /// A new ChangeOrder instance in Draft status
+    pub fn new(title: String, reason: String, impact: Option<String>, created_by: String) -> Self {
+        Self {
+            eco_id: None,
+            eco_number: String::new(),
+            title,
+            reason,
+            impact,
+            status: ChangeOrderStatus::Draft,
+            created_by,
+            created_date: SystemTime::now(),
+            released_date: None,
+            commit_hash: None,
+        }
+    }
+}
+
+/// Manager for change order operations
+pub struct ChangeOrderManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+}
+
+impl<'a> ChangeOrderManager<'a> {
+    /// Create a new ChangeOrderManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new ChangeOrderManager instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Create a new change order and assign it the next change order number
+    ///
+    /// # Arguments
+    ///
+    /// * `change_order` - The change order to create
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created change order
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the change order could not be created
+    pub fn create_change_order(&self, change_order: &ChangeOrder) -> DatabaseResult<i64> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.create_change_order_in_transaction(change_order, tx)
+        })
+    }
+
+    /// Create a new change order within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `change_order` - The change order to create
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created change order
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the change order is invalid or could not be created
+    pub fn create_change_order_in_transaction(&self, change_order: &ChangeOrder, tx: &Transaction) -> DatabaseResult<i64> {
+        if change_order.title.trim().is_empty() {
+            return Err(DatabaseError::ValidationError("Change order title cannot be empty".to_string()));
+        }
+        if change_order.reason.trim().is_empty() {
+            return Err(DatabaseError::ValidationError("Change order reason cannot be empty".to_string()));
+        }
+
+        let next_number: i64 = tx.query_row(
+            "SELECT COALESCE(MAX(eco_id), 0) + 1 FROM ChangeOrders",
+            [],
+            |row| row.get(0),
+        )?;
+        let eco_number = format!("ECO-{:05}", next_number);
+
+        tx.execute(
+            "INSERT INTO ChangeOrders (eco_number, title, reason, impact, status, created_by, created_date)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                eco_number,
+                change_order.title,
+                change_order.reason,
+                change_order.impact,
+                ChangeOrderStatus::Draft.to_str(),
+                change_order.created_by,
+                change_order.created_date.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64,
+            ],
+        )?;
+        Ok(tx.last_insert_rowid())
+    }
+
+    /// Get a change order by ID
+    ///
+    /// # Arguments
+    ///
+    /// * `eco_id` - The ID of the change order
+    ///
+    /// # Returns
+    ///
+    /// The change order
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the change order could not be retrieved
+    pub fn get_change_order(&self, eco_id: i64) -> DatabaseResult<ChangeOrder> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.get_change_order_in_transaction(eco_id, tx)
+        })
+    }
+
+    /// Get a change order by ID within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `eco_id` - The ID of the change order
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The change order
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the change order could not be retrieved
+    pub fn get_change_order_in_transaction(&self, eco_id: i64, tx: &Transaction) -> DatabaseResult<ChangeOrder> {
+        let change_order = tx.query_row(
+            "SELECT eco_id, eco_number, title, reason, impact, status, created_by, created_date, released_date, commit_hash
+             FROM ChangeOrders WHERE eco_id = ?1",
+            params![eco_id],
+            |row| self.row_to_change_order(row),
+        )?;
+        Ok(change_order)
+    }
+
+    /// Get all change orders
+    ///
+    /// # Returns
+    ///
+    /// A vector of all change orders, newest first
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the change orders could not be retrieved
+    pub fn get_all_change_orders(&self) -> DatabaseResult<Vec<ChangeOrder>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT eco_id, eco_number, title, reason, impact, status, created_by, created_date, released_date, commit_hash
+                 FROM ChangeOrders ORDER BY eco_id DESC",
+            )?;
+            let change_orders = stmt
+                .query_map([], |row| self.row_to_change_order(row))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(change_orders)
+        })
+    }
+
+    /// Update the title, reason and impact of a draft change order
+    ///
+    /// # Arguments
+    ///
+    /// * `change_order` - The change order with updated fields
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the change order was successfully updated
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the change order is not in Draft status or could not be updated
+    pub fn update_change_order(&self, change_order: &ChangeOrder) -> DatabaseResult<()> {
+        let eco_id = change_order.eco_id.ok_or_else(|| {
+            DatabaseError::ValidationError("Change order ID is required for update".to_string())
+        })?;
+
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let existing = self.get_change_order_in_transaction(eco_id, tx)?;
+            if existing.status != ChangeOrderStatus::Draft {
+                return Err(DatabaseError::ValidationError(format!(
+                    "{} is {} and can no longer be edited", existing.eco_number, existing.status.to_str()
+                )));
+            }
+            if change_order.title.trim().is_empty() || change_order.reason.trim().is_empty() {
+                return Err(DatabaseError::ValidationError("Change order title and reason cannot be empty".to_string()));
+            }
+
+            tx.execute(
+                "UPDATE ChangeOrders SET title = ?1, reason = ?2, impact = ?3 WHERE eco_id = ?4",
+                params![change_order.title, change_order.reason, change_order.impact, eco_id],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Update the status of a change order within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `eco_id` - The ID of the change order
+    /// * `status` - The new status
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the status was successfully updated
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the status could not be updated
+    pub fn update_status_in_transaction(&self, eco_id: i64, status: ChangeOrderStatus, tx: &Transaction) -> DatabaseResult<()> {
+        tx.execute(
+            "UPDATE ChangeOrders SET status = ?1 WHERE eco_id = ?2",
+            params![status.to_str(), eco_id],
+        )?;
+        Ok(())
+    }
+
+    /// Record the release of a change order within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `eco_id` - The ID of the change order
+    /// * `commit_hash` - Git commit hash of the release merge
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the release was successfully recorded
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the release could not be recorded
+    pub fn record_release_in_transaction(&self, eco_id: i64, commit_hash: &str, tx: &Transaction) -> DatabaseResult<()> {
+        let released_date = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64;
+        tx.execute(
+            "UPDATE ChangeOrders SET status = ?1, released_date = ?2, commit_hash = ?3 WHERE eco_id = ?4",
+            params![ChangeOrderStatus::Released.to_str(), released_date, commit_hash, eco_id],
+        )?;
+        Ok(())
+    }
+
+    /// Add a draft revision to a draft change order
+    ///
+    /// # Arguments
+    ///
+    /// * `eco_id` - The ID of the change order
+    /// * `revision_id` - The ID of the revision
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the revision was added
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the change order or revision is not in Draft status, the
+    /// revision already belongs to another open change order or it could not be added
+    pub fn add_revision(&self, eco_id: i64, revision_id: i64) -> DatabaseResult<()> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let change_order = self.get_change_order_in_transaction(eco_id, tx)?;
+            if change_order.status != ChangeOrderStatus::Draft {
+                return Err(DatabaseError::ValidationError(format!(
+                    "Revisions can only be added to {} while it is a draft", change_order.eco_number
+                )));
+            }
+
+            let revision = RevisionManager::new(self.connection_manager).get_revision_in_transaction(revision_id, tx)?;
+            if revision.status != RevisionStatus::Draft {
+                return Err(DatabaseError::ValidationError(format!(
+                    "Only draft revisions can be added to a change order; revision {} is {}",
+                    revision.version, revision.status.to_str()
+                )));
+            }
+
+            if let Some(other) = self.get_open_change_order_for_revision_in_transaction(revision_id, tx)? {
+                if other.eco_id != Some(eco_id) {
+                    return Err(DatabaseError::ValidationError(format!(
+                        "Revision {} already belongs to {}", revision.version, other.eco_number
+                    )));
+                }
+                return Ok(());
+            }
+
+            tx.execute(
+                "INSERT OR IGNORE INTO ChangeOrderRevisions (eco_id, revision_id) VALUES (?1, ?2)",
+                params![eco_id, revision_id],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Remove a revision from a draft change order
+    ///
+    /// # Arguments
+    ///
+    /// * `eco_id` - The ID of the change order
+    /// * `revision_id` - The ID of the revision
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the revision was removed
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the change order is not in Draft status or the revision could
+    /// not be removed
+    pub fn remove_revision(&self, eco_id: i64, revision_id: i64) -> DatabaseResult<()> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let change_order = self.get_change_order_in_transaction(eco_id, tx)?;
+            if change_order.status != ChangeOrderStatus::Draft {
+                return Err(DatabaseError::ValidationError(format!(
+                    "Revisions can only be removed from {} while it is a draft", change_order.eco_number
+                )));
+            }
+
+            tx.execute(
+                "DELETE FROM ChangeOrderRevisions WHERE eco_id = ?1 AND revision_id = ?2",
+                params![eco_id, revision_id],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Get the revisions of a change order
+    ///
+    /// # Arguments
+    ///
+    /// * `eco_id` - The ID of the change order
+    ///
+    /// # Returns
+    ///
+    /// The revisions of the change order, in the order they were added
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the revisions could not be retrieved
+    pub fn get_revisions(&self, eco_id: i64) -> DatabaseResult<Vec<Revision>> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.get_revisions_in_transaction(eco_id, tx)
+        })
+    }
+
+    /// Get the revisions of a change order within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `eco_id` - The ID of the change order
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The revisions of the change order, in the order they were added
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the revisions could not be retrieved
+    pub fn get_revisions_in_transaction(&self, eco_id: i64, tx: &Transaction) -> DatabaseResult<Vec<Revision>> {
+        let mut stmt = tx.prepare("SELECT revision_id FROM ChangeOrderRevisions WHERE eco_id = ?1 ORDER BY rowid")?;
+        let revision_ids = stmt
+            .query_map(params![eco_id], |row| row.get(0))?
+            .collect::<Result<Vec<i64>, _>>()?;
+
+        let revision_manager = RevisionManager::new(self.connection_manager);
+        revision_ids
+            .into_iter()
+            .map(|revision_id| revision_manager.get_revision_in_transaction(revision_id, tx))
+            .collect()
+    }
+
+    /// Get the open change order a revision belongs to within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The draft or in-review change order of the revision, or None if it has none
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the change order could not be retrieved
+    pub fn get_open_change_order_for_revision_in_transaction(&self, revision_id: i64, tx: &Transaction) -> DatabaseResult<Option<ChangeOrder>> {
+        let change_order = tx.query_row(
+            "SELECT c.eco_id, c.eco_number, c.title, c.reason, c.impact, c.status, c.created_by, c.created_date, c.released_date, c.commit_hash
+             FROM ChangeOrders c
+             JOIN ChangeOrderRevisions r ON r.eco_id = c.eco_id
+             WHERE r.revision_id = ?1 AND c.status IN (?2, ?3)",
+            params![revision_id, ChangeOrderStatus::Draft.to_str(), ChangeOrderStatus::InReview.to_str()],
+            |row| self.row_to_change_order(row),
+        ).optional()?;
+        Ok(change_order)
+    }
+
+    /// Evaluate the review of a change order
+    ///
+    /// # Arguments
+    ///
+    /// * `eco_id` - The ID of the change order
+    ///
+    /// # Returns
+    ///
+    /// Approved once every revision is approved under its approval policy, Rejected as soon as
+    /// one of them is rejected, and Pending otherwise
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the approvals could not be evaluated
+    pub fn evaluate(&self, eco_id: i64) -> DatabaseResult<ApprovalDecision> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.evaluate_in_transaction(eco_id, tx)
+        })
+    }
+
+    /// Evaluate the review of a change order within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `eco_id` - The ID of the change order
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The combined decision of the reviews of the revisions
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the approvals could not be evaluated
+    pub fn evaluate_in_transaction(&self, eco_id: i64, tx: &Transaction) -> DatabaseResult<ApprovalDecision> {
+        let revisions = self.get_revisions_in_transaction(eco_id, tx)?;
+        if revisions.is_empty() {
+            return Ok(ApprovalDecision::Pending);
+        }
+
+        let policy_manager = ApprovalPolicyManager::new(self.connection_manager);
+        let mut decision = ApprovalDecision::Approved;
+        for revision in revisions {
+            let evaluation = policy_manager.evaluate_in_transaction(revision.revision_id.unwrap_or_default(), None, tx)?;
+            match evaluation.decision {
+                ApprovalDecision::Rejected => return Ok(ApprovalDecision::Rejected),
+                ApprovalDecision::Pending => decision = ApprovalDecision::Pending,
+                ApprovalDecision::Approved => {}
+            }
+        }
+        Ok(decision)
+    }
+
+    /// Convert a database row to a ChangeOrder
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The database row
+    ///
+    /// # Returns
+    ///
+    /// A ChangeOrder instance
+    ///
+    /// # Errors
+    ///
+    /// Returns a SqliteError if the row could not be converted
+    fn row_to_change_order(&self, row: &Row) -> SqliteResult<ChangeOrder> {
+        let status_str: String = row.get(5)?;
+        let status = ChangeOrderStatus::from_str(&status_str).ok_or_else(|| {
+            rusqlite::Error::InvalidColumnType(5, "status".to_string(), rusqlite::types::Type::Text)
+        })?;
+        let created_secs: i64 = row.get(7)?;
+        let released_secs: Option<i64> = row.get(8)?;
+
+        Ok(ChangeOrder {
+            eco_id: Some(row.get(0)?),
+            eco_number: row.get(1)?,
+            title: row.get(2)?,
+            reason: row.get(3)?,
+            impact: row.get(4)?,
+            status,
+            created_by: row.get(6)?,
+            created_date: UNIX_EPOCH + std::time::Duration::from_secs(created_secs as u64),
+            released_date: released_secs.map(|secs| UNIX_EPOCH + std::time::Duration::from_secs(secs as u64)),
+            commit_hash: row.get(9)?,
+        })
+    }
+}
@@ -0,0 +1,433 @@
+//! Clear-to-build module for Implexa
+//!
+//! This module provides functionality for checking whether an assembly can be built from
+//! the stock on hand. It nets available stock through the whole multi-level BOM, falls back
+//! to alternate parts, applies per-line attrition and reports shortages together with the
+//! maximum buildable quantity.
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use crate::database::schema::{DatabaseError, DatabaseResult};
+use crate::database::connection_manager::ConnectionManager;
+use crate::database::inventory::InventoryManager;
+use crate::database::relationship::{Relationship, RelationshipManager, RelationshipType};
+use crate::database::revision::RevisionManager;
+use crate::database::deviation::{DeviationManager, DeviationNotice};
+
+/// Upper limit for the maximum buildable search, above which the quantity is reported as unlimited
+const MAX_BUILDABLE_LIMIT: i64 = 1 << 40;
+
+/// Stock of an alternate part allocated to a BOM line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlternateAllocation {
+    /// ID of the alternate part
+    pub part_id: i64,
+    /// Quantity allocated from the alternate part's stock
+    pub quantity: i64,
+}
+
+/// Allocation of one BOM line for the build
+#[derive(Debug, Clone)]
+pub struct ClearToBuildLine {
+    /// Depth of the line in the BOM, starting at 1 for direct children
+    pub level: usize,
+    /// ID of the parent part
+    pub parent_part_id: i64,
+    /// ID of the child part
+    pub part_id: i64,
+    /// Quantity of the child per parent
+    pub quantity_per: i64,
+    /// Attrition/scrap factor of the line
+    pub attrition: f64,
+    /// Quantity needed for this line including attrition
+    pub gross_quantity: i64,
+    /// Quantity allocated from the child part's own stock
+    pub from_stock: i64,
+    /// Quantities allocated from alternate parts
+    pub alternates: Vec<AlternateAllocation>,
+    /// Quantity of a sub-assembly that has to be built from its own BOM
+    pub to_build: i64,
+    /// Quantity of a purchased part that is not covered by stock
+    pub shortage: i64,
+}
+
+/// Shortage of a purchased part across the whole build
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shortage {
+    /// ID of the part
+    pub part_id: i64,
+    /// Quantity required across all BOM lines
+    pub required: i64,
+    /// Quantity available in stock before the build
+    pub available: i64,
+    /// Quantity missing after stock and alternates are allocated
+    pub shortage: i64,
+}
+
+/// Clear-to-build report of an assembly revision
+#[derive(Debug, Clone)]
+pub struct ClearToBuildReport {
+    /// ID of the assembly revision
+    pub revision_id: i64,
+    /// ID of the assembly part
+    pub part_id: i64,
+    /// Version of the assembly revision
+    pub version: String,
+    /// Number of assemblies to build
+    pub build_quantity: i64,
+    /// Whether the build quantity is fully covered by stock
+    pub clear_to_build: bool,
+    /// Allocation of every BOM line, depth-first
+    pub lines: Vec<ClearToBuildLine>,
+    /// Shortages of purchased parts
+    pub shortages: Vec<Shortage>,
+    /// Maximum quantity buildable from stock, or None if the BOM has no stocked parts
+    pub max_buildable: Option<i64>,
+    /// Approved deviations of the assembly and its BOM parts; expired ones are flagged
+    pub deviations: Vec<DeviationNotice>,
+}
+
+/// BOM structure, alternates and available stock loaded for an analysis
+struct BuildData {
+    /// Assembly lines per parent part
+    children: HashMap<i64, Vec<Relationship>>,
+    /// Alternate parts per part, in order of preference
+    alternates: HashMap<i64, Vec<i64>>,
+    /// Available (unreserved) stock per part
+    stock: HashMap<i64, i64>,
+}
+
+/// Running state of one build simulation
+struct Simulation<'d> {
+    /// Data the simulation runs on
+    data: &'d BuildData,
+    /// Stock that has not been allocated yet
+    pool: HashMap<i64, i64>,
+    /// Allocated BOM lines
+    lines: Vec<ClearToBuildLine>,
+    /// Shortages per part, in first-seen order
+    shortages: Vec<Shortage>,
+}
+
+impl<'d> Simulation<'d> {
+    /// Create a simulation with the full stock available
+    fn new(data: &'d BuildData) -> Self {
+        Self {
+            data,
+            pool: data.stock.clone(),
+            lines: Vec::new(),
+            shortages: Vec::new(),
+        }
+    }
+
+    /// Take up to a quantity of a part from the unallocated stock
+    fn take(&mut self, part_id: i64, quantity: i64) -> i64 {
+        let available = self.pool.entry(part_id).or_insert(0);
+        let taken = quantity.min(*available).max(0);
+        *available -= taken;
+        taken
+    }
+
+    /// Allocate the children of an assembly for a number of units
+    fn build(&mut self, part_id: i64, quantity: i64, level: usize) {
+        let data = self.data;
+        let Some(children) = data.children.get(&part_id) else {
+            return;
+        };
+
+        for relationship in children {
+            let child_id = relationship.child_part_id;
+            let gross_quantity = relationship.gross_quantity(quantity);
+            let from_stock = self.take(child_id, gross_quantity);
+            let mut remaining = gross_quantity - from_stock;
+
+            let mut alternates = Vec::new();
+            for &alternate_id in data.alternates.get(&child_id).into_iter().flatten() {
+                if remaining == 0 {
+                    break;
+                }
+                let taken = self.take(alternate_id, remaining);
+                if taken > 0 {
+                    remaining -= taken;
+                    alternates.push(AlternateAllocation { part_id: alternate_id, quantity: taken });
+                }
+            }
+
+            // Missing sub-assemblies are built from their own BOM; missing purchased parts are short
+            let is_assembly = data.children.contains_key(&child_id);
+            let (to_build, shortage) = if is_assembly { (remaining, 0) } else { (0, remaining) };
+
+            self.lines.push(ClearToBuildLine {
+                level,
+                parent_part_id: part_id,
+                part_id: child_id,
+                quantity_per: relationship.quantity,
+                attrition: relationship.attrition,
+                gross_quantity,
+                from_stock,
+                alternates,
+                to_build,
+                shortage,
+            });
+
+            if to_build > 0 {
+                self.build(child_id, to_build, level + 1);
+            }
+            if !is_assembly {
+                self.record_requirement(child_id, gross_quantity, shortage);
+            }
+        }
+    }
+
+    /// Add the requirement of a purchased part to the shortage list
+    fn record_requirement(&mut self, part_id: i64, required: i64, shortage: i64) {
+        match self.shortages.iter_mut().find(|entry| entry.part_id == part_id) {
+            Some(entry) => {
+                entry.required += required;
+                entry.shortage += shortage;
+            }
+            None => self.shortages.push(Shortage {
+                part_id,
+                required,
+                available: self.data.stock.get(&part_id).copied().unwrap_or(0),
+                shortage,
+            }),
+        }
+    }
+
+    /// Check whether the simulated build has no shortages
+    fn is_clear(&self) -> bool {
+        self.shortages.iter().all(|entry| entry.shortage == 0)
+    }
+}
+
+/// Manager for clear-to-build analysis
+pub struct ClearToBuildManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+}
+
+impl<'a> ClearToBuildManager<'a> {
+    /// Create a new ClearToBuildManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new ClearToBuildManager instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Analyze whether an assembly revision can be built from available stock
+    ///
+    /// Stock of each BOM line is allocated first from the part itself and then from its
+    /// alternates (parts linked to it with an Alternate relationship). Sub-assemblies that are
+    /// not in stock are built from their own BOM. Reserved stock is never allocated.
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the assembly revision
+    /// * `build_quantity` - The number of assemblies to build
+    ///
+    /// # Returns
+    ///
+    /// The clear-to-build report
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the revision, BOM or stock levels could not be retrieved
+    pub fn get_clear_to_build(&self, revision_id: i64, build_quantity: i64) -> DatabaseResult<ClearToBuildReport> {
+        if build_quantity < 1 {
+            return Err(DatabaseError::ValidationError("Build quantity must be at least 1".to_string()));
+        }
+
+        let revision = RevisionManager::new(self.connection_manager).get_revision(revision_id)?;
+        let data = self.load_build_data(revision.part_id)?;
+
+        let mut simulation = Simulation::new(&data);
+        simulation.build(revision.part_id, build_quantity, 1);
+        let clear_to_build = simulation.is_clear();
+        let Simulation { lines, mut shortages, .. } = simulation;
+        shortages.retain(|entry| entry.shortage > 0);
+
+        // Report the deviations that apply to the build
+        let mut part_ids: Vec<i64> = Vec::new();
+        for line in &lines {
+            if !part_ids.contains(&line.part_id) {
+                part_ids.push(line.part_id);
+            }
+        }
+        let deviations = DeviationManager::new(self.connection_manager).get_bom_deviations(revision_id, &part_ids)?;
+
+        Ok(ClearToBuildReport {
+            revision_id,
+            part_id: revision.part_id,
+            version: revision.version,
+            build_quantity,
+            clear_to_build,
+            lines,
+            shortages,
+            max_buildable: Self::max_buildable(&data, revision.part_id),
+            deviations,
+        })
+    }
+
+    /// Find the largest build quantity without shortages
+    fn max_buildable(data: &BuildData, part_id: i64) -> Option<i64> {
+        let is_clear = |quantity: i64| {
+            let mut simulation = Simulation::new(data);
+            simulation.build(part_id, quantity, 1);
+            simulation.is_clear()
+        };
+
+        // Grow the upper bound until a shortage appears
+        let mut low = 0;
+        let mut high = 1;
+        while is_clear(high) {
+            if high >= MAX_BUILDABLE_LIMIT {
+                return None;
+            }
+            low = high;
+            high *= 2;
+        }
+
+        // Invariant: low is buildable, high is not
+        while high - low > 1 {
+            let middle = low + (high - low) / 2;
+            if is_clear(middle) {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        Some(low)
+    }
+
+    /// Load the BOM, alternates and stock levels below a part
+    fn load_build_data(&self, part_id: i64) -> DatabaseResult<BuildData> {
+        let relationship_manager = RelationshipManager::new(self.connection_manager);
+        let inventory_manager = InventoryManager::new(self.connection_manager);
+
+        // Exploding the BOM first rejects circular structures
+        let bom = relationship_manager.explode_bom(part_id)?;
+
+        let mut data = BuildData {
+            children: HashMap::new(),
+            alternates: HashMap::new(),
+            stock: HashMap::new(),
+        };
+
+        let mut parents = vec![part_id];
+        parents.extend(bom.iter().filter(|line| !line.is_leaf).map(|line| line.part_id));
+        for parent_id in parents {
+            if data.children.contains_key(&parent_id) {
+                continue;
+            }
+            let children: Vec<Relationship> = relationship_manager
+                .get_child_relationships(&parent_id.to_string())?
+                .into_iter()
+                .filter(|relationship| relationship.relationship_type == RelationshipType::Assembly)
+                .collect();
+            data.children.insert(parent_id, children);
+        }
+
+        for line in &bom {
+            if data.alternates.contains_key(&line.part_id) {
+                continue;
+            }
+            let alternates: Vec<i64> = relationship_manager
+                .get_parent_relationships(&line.part_id.to_string())?
+                .into_iter()
+                .filter(|relationship| relationship.relationship_type == RelationshipType::Alternate)
+                .map(|relationship| relationship.parent_part_id)
+                .collect();
+
+            for &stocked_id in std::iter::once(&line.part_id).chain(alternates.iter()) {
+                if let Entry::Vacant(entry) = data.stock.entry(stocked_id) {
+                    entry.insert(inventory_manager.get_stock_level(stocked_id)?.quantity_available());
+                }
+            }
+            data.alternates.insert(line.part_id, alternates);
+        }
+
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::DatabaseManager;
+    use crate::database::part::{Part, PartManager};
+    use crate::database::revision::{Revision, RevisionStatus};
+    use crate::database::inventory::StockLocation;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_clear_to_build_with_alternates_and_attrition() {
+        // Create a temporary directory for the test database
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+
+        // Create a new database manager and initialize the schema
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let connection_manager = db_manager.connection_manager();
+
+        let part_manager = PartManager::new(connection_manager);
+        let relationship_manager = RelationshipManager::new(connection_manager);
+        let revision_manager = RevisionManager::new(connection_manager);
+        let inventory_manager = InventoryManager::new(connection_manager);
+        let ctb_manager = ClearToBuildManager::new(connection_manager);
+
+        // Unit 10003 contains 1 board 10002; each board contains 10 resistors 10001 (10% attrition)
+        // and 1 IC 10004, which can be replaced by 10005
+        for (part_id, subcategory, name) in [
+            (10001, "Resistor", "10K Resistor"),
+            (10002, "PCA", "Controller Board"),
+            (10003, "Assembly", "Controller Unit"),
+            (10004, "Integrated Circuit", "MCU"),
+            (10005, "Integrated Circuit", "MCU (second source)"),
+        ] {
+            let part = Part::new(part_id, "Electronic".to_string(), subcategory.to_string(), name.to_string(), None);
+            part_manager.create_part(&part).unwrap();
+        }
+        relationship_manager.create_relationship(&Relationship::new(10003, 10002, RelationshipType::Assembly, 1)).unwrap();
+        let mut resistor_line = Relationship::new(10002, 10001, RelationshipType::Assembly, 10);
+        resistor_line.attrition = 0.1;
+        relationship_manager.create_relationship(&resistor_line).unwrap();
+        relationship_manager.create_relationship(&Relationship::new(10002, 10004, RelationshipType::Assembly, 1)).unwrap();
+        relationship_manager.create_relationship(&Relationship::new(10005, 10004, RelationshipType::Alternate, 1)).unwrap();
+        let revision_id = revision_manager.create_revision(&Revision::new(
+            10003, "1".to_string(), RevisionStatus::Released, "test".to_string(), None,
+        )).unwrap();
+
+        // Stock: 2 finished boards, 100 resistors, 3 MCUs and 2 second-source MCUs
+        let stores = inventory_manager.create_location(&StockLocation::new("Stores".to_string(), None)).unwrap();
+        inventory_manager.receive(10002, stores, 2, None, "test").unwrap();
+        inventory_manager.receive(10001, stores, 100, None, "test").unwrap();
+        inventory_manager.receive(10004, stores, 3, None, "test").unwrap();
+        inventory_manager.receive(10005, stores, 2, None, "test").unwrap();
+
+        // 5 units: 2 boards from stock, 3 built needing 33 resistors and 3 MCUs
+        let report = ctb_manager.get_clear_to_build(revision_id, 5).unwrap();
+        assert!(report.clear_to_build);
+        assert_eq!(report.lines[0].from_stock, 2);
+        assert_eq!(report.lines[0].to_build, 3);
+        assert_eq!(report.lines[1].gross_quantity, 33);
+
+        // 10 units: 8 boards built need 88 resistors and 8 MCUs, of which only 5 are available
+        let report = ctb_manager.get_clear_to_build(revision_id, 10).unwrap();
+        assert!(!report.clear_to_build);
+        let mcu_line = report.lines.iter().find(|line| line.part_id == 10004).unwrap();
+        assert_eq!(mcu_line.from_stock, 3);
+        assert_eq!(mcu_line.alternates, vec![AlternateAllocation { part_id: 10005, quantity: 2 }]);
+        assert_eq!(report.shortages, vec![Shortage { part_id: 10004, required: 8, available: 3, shortage: 3 }]);
+
+        // MCUs limit the build to 2 boards from stock plus 5 built boards
+        assert_eq!(report.max_buildable, Some(7));
+    }
+}
@@ -85,6 +85,28 @@ impl ConnectionManager {
         }
     }
 
+    /// Replace the connection with a connection to another database file
+    ///
+    /// All clones of this ConnectionManager share the connection, so they use the new database
+    /// from then on.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_path` - Path to the SQLite database file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection cannot be created or the lock cannot be acquired
+    pub fn reopen(&self, db_path: &Path) -> Result<(), ConnectionManagerError> {
+        let connection = Connection::open(db_path)?;
+        connection.execute_batch("PRAGMA journal_mode=WAL")?;
+
+        let mut conn = self.connection.lock()
+            .map_err(|e| ConnectionManagerError::LockError(e.to_string()))?;
+        *conn = connection;
+        Ok(())
+    }
+
     /// Execute a read-only operation on the database connection
     ///
     /// # Arguments
@@ -196,16 +218,25 @@ impl ConnectionManager {
 
 #[cfg(test)]
 pub mod test_utils {
-    use super::*;
+    
     use std::sync::{Arc, Mutex};
     use rusqlite::{Connection, Transaction};
     use std::collections::HashMap;
 
+    /// A row of mock data, keyed by column name
+    type MockRow = HashMap<String, rusqlite::types::Value>;
+
     /// Mock connection manager for testing
     pub struct MockConnectionManager {
         /// Mock data storage
         #[allow(dead_code)]
-        data: Arc<Mutex<HashMap<String, Vec<HashMap<String, rusqlite::types::Value>>>>>,
+        data: Arc<Mutex<HashMap<String, Vec<MockRow>>>>,
+    }
+
+    impl Default for MockConnectionManager {
+        fn default() -> Self {
+            Self::new()
+        }
     }
 
     impl MockConnectionManager {
@@ -323,19 +354,16 @@ pub mod tests {
         // Create a test table
         conn_manager.execute_mut(|conn| {
             conn.execute("CREATE TABLE test (id INTEGER PRIMARY KEY, value TEXT)", [])
-                .map_err(|e| e)
         }).expect("Failed to create table");
         
         // Insert a value
         conn_manager.execute_mut(|conn| {
             conn.execute("INSERT INTO test (id, value) VALUES (1, 'test')", [])
-                .map_err(|e| e)
         }).expect("Failed to insert value");
         
         // Verify the value was inserted
         let value: String = conn_manager.execute(|conn| {
             conn.query_row("SELECT value FROM test WHERE id = 1", [], |row| row.get(0))
-                .map_err(|e| e)
         }).expect("Failed to select value");
         
         assert_eq!(value, "test");
@@ -348,7 +376,6 @@ pub mod tests {
         // Create a test table
         conn_manager.execute_mut(|conn| {
             conn.execute("CREATE TABLE test_tx (id INTEGER PRIMARY KEY, value TEXT)", [])
-                .map_err(|e| e)
         }).expect("Failed to create table");
         
         // Insert values in a transaction
@@ -361,7 +388,6 @@ pub mod tests {
         // Verify all values were inserted
         let count: i64 = conn_manager.execute(|conn| {
             conn.query_row("SELECT COUNT(*) FROM test_tx", [], |row| row.get(0))
-                .map_err(|e| e)
         }).expect("Failed to count rows");
         
         assert_eq!(count, 2);
@@ -374,13 +400,11 @@ pub mod tests {
         // Create a test table
         conn_manager1.execute_mut(|conn| {
             conn.execute("CREATE TABLE test_clone (id INTEGER PRIMARY KEY, value TEXT)", [])
-                .map_err(|e| e)
         }).expect("Failed to create table");
         
         // Insert a value
         conn_manager1.execute_mut(|conn| {
             conn.execute("INSERT INTO test_clone (id, value) VALUES (1, 'original')", [])
-                .map_err(|e| e)
         }).expect("Failed to insert value");
         
         // Clone the connection manager
@@ -389,21 +413,41 @@ pub mod tests {
         // Use the cloned manager to insert another value
         conn_manager2.execute_mut(|conn| {
             conn.execute("INSERT INTO test_clone (id, value) VALUES (2, 'cloned')", [])
-                .map_err(|e| e)
         }).expect("Failed to insert value with cloned manager");
         
         // Verify both values can be read from either manager
         let value1: String = conn_manager1.execute(|conn| {
             conn.query_row("SELECT value FROM test_clone WHERE id = 2", [], |row| row.get(0))
-                .map_err(|e| e)
         }).expect("Failed to select value from original manager");
         
         let value2: String = conn_manager2.execute(|conn| {
             conn.query_row("SELECT value FROM test_clone WHERE id = 1", [], |row| row.get(0))
-                .map_err(|e| e)
         }).expect("Failed to select value from cloned manager");
         
         assert_eq!(value1, "cloned");
         assert_eq!(value2, "original");
     }
+    
+    #[test]
+    fn test_reopen() {
+        let temp_dir = tempfile::tempdir().expect("Failed to create temporary directory");
+        let conn_manager1 = create_test_connection_manager();
+        let conn_manager2 = conn_manager1.clone();
+        
+        // Reopening one manager moves all its clones to the database file
+        conn_manager1.reopen(&temp_dir.path().join("test.db")).expect("Failed to reopen connection");
+        conn_manager2.execute_mut(|conn| {
+            conn.execute("CREATE TABLE test_reopen (id INTEGER PRIMARY KEY)", [])
+        }).expect("Failed to create table");
+        
+        let journal_mode: String = conn_manager1.execute(|conn| {
+            conn.query_row("PRAGMA journal_mode", [], |row| row.get(0))
+        }).expect("Failed to get journal mode");
+        assert_eq!(journal_mode.to_uppercase(), "WAL");
+        
+        let count: i64 = conn_manager1.execute(|conn| {
+            conn.query_row("SELECT COUNT(*) FROM test_reopen", [], |row| row.get(0))
+        }).expect("Failed to count rows");
+        assert_eq!(count, 0);
+    }
 }
\ No newline at end of file
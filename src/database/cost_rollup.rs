@@ -0,0 +1,317 @@
+//! Cost rollup module for Implexa
+//!
+//! This module provides functionality for computing multi-level bill of materials costs
+//! for a build quantity from the supplier offers of the purchased parts.
+
+use std::collections::HashMap;
+use std::time::SystemTime;
+use crate::database::schema::{DatabaseError, DatabaseResult};
+use crate::database::connection_manager::ConnectionManager;
+use crate::database::manufacturer_part::{ManufacturerPartManager, ManufacturerPartStatus};
+use crate::database::relationship::RelationshipManager;
+use crate::database::supplier::{SupplierManager, SupplierOffer, OfferQuote};
+
+/// Cost of a purchased part for the whole build
+#[derive(Debug, Clone)]
+pub struct CostRollupLine {
+    /// ID of the purchased part
+    pub part_id: i64,
+    /// Quantity required for the whole build
+    pub required_quantity: i64,
+    /// Cheapest offer for the required quantity, if any
+    pub offer: Option<SupplierOffer>,
+    /// Quote of the cheapest offer, if any
+    pub quote: Option<OfferQuote>,
+}
+
+impl CostRollupLine {
+    /// Get the effective unit cost of the part, spreading MOQ and price break overage over the required quantity
+    ///
+    /// # Returns
+    ///
+    /// The effective unit cost, or None if the part could not be costed
+    pub fn effective_unit_cost(&self) -> Option<f64> {
+        let quote = self.quote.as_ref()?;
+        if self.required_quantity == 0 {
+            return Some(quote.unit_price);
+        }
+        Some(quote.extended_price / self.required_quantity as f64)
+    }
+}
+
+/// Cost of one node of the BOM tree for the whole build
+#[derive(Debug, Clone)]
+pub struct CostRollupNode {
+    /// Depth of the node in the BOM, 0 for the top-level part
+    pub level: usize,
+    /// ID of the part
+    pub part_id: i64,
+    /// Quantity of the part across the whole build
+    pub quantity: i64,
+    /// Cost of the part across the whole build, including its sub-assemblies
+    pub extended_cost: f64,
+    /// Whether any purchased part below this node could not be costed
+    pub incomplete: bool,
+}
+
+/// Multi-level BOM cost rollup
+#[derive(Debug, Clone)]
+pub struct CostRollup {
+    /// ID of the top-level part
+    pub part_id: i64,
+    /// Number of top-level units being built
+    pub build_quantity: i64,
+    /// Currency the rollup is computed in
+    pub currency: String,
+    /// Purchased part lines, with quantities aggregated across the BOM
+    pub lines: Vec<CostRollupLine>,
+    /// BOM tree in depth-first order, starting with the top-level part
+    pub nodes: Vec<CostRollupNode>,
+    /// Total cost of the build
+    pub total_cost: f64,
+    /// Cost per top-level unit
+    pub unit_cost: f64,
+    /// Purchased parts without a usable offer in the rollup currency
+    pub uncosted_parts: Vec<i64>,
+}
+
+/// Manager for BOM cost rollup operations
+pub struct CostRollupManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+}
+
+impl<'a> CostRollupManager<'a> {
+    /// Create a new CostRollupManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new CostRollupManager instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Compute the multi-level cost rollup of a part for a build quantity
+    ///
+    /// Quantities of the same purchased part are aggregated across the whole BOM before
+    /// price breaks are applied. Only offers in the given currency for manufacturer parts
+    /// that are not obsolete or past their EOL date are considered.
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the top-level part
+    /// * `build_quantity` - The number of top-level units being built
+    /// * `currency` - The currency to compute the rollup in
+    ///
+    /// # Returns
+    ///
+    /// The cost rollup
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the BOM or offers could not be retrieved
+    pub fn get_cost_rollup(&self, part_id: i64, build_quantity: i64, currency: &str) -> DatabaseResult<CostRollup> {
+        if build_quantity < 1 {
+            return Err(DatabaseError::ValidationError("Build quantity must be at least 1".to_string()));
+        }
+
+        let relationship_manager = RelationshipManager::new(self.connection_manager);
+        let bom = relationship_manager.explode_bom(part_id)?;
+
+        // Aggregate the purchased part quantities across the BOM, keeping first-seen order
+        let mut required: Vec<(i64, i64)> = Vec::new();
+        for line in bom.iter().filter(|line| line.is_leaf) {
+            let quantity = line.extended_quantity * build_quantity;
+            match required.iter_mut().find(|(leaf_id, _)| *leaf_id == line.part_id) {
+                Some((_, total)) => *total += quantity,
+                None => required.push((line.part_id, quantity)),
+            }
+        }
+
+        let mut lines = Vec::new();
+        let mut uncosted_parts = Vec::new();
+        for (leaf_id, required_quantity) in required {
+            let line = self.cost_part(leaf_id, required_quantity, currency)?;
+            if line.quote.is_none() {
+                uncosted_parts.push(leaf_id);
+            }
+            lines.push(line);
+        }
+
+        // Roll effective unit costs up the tree, children before parents
+        let unit_costs: HashMap<i64, Option<f64>> = lines
+            .iter()
+            .map(|line| (line.part_id, line.effective_unit_cost()))
+            .collect();
+        let mut nodes: Vec<CostRollupNode> = std::iter::once(CostRollupNode {
+            level: 0,
+            part_id,
+            quantity: build_quantity,
+            extended_cost: 0.0,
+            incomplete: false,
+        })
+        .chain(bom.iter().map(|line| CostRollupNode {
+            level: line.level,
+            part_id: line.part_id,
+            quantity: line.extended_quantity * build_quantity,
+            extended_cost: 0.0,
+            incomplete: false,
+        }))
+        .collect();
+
+        for index in (0..nodes.len()).rev() {
+            let is_leaf = index + 1 >= nodes.len() || nodes[index + 1].level <= nodes[index].level;
+            if is_leaf && index > 0 {
+                let unit_cost = unit_costs.get(&nodes[index].part_id).copied().flatten();
+                nodes[index].extended_cost = unit_cost.unwrap_or(0.0) * nodes[index].quantity as f64;
+                nodes[index].incomplete = unit_cost.is_none();
+                continue;
+            }
+
+            // Sum the direct children, which follow the node until the level drops back
+            let level = nodes[index].level;
+            let (mut cost, mut incomplete) = (0.0, false);
+            for child in nodes[index + 1..].iter().take_while(|node| node.level > level) {
+                if child.level == level + 1 {
+                    cost += child.extended_cost;
+                    incomplete |= child.incomplete;
+                }
+            }
+            nodes[index].extended_cost = cost;
+            nodes[index].incomplete = incomplete;
+        }
+
+        let total_cost = nodes[0].extended_cost;
+        Ok(CostRollup {
+            part_id,
+            build_quantity,
+            currency: currency.to_string(),
+            lines,
+            nodes,
+            total_cost,
+            unit_cost: total_cost / build_quantity as f64,
+            uncosted_parts,
+        })
+    }
+
+    /// Find the cheapest usable offer for a purchased part
+    fn cost_part(&self, part_id: i64, required_quantity: i64, currency: &str) -> DatabaseResult<CostRollupLine> {
+        let manufacturer_part_manager = ManufacturerPartManager::new(self.connection_manager);
+        let supplier_manager = SupplierManager::new(self.connection_manager);
+        let now = SystemTime::now();
+
+        let usable_mpn_ids: Vec<i64> = manufacturer_part_manager
+            .get_manufacturer_parts_for_part(&part_id.to_string())?
+            .into_iter()
+            .filter(|mpn| mpn.status != ManufacturerPartStatus::Obsolete && mpn.eol_date.is_none_or(|eol| eol > now))
+            .filter_map(|mpn| mpn.mpn_id)
+            .collect();
+
+        let mut best: Option<(SupplierOffer, OfferQuote)> = None;
+        for offer in supplier_manager.get_offers_for_part(part_id)? {
+            if !usable_mpn_ids.contains(&offer.mpn_id) || !offer.currency.eq_ignore_ascii_case(currency) {
+                continue;
+            }
+            if let Some(quote) = offer.quote(required_quantity) {
+                let cheaper = best.as_ref().is_none_or(|(_, best_quote)| quote.extended_price < best_quote.extended_price);
+                if cheaper {
+                    best = Some((offer, quote));
+                }
+            }
+        }
+
+        let (offer, quote) = match best {
+            Some((offer, quote)) => (Some(offer), Some(quote)),
+            None => (None, None),
+        };
+        Ok(CostRollupLine {
+            part_id,
+            required_quantity,
+            offer,
+            quote,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::DatabaseManager;
+    use crate::database::part::{Part, PartManager};
+    use crate::database::relationship::{Relationship, RelationshipType};
+    use crate::database::manufacturer_part::ManufacturerPart;
+    use crate::database::supplier::{Supplier, PriceBreak};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_multi_level_cost_rollup() {
+        // Create a temporary directory for the test database
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+
+        // Create a new database manager and initialize the schema
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let connection_manager = db_manager.connection_manager();
+
+        let part_manager = PartManager::new(connection_manager);
+        let relationship_manager = RelationshipManager::new(connection_manager);
+        let manufacturer_part_manager = ManufacturerPartManager::new(connection_manager);
+        let supplier_manager = SupplierManager::new(connection_manager);
+        let rollup_manager = CostRollupManager::new(connection_manager);
+
+        // Unit 10003 contains 2 boards 10002 and 1 resistor 10001; each board contains 5 resistors
+        for (part_id, subcategory, name) in [
+            (10001, "Resistor", "10K Resistor"),
+            (10002, "PCA", "Controller Board"),
+            (10003, "Assembly", "Controller Unit"),
+            (10004, "Connector", "Header"),
+        ] {
+            let part = Part::new(part_id, "Electronic".to_string(), subcategory.to_string(), name.to_string(), None);
+            part_manager.create_part(&part).unwrap();
+        }
+        relationship_manager.create_relationship(&Relationship::new(10003, 10002, RelationshipType::Assembly, 2)).unwrap();
+        relationship_manager.create_relationship(&Relationship::new(10002, 10001, RelationshipType::Assembly, 5)).unwrap();
+        relationship_manager.create_relationship(&Relationship::new(10002, 10004, RelationshipType::Assembly, 1)).unwrap();
+        relationship_manager.create_relationship(&Relationship::new(10003, 10001, RelationshipType::Assembly, 1)).unwrap();
+
+        // Two offers for the resistor: cheaper in bulk at the second supplier
+        let mpn_id = manufacturer_part_manager.create_manufacturer_part(&ManufacturerPart::new(
+            10001, "Yageo".to_string(), "RC0603FR-0710KL".to_string(), None, ManufacturerPartStatus::Preferred,
+        )).unwrap();
+        let digikey = supplier_manager.create_supplier(&Supplier::new("Digi-Key".to_string(), None)).unwrap();
+        let mouser = supplier_manager.create_supplier(&Supplier::new("Mouser".to_string(), None)).unwrap();
+        supplier_manager.create_offer(&SupplierOffer::new(
+            mpn_id, digikey, "311-10.0KHRCT-ND".to_string(), 1, "USD".to_string(), None,
+            vec![PriceBreak::new(1, 0.10), PriceBreak::new(100, 0.05)],
+        )).unwrap();
+        supplier_manager.create_offer(&SupplierOffer::new(
+            mpn_id, mouser, "603-RC0603FR-0710KL".to_string(), 50, "USD".to_string(), None,
+            vec![PriceBreak::new(50, 0.04)],
+        )).unwrap();
+
+        // Build 10 units: 10 * (2 * 5 + 1) = 110 resistors, priced as one purchase
+        let rollup = rollup_manager.get_cost_rollup(10003, 10, "USD").unwrap();
+        assert_eq!(rollup.lines.len(), 2);
+        assert_eq!(rollup.lines[0].required_quantity, 110);
+        assert_eq!(rollup.lines[0].offer.as_ref().unwrap().supplier_id, mouser);
+        assert!((rollup.total_cost - 4.4).abs() < 1e-9);
+        assert!((rollup.unit_cost - 0.44).abs() < 1e-9);
+
+        // The header has no offer, so the board and the unit are incomplete
+        assert_eq!(rollup.uncosted_parts, vec![10004]);
+        assert!(rollup.nodes[0].incomplete);
+        let board = rollup.nodes.iter().find(|node| node.part_id == 10002).unwrap();
+        assert_eq!(board.quantity, 20);
+        assert!((board.extended_cost - 4.0).abs() < 1e-9);
+
+        // Building a single unit hits the MOQ of the bulk offer, so the cut tape offer wins
+        let rollup = rollup_manager.get_cost_rollup(10003, 1, "USD").unwrap();
+        assert_eq!(rollup.lines[0].offer.as_ref().unwrap().supplier_id, digikey);
+        assert!((rollup.total_cost - 1.1).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,918 @@
+//! Deviation module for Implexa
+//!
+//! This module provides functionality for managing deviations and waivers: approved,
+//! time-limited or quantity-limited permissions to build parts differently from their
+//! released documentation, such as using a substitute manufacturer part on the next 500 units
+//! of an assembly revision.
+
+use rusqlite::{Transaction, params, Row, Result as SqliteResult};
+use std::collections::BTreeSet;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::database::schema::{DatabaseError, DatabaseResult};
+use crate::database::connection_manager::ConnectionManager;
+use crate::database::part::PartManager;
+use crate::database::permission::{PermissionManager, PermissionAction};
+use crate::database::user::User;
+
+/// Columns selected for a deviation, in the order expected by `row_to_deviation`
+const DEVIATION_COLUMNS: &str = "d.deviation_id, d.deviation_number, d.type, d.title, d.description,
+    d.original_mpn_id, d.substitute_mpn_id, d.quantity_limit, d.quantity_used, d.expiry_date,
+    d.status, d.created_by, d.created_date, d.approved_by, d.approved_date";
+
+/// Kind of deviation record
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviationType {
+    /// Permission to build differently from the documentation before the fact
+    Deviation,
+    /// Acceptance of units already built differently from the documentation
+    Waiver,
+}
+
+impl DeviationType {
+    /// Convert a string to a DeviationType
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The string to convert
+    ///
+    /// # Returns
+    ///
+    /// The corresponding DeviationType, or None if the string is not a valid type
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "Deviation" => Some(Self::Deviation),
+            "Waiver" => Some(Self::Waiver),
+            _ => None,
+        }
+    }
+
+    /// Convert a DeviationType to a string
+    ///
+    /// # Returns
+    ///
+    /// The string representation of the type
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::Deviation => "Deviation",
+            Self::Waiver => "Waiver",
+        }
+    }
+}
+
+/// Status of a deviation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviationStatus {
+    /// The deviation is waiting for approval
+    Pending,
+    /// The deviation has been approved and applies until it expires
+    Approved,
+    /// The deviation has been rejected
+    Rejected,
+    /// The deviation was closed before it expired
+    Closed,
+}
+
+impl DeviationStatus {
+    /// Convert a string to a DeviationStatus
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The string to convert
+    ///
+    /// # Returns
+    ///
+    /// The corresponding DeviationStatus, or None if the string is not a valid status
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "Pending" => Some(Self::Pending),
+            "Approved" => Some(Self::Approved),
+            "Rejected" => Some(Self::Rejected),
+            "Closed" => Some(Self::Closed),
+            _ => None,
+        }
+    }
+
+    /// Convert a DeviationStatus to a string
+    ///
+    /// # Returns
+    ///
+    /// The string representation of the status
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "Pending",
+            Self::Approved => "Approved",
+            Self::Rejected => "Rejected",
+            Self::Closed => "Closed",
+        }
+    }
+}
+
+/// Represents a deviation or waiver
+#[derive(Debug, Clone)]
+pub struct Deviation {
+    /// Unique identifier for the deviation
+    pub deviation_id: Option<i64>,
+    /// Deviation number, e.g. DEV-00042; assigned when the deviation is created
+    pub deviation_number: String,
+    /// Kind of deviation
+    pub deviation_type: DeviationType,
+    /// Short title of the deviation
+    pub title: String,
+    /// Description of what deviates from the documentation and why
+    pub description: Option<String>,
+    /// ID of the manufacturer part that is replaced, if the deviation substitutes one
+    pub original_mpn_id: Option<i64>,
+    /// ID of the manufacturer part used instead
+    pub substitute_mpn_id: Option<i64>,
+    /// Number of units the deviation applies to, if it is quantity-limited
+    pub quantity_limit: Option<i64>,
+    /// Number of units built under the deviation so far
+    pub quantity_used: i64,
+    /// Date after which the deviation no longer applies, if it is time-limited
+    pub expiry_date: Option<SystemTime>,
+    /// Status of the deviation
+    pub status: DeviationStatus,
+    /// User who requested the deviation
+    pub created_by: String,
+    /// Date the deviation was requested
+    pub created_date: SystemTime,
+    /// User who approved or rejected the deviation
+    pub approved_by: Option<String>,
+    /// Date the deviation was approved or rejected
+    pub approved_date: Option<SystemTime>,
+}
+
+impl Deviation {
+    /// Create a new deviation awaiting approval
+    ///
+    /// # Arguments
+    ///
+    /// * `deviation_type` - Kind of deviation
+    /// * `title` - Short title of the deviation
+    /// * `quantity_limit` - Number of units the deviation applies to
+    /// * `expiry_date` - Date after which the deviation no longer applies
+    /// * `created_by` - User who requested the deviation
+    ///
+    /// # Returns
+    ///
+    /// A new Deviation instance
+    pub fn new(
+        deviation_type: DeviationType,
+        title: String,
+        quantity_limit: Option<i64>,
+        expiry_date: Option<SystemTime>,
+        created_by: String,
+    ) -> Self {
+        Self {
+            deviation_id: None,
+            deviation_number: String::new(),
+            deviation_type,
+            title,
+            description: None,
+            original_mpn_id: None,
+            substitute_mpn_id: None,
+            quantity_limit,
+            quantity_used: 0,
+            expiry_date,
+            status: DeviationStatus::Pending,
+            created_by,
+            created_date: SystemTime::now(),
+            approved_by: None,
+            approved_date: None,
+        }
+    }
+
+    /// Get the number of units that can still be built under the deviation
+    ///
+    /// # Returns
+    ///
+    /// The remaining quantity, or None if the deviation is not quantity-limited
+    pub fn remaining_quantity(&self) -> Option<i64> {
+        self.quantity_limit.map(|limit| (limit - self.quantity_used).max(0))
+    }
+
+    /// Check whether an approved deviation has run out of time or quantity
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The time to check against
+    ///
+    /// # Returns
+    ///
+    /// true if the deviation is approved but past its expiry date or quantity limit
+    pub fn is_expired(&self, now: SystemTime) -> bool {
+        self.status == DeviationStatus::Approved
+            && (self.expiry_date.is_some_and(|expiry| expiry <= now) || self.remaining_quantity() == Some(0))
+    }
+
+    /// Check whether the deviation currently applies
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The time to check against
+    ///
+    /// # Returns
+    ///
+    /// true if the deviation is approved and has not expired
+    pub fn is_active(&self, now: SystemTime) -> bool {
+        self.status == DeviationStatus::Approved && !self.is_expired(now)
+    }
+}
+
+/// Part or revision affected by a deviation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviationItem {
+    /// ID of the deviation
+    pub deviation_id: i64,
+    /// ID of the affected part
+    pub part_id: i64,
+    /// ID of the affected revision, or None if every revision of the part is affected
+    pub revision_id: Option<i64>,
+}
+
+/// Deviation that applies to a part of a bill of materials
+#[derive(Debug, Clone)]
+pub struct DeviationNotice {
+    /// ID of the part the deviation applies to
+    pub part_id: i64,
+    /// The deviation
+    pub deviation: Deviation,
+    /// Whether the deviation has run out of time or quantity
+    pub expired: bool,
+}
+
+/// Manager for deviation operations
+pub struct DeviationManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+}
+
+impl<'a> DeviationManager<'a> {
+    /// Create a new DeviationManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new DeviationManager instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Create a new deviation and assign it the next deviation number
+    ///
+    /// # Arguments
+    ///
+    /// * `deviation` - The deviation to create
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created deviation
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the deviation has neither an expiry date nor a quantity limit,
+    /// or could not be created
+    pub fn create_deviation(&self, deviation: &Deviation) -> DatabaseResult<i64> {
+        if deviation.title.trim().is_empty() {
+            return Err(DatabaseError::ValidationError("Deviation title cannot be empty".to_string()));
+        }
+        if deviation.expiry_date.is_none() && deviation.quantity_limit.is_none() {
+            return Err(DatabaseError::ValidationError(
+                "A deviation needs an expiry date or a quantity limit".to_string(),
+            ));
+        }
+        if deviation.quantity_limit.is_some_and(|limit| limit < 1) {
+            return Err(DatabaseError::ValidationError("Deviation quantity limit must be at least 1".to_string()));
+        }
+
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let next_number: i64 = tx.query_row(
+                "SELECT COALESCE(MAX(deviation_id), 0) + 1 FROM Deviations",
+                [],
+                |row| row.get(0),
+            )?;
+            let prefix = match deviation.deviation_type {
+                DeviationType::Deviation => "DEV",
+                DeviationType::Waiver => "WVR",
+            };
+
+            tx.execute(
+                "INSERT INTO Deviations (deviation_number, type, title, description, original_mpn_id, substitute_mpn_id,
+                                         quantity_limit, quantity_used, expiry_date, status, created_by, created_date)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, 0, ?8, ?9, ?10, ?11)",
+                params![
+                    format!("{}-{:05}", prefix, next_number),
+                    deviation.deviation_type.to_str(),
+                    deviation.title,
+                    deviation.description,
+                    deviation.original_mpn_id,
+                    deviation.substitute_mpn_id,
+                    deviation.quantity_limit,
+                    deviation.expiry_date.map(to_timestamp),
+                    DeviationStatus::Pending.to_str(),
+                    deviation.created_by,
+                    to_timestamp(deviation.created_date),
+                ],
+            )?;
+            Ok(tx.last_insert_rowid())
+        })
+    }
+
+    /// Get a deviation by ID
+    ///
+    /// # Arguments
+    ///
+    /// * `deviation_id` - The ID of the deviation
+    ///
+    /// # Returns
+    ///
+    /// The deviation
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the deviation could not be retrieved
+    pub fn get_deviation(&self, deviation_id: i64) -> DatabaseResult<Deviation> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.get_deviation_in_transaction(deviation_id, tx)
+        })
+    }
+
+    /// Get a deviation by ID within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `deviation_id` - The ID of the deviation
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The deviation
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the deviation could not be retrieved
+    pub fn get_deviation_in_transaction(&self, deviation_id: i64, tx: &Transaction) -> DatabaseResult<Deviation> {
+        let deviation = tx.query_row(
+            &format!("SELECT {} FROM Deviations d WHERE d.deviation_id = ?1", DEVIATION_COLUMNS),
+            params![deviation_id],
+            |row| self.row_to_deviation(row),
+        )?;
+        Ok(deviation)
+    }
+
+    /// Get all deviations
+    ///
+    /// # Returns
+    ///
+    /// A vector of all deviations, newest first
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the deviations could not be retrieved
+    pub fn get_all_deviations(&self) -> DatabaseResult<Vec<Deviation>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {} FROM Deviations d ORDER BY d.deviation_id DESC", DEVIATION_COLUMNS
+            ))?;
+            let deviations = stmt
+                .query_map([], |row| self.row_to_deviation(row))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(deviations)
+        })
+    }
+
+    /// Link a part or one of its revisions to a deviation
+    ///
+    /// # Arguments
+    ///
+    /// * `deviation_id` - The ID of the deviation
+    /// * `part_id` - The ID of the affected part
+    /// * `revision_id` - The ID of the affected revision, or None for every revision of the part
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the item was added or already linked
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the deviation is no longer pending, the revision belongs to
+    /// another part or the item could not be added
+    pub fn add_item(&self, deviation_id: i64, part_id: i64, revision_id: Option<i64>) -> DatabaseResult<()> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let deviation = self.get_deviation_in_transaction(deviation_id, tx)?;
+            if deviation.status != DeviationStatus::Pending {
+                return Err(DatabaseError::ValidationError(format!(
+                    "Items can only be added to {} while it is pending", deviation.deviation_number
+                )));
+            }
+
+            if let Some(revision_id) = revision_id {
+                let revision_part_id: i64 = tx.query_row(
+                    "SELECT part_id FROM Revisions WHERE revision_id = ?1",
+                    params![revision_id],
+                    |row| row.get(0),
+                )?;
+                if revision_part_id != part_id {
+                    return Err(DatabaseError::ValidationError(format!(
+                        "Revision {} does not belong to part {}", revision_id, part_id
+                    )));
+                }
+            }
+
+            let exists: bool = tx.query_row(
+                "SELECT EXISTS (SELECT 1 FROM DeviationItems WHERE deviation_id = ?1 AND part_id = ?2 AND revision_id IS ?3)",
+                params![deviation_id, part_id, revision_id],
+                |row| row.get(0),
+            )?;
+            if !exists {
+                tx.execute(
+                    "INSERT INTO DeviationItems (deviation_id, part_id, revision_id) VALUES (?1, ?2, ?3)",
+                    params![deviation_id, part_id, revision_id],
+                )?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Remove an affected part or revision from a pending deviation
+    ///
+    /// # Arguments
+    ///
+    /// * `deviation_id` - The ID of the deviation
+    /// * `part_id` - The ID of the affected part
+    /// * `revision_id` - The ID of the affected revision, or None for the whole part
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the item was removed
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the deviation is no longer pending or the item could not be removed
+    pub fn remove_item(&self, deviation_id: i64, part_id: i64, revision_id: Option<i64>) -> DatabaseResult<()> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let deviation = self.get_deviation_in_transaction(deviation_id, tx)?;
+            if deviation.status != DeviationStatus::Pending {
+                return Err(DatabaseError::ValidationError(format!(
+                    "Items can only be removed from {} while it is pending", deviation.deviation_number
+                )));
+            }
+
+            tx.execute(
+                "DELETE FROM DeviationItems WHERE deviation_id = ?1 AND part_id = ?2 AND revision_id IS ?3",
+                params![deviation_id, part_id, revision_id],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Get the parts and revisions affected by a deviation
+    ///
+    /// # Arguments
+    ///
+    /// * `deviation_id` - The ID of the deviation
+    ///
+    /// # Returns
+    ///
+    /// The affected items, in the order they were added
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the items could not be retrieved
+    pub fn get_items(&self, deviation_id: i64) -> DatabaseResult<Vec<DeviationItem>> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.get_items_in_transaction(deviation_id, tx)
+        })
+    }
+
+    /// Get the parts and revisions affected by a deviation within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `deviation_id` - The ID of the deviation
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The affected items, in the order they were added
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the items could not be retrieved
+    pub fn get_items_in_transaction(&self, deviation_id: i64, tx: &Transaction) -> DatabaseResult<Vec<DeviationItem>> {
+        let mut stmt = tx.prepare(
+            "SELECT deviation_id, part_id, revision_id FROM DeviationItems WHERE deviation_id = ?1 ORDER BY rowid",
+        )?;
+        let items = stmt
+            .query_map(params![deviation_id], |row| {
+                Ok(DeviationItem {
+                    deviation_id: row.get(0)?,
+                    part_id: row.get(1)?,
+                    revision_id: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(items)
+    }
+
+    /// Approve or reject a pending deviation
+    ///
+    /// The approver needs approval permission in the categories of all affected parts and
+    /// cannot be the user who requested the deviation.
+    ///
+    /// # Arguments
+    ///
+    /// * `deviation_id` - The ID of the deviation
+    /// * `approver` - The user deciding on the deviation
+    /// * `approved` - Whether the deviation is approved or rejected
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the decision was recorded
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the deviation is not pending, has no affected items, the
+    /// approver is not permitted or the decision could not be recorded
+    pub fn decide(&self, deviation_id: i64, approver: &User, approved: bool) -> DatabaseResult<()> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let deviation = self.get_deviation_in_transaction(deviation_id, tx)?;
+            if deviation.status != DeviationStatus::Pending {
+                return Err(DatabaseError::ValidationError(format!(
+                    "{} is {} and cannot be decided again", deviation.deviation_number, deviation.status.to_str()
+                )));
+            }
+
+            let items = self.get_items_in_transaction(deviation_id, tx)?;
+            if items.is_empty() {
+                return Err(DatabaseError::ValidationError(format!(
+                    "{} does not affect any parts", deviation.deviation_number
+                )));
+            }
+
+            // The approver must be allowed to approve changes to every affected part
+            let part_manager = PartManager::new(self.connection_manager);
+            let permission_manager = PermissionManager::new(self.connection_manager);
+            let categories: BTreeSet<String> = items
+                .iter()
+                .map(|item| part_manager.get_part_in_transaction(item.part_id, tx).map(|part| part.category))
+                .collect::<DatabaseResult<_>>()?;
+            for category in categories {
+                if !permission_manager.is_permitted_in_transaction(
+                    approver,
+                    PermissionAction::Approve,
+                    &category,
+                    Some(&deviation.created_by),
+                    tx,
+                )? {
+                    return Err(DatabaseError::ValidationError(format!(
+                        "{} is not permitted to approve deviations for {} parts", approver.username, category
+                    )));
+                }
+            }
+
+            let status = if approved { DeviationStatus::Approved } else { DeviationStatus::Rejected };
+            tx.execute(
+                "UPDATE Deviations SET status = ?1, approved_by = ?2, approved_date = ?3 WHERE deviation_id = ?4",
+                params![status.to_str(), approver.username, to_timestamp(SystemTime::now()), deviation_id],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Close a deviation before it expires
+    ///
+    /// # Arguments
+    ///
+    /// * `deviation_id` - The ID of the deviation
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the deviation was closed
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the deviation is already rejected or closed, or could not be closed
+    pub fn close_deviation(&self, deviation_id: i64) -> DatabaseResult<()> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let deviation = self.get_deviation_in_transaction(deviation_id, tx)?;
+            if !matches!(deviation.status, DeviationStatus::Pending | DeviationStatus::Approved) {
+                return Err(DatabaseError::ValidationError(format!(
+                    "{} is already {}", deviation.deviation_number, deviation.status.to_str()
+                )));
+            }
+
+            tx.execute(
+                "UPDATE Deviations SET status = ?1 WHERE deviation_id = ?2",
+                params![DeviationStatus::Closed.to_str(), deviation_id],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Record units built under an active deviation
+    ///
+    /// # Arguments
+    ///
+    /// * `deviation_id` - The ID of the deviation
+    /// * `quantity` - The number of units built
+    ///
+    /// # Returns
+    ///
+    /// The updated deviation
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the deviation is not active, the quantity would exceed its
+    /// limit or the usage could not be recorded
+    pub fn record_usage(&self, deviation_id: i64, quantity: i64) -> DatabaseResult<Deviation> {
+        if quantity < 1 {
+            return Err(DatabaseError::ValidationError("Usage quantity must be at least 1".to_string()));
+        }
+
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let deviation = self.get_deviation_in_transaction(deviation_id, tx)?;
+            if !deviation.is_active(SystemTime::now()) {
+                return Err(DatabaseError::ValidationError(format!(
+                    "{} is not active", deviation.deviation_number
+                )));
+            }
+            if let Some(remaining) = deviation.remaining_quantity() {
+                if quantity > remaining {
+                    return Err(DatabaseError::ValidationError(format!(
+                        "{} only covers {} more units", deviation.deviation_number, remaining
+                    )));
+                }
+            }
+
+            tx.execute(
+                "UPDATE Deviations SET quantity_used = quantity_used + ?1 WHERE deviation_id = ?2",
+                params![quantity, deviation_id],
+            )?;
+            self.get_deviation_in_transaction(deviation_id, tx)
+        })
+    }
+
+    /// Get the deviations that are still open against a revision within an existing transaction
+    ///
+    /// Pending deviations and approved deviations that have not expired are open, whether they
+    /// affect the revision itself or every revision of its part.
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The open deviations, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the deviations could not be retrieved
+    pub fn get_open_deviations_for_revision_in_transaction(&self, revision_id: i64, tx: &Transaction) -> DatabaseResult<Vec<Deviation>> {
+        let mut stmt = tx.prepare(&format!(
+            "SELECT DISTINCT {} FROM Deviations d
+             JOIN DeviationItems i ON i.deviation_id = d.deviation_id
+             JOIN Revisions r ON r.part_id = i.part_id
+             WHERE r.revision_id = ?1 AND (i.revision_id IS NULL OR i.revision_id = ?1)
+             AND d.status IN (?2, ?3)
+             ORDER BY d.deviation_id",
+            DEVIATION_COLUMNS
+        ))?;
+        let now = SystemTime::now();
+        let deviations = stmt
+            .query_map(
+                params![revision_id, DeviationStatus::Pending.to_str(), DeviationStatus::Approved.to_str()],
+                |row| self.row_to_deviation(row),
+            )?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter(|deviation| !deviation.is_expired(now))
+            .collect();
+        Ok(deviations)
+    }
+
+    /// Get the approved deviations that apply to an assembly revision and the parts of its BOM
+    ///
+    /// Deviations of the assembly apply if they affect the revision or the whole assembly part;
+    /// deviations of BOM parts apply if they affect the part or any of its revisions. Expired
+    /// deviations are included and flagged so that they can be reported.
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the assembly revision
+    /// * `part_ids` - The IDs of the parts in the BOM of the assembly
+    ///
+    /// # Returns
+    ///
+    /// The deviations per part, the assembly first and then in the order of `part_ids`
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the deviations could not be retrieved
+    pub fn get_bom_deviations(&self, revision_id: i64, part_ids: &[i64]) -> DatabaseResult<Vec<DeviationNotice>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let now = SystemTime::now();
+            let mut notices: Vec<DeviationNotice> = Vec::new();
+            let mut push = |part_id: i64, deviation: Deviation| {
+                if !notices.iter().any(|notice| notice.part_id == part_id && notice.deviation.deviation_id == deviation.deviation_id) {
+                    let expired = deviation.is_expired(now);
+                    notices.push(DeviationNotice { part_id, deviation, expired });
+                }
+            };
+
+            // Deviations of the assembly revision itself
+            let assembly_part_id: i64 = conn.query_row(
+                "SELECT part_id FROM Revisions WHERE revision_id = ?1",
+                params![revision_id],
+                |row| row.get(0),
+            )?;
+            let mut stmt = conn.prepare(&format!(
+                "SELECT DISTINCT {} FROM Deviations d
+                 JOIN DeviationItems i ON i.deviation_id = d.deviation_id
+                 WHERE i.part_id = ?1 AND (i.revision_id IS NULL OR i.revision_id = ?2) AND d.status = ?3
+                 ORDER BY d.deviation_id",
+                DEVIATION_COLUMNS
+            ))?;
+            let deviations = stmt
+                .query_map(params![assembly_part_id, revision_id, DeviationStatus::Approved.to_str()], |row| self.row_to_deviation(row))?
+                .collect::<Result<Vec<_>, _>>()?;
+            for deviation in deviations {
+                push(assembly_part_id, deviation);
+            }
+
+            // Deviations of the parts in the BOM
+            let mut stmt = conn.prepare(&format!(
+                "SELECT DISTINCT {} FROM Deviations d
+                 JOIN DeviationItems i ON i.deviation_id = d.deviation_id
+                 WHERE i.part_id = ?1 AND d.status = ?2
+                 ORDER BY d.deviation_id",
+                DEVIATION_COLUMNS
+            ))?;
+            for &part_id in part_ids {
+                let deviations = stmt
+                    .query_map(params![part_id, DeviationStatus::Approved.to_str()], |row| self.row_to_deviation(row))?
+                    .collect::<Result<Vec<_>, _>>()?;
+                for deviation in deviations {
+                    push(part_id, deviation);
+                }
+            }
+
+            Ok(notices)
+        })
+    }
+
+    /// Convert a database row to a Deviation
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The database row
+    ///
+    /// # Returns
+    ///
+    /// A Deviation instance
+    ///
+    /// # Errors
+    ///
+    /// Returns a SqliteError if the row could not be converted
+    fn row_to_deviation(&self, row: &Row) -> SqliteResult<Deviation> {
+        let type_str: String = row.get(2)?;
+        let deviation_type = DeviationType::from_str(&type_str).ok_or_else(|| {
+            rusqlite::Error::InvalidColumnType(2, "type".to_string(), rusqlite::types::Type::Text)
+        })?;
+        let status_str: String = row.get(10)?;
+        let status = DeviationStatus::from_str(&status_str).ok_or_else(|| {
+            rusqlite::Error::InvalidColumnType(10, "status".to_string(), rusqlite::types::Type::Text)
+        })?;
+
+        Ok(Deviation {
+            deviation_id: Some(row.get(0)?),
+            deviation_number: row.get(1)?,
+            deviation_type,
+            title: row.get(3)?,
+            description: row.get(4)?,
+            original_mpn_id: row.get(5)?,
+            substitute_mpn_id: row.get(6)?,
+            quantity_limit: row.get(7)?,
+            quantity_used: row.get(8)?,
+            expiry_date: row.get::<_, Option<i64>>(9)?.map(from_timestamp),
+            status,
+            created_by: row.get(11)?,
+            created_date: from_timestamp(row.get(12)?),
+            approved_by: row.get(13)?,
+            approved_date: row.get::<_, Option<i64>>(14)?.map(from_timestamp),
+        })
+    }
+}
+
+/// Convert a SystemTime to seconds since UNIX_EPOCH for SQLite
+fn to_timestamp(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64
+}
+
+/// Convert seconds since UNIX_EPOCH from SQLite to a SystemTime
+fn from_timestamp(secs: i64) -> SystemTime {
+    UNIX_EPOCH + std::time::Duration::from_secs(secs.max(0) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::DatabaseManager;
+    use crate::database::part::{Part, PartManager};
+    use crate::database::revision::{Revision, RevisionStatus, RevisionManager};
+    use crate::database::user::UserRole;
+    use crate::database::bom_export::BomExportManager;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_deviation_lifecycle() {
+        // Create a temporary database
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let connection_manager = db_manager.connection_manager();
+
+        // Create an assembly with a revision
+        let part_manager = PartManager::new(connection_manager);
+        let assembly = Part::new(100200, "Electronic".to_string(), "PCA".to_string(), "Controller Board".to_string(), None);
+        part_manager.create_part(&assembly).unwrap();
+        let revision_manager = RevisionManager::new(connection_manager);
+        let revision_id = revision_manager.create_revision(&Revision::new(
+            assembly.part_id,
+            "B".to_string(),
+            RevisionStatus::Released,
+            "designer".to_string(),
+            None,
+        )).unwrap();
+
+        // Request a quantity-limited deviation against the revision
+        let deviation_manager = DeviationManager::new(connection_manager);
+        let deviation = Deviation::new(
+            DeviationType::Deviation,
+            "Use MPN Y instead of X".to_string(),
+            Some(500),
+            None,
+            "designer".to_string(),
+        );
+        let deviation_id = deviation_manager.create_deviation(&deviation).unwrap();
+        deviation_manager.add_item(deviation_id, assembly.part_id, Some(revision_id)).unwrap();
+        assert_eq!(deviation_manager.get_deviation(deviation_id).unwrap().deviation_number, "DEV-00001");
+
+        // Deviations need a limit
+        let unlimited = Deviation::new(DeviationType::Waiver, "Unlimited".to_string(), None, None, "designer".to_string());
+        assert!(deviation_manager.create_deviation(&unlimited).is_err());
+
+        // The requester cannot approve their own deviation
+        let requester = User::new("designer".to_string(), UserRole::Designer);
+        assert!(deviation_manager.decide(deviation_id, &requester, true).is_err());
+
+        // A pending deviation is open against the revision
+        let open = connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            deviation_manager.get_open_deviations_for_revision_in_transaction(revision_id, tx)
+        }).unwrap();
+        assert_eq!(open.len(), 1);
+
+        // Approve it and use up its quantity
+        let approver = User::new("quality".to_string(), UserRole::Designer);
+        deviation_manager.decide(deviation_id, &approver, true).unwrap();
+        assert!(deviation_manager.record_usage(deviation_id, 501).is_err());
+        let deviation = deviation_manager.record_usage(deviation_id, 500).unwrap();
+        assert_eq!(deviation.remaining_quantity(), Some(0));
+        assert!(deviation.is_expired(SystemTime::now()));
+
+        // Expired deviations are no longer open but are still reported for the BOM
+        let open = connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            deviation_manager.get_open_deviations_for_revision_in_transaction(revision_id, tx)
+        }).unwrap();
+        assert!(open.is_empty());
+        let notices = deviation_manager.get_bom_deviations(revision_id, &[]).unwrap();
+        assert_eq!(notices.len(), 1);
+        assert!(notices[0].expired);
+
+        let mut csv = Vec::new();
+        BomExportManager::new(connection_manager).export_csv(revision_id, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.contains("DEV-00001 Use MPN Y instead of X (expired)"));
+
+        // Time-limited deviations expire after their expiry date
+        let mut waiver = Deviation::new(
+            DeviationType::Waiver,
+            "Accept scratched housings".to_string(),
+            None,
+            Some(SystemTime::now() + Duration::from_secs(86400)),
+            "designer".to_string(),
+        );
+        waiver.status = DeviationStatus::Approved;
+        assert!(waiver.is_active(SystemTime::now()));
+        assert!(waiver.is_expired(SystemTime::now() + Duration::from_secs(2 * 86400)));
+    }
+}
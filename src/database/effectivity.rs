@@ -0,0 +1,434 @@
+//! Effectivity module for Implexa
+//!
+//! This module provides functionality for managing the effectivity of released revisions: the
+//! build date or serial number a revision takes effect at, and the date or serial number it is
+//! superseded at. Production uses it to look up the revision that applies to a build.
+
+use rusqlite::{Transaction, params, Row, Result as SqliteResult, OptionalExtension};
+use std::cmp::Ordering;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::database::schema::{DatabaseError, DatabaseResult};
+use crate::database::connection_manager::ConnectionManager;
+use crate::database::revision::{Revision, RevisionStatus, RevisionManager};
+
+/// Effectivity of a released revision
+///
+/// Ranges include their start and exclude their end: the unit built at the superseding date or
+/// serial number is built to the next revision.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Effectivity {
+    /// ID of the revision
+    pub revision_id: i64,
+    /// Build date the revision takes effect at
+    pub from_date: Option<SystemTime>,
+    /// Serial number the revision takes effect at
+    pub from_serial: Option<String>,
+    /// Build date the revision is superseded at
+    pub superseded_date: Option<SystemTime>,
+    /// Serial number the revision is superseded at
+    pub superseded_serial: Option<String>,
+}
+
+impl Effectivity {
+    /// Check whether the revision applies to a build date
+    ///
+    /// # Arguments
+    ///
+    /// * `date` - The build date
+    ///
+    /// # Returns
+    ///
+    /// true if the date falls within the date range of the revision
+    pub fn applies_to_date(&self, date: SystemTime) -> bool {
+        match self.from_date {
+            Some(from_date) => from_date <= date && self.superseded_date.is_none_or(|superseded| date < superseded),
+            None => false,
+        }
+    }
+
+    /// Check whether the revision applies to a serial number
+    ///
+    /// # Arguments
+    ///
+    /// * `serial_number` - The serial number of the unit
+    ///
+    /// # Returns
+    ///
+    /// true if the serial number falls within the serial range of the revision
+    pub fn applies_to_serial(&self, serial_number: &str) -> bool {
+        match &self.from_serial {
+            Some(from_serial) => {
+                compare_serials(from_serial, serial_number) != Ordering::Greater
+                    && self.superseded_serial.as_deref()
+                        .is_none_or(|superseded| compare_serials(serial_number, superseded) == Ordering::Less)
+            }
+            None => false,
+        }
+    }
+}
+
+/// Compare two serial numbers
+///
+/// Serial numbers that share a prefix are compared by their trailing number, so SN99 comes
+/// before SN100. Other serial numbers are compared as strings.
+///
+/// # Arguments
+///
+/// * `a` - The first serial number
+/// * `b` - The second serial number
+///
+/// # Returns
+///
+/// The ordering of the serial numbers
+pub fn compare_serials(a: &str, b: &str) -> Ordering {
+    fn split(serial: &str) -> (&str, &str) {
+        let digits = serial.len() - serial.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+        serial.split_at(serial.len() - digits)
+    }
+
+    let (a_prefix, a_number) = split(a);
+    let (b_prefix, b_number) = split(b);
+    if a_prefix == b_prefix && !a_number.is_empty() && !b_number.is_empty() {
+        let a_number = a_number.trim_start_matches('0');
+        let b_number = b_number.trim_start_matches('0');
+        return a_number.len().cmp(&b_number.len()).then_with(|| a_number.cmp(b_number));
+    }
+    a.cmp(b)
+}
+
+/// Manager for effectivity operations
+pub struct EffectivityManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+}
+
+impl<'a> EffectivityManager<'a> {
+    /// Create a new EffectivityManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new EffectivityManager instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Set the effectivity of a released revision
+    ///
+    /// Other revisions of the part whose range is still open and starts before the new range are
+    /// superseded at its start.
+    ///
+    /// # Arguments
+    ///
+    /// * `effectivity` - The effectivity to set
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the effectivity was stored
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the revision is not released or the ranges are invalid
+    pub fn set_effectivity(&self, effectivity: &Effectivity) -> DatabaseResult<()> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.set_effectivity_in_transaction(effectivity, tx)
+        })
+    }
+
+    /// Set the effectivity of a released revision within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `effectivity` - The effectivity to set
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the effectivity was stored
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the revision is not released or the ranges are invalid
+    pub fn set_effectivity_in_transaction(&self, effectivity: &Effectivity, tx: &Transaction) -> DatabaseResult<()> {
+        let revision = RevisionManager::new(self.connection_manager).get_revision_in_transaction(effectivity.revision_id, tx)?;
+        if revision.status != RevisionStatus::Released && revision.status != RevisionStatus::Obsolete {
+            return Err(DatabaseError::ValidationError(format!(
+                "Effectivity can only be set on released revisions; revision {} is {}",
+                revision.version, revision.status.to_str()
+            )));
+        }
+        if effectivity.from_date.is_none() && effectivity.from_serial.is_none() {
+            return Err(DatabaseError::ValidationError(
+                "Effectivity needs a start date or a start serial number".to_string(),
+            ));
+        }
+        if effectivity.superseded_date.is_some() && effectivity.from_date.is_none() {
+            return Err(DatabaseError::ValidationError(
+                "A superseding date needs a start date".to_string(),
+            ));
+        }
+        if effectivity.superseded_serial.is_some() && effectivity.from_serial.is_none() {
+            return Err(DatabaseError::ValidationError(
+                "A superseding serial number needs a start serial number".to_string(),
+            ));
+        }
+        if let (Some(from_date), Some(superseded_date)) = (effectivity.from_date, effectivity.superseded_date) {
+            if superseded_date <= from_date {
+                return Err(DatabaseError::ValidationError(
+                    "The superseding date must be after the start date".to_string(),
+                ));
+            }
+        }
+        if let (Some(from_serial), Some(superseded_serial)) = (&effectivity.from_serial, &effectivity.superseded_serial) {
+            if compare_serials(superseded_serial, from_serial) != Ordering::Greater {
+                return Err(DatabaseError::ValidationError(
+                    "The superseding serial number must come after the start serial number".to_string(),
+                ));
+            }
+        }
+
+        // Close the open ranges of the revisions this one takes over from
+        for mut previous in self.get_effectivities_for_part_in_transaction(revision.part_id, tx)? {
+            if previous.revision_id == effectivity.revision_id {
+                continue;
+            }
+            let mut superseded = false;
+            if let (Some(from_date), Some(previous_from), None) = (effectivity.from_date, previous.from_date, previous.superseded_date) {
+                if previous_from < from_date {
+                    previous.superseded_date = Some(from_date);
+                    superseded = true;
+                }
+            }
+            if let (Some(from_serial), Some(previous_from), None) = (&effectivity.from_serial, &previous.from_serial, &previous.superseded_serial) {
+                if compare_serials(previous_from, from_serial) == Ordering::Less {
+                    previous.superseded_serial = Some(from_serial.clone());
+                    superseded = true;
+                }
+            }
+            if superseded {
+                self.store_in_transaction(&previous, tx)?;
+            }
+        }
+
+        self.store_in_transaction(effectivity, tx)
+    }
+
+    /// Get the effectivity of a revision
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    ///
+    /// # Returns
+    ///
+    /// The effectivity, or None if none was set
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the effectivity could not be retrieved
+    pub fn get_effectivity(&self, revision_id: i64) -> DatabaseResult<Option<Effectivity>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let effectivity = conn.query_row(
+                "SELECT revision_id, from_date, from_serial, superseded_date, superseded_serial
+                 FROM RevisionEffectivity WHERE revision_id = ?1",
+                params![revision_id],
+                |row| self.row_to_effectivity(row),
+            ).optional()?;
+            Ok(effectivity)
+        })
+    }
+
+    /// Get the revision of a part that applies to a build date
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the part
+    /// * `date` - The build date
+    ///
+    /// # Returns
+    ///
+    /// The applicable revision, or None if no revision is effective at the date
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the revisions could not be retrieved
+    pub fn get_revision_for_date(&self, part_id: i64, date: SystemTime) -> DatabaseResult<Option<Revision>> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let effectivity = self.get_effectivities_for_part_in_transaction(part_id, tx)?
+                .into_iter()
+                .filter(|effectivity| effectivity.applies_to_date(date))
+                .max_by_key(|effectivity| effectivity.from_date);
+            self.effectivity_to_revision_in_transaction(effectivity, tx)
+        })
+    }
+
+    /// Get the revision of a part that applies to a serial number
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the part
+    /// * `serial_number` - The serial number of the unit
+    ///
+    /// # Returns
+    ///
+    /// The applicable revision, or None if no revision is effective at the serial number
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the revisions could not be retrieved
+    pub fn get_revision_for_serial(&self, part_id: i64, serial_number: &str) -> DatabaseResult<Option<Revision>> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let effectivity = self.get_effectivities_for_part_in_transaction(part_id, tx)?
+                .into_iter()
+                .filter(|effectivity| effectivity.applies_to_serial(serial_number))
+                .max_by(|a, b| compare_serials(
+                    a.from_serial.as_deref().unwrap_or_default(),
+                    b.from_serial.as_deref().unwrap_or_default(),
+                ));
+            self.effectivity_to_revision_in_transaction(effectivity, tx)
+        })
+    }
+
+    /// Get the effectivities of all revisions of a part within an existing transaction
+    fn get_effectivities_for_part_in_transaction(&self, part_id: i64, tx: &Transaction) -> DatabaseResult<Vec<Effectivity>> {
+        let mut stmt = tx.prepare(
+            "SELECT e.revision_id, e.from_date, e.from_serial, e.superseded_date, e.superseded_serial
+             FROM RevisionEffectivity e
+             JOIN Revisions r ON r.revision_id = e.revision_id
+             WHERE r.part_id = ?1
+             ORDER BY e.revision_id",
+        )?;
+        let effectivities = stmt
+            .query_map(params![part_id], |row| self.row_to_effectivity(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(effectivities)
+    }
+
+    /// Look up the revision of an effectivity within an existing transaction
+    fn effectivity_to_revision_in_transaction(&self, effectivity: Option<Effectivity>, tx: &Transaction) -> DatabaseResult<Option<Revision>> {
+        match effectivity {
+            Some(effectivity) => Ok(Some(
+                RevisionManager::new(self.connection_manager).get_revision_in_transaction(effectivity.revision_id, tx)?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Store an effectivity within an existing transaction
+    fn store_in_transaction(&self, effectivity: &Effectivity, tx: &Transaction) -> DatabaseResult<()> {
+        tx.execute(
+            "INSERT INTO RevisionEffectivity (revision_id, from_date, from_serial, superseded_date, superseded_serial)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(revision_id) DO UPDATE SET
+                 from_date = excluded.from_date,
+                 from_serial = excluded.from_serial,
+                 superseded_date = excluded.superseded_date,
+                 superseded_serial = excluded.superseded_serial",
+            params![
+                effectivity.revision_id,
+                effectivity.from_date.map(to_timestamp),
+                effectivity.from_serial,
+                effectivity.superseded_date.map(to_timestamp),
+                effectivity.superseded_serial,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Convert a database row to an Effectivity
+    fn row_to_effectivity(&self, row: &Row) -> SqliteResult<Effectivity> {
+        Ok(Effectivity {
+            revision_id: row.get(0)?,
+            from_date: row.get::<_, Option<i64>>(1)?.map(from_timestamp),
+            from_serial: row.get(2)?,
+            superseded_date: row.get::<_, Option<i64>>(3)?.map(from_timestamp),
+            superseded_serial: row.get(4)?,
+        })
+    }
+}
+
+/// Convert a SystemTime to seconds since UNIX_EPOCH
+fn to_timestamp(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64
+}
+
+/// Convert seconds since UNIX_EPOCH to a SystemTime
+fn from_timestamp(secs: i64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs.max(0) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::DatabaseManager;
+    use crate::database::part::{Part, PartManager};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_effective_revision_by_date_and_serial() {
+        // Create a temporary database
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let connection_manager = db_manager.connection_manager();
+
+        // Create a part with two released revisions and a draft
+        PartManager::new(connection_manager).create_part(&Part::new(
+            100001, "Electronic".to_string(), "PCA".to_string(), "Controller Board".to_string(), None,
+        )).unwrap();
+        let revision_manager = RevisionManager::new(connection_manager);
+        let rev_a = revision_manager.create_revision(&Revision::new(100001, "A".to_string(), RevisionStatus::Released, "designer".to_string(), None)).unwrap();
+        let rev_b = revision_manager.create_revision(&Revision::new(100001, "B".to_string(), RevisionStatus::Released, "designer".to_string(), None)).unwrap();
+        let rev_c = revision_manager.create_revision(&Revision::new(100001, "C".to_string(), RevisionStatus::Draft, "designer".to_string(), None)).unwrap();
+
+        let effectivity_manager = EffectivityManager::new(connection_manager);
+        let day = |days: u64| UNIX_EPOCH + Duration::from_secs(days * 86_400);
+
+        // Draft revisions have no effectivity
+        assert!(effectivity_manager.set_effectivity(&Effectivity {
+            revision_id: rev_c,
+            from_date: Some(day(100)),
+            ..Effectivity::default()
+        }).is_err());
+
+        // Revision B takes over from revision A
+        effectivity_manager.set_effectivity(&Effectivity {
+            revision_id: rev_a,
+            from_date: Some(day(10)),
+            from_serial: Some("SN98".to_string()),
+            ..Effectivity::default()
+        }).unwrap();
+        effectivity_manager.set_effectivity(&Effectivity {
+            revision_id: rev_b,
+            from_date: Some(day(20)),
+            from_serial: Some("SN100".to_string()),
+            ..Effectivity::default()
+        }).unwrap();
+
+        let rev_a_effectivity = effectivity_manager.get_effectivity(rev_a).unwrap().unwrap();
+        assert_eq!(rev_a_effectivity.superseded_date, Some(day(20)));
+        assert_eq!(rev_a_effectivity.superseded_serial.as_deref(), Some("SN100"));
+
+        // Look up revisions by build date
+        assert!(effectivity_manager.get_revision_for_date(100001, day(5)).unwrap().is_none());
+        assert_eq!(effectivity_manager.get_revision_for_date(100001, day(15)).unwrap().unwrap().version, "A");
+        assert_eq!(effectivity_manager.get_revision_for_date(100001, day(20)).unwrap().unwrap().version, "B");
+
+        // Look up revisions by serial number; SN99 comes before SN100
+        assert_eq!(effectivity_manager.get_revision_for_serial(100001, "SN99").unwrap().unwrap().version, "A");
+        assert_eq!(effectivity_manager.get_revision_for_serial(100001, "SN100").unwrap().unwrap().version, "B");
+        assert_eq!(effectivity_manager.get_revision_for_serial(100001, "SN1000").unwrap().unwrap().version, "B");
+        assert!(effectivity_manager.get_revision_for_serial(100001, "SN42").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_compare_serials() {
+        assert_eq!(compare_serials("SN99", "SN100"), Ordering::Less);
+        assert_eq!(compare_serials("SN0100", "SN100"), Ordering::Equal);
+        assert_eq!(compare_serials("A100", "B1"), Ordering::Less);
+    }
+}
@@ -38,6 +38,7 @@ impl FileType {
     /// # Returns
     ///
     /// The corresponding FileType
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(type_str: &str) -> Self {
         match type_str {
             "Design" => Self::Design,
@@ -209,8 +210,7 @@ impl<'a> FileManager<'a> {
                 ],
             )?;
             Ok::<i64, DatabaseError>(conn.last_insert_rowid())
-        }).map_err(DatabaseError::from)
-    }
+        })}
     
     /// Create a new file in the database within an existing transaction
     ///
@@ -264,8 +264,7 @@ impl<'a> FileManager<'a> {
                 |row| self.row_to_file(row),
             )?;
             Ok::<File, DatabaseError>(file)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get all files for a part
     ///
@@ -294,8 +293,7 @@ impl<'a> FileManager<'a> {
                 files.push(file_result?);
             }
             Ok::<Vec<File>, DatabaseError>(files)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get all files for a revision
     ///
@@ -324,8 +322,7 @@ impl<'a> FileManager<'a> {
                 files.push(file_result?);
             }
             Ok::<Vec<File>, DatabaseError>(files)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get files by type for a part
     ///
@@ -355,8 +352,7 @@ impl<'a> FileManager<'a> {
                 files.push(file_result?);
             }
             Ok::<Vec<File>, DatabaseError>(files)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get files by type for a revision
     ///
@@ -386,8 +382,7 @@ impl<'a> FileManager<'a> {
                 files.push(file_result?);
             }
             Ok::<Vec<File>, DatabaseError>(files)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Update a file
     ///
@@ -422,8 +417,7 @@ impl<'a> FileManager<'a> {
                 ],
             )?;
             Ok::<(), DatabaseError>(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
     
     /// Update a file within an existing transaction
     ///
@@ -480,8 +474,7 @@ impl<'a> FileManager<'a> {
                 params![file_id],
             )?;
             Ok::<(), DatabaseError>(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
     
     /// Delete a file within an existing transaction
     ///
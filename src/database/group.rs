@@ -0,0 +1,328 @@
+//! Group module for Implexa
+//!
+//! This module provides functionality for managing groups of users in the database,
+//! such as the QA or Electrical teams that approval policies draw reviewers from.
+
+use rusqlite::{Transaction, params, Row, Result as SqliteResult, OptionalExtension};
+use crate::database::schema::{DatabaseResult, DatabaseError};
+use crate::database::connection_manager::ConnectionManager;
+
+/// Represents a group of users
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    /// Unique identifier for the group
+    pub group_id: Option<i64>,
+    /// Name of the group
+    pub name: String,
+    /// Description of the group
+    pub description: Option<String>,
+}
+
+impl Group {
+    /// Create a new group
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the group
+    /// * `description` - Description of the group
+    ///
+    /// # Returns
+    ///
+    /// A new Group instance
+    pub fn new(name: String, description: Option<String>) -> Self {
+        Self {
+            group_id: None,
+            name,
+            description,
+        }
+    }
+}
+
+/// Manager for group operations
+pub struct GroupManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+}
+
+impl<'a> GroupManager<'a> {
+    /// Create a new GroupManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new GroupManager instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Create a new group in the database
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - The group to create
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created group
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the group could not be created
+    pub fn create_group(&self, group: &Group) -> DatabaseResult<i64> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.create_group_in_transaction(group, tx)
+        })
+    }
+
+    /// Create a new group in the database within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - The group to create
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created group
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the group could not be created
+    pub fn create_group_in_transaction(&self, group: &Group, tx: &Transaction) -> DatabaseResult<i64> {
+        tx.execute(
+            "INSERT INTO Groups (name, description) VALUES (?1, ?2)",
+            params![group.name, group.description],
+        )?;
+        Ok(tx.last_insert_rowid())
+    }
+
+    /// Get a group by ID
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - The ID of the group
+    ///
+    /// # Returns
+    ///
+    /// The group
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the group could not be retrieved
+    pub fn get_group(&self, group_id: i64) -> DatabaseResult<Group> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let group = conn.query_row(
+                "SELECT group_id, name, description FROM Groups WHERE group_id = ?1",
+                params![group_id],
+                |row| self.row_to_group(row),
+            )?;
+            Ok(group)
+        })
+    }
+
+    /// Get a group by name within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the group
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The group, or None if no group has this name
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the group could not be retrieved
+    pub fn get_group_by_name_in_transaction(&self, name: &str, tx: &Transaction) -> DatabaseResult<Option<Group>> {
+        let group = tx.query_row(
+            "SELECT group_id, name, description FROM Groups WHERE name = ?1",
+            params![name],
+            |row| self.row_to_group(row),
+        ).optional()?;
+        Ok(group)
+    }
+
+    /// Get all groups
+    ///
+    /// # Returns
+    ///
+    /// A vector of all groups, ordered by name
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the groups could not be retrieved
+    pub fn get_all_groups(&self) -> DatabaseResult<Vec<Group>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare("SELECT group_id, name, description FROM Groups ORDER BY name")?;
+            let groups = stmt
+                .query_map([], |row| self.row_to_group(row))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(groups)
+        })
+    }
+
+    /// Delete a group and its memberships
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - The ID of the group to delete
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the group was successfully deleted
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the group could not be deleted
+    pub fn delete_group(&self, group_id: i64) -> DatabaseResult<()> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            tx.execute("DELETE FROM GroupMembers WHERE group_id = ?1", params![group_id])?;
+            tx.execute("DELETE FROM ApprovalPolicyGroups WHERE group_id = ?1", params![group_id])?;
+            tx.execute("DELETE FROM Groups WHERE group_id = ?1", params![group_id])?;
+            Ok(())
+        })
+    }
+
+    /// Add a member to a group
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - The ID of the group
+    /// * `member` - The username of the member
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the member was added or already belonged to the group
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the member could not be added
+    pub fn add_member(&self, group_id: i64, member: &str) -> DatabaseResult<()> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            conn.execute(
+                "INSERT OR IGNORE INTO GroupMembers (group_id, member) VALUES (?1, ?2)",
+                params![group_id, member],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Remove a member from a group
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - The ID of the group
+    /// * `member` - The username of the member
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the member was removed
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the member could not be removed
+    pub fn remove_member(&self, group_id: i64, member: &str) -> DatabaseResult<()> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            conn.execute(
+                "DELETE FROM GroupMembers WHERE group_id = ?1 AND member = ?2",
+                params![group_id, member],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Get the members of a group
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - The ID of the group
+    ///
+    /// # Returns
+    ///
+    /// The usernames of the members, ordered by name
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the members could not be retrieved
+    pub fn get_members(&self, group_id: i64) -> DatabaseResult<Vec<String>> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.get_members_in_transaction(group_id, tx)
+        })
+    }
+
+    /// Get the members of a group within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `group_id` - The ID of the group
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The usernames of the members, ordered by name
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the members could not be retrieved
+    pub fn get_members_in_transaction(&self, group_id: i64, tx: &Transaction) -> DatabaseResult<Vec<String>> {
+        let mut stmt = tx.prepare("SELECT member FROM GroupMembers WHERE group_id = ?1 ORDER BY member")?;
+        let members = stmt
+            .query_map(params![group_id], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(members)
+    }
+
+    /// Get the groups a user is a member of
+    ///
+    /// # Arguments
+    ///
+    /// * `member` - The username of the member
+    ///
+    /// # Returns
+    ///
+    /// The groups of the user, ordered by name
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the groups could not be retrieved
+    pub fn get_groups_for_member(&self, member: &str) -> DatabaseResult<Vec<Group>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT g.group_id, g.name, g.description
+                 FROM Groups g
+                 JOIN GroupMembers m ON m.group_id = g.group_id
+                 WHERE m.member = ?1
+                 ORDER BY g.name",
+            )?;
+            let groups = stmt
+                .query_map(params![member], |row| self.row_to_group(row))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(groups)
+        })
+    }
+
+    /// Convert a database row to a Group
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The database row
+    ///
+    /// # Returns
+    ///
+    /// A Group instance
+    ///
+    /// # Errors
+    ///
+    /// Returns a SqliteError if the row could not be converted
+    fn row_to_group(&self, row: &Row) -> SqliteResult<Group> {
+        Ok(Group {
+            group_id: Some(row.get(0)?),
+            name: row.get(1)?,
+            description: row.get(2)?,
+        })
+    }
+}
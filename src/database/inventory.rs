@@ -0,0 +1,925 @@
+//! Inventory module for Implexa
+//!
+//! This module provides functionality for tracking stock of internal parts across stock
+//! locations and lots, and for recording receive, issue, adjust and transfer transactions.
+
+use rusqlite::{Transaction, params, Row, Result as SqliteResult, OptionalExtension};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::database::schema::{DatabaseError, DatabaseResult};
+use crate::database::connection_manager::ConnectionManager;
+
+/// Type of an inventory transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InventoryTransactionType {
+    /// Stock received into a location
+    Receive,
+    /// Stock issued out of a location (e.g. to a build)
+    Issue,
+    /// Correction of the quantity on hand at a location (e.g. after a cycle count)
+    Adjust,
+    /// Stock moved from one location to another
+    Transfer,
+}
+
+impl InventoryTransactionType {
+    /// Convert a string to an InventoryTransactionType
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction_type` - The transaction type string
+    ///
+    /// # Returns
+    ///
+    /// The corresponding InventoryTransactionType
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(transaction_type: &str) -> Option<Self> {
+        match transaction_type {
+            "Receive" => Some(Self::Receive),
+            "Issue" => Some(Self::Issue),
+            "Adjust" => Some(Self::Adjust),
+            "Transfer" => Some(Self::Transfer),
+            _ => None,
+        }
+    }
+
+    /// Convert an InventoryTransactionType to a string
+    ///
+    /// # Returns
+    ///
+    /// The string representation of the InventoryTransactionType
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::Receive => "Receive",
+            Self::Issue => "Issue",
+            Self::Adjust => "Adjust",
+            Self::Transfer => "Transfer",
+        }
+    }
+}
+
+/// Represents a stock location (warehouse, shelf, bin, line-side stock, ...)
+#[derive(Debug, Clone)]
+pub struct StockLocation {
+    /// Unique identifier for the location
+    pub location_id: Option<i64>,
+    /// Name of the location
+    pub name: String,
+    /// Description of the location
+    pub description: Option<String>,
+}
+
+impl StockLocation {
+    /// Create a new stock location
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the location
+    /// * `description` - Description of the location
+    ///
+    /// # Returns
+    ///
+    /// A new StockLocation instance
+    pub fn new(name: String, description: Option<String>) -> Self {
+        Self {
+            location_id: None,
+            name,
+            description,
+        }
+    }
+}
+
+/// Stock of a part at a location, optionally for a single lot
+#[derive(Debug, Clone)]
+pub struct StockRecord {
+    /// Unique identifier for the stock record
+    pub stock_id: i64,
+    /// ID of the part
+    pub part_id: i64,
+    /// ID of the location
+    pub location_id: i64,
+    /// Lot number, if the stock is lot-controlled
+    pub lot_number: Option<String>,
+    /// Quantity physically on hand
+    pub quantity_on_hand: i64,
+    /// Quantity reserved for builds or orders
+    pub quantity_reserved: i64,
+}
+
+impl StockRecord {
+    /// Get the quantity available for new issues and reservations
+    ///
+    /// # Returns
+    ///
+    /// The quantity on hand minus the reserved quantity
+    pub fn quantity_available(&self) -> i64 {
+        self.quantity_on_hand - self.quantity_reserved
+    }
+}
+
+/// Stock totals of a part across all locations and lots
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StockLevel {
+    /// Quantity physically on hand
+    pub quantity_on_hand: i64,
+    /// Quantity reserved for builds or orders
+    pub quantity_reserved: i64,
+}
+
+impl StockLevel {
+    /// Get the quantity available for new issues and reservations
+    ///
+    /// # Returns
+    ///
+    /// The quantity on hand minus the reserved quantity
+    pub fn quantity_available(&self) -> i64 {
+        self.quantity_on_hand - self.quantity_reserved
+    }
+}
+
+/// Represents an inventory transaction
+#[derive(Debug, Clone)]
+pub struct InventoryTransaction {
+    /// Unique identifier for the transaction
+    pub transaction_id: Option<i64>,
+    /// ID of the part
+    pub part_id: i64,
+    /// Type of the transaction
+    pub transaction_type: InventoryTransactionType,
+    /// Quantity moved; signed for adjustments, positive otherwise
+    pub quantity: i64,
+    /// Location the stock leaves (Issue, Transfer)
+    pub from_location_id: Option<i64>,
+    /// Location the stock enters or is counted at (Receive, Adjust, Transfer)
+    pub to_location_id: Option<i64>,
+    /// Lot number of the stock
+    pub lot_number: Option<String>,
+    /// External reference, e.g. purchase order or work order number
+    pub reference: Option<String>,
+    /// Free-form notes
+    pub notes: Option<String>,
+    /// User who performed the transaction
+    pub performed_by: String,
+    /// Date of the transaction
+    pub date: SystemTime,
+}
+
+impl InventoryTransaction {
+    /// Create a new inventory transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - ID of the part
+    /// * `transaction_type` - Type of the transaction
+    /// * `quantity` - Quantity moved; signed for adjustments, positive otherwise
+    /// * `from_location_id` - Location the stock leaves
+    /// * `to_location_id` - Location the stock enters or is counted at
+    /// * `performed_by` - User who performed the transaction
+    ///
+    /// # Returns
+    ///
+    /// A new InventoryTransaction instance
+    pub fn new(
+        part_id: i64,
+        transaction_type: InventoryTransactionType,
+        quantity: i64,
+        from_location_id: Option<i64>,
+        to_location_id: Option<i64>,
+        performed_by: String,
+    ) -> Self {
+        Self {
+            transaction_id: None,
+            part_id,
+            transaction_type,
+            quantity,
+            from_location_id,
+            to_location_id,
+            lot_number: None,
+            reference: None,
+            notes: None,
+            performed_by,
+            date: SystemTime::now(),
+        }
+    }
+}
+
+/// Manager for inventory operations
+pub struct InventoryManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+}
+
+impl<'a> InventoryManager<'a> {
+    /// Create a new InventoryManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new InventoryManager instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Create a new stock location
+    ///
+    /// # Arguments
+    ///
+    /// * `location` - The location to create
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created location
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the location could not be created
+    pub fn create_location(&self, location: &StockLocation) -> DatabaseResult<i64> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            conn.execute(
+                "INSERT INTO StockLocations (name, description) VALUES (?1, ?2)",
+                params![location.name, location.description],
+            )?;
+            Ok::<i64, DatabaseError>(conn.last_insert_rowid())
+        })
+    }
+
+    /// Get a stock location by ID
+    ///
+    /// # Arguments
+    ///
+    /// * `location_id` - The ID of the location
+    ///
+    /// # Returns
+    ///
+    /// The location
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the location could not be retrieved
+    pub fn get_location(&self, location_id: i64) -> DatabaseResult<StockLocation> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let location = conn.query_row(
+                "SELECT location_id, name, description FROM StockLocations WHERE location_id = ?1",
+                params![location_id],
+                |row| self.row_to_location(row),
+            )?;
+            Ok::<StockLocation, DatabaseError>(location)
+        })
+    }
+
+    /// Get all stock locations
+    ///
+    /// # Returns
+    ///
+    /// A vector of all locations ordered by name
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the locations could not be retrieved
+    pub fn get_all_locations(&self) -> DatabaseResult<Vec<StockLocation>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT location_id, name, description FROM StockLocations ORDER BY name",
+            )?;
+            let locations_iter = stmt.query_map([], |row| self.row_to_location(row))?;
+            let mut locations = Vec::new();
+            for location_result in locations_iter {
+                locations.push(location_result?);
+            }
+            Ok::<Vec<StockLocation>, DatabaseError>(locations)
+        })
+    }
+
+    /// Delete a stock location
+    ///
+    /// # Arguments
+    ///
+    /// * `location_id` - The ID of the location to delete
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the location was successfully deleted
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the location still holds stock or could not be deleted
+    pub fn delete_location(&self, location_id: i64) -> DatabaseResult<()> {
+        self.connection_manager.execute_mut::<_, _, DatabaseError>(|conn| {
+            let on_hand: i64 = conn.query_row(
+                "SELECT COALESCE(SUM(quantity_on_hand), 0) FROM StockRecords WHERE location_id = ?1",
+                params![location_id],
+                |row| row.get(0),
+            )?;
+            if on_hand > 0 {
+                return Err(DatabaseError::ValidationError(format!(
+                    "Location {} still holds {} units of stock", location_id, on_hand
+                )));
+            }
+
+            conn.execute("DELETE FROM StockRecords WHERE location_id = ?1", params![location_id])?;
+            conn.execute("DELETE FROM StockLocations WHERE location_id = ?1", params![location_id])?;
+            Ok::<(), DatabaseError>(())
+        })
+    }
+
+    /// Receive stock into a location
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the part
+    /// * `location_id` - The location receiving the stock
+    /// * `quantity` - The quantity received
+    /// * `lot_number` - The lot number of the stock
+    /// * `performed_by` - The user receiving the stock
+    ///
+    /// # Returns
+    ///
+    /// The ID of the recorded transaction
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the transaction is invalid or could not be recorded
+    pub fn receive(&self, part_id: i64, location_id: i64, quantity: i64, lot_number: Option<&str>, performed_by: &str) -> DatabaseResult<i64> {
+        let mut transaction = InventoryTransaction::new(
+            part_id,
+            InventoryTransactionType::Receive,
+            quantity,
+            None,
+            Some(location_id),
+            performed_by.to_string(),
+        );
+        transaction.lot_number = lot_number.map(str::to_string);
+        self.record_transaction(&transaction)
+    }
+
+    /// Issue stock out of a location
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the part
+    /// * `location_id` - The location issuing the stock
+    /// * `quantity` - The quantity issued
+    /// * `lot_number` - The lot number of the stock
+    /// * `performed_by` - The user issuing the stock
+    ///
+    /// # Returns
+    ///
+    /// The ID of the recorded transaction
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if not enough unreserved stock is available or the transaction could not be recorded
+    pub fn issue(&self, part_id: i64, location_id: i64, quantity: i64, lot_number: Option<&str>, performed_by: &str) -> DatabaseResult<i64> {
+        let mut transaction = InventoryTransaction::new(
+            part_id,
+            InventoryTransactionType::Issue,
+            quantity,
+            Some(location_id),
+            None,
+            performed_by.to_string(),
+        );
+        transaction.lot_number = lot_number.map(str::to_string);
+        self.record_transaction(&transaction)
+    }
+
+    /// Adjust the quantity on hand at a location
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the part
+    /// * `location_id` - The location being adjusted
+    /// * `quantity_delta` - The signed change of the quantity on hand
+    /// * `lot_number` - The lot number of the stock
+    /// * `performed_by` - The user adjusting the stock
+    ///
+    /// # Returns
+    ///
+    /// The ID of the recorded transaction
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the adjustment would drop below the reserved quantity or could not be recorded
+    pub fn adjust(&self, part_id: i64, location_id: i64, quantity_delta: i64, lot_number: Option<&str>, performed_by: &str) -> DatabaseResult<i64> {
+        let mut transaction = InventoryTransaction::new(
+            part_id,
+            InventoryTransactionType::Adjust,
+            quantity_delta,
+            None,
+            Some(location_id),
+            performed_by.to_string(),
+        );
+        transaction.lot_number = lot_number.map(str::to_string);
+        self.record_transaction(&transaction)
+    }
+
+    /// Transfer stock between locations
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the part
+    /// * `from_location_id` - The location the stock leaves
+    /// * `to_location_id` - The location the stock enters
+    /// * `quantity` - The quantity transferred
+    /// * `lot_number` - The lot number of the stock
+    /// * `performed_by` - The user transferring the stock
+    ///
+    /// # Returns
+    ///
+    /// The ID of the recorded transaction
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if not enough unreserved stock is available or the transaction could not be recorded
+    pub fn transfer(
+        &self,
+        part_id: i64,
+        from_location_id: i64,
+        to_location_id: i64,
+        quantity: i64,
+        lot_number: Option<&str>,
+        performed_by: &str,
+    ) -> DatabaseResult<i64> {
+        let mut transaction = InventoryTransaction::new(
+            part_id,
+            InventoryTransactionType::Transfer,
+            quantity,
+            Some(from_location_id),
+            Some(to_location_id),
+            performed_by.to_string(),
+        );
+        transaction.lot_number = lot_number.map(str::to_string);
+        self.record_transaction(&transaction)
+    }
+
+    /// Record an inventory transaction and apply it to the stock records
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - The transaction to record
+    ///
+    /// # Returns
+    ///
+    /// The ID of the recorded transaction
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the transaction is invalid or could not be recorded
+    pub fn record_transaction(&self, transaction: &InventoryTransaction) -> DatabaseResult<i64> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.record_transaction_in_transaction(transaction, tx)
+        })
+    }
+
+    /// Record an inventory transaction and apply it to the stock records within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction` - The transaction to record
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The ID of the recorded transaction
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the transaction is invalid or could not be recorded
+    pub fn record_transaction_in_transaction(&self, transaction: &InventoryTransaction, tx: &Transaction) -> DatabaseResult<i64> {
+        let lot = transaction.lot_number.as_deref().unwrap_or("");
+        let quantity = transaction.quantity;
+        let location = |location_id: Option<i64>, role: &str| {
+            location_id.ok_or_else(|| DatabaseError::ValidationError(format!(
+                "{} transaction requires a {} location", transaction.transaction_type.to_str(), role
+            )))
+        };
+
+        match transaction.transaction_type {
+            InventoryTransactionType::Receive => {
+                Self::require_positive(quantity)?;
+                let to_location_id = location(transaction.to_location_id, "destination")?;
+                Self::change_on_hand(tx, transaction.part_id, to_location_id, lot, quantity)?;
+            }
+            InventoryTransactionType::Issue => {
+                Self::require_positive(quantity)?;
+                let from_location_id = location(transaction.from_location_id, "source")?;
+                Self::require_available(tx, transaction.part_id, from_location_id, lot, quantity)?;
+                Self::change_on_hand(tx, transaction.part_id, from_location_id, lot, -quantity)?;
+            }
+            InventoryTransactionType::Adjust => {
+                if quantity == 0 {
+                    return Err(DatabaseError::ValidationError("Adjustment quantity must not be zero".to_string()));
+                }
+                let to_location_id = location(transaction.to_location_id, "counted")?;
+                if quantity < 0 {
+                    Self::require_available(tx, transaction.part_id, to_location_id, lot, -quantity)?;
+                }
+                Self::change_on_hand(tx, transaction.part_id, to_location_id, lot, quantity)?;
+            }
+            InventoryTransactionType::Transfer => {
+                Self::require_positive(quantity)?;
+                let from_location_id = location(transaction.from_location_id, "source")?;
+                let to_location_id = location(transaction.to_location_id, "destination")?;
+                if from_location_id == to_location_id {
+                    return Err(DatabaseError::ValidationError("Transfer source and destination must differ".to_string()));
+                }
+                Self::require_available(tx, transaction.part_id, from_location_id, lot, quantity)?;
+                Self::change_on_hand(tx, transaction.part_id, from_location_id, lot, -quantity)?;
+                Self::change_on_hand(tx, transaction.part_id, to_location_id, lot, quantity)?;
+            }
+        }
+
+        tx.execute(
+            "INSERT INTO InventoryTransactions
+             (part_id, type, quantity, from_location_id, to_location_id, lot_number, reference, notes, performed_by, date)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                transaction.part_id,
+                transaction.transaction_type.to_str(),
+                quantity,
+                transaction.from_location_id,
+                transaction.to_location_id,
+                transaction.lot_number,
+                transaction.reference,
+                transaction.notes,
+                transaction.performed_by,
+                transaction.date.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64,
+            ],
+        )?;
+        Ok(tx.last_insert_rowid())
+    }
+
+    /// Reserve stock at a location
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the part
+    /// * `location_id` - The location holding the stock
+    /// * `quantity` - The quantity to reserve
+    /// * `lot_number` - The lot number of the stock
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the stock was successfully reserved
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if not enough unreserved stock is available
+    pub fn reserve(&self, part_id: i64, location_id: i64, quantity: i64, lot_number: Option<&str>) -> DatabaseResult<()> {
+        let lot = lot_number.unwrap_or("");
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            Self::require_positive(quantity)?;
+            Self::require_available(tx, part_id, location_id, lot, quantity)?;
+            tx.execute(
+                "UPDATE StockRecords SET quantity_reserved = quantity_reserved + ?4
+                 WHERE part_id = ?1 AND location_id = ?2 AND lot_number = ?3",
+                params![part_id, location_id, lot, quantity],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Release reserved stock at a location
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the part
+    /// * `location_id` - The location holding the stock
+    /// * `quantity` - The quantity to release
+    /// * `lot_number` - The lot number of the stock
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the reservation was successfully released
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if less than the given quantity is reserved
+    pub fn release_reservation(&self, part_id: i64, location_id: i64, quantity: i64, lot_number: Option<&str>) -> DatabaseResult<()> {
+        let lot = lot_number.unwrap_or("");
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            Self::require_positive(quantity)?;
+            let updated = tx.execute(
+                "UPDATE StockRecords SET quantity_reserved = quantity_reserved - ?4
+                 WHERE part_id = ?1 AND location_id = ?2 AND lot_number = ?3 AND quantity_reserved >= ?4",
+                params![part_id, location_id, lot, quantity],
+            )?;
+            if updated == 0 {
+                return Err(DatabaseError::ValidationError(format!(
+                    "Less than {} units of part {} are reserved at location {}", quantity, part_id, location_id
+                )));
+            }
+            Ok(())
+        })
+    }
+
+    /// Get all stock records of a part
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the part
+    ///
+    /// # Returns
+    ///
+    /// A vector of stock records with a non-zero quantity on hand
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the stock records could not be retrieved
+    pub fn get_stock_for_part(&self, part_id: i64) -> DatabaseResult<Vec<StockRecord>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT stock_id, part_id, location_id, lot_number, quantity_on_hand, quantity_reserved
+                 FROM StockRecords
+                 WHERE part_id = ?1 AND quantity_on_hand > 0
+                 ORDER BY location_id, lot_number",
+            )?;
+            let records_iter = stmt.query_map(params![part_id], |row| self.row_to_stock_record(row))?;
+            let mut records = Vec::new();
+            for record_result in records_iter {
+                records.push(record_result?);
+            }
+            Ok::<Vec<StockRecord>, DatabaseError>(records)
+        })
+    }
+
+    /// Get all stock records at a location
+    ///
+    /// # Arguments
+    ///
+    /// * `location_id` - The ID of the location
+    ///
+    /// # Returns
+    ///
+    /// A vector of stock records with a non-zero quantity on hand
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the stock records could not be retrieved
+    pub fn get_stock_at_location(&self, location_id: i64) -> DatabaseResult<Vec<StockRecord>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT stock_id, part_id, location_id, lot_number, quantity_on_hand, quantity_reserved
+                 FROM StockRecords
+                 WHERE location_id = ?1 AND quantity_on_hand > 0
+                 ORDER BY part_id, lot_number",
+            )?;
+            let records_iter = stmt.query_map(params![location_id], |row| self.row_to_stock_record(row))?;
+            let mut records = Vec::new();
+            for record_result in records_iter {
+                records.push(record_result?);
+            }
+            Ok::<Vec<StockRecord>, DatabaseError>(records)
+        })
+    }
+
+    /// Get the stock totals of a part across all locations and lots
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the part
+    ///
+    /// # Returns
+    ///
+    /// The stock level of the part
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the stock level could not be retrieved
+    pub fn get_stock_level(&self, part_id: i64) -> DatabaseResult<StockLevel> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let level = conn.query_row(
+                "SELECT COALESCE(SUM(quantity_on_hand), 0), COALESCE(SUM(quantity_reserved), 0)
+                 FROM StockRecords WHERE part_id = ?1",
+                params![part_id],
+                |row| Ok(StockLevel {
+                    quantity_on_hand: row.get(0)?,
+                    quantity_reserved: row.get(1)?,
+                }),
+            )?;
+            Ok::<StockLevel, DatabaseError>(level)
+        })
+    }
+
+    /// Get the transaction history of a part
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the part
+    ///
+    /// # Returns
+    ///
+    /// A vector of transactions, most recent first
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the transactions could not be retrieved
+    pub fn get_transactions_for_part(&self, part_id: i64) -> DatabaseResult<Vec<InventoryTransaction>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT transaction_id, part_id, type, quantity, from_location_id, to_location_id,
+                        lot_number, reference, notes, performed_by, date
+                 FROM InventoryTransactions
+                 WHERE part_id = ?1
+                 ORDER BY date DESC, transaction_id DESC",
+            )?;
+            let transactions_iter = stmt.query_map(params![part_id], |row| self.row_to_transaction(row))?;
+            let mut transactions = Vec::new();
+            for transaction_result in transactions_iter {
+                transactions.push(transaction_result?);
+            }
+            Ok::<Vec<InventoryTransaction>, DatabaseError>(transactions)
+        })
+    }
+
+    /// Check that a transaction quantity is positive
+    fn require_positive(quantity: i64) -> DatabaseResult<()> {
+        if quantity <= 0 {
+            return Err(DatabaseError::ValidationError(format!("Quantity must be positive, got {}", quantity)));
+        }
+        Ok(())
+    }
+
+    /// Check that enough unreserved stock is available at a location
+    fn require_available(tx: &Transaction, part_id: i64, location_id: i64, lot: &str, quantity: i64) -> DatabaseResult<()> {
+        let available: i64 = tx.query_row(
+            "SELECT quantity_on_hand - quantity_reserved FROM StockRecords
+             WHERE part_id = ?1 AND location_id = ?2 AND lot_number = ?3",
+            params![part_id, location_id, lot],
+            |row| row.get(0),
+        ).optional()?.unwrap_or(0);
+
+        if available < quantity {
+            return Err(DatabaseError::ValidationError(format!(
+                "Insufficient stock of part {} at location {}: {} available, {} requested",
+                part_id, location_id, available, quantity
+            )));
+        }
+        Ok(())
+    }
+
+    /// Apply a signed change to the quantity on hand, creating the stock record if needed
+    fn change_on_hand(tx: &Transaction, part_id: i64, location_id: i64, lot: &str, delta: i64) -> DatabaseResult<()> {
+        if delta < 0 {
+            // Decreases only apply to stock that was already checked to exist
+            tx.execute(
+                "UPDATE StockRecords SET quantity_on_hand = quantity_on_hand + ?4
+                 WHERE part_id = ?1 AND location_id = ?2 AND lot_number = ?3",
+                params![part_id, location_id, lot, delta],
+            )?;
+        } else {
+            tx.execute(
+                "INSERT INTO StockRecords (part_id, location_id, lot_number, quantity_on_hand)
+                 VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT(part_id, location_id, lot_number)
+                 DO UPDATE SET quantity_on_hand = quantity_on_hand + excluded.quantity_on_hand",
+                params![part_id, location_id, lot, delta],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Convert a database row to a StockLocation
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The database row
+    ///
+    /// # Returns
+    ///
+    /// A StockLocation instance
+    ///
+    /// # Errors
+    ///
+    /// Returns a SqliteError if the row could not be converted
+    fn row_to_location(&self, row: &Row) -> SqliteResult<StockLocation> {
+        Ok(StockLocation {
+            location_id: Some(row.get(0)?),
+            name: row.get(1)?,
+            description: row.get(2)?,
+        })
+    }
+
+    /// Convert a database row to a StockRecord
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The database row
+    ///
+    /// # Returns
+    ///
+    /// A StockRecord instance
+    ///
+    /// # Errors
+    ///
+    /// Returns a SqliteError if the row could not be converted
+    fn row_to_stock_record(&self, row: &Row) -> SqliteResult<StockRecord> {
+        let lot_number: String = row.get(3)?;
+
+        Ok(StockRecord {
+            stock_id: row.get(0)?,
+            part_id: row.get(1)?,
+            location_id: row.get(2)?,
+            lot_number: if lot_number.is_empty() { None } else { Some(lot_number) },
+            quantity_on_hand: row.get(4)?,
+            quantity_reserved: row.get(5)?,
+        })
+    }
+
+    /// Convert a database row to an InventoryTransaction
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The database row
+    ///
+    /// # Returns
+    ///
+    /// An InventoryTransaction instance
+    ///
+    /// # Errors
+    ///
+    /// Returns a SqliteError if the row could not be converted
+    fn row_to_transaction(&self, row: &Row) -> SqliteResult<InventoryTransaction> {
+        let type_str: String = row.get(2)?;
+        let transaction_type = InventoryTransactionType::from_str(&type_str)
+            .ok_or_else(|| rusqlite::Error::InvalidColumnType(2, "type".to_string(), rusqlite::types::Type::Text))?;
+        let date_secs: i64 = row.get(10)?;
+
+        Ok(InventoryTransaction {
+            transaction_id: Some(row.get(0)?),
+            part_id: row.get(1)?,
+            transaction_type,
+            quantity: row.get(3)?,
+            from_location_id: row.get(4)?,
+            to_location_id: row.get(5)?,
+            lot_number: row.get(6)?,
+            reference: row.get(7)?,
+            notes: row.get(8)?,
+            performed_by: row.get(9)?,
+            date: UNIX_EPOCH + std::time::Duration::from_secs(date_secs as u64),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::DatabaseManager;
+    use crate::database::part::{Part, PartManager};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_inventory_transactions() {
+        // Create a temporary directory for the test database
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+
+        // Create a new database manager and initialize the schema
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+
+        let part_manager = PartManager::new(db_manager.connection_manager());
+        let inventory_manager = InventoryManager::new(db_manager.connection_manager());
+
+        // Create a part and two locations
+        let part = Part::new(10001, "Electronic".to_string(), "Resistor".to_string(), "10K Resistor".to_string(), None);
+        part_manager.create_part(&part).unwrap();
+        let stores = inventory_manager.create_location(&StockLocation::new("Stores".to_string(), None)).unwrap();
+        let line = inventory_manager.create_location(&StockLocation::new("Line 1".to_string(), None)).unwrap();
+
+        // Receive two lots and move part of one to the line
+        inventory_manager.receive(10001, stores, 1000, Some("L2401"), "alice").unwrap();
+        inventory_manager.receive(10001, stores, 500, Some("L2402"), "alice").unwrap();
+        inventory_manager.transfer(10001, stores, line, 200, Some("L2401"), "bob").unwrap();
+
+        // Reserve stock on the line, then issue what is left unreserved
+        inventory_manager.reserve(10001, line, 150, Some("L2401")).unwrap();
+        assert!(inventory_manager.issue(10001, line, 100, Some("L2401"), "bob").is_err());
+        inventory_manager.issue(10001, line, 50, Some("L2401"), "bob").unwrap();
+
+        // A cycle count finds 5 units missing in stores
+        inventory_manager.adjust(10001, stores, -5, Some("L2402"), "carol").unwrap();
+
+        let level = inventory_manager.get_stock_level(10001).unwrap();
+        assert_eq!(level, StockLevel { quantity_on_hand: 1445, quantity_reserved: 150 });
+        assert_eq!(level.quantity_available(), 1295);
+
+        let line_stock = inventory_manager.get_stock_at_location(line).unwrap();
+        assert_eq!(line_stock.len(), 1);
+        assert_eq!(line_stock[0].lot_number.as_deref(), Some("L2401"));
+        assert_eq!(line_stock[0].quantity_available(), 0);
+
+        // Invalid transactions are rejected without touching stock
+        assert!(inventory_manager.transfer(10001, stores, stores, 1, None, "bob").is_err());
+        assert!(inventory_manager.issue(10001, stores, 1, None, "bob").is_err());
+        assert!(inventory_manager.release_reservation(10001, line, 151, Some("L2401")).is_err());
+        inventory_manager.release_reservation(10001, line, 150, Some("L2401")).unwrap();
+
+        // Every successful transaction is in the history, most recent first
+        let history = inventory_manager.get_transactions_for_part(10001).unwrap();
+        assert_eq!(history.len(), 5);
+        assert_eq!(history[0].transaction_type, InventoryTransactionType::Adjust);
+        assert_eq!(history[0].quantity, -5);
+    }
+}
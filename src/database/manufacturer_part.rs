@@ -3,6 +3,7 @@
 //! This module provides functionality for managing manufacturer parts in the database.
 
 use rusqlite::{Transaction, params, Row, Result as SqliteResult};
+use std::time::{SystemTime, UNIX_EPOCH};
 use crate::database::schema::{DatabaseError, DatabaseResult};
 use crate::database::connection_manager::ConnectionManager;
 
@@ -29,6 +30,7 @@ impl ManufacturerPartStatus {
     /// # Returns
     ///
     /// The corresponding ManufacturerPartStatus
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(status: &str) -> Option<Self> {
         match status {
             "Active" => Some(Self::Active),
@@ -69,6 +71,12 @@ pub struct ManufacturerPart {
     pub description: Option<String>,
     /// Status of the manufacturer part
     pub status: ManufacturerPartStatus,
+    /// Date the manufacturer declared the part Not Recommended for New Designs
+    pub nrnd_date: Option<SystemTime>,
+    /// Last date the part can be ordered from the manufacturer
+    pub last_time_buy_date: Option<SystemTime>,
+    /// Date the part reaches end of life and is no longer produced
+    pub eol_date: Option<SystemTime>,
 }
 
 impl ManufacturerPart {
@@ -99,8 +107,64 @@ impl ManufacturerPart {
             mpn,
             description,
             status,
+            nrnd_date: None,
+            last_time_buy_date: None,
+            eol_date: None,
         }
     }
+
+    /// Check whether the manufacturer part is still a usable source at a given time
+    ///
+    /// A source is usable when it is Active or Preferred and has not reached its EOL date.
+    ///
+    /// # Arguments
+    ///
+    /// * `at` - The point in time to evaluate
+    ///
+    /// # Returns
+    ///
+    /// true if the manufacturer part can still be sourced, false otherwise
+    pub fn is_available_at(&self, at: SystemTime) -> bool {
+        let usable_status = matches!(self.status, ManufacturerPartStatus::Active | ManufacturerPartStatus::Preferred);
+        let before_eol = self.eol_date.is_none_or(|eol| eol > at);
+        usable_status && before_eol
+    }
+
+    /// Check whether the last-time-buy date has passed at a given time
+    ///
+    /// # Arguments
+    ///
+    /// * `at` - The point in time to evaluate
+    ///
+    /// # Returns
+    ///
+    /// true if the last-time-buy date is set and has passed, false otherwise
+    pub fn is_past_last_time_buy(&self, at: SystemTime) -> bool {
+        self.last_time_buy_date.is_some_and(|ltb| ltb <= at)
+    }
+
+    /// Check whether the part has been declared NRND at a given time
+    ///
+    /// # Arguments
+    ///
+    /// * `at` - The point in time to evaluate
+    ///
+    /// # Returns
+    ///
+    /// true if the NRND date is set and has passed, false otherwise
+    pub fn is_nrnd(&self, at: SystemTime) -> bool {
+        self.nrnd_date.is_some_and(|nrnd| nrnd <= at)
+    }
+}
+
+/// Convert an optional timestamp to seconds since UNIX_EPOCH for SQLite
+fn to_epoch_secs(time: Option<SystemTime>) -> Option<i64> {
+    time.map(|t| t.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64)
+}
+
+/// Convert optional seconds since UNIX_EPOCH from SQLite to a timestamp
+fn from_epoch_secs(secs: Option<i64>) -> Option<SystemTime> {
+    secs.map(|s| UNIX_EPOCH + std::time::Duration::from_secs(s as u64))
 }
 
 /// Manager for manufacturer part operations
@@ -156,19 +220,21 @@ impl<'a> ManufacturerPartManager<'a> {
     pub fn create_manufacturer_part(&self, manufacturer_part: &ManufacturerPart) -> DatabaseResult<i64> {
         self.connection_manager.execute_mut::<_, _, DatabaseError>(|conn| {
             conn.execute(
-                "INSERT INTO ManufacturerParts (part_id, manufacturer, mpn, description, status)
-                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                "INSERT INTO ManufacturerParts (part_id, manufacturer, mpn, description, status, nrnd_date, last_time_buy_date, eol_date)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
                 params![
                     manufacturer_part.part_id,
                     manufacturer_part.manufacturer,
                     manufacturer_part.mpn,
                     manufacturer_part.description,
                     manufacturer_part.status.to_str(),
+                    to_epoch_secs(manufacturer_part.nrnd_date),
+                    to_epoch_secs(manufacturer_part.last_time_buy_date),
+                    to_epoch_secs(manufacturer_part.eol_date),
                 ],
             )?;
             Ok::<i64, DatabaseError>(conn.last_insert_rowid())
-        }).map_err(DatabaseError::from)
-    }
+        })}
     
     /// Create a new manufacturer part in the database within an existing transaction
     ///
@@ -186,14 +252,17 @@ impl<'a> ManufacturerPartManager<'a> {
     /// Returns a DatabaseError if the manufacturer part could not be created
     pub fn create_manufacturer_part_in_transaction(&self, manufacturer_part: &ManufacturerPart, tx: &Transaction) -> DatabaseResult<i64> {
         tx.execute(
-            "INSERT INTO ManufacturerParts (part_id, manufacturer, mpn, description, status)
-             VALUES (?1, ?2, ?3, ?4, ?5)",
+            "INSERT INTO ManufacturerParts (part_id, manufacturer, mpn, description, status, nrnd_date, last_time_buy_date, eol_date)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
             params![
                 manufacturer_part.part_id,
                 manufacturer_part.manufacturer,
                 manufacturer_part.mpn,
                 manufacturer_part.description,
                 manufacturer_part.status.to_str(),
+                to_epoch_secs(manufacturer_part.nrnd_date),
+                to_epoch_secs(manufacturer_part.last_time_buy_date),
+                to_epoch_secs(manufacturer_part.eol_date),
             ],
         )?;
         Ok(tx.last_insert_rowid())
@@ -215,15 +284,14 @@ impl<'a> ManufacturerPartManager<'a> {
     pub fn get_manufacturer_part(&self, mpn_id: i64) -> DatabaseResult<ManufacturerPart> {
         self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
             let manufacturer_part = conn.query_row(
-                "SELECT mpn_id, part_id, manufacturer, mpn, description, status
+                "SELECT mpn_id, part_id, manufacturer, mpn, description, status, nrnd_date, last_time_buy_date, eol_date
                  FROM ManufacturerParts
                  WHERE mpn_id = ?1",
                 params![mpn_id],
                 |row| self.row_to_manufacturer_part(row),
             )?;
             Ok::<ManufacturerPart, DatabaseError>(manufacturer_part)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get all manufacturer parts for a part
     ///
@@ -241,7 +309,7 @@ impl<'a> ManufacturerPartManager<'a> {
     pub fn get_manufacturer_parts_for_part(&self, part_id: &str) -> DatabaseResult<Vec<ManufacturerPart>> {
         self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
             let mut stmt = conn.prepare(
-                "SELECT mpn_id, part_id, manufacturer, mpn, description, status
+                "SELECT mpn_id, part_id, manufacturer, mpn, description, status, nrnd_date, last_time_buy_date, eol_date
                  FROM ManufacturerParts
                  WHERE part_id = ?1
                  ORDER BY manufacturer, mpn",
@@ -252,8 +320,7 @@ impl<'a> ManufacturerPartManager<'a> {
                 manufacturer_parts.push(manufacturer_part_result?);
             }
             Ok::<Vec<ManufacturerPart>, DatabaseError>(manufacturer_parts)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get manufacturer parts by manufacturer and MPN
     ///
@@ -272,7 +339,7 @@ impl<'a> ManufacturerPartManager<'a> {
     pub fn get_manufacturer_parts_by_mpn(&self, manufacturer: &str, mpn: &str) -> DatabaseResult<Vec<ManufacturerPart>> {
         self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
             let mut stmt = conn.prepare(
-                "SELECT mpn_id, part_id, manufacturer, mpn, description, status
+                "SELECT mpn_id, part_id, manufacturer, mpn, description, status, nrnd_date, last_time_buy_date, eol_date
                  FROM ManufacturerParts
                  WHERE manufacturer = ?1 AND mpn = ?2",
             )?;
@@ -282,8 +349,7 @@ impl<'a> ManufacturerPartManager<'a> {
                 manufacturer_parts.push(manufacturer_part_result?);
             }
             Ok::<Vec<ManufacturerPart>, DatabaseError>(manufacturer_parts)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Update a manufacturer part
     ///
@@ -306,7 +372,8 @@ impl<'a> ManufacturerPartManager<'a> {
         self.connection_manager.execute_mut::<_, _, DatabaseError>(|conn| {
             conn.execute(
                 "UPDATE ManufacturerParts
-                 SET part_id = ?2, manufacturer = ?3, mpn = ?4, description = ?5, status = ?6
+                 SET part_id = ?2, manufacturer = ?3, mpn = ?4, description = ?5, status = ?6,
+                     nrnd_date = ?7, last_time_buy_date = ?8, eol_date = ?9
                  WHERE mpn_id = ?1",
                 params![
                     mpn_id,
@@ -315,11 +382,13 @@ impl<'a> ManufacturerPartManager<'a> {
                     manufacturer_part.mpn,
                     manufacturer_part.description,
                     manufacturer_part.status.to_str(),
+                    to_epoch_secs(manufacturer_part.nrnd_date),
+                    to_epoch_secs(manufacturer_part.last_time_buy_date),
+                    to_epoch_secs(manufacturer_part.eol_date),
                 ],
             )?;
             Ok::<(), DatabaseError>(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
     
     /// Update a manufacturer part within an existing transaction
     ///
@@ -342,7 +411,8 @@ impl<'a> ManufacturerPartManager<'a> {
 
         tx.execute(
             "UPDATE ManufacturerParts
-             SET part_id = ?2, manufacturer = ?3, mpn = ?4, description = ?5, status = ?6
+             SET part_id = ?2, manufacturer = ?3, mpn = ?4, description = ?5, status = ?6,
+                 nrnd_date = ?7, last_time_buy_date = ?8, eol_date = ?9
              WHERE mpn_id = ?1",
             params![
                 mpn_id,
@@ -351,6 +421,9 @@ impl<'a> ManufacturerPartManager<'a> {
                 manufacturer_part.mpn,
                 manufacturer_part.description,
                 manufacturer_part.status.to_str(),
+                to_epoch_secs(manufacturer_part.nrnd_date),
+                to_epoch_secs(manufacturer_part.last_time_buy_date),
+                to_epoch_secs(manufacturer_part.eol_date),
             ],
         )?;
         Ok(())
@@ -376,8 +449,7 @@ impl<'a> ManufacturerPartManager<'a> {
                 params![mpn_id],
             )?;
             Ok::<(), DatabaseError>(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
     
     /// Delete a manufacturer part within an existing transaction
     ///
@@ -418,7 +490,7 @@ impl<'a> ManufacturerPartManager<'a> {
         self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
             let search_pattern = format!("%{}%", search_term);
             let mut stmt = conn.prepare(
-                "SELECT mpn_id, part_id, manufacturer, mpn, description, status
+                "SELECT mpn_id, part_id, manufacturer, mpn, description, status, nrnd_date, last_time_buy_date, eol_date
                  FROM ManufacturerParts
                  WHERE manufacturer LIKE ?1 OR mpn LIKE ?1
                  ORDER BY manufacturer, mpn",
@@ -429,8 +501,7 @@ impl<'a> ManufacturerPartManager<'a> {
                 manufacturer_parts.push(manufacturer_part_result?);
             }
             Ok::<Vec<ManufacturerPart>, DatabaseError>(manufacturer_parts)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Convert a database row to a ManufacturerPart
     ///
@@ -457,6 +528,9 @@ impl<'a> ManufacturerPartManager<'a> {
             mpn: row.get(3)?,
             description: row.get(4)?,
             status,
+            nrnd_date: from_epoch_secs(row.get(6)?),
+            last_time_buy_date: from_epoch_secs(row.get(7)?),
+            eol_date: from_epoch_secs(row.get(8)?),
         })
     }
 }
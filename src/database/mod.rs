@@ -8,27 +8,71 @@ pub mod schema;
 pub mod connection_manager;
 pub mod part;
 pub mod revision;
+pub mod revision_scheme;
+pub mod effectivity;
 pub mod relationship;
 pub mod property;
 pub mod manufacturer_part;
+pub mod obsolescence;
+pub mod supplier;
+pub mod cost_rollup;
+pub mod inventory;
+pub mod clear_to_build;
+pub mod bom_export;
+pub mod serialized_unit;
 pub mod approval;
+pub mod approval_policy;
+pub mod group;
+pub mod user;
+pub mod permission;
+pub mod change_order;
+pub mod deviation;
+pub mod change_impact;
 pub mod file;
 pub mod workflow;
+pub mod workflow_engine;
+pub mod workflow_definition;
 pub mod category;
 pub mod part_management;
+pub mod audit;
+pub mod signature;
+pub mod workspace;
+pub mod operation_journal;
 
 pub use schema::{DatabaseManager, DatabaseError, DatabaseResult};
 pub use connection_manager::ConnectionManager;
 pub use part::{Part, PartManager};
 pub use revision::{Revision, RevisionStatus, RevisionManager};
-pub use relationship::{Relationship, RelationshipType, RelationshipManager};
+pub use revision_scheme::{RevisionScheme, RevisionKey};
+pub use effectivity::{Effectivity, EffectivityManager};
+pub use relationship::{Relationship, RelationshipType, RelationshipManager, BomLine};
 pub use property::{Property, PropertyType, PropertyManager};
 pub use manufacturer_part::{ManufacturerPart, ManufacturerPartStatus, ManufacturerPartManager};
+pub use supplier::{Supplier, SupplierOffer, PriceBreak, OfferQuote, OfferCsvMapping, OfferImportResult, SupplierManager};
+pub use cost_rollup::{CostRollup, CostRollupLine, CostRollupNode, CostRollupManager};
+pub use inventory::{StockLocation, StockRecord, StockLevel, InventoryTransaction, InventoryTransactionType, InventoryManager};
+pub use clear_to_build::{ClearToBuildManager, ClearToBuildReport, ClearToBuildLine, AlternateAllocation, Shortage};
+pub use bom_export::BomExportManager;
+pub use serialized_unit::{SerializedUnit, AsBuiltComponent, SerializedUnitManager};
+pub use obsolescence::{ObsolescenceManager, ObsolescenceReport, ObsolescenceRisk, AtRiskPart, AffectedAssembly};
 pub use approval::{Approval, ApprovalStatus, ApprovalManager};
+pub use approval_policy::{ApprovalPolicy, ApprovalPolicyGroup, ApprovalDecision, ApprovalEvaluation, ApprovalPolicyManager};
+pub use group::{Group, GroupManager};
+pub use user::{User, UserRole, UserManager};
+pub use permission::{Permission, PermissionAction, GranteeType, PermissionManager};
+pub use change_order::{ChangeOrder, ChangeOrderStatus, ChangeOrderManager};
+pub use deviation::{Deviation, DeviationType, DeviationStatus, DeviationItem, DeviationNotice, DeviationManager};
+pub use change_impact::{ChangeFlags, ChangeRecommendation, WhereUsedEntry, ChangeImpactReport, ChangeImpactManager};
 pub use file::{File, FileType, FileManager};
-pub use workflow::{Workflow, WorkflowState, WorkflowTransition, WorkflowManager};
+pub use workflow::{Workflow, WorkflowState, WorkflowTransition, WorkflowManager, TransitionGuard, TransitionGuardType, TransitionAction, TransitionActionType, DEFAULT_PART_WORKFLOW};
+pub use workflow_engine::{WorkflowEngine, RevisionWorkflowState, GuardFailure, TransitionOutcome, WorkflowEvent};
+pub use workflow_definition::{WorkflowDefinition, StateDefinition, TransitionDefinition, RuleDefinition, WorkflowDefinitionManager};
 pub use category::{Category, Subcategory, CategoryManager};
-pub use part_management::{PartManagementManager, PartManagementError, PartManagementResult, User, UserRole};
+pub use part_management::{PartManagementManager, PartManagementError, PartManagementResult};
+pub use audit::{AuditAction, AuditEntry, AuditFilter, AuditVerification, AuditManager};
+pub use signature::{SignatureMeaning, ElectronicSignature, SignatureVerification, SigningKeyStore, SignatureManager};
+pub use workspace::{Workspace, WorkspaceMember, WorkspaceManager};
+pub use operation_journal::{CompensatingAction, JournalEntry, OperationJournal, RecoveredOperation, OperationJournalManager};
 
 /// Database module version
 pub const VERSION: &str = "0.1.0";
\ No newline at end of file
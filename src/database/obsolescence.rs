@@ -0,0 +1,408 @@
+//! Obsolescence module for Implexa
+//!
+//! This module provides functionality for evaluating manufacturer part lifecycle data
+//! and propagating obsolescence risk up the where-used tree to released assemblies.
+
+use rusqlite::{Transaction, params};
+use std::collections::{HashSet, VecDeque};
+use std::time::SystemTime;
+use crate::database::schema::{DatabaseError, DatabaseResult};
+use crate::database::connection_manager::ConnectionManager;
+use crate::database::manufacturer_part::{ManufacturerPart, ManufacturerPartManager};
+use crate::database::relationship::RelationshipType;
+use crate::database::revision::RevisionStatus;
+
+/// Level of obsolescence risk for an internal part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ObsolescenceRisk {
+    /// All remaining sources have been declared Not Recommended for New Designs
+    NotRecommended,
+    /// All remaining sources are past their last-time-buy date
+    LastTimeBuy,
+    /// No Active or Preferred source remains before end of life
+    NoSource,
+}
+
+impl ObsolescenceRisk {
+    /// Convert a string to an ObsolescenceRisk
+    ///
+    /// # Arguments
+    ///
+    /// * `risk` - The risk string
+    ///
+    /// # Returns
+    ///
+    /// The corresponding ObsolescenceRisk
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(risk: &str) -> Option<Self> {
+        match risk {
+            "NRND" => Some(Self::NotRecommended),
+            "Last Time Buy" => Some(Self::LastTimeBuy),
+            "No Source" => Some(Self::NoSource),
+            _ => None,
+        }
+    }
+
+    /// Convert an ObsolescenceRisk to a string
+    ///
+    /// # Returns
+    ///
+    /// The string representation of the ObsolescenceRisk
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::NotRecommended => "NRND",
+            Self::LastTimeBuy => "Last Time Buy",
+            Self::NoSource => "No Source",
+        }
+    }
+}
+
+/// An internal part whose manufacturer parts put future builds at risk
+#[derive(Debug, Clone)]
+pub struct AtRiskPart {
+    /// ID of the internal part
+    pub part_id: i64,
+    /// Risk level derived from the manufacturer part lifecycle data
+    pub risk: ObsolescenceRisk,
+    /// Manufacturer parts registered for the internal part
+    pub manufacturer_parts: Vec<ManufacturerPart>,
+}
+
+/// A released assembly that uses an at-risk part somewhere in its structure
+#[derive(Debug, Clone)]
+pub struct AffectedAssembly {
+    /// ID of the assembly part
+    pub part_id: i64,
+    /// ID of the released revision of the assembly
+    pub revision_id: i64,
+    /// Version of the released revision
+    pub version: String,
+    /// ID of the at-risk part used by the assembly
+    pub at_risk_part_id: i64,
+    /// Risk level of the at-risk part
+    pub risk: ObsolescenceRisk,
+    /// Part IDs from the at-risk part up to the assembly, inclusive
+    pub path: Vec<i64>,
+}
+
+/// Obsolescence risk report
+#[derive(Debug, Clone)]
+pub struct ObsolescenceReport {
+    /// Point in time the lifecycle dates were evaluated at
+    pub as_of: SystemTime,
+    /// Internal parts at risk
+    pub at_risk_parts: Vec<AtRiskPart>,
+    /// Released assemblies affected by the at-risk parts
+    pub affected_assemblies: Vec<AffectedAssembly>,
+}
+
+/// Manager for obsolescence risk operations
+pub struct ObsolescenceManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+}
+
+impl<'a> ObsolescenceManager<'a> {
+    /// Create a new ObsolescenceManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new ObsolescenceManager instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Evaluate the obsolescence risk of a single internal part
+    ///
+    /// Parts without any manufacturer parts (e.g. internal assemblies) are never at risk.
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the part to evaluate
+    /// * `as_of` - The point in time to evaluate lifecycle dates at
+    ///
+    /// # Returns
+    ///
+    /// The at-risk part, or None if the part is not at risk
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the manufacturer parts could not be retrieved
+    pub fn evaluate_part(&self, part_id: i64, as_of: SystemTime) -> DatabaseResult<Option<AtRiskPart>> {
+        let manufacturer_part_manager = ManufacturerPartManager::new(self.connection_manager);
+        let manufacturer_parts = manufacturer_part_manager.get_manufacturer_parts_for_part(&part_id.to_string())?;
+        if manufacturer_parts.is_empty() {
+            return Ok(None);
+        }
+
+        let available: Vec<&ManufacturerPart> = manufacturer_parts
+            .iter()
+            .filter(|mpn| mpn.is_available_at(as_of))
+            .collect();
+
+        let risk = if available.is_empty() {
+            ObsolescenceRisk::NoSource
+        } else if available.iter().all(|mpn| mpn.is_past_last_time_buy(as_of)) {
+            ObsolescenceRisk::LastTimeBuy
+        } else if available.iter().all(|mpn| mpn.is_nrnd(as_of)) {
+            ObsolescenceRisk::NotRecommended
+        } else {
+            return Ok(None);
+        };
+
+        Ok(Some(AtRiskPart {
+            part_id,
+            risk,
+            manufacturer_parts,
+        }))
+    }
+
+    /// Get all internal parts that are at risk
+    ///
+    /// # Arguments
+    ///
+    /// * `as_of` - The point in time to evaluate lifecycle dates at
+    ///
+    /// # Returns
+    ///
+    /// A vector of at-risk parts ordered by part ID
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the parts could not be evaluated
+    pub fn get_at_risk_parts(&self, as_of: SystemTime) -> DatabaseResult<Vec<AtRiskPart>> {
+        let part_ids = self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT DISTINCT part_id FROM ManufacturerParts ORDER BY part_id",
+            )?;
+            let part_ids_iter = stmt.query_map([], |row| row.get::<_, i64>(0))?;
+            let mut part_ids = Vec::new();
+            for part_id_result in part_ids_iter {
+                part_ids.push(part_id_result?);
+            }
+            Ok::<Vec<i64>, DatabaseError>(part_ids)
+        })?;
+
+        let mut at_risk_parts = Vec::new();
+        for part_id in part_ids {
+            if let Some(at_risk_part) = self.evaluate_part(part_id, as_of)? {
+                at_risk_parts.push(at_risk_part);
+            }
+        }
+        Ok(at_risk_parts)
+    }
+
+    /// Get every assembly that uses a part, directly or through sub-assemblies
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the part to look up
+    ///
+    /// # Returns
+    ///
+    /// A vector of (assembly part ID, path from the part up to the assembly) pairs
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the relationships could not be retrieved
+    pub fn get_where_used(&self, part_id: i64) -> DatabaseResult<Vec<(i64, Vec<i64>)>> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.get_where_used_in_transaction(part_id, tx)
+        })
+    }
+
+    /// Get every assembly that uses a part within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the part to look up
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// A vector of (assembly part ID, path from the part up to the assembly) pairs, nearest first
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the relationships could not be retrieved
+    pub fn get_where_used_in_transaction(&self, part_id: i64, tx: &Transaction) -> DatabaseResult<Vec<(i64, Vec<i64>)>> {
+        let mut stmt = tx.prepare(
+            "SELECT DISTINCT parent_part_id FROM Relationships
+             WHERE child_part_id = ?1 AND type = ?2
+             ORDER BY parent_part_id",
+        )?;
+
+        let mut where_used = Vec::new();
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        visited.insert(part_id);
+        queue.push_back(vec![part_id]);
+
+        while let Some(path) = queue.pop_front() {
+            let current = *path.last().unwrap_or(&part_id);
+            let parents_iter = stmt.query_map(
+                params![current, RelationshipType::Assembly.to_str()],
+                |row| row.get::<_, i64>(0),
+            )?;
+            let mut parents = Vec::new();
+            for parent_result in parents_iter {
+                parents.push(parent_result?);
+            }
+
+            for parent in parents {
+                // Guard against cycles in malformed structures
+                if !visited.insert(parent) {
+                    continue;
+                }
+                let mut parent_path = path.clone();
+                parent_path.push(parent);
+                where_used.push((parent, parent_path.clone()));
+                queue.push_back(parent_path);
+            }
+        }
+
+        Ok(where_used)
+    }
+
+    /// Generate an obsolescence report for all released assemblies
+    ///
+    /// # Arguments
+    ///
+    /// * `as_of` - The point in time to evaluate lifecycle dates at
+    ///
+    /// # Returns
+    ///
+    /// The obsolescence report
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the report could not be generated
+    pub fn get_obsolescence_report(&self, as_of: SystemTime) -> DatabaseResult<ObsolescenceReport> {
+        let at_risk_parts = self.get_at_risk_parts(as_of)?;
+
+        let mut affected_assemblies = Vec::new();
+        for at_risk_part in &at_risk_parts {
+            for (assembly_id, path) in self.get_where_used(at_risk_part.part_id)? {
+                for (revision_id, version) in self.get_released_revisions(assembly_id)? {
+                    affected_assemblies.push(AffectedAssembly {
+                        part_id: assembly_id,
+                        revision_id,
+                        version,
+                        at_risk_part_id: at_risk_part.part_id,
+                        risk: at_risk_part.risk.clone(),
+                        path: path.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(ObsolescenceReport {
+            as_of,
+            at_risk_parts,
+            affected_assemblies,
+        })
+    }
+
+    /// Get the released revisions of a part
+    fn get_released_revisions(&self, part_id: i64) -> DatabaseResult<Vec<(i64, String)>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT revision_id, version FROM Revisions
+                 WHERE part_id = ?1 AND status = ?2
+                 ORDER BY revision_id",
+            )?;
+            let revisions_iter = stmt.query_map(
+                params![part_id, RevisionStatus::Released.to_str()],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+            )?;
+            let mut revisions = Vec::new();
+            for revision_result in revisions_iter {
+                revisions.push(revision_result?);
+            }
+            Ok::<Vec<(i64, String)>, DatabaseError>(revisions)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::DatabaseManager;
+    use crate::database::part::{Part, PartManager};
+    use crate::database::relationship::{Relationship, RelationshipManager};
+    use crate::database::revision::{Revision, RevisionManager};
+    use crate::database::manufacturer_part::ManufacturerPartStatus;
+    use std::time::Duration;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_obsolescence_report_propagates_to_released_assemblies() {
+        // Create a temporary directory for the test database
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+
+        // Create a new database manager and initialize the schema
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let connection_manager = db_manager.connection_manager();
+
+        let part_manager = PartManager::new(connection_manager);
+        let relationship_manager = RelationshipManager::new(connection_manager);
+        let revision_manager = RevisionManager::new(connection_manager);
+        let manufacturer_part_manager = ManufacturerPartManager::new(connection_manager);
+        let obsolescence_manager = ObsolescenceManager::new(connection_manager);
+
+        // Create a resistor, a PCA using it, and a top-level assembly using the PCA
+        for (part_id, subcategory, name) in [
+            (10001, "Resistor", "10K Resistor"),
+            (10002, "PCA", "Controller Board"),
+            (10003, "Assembly", "Controller Unit"),
+        ] {
+            let part = Part::new(part_id, "Electronic".to_string(), subcategory.to_string(), name.to_string(), None);
+            part_manager.create_part(&part).unwrap();
+        }
+        relationship_manager.create_relationship(&Relationship::new(10002, 10001, RelationshipType::Assembly, 4)).unwrap();
+        relationship_manager.create_relationship(&Relationship::new(10003, 10002, RelationshipType::Assembly, 1)).unwrap();
+
+        // Only the top-level assembly is released
+        revision_manager.create_revision(&Revision::new(10002, "1".to_string(), RevisionStatus::Draft, "test".to_string(), None)).unwrap();
+        let released_id = revision_manager.create_revision(&Revision::new(10003, "1".to_string(), RevisionStatus::Released, "test".to_string(), None)).unwrap();
+
+        // The only source for the resistor reaches end of life
+        let now = SystemTime::now();
+        let mut mpn = ManufacturerPart::new(10001, "Yageo".to_string(), "RC0603FR-0710KL".to_string(), None, ManufacturerPartStatus::Preferred);
+        mpn.last_time_buy_date = Some(now - Duration::from_secs(86_400 * 30));
+        mpn.eol_date = Some(now + Duration::from_secs(86_400 * 30));
+        let mpn_id = manufacturer_part_manager.create_manufacturer_part(&mpn).unwrap();
+
+        // Before EOL the part is only past its last-time-buy date
+        let report = obsolescence_manager.get_obsolescence_report(now).unwrap();
+        assert_eq!(report.at_risk_parts.len(), 1);
+        assert_eq!(report.at_risk_parts[0].risk, ObsolescenceRisk::LastTimeBuy);
+        assert_eq!(report.affected_assemblies.len(), 1);
+        assert_eq!(report.affected_assemblies[0].part_id, 10003);
+        assert_eq!(report.affected_assemblies[0].revision_id, released_id);
+        assert_eq!(report.affected_assemblies[0].path, vec![10001, 10002, 10003]);
+
+        // After EOL no source is left
+        let report = obsolescence_manager.get_obsolescence_report(now + Duration::from_secs(86_400 * 60)).unwrap();
+        assert_eq!(report.at_risk_parts[0].risk, ObsolescenceRisk::NoSource);
+
+        // Adding an active alternate source clears the risk
+        let alternate = ManufacturerPart::new(10001, "Vishay".to_string(), "CRCW060310K0FKEA".to_string(), None, ManufacturerPartStatus::Active);
+        manufacturer_part_manager.create_manufacturer_part(&alternate).unwrap();
+        let report = obsolescence_manager.get_obsolescence_report(now).unwrap();
+        assert!(report.at_risk_parts.is_empty());
+        assert!(report.affected_assemblies.is_empty());
+
+        // Lifecycle dates round-trip through the database
+        let retrieved = manufacturer_part_manager.get_manufacturer_part(mpn_id).unwrap();
+        assert!(retrieved.eol_date.is_some());
+        assert!(retrieved.last_time_buy_date.is_some());
+        assert!(retrieved.nrnd_date.is_none());
+    }
+}
@@ -0,0 +1,789 @@
+//! Operation journal module for Implexa
+//!
+//! This module keeps the database and the Git repository in agreement when an operation changes
+//! both. When an operation starts, a journal entry holding the state of the repository is written
+//! to the Git directory. Once the operation has made its changes to the repository, the entry
+//! records the references and worktrees it created, moved or deleted, with their values before and
+//! after the operation. The
+//! database transaction of the operation then inserts a marker row for the entry, so the marker
+//! is committed if and only if the database changes are. If the operation fails, its changes to
+//! the repository are undone with compensating actions derived from the journal entry; a
+//! reference is only reset while it still has the value the operation left it at, so later
+//! changes made by others are kept. Entries left behind by a crash are resolved when the
+//! repository is opened again: operations whose marker was committed are kept and all others are
+//! rolled back, including operations interrupted before they recorded their changes, whose
+//! changes are recovered by comparing the repository with the state the operation started from.
+
+use git2::{Oid, Repository, ResetType, WorktreePruneOptions};
+use rusqlite::{Transaction, params};
+use serde::{Serialize, Deserialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::database::schema::{DatabaseError, DatabaseResult};
+use crate::database::connection_manager::ConnectionManager;
+use crate::git_backend::{GitBackendError, GitBackendManager};
+
+/// Name of the journal directory inside the Git directory of the repository
+const JOURNAL_DIRECTORY: &str = "implexa-journal";
+
+/// Action that undoes a change an operation made to the repository
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompensatingAction {
+    /// Remove a worktree created by the operation
+    RemoveWorktree {
+        /// Name of the worktree
+        name: String,
+    },
+    /// Delete a branch, tag or other reference created by the operation
+    DeleteReference {
+        /// Full name of the reference
+        reference: String,
+    },
+    /// Reset a reference moved or deleted by the operation to its previous target
+    ResetReference {
+        /// Full name of the reference
+        reference: String,
+        /// Object the reference pointed to before the operation
+        target: String,
+    },
+    /// Point HEAD back at the branch it was on before the operation
+    RestoreHead {
+        /// Full name of the branch
+        reference: String,
+    },
+}
+
+impl CompensatingAction {
+    /// Apply the action to a repository
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - The Git repository
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the repository could not be changed
+    pub fn apply(&self, repo: &Repository) -> DatabaseResult<()> {
+        match self {
+            CompensatingAction::RemoveWorktree { name } => {
+                if let Ok(worktree) = repo.find_worktree(name) {
+                    worktree.prune(Some(WorktreePruneOptions::new().valid(true).locked(true).working_tree(true)))
+                        .map_err(git_error)?;
+                }
+            }
+            CompensatingAction::DeleteReference { reference } => {
+                if let Ok(mut existing) = repo.find_reference(reference) {
+                    existing.delete().map_err(git_error)?;
+                }
+            }
+            CompensatingAction::ResetReference { reference, target } => {
+                let oid = Oid::from_str(target).map_err(git_error)?;
+
+                // A branch checked out somewhere is reset together with its working tree
+                match checked_out_in(repo, reference)? {
+                    Some(working_repo) => {
+                        let object = working_repo.find_object(oid, None).map_err(git_error)?;
+                        working_repo.reset(&object, ResetType::Hard, None).map_err(git_error)?;
+                    }
+                    None => {
+                        repo.reference(reference, oid, true, "Roll back interrupted operation")
+                            .map_err(git_error)?;
+                    }
+                }
+            }
+            CompensatingAction::RestoreHead { reference } => {
+                repo.set_head(reference).map_err(git_error)?;
+                if !repo.is_bare() {
+                    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+                        .map_err(git_error)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Change an operation made to a reference
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferenceChange {
+    /// Full name of the reference
+    pub reference: String,
+    /// Object the reference pointed to before the operation, or None if it did not exist
+    pub before: Option<String>,
+    /// Object the reference pointed to after the operation, or None if it was deleted
+    pub after: Option<String>,
+}
+
+/// Journal entry of an operation in progress
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Unique identifier of the operation
+    pub operation_id: String,
+    /// Name of the operation
+    pub operation: String,
+    /// Start of the operation in seconds since UNIX epoch
+    pub started_date: i64,
+    /// Whether the changes of the operation to the repository have been recorded
+    pub recorded: bool,
+    /// State of the repository before the operation
+    pub start: RepositoryState,
+    /// Branch HEAD pointed at before the operation, if HEAD was on a branch
+    pub head_before: Option<String>,
+    /// Branch HEAD pointed at after the operation, if HEAD was on a branch
+    pub head_after: Option<String>,
+    /// Branches, tags and notes created, moved or deleted by the operation
+    pub references: Vec<ReferenceChange>,
+    /// Names of the worktrees created by the operation
+    pub worktrees: BTreeSet<String>,
+}
+
+impl JournalEntry {
+    /// Get the actions that undo the recorded changes of the operation to a repository
+    ///
+    /// Worktrees are removed first so that the branches checked out in them can be deleted or
+    /// reset, and HEAD is restored last. A reference or HEAD that no longer has the value the
+    /// operation left it at has been changed since and is left alone. Worktrees removed by the
+    /// operation are not restored; they are recreated when their branch is next worked on.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - The Git repository
+    ///
+    /// # Returns
+    ///
+    /// The compensating actions in the order they must be applied
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the state of the repository could not be read
+    pub fn compensating_actions(&self, repo: &Repository) -> DatabaseResult<Vec<CompensatingAction>> {
+        let mut actions = Vec::new();
+
+        let worktrees = worktree_names(repo)?;
+        for name in self.worktrees.intersection(&worktrees) {
+            actions.push(CompensatingAction::RemoveWorktree { name: name.clone() });
+        }
+
+        for change in &self.references {
+            if reference_target(repo, &change.reference)? != change.after {
+                continue;
+            }
+            actions.push(match &change.before {
+                Some(target) => CompensatingAction::ResetReference {
+                    reference: change.reference.clone(),
+                    target: target.clone(),
+                },
+                None => CompensatingAction::DeleteReference { reference: change.reference.clone() },
+            });
+        }
+
+        if let Some(head) = self.head_before.as_ref().filter(|head| self.head_after.as_ref() != Some(*head)) {
+            if head_branch(repo)? == self.head_after {
+                actions.push(CompensatingAction::RestoreHead { reference: head.clone() });
+            }
+        }
+
+        Ok(actions)
+    }
+}
+
+/// State of a repository an operation starts from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepositoryState {
+    /// Branch HEAD points at, if HEAD is on a branch
+    pub head: Option<String>,
+    /// Targets of the branches, tags and notes
+    pub references: BTreeMap<String, String>,
+    /// Names of the worktrees
+    pub worktrees: BTreeSet<String>,
+}
+
+impl RepositoryState {
+    /// Read the state of a repository
+    fn read(repo: &Repository) -> DatabaseResult<Self> {
+        Ok(Self {
+            head: head_branch(repo)?,
+            references: journaled_references(repo)?,
+            worktrees: worktree_names(repo)?,
+        })
+    }
+}
+
+/// Journal of an operation in progress on a repository
+#[derive(Debug)]
+pub struct OperationJournal {
+    /// Path of the journal file
+    path: PathBuf,
+    /// The journal entry
+    entry: JournalEntry,
+}
+
+impl OperationJournal {
+    /// Begin an operation on a repository
+    ///
+    /// Writes a new journal file holding the state of the repository, which is flushed to disk
+    /// before this returns.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - The Git repository
+    /// * `operation` - Name of the operation
+    ///
+    /// # Returns
+    ///
+    /// The journal of the operation
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the state of the repository could not be read or the journal
+    /// could not be written
+    pub fn begin(repo: &Repository, operation: &str) -> DatabaseResult<Self> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let start = RepositoryState::read(repo)?;
+        let entry = JournalEntry {
+            // Sortable by start time, unique across processes
+            operation_id: format!("{:020}-{:09}-{}", now.as_secs(), now.subsec_nanos(), std::process::id()),
+            operation: operation.to_string(),
+            started_date: now.as_secs() as i64,
+            recorded: false,
+            head_before: start.head.clone(),
+            head_after: start.head.clone(),
+            references: Vec::new(),
+            worktrees: BTreeSet::new(),
+            start,
+        };
+
+        let directory = journal_directory(repo);
+        fs::create_dir_all(&directory)?;
+        let path = directory.join(format!("{}.json", entry.operation_id));
+        let journal = Self { path, entry };
+        journal.write()?;
+        Ok(journal)
+    }
+
+    /// Record the changes the operation made to a repository
+    ///
+    /// Must be called once the operation has made all its changes to the repository and before
+    /// its database changes are committed. The changes are flushed to disk before this returns.
+    /// Changes made by an operation interrupted before it recorded them are recorded the same
+    /// way when it is recovered.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - The Git repository
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the state of the repository could not be read or the journal
+    /// could not be written
+    pub fn record_changes(&mut self, repo: &Repository) -> DatabaseResult<()> {
+        let start = &self.entry.start;
+        let end = RepositoryState::read(repo)?;
+
+        let names: BTreeSet<&String> = start.references.keys().chain(end.references.keys()).collect();
+        self.entry.references = names
+            .into_iter()
+            .filter(|name| start.references.get(*name) != end.references.get(*name))
+            .map(|name| ReferenceChange {
+                reference: name.clone(),
+                before: start.references.get(name).cloned(),
+                after: end.references.get(name).cloned(),
+            })
+            .collect();
+        self.entry.worktrees = end.worktrees.difference(&start.worktrees).cloned().collect();
+        self.entry.head_after = end.head;
+        self.entry.recorded = true;
+        self.write()
+    }
+
+    /// Write the journal file, replacing it atomically
+    fn write(&self) -> DatabaseResult<()> {
+        let temp_path = self.path.with_extension("tmp");
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(&serde_json::to_vec_pretty(&self.entry)?)?;
+        file.sync_all()?;
+        fs::rename(&temp_path, &self.path)?;
+        Ok(())
+    }
+
+    /// Get the journal entry of the operation
+    pub fn entry(&self) -> &JournalEntry {
+        &self.entry
+    }
+
+    /// Mark the operation as committed within the database transaction of the operation
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - The database transaction of the operation
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the marker could not be inserted
+    pub fn commit_in_transaction(&self, tx: &Transaction) -> DatabaseResult<()> {
+        tx.execute(
+            "INSERT INTO OperationJournal (operation_id, operation, completed_date) VALUES (?1, ?2, ?3)",
+            params![
+                self.entry.operation_id,
+                self.entry.operation,
+                SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Finish a committed operation, discarding its journal
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager of the database the operation committed to
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the journal or its marker could not be removed
+    pub fn finish(self, connection_manager: &ConnectionManager) -> DatabaseResult<()> {
+        discard(&self.path, &self.entry.operation_id, connection_manager)
+    }
+
+    /// Roll back the changes a failed operation made to the repository
+    ///
+    /// Changes that have not been recorded yet are recorded first. The journal is kept if the
+    /// rollback fails, so that it is retried when the repository is opened again.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo` - The Git repository
+    ///
+    /// # Returns
+    ///
+    /// The compensating actions that were applied
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if a compensating action failed
+    pub fn roll_back(mut self, repo: &Repository) -> DatabaseResult<Vec<CompensatingAction>> {
+        if !self.entry.recorded {
+            self.record_changes(repo)?;
+        }
+        let actions = self.entry.compensating_actions(repo)?;
+        for action in &actions {
+            action.apply(repo)?;
+        }
+        fs::remove_file(&self.path)?;
+        Ok(actions)
+    }
+}
+
+/// Outcome of recovering an operation interrupted by a crash
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoveredOperation {
+    /// The journal entry of the operation
+    pub entry: JournalEntry,
+    /// Whether the database changes of the operation were committed, so that it was kept
+    pub committed: bool,
+    /// Whether changes of the operation to the repository were undone
+    pub rolled_back: bool,
+    /// The compensating actions that were applied
+    pub actions: Vec<CompensatingAction>,
+}
+
+/// Manager for journaled operations
+pub struct OperationJournalManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+    /// Git backend manager
+    git_manager: &'a GitBackendManager,
+}
+
+impl<'a> OperationJournalManager<'a> {
+    /// Create a new OperationJournalManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    /// * `git_manager` - Git backend manager
+    ///
+    /// # Returns
+    ///
+    /// A new OperationJournalManager instance
+    pub fn new(connection_manager: &'a ConnectionManager, git_manager: &'a GitBackendManager) -> Self {
+        Self {
+            connection_manager,
+            git_manager,
+        }
+    }
+
+    /// Run an operation that changes both the database and a repository
+    ///
+    /// The database changes are made within a transaction. If the operation fails, the
+    /// transaction is rolled back and the changes made to the repository are undone.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo_path` - Path to the Git repository
+    /// * `operation` - Name of the operation
+    /// * `f` - Function that performs the operation within the transaction
+    ///
+    /// # Returns
+    ///
+    /// The result of the operation
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the operation, or an error if the journal could not be written
+    pub fn run<F, T, E>(&self, repo_path: &Path, operation: &str, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&Transaction) -> Result<T, E>,
+        E: From<rusqlite::Error> + From<DatabaseError>,
+    {
+        let repo = self.git_manager.open_repository(repo_path).map_err(DatabaseError::from)?;
+        let mut journal = OperationJournal::begin(&repo, operation)?;
+
+        let result = self.connection_manager.transaction::<_, _, E>(|tx| {
+            let value = f(tx)?;
+            journal.record_changes(&repo)?;
+            journal.commit_in_transaction(tx)?;
+            Ok(value)
+        });
+
+        match result {
+            Ok(value) => {
+                // The operation is committed; a journal left behind is discarded on recovery
+                if let Err(e) = journal.finish(self.connection_manager) {
+                    log::warn!("Failed to discard journal of operation {}: {}", operation, e);
+                }
+                Ok(value)
+            }
+            Err(err) => {
+                // The rollback is retried on recovery if it fails now
+                if let Err(e) = journal.roll_back(&repo) {
+                    log::error!("Failed to roll back operation {}: {}", operation, e);
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Get the journal entries of operations that did not finish
+    ///
+    /// # Arguments
+    ///
+    /// * `repo_path` - Path to the Git repository
+    ///
+    /// # Returns
+    ///
+    /// The journal entries, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the repository or a journal could not be read
+    pub fn get_pending_operations(&self, repo_path: &Path) -> DatabaseResult<Vec<JournalEntry>> {
+        let repo = self.git_manager.open_repository(repo_path)?;
+        Ok(pending_journals(&repo)?.into_iter().map(|(_, entry)| entry).collect())
+    }
+
+    /// Recover the operations interrupted by a crash
+    ///
+    /// Operations whose database changes were committed are kept and their journals discarded.
+    /// The repository changes of all other operations are undone, newest operation first. The
+    /// changes of an operation interrupted before it recorded them are found by comparing the
+    /// repository with the state the operation started from.
+    ///
+    /// # Arguments
+    ///
+    /// * `repo_path` - Path to the Git repository
+    ///
+    /// # Returns
+    ///
+    /// The recovered operations
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if an operation could not be recovered
+    pub fn recover(&self, repo_path: &Path) -> DatabaseResult<Vec<RecoveredOperation>> {
+        let repo = self.git_manager.open_repository(repo_path)?;
+
+        let mut recovered = Vec::new();
+        for (path, entry) in pending_journals(&repo)?.into_iter().rev() {
+            let committed = self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+                let count: i64 = conn.query_row(
+                    "SELECT COUNT(*) FROM OperationJournal WHERE operation_id = ?1",
+                    params![entry.operation_id],
+                    |row| row.get(0),
+                )?;
+                Ok(count > 0)
+            })?;
+
+            if committed {
+                discard(&path, &entry.operation_id, self.connection_manager)?;
+                recovered.push(RecoveredOperation {
+                    entry,
+                    committed: true,
+                    rolled_back: false,
+                    actions: Vec::new(),
+                });
+            } else {
+                let mut journal = OperationJournal { path, entry };
+                if !journal.entry.recorded {
+                    journal.record_changes(&repo)?;
+                }
+                let entry = journal.entry.clone();
+                let actions = journal.roll_back(&repo)?;
+                recovered.push(RecoveredOperation {
+                    entry,
+                    committed: false,
+                    rolled_back: !actions.is_empty(),
+                    actions,
+                });
+            }
+        }
+
+        Ok(recovered)
+    }
+}
+
+/// Get the journal directory of a repository
+///
+/// Journals are kept in the Git directory of the main working tree, which is shared by all
+/// worktrees.
+fn journal_directory(repo: &Repository) -> PathBuf {
+    let git_dir = if repo.is_worktree() {
+        repo.path().parent().and_then(Path::parent).unwrap_or(repo.path())
+    } else {
+        repo.path()
+    };
+    git_dir.join(JOURNAL_DIRECTORY)
+}
+
+/// Read the journals of a repository, oldest first
+fn pending_journals(repo: &Repository) -> DatabaseResult<Vec<(PathBuf, JournalEntry)>> {
+    let directory = journal_directory(repo);
+    if !directory.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(&directory)? {
+        let path = entry?.path();
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => paths.push(path),
+            // A journal that was never completed belongs to an operation that never started
+            Some("tmp") => fs::remove_file(&path)?,
+            _ => {}
+        }
+    }
+    paths.sort();
+
+    let mut journals = Vec::new();
+    for path in paths {
+        let entry = serde_json::from_slice(&fs::read(&path)?)?;
+        journals.push((path, entry));
+    }
+    Ok(journals)
+}
+
+/// Remove the journal and the marker of a committed operation
+fn discard(path: &Path, operation_id: &str, connection_manager: &ConnectionManager) -> DatabaseResult<()> {
+    fs::remove_file(path)?;
+    connection_manager.execute::<_, _, DatabaseError>(|conn| {
+        conn.execute("DELETE FROM OperationJournal WHERE operation_id = ?1", params![operation_id])?;
+        Ok(())
+    })
+}
+
+/// Get the targets of the branches, tags and notes of a repository
+fn journaled_references(repo: &Repository) -> DatabaseResult<BTreeMap<String, String>> {
+    let mut references = BTreeMap::new();
+    for reference in repo.references().map_err(git_error)? {
+        let reference = reference.map_err(git_error)?;
+        let (Some(name), Some(target)) = (reference.name(), reference.target()) else {
+            continue;
+        };
+        if ["refs/heads/", "refs/tags/", "refs/notes/"].iter().any(|prefix| name.starts_with(prefix)) {
+            references.insert(name.to_string(), target.to_string());
+        }
+    }
+    Ok(references)
+}
+
+/// Get the target of a reference, or None if it does not exist
+fn reference_target(repo: &Repository, reference: &str) -> DatabaseResult<Option<String>> {
+    match repo.find_reference(reference) {
+        Ok(existing) => Ok(existing.target().map(|target| target.to_string())),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(git_error(e)),
+    }
+}
+
+/// Get the names of the worktrees of a repository
+fn worktree_names(repo: &Repository) -> DatabaseResult<BTreeSet<String>> {
+    Ok(repo.worktrees().map_err(git_error)?
+        .iter()
+        .flatten()
+        .map(str::to_string)
+        .collect())
+}
+
+/// Get the branch HEAD points at, if HEAD is on a branch
+fn head_branch(repo: &Repository) -> DatabaseResult<Option<String>> {
+    let head = repo.find_reference("HEAD").map_err(git_error)?;
+    Ok(head.symbolic_target().map(str::to_string))
+}
+
+/// Open the working tree a branch is checked out in, if any
+fn checked_out_in(repo: &Repository, reference: &str) -> DatabaseResult<Option<Repository>> {
+    if !repo.is_bare() && head_branch(repo)?.as_deref() == Some(reference) {
+        return Repository::open(repo.path()).map(Some).map_err(git_error);
+    }
+    for name in worktree_names(repo)? {
+        let Ok(worktree) = repo.find_worktree(&name) else {
+            continue;
+        };
+        if worktree.validate().is_err() {
+            continue;
+        }
+        let worktree_repo = Repository::open_from_worktree(&worktree).map_err(git_error)?;
+        if head_branch(&worktree_repo)?.as_deref() == Some(reference) {
+            return Ok(Some(worktree_repo));
+        }
+    }
+    Ok(None)
+}
+
+/// Convert a git2 error to a DatabaseError
+fn git_error(e: git2::Error) -> DatabaseError {
+    DatabaseError::GitBackend(GitBackendError::GitError(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::DatabaseManager;
+    use crate::git_backend::{AuthConfig, GitBackendConfig};
+    use tempfile::tempdir;
+
+    /// Create the main branch with an initial commit and point HEAD at it
+    fn init_main(repo: &Repository) -> Oid {
+        let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        let commit_id = repo.commit(Some("refs/heads/main"), &sig, &sig, "Initial commit", &tree, &[]).unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+        commit_id
+    }
+    
+    /// Commit an empty change to the branch HEAD points at
+    fn commit(repo: &Repository, message: &str) -> Oid {
+        let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &[&parent]).unwrap()
+    }
+
+    #[test]
+    fn test_failed_operation_is_rolled_back() {
+        let temp_dir = tempdir().unwrap();
+        let db_manager = DatabaseManager::new(temp_dir.path().join("test.db")).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let repo_dir = tempdir().unwrap();
+        let git_manager = GitBackendManager::new(GitBackendConfig::default(), AuthConfig::default()).unwrap();
+        let repo = git_manager.init_repository(repo_dir.path()).unwrap();
+        let main = init_main(&repo);
+        let journal_manager = OperationJournalManager::new(db_manager.connection_manager(), &git_manager);
+
+        // Branch, worktree, commit on main and database change all disappear on failure
+        let result = journal_manager.run::<_, (), DatabaseError>(repo_dir.path(), "test", |tx| {
+            tx.execute("INSERT INTO Workspaces (name, created_by, created_date, updated_date) VALUES ('ws', 'test', 0, 0)", [])?;
+            git_manager.create_branch(&repo, "part/EL-RES-100001/draft")?;
+            git_manager.worktree_manager(&repo).open_worktree("part/EL-RES-100001/draft")?;
+            commit(&repo, "Merge release");
+            Err(DatabaseError::ValidationError("Release failed".to_string()))
+        });
+        assert!(result.is_err());
+        assert!(repo.find_branch("part/EL-RES-100001/draft", git2::BranchType::Local).is_err());
+        assert!(repo.worktrees().unwrap().is_empty());
+        assert_eq!(repo.head().unwrap().target(), Some(main));
+        let count: i64 = db_manager.connection_manager().execute::<_, _, DatabaseError>(|conn| {
+            Ok(conn.query_row("SELECT COUNT(*) FROM Workspaces", [], |row| row.get(0))?)
+        }).unwrap();
+        assert_eq!(count, 0);
+        assert!(journal_manager.get_pending_operations(repo_dir.path()).unwrap().is_empty());
+
+        // A successful operation keeps its changes and leaves no journal behind
+        journal_manager.run::<_, (), DatabaseError>(repo_dir.path(), "test", |_| {
+            git_manager.create_branch(&repo, "part/EL-RES-100001/draft")?;
+            Ok(())
+        }).unwrap();
+        assert!(repo.find_branch("part/EL-RES-100001/draft", git2::BranchType::Local).is_ok());
+        assert!(journal_manager.get_pending_operations(repo_dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_recover_interrupted_operations() {
+        let temp_dir = tempdir().unwrap();
+        let db_manager = DatabaseManager::new(temp_dir.path().join("test.db")).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let repo_dir = tempdir().unwrap();
+        let git_manager = GitBackendManager::new(GitBackendConfig::default(), AuthConfig::default()).unwrap();
+        let repo = git_manager.init_repository(repo_dir.path()).unwrap();
+        let main = init_main(&repo);
+        let journal_manager = OperationJournalManager::new(db_manager.connection_manager(), &git_manager);
+
+        // An operation that crashed after its database changes were committed
+        let mut committed = OperationJournal::begin(&repo, "create_part").unwrap();
+        git_manager.create_branch(&repo, "part/EL-RES-100001/draft").unwrap();
+        committed.record_changes(&repo).unwrap();
+        db_manager.connection_manager().transaction::<_, _, DatabaseError>(|tx| {
+            committed.commit_in_transaction(tx)
+        }).unwrap();
+
+        // An operation that crashed before its database changes were committed
+        let mut interrupted = OperationJournal::begin(&repo, "release_revision").unwrap();
+        git_manager.create_branch(&repo, "part/EL-RES-100002/draft").unwrap();
+        git_manager.create_branch(&repo, "part/EL-RES-100003/draft").unwrap();
+        let merge = commit(&repo, "Merge release");
+        interrupted.record_changes(&repo).unwrap();
+        let interrupted_id = interrupted.entry().operation_id.clone();
+        drop(interrupted);
+
+        // Changes made after the crash are kept by the recovery
+        repo.reference("refs/heads/part/EL-RES-100003/draft", merge, true, "Moved").unwrap();
+        repo.reference("refs/tags/unrelated", merge, false, "Unrelated tag").unwrap();
+
+        // An operation that crashed before it recorded its changes to the repository
+        let unrecorded = OperationJournal::begin(&repo, "create_revision").unwrap();
+        let unrecorded_id = unrecorded.entry().operation_id.clone();
+        git_manager.create_branch(&repo, "part/EL-RES-100004/draft").unwrap();
+        drop(unrecorded);
+
+        assert_eq!(journal_manager.get_pending_operations(repo_dir.path()).unwrap().len(), 3);
+        let recovered = journal_manager.recover(repo_dir.path()).unwrap();
+        assert_eq!(recovered.len(), 3);
+        assert_eq!(recovered[0].entry.operation_id, unrecorded_id);
+        assert!(!recovered[0].committed);
+        assert!(recovered[0].rolled_back);
+        assert_eq!(recovered[0].actions, vec![CompensatingAction::DeleteReference {
+            reference: "refs/heads/part/EL-RES-100004/draft".to_string(),
+        }]);
+        assert_eq!(recovered[1].entry.operation_id, interrupted_id);
+        assert!(recovered[1].rolled_back);
+        assert!(recovered[1].actions.contains(&CompensatingAction::DeleteReference {
+            reference: "refs/heads/part/EL-RES-100002/draft".to_string(),
+        }));
+        assert!(!recovered[1].actions.iter().any(|action| matches!(action,
+            CompensatingAction::DeleteReference { reference } if reference.contains("EL-RES-100003"))));
+        assert!(recovered[2].committed);
+        assert!(!recovered[2].rolled_back);
+
+        assert!(repo.find_branch("part/EL-RES-100001/draft", git2::BranchType::Local).is_ok());
+        assert!(repo.find_branch("part/EL-RES-100002/draft", git2::BranchType::Local).is_err());
+        assert!(repo.find_branch("part/EL-RES-100003/draft", git2::BranchType::Local).is_ok());
+        assert!(repo.find_branch("part/EL-RES-100004/draft", git2::BranchType::Local).is_err());
+        assert!(repo.find_reference("refs/tags/unrelated").is_ok());
+        assert_eq!(repo.head().unwrap().target(), Some(main));
+        assert!(journal_manager.get_pending_operations(repo_dir.path()).unwrap().is_empty());
+        assert!(journal_manager.recover(repo_dir.path()).unwrap().is_empty());
+    }
+}
@@ -6,6 +6,7 @@ use rusqlite::{Transaction, params, Row, Result as SqliteResult};
 use std::time::{SystemTime, UNIX_EPOCH};
 use crate::database::schema::{DatabaseResult, DatabaseError};
 use crate::database::connection_manager::ConnectionManager;
+use crate::database::audit::{AuditAction, AuditManager, DEFAULT_ACTOR};
 
 /// Represents a part in the system
 #[derive(Debug, Clone)]
@@ -161,6 +162,8 @@ pub fn display_part_number_in_transaction(&self, tx: &Transaction) -> String {
 pub struct PartManager<'a> {
     /// Connection manager for the SQLite database
     connection_manager: &'a ConnectionManager,
+    /// User recorded in the audit log for the changes made through this manager
+    actor: String,
 }
 
 impl<'a> PartManager<'a> {
@@ -174,7 +177,24 @@ impl<'a> PartManager<'a> {
     ///
     /// A new PartManager instance
     pub fn new(connection_manager: &'a ConnectionManager) -> Self {
-        Self { connection_manager }
+        Self {
+            connection_manager,
+            actor: DEFAULT_ACTOR.to_string(),
+        }
+    }
+    
+    /// Set the user recorded in the audit log for the changes made through this manager
+    ///
+    /// # Arguments
+    ///
+    /// * `actor` - Username of the user making the changes
+    ///
+    /// # Returns
+    ///
+    /// The PartManager recording changes as made by the user
+    pub fn with_actor(mut self, actor: &str) -> Self {
+        self.actor = actor.to_string();
+        self
     }
     
     /// Create a new PartManager with a transaction
@@ -331,9 +351,10 @@ impl<'a> PartManager<'a> {
                     modified_secs,
                 ],
             )?;
+            AuditManager::new(self.connection_manager)
+                .record(conn, &self.actor, "Part", part.part_id, AuditAction::Create, Some(Self::audit_details(part)))?;
             Ok::<(), DatabaseError>(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
     
     /// Create a part in the database with a specific part_id within an existing transaction
     ///
@@ -374,6 +395,8 @@ impl<'a> PartManager<'a> {
                 modified_secs,
             ],
         )?;
+        AuditManager::new(self.connection_manager)
+            .record(tx, &self.actor, "Part", part.part_id, AuditAction::Create, Some(Self::audit_details(part)))?;
         Ok(())
     }
 
@@ -400,8 +423,7 @@ impl<'a> PartManager<'a> {
                 |row| self.row_to_part(row),
             )?;
             Ok::<Part, DatabaseError>(part)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get a part by its ID within an existing transaction
     ///
@@ -450,8 +472,7 @@ impl<'a> PartManager<'a> {
                 parts.push(part_result?);
             }
             Ok::<Vec<Part>, DatabaseError>(parts)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get parts by category
     ///
@@ -480,8 +501,7 @@ impl<'a> PartManager<'a> {
                 parts.push(part_result?);
             }
             Ok::<Vec<Part>, DatabaseError>(parts)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Update a part
     ///
@@ -517,9 +537,10 @@ impl<'a> PartManager<'a> {
                     modified_secs,
                 ],
             )?;
+            AuditManager::new(self.connection_manager)
+                .record(conn, &self.actor, "Part", part.part_id, AuditAction::Update, Some(Self::audit_details(part)))?;
             Ok::<(), DatabaseError>(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Delete a part
     ///
@@ -536,13 +557,18 @@ impl<'a> PartManager<'a> {
     /// Returns a DatabaseError if the part could not be deleted
     pub fn delete_part(&self, part_id: i64) -> DatabaseResult<()> {
         self.connection_manager.execute_mut::<_, _, DatabaseError>(|conn| {
+            conn.execute(
+                "DELETE FROM WorkspaceParts WHERE part_id = ?1",
+                params![part_id],
+            )?;
             conn.execute(
                 "DELETE FROM Parts WHERE part_id = ?1",
                 params![part_id],
             )?;
+            AuditManager::new(self.connection_manager)
+                .record(conn, &self.actor, "Part", part_id, AuditAction::Delete, None)?;
             Ok::<(), DatabaseError>(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get parts by display part number
     ///
@@ -583,8 +609,7 @@ impl<'a> PartManager<'a> {
                 parts.push(part_result?);
             }
             Ok::<Vec<Part>, DatabaseError>(parts)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Convert a database row to a Part
     ///
@@ -617,6 +642,16 @@ impl<'a> PartManager<'a> {
             modified_date,
         })
     }
+
+    /// Describe a part for the audit log
+    fn audit_details(part: &Part) -> serde_json::Value {
+        serde_json::json!({
+            "category": part.category,
+            "subcategory": part.subcategory,
+            "name": part.name,
+            "description": part.description,
+        })
+    }
 }
 
 #[cfg(test)]
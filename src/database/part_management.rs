@@ -3,12 +3,29 @@
 //! This module provides functionality for managing parts throughout their lifecycle,
 //! including creation, status transitions, and workflow enforcement.
 use crate::database::schema::DatabaseError;
+pub use crate::database::user::{User, UserRole};
 use crate::database::part::{Part, PartManager};
 use crate::database::revision::{Revision, RevisionStatus, RevisionManager};
 use crate::database::approval::{Approval, ApprovalStatus, ApprovalManager};
-use crate::git_backend::{GitBackendManager, GitBackendError};
+use crate::database::approval_policy::{ApprovalPolicyManager, ApprovalDecision};
+use crate::database::workflow::{WorkflowState, WorkflowManager, TransitionActionType, TransitionGuardType};
+use crate::database::workflow_engine::{WorkflowEngine, GuardFailure, TransitionOutcome};
+use crate::git_backend::{GitBackendManager, GitBackendError, Metadata};
+use crate::git_backend::branching::BranchContext;
+use crate::git_backend::release::ReleaseTag;
+use crate::git_backend::signing::SignatureStatus;
+use crate::git_backend::trailer::CommitTrailers;
 use crate::database::connection_manager::ConnectionManager;
-use std::path::Path;
+use crate::database::permission::{PermissionManager, PermissionAction};
+use crate::database::change_order::{ChangeOrder, ChangeOrderManager, ChangeOrderStatus};
+use crate::database::change_impact::ChangeImpactManager;
+use crate::database::signature::{SignatureManager, SignatureMeaning, SigningKeyStore};
+use crate::database::operation_journal::OperationJournalManager;
+use chrono::Utc;
+use rusqlite::{Transaction, params};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 /// Error types specific to part management
 #[derive(Debug, thiserror::Error)]
@@ -37,6 +54,10 @@ pub enum PartManagementError {
     #[error("Permission denied: {0}")]
     PermissionDenied(String),
     
+    /// Transition guards failed
+    #[error("Transition guards failed: {}", .0.iter().map(|failure| failure.message.as_str()).collect::<Vec<_>>().join("; "))]
+    GuardsFailed(Vec<GuardFailure>),
+    
     /// Other errors
     #[error("Part management error: {0}")]
     Other(String),
@@ -45,82 +66,25 @@ pub enum PartManagementError {
 /// Result type for part management operations
 pub type PartManagementResult<T> = Result<T, PartManagementError>;
 
-/// Represents a user role in the system
+/// Signatures of the commit and tag behind a released revision
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub enum UserRole {
-    /// Designer role - can create, edit, and submit parts for review
-    Designer,
-    /// Viewer role - can view and comment on parts
-    Viewer,
-    /// Admin role - has full control over the system
-    Admin,
-}
-
-/// Represents a user in the system
-#[derive(Debug, Clone)]
-pub struct User {
-    /// Username
-    pub username: String,
-    /// User role
-    pub role: UserRole,
+pub struct ReleaseVerification {
+    /// Hash of the released commit
+    pub commit_hash: String,
+    /// Signature of the released commit
+    pub commit: SignatureStatus,
+    /// Name of the release tag
+    pub tag_name: String,
+    /// Signature of the release tag, or None if the tag does not exist
+    pub tag: Option<SignatureStatus>,
 }
 
-impl User {
-    /// Create a new user
-    ///
-    /// # Arguments
-    ///
-    /// * `username` - Username
-    /// * `role` - User role
-    ///
-    /// # Returns
-    ///
-    /// A new User instance
-    pub fn new(username: String, role: UserRole) -> Self {
-        Self { username, role }
-    }
-    
-    /// Check if the user can create parts
-    ///
-    /// # Returns
-    ///
-    /// true if the user can create parts, false otherwise
-    pub fn can_create_parts(&self) -> bool {
-        matches!(self.role, UserRole::Designer | UserRole::Admin)
-    }
-    
-    /// Check if the user can edit a part
+impl ReleaseVerification {
+    /// Check whether the release is signed
     ///
-    /// # Arguments
-    ///
-    /// * `part_author` - Username of the part author
-    ///
-    /// # Returns
-    ///
-    /// true if the user can edit the part, false otherwise
-    pub fn can_edit_part(&self, part_author: &str) -> bool {
-        match self.role {
-            UserRole::Designer => self.username == part_author,
-            UserRole::Admin => true,
-            _ => false,
-        }
-    }
-    
-    /// Check if the user can approve a part
-    ///
-    /// # Arguments
-    ///
-    /// * `part_author` - Username of the part author
-    ///
-    /// # Returns
-    ///
-    /// true if the user can approve the part, false otherwise
-    pub fn can_approve_part(&self, part_author: &str) -> bool {
-        match self.role {
-            UserRole::Designer => self.username != part_author,
-            UserRole::Admin => true,
-            _ => false,
-        }
+    /// A release is signed if its commit or its release tag carries a valid signature.
+    pub fn is_signed(&self) -> bool {
+        self.commit.is_valid() || self.tag.as_ref().is_some_and(SignatureStatus::is_valid)
     }
 }
 
@@ -132,6 +96,8 @@ pub struct PartManagementManager<'a> {
     git_manager: &'a GitBackendManager,
     /// Current user
     current_user: User,
+    /// Store of the signing keys; approvals and releases are signed electronically when set
+    key_store: Option<SigningKeyStore>,
 }
 
 impl<'a> PartManagementManager<'a> {
@@ -155,8 +121,42 @@ impl<'a> PartManagementManager<'a> {
             connection_manager,
             git_manager,
             current_user,
+            key_store: None,
         }
     }
+    
+    /// Get the current user
+    ///
+    /// # Returns
+    ///
+    /// The user performing the operations of this manager
+    pub fn current_user(&self) -> &User {
+        &self.current_user
+    }
+    
+    /// Set the current user
+    ///
+    /// The user is also recorded in the audit log for the changes made through this manager.
+    ///
+    /// # Arguments
+    ///
+    /// * `current_user` - The user performing the operations of this manager
+    pub fn set_current_user(&mut self, current_user: User) {
+        self.current_user = current_user;
+    }
+    
+    /// Set the store of the signing keys
+    ///
+    /// With a key store, every approval, rejection and release is signed with the key of the
+    /// current user.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_store` - The store of the signing keys, or None to stop signing
+    pub fn set_key_store(&mut self, key_store: Option<SigningKeyStore>) {
+        self.key_store = key_store;
+    }
+    
     /// Create a new part with initial metadata and set up Git branch
     ///
     /// # Arguments
@@ -183,17 +183,17 @@ impl<'a> PartManagementManager<'a> {
         repo_path: &Path,
     ) -> PartManagementResult<(Part, i64)> {
         // Check if the user has permission to create parts
-        if !self.current_user.can_create_parts() {
+        if !PermissionManager::new(self.connection_manager).is_permitted(&self.current_user, PermissionAction::Create, &category, None)? {
             return Err(PartManagementError::PermissionDenied(
                 "User does not have permission to create parts".to_string(),
             ));
         }
         
-        // Use a transaction for the entire operation
-        self.connection_manager.transaction::<_, _, PartManagementError>(|tx| {
+        // Journal the operation so that a failure also undoes the changes to the repository
+        self.journal().run::<_, _, PartManagementError>(repo_path, "create_part", |tx| {
             // Create part managers
-            let part_manager = PartManager::new(self.connection_manager);
-            let revision_manager = RevisionManager::new(self.connection_manager);
+            let part_manager = PartManager::new(self.connection_manager).with_actor(&self.current_user.username);
+            let revision_manager = RevisionManager::new(self.connection_manager).with_actor(&self.current_user.username);
             
             // Create the part
             let part_id = part_manager.get_next_part_id_in_transaction(tx)?;
@@ -210,10 +210,11 @@ impl<'a> PartManagementManager<'a> {
             // Generate the display part number
             let display_part_number = part.display_part_number_in_transaction(tx);
             
-            // Create a new revision in Draft state
+            // Create the first revision in Draft state, labelled by the scheme of the category
+            let first_version = revision_manager.get_next_version_in_transaction(part.part_id, tx)?;
             let revision = Revision::new(
                 part.part_id,
-                "1".to_string(),
+                first_version,
                 RevisionStatus::Draft,
                 self.current_user.username.clone(),
                 None, // No commit hash yet
@@ -222,12 +223,18 @@ impl<'a> PartManagementManager<'a> {
             // Save the revision to the database
             let revision_id = revision_manager.create_revision_in_transaction(&revision, tx)?;
             
-            // Create a feature branch for the part
-            let branch_name = format!("part/{}/draft", display_part_number);
-            // Open the repository first
+            // Put the revision into the initial state of its workflow
+            WorkflowEngine::new(self.connection_manager)
+                .start_revision_in_transaction(revision_id, &self.current_user.username, tx)?;
+            
+            // Create the draft branch of the part from the mainline and open it in its own worktree
             let repo = self.git_manager.open_repository(repo_path)?;
-            self.git_manager.create_branch(&repo, &branch_name)?;
-            self.git_manager.checkout_branch(&repo, &branch_name)?;
+            let branching = self.git_manager.branching_strategy();
+            let branch_name = branching.draft_branch(&self.branch_context(&display_part_number, &revision), true);
+            if !branching.is_mainline(&branch_name) {
+                self.git_manager.create_branch_from(&repo, &branch_name, branching.mainline())?;
+                self.git_manager.worktree_manager(&repo).open_worktree(&branch_name)?;
+            }
             
             Ok((part, revision_id))
         })
@@ -239,7 +246,8 @@ impl<'a> PartManagementManager<'a> {
     ///
     /// * `revision_id` - ID of the revision to submit for review
     /// * `repo_path` - Path to the Git repository
-    /// * `reviewers` - List of usernames to request review from
+    /// * `reviewers` - List of usernames to request review from, in addition to the reviewers of
+    ///   the approval policy
     ///
     /// # Returns
     ///
@@ -254,27 +262,31 @@ impl<'a> PartManagementManager<'a> {
         repo_path: &Path,
         reviewers: Vec<String>,
     ) -> PartManagementResult<()> {
-        self.connection_manager.transaction::<_, _, PartManagementError>(|tx| {
+        self.journal().run::<_, _, PartManagementError>(repo_path, "submit_for_review", |tx| {
             // Create managers
             let revision_manager = RevisionManager::new(self.connection_manager);
-            let approval_manager = ApprovalManager::new(self.connection_manager);
             
             // Get the revision
             let revision = revision_manager.get_revision_in_transaction(revision_id, tx)?;
             
+            // Revisions of a change order are reviewed together
+            self.check_not_in_change_order_in_transaction(revision_id, "submit", tx)?;
+            
             // Check if the user has permission to submit the part for review
-            if !self.current_user.can_edit_part(&revision.created_by) {
-                return Err(PartManagementError::PermissionDenied(
-                    "User does not have permission to submit this part for review".to_string(),
-                ));
-            }
+            self.check_permission_in_transaction(
+                PermissionAction::Edit,
+                &revision,
+                "User does not have permission to submit this part for review",
+                tx,
+            )?;
             
-            // Check if the revision is in Draft state
-            if revision.status != RevisionStatus::Draft {
-                return Err(PartManagementError::InvalidStateTransition(
-                    format!("Cannot submit revision for review: current status is {}", revision.status.to_str()),
-                ));
-            }
+            // Move the revision to In Review; the workflow must allow this from the current state
+            let outcome = WorkflowEngine::new(self.connection_manager).transition_to_state_in_transaction(
+                revision_id,
+                RevisionStatus::InReview.to_str(),
+                &self.current_user.username,
+                tx,
+            )?;
             
             // Get the part
             let part_manager = PartManager::new(self.connection_manager);
@@ -283,30 +295,42 @@ impl<'a> PartManagementManager<'a> {
             // Generate the display part number
             let display_part_number = part.display_part_number_in_transaction(tx);
             
-            // Create a review branch
-            let _feature_branch = format!("part/{}/draft", display_part_number);
-            let review_branch = format!("part/{}/review", display_part_number);
-            
-            // Create and checkout the review branch
-            // Open the repository first
+            // Create the review branch from the draft branch, or bring an existing one up to date;
+            // without review branches the revision is reviewed on its draft branch
             let repo = self.git_manager.open_repository(repo_path)?;
-            self.git_manager.create_branch(&repo, &review_branch)?;
-            self.git_manager.checkout_branch(&repo, &review_branch)?;
-            
-            // Update the revision status to In Review
-            revision_manager.update_status_in_transaction(revision_id, RevisionStatus::InReview, tx)?;
+            let branching = self.git_manager.branching_strategy();
+            let context = self.branch_context(&display_part_number, &revision);
+            let draft_branch = self.find_draft_branch(&repo, &context);
+            let review_branch = branching.review_branch(&context)
+                .or_else(|| draft_branch.clone())
+                .unwrap_or_else(|| branching.mainline().to_string());
+            let review_exists = repo.find_branch(&review_branch, git2::BranchType::Local).is_ok();
+            if !review_exists {
+                let source = draft_branch.as_deref().unwrap_or(branching.mainline());
+                self.git_manager.create_branch_from(&repo, &review_branch, source)?;
+            }
             
-            // Create approval requests for each reviewer
-            for reviewer in reviewers {
-                let approval = Approval::new(
-                    revision_id,
-                    reviewer,
-                    ApprovalStatus::Pending,
-                    None,
-                );
-                approval_manager.create_approval_in_transaction(&approval, tx)?;
+            // Open the review branch in its own worktree
+            let review_repo = self.open_branch_repository(&repo, repo_path, &review_branch)?;
+            if let Some(draft_branch) = draft_branch.filter(|draft_branch| review_exists && *draft_branch != review_branch) {
+                let result = self.git_manager.merge_branch(&review_repo, &draft_branch)?;
+                if !result.success {
+                    return Err(PartManagementError::Other(
+                        format!("Merging {} into {} failed with {} conflicted files", draft_branch, review_branch, result.conflicted_files.len()),
+                    ));
+                }
             }
             
+            // Record the reviewed commit; approvals are signed against it
+            let commit_hash = self.head_commit_hash(&review_repo)?;
+            RevisionManager::new(self.connection_manager).with_actor(&self.current_user.username).update_approved_commit_hash_in_transaction(revision_id, &commit_hash, tx)?;
+            
+            // Request review from the listed reviewers and the reviewers of the approval policy
+            self.request_review_in_transaction(revision_id, reviewers, tx)?;
+            
+            // Run the actions of the transition
+            self.run_transition_actions_in_transaction(revision_id, &outcome, Some(repo_path), tx)?;
+            
             Ok(())
         })
     }
@@ -331,61 +355,19 @@ impl<'a> PartManagementManager<'a> {
         comments: Option<String>,
     ) -> PartManagementResult<()> {
         self.connection_manager.transaction::<_, _, PartManagementError>(|tx| {
-            // Create managers
-            let revision_manager = RevisionManager::new(self.connection_manager);
-            let approval_manager = ApprovalManager::new(self.connection_manager);
-            
-            // Get the revision
-            let revision = revision_manager.get_revision_in_transaction(revision_id, tx)?;
-            
-            // Check if the user has permission to approve the revision
-            if !self.current_user.can_approve_part(&revision.created_by) {
-                return Err(PartManagementError::PermissionDenied(
-                    "User does not have permission to approve this revision".to_string(),
-                ));
-            }
-            
-            // Check if the revision is in In Review state
-            if revision.status != RevisionStatus::InReview {
-                return Err(PartManagementError::InvalidStateTransition(
-                    format!("Cannot approve revision: current status is {}", revision.status.to_str()),
-                ));
-            }
-            
-            // Get the approval for this user
-            let approval = match approval_manager.get_approval_for_revision_and_approver_in_transaction(
-                revision_id,
-                &self.current_user.username,
-                tx
-            ) {
-                Ok(approval) => approval,
-                Err(_) => {
-                    // Create a new approval if one doesn't exist
-                    let approval = Approval::new(
-                        revision_id,
-                        self.current_user.username.clone(),
-                        ApprovalStatus::Pending,
-                        None,
-                    );
-                    let approval_id = approval_manager.create_approval_in_transaction(&approval, tx)?;
-                    approval_manager.get_approval_in_transaction(approval_id, tx)?
-                }
-            };
-            
-            // Update the approval status
-            approval_manager.update_status_in_transaction(
-                approval.approval_id.unwrap(),
-                ApprovalStatus::Approved,
-                comments.as_deref(),
-                tx
-            )?;
+            // Revisions of a change order are approved together
+            self.check_not_in_change_order_in_transaction(revision_id, "approve", tx)?;
             
-            Ok(())
+            // Record the approval of the current user
+            self.review_revision_in_transaction(revision_id, ApprovalStatus::Approved, comments.as_deref(), tx)
         })
     }
     
     /// Reject a revision
     ///
+    /// The revision goes back to Draft once the rejections reach the threshold of its approval
+    /// policy; without a policy a single rejection is enough.
+    ///
     /// # Arguments
     ///
     /// * `revision_id` - ID of the revision to reject
@@ -404,58 +386,26 @@ impl<'a> PartManagementManager<'a> {
         comments: Option<String>,
     ) -> PartManagementResult<()> {
         self.connection_manager.transaction::<_, _, PartManagementError>(|tx| {
-            // Create managers
-            let revision_manager = RevisionManager::new(self.connection_manager);
-            let approval_manager = ApprovalManager::new(self.connection_manager);
+            // Revisions of a change order are rejected together
+            self.check_not_in_change_order_in_transaction(revision_id, "reject", tx)?;
             
-            // Get the revision
-            let revision = revision_manager.get_revision_in_transaction(revision_id, tx)?;
-            
-            // Check if the user has permission to reject the revision
-            if !self.current_user.can_approve_part(&revision.created_by) {
-                return Err(PartManagementError::PermissionDenied(
-                    "User does not have permission to reject this revision".to_string(),
-                ));
-            }
+            // Record the rejection of the current user
+            self.review_revision_in_transaction(revision_id, ApprovalStatus::Rejected, comments.as_deref(), tx)?;
             
-            // Check if the revision is in In Review state
-            if revision.status != RevisionStatus::InReview {
-                return Err(PartManagementError::InvalidStateTransition(
-                    format!("Cannot reject revision: current status is {}", revision.status.to_str()),
-                ));
+            // Move the revision back to Draft once the rejection threshold is reached
+            let evaluation = ApprovalPolicyManager::new(self.connection_manager).evaluate_in_transaction(revision_id, None, tx)?;
+            if evaluation.decision == ApprovalDecision::Rejected {
+                let outcome = WorkflowEngine::new(self.connection_manager).transition_to_state_in_transaction(
+                    revision_id,
+                    RevisionStatus::Draft.to_str(),
+                    &self.current_user.username,
+                    tx,
+                )?;
+                
+                // Run the actions of the transition
+                self.run_transition_actions_in_transaction(revision_id, &outcome, None, tx)?;
             }
             
-            // Get the approval for this user
-            let approval = match approval_manager.get_approval_for_revision_and_approver_in_transaction(
-                revision_id,
-                &self.current_user.username,
-                tx
-            ) {
-                Ok(approval) => approval,
-                Err(_) => {
-                    // Create a new approval if one doesn't exist
-                    let approval = Approval::new(
-                        revision_id,
-                        self.current_user.username.clone(),
-                        ApprovalStatus::Pending,
-                        None,
-                    );
-                    let approval_id = approval_manager.create_approval_in_transaction(&approval, tx)?;
-                    approval_manager.get_approval_in_transaction(approval_id, tx)?
-                }
-            };
-            
-            // Update the approval status
-            approval_manager.update_status_in_transaction(
-                approval.approval_id.unwrap(),
-                ApprovalStatus::Rejected,
-                comments.as_deref(),
-                tx
-            )?;
-            
-            // Update the revision status back to Draft
-            revision_manager.update_status_in_transaction(revision_id, RevisionStatus::Draft, tx)?;
-            
             Ok(())
         })
     }
@@ -479,54 +429,8 @@ impl<'a> PartManagementManager<'a> {
         revision_id: i64,
         repo_path: &Path,
     ) -> PartManagementResult<()> {
-        self.connection_manager.transaction::<_, _, PartManagementError>(|tx| {
-            // Create managers
-            let revision_manager = RevisionManager::new(self.connection_manager);
-            let approval_manager = ApprovalManager::new(self.connection_manager);
-            let part_manager = PartManager::new(self.connection_manager);
-            
-            // Get the revision
-            let revision = revision_manager.get_revision_in_transaction(revision_id, tx)?;
-            
-            // Check if the user has permission to release the revision
-            if !self.current_user.can_approve_part(&revision.created_by) {
-                return Err(PartManagementError::PermissionDenied(
-                    "User does not have permission to release this revision".to_string(),
-                ));
-            }
-            
-            // Check if the revision is in In Review state
-            if revision.status != RevisionStatus::InReview {
-                return Err(PartManagementError::InvalidStateTransition(
-                    format!("Cannot release revision: current status is {}", revision.status.to_str()),
-                ));
-            }
-            
-            // Check if the revision is fully approved
-            if !approval_manager.is_revision_approved_in_transaction(revision_id, tx)? {
-                return Err(PartManagementError::ApprovalRequired(
-                    "Revision must be fully approved before release".to_string(),
-                ));
-            }
-            
-            // Get the part
-            let part = part_manager.get_part_in_transaction(revision.part_id, tx)?;
-            
-            // Generate the display part number
-            let display_part_number = part.display_part_number_in_transaction(tx);
-            
-            // Checkout the main branch
-            // Open the repository first
-            let repo = self.git_manager.open_repository(repo_path)?;
-            self.git_manager.checkout_branch(&repo, "main")?;
-            
-            // Merge the review branch into main
-            let review_branch = format!("part/{}/review", display_part_number);
-            self.git_manager.merge_branch(&repo, &review_branch)?;
-            
-            // Update the revision status to Released
-            revision_manager.update_status_in_transaction(revision_id, RevisionStatus::Released, tx)?;
-            
+        self.journal().run::<_, _, PartManagementError>(repo_path, "release_revision", |tx| {
+            self.release_revision_in_transaction(revision_id, None, repo_path, tx)?;
             Ok(())
         })
     }
@@ -556,21 +460,23 @@ impl<'a> PartManagementManager<'a> {
             let revision = revision_manager.get_revision_in_transaction(revision_id, tx)?;
             
             // Check if the user has permission to mark the revision as obsolete
-            if !self.current_user.can_approve_part(&revision.created_by) {
-                return Err(PartManagementError::PermissionDenied(
-                    "User does not have permission to mark this revision as obsolete".to_string(),
-                ));
-            }
+            self.check_permission_in_transaction(
+                PermissionAction::Obsolete,
+                &revision,
+                "User does not have permission to mark this revision as obsolete",
+                tx,
+            )?;
             
-            // Check if the revision is in Released state
-            if revision.status != RevisionStatus::Released {
-                return Err(PartManagementError::InvalidStateTransition(
-                    format!("Cannot mark revision as obsolete: current status is {}", revision.status.to_str()),
-                ));
-            }
+            // Move the revision to Obsolete
+            let outcome = WorkflowEngine::new(self.connection_manager).transition_to_state_in_transaction(
+                revision_id,
+                RevisionStatus::Obsolete.to_str(),
+                &self.current_user.username,
+                tx,
+            )?;
             
-            // Update the revision status to Obsolete
-            revision_manager.update_status_in_transaction(revision_id, RevisionStatus::Obsolete, tx)?;
+            // Run the actions of the transition
+            self.run_transition_actions_in_transaction(revision_id, &outcome, None, tx)?;
             
             Ok(())
         })
@@ -595,16 +501,22 @@ impl<'a> PartManagementManager<'a> {
         part_id: i64,
         repo_path: &Path,
     ) -> PartManagementResult<i64> {
-        self.connection_manager.transaction::<_, _, PartManagementError>(|tx| {
+        self.journal().run::<_, _, PartManagementError>(repo_path, "create_revision", |tx| {
             // Create managers
             let part_manager = PartManager::new(self.connection_manager);
-            let revision_manager = RevisionManager::new(self.connection_manager);
+            let revision_manager = RevisionManager::new(self.connection_manager).with_actor(&self.current_user.username);
             
             // Get the part
             let part = part_manager.get_part_in_transaction(part_id, tx)?;
             
             // Check if the user has permission to create a revision
-            if !self.current_user.can_create_parts() {
+            if !PermissionManager::new(self.connection_manager).is_permitted_in_transaction(
+                &self.current_user,
+                PermissionAction::Create,
+                &part.category,
+                None,
+                tx,
+            )? {
                 return Err(PartManagementError::PermissionDenied(
                     "User does not have permission to create revisions".to_string(),
                 ));
@@ -626,14 +538,6 @@ impl<'a> PartManagementManager<'a> {
             // Generate the display part number
             let display_part_number = part.display_part_number_in_transaction(tx);
             
-            // Create a new feature branch from main - include version number to make it unique
-            let branch_name = format!("part/{}/v{}/draft", display_part_number, next_version);
-            // Open the repository first
-            let repo = self.git_manager.open_repository(repo_path)?;
-            self.git_manager.checkout_branch(&repo, "main")?;
-            self.git_manager.create_branch(&repo, &branch_name)?;
-            self.git_manager.checkout_branch(&repo, &branch_name)?;
-            
             // Create a new revision in Draft state
             let revision = Revision::new(
                 part_id,
@@ -643,50 +547,149 @@ impl<'a> PartManagementManager<'a> {
                 None, // No commit hash yet
             );
             
+            // Create the draft branch of the revision from the mainline and open it in its own worktree
+            let repo = self.git_manager.open_repository(repo_path)?;
+            let branching = self.git_manager.branching_strategy();
+            let branch_name = branching.draft_branch(&self.branch_context(&display_part_number, &revision), false);
+            if !branching.is_mainline(&branch_name) {
+                self.git_manager.create_branch_from(&repo, &branch_name, branching.mainline())?;
+                self.git_manager.worktree_manager(&repo).open_worktree(&branch_name)?;
+            }
+            
             // Save the revision to the database
             let revision_id = revision_manager.create_revision_in_transaction(&revision, tx)?;
             
+            // Put the revision into the initial state of its workflow
+            WorkflowEngine::new(self.connection_manager)
+                .start_revision_in_transaction(revision_id, &self.current_user.username, tx)?;
+            
             Ok(revision_id)
         })
     }
     
-    /// Update the commit hash for a revision
+    /// Get the working directory of a revision
+    ///
+    /// Drafts and revisions in review are worked on in the worktree of their branch, which is
+    /// created if it does not exist yet. Released and obsolete revisions resolve to the main
+    /// working directory of the repository.
     ///
     /// # Arguments
     ///
     /// * `revision_id` - ID of the revision
-    /// * `commit_hash` - Git commit hash
+    /// * `repo_path` - Path to the Git repository
     ///
     /// # Returns
     ///
-    /// Ok(()) if the commit hash was successfully updated
+    /// The directory the files of the revision are checked out in
     ///
     /// # Errors
     ///
-    /// Returns a PartManagementError if the commit hash could not be updated
-    pub fn update_commit_hash(
+    /// Returns a PartManagementError if the revision or repository could not be opened or the
+    /// worktree could not be created
+    pub fn get_working_path(
         &self,
         revision_id: i64,
-        commit_hash: &str,
-    ) -> PartManagementResult<()> {
+        repo_path: &Path,
+    ) -> PartManagementResult<PathBuf> {
+        self.connection_manager.transaction::<_, _, PartManagementError>(|tx| {
+            self.working_path_in_transaction(revision_id, repo_path, tx)
+        })
+    }
+    
+    /// Verify the signatures of the commit and tag a revision was released with
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - ID of the released revision
+    /// * `repo_path` - Path to the Git repository
+    ///
+    /// # Returns
+    ///
+    /// The signature status of the released commit and of the release tag
+    ///
+    /// # Errors
+    ///
+    /// Returns a PartManagementError if the revision has no released commit or the repository
+    /// could not be read
+    pub fn verify_release(
+        &self,
+        revision_id: i64,
+        repo_path: &Path,
+    ) -> PartManagementResult<ReleaseVerification> {
         self.connection_manager.transaction::<_, _, PartManagementError>(|tx| {
-            // Create a revision manager
+            let repo = self.git_manager.open_repository(repo_path)?;
+            self.verify_release_in_transaction(revision_id, &repo, tx)
+        })
+    }
+    
+    /// Execute a workflow transition on a revision
+    ///
+    /// Transitions that require approval need approval permission; other transitions need
+    /// edit permission on the revision. A transition to Released releases the revision like
+    /// `release_revision`, merging, signing and tagging it.
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - ID of the revision
+    /// * `transition_id` - ID of the workflow transition to execute
+    /// * `repo_path` - Path to the Git repository
+    ///
+    /// # Returns
+    ///
+    /// The new workflow state of the revision
+    ///
+    /// # Errors
+    ///
+    /// Returns a PartManagementError if the transition is not available or not permitted, one of
+    /// its guards fails or one of its actions could not be run
+    pub fn execute_transition(
+        &self,
+        revision_id: i64,
+        transition_id: i64,
+        repo_path: &Path,
+    ) -> PartManagementResult<WorkflowState> {
+        self.journal().run::<_, _, PartManagementError>(repo_path, "execute_transition", |tx| {
+            // Create managers
             let revision_manager = RevisionManager::new(self.connection_manager);
+            let workflow_engine = WorkflowEngine::new(self.connection_manager);
             
-            // Get the revision
+            // Get the revision and the transitions available from its current state
             let revision = revision_manager.get_revision_in_transaction(revision_id, tx)?;
+            let current = workflow_engine.get_revision_state_in_transaction(revision_id, tx)?;
+            let transition = WorkflowManager::new(self.connection_manager)
+                .get_transitions_from_state_in_transaction(current.state.state_id.unwrap_or_default(), tx)?
+                .into_iter()
+                .find(|transition| transition.transition_id == Some(transition_id));
             
-            // Check if the user has permission to update the commit hash
-            if !self.current_user.can_edit_part(&revision.created_by) {
-                return Err(PartManagementError::PermissionDenied(
-                    "User does not have permission to update this revision".to_string(),
-                ));
+            // Check if the user has permission to execute the transition
+            let target = match &transition {
+                Some(transition) => Some(WorkflowManager::new(self.connection_manager).get_workflow_state_in_transaction(transition.to_state_id, tx)?),
+                None => None,
+            };
+            let action = match (&transition, target.as_ref().and_then(|state| RevisionStatus::from_str(&state.name))) {
+                (_, Some(RevisionStatus::Released)) => PermissionAction::Release,
+                (_, Some(RevisionStatus::Obsolete)) => PermissionAction::Obsolete,
+                (Some(transition), _) if transition.requires_approval => PermissionAction::Approve,
+                _ => PermissionAction::Edit,
+            };
+            self.check_permission_in_transaction(
+                action,
+                &revision,
+                "User does not have permission to execute this transition",
+                tx,
+            )?;
+            
+            // Releasing also merges, signs and tags the revision
+            if target.as_ref().and_then(|state| RevisionStatus::from_str(&state.name)) == Some(RevisionStatus::Released) {
+                return Ok(self.release_revision_in_transaction(revision_id, Some(transition_id), repo_path, tx)?.state);
             }
             
-            // Update the commit hash
-            revision_manager.update_commit_hash_in_transaction(revision_id, commit_hash, tx)?;
+            // Execute the transition and run its actions
+            let outcome = workflow_engine.execute_transition_in_transaction(revision_id, transition_id, &self.current_user.username, tx)?;
+            self.run_transition_actions_in_transaction(revision_id, &outcome, Some(repo_path), tx)?;
+            self.check_signed_release_in_transaction(revision_id, &outcome, &self.git_manager.open_repository(repo_path)?, tx)?;
             
-            Ok(())
+            Ok(outcome.state)
         })
     }
     
@@ -725,40 +728,1150 @@ impl<'a> PartManagementManager<'a> {
             Ok(result)
         })
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::database::schema::DatabaseManager;
-    use crate::git_backend::{GitBackendConfig, AuthConfig};
-    use crate::database::workflow::WorkflowManager;
-    use tempfile::tempdir;
     
-    #[test]
-    fn test_part_creation_and_workflow() {
-        // Create a temporary directory for the test database
-        let temp_dir = tempdir().unwrap();
-        let db_path = temp_dir.path().join("test.db");
-        
-        // Create a temporary directory for the Git repository
-        let repo_dir = tempdir().unwrap();
-        let repo_path = repo_dir.path();
-        
-        // Create a new database manager and initialize the schema
-        let db_manager = DatabaseManager::new(&db_path).unwrap();
-        db_manager.initialize_schema().unwrap();
-        
-        // Create a workflow manager and set up the default part workflow
-        let workflow_manager = WorkflowManager::new(db_manager.connection_manager());
-        workflow_manager.create_default_part_workflow().unwrap();
-        
-        // Create a Git backend manager
-        let git_config = GitBackendConfig::default();
-        let auth_config = AuthConfig::default();
-        let git_manager = GitBackendManager::new(git_config, auth_config).unwrap();
-        
-        // Initialize a Git repository
+    /// Submit the revisions of a change order for review as a unit
+    ///
+    /// Every revision moves to In Review and gets the same reviewers, in addition to those of its
+    /// own approval policy. The draft branches of the revisions are merged into the change order
+    /// branch, which is later released with a single merge into main.
+    ///
+    /// # Arguments
+    ///
+    /// * `eco_id` - ID of the change order to submit for review
+    /// * `repo_path` - Path to the Git repository
+    /// * `reviewers` - List of usernames to request review from
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the change order was successfully submitted for review
+    ///
+    /// # Errors
+    ///
+    /// Returns a PartManagementError if the change order is not a draft, has no revisions, or one
+    /// of its revisions could not be submitted for review
+    pub fn submit_change_order_for_review(
+        &self,
+        eco_id: i64,
+        repo_path: &Path,
+        reviewers: Vec<String>,
+    ) -> PartManagementResult<()> {
+        self.journal().run::<_, _, PartManagementError>(repo_path, "submit_change_order_for_review", |tx| {
+            // Create managers
+            let change_order_manager = ChangeOrderManager::new(self.connection_manager);
+            let part_manager = PartManager::new(self.connection_manager);
+            
+            // Get the change order and its revisions
+            let change_order = change_order_manager.get_change_order_in_transaction(eco_id, tx)?;
+            if change_order.status != ChangeOrderStatus::Draft {
+                return Err(PartManagementError::InvalidStateTransition(
+                    format!("Cannot submit {}: current status is {}", change_order.eco_number, change_order.status.to_str()),
+                ));
+            }
+            let revisions = change_order_manager.get_revisions_in_transaction(eco_id, tx)?;
+            if revisions.is_empty() {
+                return Err(DatabaseError::ValidationError(format!("{} has no revisions", change_order.eco_number)).into());
+            }
+            
+            // Create the change order branch from the mainline and open it in its own worktree
+            let repo = self.git_manager.open_repository(repo_path)?;
+            let eco_branch = self.change_order_branch(&change_order);
+            if repo.find_branch(&eco_branch, git2::BranchType::Local).is_err() {
+                self.git_manager.create_branch_from(&repo, &eco_branch, self.git_manager.branching_strategy().mainline())?;
+            }
+            let eco_repo = self.open_branch_repository(&repo, repo_path, &eco_branch)?;
+            
+            for revision in &revisions {
+                let revision_id = revision.revision_id.unwrap_or_default();
+                
+                // Check if the user has permission to submit the part for review
+                self.check_permission_in_transaction(
+                    PermissionAction::Edit,
+                    revision,
+                    "User does not have permission to submit this part for review",
+                    tx,
+                )?;
+                
+                // Move the revision to In Review
+                let outcome = WorkflowEngine::new(self.connection_manager).transition_to_state_in_transaction(
+                    revision_id,
+                    RevisionStatus::InReview.to_str(),
+                    &self.current_user.username,
+                    tx,
+                )?;
+                
+                // Collect the changes of the part on the change order branch
+                let part = part_manager.get_part_in_transaction(revision.part_id, tx)?;
+                let display_part_number = part.display_part_number_in_transaction(tx);
+                let draft_branch = self.find_draft_branch(&repo, &self.branch_context(&display_part_number, revision));
+                if let Some(draft_branch) = draft_branch.filter(|draft_branch| *draft_branch != eco_branch) {
+                    let result = self.git_manager.merge_branch(&eco_repo, &draft_branch)?;
+                    if !result.success {
+                        return Err(PartManagementError::Other(
+                            format!("Merging {} into {} failed with {} conflicted files", draft_branch, eco_branch, result.conflicted_files.len()),
+                        ));
+                    }
+                }
+                
+                // Request review from the listed reviewers and the reviewers of the approval policy
+                self.request_review_in_transaction(revision_id, reviewers.clone(), tx)?;
+                
+                // Run the actions of the transition
+                self.run_transition_actions_in_transaction(revision_id, &outcome, Some(repo_path), tx)?;
+            }
+            
+            // Record the reviewed commit; approvals are signed against it
+            let commit_hash = self.head_commit_hash(&eco_repo)?;
+            for revision in &revisions {
+                RevisionManager::new(self.connection_manager).with_actor(&self.current_user.username)
+                    .update_approved_commit_hash_in_transaction(revision.revision_id.unwrap_or_default(), &commit_hash, tx)?;
+            }
+            
+            change_order_manager.update_status_in_transaction(eco_id, ChangeOrderStatus::InReview, tx)?;
+            
+            Ok(())
+        })
+    }
+    
+    /// Approve a change order
+    ///
+    /// Records the approval of the current user on every revision of the change order the user
+    /// is a reviewer of.
+    ///
+    /// # Arguments
+    ///
+    /// * `eco_id` - ID of the change order to approve
+    /// * `comments` - Comments from the approver
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the change order was successfully approved
+    ///
+    /// # Errors
+    ///
+    /// Returns a PartManagementError if the change order is not in review or the user may not
+    /// review any of its revisions
+    pub fn approve_change_order(
+        &self,
+        eco_id: i64,
+        comments: Option<String>,
+    ) -> PartManagementResult<()> {
+        self.connection_manager.transaction::<_, _, PartManagementError>(|tx| {
+            self.review_change_order_in_transaction(eco_id, ApprovalStatus::Approved, comments.as_deref(), tx)?;
+            Ok(())
+        })
+    }
+    
+    /// Reject a change order
+    ///
+    /// Records the rejection of the current user on every revision of the change order the user
+    /// is a reviewer of. Once one of the revisions reaches the rejection threshold of its
+    /// approval policy, the whole change order goes back to Draft.
+    ///
+    /// # Arguments
+    ///
+    /// * `eco_id` - ID of the change order to reject
+    /// * `comments` - Comments from the reviewer
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the change order was successfully rejected
+    ///
+    /// # Errors
+    ///
+    /// Returns a PartManagementError if the change order is not in review or the user may not
+    /// review any of its revisions
+    pub fn reject_change_order(
+        &self,
+        eco_id: i64,
+        comments: Option<String>,
+    ) -> PartManagementResult<()> {
+        self.connection_manager.transaction::<_, _, PartManagementError>(|tx| {
+            let change_order_manager = ChangeOrderManager::new(self.connection_manager);
+            let revisions = self.review_change_order_in_transaction(eco_id, ApprovalStatus::Rejected, comments.as_deref(), tx)?;
+            
+            // Move all revisions back to Draft once the rejection threshold is reached
+            if change_order_manager.evaluate_in_transaction(eco_id, tx)? == ApprovalDecision::Rejected {
+                for revision in &revisions {
+                    let revision_id = revision.revision_id.unwrap_or_default();
+                    let outcome = WorkflowEngine::new(self.connection_manager).transition_to_state_in_transaction(
+                        revision_id,
+                        RevisionStatus::Draft.to_str(),
+                        &self.current_user.username,
+                        tx,
+                    )?;
+                    self.run_transition_actions_in_transaction(revision_id, &outcome, None, tx)?;
+                }
+                change_order_manager.update_status_in_transaction(eco_id, ChangeOrderStatus::Draft, tx)?;
+            }
+            
+            Ok(())
+        })
+    }
+    
+    /// Release a change order
+    ///
+    /// All revisions of the change order are released in one transaction, and the change order
+    /// branch is merged into main and tagged with the change order number. If any revision
+    /// cannot be released or the merge fails, none of the revisions are released.
+    ///
+    /// # Arguments
+    ///
+    /// * `eco_id` - ID of the change order to release
+    /// * `repo_path` - Path to the Git repository
+    ///
+    /// # Returns
+    ///
+    /// The commit hash of the release
+    ///
+    /// # Errors
+    ///
+    /// Returns a PartManagementError if the change order is not approved or could not be released
+    pub fn release_change_order(
+        &self,
+        eco_id: i64,
+        repo_path: &Path,
+    ) -> PartManagementResult<String> {
+        self.journal().run::<_, _, PartManagementError>(repo_path, "release_change_order", |tx| {
+            // Create managers
+            let change_order_manager = ChangeOrderManager::new(self.connection_manager);
+            let revision_manager = RevisionManager::new(self.connection_manager);
+            
+            // Get the change order and check that it has been approved as a whole
+            let change_order = change_order_manager.get_change_order_in_transaction(eco_id, tx)?;
+            if change_order.status != ChangeOrderStatus::InReview {
+                return Err(PartManagementError::InvalidStateTransition(
+                    format!("Cannot release {}: current status is {}", change_order.eco_number, change_order.status.to_str()),
+                ));
+            }
+            if change_order_manager.evaluate_in_transaction(eco_id, tx)? != ApprovalDecision::Approved {
+                return Err(PartManagementError::ApprovalRequired(
+                    format!("{} has not been approved for all of its revisions", change_order.eco_number),
+                ));
+            }
+            let revisions = change_order_manager.get_revisions_in_transaction(eco_id, tx)?;
+            
+            // Move every revision to Released; the workflow enforces the approval requirement
+            let mut outcomes = Vec::new();
+            for revision in &revisions {
+                let revision_id = revision.revision_id.unwrap_or_default();
+                self.check_permission_in_transaction(
+                    PermissionAction::Release,
+                    revision,
+                    "User does not have permission to release this revision",
+                    tx,
+                )?;
+                // The signature of the release is verified once it has been tagged
+                let outcome = WorkflowEngine::new(self.connection_manager)
+                    .with_deferred_signed_release()
+                    .transition_to_state_in_transaction(
+                        revision_id,
+                        RevisionStatus::Released.to_str(),
+                        &self.current_user.username,
+                        tx,
+                    )?;
+                outcomes.push((revision_id, outcome));
+            }
+            
+            // Merge the change order branch into the mainline, provided it is still what was
+            // approved, and tag the result
+            let repo = self.git_manager.open_repository(repo_path)?;
+            let branching = self.git_manager.branching_strategy();
+            self.git_manager.checkout_branch(&repo, branching.mainline())?;
+            let eco_branch = self.change_order_branch(&change_order);
+            if !branching.is_mainline(&eco_branch) {
+                for revision in &revisions {
+                    self.check_approved_commit(&repo, &eco_branch, revision)?;
+                }
+                self.merge_release_branch(&repo, &eco_branch)?;
+            }
+            let commit_hash = self.head_commit_hash(&repo)?;
+            let release_commit = git2::Oid::from_str(&commit_hash).map_err(GitBackendError::GitError)?;
+            self.git_manager.operation_handler(&repo).create_annotated_tag(
+                &change_order.eco_number,
+                release_commit,
+                &self.git_signature()?,
+                &format!("{}: {}", change_order.eco_number, change_order.title),
+            )?;
+            
+            // Tag, record and sign the release of every revision, and record it on the change order
+            let part_manager = PartManager::new(self.connection_manager);
+            for (revision_id, outcome) in &outcomes {
+                let revision = revision_manager.get_revision_in_transaction(*revision_id, tx)?;
+                let display_part_number = part_manager.get_part_in_transaction(revision.part_id, tx)?
+                    .display_part_number_in_transaction(tx);
+                let context = self.branch_context(&display_part_number, &revision);
+                self.tag_release_in_transaction(*revision_id, &context, Some(&change_order.eco_number), &repo, release_commit, tx)?;
+                self.sign_in_transaction(*revision_id, None, SignatureMeaning::Release, tx)?;
+                self.check_signed_release_in_transaction(*revision_id, outcome, &repo, tx)?;
+                self.run_transition_actions_in_transaction(*revision_id, outcome, Some(repo_path), tx)?;
+            }
+            change_order_manager.record_release_in_transaction(eco_id, &commit_hash, tx)?;
+            
+            // The change order and draft worktrees are no longer needed
+            let mut branches = vec![eco_branch];
+            for revision in &revisions {
+                let display_part_number = part_manager.get_part_in_transaction(revision.part_id, tx)?
+                    .display_part_number_in_transaction(tx);
+                branches.extend(self.find_draft_branch(&repo, &self.branch_context(&display_part_number, revision)));
+            }
+            self.remove_worktrees(&repo, branches.iter())?;
+            
+            Ok(commit_hash)
+        })
+    }
+    
+    /// Cancel a change order
+    ///
+    /// Revisions that are in review go back to Draft and can be added to another change order.
+    ///
+    /// # Arguments
+    ///
+    /// * `eco_id` - ID of the change order to cancel
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the change order was successfully cancelled
+    ///
+    /// # Errors
+    ///
+    /// Returns a PartManagementError if the change order is already released or cancelled
+    pub fn cancel_change_order(
+        &self,
+        eco_id: i64,
+    ) -> PartManagementResult<()> {
+        self.connection_manager.transaction::<_, _, PartManagementError>(|tx| {
+            let change_order_manager = ChangeOrderManager::new(self.connection_manager);
+            
+            let change_order = change_order_manager.get_change_order_in_transaction(eco_id, tx)?;
+            if !change_order.status.is_open() {
+                return Err(PartManagementError::InvalidStateTransition(
+                    format!("Cannot cancel {}: current status is {}", change_order.eco_number, change_order.status.to_str()),
+                ));
+            }
+            
+            for revision in change_order_manager.get_revisions_in_transaction(eco_id, tx)? {
+                if revision.status != RevisionStatus::InReview {
+                    continue;
+                }
+                self.check_permission_in_transaction(
+                    PermissionAction::Edit,
+                    &revision,
+                    "User does not have permission to withdraw this revision from review",
+                    tx,
+                )?;
+                let revision_id = revision.revision_id.unwrap_or_default();
+                let outcome = WorkflowEngine::new(self.connection_manager).transition_to_state_in_transaction(
+                    revision_id,
+                    RevisionStatus::Draft.to_str(),
+                    &self.current_user.username,
+                    tx,
+                )?;
+                self.run_transition_actions_in_transaction(revision_id, &outcome, None, tx)?;
+            }
+            
+            change_order_manager.update_status_in_transaction(eco_id, ChangeOrderStatus::Cancelled, tx)?;
+            
+            Ok(())
+        })
+    }
+    
+    /// Request review of a revision from the listed reviewers and the reviewers of its policy
+    ///
+    /// A new review round resets the decisions of the previous one and attaches a fresh change
+    /// impact report for the approvers.
+    fn request_review_in_transaction(
+        &self,
+        revision_id: i64,
+        reviewers: Vec<String>,
+        tx: &Transaction,
+    ) -> PartManagementResult<()> {
+        let approval_manager = ApprovalManager::new(self.connection_manager).with_actor(&self.current_user.username);
+        let reviewers = self.collect_reviewers_in_transaction(revision_id, reviewers, tx)?;
+        
+        let existing = approval_manager.get_approvals_for_revision_in_transaction(revision_id, tx)?;
+        for approval in existing.iter().filter(|approval| approval.status != ApprovalStatus::Pending) {
+            approval_manager.update_status_in_transaction(approval.approval_id.unwrap_or_default(), ApprovalStatus::Pending, None, tx)?;
+        }
+        
+        for reviewer in reviewers {
+            if existing.iter().any(|approval| approval.approver == reviewer) {
+                continue;
+            }
+            let approval = Approval::new(
+                revision_id,
+                reviewer,
+                ApprovalStatus::Pending,
+                None,
+            );
+            approval_manager.create_approval_in_transaction(&approval, tx)?;
+        }
+        
+        ChangeImpactManager::new(self.connection_manager)
+            .attach_report_in_transaction(revision_id, &self.current_user.username, tx)?;
+        
+        Ok(())
+    }
+    
+    /// Record the decision of the current user on a revision in review
+    fn review_revision_in_transaction(
+        &self,
+        revision_id: i64,
+        status: ApprovalStatus,
+        comments: Option<&str>,
+        tx: &Transaction,
+    ) -> PartManagementResult<()> {
+        let approval_manager = ApprovalManager::new(self.connection_manager).with_actor(&self.current_user.username);
+        let action = if status == ApprovalStatus::Rejected { "reject" } else { "approve" };
+        
+        // Get the revision
+        let revision = RevisionManager::new(self.connection_manager).get_revision_in_transaction(revision_id, tx)?;
+        
+        // Check if the user has permission to review the revision
+        self.check_permission_in_transaction(
+            PermissionAction::Approve,
+            &revision,
+            &format!("User does not have permission to {} this revision", action),
+            tx,
+        )?;
+        
+        // Check if the revision is in In Review state
+        if revision.status != RevisionStatus::InReview {
+            return Err(PartManagementError::InvalidStateTransition(
+                format!("Cannot {} revision: current status is {}", action, revision.status.to_str()),
+            ));
+        }
+        
+        // Only the reviewers of the approval policy may take part in the review
+        self.check_reviewer_in_transaction(revision_id, action, tx)?;
+        
+        // Get the approval for this user
+        let approval = match approval_manager.get_approval_for_revision_and_approver_in_transaction(
+            revision_id,
+            &self.current_user.username,
+            tx
+        ) {
+            Ok(approval) => approval,
+            Err(_) => {
+                // Create a new approval if one doesn't exist
+                let approval = Approval::new(
+                    revision_id,
+                    self.current_user.username.clone(),
+                    ApprovalStatus::Pending,
+                    None,
+                );
+                let approval_id = approval_manager.create_approval_in_transaction(&approval, tx)?;
+                approval_manager.get_approval_in_transaction(approval_id, tx)?
+            }
+        };
+        
+        // Update the approval status
+        let approval_id = approval.approval_id.unwrap();
+        let meaning = if status == ApprovalStatus::Approved { SignatureMeaning::Approve } else { SignatureMeaning::Review };
+        approval_manager.update_status_in_transaction(
+            approval_id,
+            status,
+            comments,
+            tx
+        )?;
+        
+        // Sign the decision
+        self.sign_in_transaction(revision_id, Some(approval_id), meaning, tx)?;
+        
+        Ok(())
+    }
+    
+    /// Sign a revision with the key of the current user, if signing is enabled
+    fn sign_in_transaction(
+        &self,
+        revision_id: i64,
+        approval_id: Option<i64>,
+        meaning: SignatureMeaning,
+        tx: &Transaction,
+    ) -> PartManagementResult<()> {
+        if let Some(key_store) = &self.key_store {
+            let key = key_store.load_or_create(&self.current_user.username)?;
+            SignatureManager::new(self.connection_manager)
+                .sign_in_transaction(revision_id, approval_id, &self.current_user, meaning, &key, tx)?;
+        }
+        Ok(())
+    }
+    
+    /// Release a revision within an existing journaled transaction
+    ///
+    /// The revision is moved to Released with the given workflow transition, or with the
+    /// transition of its workflow that leads to Released, then its review branch is merged into
+    /// the mainline and the release is signed and tagged.
+    fn release_revision_in_transaction(
+        &self,
+        revision_id: i64,
+        transition_id: Option<i64>,
+        repo_path: &Path,
+        tx: &Transaction,
+    ) -> PartManagementResult<TransitionOutcome> {
+        // Create managers
+        let revision_manager = RevisionManager::new(self.connection_manager);
+        let part_manager = PartManager::new(self.connection_manager);
+        
+        // Get the revision
+        let revision = revision_manager.get_revision_in_transaction(revision_id, tx)?;
+        
+        // Revisions of a change order are released together
+        self.check_not_in_change_order_in_transaction(revision_id, "release", tx)?;
+        
+        // Check if the user has permission to release the revision
+        self.check_permission_in_transaction(
+            PermissionAction::Release,
+            &revision,
+            "User does not have permission to release this revision",
+            tx,
+        )?;
+        
+        // Move the revision to Released; the workflow enforces the approval requirement, and the
+        // signature of the release is verified once it has been tagged
+        let workflow_engine = WorkflowEngine::new(self.connection_manager).with_deferred_signed_release();
+        let outcome = match transition_id {
+            Some(transition_id) => workflow_engine.execute_transition_in_transaction(revision_id, transition_id, &self.current_user.username, tx)?,
+            None => workflow_engine.transition_to_state_in_transaction(
+                revision_id,
+                RevisionStatus::Released.to_str(),
+                &self.current_user.username,
+                tx,
+            )?,
+        };
+        
+        // Get the part
+        let part = part_manager.get_part_in_transaction(revision.part_id, tx)?;
+        
+        // Generate the display part number
+        let display_part_number = part.display_part_number_in_transaction(tx);
+        
+        // Checkout the mainline
+        let repo = self.git_manager.open_repository(repo_path)?;
+        let branching = self.git_manager.branching_strategy();
+        self.git_manager.checkout_branch(&repo, branching.mainline())?;
+        
+        // Merge the branch the revision was reviewed on into the mainline, provided it is still
+        // what was approved
+        let context = self.branch_context(&display_part_number, &revision);
+        let review_branch = self.find_review_branch(&repo, &context);
+        if let Some(review_branch) = review_branch.as_ref().filter(|branch| !branching.is_mainline(branch)) {
+            self.check_approved_commit(&repo, review_branch, &revision)?;
+            self.merge_release_branch(&repo, review_branch)?;
+        }
+        
+        // Run the actions of the transition
+        self.run_transition_actions_in_transaction(revision_id, &outcome, Some(repo_path), tx)?;
+        
+        // Tag the released commit, record it on the revision and sign the release of the
+        // approved content with it
+        let release_commit = repo.head()
+            .and_then(|head| head.peel_to_commit())
+            .map_err(GitBackendError::GitError)?
+            .id();
+        self.tag_release_in_transaction(revision_id, &context, None, &repo, release_commit, tx)?;
+        self.sign_in_transaction(revision_id, None, SignatureMeaning::Release, tx)?;
+        self.check_signed_release_in_transaction(revision_id, &outcome, &repo, tx)?;
+        
+        // The draft and review worktrees are no longer needed
+        let draft_branch = self.find_draft_branch(&repo, &context);
+        self.remove_worktrees(&repo, draft_branch.iter().chain(review_branch.iter()))?;
+        
+        Ok(outcome)
+    }
+    
+    /// Merge a branch into the mainline for a release
+    ///
+    /// A conflicting merge is aborted so that nothing is released from the unmerged mainline.
+    fn merge_release_branch(&self, repo: &git2::Repository, branch: &str) -> PartManagementResult<()> {
+        let result = self.git_manager.merge_branch(repo, branch)?;
+        if !result.success {
+            self.git_manager.conflict_resolver(repo).abort_merge()?;
+            return Err(PartManagementError::Other(
+                format!("Merging {} failed with {} conflicted files", branch, result.conflicted_files.len()),
+            ));
+        }
+        Ok(())
+    }
+    
+    /// Check that a branch is still at the commit a revision was submitted for review with
+    ///
+    /// Approvals are signed against the submitted commit, so a branch that moved since then
+    /// cannot be released without another review.
+    fn check_approved_commit(&self, repo: &git2::Repository, branch: &str, revision: &Revision) -> PartManagementResult<()> {
+        let head = repo.find_branch(branch, git2::BranchType::Local)
+            .and_then(|branch| branch.get().peel_to_commit())
+            .map_err(GitBackendError::GitError)?
+            .id()
+            .to_string();
+        if revision.approved_commit_hash.as_deref() != Some(head.as_str()) {
+            return Err(PartManagementError::ApprovalRequired(format!(
+                "{} has changed since revision {} was submitted for review", branch, revision.version,
+            )));
+        }
+        Ok(())
+    }
+    
+    /// Get the hash of the commit at the head of the repository
+    fn head_commit_hash(&self, repo: &git2::Repository) -> PartManagementResult<String> {
+        Ok(repo.head()
+            .and_then(|head| head.peel_to_commit())
+            .map_err(GitBackendError::GitError)?
+            .id()
+            .to_string())
+    }
+    
+    /// Get the git signature of the current user
+    ///
+    /// Users without an email address are identified by their username on the local host.
+    fn git_signature(&self) -> PartManagementResult<git2::Signature<'static>> {
+        let name = self.current_user.display_name.as_deref().unwrap_or(&self.current_user.username);
+        let email = self.current_user.email.clone()
+            .unwrap_or_else(|| format!("{}@localhost", self.current_user.username));
+        Ok(git2::Signature::now(name, &email).map_err(GitBackendError::GitError)?)
+    }
+    
+    /// Tag the release of a revision and record the released commit on the revision
+    ///
+    /// The annotated tag carries the release metadata: who released and approved the revision,
+    /// when, and with which change order.
+    fn tag_release_in_transaction(
+        &self,
+        revision_id: i64,
+        context: &BranchContext,
+        change_order: Option<&str>,
+        repo: &git2::Repository,
+        release_commit: git2::Oid,
+        tx: &Transaction,
+    ) -> PartManagementResult<()> {
+        let mut approvers: Vec<String> = ApprovalManager::new(self.connection_manager)
+            .get_approvals_for_revision_in_transaction(revision_id, tx)?
+            .into_iter()
+            .filter(|approval| approval.status == ApprovalStatus::Approved)
+            .map(|approval| approval.approver)
+            .collect();
+        approvers.sort();
+        approvers.dedup();
+        
+        let release = ReleaseTag {
+            part_number: context.part_number.to_string(),
+            revision: context.revision.to_string(),
+            released_by: self.current_user.username.clone(),
+            release_date: Utc::now(),
+            approvers,
+            change_order: change_order.map(str::to_string),
+        };
+        let tag_name = self.git_manager.release_tag_name(context);
+        self.git_manager.create_release_tag(repo, &tag_name, release_commit, &self.git_signature()?, &release)?;
+        
+        RevisionManager::new(self.connection_manager).with_actor(&self.current_user.username)
+            .update_commit_hash_in_transaction(revision_id, &release_commit.to_string(), tx)?;
+        Ok(())
+    }
+    
+    /// Verify the signatures of the release of a revision within an existing transaction
+    fn verify_release_in_transaction(
+        &self,
+        revision_id: i64,
+        repo: &git2::Repository,
+        tx: &Transaction,
+    ) -> PartManagementResult<ReleaseVerification> {
+        let revision = RevisionManager::new(self.connection_manager).get_revision_in_transaction(revision_id, tx)?;
+        let commit_hash = revision.commit_hash.clone().ok_or_else(|| {
+            PartManagementError::Other(format!("Revision {} has no released commit", revision.version))
+        })?;
+        let display_part_number = PartManager::new(self.connection_manager)
+            .get_part_in_transaction(revision.part_id, tx)?
+            .display_part_number_in_transaction(tx);
+        let tag_name = self.git_manager.release_tag_name(&self.branch_context(&display_part_number, &revision));
+        
+        let commit_oid = git2::Oid::from_str(&commit_hash).map_err(GitBackendError::GitError)?;
+        Ok(ReleaseVerification {
+            commit: self.git_manager.verify_commit_signature(repo, commit_oid)?,
+            tag: self.git_manager.verify_tag_signature(repo, &tag_name)?,
+            commit_hash,
+            tag_name,
+        })
+    }
+    
+    /// Check the SignedRelease guards of an executed transition
+    ///
+    /// The guards can only be evaluated once the release has been tagged; a revision without a
+    /// valid signature on its release commit or tag does not count as released.
+    fn check_signed_release_in_transaction(
+        &self,
+        revision_id: i64,
+        outcome: &TransitionOutcome,
+        repo: &git2::Repository,
+        tx: &Transaction,
+    ) -> PartManagementResult<()> {
+        let guards: Vec<_> = WorkflowManager::new(self.connection_manager)
+            .get_transition_guards_in_transaction(outcome.transition.transition_id.unwrap_or_default(), tx)?
+            .into_iter()
+            .filter(|guard| guard.guard_type == TransitionGuardType::SignedRelease)
+            .collect();
+        if guards.is_empty() {
+            return Ok(());
+        }
+        
+        let revision = RevisionManager::new(self.connection_manager).get_revision_in_transaction(revision_id, tx)?;
+        let message = if revision.commit_hash.is_none() {
+            format!("Revision {} has no released commit", revision.version)
+        } else {
+            let verification = self.verify_release_in_transaction(revision_id, repo, tx)?;
+            if verification.is_signed() {
+                return Ok(());
+            }
+            format!(
+                "Neither commit {} nor tag {} of the release carries a valid signature",
+                verification.commit_hash,
+                verification.tag_name,
+            )
+        };
+        Err(PartManagementError::GuardsFailed(guards
+            .into_iter()
+            .map(|guard| GuardFailure {
+                guard_id: guard.guard_id.unwrap_or_default(),
+                guard_type: guard.guard_type,
+                part_id: None,
+                message: message.clone(),
+            })
+            .collect()))
+    }
+    
+    /// Get the journal manager that keeps the database and the repository in agreement
+    fn journal(&self) -> OperationJournalManager<'_> {
+        OperationJournalManager::new(self.connection_manager, self.git_manager)
+    }
+    
+    /// Get the values substituted into the branch names of a revision
+    ///
+    /// The branches of a revision are named after its author, so that every user finds the same
+    /// branches.
+    fn branch_context<'b>(&self, display_part_number: &'b str, revision: &'b Revision) -> BranchContext<'b> {
+        BranchContext::new(display_part_number, &revision.version, &revision.created_by)
+    }
+    
+    /// Get the branch collecting the changes of a change order
+    fn change_order_branch(&self, change_order: &ChangeOrder) -> String {
+        let context = BranchContext::new("", "", &change_order.created_by).with_change_order(&change_order.eco_number);
+        self.git_manager.branching_strategy().change_order_branch(&context)
+    }
+    
+    /// Find the draft branch of a revision
+    fn find_draft_branch(&self, repo: &git2::Repository, context: &BranchContext) -> Option<String> {
+        self.git_manager.branching_strategy()
+            .draft_branch_candidates(context)
+            .into_iter()
+            .find(|branch| repo.find_branch(branch, git2::BranchType::Local).is_ok())
+    }
+    
+    /// Find the branch a revision is reviewed on outside a change order
+    ///
+    /// This is the review branch of the part, or the draft branch of the revision if the
+    /// branching strategy has no review branches.
+    fn find_review_branch(&self, repo: &git2::Repository, context: &BranchContext) -> Option<String> {
+        match self.git_manager.branching_strategy().review_branch(context) {
+            Some(review_branch) => Some(review_branch)
+                .filter(|branch| repo.find_branch(branch, git2::BranchType::Local).is_ok()),
+            None => self.find_draft_branch(repo, context),
+        }
+    }
+    
+    /// Find the branch a revision is worked on in its current state
+    ///
+    /// Drafts are worked on their draft branch and revisions in review on their review branch,
+    /// or on the branch of their change order. Other revisions live on the mainline.
+    fn find_working_branch_in_transaction(
+        &self,
+        revision: &Revision,
+        repo: &git2::Repository,
+        tx: &Transaction,
+    ) -> PartManagementResult<Option<String>> {
+        let display_part_number = PartManager::new(self.connection_manager)
+            .get_part_in_transaction(revision.part_id, tx)?
+            .display_part_number_in_transaction(tx);
+        let context = self.branch_context(&display_part_number, revision);
+        let branch = match revision.status {
+            RevisionStatus::Draft => self.find_draft_branch(repo, &context),
+            RevisionStatus::InReview => {
+                let change_order = ChangeOrderManager::new(self.connection_manager)
+                    .get_open_change_order_for_revision_in_transaction(revision.revision_id.unwrap_or_default(), tx)?;
+                match change_order {
+                    Some(change_order) => Some(self.change_order_branch(&change_order))
+                        .filter(|branch| repo.find_branch(branch, git2::BranchType::Local).is_ok()),
+                    None => self.find_review_branch(repo, &context),
+                }
+            }
+            _ => None,
+        };
+        Ok(branch)
+    }
+    
+    /// Get the working directory of a branch
+    ///
+    /// The mainline is worked on in the main working directory of the repository, every other
+    /// branch in its own worktree, which is created if it does not exist yet.
+    fn branch_working_path(&self, repo: &git2::Repository, repo_path: &Path, branch: &str) -> PartManagementResult<PathBuf> {
+        if self.git_manager.branching_strategy().is_mainline(branch) {
+            return Ok(repo_path.to_path_buf());
+        }
+        Ok(self.git_manager.worktree_manager(repo).open_worktree(branch)?)
+    }
+    
+    /// Open the repository of the working directory of a branch
+    fn open_branch_repository(&self, repo: &git2::Repository, repo_path: &Path, branch: &str) -> PartManagementResult<git2::Repository> {
+        let working_path = self.branch_working_path(repo, repo_path, branch)?;
+        Ok(self.git_manager.open_repository(&working_path)?)
+    }
+    
+    /// Resolve the working directory of a revision within an existing transaction
+    fn working_path_in_transaction(&self, revision_id: i64, repo_path: &Path, tx: &Transaction) -> PartManagementResult<PathBuf> {
+        let revision = RevisionManager::new(self.connection_manager).get_revision_in_transaction(revision_id, tx)?;
+        let repo = self.git_manager.open_repository(repo_path)?;
+        match self.find_working_branch_in_transaction(&revision, &repo, tx)? {
+            Some(branch) => self.branch_working_path(&repo, repo_path, &branch),
+            None => Ok(repo_path.to_path_buf()),
+        }
+    }
+    
+    /// Remove the worktrees of branches that are no longer worked on
+    fn remove_worktrees<'b>(&self, repo: &git2::Repository, branches: impl Iterator<Item = &'b String>) -> PartManagementResult<()> {
+        let branching = self.git_manager.branching_strategy();
+        let worktree_manager = self.git_manager.worktree_manager(repo);
+        for branch in branches.filter(|branch| !branching.is_mainline(branch)) {
+            worktree_manager.remove_worktree(branch)?;
+        }
+        Ok(())
+    }
+    
+    /// Record the decision of the current user on the revisions of a change order in review
+    ///
+    /// Revisions whose approval policy does not include the user are skipped.
+    fn review_change_order_in_transaction(
+        &self,
+        eco_id: i64,
+        status: ApprovalStatus,
+        comments: Option<&str>,
+        tx: &Transaction,
+    ) -> PartManagementResult<Vec<Revision>> {
+        let change_order_manager = ChangeOrderManager::new(self.connection_manager);
+        let action = if status == ApprovalStatus::Rejected { "reject" } else { "approve" };
+        
+        let change_order = change_order_manager.get_change_order_in_transaction(eco_id, tx)?;
+        if change_order.status != ChangeOrderStatus::InReview {
+            return Err(PartManagementError::InvalidStateTransition(
+                format!("Cannot {} {}: current status is {}", action, change_order.eco_number, change_order.status.to_str()),
+            ));
+        }
+        
+        let revisions = change_order_manager.get_revisions_in_transaction(eco_id, tx)?;
+        let mut reviewed = 0;
+        for revision in &revisions {
+            let revision_id = revision.revision_id.unwrap_or_default();
+            if !self.is_reviewer_in_transaction(revision_id, tx)? {
+                continue;
+            }
+            self.review_revision_in_transaction(revision_id, status.clone(), comments, tx)?;
+            reviewed += 1;
+        }
+        
+        if reviewed == 0 {
+            return Err(PartManagementError::PermissionDenied(format!(
+                "User is not a reviewer of any revision of {} and cannot {} it", change_order.eco_number, action
+            )));
+        }
+        
+        Ok(revisions)
+    }
+    
+    /// Combine the requested reviewers with the reviewers of the revision's approval policy
+    ///
+    /// With a policy that has groups, every requested reviewer must belong to one of them and the
+    /// reviewers, not counting the submitter, must be able to reach the quorum.
+    fn collect_reviewers_in_transaction(
+        &self,
+        revision_id: i64,
+        requested: Vec<String>,
+        tx: &Transaction,
+    ) -> PartManagementResult<Vec<String>> {
+        let policy_manager = ApprovalPolicyManager::new(self.connection_manager);
+        let mut reviewers: BTreeSet<String> = requested.into_iter().collect();
+
+        if let Some(policy) = policy_manager.resolve_policy_in_transaction(revision_id, None, tx)? {
+            let pool = policy_manager.get_reviewers_in_transaction(policy.policy_id.unwrap_or_default(), tx)?;
+            if !pool.is_empty() {
+                if let Some(outsider) = reviewers.iter().find(|reviewer| !pool.contains(reviewer)) {
+                    return Err(DatabaseError::ValidationError(format!(
+                        "{} is not a reviewer under approval policy {}", outsider, policy.name
+                    )).into());
+                }
+                reviewers.extend(pool);
+            }
+
+            reviewers.remove(&self.current_user.username);
+            if (reviewers.len() as i64) < policy.quorum {
+                return Err(DatabaseError::ValidationError(format!(
+                    "Approval policy {} needs {} approvals but only {} reviewers are available",
+                    policy.name, policy.quorum, reviewers.len()
+                )).into());
+            }
+        }
+
+        Ok(reviewers.into_iter().collect())
+    }
+    
+    /// Check that the current user may perform an action on a revision
+    fn check_permission_in_transaction(
+        &self,
+        action: PermissionAction,
+        revision: &Revision,
+        message: &str,
+        tx: &Transaction,
+    ) -> PartManagementResult<()> {
+        let part = PartManager::new(self.connection_manager).get_part_in_transaction(revision.part_id, tx)?;
+        let permitted = PermissionManager::new(self.connection_manager).is_permitted_in_transaction(
+            &self.current_user,
+            action,
+            &part.category,
+            Some(&revision.created_by),
+            tx,
+        )?;
+        if !permitted {
+            return Err(PartManagementError::PermissionDenied(message.to_string()));
+        }
+        Ok(())
+    }
+    
+    /// Check that the current user is a reviewer under the revision's approval policy
+    fn check_reviewer_in_transaction(&self, revision_id: i64, action: &str, tx: &Transaction) -> PartManagementResult<()> {
+        let policy_manager = ApprovalPolicyManager::new(self.connection_manager);
+        if let Some(policy) = policy_manager.resolve_policy_in_transaction(revision_id, None, tx)? {
+            let pool = policy_manager.get_reviewers_in_transaction(policy.policy_id.unwrap_or_default(), tx)?;
+            if !pool.is_empty() && !pool.contains(&self.current_user.username) {
+                return Err(PartManagementError::PermissionDenied(format!(
+                    "User is not a reviewer under approval policy {} and cannot {} this revision",
+                    policy.name, action
+                )));
+            }
+        }
+        Ok(())
+    }
+    
+    /// Check whether the current user is a reviewer under the revision's approval policy
+    fn is_reviewer_in_transaction(&self, revision_id: i64, tx: &Transaction) -> PartManagementResult<bool> {
+        let policy_manager = ApprovalPolicyManager::new(self.connection_manager);
+        if let Some(policy) = policy_manager.resolve_policy_in_transaction(revision_id, None, tx)? {
+            let pool = policy_manager.get_reviewers_in_transaction(policy.policy_id.unwrap_or_default(), tx)?;
+            return Ok(pool.is_empty() || pool.contains(&self.current_user.username));
+        }
+        Ok(true)
+    }
+    
+    /// Check that a revision is not reviewed and released through an open change order
+    fn check_not_in_change_order_in_transaction(&self, revision_id: i64, action: &str, tx: &Transaction) -> PartManagementResult<()> {
+        if let Some(change_order) = ChangeOrderManager::new(self.connection_manager)
+            .get_open_change_order_for_revision_in_transaction(revision_id, tx)?
+        {
+            return Err(PartManagementError::InvalidStateTransition(format!(
+                "Revision belongs to {}; {} the change order instead", change_order.eco_number, action
+            )));
+        }
+        Ok(())
+    }
+    
+    /// Run the actions of an executed transition in order
+    ///
+    /// Git actions need a repository; transitions executed without one can only emit events.
+    fn run_transition_actions_in_transaction(
+        &self,
+        revision_id: i64,
+        outcome: &TransitionOutcome,
+        repo_path: Option<&Path>,
+        tx: &Transaction,
+    ) -> PartManagementResult<()> {
+        if outcome.actions.is_empty() {
+            return Ok(());
+        }
+        
+        // Get the revision and its part for the placeholders
+        let revision = RevisionManager::new(self.connection_manager).get_revision_in_transaction(revision_id, tx)?;
+        let part = PartManager::new(self.connection_manager).get_part_in_transaction(revision.part_id, tx)?;
+        let display_part_number = part.display_part_number_in_transaction(tx);
+        
+        for action in &outcome.actions {
+            let parameter = action.expand_parameter(&display_part_number, &revision.version);
+            // Actions run in the working directory of the branch the revision is now on
+            let repository = || -> PartManagementResult<(PathBuf, git2::Repository)> {
+                let repo_path = repo_path.ok_or_else(|| PartManagementError::Other(
+                    format!("Action {} of transition {} needs a repository", action.action_type.to_str(), outcome.transition.name),
+                ))?;
+                let working_path = self.working_path_in_transaction(revision_id, repo_path, tx)?;
+                let repo = self.git_manager.open_repository(&working_path)?;
+                Ok((working_path, repo))
+            };
+            
+            match action.action_type {
+                TransitionActionType::EmitEvent => {
+                    let name = parameter.unwrap_or_else(|| outcome.transition.name.clone());
+                    WorkflowEngine::new(self.connection_manager).record_event_in_transaction(
+                        &name,
+                        revision_id,
+                        action.transition_id,
+                        &self.current_user.username,
+                        tx,
+                    )?;
+                }
+                TransitionActionType::CreateTag => {
+                    let (_, repo) = repository()?;
+                    let tag_name = parameter.unwrap_or_else(|| format!("{}/rev-{}", display_part_number, revision.version));
+                    let message = format!("{} revision {}", display_part_number, revision.version);
+                    let target = git2::Oid::from_str(&self.head_commit_hash(&repo)?).map_err(GitBackendError::GitError)?;
+                    self.git_manager.operation_handler(&repo).create_annotated_tag(&tag_name, target, &self.git_signature()?, &message)?;
+                }
+                TransitionActionType::MergeBranch => {
+                    let branch = parameter.ok_or_else(|| PartManagementError::Other(
+                        format!("Merge action of transition {} has no branch", outcome.transition.name),
+                    ))?;
+                    let (_, repo) = repository()?;
+                    let result = self.git_manager.merge_branch(&repo, &branch)?;
+                    if !result.success {
+                        return Err(PartManagementError::Other(
+                            format!("Merging {} failed with {} conflicted files", branch, result.conflicted_files.len()),
+                        ));
+                    }
+                }
+                TransitionActionType::RegenerateReadme => {
+                    let (repo_path, repo) = repository()?;
+                    let readme_path = Path::new("parts").join(&display_part_number).join("README.md");
+                    let absolute_path = repo_path.join(&readme_path);
+                    if let Some(parent) = absolute_path.parent() {
+                        fs::create_dir_all(parent).map_err(GitBackendError::IoError)?;
+                    }
+                    fs::write(&absolute_path, self.render_readme_in_transaction(&part, &revision, &display_part_number, tx)?)
+                        .map_err(GitBackendError::IoError)?;
+                    let metadata = self.commit_metadata_in_transaction(&revision, &display_part_number, outcome, tx)?;
+                    self.git_manager.commit_with_metadata(
+                        &repo,
+                        &format!("Regenerate README for {} revision {}", display_part_number, revision.version),
+                        &[readme_path.as_path()],
+                        &metadata,
+                        &self.git_signature()?,
+                    )?;
+                }
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Get the metadata of a commit made for a revision by a transition
+    ///
+    /// The commit message names the part, revision and open change order in trailers and is
+    /// signed off by the current user; the transition is recorded in the notes of the commit.
+    fn commit_metadata_in_transaction(
+        &self,
+        revision: &Revision,
+        display_part_number: &str,
+        outcome: &TransitionOutcome,
+        tx: &Transaction,
+    ) -> PartManagementResult<Metadata> {
+        let revision_id = revision.revision_id.unwrap_or_default();
+        let change_order = ChangeOrderManager::new(self.connection_manager)
+            .get_open_change_order_for_revision_in_transaction(revision_id, tx)?;
+        let signature = self.git_signature()?;
+        
+        let trailers = CommitTrailers {
+            part: Some(display_part_number.to_string()),
+            revision: Some(revision.version.clone()),
+            change_order: change_order.map(|change_order| change_order.eco_number),
+            signed_off_by: vec![format!("{} <{}>", signature.name().unwrap_or_default(), signature.email().unwrap_or_default())],
+            other: Vec::new(),
+        };
+        let properties = [
+            ("revision_id".to_string(), revision_id.to_string()),
+            ("transition".to_string(), outcome.transition.name.clone()),
+            ("state".to_string(), outcome.state.name.clone()),
+            ("performed_by".to_string(), self.current_user.username.clone()),
+        ].into_iter().collect();
+        Ok(Metadata { trailers, properties })
+    }
+    
+    /// Render the README of a part from its metadata
+    fn render_readme_in_transaction(
+        &self,
+        part: &Part,
+        revision: &Revision,
+        display_part_number: &str,
+        tx: &Transaction,
+    ) -> PartManagementResult<String> {
+        let mut readme = format!("# {} {}\n\n", display_part_number, part.name);
+        if let Some(description) = &part.description {
+            readme.push_str(&format!("{}\n\n", description));
+        }
+        
+        readme.push_str("| Field | Value |\n|---|---|\n");
+        readme.push_str(&format!("| Category | {} / {} |\n", part.category, part.subcategory));
+        readme.push_str(&format!("| Revision | {} |\n", revision.version));
+        readme.push_str(&format!("| Status | {} |\n", revision.status.to_str()));
+        
+        // List the part and revision properties
+        let mut stmt = tx.prepare(
+            "SELECT key, value FROM Properties
+             WHERE part_id = ?1 OR revision_id = ?2
+             ORDER BY key",
+        )?;
+        let properties = stmt.query_map(params![part.part_id, revision.revision_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+        })?.collect::<Result<Vec<_>, _>>()?;
+        
+        if !properties.is_empty() {
+            readme.push_str("\n## Properties\n\n");
+            for (key, value) in properties {
+                readme.push_str(&format!("- **{}**: {}\n", key, value.unwrap_or_default()));
+            }
+        }
+        
+        Ok(readme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::DatabaseManager;
+    use crate::git_backend::{GitBackendConfig, AuthConfig};
+    use crate::git_backend::branching::{BranchingMode, BranchTemplates};
+    use crate::git_backend::signing::{SigningConfig, SigningFormat};
+    use crate::database::workflow::{WorkflowManager, TransitionGuard, TransitionAction};
+    use crate::database::audit::{AuditFilter, AuditManager};
+    use crate::database::category::CategoryManager;
+    use crate::database::revision_scheme::RevisionScheme;
+    use tempfile::tempdir;
+    
+    #[test]
+    fn test_part_creation_and_workflow() {
+        // Create a temporary directory for the test database
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        
+        // Create a temporary directory for the Git repository
+        let repo_dir = tempdir().unwrap();
+        let repo_path = repo_dir.path();
+        
+        // Create a new database manager and initialize the schema
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+        
+        // Create a workflow manager and set up the default part workflow
+        let workflow_manager = WorkflowManager::new(db_manager.connection_manager());
+        workflow_manager.create_default_part_workflow().unwrap();
+        
+        // Create a Git backend manager
+        let git_config = GitBackendConfig::default();
+        let auth_config = AuthConfig::default();
+        let git_manager = GitBackendManager::new(git_config, auth_config).unwrap();
+        
+        // Initialize a Git repository
         let repo = git_manager.init_repository(repo_path).unwrap();
         
         // Create an initial commit so that the main branch exists
@@ -806,6 +1919,12 @@ mod tests {
         let revision = revision_manager.get_revision(revision_id).unwrap();
         assert_eq!(revision.status, RevisionStatus::Draft);
         
+        // Check that the draft is worked on in its own worktree
+        let draft_path = part_mgmt.get_working_path(revision_id, repo_path).unwrap();
+        assert_ne!(draft_path, repo_path);
+        assert!(draft_path.is_dir());
+        assert_eq!(repo.head().unwrap().shorthand(), Some("main"));
+        
         // Create a reviewer user
         let reviewer = User::new("reviewer".to_string(), UserRole::Designer);
         
@@ -826,6 +1945,8 @@ mod tests {
         // Check that the revision status was updated
         let revision = revision_manager.get_revision(revision_id).unwrap();
         assert_eq!(revision.status, RevisionStatus::InReview);
+        let review_path = part_mgmt.get_working_path(revision_id, repo_path).unwrap();
+        assert_ne!(review_path, draft_path);
         
         // Check that an approval was created
         let approval_manager = ApprovalManager::new(db_manager.connection_manager());
@@ -854,6 +1975,34 @@ mod tests {
         let revision = revision_manager.get_revision(revision_id).unwrap();
         assert_eq!(revision.status, RevisionStatus::Released);
         
+        // Check that the release was tagged and the released commit recorded
+        let tag_name = format!("{}/rev-1", part.display_part_number(db_manager.connection_manager()));
+        let release = git_manager.get_release_tag(&repo, &tag_name).unwrap().unwrap();
+        assert_eq!(release.approvers, vec!["reviewer".to_string()]);
+        assert_eq!(release.released_by, "reviewer");
+        assert_eq!(release.change_order, None);
+        let tagged_commit = repo.revparse_single(&tag_name).unwrap().peel_to_commit().unwrap().id().to_string();
+        assert_eq!(revision.commit_hash, Some(tagged_commit));
+        
+        // Check that every change is recorded in the audit log for the user who made it
+        let entries = AuditManager::new(db_manager.connection_manager()).query(&AuditFilter {
+            entity_type: Some("Revision".to_string()),
+            entity_id: Some(revision_id),
+            ..AuditFilter::default()
+        }).unwrap();
+        let users = |status: &str| entries.iter()
+            .filter(|entry| entry.details.as_deref().is_some_and(|details| details.contains(status)))
+            .map(|entry| entry.user.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(entries[0].user, "test_user");
+        assert_eq!(users("\"In Review\""), vec!["test_user"]);
+        assert_eq!(users("\"Released\""), vec!["reviewer"]);
+        
+        // Check that the worktrees were cleaned up on release
+        assert!(!draft_path.exists());
+        assert!(!review_path.exists());
+        assert_eq!(part_mgmt.get_working_path(revision_id, repo_path).unwrap(), repo_path);
+        
         // Create a new revision
         let new_revision_id = reviewer_mgmt.create_revision(
             part.part_id,
@@ -865,11 +2014,483 @@ mod tests {
         assert_eq!(new_revision.status, RevisionStatus::Draft);
         assert_eq!(new_revision.version, "2");
         
+        // Releasing through a workflow transition also merges, signs and tags the revision
+        reviewer_mgmt.submit_for_review(new_revision_id, repo_path, vec!["test_user".to_string()]).unwrap();
+        part_mgmt.approve_revision(new_revision_id, None).unwrap();
+        let release_transition = WorkflowEngine::new(db_manager.connection_manager())
+            .get_available_transitions(new_revision_id)
+            .unwrap()
+            .into_iter()
+            .find(|transition| workflow_manager.get_workflow_state(transition.to_state_id).unwrap().name == "Released")
+            .unwrap();
+        part_mgmt.execute_transition(new_revision_id, release_transition.transition_id.unwrap(), repo_path).unwrap();
+        let new_revision = revision_manager.get_revision(new_revision_id).unwrap();
+        assert_eq!(new_revision.status, RevisionStatus::Released);
+        let tag_name = format!("{}/rev-2", part.display_part_number(db_manager.connection_manager()));
+        let tagged_commit = repo.revparse_single(&tag_name).unwrap().peel_to_commit().unwrap().id().to_string();
+        assert_eq!(new_revision.commit_hash, Some(tagged_commit));
+        
         // Mark the original revision as obsolete
         reviewer_mgmt.mark_as_obsolete(revision_id).unwrap();
         
         // Check that the revision status was updated
         let revision = revision_manager.get_revision(revision_id).unwrap();
         assert_eq!(revision.status, RevisionStatus::Obsolete);
+        
+        // The first revision of a part is labelled by the revision scheme of its category
+        let category_manager = CategoryManager::new(db_manager.connection_manager());
+        let mut category = category_manager.get_category_by_code("EL").unwrap();
+        category.revision_scheme = RevisionScheme::Alphabetic;
+        category_manager.update_category(&category).unwrap();
+        let (part, revision_id) = part_mgmt.create_part(
+            "Electronic".to_string(),
+            "Capacitor".to_string(),
+            "100nF Capacitor".to_string(),
+            None,
+            repo_path,
+        ).unwrap();
+        assert_eq!(revision_manager.get_revision(revision_id).unwrap().version, "A");
+        assert_eq!(revision_manager.get_latest_revision(part.part_id).unwrap().version, "A");
+        assert_eq!(revision_manager.get_next_version(part.part_id).unwrap(), "B");
+    }
+    
+    #[test]
+    fn test_change_order_release() {
+        // Create a temporary database and Git repository
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let repo_dir = tempdir().unwrap();
+        let repo_path = repo_dir.path();
+        
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+        WorkflowManager::new(db_manager.connection_manager()).create_default_part_workflow().unwrap();
+        
+        let git_manager = GitBackendManager::new(GitBackendConfig::default(), AuthConfig::default()).unwrap();
+        let repo = git_manager.init_repository(repo_path).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test User").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        
+        // Create an initial commit on main
+        let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        repo.commit(Some("refs/heads/main"), &sig, &sig, "Initial commit", &tree, &[]).unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+        
+        let designer = PartManagementManager::new(
+            db_manager.connection_manager(),
+            &git_manager,
+            User::new("designer".to_string(), UserRole::Designer),
+        );
+        let mut reviewer = PartManagementManager::new(
+            db_manager.connection_manager(),
+            &git_manager,
+            User::new("reviewer".to_string(), UserRole::Designer),
+        );
+        reviewer.set_key_store(Some(SigningKeyStore::new(&temp_dir.path().join("keys"))));
+        
+        // Create two parts and group their revisions in a change order
+        let (_, resistor_revision) = designer.create_part(
+            "Electronic".to_string(),
+            "Resistor".to_string(),
+            "10K Resistor".to_string(),
+            None,
+            repo_path,
+        ).unwrap();
+        let (_, bracket_revision) = designer.create_part(
+            "Mechanical".to_string(),
+            "Bracket".to_string(),
+            "Mounting Bracket".to_string(),
+            None,
+            repo_path,
+        ).unwrap();
+        
+        let change_order_manager = ChangeOrderManager::new(db_manager.connection_manager());
+        let change_order = ChangeOrder::new(
+            "Replace bracket".to_string(),
+            "Bracket cracks under vibration".to_string(),
+            Some("Rework units in stock".to_string()),
+            "designer".to_string(),
+        );
+        let eco_id = change_order_manager.create_change_order(&change_order).unwrap();
+        change_order_manager.add_revision(eco_id, resistor_revision).unwrap();
+        change_order_manager.add_revision(eco_id, bracket_revision).unwrap();
+        assert_eq!(change_order_manager.get_change_order(eco_id).unwrap().eco_number, "ECO-00001");
+        
+        // Revisions of the change order cannot be submitted on their own
+        assert!(matches!(
+            designer.submit_for_review(resistor_revision, repo_path, vec!["reviewer".to_string()]),
+            Err(PartManagementError::InvalidStateTransition(_))
+        ));
+        
+        // Submit and approve the change order as a unit
+        designer.submit_change_order_for_review(eco_id, repo_path, vec!["reviewer".to_string()]).unwrap();
+        let revision_manager = RevisionManager::new(db_manager.connection_manager());
+        assert_eq!(revision_manager.get_revision(bracket_revision).unwrap().status, RevisionStatus::InReview);
+        assert!(matches!(
+            reviewer.release_change_order(eco_id, repo_path),
+            Err(PartManagementError::ApprovalRequired(_))
+        ));
+        
+        reviewer.approve_change_order(eco_id, Some("Looks good".to_string())).unwrap();
+        assert_eq!(change_order_manager.evaluate(eco_id).unwrap(), ApprovalDecision::Approved);
+        
+        // A change order branch that moved after the approval cannot be released
+        let eco_branch = format!("refs/heads/{}", reviewer.change_order_branch(&change_order_manager.get_change_order(eco_id).unwrap()));
+        let approved_commit = repo.find_reference(&eco_branch).unwrap().peel_to_commit().unwrap();
+        repo.commit(Some(&eco_branch), &sig, &sig, "Unreviewed change", &approved_commit.tree().unwrap(), &[&approved_commit]).unwrap();
+        assert!(matches!(
+            reviewer.release_change_order(eco_id, repo_path),
+            Err(PartManagementError::ApprovalRequired(ref message)) if message.contains("since revision")
+        ));
+        repo.reference(&eco_branch, approved_commit.id(), true, "Drop unreviewed change").unwrap();
+        
+        // Releasing the change order releases every revision with a single tag
+        let commit_hash = reviewer.release_change_order(eco_id, repo_path).unwrap();
+        for revision_id in [resistor_revision, bracket_revision] {
+            let revision = revision_manager.get_revision(revision_id).unwrap();
+            assert_eq!(revision.status, RevisionStatus::Released);
+            assert_eq!(revision.commit_hash.as_deref(), Some(commit_hash.as_str()));
+            assert_eq!(revision.approved_commit_hash, Some(approved_commit.id().to_string()));
+            
+            // The approval covers the approved commit the release was signed with
+            let verifications = SignatureManager::new(db_manager.connection_manager()).verify(revision_id).unwrap();
+            assert!(verifications.iter().all(|verification| verification.is_valid()));
+            assert!(verifications.iter().any(|verification| verification.covers_release == Some(true)));
+            let release = verifications.iter().find(|verification| verification.signature.meaning == SignatureMeaning::Release).unwrap();
+            assert_eq!(release.signature.released_commit_hash.as_deref(), Some(commit_hash.as_str()));
+        }
+        
+        let released = change_order_manager.get_change_order(eco_id).unwrap();
+        assert_eq!(released.status, ChangeOrderStatus::Released);
+        assert_eq!(released.commit_hash, Some(commit_hash));
+        assert!(released.released_date.is_some());
+        let eco_tag = repo.revparse_single("refs/tags/ECO-00001").unwrap().peel_to_tag().unwrap();
+        assert_eq!(Some(eco_tag.target_id().to_string()), released.commit_hash);
+        assert_eq!(eco_tag.tagger().unwrap().name(), Some("reviewer"));
+        assert_eq!(git_manager.verify_tag_signature(&repo, "ECO-00001").unwrap(), Some(SignatureStatus::Unsigned));
+        
+        // Every revision is tagged with the change order it was released with
+        let resistor = revision_manager.get_revision(resistor_revision).unwrap();
+        let resistor_part = PartManager::new(db_manager.connection_manager()).get_part(resistor.part_id).unwrap();
+        let tag_name = format!("{}/rev-{}", resistor_part.display_part_number(db_manager.connection_manager()), resistor.version);
+        let release = git_manager.get_release_tag(&repo, &tag_name).unwrap().unwrap();
+        assert_eq!(release.change_order.as_deref(), Some("ECO-00001"));
+    }
+    
+    #[test]
+    fn test_trunk_only_branching() {
+        // Create a temporary database and a Git repository whose mainline is called trunk
+        let temp_dir = tempdir().unwrap();
+        let db_manager = DatabaseManager::new(temp_dir.path().join("test.db")).unwrap();
+        db_manager.initialize_schema().unwrap();
+        WorkflowManager::new(db_manager.connection_manager()).create_default_part_workflow().unwrap();
+        let repo_dir = tempdir().unwrap();
+        let repo_path = repo_dir.path();
+        
+        let git_config = GitBackendConfig {
+            default_branch: "trunk".to_string(),
+            branching_mode: BranchingMode::TrunkOnly,
+            ..GitBackendConfig::default()
+        };
+        let git_manager = GitBackendManager::new(git_config, AuthConfig::default()).unwrap();
+        let repo = git_manager.init_repository(repo_path).unwrap();
+        let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        repo.commit(Some("refs/heads/trunk"), &sig, &sig, "Initial commit", &tree, &[]).unwrap();
+        repo.set_head("refs/heads/trunk").unwrap();
+        
+        let designer = PartManagementManager::new(
+            db_manager.connection_manager(),
+            &git_manager,
+            User::new("designer".to_string(), UserRole::Designer),
+        );
+        let reviewer = PartManagementManager::new(
+            db_manager.connection_manager(),
+            &git_manager,
+            User::new("reviewer".to_string(), UserRole::Designer),
+        );
+        
+        // The part is drafted, reviewed and released on the mainline without any other branch
+        let (_, revision_id) = designer.create_part(
+            "Electronic".to_string(),
+            "Resistor".to_string(),
+            "10K Resistor".to_string(),
+            None,
+            repo_path,
+        ).unwrap();
+        assert_eq!(designer.get_working_path(revision_id, repo_path).unwrap(), repo_path);
+        designer.submit_for_review(revision_id, repo_path, vec!["reviewer".to_string()]).unwrap();
+        reviewer.approve_revision(revision_id, None).unwrap();
+        
+        // The README regenerated on release is committed with trailers and notes
+        let workflow_manager = WorkflowManager::new(db_manager.connection_manager());
+        let release = WorkflowEngine::new(db_manager.connection_manager()).get_available_transitions(revision_id).unwrap()
+            .into_iter()
+            .find(|transition| transition.name == "Approve")
+            .and_then(|transition| transition.transition_id)
+            .unwrap();
+        workflow_manager.create_transition_action(&TransitionAction::new(release, TransitionActionType::RegenerateReadme, None, 1)).unwrap();
+        reviewer.release_revision(revision_id, repo_path).unwrap();
+        
+        // The commit and its notes are authored by the releasing user, not the repository user
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.author().email(), Some("reviewer@localhost"));
+        let note = repo.find_note(Some(crate::git_backend::NOTES_REF), head.id()).unwrap();
+        assert_eq!(note.author().email(), Some("reviewer@localhost"));
+        let metadata = git_manager.get_commit_metadata(&repo, &head).unwrap();
+        assert_eq!(metadata.trailers.part.as_deref(), Some("EL-RES-10000"));
+        assert_eq!(metadata.trailers.revision.as_deref(), Some("1"));
+        assert_eq!(metadata.trailers.signed_off_by, vec!["reviewer <reviewer@localhost>".to_string()]);
+        assert_eq!(metadata.properties.get("transition").map(String::as_str), Some("Approve"));
+        assert!(repo.find_reference(crate::git_backend::NOTES_REF).is_ok());
+        
+        let revision = RevisionManager::new(db_manager.connection_manager()).get_revision(revision_id).unwrap();
+        assert_eq!(revision.status, RevisionStatus::Released);
+        let branches: Vec<String> = repo.branches(Some(git2::BranchType::Local)).unwrap()
+            .map(|branch| branch.unwrap().0.name().unwrap().unwrap().to_string())
+            .collect();
+        assert_eq!(branches, vec!["trunk".to_string()]);
+        assert!(repo.worktrees().unwrap().is_empty());
+    }
+    
+    #[test]
+    fn test_signed_release() {
+        // Generate an SSH signing key; skip the test where ssh-keygen is not installed
+        let key_dir = tempdir().unwrap();
+        let key_path = key_dir.path().join("id_ed25519");
+        let generated = std::process::Command::new("ssh-keygen")
+            .args(["-q", "-t", "ed25519", "-N", "", "-C", "reviewer@localhost", "-f"])
+            .arg(&key_path)
+            .output();
+        if !matches!(generated, Ok(ref output) if output.status.success()) {
+            eprintln!("Skipping test_signed_release: ssh-keygen is not available");
+            return;
+        }
+        let allowed_signers = key_dir.path().join("allowed_signers");
+        let public_key = fs::read_to_string(key_path.with_extension("pub")).unwrap();
+        fs::write(&allowed_signers, format!("reviewer@localhost {}", public_key)).unwrap();
+        
+        // Create a temporary database and Git repository whose releases must be signed
+        let temp_dir = tempdir().unwrap();
+        let db_manager = DatabaseManager::new(temp_dir.path().join("test.db")).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let workflow_manager = WorkflowManager::new(db_manager.connection_manager());
+        workflow_manager.create_default_part_workflow().unwrap();
+        let repo_dir = tempdir().unwrap();
+        let repo_path = repo_dir.path();
+        
+        let git_manager = GitBackendManager::new(GitBackendConfig::default(), AuthConfig::default()).unwrap();
+        let signing_git_manager = GitBackendManager::new(GitBackendConfig::default(), AuthConfig {
+            signing: Some(SigningConfig {
+                allowed_signers: Some(allowed_signers),
+                ..SigningConfig::ssh(&key_path)
+            }),
+            ..AuthConfig::default()
+        }).unwrap();
+        let repo = git_manager.init_repository(repo_path).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test User").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        repo.commit(Some("refs/heads/main"), &sig, &sig, "Initial commit", &tree, &[]).unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+        
+        let designer = PartManagementManager::new(
+            db_manager.connection_manager(),
+            &git_manager,
+            User::new("designer".to_string(), UserRole::Designer),
+        );
+        let reviewer = PartManagementManager::new(
+            db_manager.connection_manager(),
+            &git_manager,
+            User::new("reviewer".to_string(), UserRole::Designer),
+        );
+        let signing_reviewer = PartManagementManager::new(
+            db_manager.connection_manager(),
+            &signing_git_manager,
+            User::new("reviewer".to_string(), UserRole::Designer),
+        );
+        
+        let (_, revision_id) = designer.create_part(
+            "Electronic".to_string(),
+            "Resistor".to_string(),
+            "10K Resistor".to_string(),
+            None,
+            repo_path,
+        ).unwrap();
+        let draft_path = designer.get_working_path(revision_id, repo_path).unwrap();
+        fs::write(draft_path.join("notes.txt"), "10K").unwrap();
+        let draft_repo = git_manager.open_repository(&draft_path).unwrap();
+        git_manager.operation_handler(&draft_repo).commit("Add notes", &[Path::new("notes.txt")]).unwrap();
+        designer.submit_for_review(revision_id, repo_path, vec!["reviewer".to_string()]).unwrap();
+        reviewer.approve_revision(revision_id, None).unwrap();
+        
+        // Require a signed release
+        let release = WorkflowEngine::new(db_manager.connection_manager()).get_available_transitions(revision_id).unwrap()
+            .into_iter()
+            .find(|transition| transition.name == "Approve")
+            .and_then(|transition| transition.transition_id)
+            .unwrap();
+        workflow_manager.create_transition_guard(&TransitionGuard::new(release, TransitionGuardType::SignedRelease, None)).unwrap();
+        workflow_manager.create_transition_action(&TransitionAction::new(
+            release,
+            TransitionActionType::CreateTag,
+            Some("EL-RES-10000/approved".to_string()),
+            1,
+        )).unwrap();
+        
+        // An unsigned release does not count and leaves neither the release nor its tag behind
+        let result = reviewer.release_revision(revision_id, repo_path);
+        assert!(matches!(result, Err(PartManagementError::GuardsFailed(ref failures)) if failures[0].guard_type == TransitionGuardType::SignedRelease));
+        let revision = RevisionManager::new(db_manager.connection_manager()).get_revision(revision_id).unwrap();
+        assert_eq!(revision.status, RevisionStatus::InReview);
+        assert!(repo.tag_names(None).unwrap().is_empty());
+        
+        // A release signed with the configured key is accepted
+        signing_reviewer.release_revision(revision_id, repo_path).unwrap();
+        let verification = signing_reviewer.verify_release(revision_id, repo_path).unwrap();
+        assert!(verification.is_signed());
+        assert_eq!(verification.tag_name, "EL-RES-10000/rev-1");
+        assert_eq!(
+            verification.commit,
+            SignatureStatus::Valid { format: SigningFormat::Ssh, signer: Some("reviewer@localhost".to_string()) },
+        );
+        assert!(verification.tag.unwrap().is_valid());
+        assert!(signing_git_manager.verify_tag_signature(&repo, "EL-RES-10000/approved").unwrap().unwrap().is_valid());
+        assert_eq!(repo.find_commit(git2::Oid::from_str(&verification.commit_hash).unwrap()).unwrap().parent_count(), 2);
+    }
+    
+    #[test]
+    fn test_release_merge_conflict() {
+        // Create a temporary database and Git repository
+        let temp_dir = tempdir().unwrap();
+        let db_manager = DatabaseManager::new(temp_dir.path().join("test.db")).unwrap();
+        db_manager.initialize_schema().unwrap();
+        WorkflowManager::new(db_manager.connection_manager()).create_default_part_workflow().unwrap();
+        let repo_dir = tempdir().unwrap();
+        let repo_path = repo_dir.path();
+        
+        let git_manager = GitBackendManager::new(GitBackendConfig::default(), AuthConfig::default()).unwrap();
+        let repo = git_manager.init_repository(repo_path).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test User").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        repo.commit(Some("refs/heads/main"), &sig, &sig, "Initial commit", &tree, &[]).unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+        
+        let designer = PartManagementManager::new(
+            db_manager.connection_manager(),
+            &git_manager,
+            User::new("designer".to_string(), UserRole::Designer),
+        );
+        let reviewer = PartManagementManager::new(
+            db_manager.connection_manager(),
+            &git_manager,
+            User::new("reviewer".to_string(), UserRole::Designer),
+        );
+        
+        // The draft and the mainline change the same file
+        let (_, revision_id) = designer.create_part(
+            "Electronic".to_string(),
+            "Resistor".to_string(),
+            "10K Resistor".to_string(),
+            None,
+            repo_path,
+        ).unwrap();
+        let draft_path = designer.get_working_path(revision_id, repo_path).unwrap();
+        fs::write(draft_path.join("value.txt"), "10K").unwrap();
+        let draft_repo = git_manager.open_repository(&draft_path).unwrap();
+        git_manager.operation_handler(&draft_repo).commit("Set value", &[Path::new("value.txt")]).unwrap();
+        designer.submit_for_review(revision_id, repo_path, vec!["reviewer".to_string()]).unwrap();
+        reviewer.approve_revision(revision_id, None).unwrap();
+        
+        fs::write(repo_path.join("value.txt"), "22K").unwrap();
+        let mainline_commit = git_manager.operation_handler(&repo).commit("Change value", &[Path::new("value.txt")]).unwrap();
+        let reviewed_commit = RevisionManager::new(db_manager.connection_manager()).get_revision(revision_id).unwrap().approved_commit_hash;
+        assert!(reviewed_commit.is_some());
+        
+        // The conflicting merge fails the release instead of tagging the unmerged mainline
+        assert!(reviewer.release_revision(revision_id, repo_path).is_err());
+        let revision = RevisionManager::new(db_manager.connection_manager()).get_revision(revision_id).unwrap();
+        assert_eq!(revision.status, RevisionStatus::InReview);
+        assert_eq!(revision.approved_commit_hash, reviewed_commit);
+        assert_eq!(revision.commit_hash, None);
+        assert!(repo.tag_names(None).unwrap().is_empty());
+        assert_eq!(repo.head().unwrap().target(), Some(mainline_commit));
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert!(!repo.index().unwrap().has_conflicts());
+    }
+    
+    #[test]
+    fn test_user_branch_names() {
+        // Create a temporary database and a Git repository whose branches are named after users
+        let temp_dir = tempdir().unwrap();
+        let db_manager = DatabaseManager::new(temp_dir.path().join("test.db")).unwrap();
+        db_manager.initialize_schema().unwrap();
+        WorkflowManager::new(db_manager.connection_manager()).create_default_part_workflow().unwrap();
+        let repo_dir = tempdir().unwrap();
+        let repo_path = repo_dir.path();
+        
+        let git_config = GitBackendConfig {
+            branch_templates: BranchTemplates {
+                draft: "users/{user}/{part}".to_string(),
+                review: "review/{user}/{part}".to_string(),
+                ..BranchTemplates::default()
+            },
+            ..GitBackendConfig::default()
+        };
+        let git_manager = GitBackendManager::new(git_config, AuthConfig::default()).unwrap();
+        let repo = git_manager.init_repository(repo_path).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test User").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        repo.commit(Some("refs/heads/main"), &sig, &sig, "Initial commit", &tree, &[]).unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+        
+        let designer = PartManagementManager::new(
+            db_manager.connection_manager(),
+            &git_manager,
+            User::new("designer".to_string(), UserRole::Designer),
+        );
+        let reviewer = PartManagementManager::new(
+            db_manager.connection_manager(),
+            &git_manager,
+            User::new("reviewer".to_string(), UserRole::Designer),
+        );
+        
+        // The draft is committed on the branch named after its author
+        let (part, revision_id) = designer.create_part(
+            "Electronic".to_string(),
+            "Resistor".to_string(),
+            "10K Resistor".to_string(),
+            None,
+            repo_path,
+        ).unwrap();
+        let display_part_number = part.display_part_number(db_manager.connection_manager());
+        let draft_path = designer.get_working_path(revision_id, repo_path).unwrap();
+        assert_eq!(reviewer.get_working_path(revision_id, repo_path).unwrap(), draft_path);
+        fs::write(draft_path.join("value.txt"), "10K").unwrap();
+        let draft_repo = git_manager.open_repository(&draft_path).unwrap();
+        git_manager.operation_handler(&draft_repo).commit("Set value", &[Path::new("value.txt")]).unwrap();
+        
+        // Other users review and release the branches of the author
+        designer.submit_for_review(revision_id, repo_path, vec!["reviewer".to_string()]).unwrap();
+        let review_branch = format!("review/designer/{}", display_part_number);
+        assert!(repo.find_branch(&review_branch, git2::BranchType::Local).is_ok());
+        assert!(repo.find_branch(&format!("review/reviewer/{}", display_part_number), git2::BranchType::Local).is_err());
+        reviewer.approve_revision(revision_id, None).unwrap();
+        reviewer.release_revision(revision_id, repo_path).unwrap();
+        
+        let released = repo.head().unwrap().peel_to_tree().unwrap();
+        assert!(released.get_name("value.txt").is_some());
+        assert!(repo.find_branch(&format!("users/reviewer/{}", display_part_number), git2::BranchType::Local).is_err());
     }
-}
\ No newline at end of file
+}
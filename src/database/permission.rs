@@ -0,0 +1,463 @@
+//! Permission module for Implexa
+//!
+//! This module provides the permission service that decides whether a user may perform an
+//! action on the parts of a category. Permissions are granted to users, groups or roles, either
+//! for all categories or for a single one. Admins may do everything; for everyone else authors
+//! may only edit their own revisions and may not approve, release or obsolete them.
+
+use rusqlite::{Transaction, params, Row, Result as SqliteResult, OptionalExtension};
+use rusqlite::types::Type;
+use crate::database::schema::{DatabaseResult, DatabaseError};
+use crate::database::connection_manager::ConnectionManager;
+use crate::database::user::{User, UserRole, UserManager};
+
+/// Action a permission grants
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PermissionAction {
+    /// Create parts and revisions
+    Create,
+    /// Edit revisions and submit them for review
+    Edit,
+    /// Approve or reject revisions under review
+    Approve,
+    /// Release revisions
+    Release,
+    /// Mark revisions as obsolete
+    Obsolete,
+}
+
+impl PermissionAction {
+    /// Convert a string to a PermissionAction
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The action string
+    ///
+    /// # Returns
+    ///
+    /// The corresponding PermissionAction
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(action: &str) -> Option<Self> {
+        match action {
+            "Create" => Some(Self::Create),
+            "Edit" => Some(Self::Edit),
+            "Approve" => Some(Self::Approve),
+            "Release" => Some(Self::Release),
+            "Obsolete" => Some(Self::Obsolete),
+            _ => None,
+        }
+    }
+
+    /// Convert a PermissionAction to a string
+    ///
+    /// # Returns
+    ///
+    /// The string representation of the action
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::Create => "Create",
+            Self::Edit => "Edit",
+            Self::Approve => "Approve",
+            Self::Release => "Release",
+            Self::Obsolete => "Obsolete",
+        }
+    }
+}
+
+/// Kind of grantee a permission is granted to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GranteeType {
+    /// A single user, by username
+    User,
+    /// The members of a group, by group name
+    Group,
+    /// All users with a role, by role name
+    Role,
+}
+
+impl GranteeType {
+    /// Convert a string to a GranteeType
+    ///
+    /// # Arguments
+    ///
+    /// * `grantee_type` - The grantee type string
+    ///
+    /// # Returns
+    ///
+    /// The corresponding GranteeType
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(grantee_type: &str) -> Option<Self> {
+        match grantee_type {
+            "User" => Some(Self::User),
+            "Group" => Some(Self::Group),
+            "Role" => Some(Self::Role),
+            _ => None,
+        }
+    }
+
+    /// Convert a GranteeType to a string
+    ///
+    /// # Returns
+    ///
+    /// The string representation of the grantee type
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::User => "User",
+            Self::Group => "Group",
+            Self::Role => "Role",
+        }
+    }
+}
+
+/// Represents a permission granted to a user, group or role
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Permission {
+    /// Unique identifier for the permission
+    pub permission_id: Option<i64>,
+    /// Action the permission grants
+    pub action: PermissionAction,
+    /// Kind of grantee
+    pub grantee_type: GranteeType,
+    /// Username, group name or role name of the grantee
+    pub grantee: String,
+    /// Category the permission is limited to, or None for all categories
+    pub category_id: Option<i64>,
+}
+
+impl Permission {
+    /// Create a new permission
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - Action the permission grants
+    /// * `grantee_type` - Kind of grantee
+    /// * `grantee` - Username, group name or role name of the grantee
+    /// * `category_id` - Category the permission is limited to, or None for all categories
+    ///
+    /// # Returns
+    ///
+    /// A new Permission instance
+    pub fn new(action: PermissionAction, grantee_type: GranteeType, grantee: String, category_id: Option<i64>) -> Self {
+        Self {
+            permission_id: None,
+            action,
+            grantee_type,
+            grantee,
+            category_id,
+        }
+    }
+}
+
+/// Manager for permission operations
+pub struct PermissionManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+}
+
+impl<'a> PermissionManager<'a> {
+    /// Create a new PermissionManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new PermissionManager instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Grant a permission
+    ///
+    /// # Arguments
+    ///
+    /// * `permission` - The permission to grant
+    ///
+    /// # Returns
+    ///
+    /// The ID of the permission; the existing ID if it was already granted
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the permission could not be granted
+    pub fn grant_permission(&self, permission: &Permission) -> DatabaseResult<i64> {
+        if permission.grantee_type == GranteeType::Role && UserRole::from_str(&permission.grantee).is_none() {
+            return Err(DatabaseError::ValidationError(format!("Unknown role: {}", permission.grantee)));
+        }
+
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let existing: Option<i64> = tx.query_row(
+                "SELECT permission_id FROM Permissions
+                 WHERE action = ?1 AND grantee_type = ?2 AND grantee = ?3 AND category_id IS ?4",
+                params![
+                    permission.action.to_str(),
+                    permission.grantee_type.to_str(),
+                    permission.grantee,
+                    permission.category_id,
+                ],
+                |row| row.get(0),
+            ).optional()?;
+            if let Some(permission_id) = existing {
+                return Ok(permission_id);
+            }
+
+            tx.execute(
+                "INSERT INTO Permissions (action, grantee_type, grantee, category_id) VALUES (?1, ?2, ?3, ?4)",
+                params![
+                    permission.action.to_str(),
+                    permission.grantee_type.to_str(),
+                    permission.grantee,
+                    permission.category_id,
+                ],
+            )?;
+            Ok(tx.last_insert_rowid())
+        })
+    }
+
+    /// Revoke a permission
+    ///
+    /// # Arguments
+    ///
+    /// * `permission_id` - The ID of the permission to revoke
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the permission was successfully revoked
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the permission could not be revoked
+    pub fn revoke_permission(&self, permission_id: i64) -> DatabaseResult<()> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            conn.execute("DELETE FROM Permissions WHERE permission_id = ?1", params![permission_id])?;
+            Ok(())
+        })
+    }
+
+    /// Get a permission by ID
+    ///
+    /// # Arguments
+    ///
+    /// * `permission_id` - The ID of the permission
+    ///
+    /// # Returns
+    ///
+    /// The permission
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the permission could not be retrieved
+    pub fn get_permission(&self, permission_id: i64) -> DatabaseResult<Permission> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let permission = conn.query_row(
+                "SELECT permission_id, action, grantee_type, grantee, category_id FROM Permissions WHERE permission_id = ?1",
+                params![permission_id],
+                |row| self.row_to_permission(row),
+            )?;
+            Ok(permission)
+        })
+    }
+
+    /// Get all permissions
+    ///
+    /// # Returns
+    ///
+    /// A vector of all permissions, ordered by grantee
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the permissions could not be retrieved
+    pub fn get_all_permissions(&self) -> DatabaseResult<Vec<Permission>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT permission_id, action, grantee_type, grantee, category_id
+                 FROM Permissions
+                 ORDER BY grantee_type, grantee, action",
+            )?;
+            let permissions = stmt
+                .query_map([], |row| self.row_to_permission(row))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(permissions)
+        })
+    }
+
+    /// Check whether a user may perform an action on the parts of a category
+    ///
+    /// # Arguments
+    ///
+    /// * `user` - The user
+    /// * `action` - The action
+    /// * `category` - The name of the part's category
+    /// * `author` - The author of the revision the action is performed on, if any
+    ///
+    /// # Returns
+    ///
+    /// true if the user is permitted to perform the action, false otherwise
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the permissions could not be checked
+    pub fn is_permitted(&self, user: &User, action: PermissionAction, category: &str, author: Option<&str>) -> DatabaseResult<bool> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.is_permitted_in_transaction(user, action, category, author, tx)
+        })
+    }
+
+    /// Check whether a user may perform an action on the parts of a category within an existing transaction
+    ///
+    /// The stored role and active flag of the user take precedence over those of `user`.
+    ///
+    /// # Arguments
+    ///
+    /// * `user` - The user
+    /// * `action` - The action
+    /// * `category` - The name of the part's category
+    /// * `author` - The author of the revision the action is performed on, if any
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// true if the user is permitted to perform the action, false otherwise
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the permissions could not be checked
+    pub fn is_permitted_in_transaction(
+        &self,
+        user: &User,
+        action: PermissionAction,
+        category: &str,
+        author: Option<&str>,
+        tx: &Transaction,
+    ) -> DatabaseResult<bool> {
+        let stored = UserManager::new(self.connection_manager).get_user_by_username_in_transaction(&user.username, tx)?;
+        let user = stored.as_ref().unwrap_or(user);
+
+        if !user.active {
+            return Ok(false);
+        }
+        if user.role == UserRole::Admin {
+            return Ok(true);
+        }
+
+        // Authors edit their own revisions and leave the decisions on them to others
+        let is_author = author == Some(user.username.as_str());
+        let allowed_for_author = match action {
+            PermissionAction::Create => true,
+            PermissionAction::Edit => author.is_none() || is_author,
+            PermissionAction::Approve | PermissionAction::Release | PermissionAction::Obsolete => !is_author,
+        };
+        if !allowed_for_author {
+            return Ok(false);
+        }
+
+        let grants: i64 = tx.query_row(
+            "SELECT COUNT(*)
+             FROM Permissions p
+             WHERE p.action = ?1
+             AND (p.category_id IS NULL OR p.category_id IN (SELECT category_id FROM Categories WHERE name = ?2))
+             AND (
+                 (p.grantee_type = 'User' AND p.grantee = ?3)
+                 OR (p.grantee_type = 'Role' AND p.grantee = ?4)
+                 OR (p.grantee_type = 'Group' AND p.grantee IN (
+                     SELECT g.name FROM Groups g JOIN GroupMembers m ON m.group_id = g.group_id WHERE m.member = ?3
+                 ))
+             )",
+            params![action.to_str(), category, user.username, user.role.to_str()],
+            |row| row.get(0),
+        )?;
+        Ok(grants > 0)
+    }
+
+    /// Convert a database row to a Permission
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The database row
+    ///
+    /// # Returns
+    ///
+    /// A Permission instance
+    ///
+    /// # Errors
+    ///
+    /// Returns a SqliteError if the row could not be converted
+    fn row_to_permission(&self, row: &Row) -> SqliteResult<Permission> {
+        let action_str: String = row.get(1)?;
+        let action = PermissionAction::from_str(&action_str)
+            .ok_or_else(|| rusqlite::Error::InvalidColumnType(1, "action".to_string(), Type::Text))?;
+        let grantee_type_str: String = row.get(2)?;
+        let grantee_type = GranteeType::from_str(&grantee_type_str)
+            .ok_or_else(|| rusqlite::Error::InvalidColumnType(2, "grantee_type".to_string(), Type::Text))?;
+
+        Ok(Permission {
+            permission_id: Some(row.get(0)?),
+            action,
+            grantee_type,
+            grantee: row.get(3)?,
+            category_id: row.get(4)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::DatabaseManager;
+    use crate::database::category::CategoryManager;
+    use crate::database::group::{Group, GroupManager};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_permission_checks() {
+        // Create a temporary directory for the test database
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+
+        // Create a new database manager and initialize the schema
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let connection_manager = db_manager.connection_manager();
+        let permission_manager = PermissionManager::new(connection_manager);
+
+        // Designers may do everything by default, within the author rules
+        let designer = User::new("alice".to_string(), UserRole::Designer);
+        assert!(permission_manager.is_permitted(&designer, PermissionAction::Create, "Electronic", None).unwrap());
+        assert!(permission_manager.is_permitted(&designer, PermissionAction::Edit, "Electronic", Some("alice")).unwrap());
+        assert!(!permission_manager.is_permitted(&designer, PermissionAction::Edit, "Electronic", Some("bob")).unwrap());
+        assert!(permission_manager.is_permitted(&designer, PermissionAction::Approve, "Electronic", Some("bob")).unwrap());
+        assert!(!permission_manager.is_permitted(&designer, PermissionAction::Release, "Electronic", Some("alice")).unwrap());
+
+        // Viewers may do nothing until they are granted a permission
+        let viewer = User::new("carol".to_string(), UserRole::Viewer);
+        assert!(!permission_manager.is_permitted(&viewer, PermissionAction::Approve, "Electronic", Some("alice")).unwrap());
+
+        // A grant to a group applies to its members, limited to the category
+        let group_manager = GroupManager::new(connection_manager);
+        let qa = group_manager.create_group(&Group::new("QA".to_string(), None)).unwrap();
+        group_manager.add_member(qa, "carol").unwrap();
+        let electronic = CategoryManager::new(connection_manager).get_category_by_code("EL").unwrap();
+        let permission_id = permission_manager.grant_permission(&Permission::new(
+            PermissionAction::Approve, GranteeType::Group, "QA".to_string(), electronic.category_id,
+        )).unwrap();
+        assert!(permission_manager.is_permitted(&viewer, PermissionAction::Approve, "Electronic", Some("alice")).unwrap());
+        assert!(!permission_manager.is_permitted(&viewer, PermissionAction::Approve, "Mechanical", Some("alice")).unwrap());
+        assert!(!permission_manager.is_permitted(&viewer, PermissionAction::Release, "Electronic", Some("alice")).unwrap());
+
+        // Granting the same permission twice keeps a single grant
+        let again = permission_manager.grant_permission(&permission_manager.get_permission(permission_id).unwrap()).unwrap();
+        assert_eq!(again, permission_id);
+        permission_manager.revoke_permission(permission_id).unwrap();
+        assert!(!permission_manager.is_permitted(&viewer, PermissionAction::Approve, "Electronic", Some("alice")).unwrap());
+
+        // The stored user takes precedence: a deactivated admin may do nothing
+        let user_manager = UserManager::new(connection_manager);
+        let mut admin = User::new("dave".to_string(), UserRole::Admin);
+        assert!(permission_manager.is_permitted(&admin, PermissionAction::Release, "Electronic", Some("dave")).unwrap());
+        admin.active = false;
+        user_manager.create_user(&admin).unwrap();
+        let admin = User::new("dave".to_string(), UserRole::Admin);
+        assert!(!permission_manager.is_permitted(&admin, PermissionAction::Create, "Electronic", None).unwrap());
+    }
+}
@@ -5,6 +5,7 @@
 use rusqlite::{Transaction, params, Row, Result as SqliteResult};
 use crate::database::schema::{DatabaseError, DatabaseResult};
 use crate::database::connection_manager::ConnectionManager;
+use crate::database::audit::{AuditAction, AuditManager, DEFAULT_ACTOR};
 
 /// Type of property value
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -35,6 +36,7 @@ impl PropertyType {
     /// # Returns
     ///
     /// The corresponding PropertyType
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(type_str: &str) -> Option<Self> {
         match type_str {
             "string" => Some(Self::String),
@@ -145,6 +147,8 @@ impl Property {
 pub struct PropertyManager<'a> {
     /// Connection manager for the SQLite database
     connection_manager: &'a ConnectionManager,
+    /// User recorded in the audit log for the changes made through this manager
+    actor: String,
 }
 
 impl<'a> PropertyManager<'a> {
@@ -158,7 +162,24 @@ impl<'a> PropertyManager<'a> {
     ///
     /// A new PropertyManager instance
     pub fn new(connection_manager: &'a ConnectionManager) -> Self {
-        Self { connection_manager }
+        Self {
+            connection_manager,
+            actor: DEFAULT_ACTOR.to_string(),
+        }
+    }
+    
+    /// Set the user recorded in the audit log for the changes made through this manager
+    ///
+    /// # Arguments
+    ///
+    /// * `actor` - Username of the user making the changes
+    ///
+    /// # Returns
+    ///
+    /// The PropertyManager recording changes as made by the user
+    pub fn with_actor(mut self, actor: &str) -> Self {
+        self.actor = actor.to_string();
+        self
     }
     
     /// Create a new PropertyManager with a transaction
@@ -204,9 +225,11 @@ impl<'a> PropertyManager<'a> {
                     property.property_type.to_str(),
                 ],
             )?;
-            Ok::<i64, DatabaseError>(conn.last_insert_rowid())
-        }).map_err(DatabaseError::from)
-    }
+            let property_id = conn.last_insert_rowid();
+            AuditManager::new(self.connection_manager)
+                .record(conn, &self.actor, "Property", property_id, AuditAction::Create, Some(Self::audit_details(property)))?;
+            Ok::<i64, DatabaseError>(property_id)
+        })}
     
     /// Create a new property in the database within an existing transaction
     ///
@@ -234,7 +257,10 @@ impl<'a> PropertyManager<'a> {
                 property.property_type.to_str(),
             ],
         )?;
-        Ok(tx.last_insert_rowid())
+        let property_id = tx.last_insert_rowid();
+        AuditManager::new(self.connection_manager)
+            .record(tx, &self.actor, "Property", property_id, AuditAction::Create, Some(Self::audit_details(property)))?;
+        Ok(property_id)
     }
 
     /// Get a property by its ID
@@ -260,8 +286,7 @@ impl<'a> PropertyManager<'a> {
                 |row| self.row_to_property(row),
             )?;
             Ok::<Property, DatabaseError>(property)
-        }).map_err(DatabaseError::from)
-    }
+        })}
     
     /// Get a property by its ID within an existing transaction
     ///
@@ -315,8 +340,7 @@ impl<'a> PropertyManager<'a> {
                 properties.push(property_result?);
             }
             Ok::<Vec<Property>, DatabaseError>(properties)
-        }).map_err(DatabaseError::from)
-    }
+        })}
     
     /// Get all properties for a part within an existing transaction
     ///
@@ -374,8 +398,7 @@ impl<'a> PropertyManager<'a> {
                 properties.push(property_result?);
             }
             Ok::<Vec<Property>, DatabaseError>(properties)
-        }).map_err(DatabaseError::from)
-    }
+        })}
     
     /// Get all properties for a revision within an existing transaction
     ///
@@ -430,8 +453,7 @@ impl<'a> PropertyManager<'a> {
                 |row| self.row_to_property(row),
             )?;
             Ok::<Property, DatabaseError>(property)
-        }).map_err(DatabaseError::from)
-    }
+        })}
     
     /// Get a specific property for a part within an existing transaction
     ///
@@ -483,8 +505,7 @@ impl<'a> PropertyManager<'a> {
                 |row| self.row_to_property(row),
             )?;
             Ok::<Property, DatabaseError>(property)
-        }).map_err(DatabaseError::from)
-    }
+        })}
     
     /// Get a specific property for a revision within an existing transaction
     ///
@@ -544,9 +565,10 @@ impl<'a> PropertyManager<'a> {
                     property.property_type.to_str(),
                 ],
             )?;
+            AuditManager::new(self.connection_manager)
+                .record(conn, &self.actor, "Property", property_id, AuditAction::Update, Some(Self::audit_details(property)))?;
             Ok::<(), DatabaseError>(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
     
     /// Update a property within an existing transaction
     ///
@@ -580,6 +602,8 @@ impl<'a> PropertyManager<'a> {
                 property.property_type.to_str(),
             ],
         )?;
+        AuditManager::new(self.connection_manager)
+            .record(tx, &self.actor, "Property", property_id, AuditAction::Update, Some(Self::audit_details(property)))?;
         Ok(())
     }
 
@@ -602,9 +626,10 @@ impl<'a> PropertyManager<'a> {
                 "DELETE FROM Properties WHERE property_id = ?1",
                 params![property_id],
             )?;
+            AuditManager::new(self.connection_manager)
+                .record(conn, &self.actor, "Property", property_id, AuditAction::Delete, None)?;
             Ok::<(), DatabaseError>(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
     
     /// Delete a property within an existing transaction
     ///
@@ -625,6 +650,8 @@ impl<'a> PropertyManager<'a> {
             "DELETE FROM Properties WHERE property_id = ?1",
             params![property_id],
         )?;
+        AuditManager::new(self.connection_manager)
+            .record(tx, &self.actor, "Property", property_id, AuditAction::Delete, None)?;
         Ok(())
     }
 
@@ -655,6 +682,17 @@ impl<'a> PropertyManager<'a> {
             property_type,
         })
     }
+
+    /// Describe a property for the audit log
+    fn audit_details(property: &Property) -> serde_json::Value {
+        serde_json::json!({
+            "part_id": property.part_id,
+            "revision_id": property.revision_id,
+            "key": property.key,
+            "value": property.value,
+            "type": property.property_type.to_str(),
+        })
+    }
 }
 
 #[cfg(test)]
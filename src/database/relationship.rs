@@ -5,6 +5,7 @@
 use rusqlite::{Transaction, params, Row, Result as SqliteResult};
 use crate::database::schema::{DatabaseError, DatabaseResult};
 use crate::database::connection_manager::ConnectionManager;
+use crate::database::audit::{AuditAction, AuditManager, DEFAULT_ACTOR};
 
 /// Type of relationship between parts
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -31,6 +32,7 @@ impl RelationshipType {
     /// # Returns
     ///
     /// The corresponding RelationshipType
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(type_str: &str) -> Self {
         match type_str {
             "Assembly" => Self::Assembly,
@@ -70,6 +72,8 @@ pub struct Relationship {
     pub relationship_type: RelationshipType,
     /// Quantity of child parts in the relationship
     pub quantity: i64,
+    /// Attrition/scrap factor of an assembly line as a fraction (0.02 = 2% extra)
+    pub attrition: f64,
 }
 
 impl Relationship {
@@ -97,14 +101,51 @@ impl Relationship {
             child_part_id,
             relationship_type,
             quantity,
+            attrition: 0.0,
         }
     }
+
+    /// Get the quantity of child parts needed to build a number of parents, including attrition
+    ///
+    /// # Arguments
+    ///
+    /// * `parent_quantity` - The number of parents being built
+    ///
+    /// # Returns
+    ///
+    /// The gross child quantity, rounded up to whole units
+    pub fn gross_quantity(&self, parent_quantity: i64) -> i64 {
+        let net = parent_quantity * self.quantity;
+        (net as f64 * (1.0 + self.attrition)).ceil() as i64
+    }
 }
 
+/// A line in a multi-level (indented) bill of materials
+#[derive(Debug, Clone)]
+pub struct BomLine {
+    /// Depth of the line in the BOM, starting at 1 for direct children
+    pub level: usize,
+    /// ID of the parent part
+    pub parent_part_id: i64,
+    /// ID of the child part
+    pub part_id: i64,
+    /// Quantity of the child per parent
+    pub quantity: i64,
+    /// Quantity of the child per top-level assembly
+    pub extended_quantity: i64,
+    /// Whether the child has no assembly children of its own
+    pub is_leaf: bool,
+}
+
+/// A single-level BOM entry: (part_id, name, category, subcategory, quantity)
+pub type BomEntry = (String, String, String, String, i64);
+
 /// Manager for relationship operations
 pub struct RelationshipManager<'a> {
     /// Connection manager for the SQLite database
     connection_manager: &'a ConnectionManager,
+    /// User recorded in the audit log for the changes made through this manager
+    actor: String,
 }
 
 impl<'a> RelationshipManager<'a> {
@@ -118,7 +159,24 @@ impl<'a> RelationshipManager<'a> {
     ///
     /// A new RelationshipManager instance
     pub fn new(connection_manager: &'a ConnectionManager) -> Self {
-        Self { connection_manager }
+        Self {
+            connection_manager,
+            actor: DEFAULT_ACTOR.to_string(),
+        }
+    }
+    
+    /// Set the user recorded in the audit log for the changes made through this manager
+    ///
+    /// # Arguments
+    ///
+    /// * `actor` - Username of the user making the changes
+    ///
+    /// # Returns
+    ///
+    /// The RelationshipManager recording changes as made by the user
+    pub fn with_actor(mut self, actor: &str) -> Self {
+        self.actor = actor.to_string();
+        self
     }
     
     /// Create a new RelationshipManager with a transaction
@@ -154,18 +212,21 @@ impl<'a> RelationshipManager<'a> {
     pub fn create_relationship(&self, relationship: &Relationship) -> DatabaseResult<i64> {
         self.connection_manager.execute_mut::<_, _, DatabaseError>(|conn| {
             conn.execute(
-                "INSERT INTO Relationships (parent_part_id, child_part_id, type, quantity)
-                 VALUES (?1, ?2, ?3, ?4)",
+                "INSERT INTO Relationships (parent_part_id, child_part_id, type, quantity, attrition)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
                 params![
                     relationship.parent_part_id,
                     relationship.child_part_id,
                     relationship.relationship_type.to_str(),
                     relationship.quantity,
+                    relationship.attrition,
                 ],
             )?;
-            Ok::<i64, DatabaseError>(conn.last_insert_rowid())
-        }).map_err(DatabaseError::from)
-    }
+            let relationship_id = conn.last_insert_rowid();
+            AuditManager::new(self.connection_manager)
+                .record(conn, &self.actor, "Relationship", relationship_id, AuditAction::Create, Some(Self::audit_details(relationship)))?;
+            Ok::<i64, DatabaseError>(relationship_id)
+        })}
     
     /// Create a new relationship in the database within an existing transaction
     ///
@@ -183,16 +244,20 @@ impl<'a> RelationshipManager<'a> {
     /// Returns a DatabaseError if the relationship could not be created
     pub fn create_relationship_in_transaction(&self, relationship: &Relationship, tx: &Transaction) -> DatabaseResult<i64> {
         tx.execute(
-            "INSERT INTO Relationships (parent_part_id, child_part_id, type, quantity)
-             VALUES (?1, ?2, ?3, ?4)",
+            "INSERT INTO Relationships (parent_part_id, child_part_id, type, quantity, attrition)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
             params![
                 relationship.parent_part_id,
                 relationship.child_part_id,
                 relationship.relationship_type.to_str(),
                 relationship.quantity,
+                relationship.attrition,
             ],
         )?;
-        Ok(tx.last_insert_rowid())
+        let relationship_id = tx.last_insert_rowid();
+        AuditManager::new(self.connection_manager)
+            .record(tx, &self.actor, "Relationship", relationship_id, AuditAction::Create, Some(Self::audit_details(relationship)))?;
+        Ok(relationship_id)
     }
 
     /// Get a relationship by its ID
@@ -211,15 +276,14 @@ impl<'a> RelationshipManager<'a> {
     pub fn get_relationship(&self, relationship_id: i64) -> DatabaseResult<Relationship> {
         self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
             let relationship = conn.query_row(
-                "SELECT relationship_id, parent_part_id, child_part_id, type, quantity
+                "SELECT relationship_id, parent_part_id, child_part_id, type, quantity, attrition
                  FROM Relationships
                  WHERE relationship_id = ?1",
                 params![relationship_id],
                 |row| self.row_to_relationship(row),
             )?;
             Ok::<Relationship, DatabaseError>(relationship)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get all relationships where the specified part is the parent
     ///
@@ -237,7 +301,7 @@ impl<'a> RelationshipManager<'a> {
     pub fn get_child_relationships(&self, part_id: &str) -> DatabaseResult<Vec<Relationship>> {
         self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
             let mut stmt = conn.prepare(
-                "SELECT relationship_id, parent_part_id, child_part_id, type, quantity
+                "SELECT relationship_id, parent_part_id, child_part_id, type, quantity, attrition
                  FROM Relationships
                  WHERE parent_part_id = ?1",
             )?;
@@ -247,8 +311,7 @@ impl<'a> RelationshipManager<'a> {
                 relationships.push(relationship_result?);
             }
             Ok::<Vec<Relationship>, DatabaseError>(relationships)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get all relationships where the specified part is the child
     ///
@@ -266,7 +329,7 @@ impl<'a> RelationshipManager<'a> {
     pub fn get_parent_relationships(&self, part_id: &str) -> DatabaseResult<Vec<Relationship>> {
         self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
             let mut stmt = conn.prepare(
-                "SELECT relationship_id, parent_part_id, child_part_id, type, quantity
+                "SELECT relationship_id, parent_part_id, child_part_id, type, quantity, attrition
                  FROM Relationships
                  WHERE child_part_id = ?1",
             )?;
@@ -276,8 +339,7 @@ impl<'a> RelationshipManager<'a> {
                 relationships.push(relationship_result?);
             }
             Ok::<Vec<Relationship>, DatabaseError>(relationships)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Update a relationship
     ///
@@ -300,7 +362,7 @@ impl<'a> RelationshipManager<'a> {
         self.connection_manager.execute_mut::<_, _, DatabaseError>(|conn| {
             conn.execute(
                 "UPDATE Relationships
-                 SET parent_part_id = ?2, child_part_id = ?3, type = ?4, quantity = ?5
+                 SET parent_part_id = ?2, child_part_id = ?3, type = ?4, quantity = ?5, attrition = ?6
                  WHERE relationship_id = ?1",
                 params![
                     relationship_id,
@@ -308,11 +370,13 @@ impl<'a> RelationshipManager<'a> {
                     relationship.child_part_id,
                     relationship.relationship_type.to_str(),
                     relationship.quantity,
+                    relationship.attrition,
                 ],
             )?;
+            AuditManager::new(self.connection_manager)
+                .record(conn, &self.actor, "Relationship", relationship_id, AuditAction::Update, Some(Self::audit_details(relationship)))?;
             Ok::<(), DatabaseError>(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Delete a relationship
     ///
@@ -333,9 +397,10 @@ impl<'a> RelationshipManager<'a> {
                 "DELETE FROM Relationships WHERE relationship_id = ?1",
                 params![relationship_id],
             )?;
+            AuditManager::new(self.connection_manager)
+                .record(conn, &self.actor, "Relationship", relationship_id, AuditAction::Delete, None)?;
             Ok::<(), DatabaseError>(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get the bill of materials (BOM) for a part
     ///
@@ -350,7 +415,7 @@ impl<'a> RelationshipManager<'a> {
     /// # Errors
     ///
     /// Returns a DatabaseError if the BOM could not be retrieved
-    pub fn get_bom(&self, part_id: &str) -> DatabaseResult<Vec<(String, String, String, String, i64)>> {
+    pub fn get_bom(&self, part_id: &str) -> DatabaseResult<Vec<BomEntry>> {
         self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
             let mut stmt = conn.prepare(
                 "SELECT p.part_id, p.name, p.category, p.subcategory, r.quantity
@@ -372,7 +437,75 @@ impl<'a> RelationshipManager<'a> {
                 bom.push(bom_result?);
             }
             Ok::<Vec<(String, String, String, String, i64)>, DatabaseError>(bom)
-        }).map_err(DatabaseError::from)
+        })}
+
+    /// Explode the bill of materials of a part across all assembly levels
+    ///
+    /// Lines are returned depth-first, so every sub-assembly is directly followed by its children.
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the top-level part
+    ///
+    /// # Returns
+    ///
+    /// A vector of BOM lines
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the BOM could not be retrieved or contains a circular reference
+    pub fn explode_bom(&self, part_id: i64) -> DatabaseResult<Vec<BomLine>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT child_part_id, quantity FROM Relationships
+                 WHERE parent_part_id = ?1 AND type = 'Assembly'
+                 ORDER BY relationship_id",
+            )?;
+
+            let mut lines = Vec::new();
+            let mut path = vec![part_id];
+            Self::explode_level(&mut stmt, part_id, 1, 1, &mut path, &mut lines)?;
+            Ok::<Vec<BomLine>, DatabaseError>(lines)
+        })
+    }
+
+    /// Recursively append the children of a part to an exploded BOM
+    fn explode_level(
+        stmt: &mut rusqlite::Statement,
+        parent_part_id: i64,
+        level: usize,
+        parent_quantity: i64,
+        path: &mut Vec<i64>,
+        lines: &mut Vec<BomLine>,
+    ) -> DatabaseResult<()> {
+        let children = stmt
+            .query_map(params![parent_part_id], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?
+            .collect::<SqliteResult<Vec<(i64, i64)>>>()?;
+
+        for (child_part_id, quantity) in children {
+            if path.contains(&child_part_id) {
+                return Err(DatabaseError::ValidationError(format!(
+                    "Circular assembly reference: part {} contains itself", child_part_id
+                )));
+            }
+
+            let line_index = lines.len();
+            lines.push(BomLine {
+                level,
+                parent_part_id,
+                part_id: child_part_id,
+                quantity,
+                extended_quantity: parent_quantity * quantity,
+                is_leaf: true,
+            });
+
+            path.push(child_part_id);
+            Self::explode_level(stmt, child_part_id, level + 1, parent_quantity * quantity, path, lines)?;
+            path.pop();
+
+            lines[line_index].is_leaf = lines.len() == line_index + 1;
+        }
+        Ok(())
     }
 
     /// Convert a database row to a Relationship
@@ -398,6 +531,18 @@ impl<'a> RelationshipManager<'a> {
             child_part_id: row.get(2)?,
             relationship_type,
             quantity: row.get(4)?,
+            attrition: row.get(5)?,
+        })
+    }
+
+    /// Describe a relationship for the audit log
+    fn audit_details(relationship: &Relationship) -> serde_json::Value {
+        serde_json::json!({
+            "parent_part_id": relationship.parent_part_id,
+            "child_part_id": relationship.child_part_id,
+            "type": relationship.relationship_type.to_str(),
+            "quantity": relationship.quantity,
+            "attrition": relationship.attrition,
         })
     }
 }
@@ -466,6 +611,10 @@ mod tests {
             relationship.relationship_type.to_str()
         );
         assert_eq!(retrieved_relationship.quantity, relationship.quantity);
+
+        // A child that contains its parent makes the BOM circular
+        relationship_manager.create_relationship(&Relationship::new(10003, 10002, RelationshipType::Assembly, 1)).unwrap();
+        assert!(matches!(relationship_manager.explode_bom(10002), Err(DatabaseError::ValidationError(_))));
     }
 
     #[test]
@@ -2,10 +2,12 @@
 //!
 //! This module provides functionality for managing part revisions in the database.
 
-use rusqlite::{Transaction, params, Row, Result as SqliteResult, OptionalExtension};
+use rusqlite::{Connection, Transaction, params, Row, Result as SqliteResult, OptionalExtension};
 use std::time::{SystemTime, UNIX_EPOCH};
 use crate::database::schema::{DatabaseResult, DatabaseError};
 use crate::database::connection_manager::ConnectionManager;
+use crate::database::audit::{AuditAction, AuditManager, DEFAULT_ACTOR};
+use crate::database::revision_scheme::RevisionScheme;
 
 /// Status of a revision
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -30,6 +32,7 @@ impl RevisionStatus {
     /// # Returns
     ///
     /// The corresponding RevisionStatus
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(status: &str) -> Option<Self> {
         match status {
             "Draft" => Some(Self::Draft),
@@ -70,8 +73,10 @@ pub struct Revision {
     pub created_date: SystemTime,
     /// User who created the revision
     pub created_by: String,
-    /// Git commit hash associated with this revision
+    /// Git commit hash associated with this revision; the released commit once released
     pub commit_hash: Option<String>,
+    /// Git commit hash the revision was submitted for review with; approvals are signed against it
+    pub approved_commit_hash: Option<String>,
 }
 
 impl Revision {
@@ -103,6 +108,7 @@ impl Revision {
             created_date: SystemTime::now(),
             created_by,
             commit_hash,
+            approved_commit_hash: None,
         }
     }
 }
@@ -111,6 +117,8 @@ impl Revision {
 pub struct RevisionManager<'a> {
     /// Connection manager for the SQLite database
     connection_manager: &'a ConnectionManager,
+    /// User recorded in the audit log for the changes made through this manager
+    actor: String,
 }
 
 impl<'a> RevisionManager<'a> {
@@ -124,7 +132,24 @@ impl<'a> RevisionManager<'a> {
     ///
     /// A new RevisionManager instance
     pub fn new(connection_manager: &'a ConnectionManager) -> Self {
-        Self { connection_manager }
+        Self {
+            connection_manager,
+            actor: DEFAULT_ACTOR.to_string(),
+        }
+    }
+    
+    /// Set the user recorded in the audit log for the changes made through this manager
+    ///
+    /// # Arguments
+    ///
+    /// * `actor` - Username of the user making the changes
+    ///
+    /// # Returns
+    ///
+    /// The RevisionManager recording changes as made by the user
+    pub fn with_actor(mut self, actor: &str) -> Self {
+        self.actor = actor.to_string();
+        self
     }
     
     /// Create a new RevisionManager with a transaction
@@ -177,9 +202,11 @@ impl<'a> RevisionManager<'a> {
                     revision.commit_hash,
                 ],
             )?;
-            Ok::<i64, DatabaseError>(conn.last_insert_rowid())
-        }).map_err(DatabaseError::from)
-    }
+            let revision_id = conn.last_insert_rowid();
+            AuditManager::new(self.connection_manager)
+                .record(conn, &self.actor, "Revision", revision_id, AuditAction::Create, Some(Self::audit_details(revision)))?;
+            Ok::<i64, DatabaseError>(revision_id)
+        })}
     
     /// Create a new revision in the database within an existing transaction
     ///
@@ -214,7 +241,10 @@ impl<'a> RevisionManager<'a> {
                 revision.commit_hash,
             ],
         )?;
-        Ok(tx.last_insert_rowid())
+        let revision_id = tx.last_insert_rowid();
+        AuditManager::new(self.connection_manager)
+            .record(tx, &self.actor, "Revision", revision_id, AuditAction::Create, Some(Self::audit_details(revision)))?;
+        Ok(revision_id)
     }
 
     /// Get a revision by its ID
@@ -233,15 +263,14 @@ impl<'a> RevisionManager<'a> {
     pub fn get_revision(&self, revision_id: i64) -> DatabaseResult<Revision> {
         self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
             let revision = conn.query_row(
-                "SELECT revision_id, part_id, version, status, created_date, created_by, commit_hash
+                "SELECT revision_id, part_id, version, status, created_date, created_by, commit_hash, approved_commit_hash
                  FROM Revisions
                  WHERE revision_id = ?1",
                 params![revision_id],
                 |row| self.row_to_revision(row),
             )?;
             Ok::<Revision, DatabaseError>(revision)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get a revision by its ID within an existing transaction
     ///
@@ -259,7 +288,7 @@ impl<'a> RevisionManager<'a> {
     /// Returns a DatabaseError if the revision could not be found
     pub fn get_revision_in_transaction(&self, revision_id: i64, tx: &Transaction) -> DatabaseResult<Revision> {
         let revision = tx.query_row(
-            "SELECT revision_id, part_id, version, status, created_date, created_by, commit_hash
+            "SELECT revision_id, part_id, version, status, created_date, created_by, commit_hash, approved_commit_hash
              FROM Revisions
              WHERE revision_id = ?1",
             params![revision_id],
@@ -284,7 +313,7 @@ impl<'a> RevisionManager<'a> {
     pub fn get_revisions_for_part(&self, part_id: i64) -> DatabaseResult<Vec<Revision>> {
         self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
             let mut stmt = conn.prepare(
-                "SELECT revision_id, part_id, version, status, created_date, created_by, commit_hash
+                "SELECT revision_id, part_id, version, status, created_date, created_by, commit_hash, approved_commit_hash
                  FROM Revisions
                  WHERE part_id = ?1
                  ORDER BY created_date DESC",
@@ -294,12 +323,18 @@ impl<'a> RevisionManager<'a> {
             for revision_result in revisions_iter {
                 revisions.push(revision_result?);
             }
+
+            // Order by the part's revision scheme, newest first
+            let scheme = Self::query_revision_scheme(conn, part_id)?;
+            revisions.sort_by(|a, b| scheme.compare(&b.version, &a.version));
             Ok::<Vec<Revision>, DatabaseError>(revisions)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get the latest revision for a part
     ///
+    /// The revisions of the part are ordered by its revision scheme, as in
+    /// [`RevisionManager::get_next_version`].
+    ///
     /// # Arguments
     ///
     /// * `part_id` - The ID of the part
@@ -313,17 +348,8 @@ impl<'a> RevisionManager<'a> {
     /// Returns a DatabaseError if the revision could not be retrieved
     pub fn get_latest_revision(&self, part_id: i64) -> DatabaseResult<Revision> {
         self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
-            let revision = conn.query_row(
-                "SELECT revision_id, part_id, version, status, created_date, created_by, commit_hash
-                 FROM Revisions
-                 WHERE part_id = ?1
-                 ORDER BY created_date DESC
-                 LIMIT 1",
-                params![part_id],
-                |row| self.row_to_revision(row),
-            )?;
-            Ok::<Revision, DatabaseError>(revision)
-        }).map_err(DatabaseError::from)
+            self.query_latest_revision(conn, part_id)
+        })
     }
 
     /// Get the latest revision for a part within an existing transaction
@@ -341,16 +367,7 @@ impl<'a> RevisionManager<'a> {
     ///
     /// Returns a DatabaseError if the revision could not be retrieved
     pub fn get_latest_revision_in_transaction(&self, part_id: i64, tx: &Transaction) -> DatabaseResult<Revision> {
-        let revision = tx.query_row(
-            "SELECT revision_id, part_id, version, status, created_date, created_by, commit_hash
-             FROM Revisions
-             WHERE part_id = ?1
-             ORDER BY created_date DESC
-             LIMIT 1",
-            params![part_id],
-            |row| self.row_to_revision(row),
-        )?;
-        Ok(revision)
+        self.query_latest_revision(tx, part_id)
     }
 
     /// Update the status of a revision
@@ -375,9 +392,10 @@ impl<'a> RevisionManager<'a> {
                  WHERE revision_id = ?1",
                 params![revision_id, status.to_str()],
             )?;
+            AuditManager::new(self.connection_manager)
+                .record(conn, &self.actor, "Revision", revision_id, AuditAction::Update, Some(serde_json::json!({ "status": status.to_str() })))?;
             Ok::<(), DatabaseError>(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Update the status of a revision within an existing transaction
     ///
@@ -401,6 +419,8 @@ impl<'a> RevisionManager<'a> {
              WHERE revision_id = ?1",
             params![revision_id, status.to_str()],
         )?;
+        AuditManager::new(self.connection_manager)
+            .record(tx, &self.actor, "Revision", revision_id, AuditAction::Update, Some(serde_json::json!({ "status": status.to_str() })))?;
         Ok(())
     }
 
@@ -426,9 +446,10 @@ impl<'a> RevisionManager<'a> {
                  WHERE revision_id = ?1",
                 params![revision_id, commit_hash],
             )?;
+            AuditManager::new(self.connection_manager)
+                .record(conn, &self.actor, "Revision", revision_id, AuditAction::Update, Some(serde_json::json!({ "commit_hash": commit_hash })))?;
             Ok::<(), DatabaseError>(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Update the commit hash of a revision within an existing transaction
     ///
@@ -452,11 +473,65 @@ impl<'a> RevisionManager<'a> {
              WHERE revision_id = ?1",
             params![revision_id, commit_hash],
         )?;
+        AuditManager::new(self.connection_manager)
+            .record(tx, &self.actor, "Revision", revision_id, AuditAction::Update, Some(serde_json::json!({ "commit_hash": commit_hash })))?;
         Ok(())
     }
 
+    /// Record the commit a revision was submitted for review with within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    /// * `commit_hash` - The submitted commit hash
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the approved commit hash was successfully updated
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the approved commit hash could not be updated
+    pub fn update_approved_commit_hash_in_transaction(&self, revision_id: i64, commit_hash: &str, tx: &Transaction) -> DatabaseResult<()> {
+        tx.execute(
+            "UPDATE Revisions
+             SET approved_commit_hash = ?2
+             WHERE revision_id = ?1",
+            params![revision_id, commit_hash],
+        )?;
+        AuditManager::new(self.connection_manager)
+            .record(tx, &self.actor, "Revision", revision_id, AuditAction::Update, Some(serde_json::json!({ "approved_commit_hash": commit_hash })))?;
+        Ok(())
+    }
+
+    /// Get the revision scheme of a part
+    ///
+    /// The scheme is configured on the part's category; parts of unknown categories use
+    /// the numeric scheme.
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the part
+    ///
+    /// # Returns
+    ///
+    /// The revision scheme of the part
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the scheme could not be determined
+    pub fn get_revision_scheme(&self, part_id: i64) -> DatabaseResult<RevisionScheme> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            Self::query_revision_scheme(conn, part_id)
+        })
+    }
+
     /// Get the next version number for a part
     ///
+    /// The versions of the part are ordered by its revision scheme, so "10" follows "9"
+    /// and "AA" follows "Y".
+    ///
     /// # Arguments
     ///
     /// * `part_id` - The ID of the part
@@ -470,24 +545,8 @@ impl<'a> RevisionManager<'a> {
     /// Returns a DatabaseError if the version number could not be determined
     pub fn get_next_version(&self, part_id: i64) -> DatabaseResult<String> {
         self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
-            let max_version: Option<String> = conn.query_row(
-                "SELECT MAX(version)
-                 FROM Revisions
-                 WHERE part_id = ?1",
-                params![part_id],
-                |row| row.get(0),
-            ).optional()?;
-
-            if let Some(version) = max_version {
-                // Parse the version and increment it
-                if let Ok(num) = version.parse::<u32>() {
-                    return Ok::<String, DatabaseError>((num + 1).to_string());
-                }
-            }
-
-            // If no version exists or parsing failed, start with "1"
-            Ok::<String, DatabaseError>("1".to_string())
-        }).map_err(DatabaseError::from)
+            Self::query_next_version(conn, part_id)
+        })
     }
 
     /// Get the next version number for a part within an existing transaction
@@ -505,23 +564,86 @@ impl<'a> RevisionManager<'a> {
     ///
     /// Returns a DatabaseError if the version number could not be determined
     pub fn get_next_version_in_transaction(&self, part_id: i64, tx: &Transaction) -> DatabaseResult<String> {
-        let max_version: Option<String> = tx.query_row(
-            "SELECT MAX(version)
-             FROM Revisions
-             WHERE part_id = ?1",
+        Self::query_next_version(tx, part_id)
+    }
+
+    /// Get the next prerelease version (X1, X2, ...) for a part
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the part
+    ///
+    /// # Returns
+    ///
+    /// The next prerelease version
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the part already has a released version or the version could not be determined
+    pub fn get_next_prerelease_version(&self, part_id: i64) -> DatabaseResult<String> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let scheme = Self::query_revision_scheme(conn, part_id)?;
+            let versions = Self::query_versions(conn, part_id)?;
+            scheme.next_prerelease(scheme.latest(versions.iter().map(String::as_str)))
+                .ok_or_else(|| DatabaseError::ValidationError(format!(
+                    "Part {} already has a released version", part_id
+                )))
+        })
+    }
+
+    /// Look up the revision scheme of a part's category
+    fn query_revision_scheme(conn: &Connection, part_id: i64) -> DatabaseResult<RevisionScheme> {
+        let scheme: Option<String> = conn.query_row(
+            "SELECT c.revision_scheme
+             FROM Parts p
+             JOIN Categories c ON c.name = p.category
+             WHERE p.part_id = ?1",
             params![part_id],
             |row| row.get(0),
         ).optional()?;
 
-        if let Some(version) = max_version {
-            // Parse the version and increment it
-            if let Ok(num) = version.parse::<u32>() {
-                return Ok((num + 1).to_string());
-            }
+        Ok(scheme
+            .and_then(|scheme| RevisionScheme::from_str(&scheme))
+            .unwrap_or_default())
+    }
+
+    /// Get all version labels of a part
+    fn query_versions(conn: &Connection, part_id: i64) -> DatabaseResult<Vec<String>> {
+        let mut stmt = conn.prepare("SELECT version FROM Revisions WHERE part_id = ?1")?;
+        let versions_iter = stmt.query_map(params![part_id], |row| row.get(0))?;
+        let mut versions = Vec::new();
+        for version_result in versions_iter {
+            versions.push(version_result?);
         }
+        Ok(versions)
+    }
+
+    /// Find the highest revision of a part by its scheme, the newest one among equal labels
+    fn query_latest_revision(&self, conn: &Connection, part_id: i64) -> DatabaseResult<Revision> {
+        let mut stmt = conn.prepare(
+            "SELECT revision_id, part_id, version, status, created_date, created_by, commit_hash, approved_commit_hash
+             FROM Revisions
+             WHERE part_id = ?1
+             ORDER BY created_date DESC, revision_id DESC",
+        )?;
+        let revisions = stmt
+            .query_map(params![part_id], |row| self.row_to_revision(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let scheme = Self::query_revision_scheme(conn, part_id)?;
+        revisions
+            .into_iter()
+            .reduce(|latest, revision| {
+                if scheme.compare(&revision.version, &latest.version).is_gt() { revision } else { latest }
+            })
+            .ok_or_else(|| rusqlite::Error::QueryReturnedNoRows.into())
+    }
 
-        // If no version exists or parsing failed, start with "1"
-        Ok("1".to_string())
+    /// Determine the next released version of a part from its scheme
+    fn query_next_version(conn: &Connection, part_id: i64) -> DatabaseResult<String> {
+        let scheme = Self::query_revision_scheme(conn, part_id)?;
+        let versions = Self::query_versions(conn, part_id)?;
+        Ok(scheme.next_release(scheme.latest(versions.iter().map(String::as_str))))
     }
 
     /// Convert a database row to a Revision
@@ -554,6 +676,17 @@ impl<'a> RevisionManager<'a> {
             created_date,
             created_by: row.get(5)?,
             commit_hash: row.get(6)?,
+            approved_commit_hash: row.get(7)?,
+        })
+    }
+
+    /// Describe a revision for the audit log
+    fn audit_details(revision: &Revision) -> serde_json::Value {
+        serde_json::json!({
+            "part_id": revision.part_id,
+            "version": revision.version,
+            "status": revision.status.to_str(),
+            "commit_hash": revision.commit_hash,
         })
     }
 }
@@ -563,6 +696,7 @@ mod tests {
     use super::*;
     use crate::database::schema::DatabaseManager;
     use crate::database::part::{Part, PartManager};
+    use crate::database::category::CategoryManager;
     use tempfile::tempdir;
 
     #[test]
@@ -613,6 +747,50 @@ mod tests {
         assert_eq!(retrieved_revision.commit_hash, revision.commit_hash);
     }
 
+    #[test]
+    fn test_next_version_follows_revision_scheme() {
+        // Create a temporary directory for the test database
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+
+        // Create a new database manager and initialize the schema
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+
+        let part_manager = PartManager::new(db_manager.connection_manager());
+        let revision_manager = RevisionManager::new(db_manager.connection_manager());
+        let category_manager = CategoryManager::new(db_manager.connection_manager());
+
+        // Create a part with revisions 1 to 10
+        let part = Part::new(10001, "Electronic".to_string(), "Resistor".to_string(), "10K Resistor".to_string(), None);
+        part_manager.create_part(&part).unwrap();
+        for version in 1..=10 {
+            let revision = Revision::new(10001, version.to_string(), RevisionStatus::Released, "test_user".to_string(), None);
+            revision_manager.create_revision(&revision).unwrap();
+        }
+
+        // "10" is the highest version, even though it sorts below "9" as a string
+        assert_eq!(revision_manager.get_next_version(10001).unwrap(), "11");
+        assert_eq!(revision_manager.get_revisions_for_part(10001).unwrap()[0].version, "10");
+        assert_eq!(revision_manager.get_latest_revision(10001).unwrap().version, "10");
+
+        // Switch the category to alphabetic revisions and start a new part with prereleases
+        let mut category = category_manager.get_category_by_code("EL").unwrap();
+        category.revision_scheme = RevisionScheme::Alphabetic;
+        category_manager.update_category(&category).unwrap();
+        let part = Part::new(10002, "Electronic".to_string(), "Resistor".to_string(), "1K Resistor".to_string(), None);
+        part_manager.create_part(&part).unwrap();
+        assert_eq!(revision_manager.get_next_prerelease_version(10002).unwrap(), "X1");
+        let revision = Revision::new(10002, "X1".to_string(), RevisionStatus::Released, "test_user".to_string(), None);
+        revision_manager.create_revision(&revision).unwrap();
+        assert_eq!(revision_manager.get_next_version(10002).unwrap(), "A");
+        let revision = Revision::new(10002, "A".to_string(), RevisionStatus::Released, "test_user".to_string(), None);
+        revision_manager.create_revision(&revision).unwrap();
+        assert_eq!(revision_manager.get_next_version(10002).unwrap(), "B");
+        assert_eq!(revision_manager.get_latest_revision(10002).unwrap().version, "A");
+        assert!(revision_manager.get_next_prerelease_version(10002).is_err());
+    }
+
     #[test]
     fn test_revision_status_conversion() {
         assert_eq!(RevisionStatus::from_str("Draft"), Some(RevisionStatus::Draft));
@@ -0,0 +1,321 @@
+//! Revision scheme module for Implexa
+//!
+//! This module provides the revision lettering and numbering schemes used for part revisions.
+//! A scheme parses version labels into comparable keys, so that revisions are ordered by the
+//! scheme instead of by string comparison, and generates the label that follows a given one.
+
+use std::cmp::Ordering;
+
+/// Letters used by the alphabetic scheme; I, O, Q, S, X and Z are skipped as in ASME Y14.35
+const REVISION_LETTERS: [char; 20] = [
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'J', 'K',
+    'L', 'M', 'N', 'P', 'R', 'T', 'U', 'V', 'W', 'Y',
+];
+
+/// Prefix of prerelease revisions (X1, X2, ...), which come before the first release
+const PRERELEASE_PREFIX: char = 'X';
+
+/// Revision scheme of a part
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RevisionScheme {
+    /// Integer revisions: 1, 2, 3, ...
+    #[default]
+    Numeric,
+    /// Letter revisions: A, B, ..., Y, AA, AB, ...
+    Alphabetic,
+    /// Major and minor revisions: 1.0, 1.1, 2.0, ...
+    MajorMinor,
+}
+
+/// Comparable key of a revision label
+///
+/// Prerelease revisions sort before all released revisions.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RevisionKey {
+    /// Prerelease revision Xn
+    Prerelease(u32),
+    /// Released revision, compared component by component
+    Release(Vec<u32>),
+}
+
+impl RevisionScheme {
+    /// Convert a string to a RevisionScheme
+    ///
+    /// # Arguments
+    ///
+    /// * `scheme` - The scheme string
+    ///
+    /// # Returns
+    ///
+    /// The corresponding RevisionScheme
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(scheme: &str) -> Option<Self> {
+        match scheme {
+            "Numeric" => Some(Self::Numeric),
+            "Alphabetic" => Some(Self::Alphabetic),
+            "MajorMinor" => Some(Self::MajorMinor),
+            _ => None,
+        }
+    }
+
+    /// Convert a RevisionScheme to a string
+    ///
+    /// # Returns
+    ///
+    /// The string representation of the RevisionScheme
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::Numeric => "Numeric",
+            Self::Alphabetic => "Alphabetic",
+            Self::MajorMinor => "MajorMinor",
+        }
+    }
+
+    /// Parse a revision label into a comparable key
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The revision label
+    ///
+    /// # Returns
+    ///
+    /// The key of the label, or None if the label is not valid in this scheme
+    pub fn parse(&self, version: &str) -> Option<RevisionKey> {
+        if let Some(number) = version.strip_prefix(PRERELEASE_PREFIX) {
+            return Self::parse_number(number)
+                .filter(|n| *n > 0)
+                .map(RevisionKey::Prerelease);
+        }
+
+        match self {
+            Self::Numeric => Self::parse_number(version)
+                .filter(|n| *n > 0)
+                .map(|n| RevisionKey::Release(vec![n])),
+            Self::Alphabetic => Self::parse_letters(version)
+                .map(|n| RevisionKey::Release(vec![n])),
+            Self::MajorMinor => {
+                let (major, minor) = version.split_once('.')?;
+                Some(RevisionKey::Release(vec![Self::parse_number(major)?, Self::parse_number(minor)?]))
+            }
+        }
+    }
+
+    /// Check whether a label is valid in this scheme
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The revision label
+    ///
+    /// # Returns
+    ///
+    /// true if the label can be parsed by this scheme
+    pub fn is_valid(&self, version: &str) -> bool {
+        self.parse(version).is_some()
+    }
+
+    /// Check whether a label is a prerelease revision
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The revision label
+    ///
+    /// # Returns
+    ///
+    /// true if the label is a prerelease revision such as X1
+    pub fn is_prerelease(&self, version: &str) -> bool {
+        matches!(self.parse(version), Some(RevisionKey::Prerelease(_)))
+    }
+
+    /// Compare two revision labels
+    ///
+    /// Labels that are not valid in this scheme sort before all valid labels and are
+    /// compared as strings among themselves.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The first revision label
+    /// * `b` - The second revision label
+    ///
+    /// # Returns
+    ///
+    /// The ordering of the two labels
+    pub fn compare(&self, a: &str, b: &str) -> Ordering {
+        match (self.parse(a), self.parse(b)) {
+            (Some(key_a), Some(key_b)) => key_a.cmp(&key_b),
+            (Some(_), None) => Ordering::Greater,
+            (None, Some(_)) => Ordering::Less,
+            (None, None) => a.cmp(b),
+        }
+    }
+
+    /// Get the highest valid revision label
+    ///
+    /// # Arguments
+    ///
+    /// * `versions` - The revision labels
+    ///
+    /// # Returns
+    ///
+    /// The highest label that is valid in this scheme, or None if there is none
+    pub fn latest<'v>(&self, versions: impl IntoIterator<Item = &'v str>) -> Option<&'v str> {
+        versions
+            .into_iter()
+            .filter_map(|version| self.parse(version).map(|key| (key, version)))
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, version)| version)
+    }
+
+    /// Get the first released revision label of this scheme
+    ///
+    /// # Returns
+    ///
+    /// "1", "A" or "1.0"
+    pub fn first_release(&self) -> String {
+        match self {
+            Self::Numeric => "1".to_string(),
+            Self::Alphabetic => "A".to_string(),
+            Self::MajorMinor => "1.0".to_string(),
+        }
+    }
+
+    /// Get the released revision label that follows a label
+    ///
+    /// A prerelease revision is followed by the first release. For the major.minor scheme
+    /// the minor revision is incremented.
+    ///
+    /// # Arguments
+    ///
+    /// * `latest` - The current highest revision label, or None for a new part
+    ///
+    /// # Returns
+    ///
+    /// The next released revision label
+    pub fn next_release(&self, latest: Option<&str>) -> String {
+        match latest.and_then(|version| self.parse(version)) {
+            Some(RevisionKey::Release(components)) => self.format_release(&Self::increment_last(components)),
+            _ => self.first_release(),
+        }
+    }
+
+    /// Get the major revision label that follows a label
+    ///
+    /// This is the same as [`RevisionScheme::next_release`] for schemes without minor revisions.
+    ///
+    /// # Arguments
+    ///
+    /// * `latest` - The current highest revision label, or None for a new part
+    ///
+    /// # Returns
+    ///
+    /// The next major revision label
+    pub fn next_major_release(&self, latest: Option<&str>) -> String {
+        match (self, latest.and_then(|version| self.parse(version))) {
+            (Self::MajorMinor, Some(RevisionKey::Release(components))) => format!("{}.0", components[0] + 1),
+            _ => self.next_release(latest),
+        }
+    }
+
+    /// Get the prerelease revision label that follows a label
+    ///
+    /// # Arguments
+    ///
+    /// * `latest` - The current highest revision label, or None for a new part
+    ///
+    /// # Returns
+    ///
+    /// The next prerelease label (X1, X2, ...), or None once the part has been released
+    pub fn next_prerelease(&self, latest: Option<&str>) -> Option<String> {
+        match latest.and_then(|version| self.parse(version)) {
+            None => Some(format!("{}1", PRERELEASE_PREFIX)),
+            Some(RevisionKey::Prerelease(n)) => Some(format!("{}{}", PRERELEASE_PREFIX, n + 1)),
+            Some(RevisionKey::Release(_)) => None,
+        }
+    }
+
+    /// Format the components of a released revision
+    fn format_release(&self, components: &[u32]) -> String {
+        match self {
+            Self::Numeric => components[0].to_string(),
+            Self::Alphabetic => Self::format_letters(components[0]),
+            Self::MajorMinor => format!("{}.{}", components[0], components[1]),
+        }
+    }
+
+    /// Increment the last component of a released revision
+    fn increment_last(mut components: Vec<u32>) -> Vec<u32> {
+        if let Some(last) = components.last_mut() {
+            *last += 1;
+        }
+        components
+    }
+
+    /// Parse a plain decimal number without sign or leading zeros
+    fn parse_number(number: &str) -> Option<u32> {
+        if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) || (number.len() > 1 && number.starts_with('0')) {
+            return None;
+        }
+        number.parse().ok()
+    }
+
+    /// Parse revision letters as a bijective base-20 number (A = 1, Y = 20, AA = 21)
+    fn parse_letters(letters: &str) -> Option<u32> {
+        if letters.is_empty() {
+            return None;
+        }
+        letters.chars().try_fold(0u32, |value, letter| {
+            let digit = REVISION_LETTERS.iter().position(|l| *l == letter)? as u32 + 1;
+            value.checked_mul(REVISION_LETTERS.len() as u32)?.checked_add(digit)
+        })
+    }
+
+    /// Format a bijective base-20 number as revision letters
+    fn format_letters(mut value: u32) -> String {
+        let base = REVISION_LETTERS.len() as u32;
+        let mut letters = Vec::new();
+        while value > 0 {
+            value -= 1;
+            letters.push(REVISION_LETTERS[(value % base) as usize]);
+            value /= base;
+        }
+        letters.iter().rev().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_revision_scheme_ordering_and_sequence() {
+        // Numeric revisions are ordered numerically, not as strings
+        let numeric = RevisionScheme::Numeric;
+        assert_eq!(numeric.compare("10", "9"), Ordering::Greater);
+        assert_eq!(numeric.latest(["1", "9", "10", "2"]), Some("10"));
+        assert_eq!(numeric.next_release(Some("10")), "11");
+        assert_eq!(numeric.next_release(None), "1");
+
+        // Alphabetic revisions skip I, O, Q, S, X and Z and continue with double letters
+        let alphabetic = RevisionScheme::Alphabetic;
+        assert_eq!(alphabetic.next_release(Some("H")), "J");
+        assert_eq!(alphabetic.next_release(Some("R")), "T");
+        assert_eq!(alphabetic.next_release(Some("Y")), "AA");
+        assert_eq!(alphabetic.next_release(Some("AY")), "BA");
+        assert!(!alphabetic.is_valid("I"));
+        assert_eq!(alphabetic.compare("AA", "Y"), Ordering::Greater);
+
+        // Prerelease revisions come before the first release
+        assert_eq!(alphabetic.next_prerelease(None), Some("X1".to_string()));
+        assert_eq!(alphabetic.next_prerelease(Some("X9")), Some("X10".to_string()));
+        assert_eq!(alphabetic.latest(["X2", "X10", "A"]), Some("A"));
+        assert_eq!(alphabetic.next_release(Some("X10")), "A");
+        assert_eq!(alphabetic.next_prerelease(Some("A")), None);
+        assert!(alphabetic.is_prerelease("X1"));
+
+        // Major.minor revisions compare by component
+        let major_minor = RevisionScheme::MajorMinor;
+        assert_eq!(major_minor.compare("1.10", "1.9"), Ordering::Greater);
+        assert_eq!(major_minor.next_release(Some("1.9")), "1.10");
+        assert_eq!(major_minor.next_major_release(Some("1.9")), "2.0");
+        assert!(!major_minor.is_valid("1"));
+    }
+}
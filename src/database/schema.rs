@@ -3,7 +3,7 @@
 //! This module provides functionality for managing the SQLite database schema,
 //! including initialization, migrations, and version management.
 
-use rusqlite::{Error as SqliteError, params};
+use rusqlite::{Error as SqliteError, Transaction, params};
 use std::path::Path;
 use thiserror::Error;
 use crate::database::connection_manager::ConnectionManager;
@@ -31,6 +31,22 @@ pub enum DatabaseError {
     /// Git Backend error
     #[error("Git backend error: {0}")]
     GitBackend(#[from] GitBackendError),
+
+    /// CSV parsing error
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+
+    /// I/O error
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// JSON serialization error
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    /// Validation error for data that violates a business rule
+    #[error("Validation error: {0}")]
+    ValidationError(String),
 }
 
 /// Result type for database operations
@@ -65,6 +81,21 @@ impl DatabaseManager {
         })
     }
 
+    /// Create a new DatabaseManager on an existing connection manager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new DatabaseManager instance sharing the connection
+    pub fn from_connection_manager(connection_manager: ConnectionManager) -> Self {
+        Self {
+            connection_manager,
+        }
+    }
+
     /// Initialize the database schema
     ///
     /// This method creates all the necessary tables, indexes, and constraints
@@ -133,6 +164,8 @@ impl DatabaseManager {
                 name TEXT NOT NULL,
                 code TEXT NOT NULL,
                 description TEXT,
+                revision_scheme TEXT NOT NULL DEFAULT 'Numeric' CHECK(revision_scheme IN ('Numeric', 'Alphabetic', 'MajorMinor')),
+                workflow_id INTEGER,
                 UNIQUE(name),
                 UNIQUE(code)
             )",
@@ -214,6 +247,7 @@ impl DatabaseManager {
                 created_date TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
                 created_by TEXT NOT NULL,
                 commit_hash TEXT,
+                approved_commit_hash TEXT,
                 FOREIGN KEY (part_id) REFERENCES Parts(part_id) ON DELETE CASCADE,
                 UNIQUE(part_id, version)
             )",
@@ -233,6 +267,7 @@ impl DatabaseManager {
                 child_part_id INTEGER NOT NULL,
                 type TEXT NOT NULL,
                 quantity INTEGER NOT NULL DEFAULT 1,
+                attrition REAL NOT NULL DEFAULT 0 CHECK(attrition >= 0),
                 FOREIGN KEY (parent_part_id) REFERENCES Parts(part_id) ON DELETE CASCADE,
                 FOREIGN KEY (child_part_id) REFERENCES Parts(part_id) ON DELETE CASCADE,
                 UNIQUE(parent_part_id, child_part_id, type)
@@ -276,6 +311,9 @@ impl DatabaseManager {
                 mpn TEXT NOT NULL,
                 description TEXT,
                 status TEXT NOT NULL DEFAULT 'Active' CHECK(status IN ('Active', 'Preferred', 'Alternate', 'Obsolete')),
+                nrnd_date INTEGER,
+                last_time_buy_date INTEGER,
+                eol_date INTEGER,
                 FOREIGN KEY (part_id) REFERENCES Parts(part_id) ON DELETE CASCADE,
                 UNIQUE(manufacturer, mpn)
             )",
@@ -288,6 +326,147 @@ impl DatabaseManager {
         tx.execute("CREATE INDEX IF NOT EXISTS idx_mpn_mpn ON ManufacturerParts(mpn)", [])?;
         tx.execute("CREATE INDEX IF NOT EXISTS idx_mpn_status ON ManufacturerParts(status)", [])?;
 
+        // Create Suppliers table
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS Suppliers (
+                supplier_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                website TEXT
+            )",
+            [],
+        )?;
+
+        // Create SupplierOffers table
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS SupplierOffers (
+                offer_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                mpn_id INTEGER NOT NULL,
+                supplier_id INTEGER NOT NULL,
+                sku TEXT NOT NULL,
+                moq INTEGER NOT NULL DEFAULT 1,
+                currency TEXT NOT NULL DEFAULT 'USD',
+                lead_time_days INTEGER,
+                updated_date INTEGER NOT NULL,
+                FOREIGN KEY (mpn_id) REFERENCES ManufacturerParts(mpn_id) ON DELETE CASCADE,
+                FOREIGN KEY (supplier_id) REFERENCES Suppliers(supplier_id) ON DELETE CASCADE,
+                UNIQUE(supplier_id, sku)
+            )",
+            [],
+        )?;
+
+        // Create PriceBreaks table
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS PriceBreaks (
+                price_break_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                offer_id INTEGER NOT NULL,
+                quantity INTEGER NOT NULL,
+                unit_price REAL NOT NULL,
+                FOREIGN KEY (offer_id) REFERENCES SupplierOffers(offer_id) ON DELETE CASCADE,
+                UNIQUE(offer_id, quantity)
+            )",
+            [],
+        )?;
+
+        // Create indexes for supplier tables
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_supplier_offers_mpn_id ON SupplierOffers(mpn_id)", [])?;
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_supplier_offers_supplier_id ON SupplierOffers(supplier_id)", [])?;
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_price_breaks_offer_id ON PriceBreaks(offer_id)", [])?;
+
+        // Create StockLocations table
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS StockLocations (
+                location_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                description TEXT
+            )",
+            [],
+        )?;
+
+        // Create StockRecords table (lot_number is '' for stock without a lot)
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS StockRecords (
+                stock_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                part_id INTEGER NOT NULL,
+                location_id INTEGER NOT NULL,
+                lot_number TEXT NOT NULL DEFAULT '',
+                quantity_on_hand INTEGER NOT NULL DEFAULT 0 CHECK(quantity_on_hand >= 0),
+                quantity_reserved INTEGER NOT NULL DEFAULT 0 CHECK(quantity_reserved >= 0 AND quantity_reserved <= quantity_on_hand),
+                FOREIGN KEY (part_id) REFERENCES Parts(part_id) ON DELETE CASCADE,
+                FOREIGN KEY (location_id) REFERENCES StockLocations(location_id) ON DELETE RESTRICT,
+                UNIQUE(part_id, location_id, lot_number)
+            )",
+            [],
+        )?;
+
+        // Create InventoryTransactions table
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS InventoryTransactions (
+                transaction_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                part_id INTEGER NOT NULL,
+                type TEXT NOT NULL CHECK(type IN ('Receive', 'Issue', 'Adjust', 'Transfer')),
+                quantity INTEGER NOT NULL,
+                from_location_id INTEGER,
+                to_location_id INTEGER,
+                lot_number TEXT,
+                reference TEXT,
+                notes TEXT,
+                performed_by TEXT NOT NULL,
+                date INTEGER NOT NULL,
+                FOREIGN KEY (part_id) REFERENCES Parts(part_id) ON DELETE CASCADE,
+                FOREIGN KEY (from_location_id) REFERENCES StockLocations(location_id),
+                FOREIGN KEY (to_location_id) REFERENCES StockLocations(location_id)
+            )",
+            [],
+        )?;
+
+        // Create indexes for inventory tables
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_stock_records_part_id ON StockRecords(part_id)", [])?;
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_stock_records_location_id ON StockRecords(location_id)", [])?;
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_stock_records_lot_number ON StockRecords(lot_number)", [])?;
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_inventory_transactions_part_id ON InventoryTransactions(part_id)", [])?;
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_inventory_transactions_date ON InventoryTransactions(date)", [])?;
+
+        // Create SerializedUnits table
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS SerializedUnits (
+                unit_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                serial_number TEXT NOT NULL UNIQUE,
+                part_id INTEGER NOT NULL,
+                revision_id INTEGER NOT NULL,
+                build_date INTEGER NOT NULL,
+                built_by TEXT NOT NULL,
+                notes TEXT,
+                FOREIGN KEY (part_id) REFERENCES Parts(part_id) ON DELETE RESTRICT,
+                FOREIGN KEY (revision_id) REFERENCES Revisions(revision_id) ON DELETE RESTRICT
+            )",
+            [],
+        )?;
+
+        // Create AsBuiltComponents table
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS AsBuiltComponents (
+                component_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                unit_id INTEGER NOT NULL,
+                position TEXT NOT NULL,
+                part_id INTEGER NOT NULL,
+                quantity INTEGER NOT NULL DEFAULT 1 CHECK(quantity > 0),
+                mpn_id INTEGER,
+                lot_number TEXT,
+                installed_unit_id INTEGER,
+                FOREIGN KEY (unit_id) REFERENCES SerializedUnits(unit_id) ON DELETE CASCADE,
+                FOREIGN KEY (part_id) REFERENCES Parts(part_id) ON DELETE RESTRICT,
+                FOREIGN KEY (mpn_id) REFERENCES ManufacturerParts(mpn_id) ON DELETE RESTRICT,
+                FOREIGN KEY (installed_unit_id) REFERENCES SerializedUnits(unit_id) ON DELETE RESTRICT
+            )",
+            [],
+        )?;
+
+        // Create indexes for as-built tables
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_serialized_units_revision_id ON SerializedUnits(revision_id)", [])?;
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_as_built_components_unit_id ON AsBuiltComponents(unit_id)", [])?;
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_as_built_components_lot_number ON AsBuiltComponents(lot_number)", [])?;
+        tx.execute("CREATE UNIQUE INDEX IF NOT EXISTS idx_as_built_components_installed_unit_id ON AsBuiltComponents(installed_unit_id)", [])?;
+
         // Create Approvals table
         tx.execute(
             "CREATE TABLE IF NOT EXISTS Approvals (
@@ -382,16 +561,521 @@ impl DatabaseManager {
         tx.execute("CREATE INDEX IF NOT EXISTS idx_workflow_transitions_from_state_id ON WorkflowTransitions(from_state_id)", [])?;
         tx.execute("CREATE INDEX IF NOT EXISTS idx_workflow_transitions_to_state_id ON WorkflowTransitions(to_state_id)", [])?;
 
+        // Create WorkflowTransitionGuards table
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS WorkflowTransitionGuards (
+                guard_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                transition_id INTEGER NOT NULL,
+                guard_type TEXT NOT NULL CHECK(guard_type IN ('ChildrenReleased', 'FileAttached', 'PropertiesPresent', 'NoOpenDeviations', 'SignedRelease')),
+                parameter TEXT,
+                FOREIGN KEY (transition_id) REFERENCES WorkflowTransitions(transition_id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Create index for WorkflowTransitionGuards table
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_workflow_transition_guards_transition_id ON WorkflowTransitionGuards(transition_id)", [])?;
+
+        // Create WorkflowTransitionActions table
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS WorkflowTransitionActions (
+                action_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                transition_id INTEGER NOT NULL,
+                action_type TEXT NOT NULL CHECK(action_type IN ('CreateTag', 'MergeBranch', 'RegenerateReadme', 'EmitEvent')),
+                parameter TEXT,
+                sequence INTEGER NOT NULL DEFAULT 0,
+                FOREIGN KEY (transition_id) REFERENCES WorkflowTransitions(transition_id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Create index for WorkflowTransitionActions table
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_workflow_transition_actions_transition_id ON WorkflowTransitionActions(transition_id)", [])?;
+
+        // Create WorkflowEvents table for events emitted by transition actions
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS WorkflowEvents (
+                event_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                revision_id INTEGER NOT NULL,
+                transition_id INTEGER NOT NULL,
+                performed_by TEXT NOT NULL,
+                created_date INTEGER NOT NULL,
+                FOREIGN KEY (revision_id) REFERENCES Revisions(revision_id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Create indexes for WorkflowEvents table
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_workflow_events_revision_id ON WorkflowEvents(revision_id)", [])?;
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_workflow_events_name ON WorkflowEvents(name)", [])?;
+
+        // Create RevisionWorkflowStates table
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS RevisionWorkflowStates (
+                revision_id INTEGER PRIMARY KEY,
+                workflow_id INTEGER NOT NULL,
+                state_id INTEGER NOT NULL,
+                updated_by TEXT NOT NULL,
+                updated_date INTEGER NOT NULL,
+                FOREIGN KEY (revision_id) REFERENCES Revisions(revision_id) ON DELETE CASCADE,
+                FOREIGN KEY (workflow_id) REFERENCES Workflows(workflow_id) ON DELETE RESTRICT,
+                FOREIGN KEY (state_id) REFERENCES WorkflowStates(state_id) ON DELETE RESTRICT
+            )",
+            [],
+        )?;
+
+        // Create index for RevisionWorkflowStates table
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_revision_workflow_states_state_id ON RevisionWorkflowStates(state_id)", [])?;
+
+        // Create Groups table
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS Groups (
+                group_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                description TEXT,
+                UNIQUE(name)
+            )",
+            [],
+        )?;
+
+        // Create GroupMembers table
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS GroupMembers (
+                group_id INTEGER NOT NULL,
+                member TEXT NOT NULL,
+                FOREIGN KEY (group_id) REFERENCES Groups(group_id) ON DELETE CASCADE,
+                UNIQUE(group_id, member)
+            )",
+            [],
+        )?;
+
+        // Create index for GroupMembers table
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_group_members_member ON GroupMembers(member)", [])?;
+
+        // Create Users table; users are matched to git identities by email or username
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS Users (
+                user_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                username TEXT NOT NULL,
+                display_name TEXT,
+                email TEXT,
+                role TEXT NOT NULL CHECK(role IN ('Designer', 'Viewer', 'Admin')),
+                active BOOLEAN NOT NULL DEFAULT 1,
+                UNIQUE(username)
+            )",
+            [],
+        )?;
+
+        // Create index for Users table
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_users_email ON Users(email)", [])?;
+
+        // Create Permissions table; a permission without category applies to all categories
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS Permissions (
+                permission_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                action TEXT NOT NULL CHECK(action IN ('Create', 'Edit', 'Approve', 'Release', 'Obsolete')),
+                grantee_type TEXT NOT NULL CHECK(grantee_type IN ('User', 'Group', 'Role')),
+                grantee TEXT NOT NULL,
+                category_id INTEGER
+            )",
+            [],
+        )?;
+
+        // Create index for Permissions table
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_permissions_grantee ON Permissions(grantee_type, grantee)", [])?;
+
+        // Grant designers every action in a new database
+        let permission_count: i64 = tx.query_row("SELECT COUNT(*) FROM Permissions", [], |row| row.get(0))?;
+        if permission_count == 0 {
+            for action in ["Create", "Edit", "Approve", "Release", "Obsolete"] {
+                tx.execute(
+                    "INSERT INTO Permissions (action, grantee_type, grantee) VALUES (?1, 'Role', 'Designer')",
+                    params![action],
+                )?;
+            }
+        }
+
+        // Create ApprovalPolicies table; a policy without category or transition applies to all reviews
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS ApprovalPolicies (
+                policy_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                category_id INTEGER,
+                transition_id INTEGER,
+                quorum INTEGER NOT NULL DEFAULT 1,
+                rejection_threshold INTEGER NOT NULL DEFAULT 1,
+                active BOOLEAN NOT NULL DEFAULT 1,
+                UNIQUE(name)
+            )",
+            [],
+        )?;
+
+        // Create ApprovalPolicyGroups table for the groups a policy draws reviewers from
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS ApprovalPolicyGroups (
+                policy_id INTEGER NOT NULL,
+                group_id INTEGER NOT NULL,
+                min_approvals INTEGER NOT NULL DEFAULT 0,
+                FOREIGN KEY (policy_id) REFERENCES ApprovalPolicies(policy_id) ON DELETE CASCADE,
+                FOREIGN KEY (group_id) REFERENCES Groups(group_id) ON DELETE CASCADE,
+                UNIQUE(policy_id, group_id)
+            )",
+            [],
+        )?;
+
+        // Create ChangeOrders table for engineering change orders
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS ChangeOrders (
+                eco_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                eco_number TEXT NOT NULL,
+                title TEXT NOT NULL,
+                reason TEXT NOT NULL,
+                impact TEXT,
+                status TEXT NOT NULL CHECK(status IN ('Draft', 'In Review', 'Released', 'Cancelled')),
+                created_by TEXT NOT NULL,
+                created_date INTEGER NOT NULL,
+                released_date INTEGER,
+                commit_hash TEXT,
+                UNIQUE(eco_number)
+            )",
+            [],
+        )?;
+
+        // Create ChangeOrderRevisions table for the revisions grouped by a change order
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS ChangeOrderRevisions (
+                eco_id INTEGER NOT NULL,
+                revision_id INTEGER NOT NULL,
+                FOREIGN KEY (eco_id) REFERENCES ChangeOrders(eco_id) ON DELETE CASCADE,
+                FOREIGN KEY (revision_id) REFERENCES Revisions(revision_id) ON DELETE CASCADE,
+                UNIQUE(eco_id, revision_id)
+            )",
+            [],
+        )?;
+
+        // Create index for ChangeOrderRevisions table
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_change_order_revisions_revision_id ON ChangeOrderRevisions(revision_id)", [])?;
+
+        // Create Deviations table; a deviation is limited by an expiry date, a quantity or both
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS Deviations (
+                deviation_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                deviation_number TEXT NOT NULL,
+                type TEXT NOT NULL CHECK(type IN ('Deviation', 'Waiver')),
+                title TEXT NOT NULL,
+                description TEXT,
+                original_mpn_id INTEGER,
+                substitute_mpn_id INTEGER,
+                quantity_limit INTEGER,
+                quantity_used INTEGER NOT NULL DEFAULT 0,
+                expiry_date INTEGER,
+                status TEXT NOT NULL CHECK(status IN ('Pending', 'Approved', 'Rejected', 'Closed')),
+                created_by TEXT NOT NULL,
+                created_date INTEGER NOT NULL,
+                approved_by TEXT,
+                approved_date INTEGER,
+                FOREIGN KEY (original_mpn_id) REFERENCES ManufacturerParts(mpn_id) ON DELETE SET NULL,
+                FOREIGN KEY (substitute_mpn_id) REFERENCES ManufacturerParts(mpn_id) ON DELETE SET NULL,
+                UNIQUE(deviation_number)
+            )",
+            [],
+        )?;
+
+        // Create DeviationItems table for the parts and revisions affected by a deviation
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS DeviationItems (
+                deviation_id INTEGER NOT NULL,
+                part_id INTEGER NOT NULL,
+                revision_id INTEGER,
+                FOREIGN KEY (deviation_id) REFERENCES Deviations(deviation_id) ON DELETE CASCADE,
+                FOREIGN KEY (part_id) REFERENCES Parts(part_id) ON DELETE CASCADE,
+                FOREIGN KEY (revision_id) REFERENCES Revisions(revision_id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Create index for DeviationItems table
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_deviation_items_part_id ON DeviationItems(part_id)", [])?;
+
+        // Create RevisionChangeFlags table for the form-fit-function flags of a revision
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS RevisionChangeFlags (
+                revision_id INTEGER PRIMARY KEY,
+                form_changed BOOLEAN NOT NULL DEFAULT 0,
+                fit_changed BOOLEAN NOT NULL DEFAULT 0,
+                function_changed BOOLEAN NOT NULL DEFAULT 0,
+                notes TEXT,
+                FOREIGN KEY (revision_id) REFERENCES Revisions(revision_id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Create ChangeImpactReports table for the impact reports attached to reviews
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS ChangeImpactReports (
+                report_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                revision_id INTEGER NOT NULL,
+                generated_by TEXT NOT NULL,
+                generated_date INTEGER NOT NULL,
+                content TEXT NOT NULL,
+                FOREIGN KEY (revision_id) REFERENCES Revisions(revision_id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Create index for ChangeImpactReports table
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_change_impact_reports_revision_id ON ChangeImpactReports(revision_id)", [])?;
+
+        // Create RevisionEffectivity table for the date and serial ranges of released revisions
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS RevisionEffectivity (
+                revision_id INTEGER PRIMARY KEY,
+                from_date INTEGER,
+                from_serial TEXT,
+                superseded_date INTEGER,
+                superseded_serial TEXT,
+                FOREIGN KEY (revision_id) REFERENCES Revisions(revision_id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        // Create AuditLog table; every entry is hash-chained to the previous one
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS AuditLog (
+                entry_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp INTEGER NOT NULL,
+                user TEXT NOT NULL,
+                entity_type TEXT NOT NULL,
+                entity_id INTEGER NOT NULL,
+                action TEXT NOT NULL CHECK(action IN ('Create', 'Update', 'Delete')),
+                details TEXT,
+                previous_hash TEXT NOT NULL,
+                hash TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        // Create indexes for AuditLog table
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_audit_log_entity ON AuditLog(entity_type, entity_id)", [])?;
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_audit_log_user ON AuditLog(user)", [])?;
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_audit_log_timestamp ON AuditLog(timestamp)", [])?;
+
+        // Keep the AuditLog append-only
+        tx.execute(
+            "CREATE TRIGGER IF NOT EXISTS audit_log_no_update BEFORE UPDATE ON AuditLog
+             BEGIN SELECT RAISE(ABORT, 'The audit log is append-only'); END",
+            [],
+        )?;
+        tx.execute(
+            "CREATE TRIGGER IF NOT EXISTS audit_log_no_delete BEFORE DELETE ON AuditLog
+             BEGIN SELECT RAISE(ABORT, 'The audit log is append-only'); END",
+            [],
+        )?;
+
+        // Create Signatures table for electronic signatures on approvals and releases
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS Signatures (
+                signature_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                revision_id INTEGER NOT NULL,
+                approval_id INTEGER,
+                signer TEXT NOT NULL,
+                signer_email TEXT,
+                meaning TEXT NOT NULL CHECK(meaning IN ('Review', 'Approve', 'Release')),
+                timestamp INTEGER NOT NULL,
+                commit_hash TEXT,
+                released_commit_hash TEXT,
+                content_digest TEXT NOT NULL,
+                public_key TEXT NOT NULL,
+                signature TEXT NOT NULL,
+                FOREIGN KEY (revision_id) REFERENCES Revisions(revision_id) ON DELETE CASCADE,
+                FOREIGN KEY (approval_id) REFERENCES Approvals(approval_id) ON DELETE SET NULL
+            )",
+            [],
+        )?;
+
+        // Create index for Signatures table
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_signatures_revision ON Signatures(revision_id)", [])?;
+
+        // Create SigningKeys table binding each signer to the key registered on first signature
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS SigningKeys (
+                username TEXT PRIMARY KEY,
+                public_key TEXT NOT NULL,
+                registered_date INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        // Create Workspaces table
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS Workspaces (
+                workspace_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                description TEXT,
+                include_bom INTEGER NOT NULL DEFAULT 0,
+                created_by TEXT NOT NULL,
+                created_date INTEGER NOT NULL,
+                updated_date INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        // Create WorkspaceParts table; a NULL revision follows the latest revision of the part
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS WorkspaceParts (
+                workspace_id INTEGER NOT NULL,
+                part_id INTEGER NOT NULL,
+                revision_id INTEGER,
+                added_date INTEGER NOT NULL,
+                PRIMARY KEY (workspace_id, part_id),
+                FOREIGN KEY (workspace_id) REFERENCES Workspaces(workspace_id) ON DELETE CASCADE,
+                FOREIGN KEY (part_id) REFERENCES Parts(part_id) ON DELETE CASCADE,
+                FOREIGN KEY (revision_id) REFERENCES Revisions(revision_id) ON DELETE SET NULL
+            )",
+            [],
+        )?;
+
+        // Create index for WorkspaceParts table
+        tx.execute("CREATE INDEX IF NOT EXISTS idx_workspace_parts_part ON WorkspaceParts(part_id)", [])?;
+
+        // Create OperationJournal table; a row marks a journaled operation whose changes were committed
+        tx.execute(
+            "CREATE TABLE IF NOT EXISTS OperationJournal (
+                operation_id TEXT PRIMARY KEY,
+                operation TEXT NOT NULL,
+                completed_date INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
         // Insert initial schema version (use OR IGNORE to handle multiple initializations)
         tx.execute(
             "INSERT OR IGNORE INTO SchemaVersion (version, description) VALUES (1, 'Initial schema creation')",
             [],
         )?;
+
+        // Bring databases created by earlier releases up to date
+        Self::apply_migrations(tx)?;
             
             Ok(())
         })
     }
 
+    /// Apply the schema migrations that are newer than the recorded schema version
+    ///
+    /// Tables created by `initialize_schema` already have the current layout, so each
+    /// migration only changes what an older database is missing.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - Transaction the schema is being initialized in
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if a migration fails
+    fn apply_migrations(tx: &Transaction) -> DatabaseResult<()> {
+        let version: i64 = tx.query_row("SELECT MAX(version) FROM SchemaVersion", [], |row| row.get(0))?;
+
+        if version < 2 {
+            Self::migrate_to_version_2(tx)?;
+            tx.execute(
+                "INSERT INTO SchemaVersion (version, description) VALUES (2, 'Add part lifecycle, BOM and category columns')",
+                [],
+            )?;
+        }
+
+        if version < 3 {
+            Self::migrate_to_version_3(tx)?;
+            tx.execute(
+                "INSERT INTO SchemaVersion (version, description) VALUES (3, 'Add approved and released commits of signatures')",
+                [],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Migration to schema version 2
+    ///
+    /// Adds the manufacturer part lifecycle dates, the BOM line attrition and the
+    /// category revision scheme and workflow.
+    fn migrate_to_version_2(tx: &Transaction) -> DatabaseResult<()> {
+        for column in ["nrnd_date", "last_time_buy_date", "eol_date"] {
+            if !Self::column_exists(tx, "ManufacturerParts", column)? {
+                tx.execute(&format!("ALTER TABLE ManufacturerParts ADD COLUMN {} INTEGER", column), [])?;
+            }
+        }
+
+        if !Self::column_exists(tx, "Relationships", "attrition")? {
+            tx.execute(
+                "ALTER TABLE Relationships ADD COLUMN attrition REAL NOT NULL DEFAULT 0 CHECK(attrition >= 0)",
+                [],
+            )?;
+        }
+
+        // The revision scheme has a CHECK constraint, so Categories is rebuilt instead of altered
+        if !Self::column_exists(tx, "Categories", "revision_scheme")? || !Self::column_exists(tx, "Categories", "workflow_id")? {
+            let mut columns = vec!["category_id", "name", "code", "description"];
+            for column in ["revision_scheme", "workflow_id"] {
+                if Self::column_exists(tx, "Categories", column)? {
+                    columns.push(column);
+                }
+            }
+            let columns = columns.join(", ");
+
+            tx.execute(
+                "CREATE TABLE Categories_new (
+                    category_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    name TEXT NOT NULL,
+                    code TEXT NOT NULL,
+                    description TEXT,
+                    revision_scheme TEXT NOT NULL DEFAULT 'Numeric' CHECK(revision_scheme IN ('Numeric', 'Alphabetic', 'MajorMinor')),
+                    workflow_id INTEGER,
+                    UNIQUE(name),
+                    UNIQUE(code)
+                )",
+                [],
+            )?;
+            tx.execute(
+                &format!("INSERT INTO Categories_new ({0}) SELECT {0} FROM Categories", columns),
+                [],
+            )?;
+            tx.execute("DROP TABLE Categories", [])?;
+            tx.execute("ALTER TABLE Categories_new RENAME TO Categories", [])?;
+            tx.execute("CREATE INDEX IF NOT EXISTS idx_categories_code ON Categories(code)", [])?;
+        }
+
+        Ok(())
+    }
+
+    /// Migration to schema version 3
+    ///
+    /// Records the commit a revision was approved at apart from the commit it was released
+    /// with, on revisions and on release signatures.
+    fn migrate_to_version_3(tx: &Transaction) -> DatabaseResult<()> {
+        if !Self::column_exists(tx, "Revisions", "approved_commit_hash")? {
+            tx.execute("ALTER TABLE Revisions ADD COLUMN approved_commit_hash TEXT", [])?;
+        }
+        if !Self::column_exists(tx, "Signatures", "released_commit_hash")? {
+            tx.execute("ALTER TABLE Signatures ADD COLUMN released_commit_hash TEXT", [])?;
+        }
+
+        Ok(())
+    }
+
+    /// Check whether a table has a column
+    fn column_exists(tx: &Transaction, table: &str, column: &str) -> DatabaseResult<bool> {
+        let mut stmt = tx.prepare(&format!("PRAGMA table_info({})", table))?;
+        let columns = stmt.query_map([], |row| row.get::<_, String>(1))?;
+        for name in columns {
+            if name? == column {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
     /// Get the current schema version
     ///
     /// # Returns
@@ -440,9 +1124,9 @@ mod tests {
         let result = db_manager.initialize_schema();
         assert!(result.is_ok());
 
-        // Check that the schema version is 1
+        // Check that the schema version is the latest
         let version = db_manager.get_schema_version().unwrap();
-        assert_eq!(version, 1);
+        assert_eq!(version, 3);
 
         // Check that all tables were created
         let tables = db_manager
@@ -466,15 +1150,176 @@ mod tests {
             "Relationships",
             "Properties",
             "ManufacturerParts",
+            "Suppliers",
+            "SupplierOffers",
+            "PriceBreaks",
+            "StockLocations",
+            "StockRecords",
+            "InventoryTransactions",
+            "SerializedUnits",
+            "AsBuiltComponents",
             "Approvals",
             "Files",
             "Workflows",
             "WorkflowStates",
             "WorkflowTransitions",
+            "WorkflowTransitionGuards",
+            "WorkflowTransitionActions",
+            "WorkflowEvents",
+            "RevisionWorkflowStates",
+            "Groups",
+            "GroupMembers",
+            "Users",
+            "Permissions",
+            "ApprovalPolicies",
+            "ApprovalPolicyGroups",
+            "ChangeOrders",
+            "ChangeOrderRevisions",
+            "Deviations",
+            "DeviationItems",
+            "RevisionChangeFlags",
+            "ChangeImpactReports",
+            "RevisionEffectivity",
+            "AuditLog",
+            "Signatures",
+            "SigningKeys",
+            "Workspaces",
+            "WorkspaceParts",
+            "OperationJournal",
         ];
 
         for table in expected_tables {
             assert!(tables.contains(&table.to_string()));
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_migrate_version_1_database() {
+        let temp_dir = tempdir().unwrap();
+        let db_manager = DatabaseManager::new(temp_dir.path().join("test.db")).unwrap();
+
+        // Create the tables changed since version 1 the way version 1 created them
+        db_manager
+            .connection_manager()
+            .execute::<_, _, DatabaseError>(|conn| {
+                conn.execute_batch(
+                    "CREATE TABLE SchemaVersion (
+                        version INTEGER PRIMARY KEY,
+                        applied_date TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                        description TEXT
+                    );
+                    INSERT INTO SchemaVersion (version, description) VALUES (1, 'Initial schema creation');
+                    CREATE TABLE Parts (
+                        part_id INTEGER PRIMARY KEY,
+                        category TEXT NOT NULL,
+                        subcategory TEXT NOT NULL,
+                        name TEXT NOT NULL,
+                        description TEXT,
+                        created_date TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                        modified_date TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                        UNIQUE(category, subcategory, name)
+                    );
+                    INSERT INTO Parts (part_id, category, subcategory, name) VALUES (10000, 'EL', 'RES', '10k Resistor');
+                    INSERT INTO Parts (part_id, category, subcategory, name) VALUES (10001, 'EL', 'PCA', 'Controller Board');
+                    CREATE TABLE ManufacturerParts (
+                        mpn_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                        part_id INTEGER NOT NULL,
+                        manufacturer TEXT NOT NULL,
+                        mpn TEXT NOT NULL,
+                        description TEXT,
+                        status TEXT NOT NULL DEFAULT 'Active' CHECK(status IN ('Active', 'Preferred', 'Alternate', 'Obsolete')),
+                        FOREIGN KEY (part_id) REFERENCES Parts(part_id) ON DELETE CASCADE,
+                        UNIQUE(manufacturer, mpn)
+                    );
+                    INSERT INTO ManufacturerParts (part_id, manufacturer, mpn) VALUES (10000, 'Yageo', 'RC0603FR-0710KL');
+                    CREATE TABLE Relationships (
+                        relationship_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                        parent_part_id INTEGER NOT NULL,
+                        child_part_id INTEGER NOT NULL,
+                        type TEXT NOT NULL,
+                        quantity INTEGER NOT NULL DEFAULT 1,
+                        FOREIGN KEY (parent_part_id) REFERENCES Parts(part_id) ON DELETE CASCADE,
+                        FOREIGN KEY (child_part_id) REFERENCES Parts(part_id) ON DELETE CASCADE,
+                        UNIQUE(parent_part_id, child_part_id, type)
+                    );
+                    INSERT INTO Relationships (parent_part_id, child_part_id, type, quantity) VALUES (10001, 10000, 'BOM', 2);
+                    CREATE TABLE Revisions (
+                        revision_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                        part_id INTEGER NOT NULL,
+                        version TEXT NOT NULL,
+                        status TEXT NOT NULL CHECK(status IN ('Draft', 'In Review', 'Released', 'Obsolete')),
+                        created_date TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                        created_by TEXT NOT NULL,
+                        commit_hash TEXT,
+                        FOREIGN KEY (part_id) REFERENCES Parts(part_id) ON DELETE CASCADE,
+                        UNIQUE(part_id, version)
+                    );
+                    INSERT INTO Revisions (part_id, version, status, created_by, commit_hash) VALUES (10000, '1', 'Released', 'alice', '0123456789abcdef');
+                    CREATE TABLE Categories (
+                        category_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                        name TEXT NOT NULL,
+                        code TEXT NOT NULL,
+                        description TEXT,
+                        UNIQUE(name),
+                        UNIQUE(code)
+                    );
+                    INSERT INTO Categories (name, code, description) VALUES ('Electronic', 'EL', 'Electronic components and PCBAs');
+                    INSERT INTO Categories (name, code, description) VALUES ('Tooling', 'TL', 'Fixtures and tooling');",
+                )?;
+                Ok(())
+            })
+            .unwrap();
+
+        db_manager.initialize_schema().unwrap();
+        assert_eq!(db_manager.get_schema_version().unwrap(), 3);
+
+        // The existing rows can be read through the new columns
+        let eol_date: Option<i64> = db_manager
+            .connection_manager()
+            .execute::<_, _, DatabaseError>(|conn| {
+                Ok(conn.query_row(
+                    "SELECT eol_date FROM ManufacturerParts WHERE mpn = 'RC0603FR-0710KL'",
+                    [],
+                    |row| row.get(0),
+                )?)
+            })
+            .unwrap();
+        assert_eq!(eol_date, None);
+
+        let attrition: f64 = db_manager
+            .connection_manager()
+            .execute::<_, _, DatabaseError>(|conn| {
+                Ok(conn.query_row("SELECT attrition FROM Relationships", [], |row| row.get(0))?)
+            })
+            .unwrap();
+        assert_eq!(attrition, 0.0);
+
+        // Revisions released before approvals were recorded apart have no approved commit
+        let approved_commit_hash: Option<String> = db_manager
+            .connection_manager()
+            .execute::<_, _, DatabaseError>(|conn| {
+                Ok(conn.query_row("SELECT approved_commit_hash FROM Revisions", [], |row| row.get(0))?)
+            })
+            .unwrap();
+        assert_eq!(approved_commit_hash, None);
+
+        // Categories keep their ids and pick up the default revision scheme
+        let (category_id, scheme, workflow_id): (i64, String, Option<i64>) = db_manager
+            .connection_manager()
+            .execute::<_, _, DatabaseError>(|conn| {
+                Ok(conn.query_row(
+                    "SELECT category_id, revision_scheme, workflow_id FROM Categories WHERE code = 'TL'",
+                    [],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+                )?)
+            })
+            .unwrap();
+        assert_eq!(category_id, 2);
+        assert_eq!(scheme, "Numeric");
+        assert_eq!(workflow_id, None);
+
+        // Initializing an up-to-date database again is a no-op
+        db_manager.initialize_schema().unwrap();
+        assert_eq!(db_manager.get_schema_version().unwrap(), 3);
+    }
+}
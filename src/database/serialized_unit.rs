@@ -0,0 +1,604 @@
+//! Serialized unit module for Implexa
+//!
+//! This module provides functionality for recording individually built units of an assembly
+//! and their as-built configuration: the revision each unit was built to and the parts,
+//! manufacturer parts, lots and serialized sub-assemblies installed in each position.
+
+use rusqlite::{Transaction, params, Row, Result as SqliteResult, OptionalExtension};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::database::schema::{DatabaseError, DatabaseResult};
+use crate::database::connection_manager::ConnectionManager;
+
+/// Represents an individually built unit of an assembly
+#[derive(Debug, Clone)]
+pub struct SerializedUnit {
+    /// Unique identifier for the unit
+    pub unit_id: Option<i64>,
+    /// Serial number of the unit
+    pub serial_number: String,
+    /// ID of the assembly part
+    pub part_id: i64,
+    /// ID of the assembly revision the unit was built to
+    pub revision_id: i64,
+    /// Date the unit was built
+    pub build_date: SystemTime,
+    /// User who built the unit
+    pub built_by: String,
+    /// Free-form notes
+    pub notes: Option<String>,
+}
+
+impl SerializedUnit {
+    /// Create a new serialized unit
+    ///
+    /// # Arguments
+    ///
+    /// * `serial_number` - Serial number of the unit
+    /// * `part_id` - ID of the assembly part
+    /// * `revision_id` - ID of the assembly revision the unit was built to
+    /// * `built_by` - User who built the unit
+    ///
+    /// # Returns
+    ///
+    /// A new SerializedUnit instance
+    pub fn new(serial_number: String, part_id: i64, revision_id: i64, built_by: String) -> Self {
+        Self {
+            unit_id: None,
+            serial_number,
+            part_id,
+            revision_id,
+            build_date: SystemTime::now(),
+            built_by,
+            notes: None,
+        }
+    }
+}
+
+/// Represents a component installed in a position of a serialized unit
+#[derive(Debug, Clone)]
+pub struct AsBuiltComponent {
+    /// Unique identifier for the component record
+    pub component_id: Option<i64>,
+    /// ID of the unit the component is installed in
+    pub unit_id: Option<i64>,
+    /// Position of the component, e.g. a reference designator or BOM find number
+    pub position: String,
+    /// ID of the installed part
+    pub part_id: i64,
+    /// Installed quantity
+    pub quantity: i64,
+    /// ID of the manufacturer part actually installed
+    pub mpn_id: Option<i64>,
+    /// Lot number of the installed stock
+    pub lot_number: Option<String>,
+    /// ID of the installed serialized sub-assembly
+    pub installed_unit_id: Option<i64>,
+}
+
+impl AsBuiltComponent {
+    /// Create a new as-built component
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - Position of the component
+    /// * `part_id` - ID of the installed part
+    /// * `quantity` - Installed quantity
+    ///
+    /// # Returns
+    ///
+    /// A new AsBuiltComponent instance
+    pub fn new(position: String, part_id: i64, quantity: i64) -> Self {
+        Self {
+            component_id: None,
+            unit_id: None,
+            position,
+            part_id,
+            quantity,
+            mpn_id: None,
+            lot_number: None,
+            installed_unit_id: None,
+        }
+    }
+}
+
+/// Manager for serialized unit operations
+pub struct SerializedUnitManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+}
+
+impl<'a> SerializedUnitManager<'a> {
+    /// Create a new SerializedUnitManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new SerializedUnitManager instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Record a built unit together with its as-built components
+    ///
+    /// # Arguments
+    ///
+    /// * `unit` - The unit to record
+    /// * `components` - The components installed in the unit
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created unit
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the unit or any component is invalid or could not be recorded
+    pub fn create_unit(&self, unit: &SerializedUnit, components: &[AsBuiltComponent]) -> DatabaseResult<i64> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.create_unit_in_transaction(unit, components, tx)
+        })
+    }
+
+    /// Record a built unit together with its as-built components within a transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `unit` - The unit to record
+    /// * `components` - The components installed in the unit
+    /// * `tx` - The transaction to use
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created unit
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the unit or any component is invalid or could not be recorded
+    pub fn create_unit_in_transaction(&self, unit: &SerializedUnit, components: &[AsBuiltComponent], tx: &Transaction) -> DatabaseResult<i64> {
+        if unit.serial_number.trim().is_empty() {
+            return Err(DatabaseError::ValidationError("Serial number must not be empty".to_string()));
+        }
+
+        // The unit must be built to a revision of its own part
+        let revision_part_id: Option<i64> = tx.query_row(
+            "SELECT part_id FROM Revisions WHERE revision_id = ?1",
+            params![unit.revision_id],
+            |row| row.get(0),
+        ).optional()?;
+        match revision_part_id {
+            None => return Err(DatabaseError::ValidationError(format!("Revision {} does not exist", unit.revision_id))),
+            Some(part_id) if part_id != unit.part_id => {
+                return Err(DatabaseError::ValidationError(format!(
+                    "Revision {} belongs to part {}, not part {}", unit.revision_id, part_id, unit.part_id
+                )));
+            }
+            Some(_) => {}
+        }
+
+        tx.execute(
+            "INSERT INTO SerializedUnits (serial_number, part_id, revision_id, build_date, built_by, notes)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                unit.serial_number,
+                unit.part_id,
+                unit.revision_id,
+                unit.build_date.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64,
+                unit.built_by,
+                unit.notes,
+            ],
+        )?;
+        let unit_id = tx.last_insert_rowid();
+
+        for component in components {
+            self.add_component_in_transaction(unit_id, component, tx)?;
+        }
+
+        Ok(unit_id)
+    }
+
+    /// Add a component to the as-built record of a unit
+    ///
+    /// # Arguments
+    ///
+    /// * `unit_id` - The ID of the unit
+    /// * `component` - The installed component
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created component record
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the component is invalid or could not be recorded
+    pub fn add_component(&self, unit_id: i64, component: &AsBuiltComponent) -> DatabaseResult<i64> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.add_component_in_transaction(unit_id, component, tx)
+        })
+    }
+
+    /// Add a component to the as-built record of a unit within a transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `unit_id` - The ID of the unit
+    /// * `component` - The installed component
+    /// * `tx` - The transaction to use
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created component record
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the component is invalid or could not be recorded
+    pub fn add_component_in_transaction(&self, unit_id: i64, component: &AsBuiltComponent, tx: &Transaction) -> DatabaseResult<i64> {
+        if component.position.trim().is_empty() {
+            return Err(DatabaseError::ValidationError("Component position must not be empty".to_string()));
+        }
+        if component.quantity <= 0 {
+            return Err(DatabaseError::ValidationError(format!("Quantity must be positive, got {}", component.quantity)));
+        }
+
+        // The installed manufacturer part must be a source of the installed part
+        if let Some(mpn_id) = component.mpn_id {
+            let mpn_part_id: Option<i64> = tx.query_row(
+                "SELECT part_id FROM ManufacturerParts WHERE mpn_id = ?1",
+                params![mpn_id],
+                |row| row.get(0),
+            ).optional()?;
+            if mpn_part_id != Some(component.part_id) {
+                return Err(DatabaseError::ValidationError(format!(
+                    "Manufacturer part {} is not a source of part {}", mpn_id, component.part_id
+                )));
+            }
+        }
+
+        // An installed sub-assembly must be a unit of the installed part and not the unit itself
+        if let Some(installed_unit_id) = component.installed_unit_id {
+            let installed_part_id: Option<i64> = tx.query_row(
+                "SELECT part_id FROM SerializedUnits WHERE unit_id = ?1",
+                params![installed_unit_id],
+                |row| row.get(0),
+            ).optional()?;
+            if installed_unit_id == unit_id || installed_part_id != Some(component.part_id) {
+                return Err(DatabaseError::ValidationError(format!(
+                    "Unit {} is not a serialized unit of part {}", installed_unit_id, component.part_id
+                )));
+            }
+        }
+
+        tx.execute(
+            "INSERT INTO AsBuiltComponents (unit_id, position, part_id, quantity, mpn_id, lot_number, installed_unit_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                unit_id,
+                component.position,
+                component.part_id,
+                component.quantity,
+                component.mpn_id,
+                component.lot_number,
+                component.installed_unit_id,
+            ],
+        )?;
+        Ok(tx.last_insert_rowid())
+    }
+
+    /// Get a unit by ID
+    ///
+    /// # Arguments
+    ///
+    /// * `unit_id` - The ID of the unit
+    ///
+    /// # Returns
+    ///
+    /// The unit
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the unit could not be found
+    pub fn get_unit(&self, unit_id: i64) -> DatabaseResult<SerializedUnit> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let unit = conn.query_row(
+                "SELECT unit_id, serial_number, part_id, revision_id, build_date, built_by, notes
+                 FROM SerializedUnits WHERE unit_id = ?1",
+                params![unit_id],
+                |row| self.row_to_unit(row),
+            )?;
+            Ok::<SerializedUnit, DatabaseError>(unit)
+        })
+    }
+
+    /// Get a unit by serial number
+    ///
+    /// # Arguments
+    ///
+    /// * `serial_number` - The serial number of the unit
+    ///
+    /// # Returns
+    ///
+    /// The unit, or None if no unit has the serial number
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the unit could not be retrieved
+    pub fn get_unit_by_serial(&self, serial_number: &str) -> DatabaseResult<Option<SerializedUnit>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let unit = conn.query_row(
+                "SELECT unit_id, serial_number, part_id, revision_id, build_date, built_by, notes
+                 FROM SerializedUnits WHERE serial_number = ?1",
+                params![serial_number],
+                |row| self.row_to_unit(row),
+            ).optional()?;
+            Ok::<Option<SerializedUnit>, DatabaseError>(unit)
+        })
+    }
+
+    /// Get all units built to a revision
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    ///
+    /// # Returns
+    ///
+    /// A vector of units ordered by serial number
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the units could not be retrieved
+    pub fn get_units_for_revision(&self, revision_id: i64) -> DatabaseResult<Vec<SerializedUnit>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT unit_id, serial_number, part_id, revision_id, build_date, built_by, notes
+                 FROM SerializedUnits WHERE revision_id = ?1
+                 ORDER BY serial_number",
+            )?;
+            let units_iter = stmt.query_map(params![revision_id], |row| self.row_to_unit(row))?;
+            let mut units = Vec::new();
+            for unit_result in units_iter {
+                units.push(unit_result?);
+            }
+            Ok::<Vec<SerializedUnit>, DatabaseError>(units)
+        })
+    }
+
+    /// Get the as-built components of a unit
+    ///
+    /// # Arguments
+    ///
+    /// * `unit_id` - The ID of the unit
+    ///
+    /// # Returns
+    ///
+    /// A vector of components ordered by position
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the components could not be retrieved
+    pub fn get_components(&self, unit_id: i64) -> DatabaseResult<Vec<AsBuiltComponent>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT component_id, unit_id, position, part_id, quantity, mpn_id, lot_number, installed_unit_id
+                 FROM AsBuiltComponents WHERE unit_id = ?1
+                 ORDER BY position, component_id",
+            )?;
+            let components_iter = stmt.query_map(params![unit_id], |row| self.row_to_component(row))?;
+            let mut components = Vec::new();
+            for component_result in components_iter {
+                components.push(component_result?);
+            }
+            Ok::<Vec<AsBuiltComponent>, DatabaseError>(components)
+        })
+    }
+
+    /// Get all units that contain a lot, directly or through installed sub-assemblies
+    ///
+    /// # Arguments
+    ///
+    /// * `lot_number` - The lot number
+    /// * `part_id` - The ID of the part the lot belongs to, or None to match the lot of any part
+    ///
+    /// # Returns
+    ///
+    /// A vector of units ordered by serial number
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the units could not be retrieved
+    pub fn get_units_containing_lot(&self, lot_number: &str, part_id: Option<i64>) -> DatabaseResult<Vec<SerializedUnit>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "WITH RECURSIVE Containing(unit_id) AS (
+                     SELECT unit_id FROM AsBuiltComponents
+                     WHERE lot_number = ?1 AND (?2 IS NULL OR part_id = ?2)
+                     UNION
+                     SELECT c.unit_id FROM AsBuiltComponents c
+                     JOIN Containing ON c.installed_unit_id = Containing.unit_id
+                 )
+                 SELECT unit_id, serial_number, part_id, revision_id, build_date, built_by, notes
+                 FROM SerializedUnits WHERE unit_id IN (SELECT unit_id FROM Containing)
+                 ORDER BY serial_number",
+            )?;
+            let units_iter = stmt.query_map(params![lot_number, part_id], |row| self.row_to_unit(row))?;
+            let mut units = Vec::new();
+            for unit_result in units_iter {
+                units.push(unit_result?);
+            }
+            Ok::<Vec<SerializedUnit>, DatabaseError>(units)
+        })
+    }
+
+    /// Delete a unit and its as-built components
+    ///
+    /// # Arguments
+    ///
+    /// * `unit_id` - The ID of the unit
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the unit was successfully deleted
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the unit is installed in another unit or could not be deleted
+    pub fn delete_unit(&self, unit_id: i64) -> DatabaseResult<()> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let parent_unit_id: Option<i64> = tx.query_row(
+                "SELECT unit_id FROM AsBuiltComponents WHERE installed_unit_id = ?1",
+                params![unit_id],
+                |row| row.get(0),
+            ).optional()?;
+            if let Some(parent_unit_id) = parent_unit_id {
+                return Err(DatabaseError::ValidationError(format!(
+                    "Unit {} is installed in unit {}", unit_id, parent_unit_id
+                )));
+            }
+
+            tx.execute("DELETE FROM AsBuiltComponents WHERE unit_id = ?1", params![unit_id])?;
+            tx.execute("DELETE FROM SerializedUnits WHERE unit_id = ?1", params![unit_id])?;
+            Ok(())
+        })
+    }
+
+    /// Convert a database row to a SerializedUnit
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The database row
+    ///
+    /// # Returns
+    ///
+    /// A SerializedUnit instance
+    ///
+    /// # Errors
+    ///
+    /// Returns a SqliteError if the row could not be converted
+    fn row_to_unit(&self, row: &Row) -> SqliteResult<SerializedUnit> {
+        let build_date_secs: i64 = row.get(4)?;
+
+        Ok(SerializedUnit {
+            unit_id: Some(row.get(0)?),
+            serial_number: row.get(1)?,
+            part_id: row.get(2)?,
+            revision_id: row.get(3)?,
+            build_date: UNIX_EPOCH + std::time::Duration::from_secs(build_date_secs as u64),
+            built_by: row.get(5)?,
+            notes: row.get(6)?,
+        })
+    }
+
+    /// Convert a database row to an AsBuiltComponent
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The database row
+    ///
+    /// # Returns
+    ///
+    /// An AsBuiltComponent instance
+    ///
+    /// # Errors
+    ///
+    /// Returns a SqliteError if the row could not be converted
+    fn row_to_component(&self, row: &Row) -> SqliteResult<AsBuiltComponent> {
+        Ok(AsBuiltComponent {
+            component_id: Some(row.get(0)?),
+            unit_id: Some(row.get(1)?),
+            position: row.get(2)?,
+            part_id: row.get(3)?,
+            quantity: row.get(4)?,
+            mpn_id: row.get(5)?,
+            lot_number: row.get(6)?,
+            installed_unit_id: row.get(7)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::DatabaseManager;
+    use crate::database::part::{Part, PartManager};
+    use crate::database::revision::{Revision, RevisionManager, RevisionStatus};
+    use crate::database::manufacturer_part::{ManufacturerPart, ManufacturerPartManager, ManufacturerPartStatus};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_as_built_traceability() {
+        // Create a temporary directory for the test database
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+
+        // Create a new database manager and initialize the schema
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let connection_manager = db_manager.connection_manager();
+
+        let part_manager = PartManager::new(connection_manager);
+        let revision_manager = RevisionManager::new(connection_manager);
+        let mpn_manager = ManufacturerPartManager::new(connection_manager);
+        let unit_manager = SerializedUnitManager::new(connection_manager);
+
+        // Create a resistor, a board and a unit containing the board
+        for (part_id, subcategory, name) in [
+            (10001, "Resistor", "10K Resistor"),
+            (10002, "PCA", "Controller Board"),
+            (10003, "Assembly", "Controller Unit"),
+        ] {
+            let part = Part::new(part_id, "Electronic".to_string(), subcategory.to_string(), name.to_string(), None);
+            part_manager.create_part(&part).unwrap();
+        }
+        let board_revision = revision_manager.create_revision(&Revision::new(
+            10002, "1".to_string(), RevisionStatus::Released, "test".to_string(), None,
+        )).unwrap();
+        let unit_revision = revision_manager.create_revision(&Revision::new(
+            10003, "1".to_string(), RevisionStatus::Released, "test".to_string(), None,
+        )).unwrap();
+        let mpn_id = mpn_manager.create_manufacturer_part(&ManufacturerPart::new(
+            10001, "Yageo".to_string(), "RC0603FR-0710KL".to_string(), None, ManufacturerPartStatus::Active,
+        )).unwrap();
+
+        // Build a board with resistors from lot L2401
+        let mut resistors = AsBuiltComponent::new("R1-R10".to_string(), 10001, 10);
+        resistors.mpn_id = Some(mpn_id);
+        resistors.lot_number = Some("L2401".to_string());
+        let board = SerializedUnit::new("PCB0001".to_string(), 10002, board_revision, "alice".to_string());
+        let board_id = unit_manager.create_unit(&board, &[resistors]).unwrap();
+
+        // A unit cannot be built to a revision of another part
+        let wrong_revision = SerializedUnit::new("SN000".to_string(), 10003, board_revision, "bob".to_string());
+        assert!(unit_manager.create_unit(&wrong_revision, &[]).is_err());
+
+        // Build a unit containing the board
+        let mut installed_board = AsBuiltComponent::new("A1".to_string(), 10002, 1);
+        installed_board.installed_unit_id = Some(board_id);
+        let unit = SerializedUnit::new("SN123".to_string(), 10003, unit_revision, "bob".to_string());
+        let unit_id = unit_manager.create_unit(&unit, &[installed_board.clone()]).unwrap();
+
+        // The board cannot be installed in a second unit
+        let other_unit = SerializedUnit::new("SN124".to_string(), 10003, unit_revision, "bob".to_string());
+        assert!(unit_manager.create_unit(&other_unit, &[installed_board]).is_err());
+        assert!(unit_manager.get_unit_by_serial("SN124").unwrap().is_none());
+
+        // What is in SN123
+        let unit = unit_manager.get_unit_by_serial("SN123").unwrap().unwrap();
+        assert_eq!(unit.unit_id, Some(unit_id));
+        let components = unit_manager.get_components(unit_id).unwrap();
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].installed_unit_id, Some(board_id));
+        assert_eq!(unit_manager.get_components(board_id).unwrap()[0].lot_number.as_deref(), Some("L2401"));
+
+        // Which units contain lot L2401, directly or through the board
+        let serials: Vec<String> = unit_manager.get_units_containing_lot("L2401", Some(10001)).unwrap()
+            .into_iter()
+            .map(|unit| unit.serial_number)
+            .collect();
+        assert_eq!(serials, vec!["PCB0001".to_string(), "SN123".to_string()]);
+        assert!(unit_manager.get_units_containing_lot("L2401", Some(10002)).unwrap().is_empty());
+
+        // An installed board cannot be deleted
+        assert!(unit_manager.delete_unit(board_id).is_err());
+    }
+}
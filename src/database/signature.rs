@@ -0,0 +1,724 @@
+//! Electronic signature module for Implexa
+//!
+//! This module provides functionality for signing approvals and releases. A signature captures
+//! the identity of the signer, the meaning of the signature, a timestamp and a digest of the
+//! exact revision content (the commit approved for release plus the metadata of the revision),
+//! and is signed with an Ed25519 key held locally by the signer. A release signature
+//! also covers the commit the revision was released with. The public key of every signer is
+//! registered on their first signature and signatures are verified against it, so anyone can
+//! later verify that an approval covers exactly what was released.
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rusqlite::{Transaction, params, Row, Result as SqliteResult};
+use rusqlite::types::Type;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::database::schema::{DatabaseError, DatabaseResult};
+use crate::database::connection_manager::ConnectionManager;
+use crate::database::revision::RevisionManager;
+use crate::database::user::User;
+
+/// Meaning of an electronic signature
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureMeaning {
+    /// The signer reviewed the revision without approving it
+    Review,
+    /// The signer approved the revision
+    Approve,
+    /// The signer released the revision
+    Release,
+}
+
+impl SignatureMeaning {
+    /// Convert a string to a SignatureMeaning
+    ///
+    /// # Arguments
+    ///
+    /// * `meaning` - The meaning string
+    ///
+    /// # Returns
+    ///
+    /// The corresponding SignatureMeaning
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(meaning: &str) -> Option<Self> {
+        match meaning {
+            "Review" => Some(Self::Review),
+            "Approve" => Some(Self::Approve),
+            "Release" => Some(Self::Release),
+            _ => None,
+        }
+    }
+
+    /// Convert a SignatureMeaning to a string
+    ///
+    /// # Returns
+    ///
+    /// The string representation of the meaning
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::Review => "Review",
+            Self::Approve => "Approve",
+            Self::Release => "Release",
+        }
+    }
+}
+
+/// Represents an electronic signature on a revision
+#[derive(Debug, Clone)]
+pub struct ElectronicSignature {
+    /// Unique identifier for the signature
+    pub signature_id: Option<i64>,
+    /// ID of the signed revision
+    pub revision_id: i64,
+    /// ID of the approval the signature belongs to, if any
+    pub approval_id: Option<i64>,
+    /// Username of the signer
+    pub signer: String,
+    /// Email address of the signer
+    pub signer_email: Option<String>,
+    /// Meaning of the signature
+    pub meaning: SignatureMeaning,
+    /// Date of the signature
+    pub timestamp: SystemTime,
+    /// Commit the signed content was taken from, which is the commit approved for release
+    pub commit_hash: Option<String>,
+    /// Commit the revision was released with, for release signatures
+    pub released_commit_hash: Option<String>,
+    /// SHA-256 digest of the signed revision content
+    pub content_digest: String,
+    /// Hex-encoded Ed25519 public key of the signer
+    pub public_key: String,
+    /// Hex-encoded Ed25519 signature over the signed payload
+    pub signature: String,
+}
+
+impl ElectronicSignature {
+    /// Build the payload covered by the signature
+    ///
+    /// # Returns
+    ///
+    /// The canonical text that is signed
+    pub fn payload(&self) -> String {
+        format!(
+            "implexa-signature-v1\nrevision:{}\nsigner:{}\nemail:{}\nmeaning:{}\ntimestamp:{}\ncommit:{}\nreleased:{}\ndigest:{}\n",
+            self.revision_id,
+            self.signer,
+            self.signer_email.as_deref().unwrap_or_default(),
+            self.meaning.to_str(),
+            self.timestamp.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+            self.commit_hash.as_deref().unwrap_or_default(),
+            self.released_commit_hash.as_deref().unwrap_or_default(),
+            self.content_digest,
+        )
+    }
+
+    /// Check that the signature was made with the registered key of the signer over the stored
+    /// payload
+    ///
+    /// The public key stored with the signature is not trusted on its own; it must be the key
+    /// registered for the signer.
+    ///
+    /// # Arguments
+    ///
+    /// * `registered_key` - Hex-encoded public key registered for the signer, if any
+    ///
+    /// # Returns
+    ///
+    /// true if the signature is cryptographically valid under the registered key
+    pub fn is_authentic(&self, registered_key: Option<&str>) -> bool {
+        let Some(registered_key) = registered_key.filter(|key| key.eq_ignore_ascii_case(&self.public_key)) else {
+            return false;
+        };
+        let public_key = decode_hex(registered_key)
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok());
+        let signature = decode_hex(&self.signature)
+            .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+            .map(|bytes| Signature::from_bytes(&bytes));
+        match (public_key, signature) {
+            (Some(public_key), Some(signature)) => public_key.verify(self.payload().as_bytes(), &signature).is_ok(),
+            _ => false,
+        }
+    }
+}
+
+/// Result of verifying a signature
+#[derive(Debug, Clone)]
+pub struct SignatureVerification {
+    /// The verified signature
+    pub signature: ElectronicSignature,
+    /// Whether the signature was made with the registered key of the signer over the stored payload
+    pub authentic: bool,
+    /// Whether the revision metadata still matches the signed digest and, for a release
+    /// signature, the revision is still recorded as released with the signed commit
+    pub content_matches: bool,
+    /// Whether the signed digest equals the digest of the release signature; None if the
+    /// revision has not been released with a signature
+    pub covers_release: Option<bool>,
+}
+
+impl SignatureVerification {
+    /// Check whether the signature holds
+    ///
+    /// # Returns
+    ///
+    /// true if the signature is authentic, matches the revision and covers its release
+    pub fn is_valid(&self) -> bool {
+        self.authentic && self.content_matches && self.covers_release.unwrap_or(true)
+    }
+}
+
+/// Store of the Ed25519 keys of the local users
+///
+/// Keys are kept outside the repository, one file per user, and created on first use.
+#[derive(Debug, Clone)]
+pub struct SigningKeyStore {
+    /// Directory holding the key files
+    directory: PathBuf,
+}
+
+impl SigningKeyStore {
+    /// Create a key store in a directory
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - Directory holding the key files
+    ///
+    /// # Returns
+    ///
+    /// A new SigningKeyStore instance
+    pub fn new(directory: &Path) -> Self {
+        Self { directory: directory.to_path_buf() }
+    }
+
+    /// Create a key store in the home directory of the local user (~/.implexa/keys)
+    ///
+    /// # Returns
+    ///
+    /// A new SigningKeyStore instance, or None if the home directory is unknown
+    pub fn in_home_directory() -> Option<Self> {
+        std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .map(|home| Self::new(&Path::new(&home).join(".implexa").join("keys")))
+    }
+
+    /// Load the key of a user, creating it if it does not exist
+    ///
+    /// New key files are only readable by their owner, and existing key files that other users
+    /// can access are refused.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - Username of the key owner
+    ///
+    /// # Returns
+    ///
+    /// The signing key of the user
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the key could not be read, generated or written, or if other
+    /// users can access the key file
+    pub fn load_or_create(&self, username: &str) -> DatabaseResult<SigningKey> {
+        if username.is_empty() || username.starts_with('.') || username.contains(['/', '\\']) {
+            return Err(DatabaseError::ValidationError(format!("Invalid username for a signing key: {}", username)));
+        }
+        let path = self.directory.join(format!("{}.ed25519", username));
+        if path.exists() {
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                if fs::metadata(&path)?.permissions().mode() & 0o077 != 0 {
+                    return Err(DatabaseError::ValidationError(format!(
+                        "Signing key {} is accessible by other users; restrict it to its owner (chmod 600)", path.display()
+                    )));
+                }
+            }
+            let secret = decode_hex(fs::read_to_string(&path)?.trim())
+                .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+                .ok_or_else(|| DatabaseError::ValidationError(format!("Signing key {} is corrupt", path.display())))?;
+            return Ok(SigningKey::from_bytes(&secret));
+        }
+
+        let mut secret = [0u8; 32];
+        getrandom::getrandom(&mut secret)
+            .map_err(|e| DatabaseError::ValidationError(format!("Failed to generate a signing key: {}", e)))?;
+        fs::create_dir_all(&self.directory)?;
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options.open(&path)?.write_all(encode_hex(&secret).as_bytes())?;
+        Ok(SigningKey::from_bytes(&secret))
+    }
+}
+
+/// Manager for electronic signature operations
+pub struct SignatureManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+}
+
+impl<'a> SignatureManager<'a> {
+    /// Create a new SignatureManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new SignatureManager instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Compute the digest of the content of a revision within an existing transaction
+    ///
+    /// The digest covers the commit, the part and revision identifiers, the version and the
+    /// properties of the revision. Status and dates are left out, as they change on release.
+    /// Part properties, the name and description of the part and its BOM are shared by all
+    /// revisions of the part and are left out too, so that editing them for a new revision does
+    /// not invalidate the signatures of earlier revisions.
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    /// * `commit_hash` - The commit the content was taken from
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The hex-encoded SHA-256 digest
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the revision content could not be retrieved
+    pub fn revision_digest_in_transaction(&self, revision_id: i64, commit_hash: Option<&str>, tx: &Transaction) -> DatabaseResult<String> {
+        let revision = RevisionManager::new(self.connection_manager).get_revision_in_transaction(revision_id, tx)?;
+
+        let mut stmt = tx.prepare(
+            "SELECT key, value, type FROM Properties
+             WHERE revision_id = ?1
+             ORDER BY key",
+        )?;
+        let properties = stmt
+            .query_map(params![revision_id], |row| {
+                Ok(serde_json::json!({
+                    "key": row.get::<_, String>(0)?,
+                    "value": row.get::<_, Option<String>>(1)?,
+                    "type": row.get::<_, String>(2)?,
+                }))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let content = serde_json::json!({
+            "commit": commit_hash,
+            "part": {
+                "part_id": revision.part_id,
+            },
+            "revision": {
+                "revision_id": revision_id,
+                "version": revision.version,
+            },
+            "properties": properties,
+        });
+        Ok(format!("{:x}", Sha256::digest(content.to_string().as_bytes())))
+    }
+
+    /// Sign the current content of a revision within an existing transaction
+    ///
+    /// The signed commit is the commit recorded on the revision when it was submitted for review.
+    /// A release signature also covers the commit recorded on the revision as released, so the
+    /// revision must be released before it is signed. The key of the signer is registered on
+    /// their first signature, and signing with any other key is refused.
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    /// * `approval_id` - The ID of the approval the signature belongs to, if any
+    /// * `signer` - The signing user
+    /// * `meaning` - The meaning of the signature
+    /// * `key` - The signing key of the user
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The ID of the new signature
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the key is not the registered key of the signer, or if the
+    /// revision content could not be retrieved or the signature stored
+    pub fn sign_in_transaction(
+        &self,
+        revision_id: i64,
+        approval_id: Option<i64>,
+        signer: &User,
+        meaning: SignatureMeaning,
+        key: &SigningKey,
+        tx: &Transaction,
+    ) -> DatabaseResult<i64> {
+        self.register_key_in_transaction(&signer.username, &key.verifying_key(), tx)?;
+        let revision = RevisionManager::new(self.connection_manager).get_revision_in_transaction(revision_id, tx)?;
+        let content_digest = self.revision_digest_in_transaction(revision_id, revision.approved_commit_hash.as_deref(), tx)?;
+
+        // Signatures are made to the second, as they are stored
+        let timestamp = UNIX_EPOCH + Duration::from_secs(SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs());
+        let mut signature = ElectronicSignature {
+            signature_id: None,
+            revision_id,
+            approval_id,
+            signer: signer.username.clone(),
+            signer_email: signer.email.clone(),
+            meaning,
+            timestamp,
+            commit_hash: revision.approved_commit_hash,
+            released_commit_hash: revision.commit_hash.filter(|_| meaning == SignatureMeaning::Release),
+            content_digest,
+            public_key: encode_hex(key.verifying_key().as_bytes()),
+            signature: String::new(),
+        };
+        signature.signature = encode_hex(&key.sign(signature.payload().as_bytes()).to_bytes());
+
+        tx.execute(
+            "INSERT INTO Signatures (revision_id, approval_id, signer, signer_email, meaning, timestamp, commit_hash, released_commit_hash, content_digest, public_key, signature)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                signature.revision_id,
+                signature.approval_id,
+                signature.signer,
+                signature.signer_email,
+                signature.meaning.to_str(),
+                signature.timestamp.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64,
+                signature.commit_hash,
+                signature.released_commit_hash,
+                signature.content_digest,
+                signature.public_key,
+                signature.signature,
+            ],
+        )?;
+        Ok(tx.last_insert_rowid())
+    }
+
+    /// Register the public key of a user within an existing transaction
+    ///
+    /// The first key a user signs with is registered for them; a user cannot sign with any
+    /// other key afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - Username of the key owner
+    /// * `key` - The public key of the user
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if another key is registered for the user or the key could not be
+    /// stored
+    pub fn register_key_in_transaction(&self, username: &str, key: &VerifyingKey, tx: &Transaction) -> DatabaseResult<()> {
+        let public_key = encode_hex(key.as_bytes());
+        match self.get_registered_key_in_transaction(username, tx)? {
+            Some(registered_key) if registered_key.eq_ignore_ascii_case(&public_key) => Ok(()),
+            Some(_) => Err(DatabaseError::ValidationError(format!(
+                "The signing key of {} is not the key registered for the user", username
+            ))),
+            None => {
+                let registered_date = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64;
+                tx.execute(
+                    "INSERT INTO SigningKeys (username, public_key, registered_date) VALUES (?1, ?2, ?3)",
+                    params![username, public_key, registered_date],
+                )?;
+                Ok(())
+            }
+        }
+    }
+
+    /// Get the public key registered for a user within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - Username of the key owner
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The hex-encoded public key, or None if the user has not signed anything yet
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the key could not be retrieved
+    pub fn get_registered_key_in_transaction(&self, username: &str, tx: &Transaction) -> DatabaseResult<Option<String>> {
+        match tx.query_row("SELECT public_key FROM SigningKeys WHERE username = ?1", params![username], |row| row.get(0)) {
+            Ok(public_key) => Ok(Some(public_key)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Get the signatures on a revision
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    ///
+    /// # Returns
+    ///
+    /// The signatures, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the signatures could not be retrieved
+    pub fn get_signatures(&self, revision_id: i64) -> DatabaseResult<Vec<ElectronicSignature>> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.get_signatures_in_transaction(revision_id, tx)
+        })
+    }
+
+    /// Get the signatures on a revision within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The signatures, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the signatures could not be retrieved
+    pub fn get_signatures_in_transaction(&self, revision_id: i64, tx: &Transaction) -> DatabaseResult<Vec<ElectronicSignature>> {
+        let mut stmt = tx.prepare(
+            "SELECT signature_id, revision_id, approval_id, signer, signer_email, meaning, timestamp, commit_hash, released_commit_hash, content_digest, public_key, signature
+             FROM Signatures WHERE revision_id = ?1 ORDER BY signature_id",
+        )?;
+        let signatures = stmt
+            .query_map(params![revision_id], |row| self.row_to_signature(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(signatures)
+    }
+
+    /// Verify the signatures on a revision
+    ///
+    /// Every signature is checked against the registered key of its signer and against the
+    /// current revision metadata, and a release signature against the commit the revision is
+    /// recorded as released with. Once the revision has a release signature, the approvals must
+    /// cover exactly the released content.
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    ///
+    /// # Returns
+    ///
+    /// The verification result of every signature, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the signatures or the revision content could not be retrieved
+    pub fn verify(&self, revision_id: i64) -> DatabaseResult<Vec<SignatureVerification>> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let revision = RevisionManager::new(self.connection_manager).get_revision_in_transaction(revision_id, tx)?;
+            let signatures = self.get_signatures_in_transaction(revision_id, tx)?;
+            let release_digest = signatures.iter()
+                .rev()
+                .find(|signature| signature.meaning == SignatureMeaning::Release)
+                .map(|signature| signature.content_digest.clone());
+
+            let mut verifications = Vec::new();
+            for signature in signatures {
+                let current_digest = self.revision_digest_in_transaction(revision_id, signature.commit_hash.as_deref(), tx)?;
+                let registered_key = self.get_registered_key_in_transaction(&signature.signer, tx)?;
+                let covers_release = match (&release_digest, signature.meaning) {
+                    (Some(release_digest), SignatureMeaning::Approve) => Some(&signature.content_digest == release_digest),
+                    _ => None,
+                };
+                let released_commit_matches = signature.meaning != SignatureMeaning::Release
+                    || signature.released_commit_hash == revision.commit_hash;
+                verifications.push(SignatureVerification {
+                    authentic: signature.is_authentic(registered_key.as_deref()),
+                    content_matches: signature.content_digest == current_digest && released_commit_matches,
+                    covers_release,
+                    signature,
+                });
+            }
+            Ok(verifications)
+        })
+    }
+
+    /// Convert a database row to an ElectronicSignature
+    fn row_to_signature(&self, row: &Row) -> SqliteResult<ElectronicSignature> {
+        let meaning_str: String = row.get(5)?;
+        let meaning = SignatureMeaning::from_str(&meaning_str)
+            .ok_or_else(|| rusqlite::Error::InvalidColumnType(5, "meaning".to_string(), Type::Text))?;
+
+        Ok(ElectronicSignature {
+            signature_id: Some(row.get(0)?),
+            revision_id: row.get(1)?,
+            approval_id: row.get(2)?,
+            signer: row.get(3)?,
+            signer_email: row.get(4)?,
+            meaning,
+            timestamp: UNIX_EPOCH + Duration::from_secs(row.get::<_, i64>(6)?.max(0) as u64),
+            commit_hash: row.get(7)?,
+            released_commit_hash: row.get(8)?,
+            content_digest: row.get(9)?,
+            public_key: row.get(10)?,
+            signature: row.get(11)?,
+        })
+    }
+}
+
+/// Encode bytes as lowercase hex
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Decode lowercase or uppercase hex into bytes
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| hex.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::DatabaseManager;
+    use crate::database::part::{Part, PartManager};
+    use crate::database::property::{Property, PropertyManager, PropertyType};
+    use crate::database::revision::{Revision, RevisionStatus};
+    use crate::database::user::UserRole;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_signatures_cover_released_content() {
+        // Create a temporary database and key store
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let connection_manager = db_manager.connection_manager();
+        let key_store = SigningKeyStore::new(&temp_dir.path().join("keys"));
+
+        let part_manager = PartManager::new(connection_manager);
+        let mut part = Part::new(100001, "Electronic".to_string(), "Resistor".to_string(), "10K Resistor".to_string(), None);
+        part_manager.create_part(&part).unwrap();
+        let revision_manager = RevisionManager::new(connection_manager);
+        let revision_id = revision_manager.create_revision(&Revision::new(
+            100001,
+            "1".to_string(),
+            RevisionStatus::InReview,
+            "alice".to_string(),
+            None,
+        )).unwrap();
+        connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            revision_manager.update_approved_commit_hash_in_transaction(revision_id, "0123456789abcdef", tx)
+        }).unwrap();
+
+        // Properties without a value are signed as well
+        let property_manager = PropertyManager::new(connection_manager);
+        property_manager.create_property(&Property::new_revision_property(revision_id, "Value".to_string(), None, PropertyType::String)).unwrap();
+
+        // Keys are created on first use and reused afterwards
+        let key = key_store.load_or_create("alice").unwrap();
+        assert_eq!(key_store.load_or_create("alice").unwrap().to_bytes(), key.to_bytes());
+        assert!(key_store.load_or_create("../alice").is_err());
+
+        // Key files are private to their owner, and keys others can read are refused
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let key_path = temp_dir.path().join("keys").join("alice.ed25519");
+            assert_eq!(fs::metadata(&key_path).unwrap().permissions().mode() & 0o777, 0o600);
+            fs::set_permissions(&key_path, fs::Permissions::from_mode(0o644)).unwrap();
+            assert!(key_store.load_or_create("alice").is_err());
+            fs::set_permissions(&key_path, fs::Permissions::from_mode(0o600)).unwrap();
+        }
+
+        let signature_manager = SignatureManager::new(connection_manager);
+        let alice = User::new("alice".to_string(), UserRole::Designer);
+        let bob = User::new("bob".to_string(), UserRole::Admin);
+        connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            signature_manager.sign_in_transaction(revision_id, None, &alice, SignatureMeaning::Approve, &key, tx)?;
+            revision_manager.update_commit_hash_in_transaction(revision_id, "fedcba9876543210", tx)?;
+            signature_manager.sign_in_transaction(revision_id, None, &bob, SignatureMeaning::Release, &key_store.load_or_create("bob")?, tx)?;
+            Ok(())
+        }).unwrap();
+
+        let verifications = signature_manager.verify(revision_id).unwrap();
+        assert_eq!(verifications.len(), 2);
+        assert!(verifications.iter().all(|verification| verification.is_valid()));
+        assert_eq!(verifications[0].covers_release, Some(true));
+        assert_eq!(verifications[0].signature.commit_hash.as_deref(), Some("0123456789abcdef"));
+        assert_eq!(verifications[1].signature.commit_hash.as_deref(), Some("0123456789abcdef"));
+        assert_eq!(verifications[1].signature.released_commit_hash.as_deref(), Some("fedcba9876543210"));
+
+        // A user can only sign with the key registered for them
+        let result = connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            signature_manager.sign_in_transaction(revision_id, None, &alice, SignatureMeaning::Review, &key_store.load_or_create("bob")?, tx)
+        });
+        assert!(matches!(result, Err(DatabaseError::ValidationError(_))));
+
+        // Recording another released commit invalidates the release signature
+        connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            conn.execute("UPDATE Revisions SET commit_hash = '1111111111111111' WHERE revision_id = ?1", params![revision_id])?;
+            Ok(())
+        }).unwrap();
+        let verifications = signature_manager.verify(revision_id).unwrap();
+        assert!(verifications[0].content_matches);
+        assert!(verifications[1].authentic && !verifications[1].content_matches);
+        connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            conn.execute("UPDATE Revisions SET commit_hash = 'fedcba9876543210' WHERE revision_id = ?1", params![revision_id])?;
+            Ok(())
+        }).unwrap();
+
+        // Editing the part shared by all revisions leaves the signatures valid
+        part.name = "10K Resistor 1%".to_string();
+        part_manager.update_part(&part).unwrap();
+        property_manager.create_property(&Property::new_part_property(100001, "Tolerance".to_string(), None, PropertyType::String)).unwrap();
+        let verifications = signature_manager.verify(revision_id).unwrap();
+        assert!(verifications.iter().all(|verification| verification.is_valid()));
+
+        // Changing the metadata of the revision after signing invalidates the signatures
+        property_manager.create_property(&Property::new_revision_property(revision_id, "Power".to_string(), None, PropertyType::String)).unwrap();
+        let verifications = signature_manager.verify(revision_id).unwrap();
+        assert!(verifications.iter().all(|verification| verification.authentic && !verification.content_matches));
+
+        // A signature re-made with a key that is not registered for the signer is not authentic
+        let mut forged = verifications[0].signature.clone();
+        let mallory_key = key_store.load_or_create("mallory").unwrap();
+        forged.public_key = encode_hex(mallory_key.verifying_key().as_bytes());
+        forged.signature = encode_hex(&mallory_key.sign(forged.payload().as_bytes()).to_bytes());
+        connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            conn.execute(
+                "UPDATE Signatures SET public_key = ?1, signature = ?2 WHERE signature_id = ?3",
+                params![forged.public_key, forged.signature, forged.signature_id],
+            )?;
+            Ok(())
+        }).unwrap();
+        let verifications = signature_manager.verify(revision_id).unwrap();
+        assert!(!verifications[0].authentic);
+        assert!(verifications[1].authentic);
+
+        // Tampering with a stored signature is detected
+        connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            conn.execute("UPDATE Signatures SET signer = 'mallory' WHERE meaning = 'Release'", [])?;
+            Ok(())
+        }).unwrap();
+        let verifications = signature_manager.verify(revision_id).unwrap();
+        assert!(!verifications[1].authentic);
+    }
+}
@@ -0,0 +1,980 @@
+//! Supplier module for Implexa
+//!
+//! This module provides functionality for managing suppliers, supplier offers and
+//! quantity price breaks for manufacturer parts in the database, including importing
+//! offers from distributor CSV exports.
+
+use rusqlite::{Transaction, params, Row, Result as SqliteResult, OptionalExtension};
+use std::io::Read;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::database::schema::{DatabaseError, DatabaseResult};
+use crate::database::connection_manager::ConnectionManager;
+
+/// Represents a supplier (distributor or direct manufacturer sales channel)
+#[derive(Debug, Clone)]
+pub struct Supplier {
+    /// Unique identifier for the supplier
+    pub supplier_id: Option<i64>,
+    /// Name of the supplier
+    pub name: String,
+    /// Website of the supplier
+    pub website: Option<String>,
+}
+
+impl Supplier {
+    /// Create a new supplier
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the supplier
+    /// * `website` - Website of the supplier
+    ///
+    /// # Returns
+    ///
+    /// A new Supplier instance
+    pub fn new(name: String, website: Option<String>) -> Self {
+        Self {
+            supplier_id: None,
+            name,
+            website,
+        }
+    }
+}
+
+/// A quantity price break of a supplier offer
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceBreak {
+    /// Minimum order quantity for this price
+    pub quantity: i64,
+    /// Unit price at this quantity
+    pub unit_price: f64,
+}
+
+impl PriceBreak {
+    /// Create a new price break
+    ///
+    /// # Arguments
+    ///
+    /// * `quantity` - Minimum order quantity for this price
+    /// * `unit_price` - Unit price at this quantity
+    ///
+    /// # Returns
+    ///
+    /// A new PriceBreak instance
+    pub fn new(quantity: i64, unit_price: f64) -> Self {
+        Self { quantity, unit_price }
+    }
+}
+
+/// Price quote for a given order quantity
+#[derive(Debug, Clone, PartialEq)]
+pub struct OfferQuote {
+    /// Quantity that has to be ordered, after applying MOQ and price break minimums
+    pub order_quantity: i64,
+    /// Unit price at the order quantity
+    pub unit_price: f64,
+    /// Total price of the order
+    pub extended_price: f64,
+}
+
+/// Represents an offer of a manufacturer part by a supplier
+#[derive(Debug, Clone)]
+pub struct SupplierOffer {
+    /// Unique identifier for the offer
+    pub offer_id: Option<i64>,
+    /// ID of the manufacturer part being offered
+    pub mpn_id: i64,
+    /// ID of the supplier making the offer
+    pub supplier_id: i64,
+    /// Supplier stock keeping unit
+    pub sku: String,
+    /// Minimum order quantity
+    pub moq: i64,
+    /// ISO 4217 currency code of the prices
+    pub currency: String,
+    /// Lead time in days
+    pub lead_time_days: Option<i64>,
+    /// Quantity price breaks, ordered by ascending quantity
+    pub price_breaks: Vec<PriceBreak>,
+    /// Date the offer was last updated
+    pub updated_date: SystemTime,
+}
+
+impl SupplierOffer {
+    /// Create a new supplier offer
+    ///
+    /// # Arguments
+    ///
+    /// * `mpn_id` - ID of the manufacturer part being offered
+    /// * `supplier_id` - ID of the supplier making the offer
+    /// * `sku` - Supplier stock keeping unit
+    /// * `moq` - Minimum order quantity
+    /// * `currency` - ISO 4217 currency code of the prices
+    /// * `lead_time_days` - Lead time in days
+    /// * `price_breaks` - Quantity price breaks
+    ///
+    /// # Returns
+    ///
+    /// A new SupplierOffer instance
+    pub fn new(
+        mpn_id: i64,
+        supplier_id: i64,
+        sku: String,
+        moq: i64,
+        currency: String,
+        lead_time_days: Option<i64>,
+        mut price_breaks: Vec<PriceBreak>,
+    ) -> Self {
+        price_breaks.sort_by_key(|price_break| price_break.quantity);
+        Self {
+            offer_id: None,
+            mpn_id,
+            supplier_id,
+            sku,
+            moq,
+            currency,
+            lead_time_days,
+            price_breaks,
+            updated_date: SystemTime::now(),
+        }
+    }
+
+    /// Quote the offer for a required quantity
+    ///
+    /// The order quantity is raised to the MOQ and to the smallest price break if needed,
+    /// and priced at the largest price break not exceeding the order quantity.
+    ///
+    /// # Arguments
+    ///
+    /// * `quantity` - The required quantity
+    ///
+    /// # Returns
+    ///
+    /// The quote, or None if the offer has no price breaks
+    pub fn quote(&self, quantity: i64) -> Option<OfferQuote> {
+        let smallest_break = self.price_breaks.iter().map(|price_break| price_break.quantity).min()?;
+        let order_quantity = quantity.max(self.moq).max(smallest_break);
+        let unit_price = self.price_breaks
+            .iter()
+            .filter(|price_break| price_break.quantity <= order_quantity)
+            .max_by_key(|price_break| price_break.quantity)?
+            .unit_price;
+
+        Some(OfferQuote {
+            order_quantity,
+            unit_price,
+            extended_price: unit_price * order_quantity as f64,
+        })
+    }
+}
+
+/// Column mapping for importing offers from a distributor CSV export
+///
+/// Column names are matched case-insensitively against the CSV header. Price break
+/// columns that are not present in the header are ignored.
+#[derive(Debug, Clone)]
+pub struct OfferCsvMapping {
+    /// Supplier to use for every row, for single-distributor exports without a supplier column
+    pub supplier_name: Option<String>,
+    /// Column holding the supplier name
+    pub supplier_column: String,
+    /// Column holding the manufacturer name
+    pub manufacturer_column: String,
+    /// Column holding the manufacturer part number
+    pub mpn_column: String,
+    /// Column holding the supplier SKU
+    pub sku_column: String,
+    /// Column holding the minimum order quantity
+    pub moq_column: String,
+    /// Column holding the currency code
+    pub currency_column: String,
+    /// Column holding the lead time in days
+    pub lead_time_column: String,
+    /// Currency to use when the export has no currency column
+    pub default_currency: String,
+    /// (quantity column, unit price column) pairs holding the price breaks
+    pub price_break_columns: Vec<(String, String)>,
+}
+
+impl Default for OfferCsvMapping {
+    fn default() -> Self {
+        Self {
+            supplier_name: None,
+            supplier_column: "Supplier".to_string(),
+            manufacturer_column: "Manufacturer".to_string(),
+            mpn_column: "Manufacturer Part Number".to_string(),
+            sku_column: "Supplier Part Number".to_string(),
+            moq_column: "Minimum Order Quantity".to_string(),
+            currency_column: "Currency".to_string(),
+            lead_time_column: "Lead Time (Days)".to_string(),
+            default_currency: "USD".to_string(),
+            price_break_columns: (1..=10)
+                .map(|n| (format!("Break Quantity {}", n), format!("Unit Price {}", n)))
+                .collect(),
+        }
+    }
+}
+
+/// Result of importing offers from a CSV export
+#[derive(Debug, Clone, Default)]
+pub struct OfferImportResult {
+    /// Number of offers created
+    pub created: usize,
+    /// Number of existing offers updated
+    pub updated: usize,
+    /// Rows that were skipped, as (line number, reason) pairs
+    pub skipped: Vec<(usize, String)>,
+}
+
+/// Manager for supplier and offer operations
+pub struct SupplierManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+}
+
+impl<'a> SupplierManager<'a> {
+    /// Create a new SupplierManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new SupplierManager instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Create a new supplier
+    ///
+    /// # Arguments
+    ///
+    /// * `supplier` - The supplier to create
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created supplier
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the supplier could not be created
+    pub fn create_supplier(&self, supplier: &Supplier) -> DatabaseResult<i64> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.create_supplier_in_transaction(supplier, tx)
+        })
+    }
+
+    /// Create a new supplier within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `supplier` - The supplier to create
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created supplier
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the supplier could not be created
+    pub fn create_supplier_in_transaction(&self, supplier: &Supplier, tx: &Transaction) -> DatabaseResult<i64> {
+        tx.execute(
+            "INSERT INTO Suppliers (name, website) VALUES (?1, ?2)",
+            params![supplier.name, supplier.website],
+        )?;
+        Ok(tx.last_insert_rowid())
+    }
+
+    /// Get a supplier by ID
+    ///
+    /// # Arguments
+    ///
+    /// * `supplier_id` - The ID of the supplier
+    ///
+    /// # Returns
+    ///
+    /// The supplier
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the supplier could not be retrieved
+    pub fn get_supplier(&self, supplier_id: i64) -> DatabaseResult<Supplier> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let supplier = conn.query_row(
+                "SELECT supplier_id, name, website FROM Suppliers WHERE supplier_id = ?1",
+                params![supplier_id],
+                |row| self.row_to_supplier(row),
+            )?;
+            Ok::<Supplier, DatabaseError>(supplier)
+        })
+    }
+
+    /// Get a supplier by name
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the supplier
+    ///
+    /// # Returns
+    ///
+    /// The supplier, or None if no supplier has that name
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the supplier could not be retrieved
+    pub fn get_supplier_by_name(&self, name: &str) -> DatabaseResult<Option<Supplier>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let supplier = conn.query_row(
+                "SELECT supplier_id, name, website FROM Suppliers WHERE name = ?1",
+                params![name],
+                |row| self.row_to_supplier(row),
+            ).optional()?;
+            Ok::<Option<Supplier>, DatabaseError>(supplier)
+        })
+    }
+
+    /// Get all suppliers
+    ///
+    /// # Returns
+    ///
+    /// A vector of all suppliers ordered by name
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the suppliers could not be retrieved
+    pub fn get_all_suppliers(&self) -> DatabaseResult<Vec<Supplier>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT supplier_id, name, website FROM Suppliers ORDER BY name",
+            )?;
+            let suppliers_iter = stmt.query_map([], |row| self.row_to_supplier(row))?;
+            let mut suppliers = Vec::new();
+            for supplier_result in suppliers_iter {
+                suppliers.push(supplier_result?);
+            }
+            Ok::<Vec<Supplier>, DatabaseError>(suppliers)
+        })
+    }
+
+    /// Delete a supplier and all of its offers
+    ///
+    /// # Arguments
+    ///
+    /// * `supplier_id` - The ID of the supplier to delete
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the supplier was successfully deleted
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the supplier could not be deleted
+    pub fn delete_supplier(&self, supplier_id: i64) -> DatabaseResult<()> {
+        self.connection_manager.execute_mut::<_, _, DatabaseError>(|conn| {
+            conn.execute("DELETE FROM Suppliers WHERE supplier_id = ?1", params![supplier_id])?;
+            Ok::<(), DatabaseError>(())
+        })
+    }
+
+    /// Create a new supplier offer with its price breaks
+    ///
+    /// # Arguments
+    ///
+    /// * `offer` - The offer to create
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created offer
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the offer is invalid or could not be created
+    pub fn create_offer(&self, offer: &SupplierOffer) -> DatabaseResult<i64> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.create_offer_in_transaction(offer, tx)
+        })
+    }
+
+    /// Create a new supplier offer with its price breaks within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `offer` - The offer to create
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created offer
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the offer is invalid or could not be created
+    pub fn create_offer_in_transaction(&self, offer: &SupplierOffer, tx: &Transaction) -> DatabaseResult<i64> {
+        Self::validate_offer(offer)?;
+
+        tx.execute(
+            "INSERT INTO SupplierOffers (mpn_id, supplier_id, sku, moq, currency, lead_time_days, updated_date)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                offer.mpn_id,
+                offer.supplier_id,
+                offer.sku,
+                offer.moq,
+                offer.currency,
+                offer.lead_time_days,
+                offer.updated_date.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64,
+            ],
+        )?;
+        let offer_id = tx.last_insert_rowid();
+
+        Self::insert_price_breaks(offer_id, &offer.price_breaks, tx)?;
+        Ok(offer_id)
+    }
+
+    /// Get a supplier offer by ID
+    ///
+    /// # Arguments
+    ///
+    /// * `offer_id` - The ID of the offer
+    ///
+    /// # Returns
+    ///
+    /// The offer including its price breaks
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the offer could not be retrieved
+    pub fn get_offer(&self, offer_id: i64) -> DatabaseResult<SupplierOffer> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut offer = conn.query_row(
+                "SELECT offer_id, mpn_id, supplier_id, sku, moq, currency, lead_time_days, updated_date
+                 FROM SupplierOffers WHERE offer_id = ?1",
+                params![offer_id],
+                |row| self.row_to_offer(row),
+            )?;
+            offer.price_breaks = Self::get_price_breaks(conn, offer_id)?;
+            Ok::<SupplierOffer, DatabaseError>(offer)
+        })
+    }
+
+    /// Get all offers for a manufacturer part
+    ///
+    /// # Arguments
+    ///
+    /// * `mpn_id` - The ID of the manufacturer part
+    ///
+    /// # Returns
+    ///
+    /// A vector of offers including their price breaks
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the offers could not be retrieved
+    pub fn get_offers_for_manufacturer_part(&self, mpn_id: i64) -> DatabaseResult<Vec<SupplierOffer>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT offer_id, mpn_id, supplier_id, sku, moq, currency, lead_time_days, updated_date
+                 FROM SupplierOffers WHERE mpn_id = ?1 ORDER BY offer_id",
+            )?;
+            let offers_iter = stmt.query_map(params![mpn_id], |row| self.row_to_offer(row))?;
+            let mut offers = Vec::new();
+            for offer_result in offers_iter {
+                let mut offer = offer_result?;
+                offer.price_breaks = Self::get_price_breaks(conn, offer.offer_id.unwrap_or_default())?;
+                offers.push(offer);
+            }
+            Ok::<Vec<SupplierOffer>, DatabaseError>(offers)
+        })
+    }
+
+    /// Get all offers for every manufacturer part of an internal part
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the internal part
+    ///
+    /// # Returns
+    ///
+    /// A vector of offers including their price breaks
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the offers could not be retrieved
+    pub fn get_offers_for_part(&self, part_id: i64) -> DatabaseResult<Vec<SupplierOffer>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT o.offer_id, o.mpn_id, o.supplier_id, o.sku, o.moq, o.currency, o.lead_time_days, o.updated_date
+                 FROM SupplierOffers o
+                 JOIN ManufacturerParts m ON o.mpn_id = m.mpn_id
+                 WHERE m.part_id = ?1
+                 ORDER BY o.offer_id",
+            )?;
+            let offers_iter = stmt.query_map(params![part_id], |row| self.row_to_offer(row))?;
+            let mut offers = Vec::new();
+            for offer_result in offers_iter {
+                let mut offer = offer_result?;
+                offer.price_breaks = Self::get_price_breaks(conn, offer.offer_id.unwrap_or_default())?;
+                offers.push(offer);
+            }
+            Ok::<Vec<SupplierOffer>, DatabaseError>(offers)
+        })
+    }
+
+    /// Update a supplier offer and replace its price breaks
+    ///
+    /// # Arguments
+    ///
+    /// * `offer` - The offer to update
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the offer was successfully updated
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the offer is invalid or could not be updated
+    pub fn update_offer(&self, offer: &SupplierOffer) -> DatabaseResult<()> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.update_offer_in_transaction(offer, tx)
+        })
+    }
+
+    /// Update a supplier offer and replace its price breaks within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `offer` - The offer to update
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the offer was successfully updated
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the offer is invalid or could not be updated
+    pub fn update_offer_in_transaction(&self, offer: &SupplierOffer, tx: &Transaction) -> DatabaseResult<()> {
+        let offer_id = offer.offer_id.ok_or_else(|| {
+            DatabaseError::InitializationError("Offer ID is required for update".to_string())
+        })?;
+        Self::validate_offer(offer)?;
+
+        tx.execute(
+            "UPDATE SupplierOffers
+             SET mpn_id = ?2, supplier_id = ?3, sku = ?4, moq = ?5, currency = ?6, lead_time_days = ?7, updated_date = ?8
+             WHERE offer_id = ?1",
+            params![
+                offer_id,
+                offer.mpn_id,
+                offer.supplier_id,
+                offer.sku,
+                offer.moq,
+                offer.currency,
+                offer.lead_time_days,
+                offer.updated_date.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64,
+            ],
+        )?;
+
+        tx.execute("DELETE FROM PriceBreaks WHERE offer_id = ?1", params![offer_id])?;
+        Self::insert_price_breaks(offer_id, &offer.price_breaks, tx)?;
+        Ok(())
+    }
+
+    /// Delete a supplier offer
+    ///
+    /// # Arguments
+    ///
+    /// * `offer_id` - The ID of the offer to delete
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the offer was successfully deleted
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the offer could not be deleted
+    pub fn delete_offer(&self, offer_id: i64) -> DatabaseResult<()> {
+        self.connection_manager.execute_mut::<_, _, DatabaseError>(|conn| {
+            conn.execute("DELETE FROM SupplierOffers WHERE offer_id = ?1", params![offer_id])?;
+            Ok::<(), DatabaseError>(())
+        })
+    }
+
+    /// Import supplier offers from a distributor CSV export
+    ///
+    /// Each row is matched to an existing manufacturer part by manufacturer and MPN.
+    /// Suppliers are created on first use, and an existing offer with the same supplier
+    /// and SKU is updated in place. Rows that cannot be matched or parsed are skipped
+    /// and reported; the remaining rows are imported in a single transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Reader over the CSV content, including the header row
+    /// * `mapping` - Column mapping of the export
+    ///
+    /// # Returns
+    ///
+    /// A summary of the import
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the CSV is malformed or the offers could not be saved
+    pub fn import_offers_csv<R: Read>(&self, reader: R, mapping: &OfferCsvMapping) -> DatabaseResult<OfferImportResult> {
+        let mut csv_reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(reader);
+        let headers = csv_reader.headers()?.clone();
+        let column = |name: &str| headers.iter().position(|header| header.eq_ignore_ascii_case(name));
+
+        let manufacturer_index = column(&mapping.manufacturer_column).ok_or_else(|| {
+            DatabaseError::ValidationError(format!("CSV is missing the '{}' column", mapping.manufacturer_column))
+        })?;
+        let mpn_index = column(&mapping.mpn_column).ok_or_else(|| {
+            DatabaseError::ValidationError(format!("CSV is missing the '{}' column", mapping.mpn_column))
+        })?;
+        let sku_index = column(&mapping.sku_column).ok_or_else(|| {
+            DatabaseError::ValidationError(format!("CSV is missing the '{}' column", mapping.sku_column))
+        })?;
+        let supplier_index = column(&mapping.supplier_column);
+        if supplier_index.is_none() && mapping.supplier_name.is_none() {
+            return Err(DatabaseError::ValidationError(format!(
+                "CSV is missing the '{}' column and no supplier name was given", mapping.supplier_column
+            )));
+        }
+        let moq_index = column(&mapping.moq_column);
+        let currency_index = column(&mapping.currency_column);
+        let lead_time_index = column(&mapping.lead_time_column);
+        let price_break_indexes: Vec<(usize, usize)> = mapping.price_break_columns
+            .iter()
+            .filter_map(|(quantity_column, price_column)| Some((column(quantity_column)?, column(price_column)?)))
+            .collect();
+
+        let records = csv_reader.records().collect::<Result<Vec<_>, _>>()?;
+
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let mut result = OfferImportResult::default();
+
+            for (index, record) in records.iter().enumerate() {
+                // Line 1 is the header
+                let line = index + 2;
+                let field = |i: usize| record.get(i).unwrap_or("").trim();
+                let optional_field = |i: Option<usize>| i.map(field).filter(|value| !value.is_empty());
+
+                let supplier_name = match mapping.supplier_name.as_deref().or(optional_field(supplier_index)) {
+                    Some(name) => name,
+                    None => {
+                        result.skipped.push((line, "Missing supplier".to_string()));
+                        continue;
+                    }
+                };
+                let (manufacturer, mpn, sku) = (field(manufacturer_index), field(mpn_index), field(sku_index));
+                if mpn.is_empty() || sku.is_empty() {
+                    result.skipped.push((line, "Missing MPN or SKU".to_string()));
+                    continue;
+                }
+
+                let mpn_id: Option<i64> = tx.query_row(
+                    "SELECT mpn_id FROM ManufacturerParts WHERE manufacturer = ?1 COLLATE NOCASE AND mpn = ?2",
+                    params![manufacturer, mpn],
+                    |row| row.get(0),
+                ).optional()?;
+                let mpn_id = match mpn_id {
+                    Some(mpn_id) => mpn_id,
+                    None => {
+                        result.skipped.push((line, format!("Unknown manufacturer part {} {}", manufacturer, mpn)));
+                        continue;
+                    }
+                };
+
+                let mut price_breaks = Vec::new();
+                for (quantity_index, price_index) in &price_break_indexes {
+                    let (quantity, price) = (field(*quantity_index), field(*price_index));
+                    if quantity.is_empty() && price.is_empty() {
+                        continue;
+                    }
+                    match (parse_quantity(quantity), parse_price(price)) {
+                        (Some(quantity), Some(unit_price)) => price_breaks.push(PriceBreak::new(quantity, unit_price)),
+                        _ => {
+                            price_breaks.clear();
+                            break;
+                        }
+                    }
+                }
+                if price_breaks.is_empty() {
+                    result.skipped.push((line, "Missing or invalid price breaks".to_string()));
+                    continue;
+                }
+
+                let moq = optional_field(moq_index).and_then(parse_quantity)
+                    .unwrap_or_else(|| price_breaks.iter().map(|price_break| price_break.quantity).min().unwrap_or(1));
+                let currency = optional_field(currency_index)
+                    .map(str::to_uppercase)
+                    .unwrap_or_else(|| mapping.default_currency.clone());
+                let lead_time_days = optional_field(lead_time_index).and_then(parse_quantity);
+
+                let mut offer = SupplierOffer::new(
+                    mpn_id,
+                    0,
+                    sku.to_string(),
+                    moq,
+                    currency,
+                    lead_time_days,
+                    price_breaks,
+                );
+                // Check the row before creating its supplier so that a skipped row leaves nothing behind
+                if let Err(DatabaseError::ValidationError(reason)) = Self::validate_offer(&offer) {
+                    result.skipped.push((line, reason));
+                    continue;
+                }
+                let supplier_id = self.get_or_create_supplier_in_transaction(supplier_name, tx)?;
+                offer.supplier_id = supplier_id;
+
+                let existing_offer_id: Option<i64> = tx.query_row(
+                    "SELECT offer_id FROM SupplierOffers WHERE supplier_id = ?1 AND sku = ?2",
+                    params![supplier_id, sku],
+                    |row| row.get(0),
+                ).optional()?;
+
+                let saved = match existing_offer_id {
+                    Some(offer_id) => {
+                        offer.offer_id = Some(offer_id);
+                        self.update_offer_in_transaction(&offer, tx).map(|_| false)
+                    }
+                    None => self.create_offer_in_transaction(&offer, tx).map(|_| true),
+                };
+                match saved {
+                    Ok(true) => result.created += 1,
+                    Ok(false) => result.updated += 1,
+                    Err(DatabaseError::ValidationError(reason)) => result.skipped.push((line, reason)),
+                    Err(e) => return Err(e),
+                }
+            }
+
+            Ok(result)
+        })
+    }
+
+    /// Get the ID of a supplier by name, creating the supplier if it does not exist
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the supplier
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The ID of the supplier
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the supplier could not be retrieved or created
+    pub fn get_or_create_supplier_in_transaction(&self, name: &str, tx: &Transaction) -> DatabaseResult<i64> {
+        let supplier_id: Option<i64> = tx.query_row(
+            "SELECT supplier_id FROM Suppliers WHERE name = ?1",
+            params![name],
+            |row| row.get(0),
+        ).optional()?;
+
+        match supplier_id {
+            Some(supplier_id) => Ok(supplier_id),
+            None => self.create_supplier_in_transaction(&Supplier::new(name.to_string(), None), tx),
+        }
+    }
+
+    /// Check that an offer can be saved
+    fn validate_offer(offer: &SupplierOffer) -> DatabaseResult<()> {
+        if offer.moq < 1 {
+            return Err(DatabaseError::ValidationError(format!("MOQ of offer {} must be at least 1", offer.sku)));
+        }
+        if offer.price_breaks.iter().any(|price_break| price_break.quantity < 1 || price_break.unit_price < 0.0) {
+            return Err(DatabaseError::ValidationError(format!("Offer {} has an invalid price break", offer.sku)));
+        }
+        let mut quantities: Vec<i64> = offer.price_breaks.iter().map(|price_break| price_break.quantity).collect();
+        quantities.sort_unstable();
+        if let Some(quantity) = quantities.windows(2).find(|pair| pair[0] == pair[1]).map(|pair| pair[0]) {
+            return Err(DatabaseError::ValidationError(format!(
+                "Offer {} has more than one price break for quantity {}", offer.sku, quantity
+            )));
+        }
+        Ok(())
+    }
+
+    /// Insert the price breaks of an offer
+    fn insert_price_breaks(offer_id: i64, price_breaks: &[PriceBreak], tx: &Transaction) -> DatabaseResult<()> {
+        for price_break in price_breaks {
+            tx.execute(
+                "INSERT INTO PriceBreaks (offer_id, quantity, unit_price) VALUES (?1, ?2, ?3)",
+                params![offer_id, price_break.quantity, price_break.unit_price],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Get the price breaks of an offer ordered by ascending quantity
+    fn get_price_breaks(conn: &rusqlite::Connection, offer_id: i64) -> DatabaseResult<Vec<PriceBreak>> {
+        let mut stmt = conn.prepare(
+            "SELECT quantity, unit_price FROM PriceBreaks WHERE offer_id = ?1 ORDER BY quantity",
+        )?;
+        let price_breaks = stmt
+            .query_map(params![offer_id], |row| Ok(PriceBreak::new(row.get(0)?, row.get(1)?)))?
+            .collect::<SqliteResult<Vec<PriceBreak>>>()?;
+        Ok(price_breaks)
+    }
+
+    /// Convert a database row to a Supplier
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The database row
+    ///
+    /// # Returns
+    ///
+    /// A Supplier instance
+    ///
+    /// # Errors
+    ///
+    /// Returns a SqliteError if the row could not be converted
+    fn row_to_supplier(&self, row: &Row) -> SqliteResult<Supplier> {
+        Ok(Supplier {
+            supplier_id: Some(row.get(0)?),
+            name: row.get(1)?,
+            website: row.get(2)?,
+        })
+    }
+
+    /// Convert a database row to a SupplierOffer without price breaks
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The database row
+    ///
+    /// # Returns
+    ///
+    /// A SupplierOffer instance
+    ///
+    /// # Errors
+    ///
+    /// Returns a SqliteError if the row could not be converted
+    fn row_to_offer(&self, row: &Row) -> SqliteResult<SupplierOffer> {
+        let updated_secs: i64 = row.get(7)?;
+
+        Ok(SupplierOffer {
+            offer_id: Some(row.get(0)?),
+            mpn_id: row.get(1)?,
+            supplier_id: row.get(2)?,
+            sku: row.get(3)?,
+            moq: row.get(4)?,
+            currency: row.get(5)?,
+            lead_time_days: row.get(6)?,
+            price_breaks: Vec::new(),
+            updated_date: UNIX_EPOCH + std::time::Duration::from_secs(updated_secs as u64),
+        })
+    }
+}
+
+/// Parse a quantity from a CSV field, accepting thousands separators (e.g. "1,000")
+fn parse_quantity(value: &str) -> Option<i64> {
+    let digits: String = value.chars().filter(|c| !matches!(c, ',' | ' ' | '\'')).collect();
+    digits.parse().ok()
+}
+
+/// Parse a price from a CSV field, ignoring currency symbols and thousands separators (e.g. "$1,234.50")
+fn parse_price(value: &str) -> Option<f64> {
+    let number: String = value.chars().filter(|c| c.is_ascii_digit() || *c == '.').collect();
+    number.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::DatabaseManager;
+    use crate::database::part::{Part, PartManager};
+    use crate::database::manufacturer_part::{ManufacturerPart, ManufacturerPartManager, ManufacturerPartStatus};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_offer_quote() {
+        let offer = SupplierOffer::new(
+            1,
+            1,
+            "311-10.0KHRCT-ND".to_string(),
+            1,
+            "USD".to_string(),
+            Some(5),
+            vec![PriceBreak::new(100, 0.02), PriceBreak::new(1, 0.10), PriceBreak::new(10, 0.05)],
+        );
+
+        // Below the first break the smallest break applies
+        assert_eq!(offer.quote(5).unwrap().unit_price, 0.10);
+        // Exactly on a break
+        assert_eq!(offer.quote(10).unwrap().unit_price, 0.05);
+        // Above the largest break
+        let quote = offer.quote(250).unwrap();
+        assert_eq!(quote.order_quantity, 250);
+        assert_eq!(quote.unit_price, 0.02);
+        assert!((quote.extended_price - 5.0).abs() < 1e-9);
+
+        // MOQ raises the order quantity
+        let reel = SupplierOffer::new(1, 1, "311-10.0KHRTR-ND".to_string(), 5000, "USD".to_string(), None, vec![PriceBreak::new(5000, 0.004)]);
+        assert_eq!(reel.quote(10).unwrap().order_quantity, 5000);
+    }
+
+    #[test]
+    fn test_offer_csv_import() {
+        // Create a temporary directory for the test database
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+
+        // Create a new database manager and initialize the schema
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+
+        let part_manager = PartManager::new(db_manager.connection_manager());
+        let manufacturer_part_manager = ManufacturerPartManager::new(db_manager.connection_manager());
+        let supplier_manager = SupplierManager::new(db_manager.connection_manager());
+
+        // Create a part with one manufacturer part
+        let part = Part::new(10001, "Electronic".to_string(), "Resistor".to_string(), "10K Resistor".to_string(), None);
+        part_manager.create_part(&part).unwrap();
+        let mpn_id = manufacturer_part_manager.create_manufacturer_part(&ManufacturerPart::new(
+            10001,
+            "Yageo".to_string(),
+            "RC0603FR-0710KL".to_string(),
+            None,
+            ManufacturerPartStatus::Preferred,
+        )).unwrap();
+
+        let csv = "\
+Supplier,Manufacturer,Manufacturer Part Number,Supplier Part Number,Minimum Order Quantity,Lead Time (Days),Break Quantity 1,Unit Price 1,Break Quantity 2,Unit Price 2
+Digi-Key,YAGEO,RC0603FR-0710KL,311-10.0KHRCT-ND,1,0,1,$0.10,\"1,000\",$0.01
+Mouser,Yageo,RC0603FR-0710KL,603-RC0603FR-0710KL,10,14,10,0.08,,
+Mouser,Vishay,CRCW060310K0FKEA,71-CRCW060310K0FKEA,1,14,1,0.10,,
+Arrow,Yageo,RC0603FR-0710KL,RC0603FR-0710KL-ARW,1,7,100,0.02,100,0.015
+";
+
+        // Import the offers
+        let result = supplier_manager.import_offers_csv(csv.as_bytes(), &OfferCsvMapping::default()).unwrap();
+        assert_eq!(result.created, 2);
+        assert_eq!(result.updated, 0);
+        assert_eq!(result.skipped.len(), 2);
+        assert_eq!(result.skipped[0].0, 4);
+        // A repeated break quantity skips the row instead of failing the import
+        assert_eq!(result.skipped[1].0, 5);
+
+        let offers = supplier_manager.get_offers_for_manufacturer_part(mpn_id).unwrap();
+        assert_eq!(offers.len(), 2);
+        assert_eq!(offers[0].price_breaks, vec![PriceBreak::new(1, 0.10), PriceBreak::new(1000, 0.01)]);
+        assert_eq!(offers[1].moq, 10);
+        assert_eq!(offers[1].lead_time_days, Some(14));
+        assert_eq!(supplier_manager.get_all_suppliers().unwrap().len(), 2);
+
+        // Importing again updates the existing offers instead of duplicating them
+        let result = supplier_manager.import_offers_csv(csv.as_bytes(), &OfferCsvMapping::default()).unwrap();
+        assert_eq!(result.created, 0);
+        assert_eq!(result.updated, 2);
+        assert_eq!(supplier_manager.get_offers_for_part(10001).unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,483 @@
+//! User module for Implexa
+//!
+//! This module provides functionality for managing the users of a repository in the database.
+//! Users are identified by their git identity (`user.name` and `user.email`), so that the
+//! author of a commit and the user performing an operation are the same person.
+
+use rusqlite::{Transaction, params, Row, Result as SqliteResult, OptionalExtension};
+use rusqlite::types::Type;
+use crate::database::schema::{DatabaseResult, DatabaseError};
+use crate::database::connection_manager::ConnectionManager;
+
+/// Represents a user role in the system
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UserRole {
+    /// Designer role - can create, edit, and submit parts for review
+    Designer,
+    /// Viewer role - can view and comment on parts
+    Viewer,
+    /// Admin role - has full control over the system
+    Admin,
+}
+
+impl UserRole {
+    /// Convert a string to a UserRole
+    ///
+    /// # Arguments
+    ///
+    /// * `role` - The role string
+    ///
+    /// # Returns
+    ///
+    /// The corresponding UserRole
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(role: &str) -> Option<Self> {
+        match role {
+            "Designer" => Some(Self::Designer),
+            "Viewer" => Some(Self::Viewer),
+            "Admin" => Some(Self::Admin),
+            _ => None,
+        }
+    }
+
+    /// Convert a UserRole to a string
+    ///
+    /// # Returns
+    ///
+    /// The string representation of the role
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::Designer => "Designer",
+            Self::Viewer => "Viewer",
+            Self::Admin => "Admin",
+        }
+    }
+}
+
+/// Represents a user in the system
+#[derive(Debug, Clone)]
+pub struct User {
+    /// Unique identifier for the user, if the user is stored in the database
+    pub user_id: Option<i64>,
+    /// Username
+    pub username: String,
+    /// Display name of the user
+    pub display_name: Option<String>,
+    /// Email address of the user
+    pub email: Option<String>,
+    /// User role
+    pub role: UserRole,
+    /// Whether the user is active
+    pub active: bool,
+}
+
+impl User {
+    /// Create a new user
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - Username
+    /// * `role` - User role
+    ///
+    /// # Returns
+    ///
+    /// A new, active User instance
+    pub fn new(username: String, role: UserRole) -> Self {
+        Self {
+            user_id: None,
+            username,
+            display_name: None,
+            email: None,
+            role,
+            active: true,
+        }
+    }
+
+    /// Create a user from the identity in a git configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The git configuration
+    /// * `role` - User role
+    ///
+    /// # Returns
+    ///
+    /// The user named by `user.name`, with the email address from `user.email`, or None if
+    /// `user.name` is not set
+    pub fn from_git_config(config: &git2::Config, role: UserRole) -> Option<Self> {
+        let name = config.get_string("user.name").ok().filter(|name| !name.trim().is_empty())?;
+        let mut user = Self::new(name, role);
+        user.email = config.get_string("user.email").ok().filter(|email| !email.trim().is_empty());
+        Some(user)
+    }
+}
+
+/// Manager for user operations
+pub struct UserManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+}
+
+impl<'a> UserManager<'a> {
+    /// Create a new UserManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new UserManager instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Create a new user in the database
+    ///
+    /// # Arguments
+    ///
+    /// * `user` - The user to create
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created user
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the user could not be created
+    pub fn create_user(&self, user: &User) -> DatabaseResult<i64> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.create_user_in_transaction(user, tx)
+        })
+    }
+
+    /// Create a new user in the database within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `user` - The user to create
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created user
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the user could not be created
+    pub fn create_user_in_transaction(&self, user: &User, tx: &Transaction) -> DatabaseResult<i64> {
+        tx.execute(
+            "INSERT INTO Users (username, display_name, email, role, active) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![user.username, user.display_name, user.email, user.role.to_str(), user.active],
+        )?;
+        Ok(tx.last_insert_rowid())
+    }
+
+    /// Get a user by ID
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The ID of the user
+    ///
+    /// # Returns
+    ///
+    /// The user
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the user could not be retrieved
+    pub fn get_user(&self, user_id: i64) -> DatabaseResult<User> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let user = conn.query_row(
+                "SELECT user_id, username, display_name, email, role, active FROM Users WHERE user_id = ?1",
+                params![user_id],
+                |row| self.row_to_user(row),
+            )?;
+            Ok(user)
+        })
+    }
+
+    /// Get a user by username within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - The username
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The user, or None if no user has this username
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the user could not be retrieved
+    pub fn get_user_by_username_in_transaction(&self, username: &str, tx: &Transaction) -> DatabaseResult<Option<User>> {
+        let user = tx.query_row(
+            "SELECT user_id, username, display_name, email, role, active FROM Users WHERE username = ?1",
+            params![username],
+            |row| self.row_to_user(row),
+        ).optional()?;
+        Ok(user)
+    }
+
+    /// Get all users
+    ///
+    /// # Returns
+    ///
+    /// A vector of all users, ordered by username
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the users could not be retrieved
+    pub fn get_all_users(&self) -> DatabaseResult<Vec<User>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT user_id, username, display_name, email, role, active FROM Users ORDER BY username",
+            )?;
+            let users = stmt
+                .query_map([], |row| self.row_to_user(row))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(users)
+        })
+    }
+
+    /// Update a user
+    ///
+    /// # Arguments
+    ///
+    /// * `user` - The user to update
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the user was successfully updated
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the user could not be updated
+    pub fn update_user(&self, user: &User) -> DatabaseResult<()> {
+        let user_id = user.user_id.ok_or_else(|| {
+            DatabaseError::ValidationError("Cannot update a user without an ID".to_string())
+        })?;
+
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            conn.execute(
+                "UPDATE Users SET display_name = ?2, email = ?3, role = ?4, active = ?5 WHERE user_id = ?1",
+                params![user_id, user.display_name, user.email, user.role.to_str(), user.active],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Delete a user with their group memberships and permissions
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id` - The ID of the user to delete
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the user was successfully deleted
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the user could not be deleted
+    pub fn delete_user(&self, user_id: i64) -> DatabaseResult<()> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let username: String = tx.query_row(
+                "SELECT username FROM Users WHERE user_id = ?1",
+                params![user_id],
+                |row| row.get(0),
+            )?;
+            tx.execute("DELETE FROM GroupMembers WHERE member = ?1", params![username])?;
+            tx.execute(
+                "DELETE FROM Permissions WHERE grantee_type = 'User' AND grantee = ?1",
+                params![username],
+            )?;
+            tx.execute("DELETE FROM Users WHERE user_id = ?1", params![user_id])?;
+            Ok(())
+        })
+    }
+
+    /// Find the user of a git identity, registering the identity if it is new
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The git `user.name`
+    /// * `email` - The git `user.email`
+    ///
+    /// # Returns
+    ///
+    /// The user with this email address, or else with this username; a new Designer otherwise
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the user could not be found or registered
+    pub fn resolve_git_identity(&self, name: &str, email: Option<&str>) -> DatabaseResult<User> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.resolve_git_identity_in_transaction(name, email, tx)
+        })
+    }
+
+    /// Find the user of a git identity within an existing transaction, registering the identity if it is new
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The git `user.name`
+    /// * `email` - The git `user.email`
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The user with this email address, or else with this username; a new Designer otherwise
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the user could not be found or registered
+    pub fn resolve_git_identity_in_transaction(&self, name: &str, email: Option<&str>, tx: &Transaction) -> DatabaseResult<User> {
+        if let Some(email) = email {
+            let user = tx.query_row(
+                "SELECT user_id, username, display_name, email, role, active
+                 FROM Users
+                 WHERE email = ?1 COLLATE NOCASE
+                 ORDER BY user_id
+                 LIMIT 1",
+                params![email],
+                |row| self.row_to_user(row),
+            ).optional()?;
+            if let Some(user) = user {
+                return Ok(user);
+            }
+        }
+
+        if let Some(user) = self.get_user_by_username_in_transaction(name, tx)? {
+            return Ok(user);
+        }
+
+        let mut user = User::new(name.to_string(), UserRole::Designer);
+        user.display_name = Some(name.to_string());
+        user.email = email.map(str::to_string);
+        user.user_id = Some(self.create_user_in_transaction(&user, tx)?);
+        Ok(user)
+    }
+
+    /// Check whether a user may manage users and permissions
+    ///
+    /// The stored role and active flag of the user take precedence over those of `user`. Until
+    /// an active administrator is stored, any user may, so that the first one can be set up.
+    ///
+    /// # Arguments
+    ///
+    /// * `user` - The user
+    ///
+    /// # Returns
+    ///
+    /// true if the user is an active administrator or no administrator exists yet
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the users could not be checked
+    pub fn is_administrator(&self, user: &User) -> DatabaseResult<bool> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let administrators: i64 = tx.query_row(
+                "SELECT COUNT(*) FROM Users WHERE role = ?1 AND active = 1",
+                params![UserRole::Admin.to_str()],
+                |row| row.get(0),
+            )?;
+            if administrators == 0 {
+                return Ok(true);
+            }
+
+            let stored = self.get_user_by_username_in_transaction(&user.username, tx)?;
+            let user = stored.as_ref().unwrap_or(user);
+            Ok(user.active && user.role == UserRole::Admin)
+        })
+    }
+
+    /// Convert a database row to a User
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The database row
+    ///
+    /// # Returns
+    ///
+    /// A User instance
+    ///
+    /// # Errors
+    ///
+    /// Returns a SqliteError if the row could not be converted
+    fn row_to_user(&self, row: &Row) -> SqliteResult<User> {
+        let role_str: String = row.get(4)?;
+        let role = UserRole::from_str(&role_str)
+            .ok_or_else(|| rusqlite::Error::InvalidColumnType(4, "role".to_string(), Type::Text))?;
+
+        Ok(User {
+            user_id: Some(row.get(0)?),
+            username: row.get(1)?,
+            display_name: row.get(2)?,
+            email: row.get(3)?,
+            role,
+            active: row.get(5)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::DatabaseManager;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_resolve_git_identity() {
+        // Create a temporary directory for the test database
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+
+        // Create a new database manager and initialize the schema
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let user_manager = UserManager::new(db_manager.connection_manager());
+
+        // Read the identity from a git configuration
+        let mut config = git2::Config::open(&temp_dir.path().join("gitconfig")).unwrap();
+        assert!(User::from_git_config(&config, UserRole::Designer).is_none());
+        config.set_str("user.name", "Alice Smith").unwrap();
+        config.set_str("user.email", "alice@example.com").unwrap();
+        let identity = User::from_git_config(&config, UserRole::Designer).unwrap();
+        assert_eq!(identity.username, "Alice Smith");
+        assert_eq!(identity.email.as_deref(), Some("alice@example.com"));
+
+        // An unknown identity is registered as a designer
+        let alice = user_manager.resolve_git_identity(&identity.username, identity.email.as_deref()).unwrap();
+        assert!(alice.user_id.is_some());
+        assert_eq!(alice.role, UserRole::Designer);
+
+        // Until an administrator exists, any user may set one up
+        assert!(user_manager.is_administrator(&alice).unwrap());
+
+        // The email address identifies the user even if the name changes
+        let mut stored = user_manager.get_user(alice.user_id.unwrap()).unwrap();
+        stored.role = UserRole::Admin;
+        user_manager.update_user(&stored).unwrap();
+        let renamed = user_manager.resolve_git_identity("A. Smith", Some("ALICE@example.com")).unwrap();
+        assert_eq!(renamed.user_id, alice.user_id);
+        assert_eq!(renamed.role, UserRole::Admin);
+
+        // Without an email address the username identifies the user
+        let by_name = user_manager.resolve_git_identity("Alice Smith", None).unwrap();
+        assert_eq!(by_name.user_id, alice.user_id);
+        assert_eq!(user_manager.get_all_users().unwrap().len(), 1);
+
+        // Once an administrator exists, only administrators manage users
+        let bob = user_manager.resolve_git_identity("Bob", Some("bob@example.com")).unwrap();
+        assert!(user_manager.is_administrator(&stored).unwrap());
+        assert!(!user_manager.is_administrator(&bob).unwrap());
+        assert!(!user_manager.is_administrator(&User::new("Bob".to_string(), UserRole::Admin)).unwrap());
+        user_manager.delete_user(bob.user_id.unwrap()).unwrap();
+
+        // Deleting the user removes it
+        user_manager.delete_user(alice.user_id.unwrap()).unwrap();
+        assert!(user_manager.get_all_users().unwrap().is_empty());
+    }
+}
@@ -2,10 +2,13 @@
 //!
 //! This module provides functionality for managing workflows, workflow states, and workflow transitions in the database.
 
-use rusqlite::{Transaction, params, Row, Result as SqliteResult};
+use rusqlite::{Transaction, params, Row, Result as SqliteResult, OptionalExtension};
 use crate::database::schema::{DatabaseError, DatabaseResult};
 use crate::database::connection_manager::ConnectionManager;
 
+/// Name of the default workflow used for parts whose category has no workflow assigned
+pub const DEFAULT_PART_WORKFLOW: &str = "Part Workflow";
+
 /// Represents a workflow
 #[derive(Debug, Clone)]
 pub struct Workflow {
@@ -148,6 +151,203 @@ impl WorkflowTransition {
     }
 }
 
+/// Type of a guard that must pass before a transition can be executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionGuardType {
+    /// Every child part in the BOM has a released revision
+    ChildrenReleased,
+    /// A file of the type given by the guard parameter is attached
+    FileAttached,
+    /// The comma-separated properties given by the guard parameter have values
+    PropertiesPresent,
+    /// No deviation against the part is open
+    NoOpenDeviations,
+    /// The release commit or the release tag of the revision carries a valid signature; checked
+    /// once the release has been tagged
+    SignedRelease,
+}
+
+impl TransitionGuardType {
+    /// Convert a string to a TransitionGuardType
+    ///
+    /// # Arguments
+    ///
+    /// * `guard_type` - The guard type string
+    ///
+    /// # Returns
+    ///
+    /// The corresponding TransitionGuardType
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(guard_type: &str) -> Option<Self> {
+        match guard_type {
+            "ChildrenReleased" => Some(Self::ChildrenReleased),
+            "FileAttached" => Some(Self::FileAttached),
+            "PropertiesPresent" => Some(Self::PropertiesPresent),
+            "NoOpenDeviations" => Some(Self::NoOpenDeviations),
+            "SignedRelease" => Some(Self::SignedRelease),
+            _ => None,
+        }
+    }
+
+    /// Convert a TransitionGuardType to a string
+    ///
+    /// # Returns
+    ///
+    /// The string representation of the TransitionGuardType
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::ChildrenReleased => "ChildrenReleased",
+            Self::FileAttached => "FileAttached",
+            Self::PropertiesPresent => "PropertiesPresent",
+            Self::NoOpenDeviations => "NoOpenDeviations",
+            Self::SignedRelease => "SignedRelease",
+        }
+    }
+}
+
+/// Represents a guard on a workflow transition
+#[derive(Debug, Clone)]
+pub struct TransitionGuard {
+    /// Unique identifier for the guard
+    pub guard_id: Option<i64>,
+    /// ID of the transition this guard belongs to
+    pub transition_id: i64,
+    /// Type of the guard
+    pub guard_type: TransitionGuardType,
+    /// Parameter of the guard, e.g. a file type or property names
+    pub parameter: Option<String>,
+}
+
+impl TransitionGuard {
+    /// Create a new transition guard
+    ///
+    /// # Arguments
+    ///
+    /// * `transition_id` - ID of the transition this guard belongs to
+    /// * `guard_type` - Type of the guard
+    /// * `parameter` - Parameter of the guard
+    ///
+    /// # Returns
+    ///
+    /// A new TransitionGuard instance
+    pub fn new(transition_id: i64, guard_type: TransitionGuardType, parameter: Option<String>) -> Self {
+        Self {
+            guard_id: None,
+            transition_id,
+            guard_type,
+            parameter,
+        }
+    }
+}
+
+/// Type of an action executed after a transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionActionType {
+    /// Create a git tag named by the action parameter
+    CreateTag,
+    /// Merge the branch named by the action parameter into the current branch
+    MergeBranch,
+    /// Regenerate and commit the README of the part
+    RegenerateReadme,
+    /// Record the event named by the action parameter
+    EmitEvent,
+}
+
+impl TransitionActionType {
+    /// Convert a string to a TransitionActionType
+    ///
+    /// # Arguments
+    ///
+    /// * `action_type` - The action type string
+    ///
+    /// # Returns
+    ///
+    /// The corresponding TransitionActionType
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(action_type: &str) -> Option<Self> {
+        match action_type {
+            "CreateTag" => Some(Self::CreateTag),
+            "MergeBranch" => Some(Self::MergeBranch),
+            "RegenerateReadme" => Some(Self::RegenerateReadme),
+            "EmitEvent" => Some(Self::EmitEvent),
+            _ => None,
+        }
+    }
+
+    /// Convert a TransitionActionType to a string
+    ///
+    /// # Returns
+    ///
+    /// The string representation of the TransitionActionType
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::CreateTag => "CreateTag",
+            Self::MergeBranch => "MergeBranch",
+            Self::RegenerateReadme => "RegenerateReadme",
+            Self::EmitEvent => "EmitEvent",
+        }
+    }
+}
+
+/// Represents an action executed after a workflow transition
+///
+/// Tag and branch parameters may contain the placeholders `{part_number}` and `{version}`.
+#[derive(Debug, Clone)]
+pub struct TransitionAction {
+    /// Unique identifier for the action
+    pub action_id: Option<i64>,
+    /// ID of the transition this action belongs to
+    pub transition_id: i64,
+    /// Type of the action
+    pub action_type: TransitionActionType,
+    /// Parameter of the action, e.g. a tag name or event name
+    pub parameter: Option<String>,
+    /// Position of the action in the execution order
+    pub sequence: i64,
+}
+
+impl TransitionAction {
+    /// Create a new transition action
+    ///
+    /// # Arguments
+    ///
+    /// * `transition_id` - ID of the transition this action belongs to
+    /// * `action_type` - Type of the action
+    /// * `parameter` - Parameter of the action
+    /// * `sequence` - Position of the action in the execution order
+    ///
+    /// # Returns
+    ///
+    /// A new TransitionAction instance
+    pub fn new(transition_id: i64, action_type: TransitionActionType, parameter: Option<String>, sequence: i64) -> Self {
+        Self {
+            action_id: None,
+            transition_id,
+            action_type,
+            parameter,
+            sequence,
+        }
+    }
+
+    /// Get the parameter with its placeholders replaced
+    ///
+    /// # Arguments
+    ///
+    /// * `part_number` - Display part number substituted for `{part_number}`
+    /// * `version` - Revision version substituted for `{version}`
+    ///
+    /// # Returns
+    ///
+    /// The expanded parameter, or None if the action has no parameter
+    pub fn expand_parameter(&self, part_number: &str, version: &str) -> Option<String> {
+        self.parameter.as_ref().map(|parameter| {
+            parameter
+                .replace("{part_number}", part_number)
+                .replace("{version}", version)
+        })
+    }
+}
+
 /// Manager for workflow operations
 pub struct WorkflowManager<'a> {
     /// Connection manager for the SQLite database
@@ -210,8 +410,7 @@ impl<'a> WorkflowManager<'a> {
                 ],
             )?;
             Ok::<i64, DatabaseError>(conn.last_insert_rowid())
-        }).map_err(DatabaseError::from)
-    }
+        })}
     
     /// Create a new workflow in the database within an existing transaction
     ///
@@ -263,7 +462,31 @@ impl<'a> WorkflowManager<'a> {
                 |row| self.row_to_workflow(row),
             )?;
             Ok(workflow)
-        }).map_err(DatabaseError::from)
+        })}
+
+    /// Get a workflow by its ID within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `workflow_id` - The ID of the workflow to get
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The workflow with the specified ID
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workflow could not be found
+    pub fn get_workflow_in_transaction(&self, workflow_id: i64, tx: &Transaction) -> DatabaseResult<Workflow> {
+        let workflow = tx.query_row(
+            "SELECT workflow_id, name, description, active
+             FROM Workflows
+             WHERE workflow_id = ?1",
+            params![workflow_id],
+            |row| self.row_to_workflow(row),
+        )?;
+        Ok(workflow)
     }
 
     /// Get a workflow by its name
@@ -289,7 +512,31 @@ impl<'a> WorkflowManager<'a> {
                 |row| self.row_to_workflow(row),
             )?;
             Ok(workflow)
-        }).map_err(DatabaseError::from)
+        })}
+
+    /// Get a workflow by its name within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the workflow to get
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The workflow with the specified name, or None if it does not exist
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workflow could not be retrieved
+    pub fn get_workflow_by_name_in_transaction(&self, name: &str, tx: &Transaction) -> DatabaseResult<Option<Workflow>> {
+        let workflow = tx.query_row(
+            "SELECT workflow_id, name, description, active
+             FROM Workflows
+             WHERE name = ?1",
+            params![name],
+            |row| self.row_to_workflow(row),
+        ).optional()?;
+        Ok(workflow)
     }
 
     /// Get all workflows
@@ -314,7 +561,31 @@ impl<'a> WorkflowManager<'a> {
                 workflows.push(workflow_result?);
             }
             Ok(workflows)
-        }).map_err(DatabaseError::from)
+        })}
+
+    /// Get all workflows within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// A vector of all workflows
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workflows could not be retrieved
+    pub fn get_all_workflows_in_transaction(&self, tx: &Transaction) -> DatabaseResult<Vec<Workflow>> {
+        let mut stmt = tx.prepare(
+            "SELECT workflow_id, name, description, active
+             FROM Workflows
+             ORDER BY name",
+        )?;
+        let workflows = stmt
+            .query_map([], |row| self.row_to_workflow(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(workflows)
     }
 
     /// Get all active workflows
@@ -340,8 +611,7 @@ impl<'a> WorkflowManager<'a> {
                 workflows.push(workflow_result?);
             }
             Ok(workflows)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Update a workflow
     ///
@@ -374,8 +644,7 @@ impl<'a> WorkflowManager<'a> {
                 ],
             )?;
             Ok(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Delete a workflow
     ///
@@ -392,13 +661,17 @@ impl<'a> WorkflowManager<'a> {
     /// Returns a DatabaseError if the workflow could not be deleted
     pub fn delete_workflow(&self, workflow_id: i64) -> DatabaseResult<()> {
         self.connection_manager.execute_mut::<_, _, DatabaseError>(|conn| {
+            // Categories using the workflow fall back to the default part workflow
+            conn.execute(
+                "UPDATE Categories SET workflow_id = NULL WHERE workflow_id = ?1",
+                params![workflow_id],
+            )?;
             conn.execute(
                 "DELETE FROM Workflows WHERE workflow_id = ?1",
                 params![workflow_id],
             )?;
             Ok(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Create a new workflow state in the database
     ///
@@ -427,8 +700,7 @@ impl<'a> WorkflowManager<'a> {
                 ],
             )?;
             Ok(conn.last_insert_rowid())
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get a workflow state by its ID
     ///
@@ -453,7 +725,57 @@ impl<'a> WorkflowManager<'a> {
                 |row| self.row_to_workflow_state(row),
             )?;
             Ok(state)
-        }).map_err(DatabaseError::from)
+        })}
+
+    /// Get a workflow state by its ID within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `state_id` - The ID of the workflow state to get
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The workflow state with the specified ID
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workflow state could not be found
+    pub fn get_workflow_state_in_transaction(&self, state_id: i64, tx: &Transaction) -> DatabaseResult<WorkflowState> {
+        let state = tx.query_row(
+            "SELECT state_id, workflow_id, name, description, is_initial, is_terminal
+             FROM WorkflowStates
+             WHERE state_id = ?1",
+            params![state_id],
+            |row| self.row_to_workflow_state(row),
+        )?;
+        Ok(state)
+    }
+
+    /// Get a workflow state by its name within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `workflow_id` - The ID of the workflow
+    /// * `name` - The name of the state
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The workflow state with the specified name, or None if the workflow has no such state
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workflow state could not be retrieved
+    pub fn get_workflow_state_by_name_in_transaction(&self, workflow_id: i64, name: &str, tx: &Transaction) -> DatabaseResult<Option<WorkflowState>> {
+        let state = tx.query_row(
+            "SELECT state_id, workflow_id, name, description, is_initial, is_terminal
+             FROM WorkflowStates
+             WHERE workflow_id = ?1 AND name = ?2",
+            params![workflow_id, name],
+            |row| self.row_to_workflow_state(row),
+        ).optional()?;
+        Ok(state)
     }
 
     /// Get all workflow states for a workflow
@@ -483,7 +805,32 @@ impl<'a> WorkflowManager<'a> {
                 states.push(state_result?);
             }
             Ok(states)
-        }).map_err(DatabaseError::from)
+        })}
+
+    /// Get all workflow states for a workflow within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `workflow_id` - The ID of the workflow
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// A vector of all workflow states for the specified workflow
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workflow states could not be retrieved
+    pub fn get_workflow_states_in_transaction(&self, workflow_id: i64, tx: &Transaction) -> DatabaseResult<Vec<WorkflowState>> {
+        let mut stmt = tx.prepare(
+            "SELECT state_id, workflow_id, name, description, is_initial, is_terminal
+             FROM WorkflowStates
+             WHERE workflow_id = ?1
+             ORDER BY name",
+        )?;
+        let states = stmt.query_map(params![workflow_id], |row| self.row_to_workflow_state(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(states)
     }
 
     /// Get the initial state for a workflow
@@ -509,7 +856,31 @@ impl<'a> WorkflowManager<'a> {
                 |row| self.row_to_workflow_state(row),
             )?;
             Ok::<WorkflowState, DatabaseError>(state)
-        }).map_err(DatabaseError::from)
+        })}
+
+    /// Get the initial state for a workflow within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `workflow_id` - The ID of the workflow
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The initial state for the specified workflow
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the initial state could not be found
+    pub fn get_initial_state_in_transaction(&self, workflow_id: i64, tx: &Transaction) -> DatabaseResult<WorkflowState> {
+        let state = tx.query_row(
+            "SELECT state_id, workflow_id, name, description, is_initial, is_terminal
+             FROM WorkflowStates
+             WHERE workflow_id = ?1 AND is_initial = 1",
+            params![workflow_id],
+            |row| self.row_to_workflow_state(row),
+        )?;
+        Ok(state)
     }
 
     /// Update a workflow state
@@ -545,8 +916,7 @@ impl<'a> WorkflowManager<'a> {
                 ],
             )?;
             Ok::<(), DatabaseError>(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Delete a workflow state
     ///
@@ -568,8 +938,7 @@ impl<'a> WorkflowManager<'a> {
                 params![state_id],
             )?;
             Ok::<(), DatabaseError>(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Create a new workflow transition in the database
     ///
@@ -599,8 +968,7 @@ impl<'a> WorkflowManager<'a> {
                 ],
             )?;
             Ok::<i64, DatabaseError>(conn.last_insert_rowid())
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get a workflow transition by its ID
     ///
@@ -625,8 +993,7 @@ impl<'a> WorkflowManager<'a> {
                 |row| self.row_to_workflow_transition(row),
             )?;
             Ok::<WorkflowTransition, DatabaseError>(transition)
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Get all workflow transitions for a workflow
     ///
@@ -655,7 +1022,32 @@ impl<'a> WorkflowManager<'a> {
                 transitions.push(transition_result?);
             }
             Ok::<Vec<WorkflowTransition>, DatabaseError>(transitions)
-        }).map_err(DatabaseError::from)
+        })}
+
+    /// Get all workflow transitions for a workflow within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `workflow_id` - The ID of the workflow
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// A vector of all workflow transitions for the specified workflow
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workflow transitions could not be retrieved
+    pub fn get_workflow_transitions_in_transaction(&self, workflow_id: i64, tx: &Transaction) -> DatabaseResult<Vec<WorkflowTransition>> {
+        let mut stmt = tx.prepare(
+            "SELECT transition_id, workflow_id, from_state_id, to_state_id, name, description, requires_approval
+             FROM WorkflowTransitions
+             WHERE workflow_id = ?1
+             ORDER BY name",
+        )?;
+        let transitions = stmt.query_map(params![workflow_id], |row| self.row_to_workflow_transition(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(transitions)
     }
 
     /// Get all workflow transitions from a specific state
@@ -685,7 +1077,35 @@ impl<'a> WorkflowManager<'a> {
                 transitions.push(transition_result?);
             }
             Ok::<Vec<WorkflowTransition>, DatabaseError>(transitions)
-        }).map_err(DatabaseError::from)
+        })}
+
+    /// Get all workflow transitions from a specific state within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `from_state_id` - The ID of the state to get transitions from
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// A vector of all workflow transitions from the specified state
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workflow transitions could not be retrieved
+    pub fn get_transitions_from_state_in_transaction(&self, from_state_id: i64, tx: &Transaction) -> DatabaseResult<Vec<WorkflowTransition>> {
+        let mut stmt = tx.prepare(
+            "SELECT transition_id, workflow_id, from_state_id, to_state_id, name, description, requires_approval
+             FROM WorkflowTransitions
+             WHERE from_state_id = ?1
+             ORDER BY name",
+        )?;
+        let transitions_iter = stmt.query_map(params![from_state_id], |row| self.row_to_workflow_transition(row))?;
+        let mut transitions = Vec::new();
+        for transition_result in transitions_iter {
+            transitions.push(transition_result?);
+        }
+        Ok(transitions)
     }
 
     /// Update a workflow transition
@@ -722,8 +1142,7 @@ impl<'a> WorkflowManager<'a> {
                 ],
             )?;
             Ok::<(), DatabaseError>(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
     /// Delete a workflow transition
     ///
@@ -740,231 +1159,448 @@ impl<'a> WorkflowManager<'a> {
     /// Returns a DatabaseError if the workflow transition could not be deleted
     pub fn delete_workflow_transition(&self, transition_id: i64) -> DatabaseResult<()> {
         self.connection_manager.execute_mut::<_, _, DatabaseError>(|conn| {
+            conn.execute(
+                "DELETE FROM WorkflowTransitionGuards WHERE transition_id = ?1",
+                params![transition_id],
+            )?;
+            conn.execute(
+                "DELETE FROM WorkflowTransitionActions WHERE transition_id = ?1",
+                params![transition_id],
+            )?;
             conn.execute(
                 "DELETE FROM WorkflowTransitions WHERE transition_id = ?1",
                 params![transition_id],
             )?;
             Ok::<(), DatabaseError>(())
-        }).map_err(DatabaseError::from)
-    }
+        })}
 
-    /// Create a default part workflow
+    /// Add a guard to a workflow transition
+    ///
+    /// # Arguments
+    ///
+    /// * `guard` - The guard to add
     ///
     /// # Returns
     ///
-    /// The ID of the newly created workflow
+    /// The ID of the newly created guard
     ///
     /// # Errors
     ///
-    /// Returns a DatabaseError if the workflow could not be created
-    pub fn create_default_part_workflow(&self) -> DatabaseResult<i64> {
-        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
-            // Create the workflow
-            let workflow = Workflow::new(
-                "Part Workflow".to_string(),
-                Some("Default workflow for parts".to_string()),
-                true,
-            );
-            let workflow_id = self.create_workflow_in_transaction(&workflow, tx)?;
-
-            // Create the states
-            let draft_state = WorkflowState::new(
-                workflow_id,
-                "Draft".to_string(),
-                Some("Initial state for new parts".to_string()),
-                true,
-                false,
-            );
-            
-            tx.execute(
-                "INSERT INTO WorkflowStates (workflow_id, name, description, is_initial, is_terminal)
-                 VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![
-                    draft_state.workflow_id,
-                    draft_state.name,
-                    draft_state.description,
-                    draft_state.is_initial,
-                    draft_state.is_terminal,
-                ],
-            )?;
-            let draft_state_id = tx.last_insert_rowid();
-
-            let in_review_state = WorkflowState::new(
-                workflow_id,
-                "In Review".to_string(),
-                Some("Part is being reviewed".to_string()),
-                false,
-                false,
-            );
-            
-            tx.execute(
-                "INSERT INTO WorkflowStates (workflow_id, name, description, is_initial, is_terminal)
-                 VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![
-                    in_review_state.workflow_id,
-                    in_review_state.name,
-                    in_review_state.description,
-                    in_review_state.is_initial,
-                    in_review_state.is_terminal,
-                ],
-            )?;
-            let in_review_state_id = tx.last_insert_rowid();
-
-            let released_state = WorkflowState::new(
-                workflow_id,
-                "Released".to_string(),
-                Some("Part has been approved and released".to_string()),
-                false,
-                false,
-            );
-            
-            tx.execute(
-                "INSERT INTO WorkflowStates (workflow_id, name, description, is_initial, is_terminal)
-                 VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![
-                    released_state.workflow_id,
-                    released_state.name,
-                    released_state.description,
-                    released_state.is_initial,
-                    released_state.is_terminal,
-                ],
-            )?;
-            let released_state_id = tx.last_insert_rowid();
-
-            let obsolete_state = WorkflowState::new(
-                workflow_id,
-                "Obsolete".to_string(),
-                Some("Part is no longer active".to_string()),
-                false,
-                true,
-            );
-            
-            tx.execute(
-                "INSERT INTO WorkflowStates (workflow_id, name, description, is_initial, is_terminal)
-                 VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![
-                    obsolete_state.workflow_id,
-                    obsolete_state.name,
-                    obsolete_state.description,
-                    obsolete_state.is_initial,
-                    obsolete_state.is_terminal,
-                ],
-            )?;
-            let obsolete_state_id = tx.last_insert_rowid();
-
-            // Create the transitions
-            let submit_for_review = WorkflowTransition::new(
-                workflow_id,
-                draft_state_id,
-                in_review_state_id,
-                "Submit for Review".to_string(),
-                Some("Submit the part for review".to_string()),
-                false,
-            );
-            
-            tx.execute(
-                "INSERT INTO WorkflowTransitions (workflow_id, from_state_id, to_state_id, name, description, requires_approval)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    /// Returns a DatabaseError if the guard could not be created
+    pub fn create_transition_guard(&self, guard: &TransitionGuard) -> DatabaseResult<i64> {
+        self.connection_manager.execute_mut::<_, _, DatabaseError>(|conn| {
+            conn.execute(
+                "INSERT INTO WorkflowTransitionGuards (transition_id, guard_type, parameter)
+                 VALUES (?1, ?2, ?3)",
                 params![
-                    submit_for_review.workflow_id,
-                    submit_for_review.from_state_id,
-                    submit_for_review.to_state_id,
-                    submit_for_review.name,
-                    submit_for_review.description,
-                    submit_for_review.requires_approval,
+                    guard.transition_id,
+                    guard.guard_type.to_str(),
+                    guard.parameter,
                 ],
             )?;
+            Ok::<i64, DatabaseError>(conn.last_insert_rowid())
+        })
+    }
 
-            let approve = WorkflowTransition::new(
-                workflow_id,
-                in_review_state_id,
-                released_state_id,
-                "Approve".to_string(),
-                Some("Approve the part for release".to_string()),
-                true,
-            );
-            
-            tx.execute(
-                "INSERT INTO WorkflowTransitions (workflow_id, from_state_id, to_state_id, name, description, requires_approval)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                params![
-                    approve.workflow_id,
-                    approve.from_state_id,
-                    approve.to_state_id,
-                    approve.name,
-                    approve.description,
-                    approve.requires_approval,
-                ],
-            )?;
+    /// Get the guards of a workflow transition
+    ///
+    /// # Arguments
+    ///
+    /// * `transition_id` - The ID of the transition
+    ///
+    /// # Returns
+    ///
+    /// A vector of guards of the transition
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the guards could not be retrieved
+    pub fn get_transition_guards(&self, transition_id: i64) -> DatabaseResult<Vec<TransitionGuard>> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.get_transition_guards_in_transaction(transition_id, tx)
+        })
+    }
 
-            let reject = WorkflowTransition::new(
-                workflow_id,
-                in_review_state_id,
-                draft_state_id,
-                "Reject".to_string(),
-                Some("Reject the part and return to draft".to_string()),
-                true,
-            );
-            
-            tx.execute(
-                "INSERT INTO WorkflowTransitions (workflow_id, from_state_id, to_state_id, name, description, requires_approval)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                params![
-                    reject.workflow_id,
-                    reject.from_state_id,
-                    reject.to_state_id,
-                    reject.name,
-                    reject.description,
-                    reject.requires_approval,
-                ],
+    /// Get the guards of a workflow transition within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `transition_id` - The ID of the transition
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// A vector of guards of the transition
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the guards could not be retrieved
+    pub fn get_transition_guards_in_transaction(&self, transition_id: i64, tx: &Transaction) -> DatabaseResult<Vec<TransitionGuard>> {
+        let mut stmt = tx.prepare(
+            "SELECT guard_id, transition_id, guard_type, parameter
+             FROM WorkflowTransitionGuards
+             WHERE transition_id = ?1
+             ORDER BY guard_id",
+        )?;
+        let guards = stmt.query_map(params![transition_id], |row| self.row_to_transition_guard(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(guards)
+    }
+
+    /// Delete a transition guard
+    ///
+    /// # Arguments
+    ///
+    /// * `guard_id` - The ID of the guard to delete
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the guard was successfully deleted
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the guard could not be deleted
+    pub fn delete_transition_guard(&self, guard_id: i64) -> DatabaseResult<()> {
+        self.connection_manager.execute_mut::<_, _, DatabaseError>(|conn| {
+            conn.execute(
+                "DELETE FROM WorkflowTransitionGuards WHERE guard_id = ?1",
+                params![guard_id],
             )?;
+            Ok::<(), DatabaseError>(())
+        })
+    }
 
-            let obsolete = WorkflowTransition::new(
-                workflow_id,
-                released_state_id,
-                obsolete_state_id,
-                "Obsolete".to_string(),
-                Some("Mark the part as obsolete".to_string()),
-                true,
-            );
-            
-            tx.execute(
-                "INSERT INTO WorkflowTransitions (workflow_id, from_state_id, to_state_id, name, description, requires_approval)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    /// Add an action to a workflow transition
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The action to add
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created action
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the action could not be created
+    pub fn create_transition_action(&self, action: &TransitionAction) -> DatabaseResult<i64> {
+        self.connection_manager.execute_mut::<_, _, DatabaseError>(|conn| {
+            conn.execute(
+                "INSERT INTO WorkflowTransitionActions (transition_id, action_type, parameter, sequence)
+                 VALUES (?1, ?2, ?3, ?4)",
                 params![
-                    obsolete.workflow_id,
-                    obsolete.from_state_id,
-                    obsolete.to_state_id,
-                    obsolete.name,
-                    obsolete.description,
-                    obsolete.requires_approval,
+                    action.transition_id,
+                    action.action_type.to_str(),
+                    action.parameter,
+                    action.sequence,
                 ],
             )?;
+            Ok::<i64, DatabaseError>(conn.last_insert_rowid())
+        })
+    }
 
-            let revise = WorkflowTransition::new(
-                workflow_id,
-                released_state_id,
-                draft_state_id,
-                "Revise".to_string(),
-                Some("Create a new revision of the part".to_string()),
-                false,
-            );
-            
-            tx.execute(
-                "INSERT INTO WorkflowTransitions (workflow_id, from_state_id, to_state_id, name, description, requires_approval)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-                params![
-                    revise.workflow_id,
-                    revise.from_state_id,
-                    revise.to_state_id,
-                    revise.name,
-                    revise.description,
-                    revise.requires_approval,
-                ],
+    /// Get the actions of a workflow transition in execution order
+    ///
+    /// # Arguments
+    ///
+    /// * `transition_id` - The ID of the transition
+    ///
+    /// # Returns
+    ///
+    /// A vector of actions of the transition
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the actions could not be retrieved
+    pub fn get_transition_actions(&self, transition_id: i64) -> DatabaseResult<Vec<TransitionAction>> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.get_transition_actions_in_transaction(transition_id, tx)
+        })
+    }
+
+    /// Get the actions of a workflow transition in execution order within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `transition_id` - The ID of the transition
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// A vector of actions of the transition
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the actions could not be retrieved
+    pub fn get_transition_actions_in_transaction(&self, transition_id: i64, tx: &Transaction) -> DatabaseResult<Vec<TransitionAction>> {
+        let mut stmt = tx.prepare(
+            "SELECT action_id, transition_id, action_type, parameter, sequence
+             FROM WorkflowTransitionActions
+             WHERE transition_id = ?1
+             ORDER BY sequence, action_id",
+        )?;
+        let actions = stmt.query_map(params![transition_id], |row| self.row_to_transition_action(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(actions)
+    }
+
+    /// Delete a transition action
+    ///
+    /// # Arguments
+    ///
+    /// * `action_id` - The ID of the action to delete
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the action was successfully deleted
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the action could not be deleted
+    pub fn delete_transition_action(&self, action_id: i64) -> DatabaseResult<()> {
+        self.connection_manager.execute_mut::<_, _, DatabaseError>(|conn| {
+            conn.execute(
+                "DELETE FROM WorkflowTransitionActions WHERE action_id = ?1",
+                params![action_id],
             )?;
+            Ok::<(), DatabaseError>(())
+        })
+    }
 
-            Ok::<i64, DatabaseError>(workflow_id)
-        }).map_err(DatabaseError::from)
+    /// Create a default part workflow
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created workflow
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workflow could not be created
+    pub fn create_default_part_workflow(&self) -> DatabaseResult<i64> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.create_default_part_workflow_in_transaction(tx)
+        })
+    }
+
+    /// Create a default part workflow within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created workflow
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workflow could not be created
+    pub fn create_default_part_workflow_in_transaction(&self, tx: &Transaction) -> DatabaseResult<i64> {
+        // Create the workflow
+        let workflow = Workflow::new(
+            DEFAULT_PART_WORKFLOW.to_string(),
+            Some("Default workflow for parts".to_string()),
+            true,
+        );
+        let workflow_id = self.create_workflow_in_transaction(&workflow, tx)?;
+
+        // Create the states
+        let draft_state = WorkflowState::new(
+            workflow_id,
+            "Draft".to_string(),
+            Some("Initial state for new parts".to_string()),
+            true,
+            false,
+        );
+        
+        tx.execute(
+            "INSERT INTO WorkflowStates (workflow_id, name, description, is_initial, is_terminal)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                draft_state.workflow_id,
+                draft_state.name,
+                draft_state.description,
+                draft_state.is_initial,
+                draft_state.is_terminal,
+            ],
+        )?;
+        let draft_state_id = tx.last_insert_rowid();
+
+        let in_review_state = WorkflowState::new(
+            workflow_id,
+            "In Review".to_string(),
+            Some("Part is being reviewed".to_string()),
+            false,
+            false,
+        );
+        
+        tx.execute(
+            "INSERT INTO WorkflowStates (workflow_id, name, description, is_initial, is_terminal)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                in_review_state.workflow_id,
+                in_review_state.name,
+                in_review_state.description,
+                in_review_state.is_initial,
+                in_review_state.is_terminal,
+            ],
+        )?;
+        let in_review_state_id = tx.last_insert_rowid();
+
+        let released_state = WorkflowState::new(
+            workflow_id,
+            "Released".to_string(),
+            Some("Part has been approved and released".to_string()),
+            false,
+            false,
+        );
+        
+        tx.execute(
+            "INSERT INTO WorkflowStates (workflow_id, name, description, is_initial, is_terminal)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                released_state.workflow_id,
+                released_state.name,
+                released_state.description,
+                released_state.is_initial,
+                released_state.is_terminal,
+            ],
+        )?;
+        let released_state_id = tx.last_insert_rowid();
+
+        let obsolete_state = WorkflowState::new(
+            workflow_id,
+            "Obsolete".to_string(),
+            Some("Part is no longer active".to_string()),
+            false,
+            true,
+        );
+        
+        tx.execute(
+            "INSERT INTO WorkflowStates (workflow_id, name, description, is_initial, is_terminal)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                obsolete_state.workflow_id,
+                obsolete_state.name,
+                obsolete_state.description,
+                obsolete_state.is_initial,
+                obsolete_state.is_terminal,
+            ],
+        )?;
+        let obsolete_state_id = tx.last_insert_rowid();
+
+        // Create the transitions
+        let submit_for_review = WorkflowTransition::new(
+            workflow_id,
+            draft_state_id,
+            in_review_state_id,
+            "Submit for Review".to_string(),
+            Some("Submit the part for review".to_string()),
+            false,
+        );
+        
+        tx.execute(
+            "INSERT INTO WorkflowTransitions (workflow_id, from_state_id, to_state_id, name, description, requires_approval)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                submit_for_review.workflow_id,
+                submit_for_review.from_state_id,
+                submit_for_review.to_state_id,
+                submit_for_review.name,
+                submit_for_review.description,
+                submit_for_review.requires_approval,
+            ],
+        )?;
+
+        let approve = WorkflowTransition::new(
+            workflow_id,
+            in_review_state_id,
+            released_state_id,
+            "Approve".to_string(),
+            Some("Approve the part for release".to_string()),
+            true,
+        );
+        
+        tx.execute(
+            "INSERT INTO WorkflowTransitions (workflow_id, from_state_id, to_state_id, name, description, requires_approval)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                approve.workflow_id,
+                approve.from_state_id,
+                approve.to_state_id,
+                approve.name,
+                approve.description,
+                approve.requires_approval,
+            ],
+        )?;
+
+        let reject = WorkflowTransition::new(
+            workflow_id,
+            in_review_state_id,
+            draft_state_id,
+            "Reject".to_string(),
+            Some("Reject the part and return to draft".to_string()),
+            false,
+        );
+        
+        tx.execute(
+            "INSERT INTO WorkflowTransitions (workflow_id, from_state_id, to_state_id, name, description, requires_approval)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                reject.workflow_id,
+                reject.from_state_id,
+                reject.to_state_id,
+                reject.name,
+                reject.description,
+                reject.requires_approval,
+            ],
+        )?;
+
+        let obsolete = WorkflowTransition::new(
+            workflow_id,
+            released_state_id,
+            obsolete_state_id,
+            "Obsolete".to_string(),
+            Some("Mark the part as obsolete".to_string()),
+            true,
+        );
+        
+        tx.execute(
+            "INSERT INTO WorkflowTransitions (workflow_id, from_state_id, to_state_id, name, description, requires_approval)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                obsolete.workflow_id,
+                obsolete.from_state_id,
+                obsolete.to_state_id,
+                obsolete.name,
+                obsolete.description,
+                obsolete.requires_approval,
+            ],
+        )?;
+
+        let revise = WorkflowTransition::new(
+            workflow_id,
+            released_state_id,
+            draft_state_id,
+            "Revise".to_string(),
+            Some("Create a new revision of the part".to_string()),
+            false,
+        );
+        
+        tx.execute(
+            "INSERT INTO WorkflowTransitions (workflow_id, from_state_id, to_state_id, name, description, requires_approval)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                revise.workflow_id,
+                revise.from_state_id,
+                revise.to_state_id,
+                revise.name,
+                revise.description,
+                revise.requires_approval,
+            ],
+        )?;
+
+        Ok(workflow_id)
     }
 
     /// Convert a database row to a Workflow
@@ -1037,6 +1673,35 @@ impl<'a> WorkflowManager<'a> {
             requires_approval: row.get(6)?,
         })
     }
+
+    /// Convert a database row to a TransitionGuard
+    fn row_to_transition_guard(&self, row: &Row) -> SqliteResult<TransitionGuard> {
+        let type_str: String = row.get(2)?;
+        let guard_type = TransitionGuardType::from_str(&type_str)
+            .ok_or_else(|| rusqlite::Error::InvalidColumnType(2, "guard_type".to_string(), rusqlite::types::Type::Text))?;
+
+        Ok(TransitionGuard {
+            guard_id: Some(row.get(0)?),
+            transition_id: row.get(1)?,
+            guard_type,
+            parameter: row.get(3)?,
+        })
+    }
+
+    /// Convert a database row to a TransitionAction
+    fn row_to_transition_action(&self, row: &Row) -> SqliteResult<TransitionAction> {
+        let type_str: String = row.get(2)?;
+        let action_type = TransitionActionType::from_str(&type_str)
+            .ok_or_else(|| rusqlite::Error::InvalidColumnType(2, "action_type".to_string(), rusqlite::types::Type::Text))?;
+
+        Ok(TransitionAction {
+            action_id: Some(row.get(0)?),
+            transition_id: row.get(1)?,
+            action_type,
+            parameter: row.get(3)?,
+            sequence: row.get(4)?,
+        })
+    }
 }
 
 #[cfg(test)]
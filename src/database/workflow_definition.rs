@@ -0,0 +1,635 @@
+//! Workflow definition module for Implexa
+//!
+//! This module provides workflow definitions stored as JSON files in the `config/workflows`
+//! directory of a repository, so that workflows are reviewed and versioned like any other file.
+//! Definitions are validated before they are loaded: a workflow needs exactly one initial state,
+//! terminal states reachable from it, and transitions that only refer to defined states.
+//! Workflows in the database can be exported back to definition files.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use rusqlite::{Transaction, params};
+use serde::{Serialize, Deserialize};
+use crate::database::schema::{DatabaseError, DatabaseResult};
+use crate::database::connection_manager::ConnectionManager;
+use crate::database::workflow::{Workflow, WorkflowManager, TransitionGuardType, TransitionActionType};
+
+/// File extension of workflow definition files
+const DEFINITION_EXTENSION: &str = "json";
+
+/// Workflow as defined in a workflow file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowDefinition {
+    /// Name of the workflow
+    pub name: String,
+    /// Description of the workflow
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether the workflow is active
+    #[serde(default = "default_active")]
+    pub active: bool,
+    /// States of the workflow
+    pub states: Vec<StateDefinition>,
+    /// Transitions of the workflow
+    #[serde(default)]
+    pub transitions: Vec<TransitionDefinition>,
+}
+
+/// State as defined in a workflow file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateDefinition {
+    /// Name of the state
+    pub name: String,
+    /// Description of the state
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether this is the initial state
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub initial: bool,
+    /// Whether this is a terminal state
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub terminal: bool,
+}
+
+/// Transition as defined in a workflow file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransitionDefinition {
+    /// Name of the transition
+    pub name: String,
+    /// Name of the state the transition leaves
+    pub from: String,
+    /// Name of the state the transition enters
+    pub to: String,
+    /// Description of the transition
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Whether the transition requires approval
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub requires_approval: bool,
+    /// Guards of the transition
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub guards: Vec<RuleDefinition>,
+    /// Actions of the transition, in execution order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<RuleDefinition>,
+}
+
+/// Guard or action as defined in a workflow file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleDefinition {
+    /// Guard or action type, e.g. `FileAttached` or `CreateTag`
+    #[serde(rename = "type")]
+    pub rule_type: String,
+    /// Parameter of the guard or action
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameter: Option<String>,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+impl WorkflowDefinition {
+    /// Check the definition for structural problems
+    ///
+    /// # Returns
+    ///
+    /// A description of every problem found; empty if the definition is valid
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        if self.name.trim().is_empty() {
+            problems.push("Workflow has no name".to_string());
+        }
+
+        // State names must be unique
+        let mut state_names = HashSet::new();
+        for state in &self.states {
+            if !state_names.insert(state.name.as_str()) {
+                problems.push(format!("State {} is defined more than once", state.name));
+            }
+        }
+
+        // Exactly one initial state
+        let initial_states: Vec<&StateDefinition> = self.states.iter().filter(|state| state.initial).collect();
+        if initial_states.len() != 1 {
+            problems.push(format!("Workflow must have exactly one initial state, found {}", initial_states.len()));
+        }
+        if !self.states.iter().any(|state| state.terminal) {
+            problems.push("Workflow has no terminal state".to_string());
+        }
+
+        // Transitions must connect defined states, at most once per pair of states
+        let mut pairs = HashSet::new();
+        let mut successors: HashMap<&str, Vec<&str>> = HashMap::new();
+        for transition in &self.transitions {
+            let mut dangling = false;
+            for state in [&transition.from, &transition.to] {
+                if !state_names.contains(state.as_str()) {
+                    problems.push(format!("Transition {} refers to undefined state {}", transition.name, state));
+                    dangling = true;
+                }
+            }
+            if !pairs.insert((transition.from.as_str(), transition.to.as_str())) {
+                problems.push(format!("More than one transition from {} to {}", transition.from, transition.to));
+            }
+            if !dangling {
+                successors.entry(transition.from.as_str()).or_default().push(transition.to.as_str());
+            }
+
+            for guard in &transition.guards {
+                if TransitionGuardType::from_str(&guard.rule_type).is_none() {
+                    problems.push(format!("Transition {} has unknown guard type {}", transition.name, guard.rule_type));
+                }
+            }
+            for action in &transition.actions {
+                if TransitionActionType::from_str(&action.rule_type).is_none() {
+                    problems.push(format!("Transition {} has unknown action type {}", transition.name, action.rule_type));
+                }
+            }
+        }
+
+        // Every terminal state must be reachable from the initial state
+        if let [initial] = initial_states.as_slice() {
+            let mut reachable = HashSet::from([initial.name.as_str()]);
+            let mut queue = VecDeque::from([initial.name.as_str()]);
+            while let Some(state) = queue.pop_front() {
+                for next in successors.get(state).into_iter().flatten() {
+                    if reachable.insert(*next) {
+                        queue.push_back(*next);
+                    }
+                }
+            }
+
+            for state in self.states.iter().filter(|state| state.terminal) {
+                if !reachable.contains(state.name.as_str()) {
+                    problems.push(format!("Terminal state {} is not reachable from initial state {}", state.name, initial.name));
+                }
+            }
+        }
+
+        problems
+    }
+
+    /// Get the file name of the definition, derived from the workflow name
+    ///
+    /// # Returns
+    ///
+    /// A file name such as `part-workflow.json`
+    pub fn file_name(&self) -> String {
+        let slug: String = self.name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+            .collect();
+        let slug = slug.split('-').filter(|part| !part.is_empty()).collect::<Vec<_>>().join("-");
+        format!("{}.{}", slug, DEFINITION_EXTENSION)
+    }
+}
+
+/// Manager for loading and exporting workflow definition files
+pub struct WorkflowDefinitionManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+}
+
+impl<'a> WorkflowDefinitionManager<'a> {
+    /// Create a new WorkflowDefinitionManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new WorkflowDefinitionManager instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Read and validate the workflow definition files in a directory
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory containing the definition files
+    ///
+    /// # Returns
+    ///
+    /// The definitions in file name order; empty if the directory does not exist
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if a file could not be read or parsed, or listing the problems of
+    /// every invalid definition
+    pub fn read_directory(&self, directory: &Path) -> DatabaseResult<Vec<WorkflowDefinition>> {
+        if !directory.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut paths = fs::read_dir(directory)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        paths.retain(|path| path.is_file() && path.extension().is_some_and(|ext| ext == DEFINITION_EXTENSION));
+        paths.sort();
+
+        let mut definitions = Vec::new();
+        let mut problems = Vec::new();
+        let mut names = HashSet::new();
+        for path in paths {
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+            let definition: WorkflowDefinition = serde_json::from_str(&fs::read_to_string(&path)?)
+                .map_err(|e| DatabaseError::ValidationError(format!("{}: {}", file_name, e)))?;
+
+            problems.extend(definition.validate().into_iter().map(|problem| format!("{}: {}", file_name, problem)));
+            if !names.insert(definition.name.clone()) {
+                problems.push(format!("{}: Workflow {} is defined in more than one file", file_name, definition.name));
+            }
+            definitions.push(definition);
+        }
+
+        if !problems.is_empty() {
+            return Err(DatabaseError::ValidationError(problems.join("; ")));
+        }
+        Ok(definitions)
+    }
+
+    /// Load the workflow definition files in a directory into the database
+    ///
+    /// All files are validated before any workflow is changed. Workflows are matched by name,
+    /// states by name and transitions by their states, so revisions keep their current state.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory containing the definition files
+    ///
+    /// # Returns
+    ///
+    /// The IDs of the loaded workflows
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if a definition is invalid or removes a state that revisions are in
+    pub fn load_directory(&self, directory: &Path) -> DatabaseResult<Vec<i64>> {
+        let definitions = self.read_directory(directory)?;
+
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            definitions
+                .iter()
+                .map(|definition| self.import_definition_in_transaction(definition, tx))
+                .collect()
+        })
+    }
+
+    /// Create or update a workflow from a definition within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `definition` - The workflow definition
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The ID of the workflow
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the definition is invalid or removes a state that revisions are in
+    pub fn import_definition_in_transaction(&self, definition: &WorkflowDefinition, tx: &Transaction) -> DatabaseResult<i64> {
+        let problems = definition.validate();
+        if !problems.is_empty() {
+            return Err(DatabaseError::ValidationError(format!("Workflow {}: {}", definition.name, problems.join("; "))));
+        }
+
+        let workflow_manager = WorkflowManager::new(self.connection_manager);
+
+        // Create or update the workflow
+        let workflow_id = match workflow_manager.get_workflow_by_name_in_transaction(&definition.name, tx)? {
+            Some(workflow) => {
+                let workflow_id = workflow.workflow_id.unwrap_or_default();
+                tx.execute(
+                    "UPDATE Workflows SET description = ?2, active = ?3 WHERE workflow_id = ?1",
+                    params![workflow_id, definition.description, definition.active],
+                )?;
+                workflow_id
+            }
+            None => {
+                let workflow = Workflow::new(definition.name.clone(), definition.description.clone(), definition.active);
+                workflow_manager.create_workflow_in_transaction(&workflow, tx)?
+            }
+        };
+
+        // Remove the transitions that are no longer defined, with their guards and actions
+        let existing_states: HashMap<String, i64> = workflow_manager
+            .get_workflow_states_in_transaction(workflow_id, tx)?
+            .into_iter()
+            .map(|state| (state.name, state.state_id.unwrap_or_default()))
+            .collect();
+        let state_name = |state_id: i64| existing_states.iter().find(|(_, id)| **id == state_id).map(|(name, _)| name.as_str());
+        let defined_pairs: HashSet<(&str, &str)> = definition.transitions
+            .iter()
+            .map(|transition| (transition.from.as_str(), transition.to.as_str()))
+            .collect();
+        for transition in workflow_manager.get_workflow_transitions_in_transaction(workflow_id, tx)? {
+            let pair = (state_name(transition.from_state_id).unwrap_or_default(), state_name(transition.to_state_id).unwrap_or_default());
+            if !defined_pairs.contains(&pair) {
+                self.delete_transition_in_transaction(transition.transition_id.unwrap_or_default(), tx)?;
+            }
+        }
+
+        // Remove the states that are no longer defined, unless revisions are in them
+        let defined_states: HashSet<&str> = definition.states.iter().map(|state| state.name.as_str()).collect();
+        for (name, state_id) in &existing_states {
+            if defined_states.contains(name.as_str()) {
+                continue;
+            }
+            let revisions: i64 = tx.query_row(
+                "SELECT COUNT(*) FROM RevisionWorkflowStates WHERE state_id = ?1",
+                params![state_id],
+                |row| row.get(0),
+            )?;
+            if revisions > 0 {
+                return Err(DatabaseError::ValidationError(format!(
+                    "Workflow {}: state {} cannot be removed because {} revisions are in it",
+                    definition.name, name, revisions
+                )));
+            }
+            tx.execute(
+                "DELETE FROM WorkflowTransitions WHERE from_state_id = ?1 OR to_state_id = ?1",
+                params![state_id],
+            )?;
+            tx.execute("DELETE FROM WorkflowStates WHERE state_id = ?1", params![state_id])?;
+        }
+
+        // Create or update the states
+        let mut state_ids = HashMap::new();
+        for state in &definition.states {
+            let state_id = match existing_states.get(&state.name) {
+                Some(state_id) => {
+                    tx.execute(
+                        "UPDATE WorkflowStates SET description = ?2, is_initial = ?3, is_terminal = ?4 WHERE state_id = ?1",
+                        params![state_id, state.description, state.initial, state.terminal],
+                    )?;
+                    *state_id
+                }
+                None => {
+                    tx.execute(
+                        "INSERT INTO WorkflowStates (workflow_id, name, description, is_initial, is_terminal)
+                         VALUES (?1, ?2, ?3, ?4, ?5)",
+                        params![workflow_id, state.name, state.description, state.initial, state.terminal],
+                    )?;
+                    tx.last_insert_rowid()
+                }
+            };
+            state_ids.insert(state.name.as_str(), state_id);
+        }
+
+        // Create or update the transitions and replace their guards and actions
+        for transition in &definition.transitions {
+            let from_state_id = state_ids[transition.from.as_str()];
+            let to_state_id = state_ids[transition.to.as_str()];
+            tx.execute(
+                "INSERT INTO WorkflowTransitions (workflow_id, from_state_id, to_state_id, name, description, requires_approval)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                 ON CONFLICT(workflow_id, from_state_id, to_state_id)
+                 DO UPDATE SET name = excluded.name, description = excluded.description, requires_approval = excluded.requires_approval",
+                params![workflow_id, from_state_id, to_state_id, transition.name, transition.description, transition.requires_approval],
+            )?;
+            let transition_id: i64 = tx.query_row(
+                "SELECT transition_id FROM WorkflowTransitions
+                 WHERE workflow_id = ?1 AND from_state_id = ?2 AND to_state_id = ?3",
+                params![workflow_id, from_state_id, to_state_id],
+                |row| row.get(0),
+            )?;
+
+            tx.execute("DELETE FROM WorkflowTransitionGuards WHERE transition_id = ?1", params![transition_id])?;
+            for guard in &transition.guards {
+                tx.execute(
+                    "INSERT INTO WorkflowTransitionGuards (transition_id, guard_type, parameter) VALUES (?1, ?2, ?3)",
+                    params![transition_id, guard.rule_type, guard.parameter],
+                )?;
+            }
+            tx.execute("DELETE FROM WorkflowTransitionActions WHERE transition_id = ?1", params![transition_id])?;
+            for (sequence, action) in transition.actions.iter().enumerate() {
+                tx.execute(
+                    "INSERT INTO WorkflowTransitionActions (transition_id, action_type, parameter, sequence) VALUES (?1, ?2, ?3, ?4)",
+                    params![transition_id, action.rule_type, action.parameter, sequence as i64],
+                )?;
+            }
+        }
+
+        Ok(workflow_id)
+    }
+
+    /// Get the definition of a workflow in the database
+    ///
+    /// # Arguments
+    ///
+    /// * `workflow_id` - The ID of the workflow
+    ///
+    /// # Returns
+    ///
+    /// The definition of the workflow
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workflow could not be retrieved
+    pub fn get_definition(&self, workflow_id: i64) -> DatabaseResult<WorkflowDefinition> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.get_definition_in_transaction(workflow_id, tx)
+        })
+    }
+
+    /// Get the definition of a workflow in the database within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `workflow_id` - The ID of the workflow
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The definition of the workflow
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workflow could not be retrieved
+    pub fn get_definition_in_transaction(&self, workflow_id: i64, tx: &Transaction) -> DatabaseResult<WorkflowDefinition> {
+        let workflow_manager = WorkflowManager::new(self.connection_manager);
+        let workflow = workflow_manager.get_workflow_in_transaction(workflow_id, tx)?;
+
+        // Keep the states and transitions in the order they were created
+        let mut states = workflow_manager.get_workflow_states_in_transaction(workflow_id, tx)?;
+        states.sort_by_key(|state| state.state_id);
+        let mut transitions = workflow_manager.get_workflow_transitions_in_transaction(workflow_id, tx)?;
+        transitions.sort_by_key(|transition| transition.transition_id);
+
+        let state_names: HashMap<i64, String> = states
+            .iter()
+            .map(|state| (state.state_id.unwrap_or_default(), state.name.clone()))
+            .collect();
+
+        let mut transition_definitions = Vec::new();
+        for transition in transitions {
+            let transition_id = transition.transition_id.unwrap_or_default();
+            let guards = workflow_manager.get_transition_guards_in_transaction(transition_id, tx)?
+                .into_iter()
+                .map(|guard| RuleDefinition { rule_type: guard.guard_type.to_str().to_string(), parameter: guard.parameter })
+                .collect();
+            let actions = workflow_manager.get_transition_actions_in_transaction(transition_id, tx)?
+                .into_iter()
+                .map(|action| RuleDefinition { rule_type: action.action_type.to_str().to_string(), parameter: action.parameter })
+                .collect();
+
+            transition_definitions.push(TransitionDefinition {
+                name: transition.name,
+                from: state_names.get(&transition.from_state_id).cloned().unwrap_or_default(),
+                to: state_names.get(&transition.to_state_id).cloned().unwrap_or_default(),
+                description: transition.description,
+                requires_approval: transition.requires_approval,
+                guards,
+                actions,
+            });
+        }
+
+        Ok(WorkflowDefinition {
+            name: workflow.name,
+            description: workflow.description,
+            active: workflow.active,
+            states: states
+                .into_iter()
+                .map(|state| StateDefinition {
+                    name: state.name,
+                    description: state.description,
+                    initial: state.is_initial,
+                    terminal: state.is_terminal,
+                })
+                .collect(),
+            transitions: transition_definitions,
+        })
+    }
+
+    /// Export all workflows in the database to definition files
+    ///
+    /// Existing files of the exported workflows are overwritten; other files are left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory to write the definition files to
+    ///
+    /// # Returns
+    ///
+    /// The paths of the written files
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workflows could not be retrieved or a file could not be written
+    pub fn export_directory(&self, directory: &Path) -> DatabaseResult<Vec<PathBuf>> {
+        let definitions = self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let workflows = WorkflowManager::new(self.connection_manager).get_all_workflows_in_transaction(tx)?;
+            workflows
+                .iter()
+                .map(|workflow| self.get_definition_in_transaction(workflow.workflow_id.unwrap_or_default(), tx))
+                .collect::<DatabaseResult<Vec<_>>>()
+        })?;
+
+        fs::create_dir_all(directory)?;
+        let mut paths = Vec::new();
+        for definition in definitions {
+            let path = directory.join(definition.file_name());
+            fs::write(&path, serde_json::to_string_pretty(&definition)? + "\n")?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    /// Delete a transition with its guards and actions
+    fn delete_transition_in_transaction(&self, transition_id: i64, tx: &Transaction) -> DatabaseResult<()> {
+        tx.execute("DELETE FROM WorkflowTransitionGuards WHERE transition_id = ?1", params![transition_id])?;
+        tx.execute("DELETE FROM WorkflowTransitionActions WHERE transition_id = ?1", params![transition_id])?;
+        tx.execute("DELETE FROM WorkflowTransitions WHERE transition_id = ?1", params![transition_id])?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::DatabaseManager;
+    use crate::database::workflow::DEFAULT_PART_WORKFLOW;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_workflow_definition_round_trip_and_validation() {
+        // Create a temporary directory for the test database and workflow files
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let workflows_dir = temp_dir.path().join("config").join("workflows");
+
+        // Create a new database manager and initialize the schema
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let connection_manager = db_manager.connection_manager();
+
+        // Export the default part workflow
+        let workflow_manager = WorkflowManager::new(connection_manager);
+        let workflow_id = workflow_manager.create_default_part_workflow().unwrap();
+        let definition_manager = WorkflowDefinitionManager::new(connection_manager);
+        let paths = definition_manager.export_directory(&workflows_dir).unwrap();
+        assert_eq!(paths, vec![workflows_dir.join("part-workflow.json")]);
+
+        // The exported file is valid and loads back into the same workflow
+        let definitions = definition_manager.read_directory(&workflows_dir).unwrap();
+        assert_eq!(definitions[0].name, DEFAULT_PART_WORKFLOW);
+        assert!(definitions[0].validate().is_empty());
+        assert_eq!(definition_manager.load_directory(&workflows_dir).unwrap(), vec![workflow_id]);
+        assert_eq!(definition_manager.get_definition(workflow_id).unwrap(), definitions[0]);
+
+        // Edit the file: add a guard and drop the Obsolete state
+        let mut definition = definitions[0].clone();
+        definition.states.retain(|state| state.name != "Obsolete");
+        definition.transitions.retain(|transition| transition.to != "Obsolete");
+        definition.states.iter_mut().find(|state| state.name == "Released").unwrap().terminal = true;
+        definition.transitions[0].guards.push(RuleDefinition {
+            rule_type: "FileAttached".to_string(),
+            parameter: Some("Manufacturing".to_string()),
+        });
+        fs::write(&paths[0], serde_json::to_string_pretty(&definition).unwrap()).unwrap();
+        definition_manager.load_directory(&workflows_dir).unwrap();
+        assert_eq!(definition_manager.get_definition(workflow_id).unwrap(), definition);
+        assert_eq!(workflow_manager.get_workflow_states(workflow_id).unwrap().len(), 3);
+
+        // Invalid definitions are rejected with every problem listed
+        let invalid = WorkflowDefinition {
+            name: "Broken".to_string(),
+            description: None,
+            active: true,
+            states: vec![
+                StateDefinition { name: "Open".to_string(), description: None, initial: true, terminal: false },
+                StateDefinition { name: "Closed".to_string(), description: None, initial: true, terminal: true },
+            ],
+            transitions: vec![TransitionDefinition {
+                name: "Close".to_string(),
+                from: "Open".to_string(),
+                to: "Done".to_string(),
+                description: None,
+                requires_approval: false,
+                guards: Vec::new(),
+                actions: Vec::new(),
+            }],
+        };
+        let problems = invalid.validate();
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].contains("exactly one initial state"));
+        assert!(problems[1].contains("undefined state Done"));
+
+        fs::write(workflows_dir.join(invalid.file_name()), serde_json::to_string(&invalid).unwrap()).unwrap();
+        assert!(matches!(
+            definition_manager.load_directory(&workflows_dir),
+            Err(DatabaseError::ValidationError(message)) if message.starts_with("broken.json")
+        ));
+    }
+}
@@ -0,0 +1,868 @@
+//! Workflow engine module for Implexa
+//!
+//! This module drives the lifecycle of part revisions from the configurable workflow tables.
+//! Each category can be assigned a workflow (parts of other categories use the default part
+//! workflow), each revision tracks its current workflow state, and only transitions defined
+//! in the workflow can be executed. Transitions marked `requires_approval` and transitions to
+//! Released can only be executed once the revision has been approved, and all guards of a
+//! transition must pass. The actions of an executed transition are returned to the caller, which
+//! runs them against the repository.
+
+use rusqlite::{Transaction, params, Row, Result as SqliteResult, OptionalExtension};
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::database::schema::{DatabaseError, DatabaseResult};
+use crate::database::connection_manager::ConnectionManager;
+use crate::database::revision::{Revision, RevisionStatus, RevisionManager};
+use crate::database::approval_policy::{ApprovalPolicyManager, ApprovalDecision};
+use crate::database::part::PartManager;
+use crate::database::deviation::{DeviationManager, DeviationStatus};
+use crate::database::workflow::{
+    Workflow, WorkflowState, WorkflowTransition, WorkflowManager, DEFAULT_PART_WORKFLOW,
+    TransitionGuard, TransitionGuardType, TransitionAction,
+};
+use crate::database::part_management::{PartManagementError, PartManagementResult};
+
+/// Current workflow state of a revision
+#[derive(Debug, Clone)]
+pub struct RevisionWorkflowState {
+    /// ID of the revision
+    pub revision_id: i64,
+    /// Workflow governing the revision
+    pub workflow: Workflow,
+    /// Current state of the revision
+    pub state: WorkflowState,
+    /// User who moved the revision into the current state
+    pub updated_by: String,
+    /// Date the revision entered the current state
+    pub updated_date: SystemTime,
+}
+
+/// Reason a transition guard did not pass
+#[derive(Debug, Clone)]
+pub struct GuardFailure {
+    /// ID of the guard that failed
+    pub guard_id: i64,
+    /// Type of the guard that failed
+    pub guard_type: TransitionGuardType,
+    /// Part the failure refers to, e.g. an unreleased child part
+    pub part_id: Option<i64>,
+    /// Human-readable reason
+    pub message: String,
+}
+
+/// Result of an executed transition
+#[derive(Debug, Clone)]
+pub struct TransitionOutcome {
+    /// The executed transition
+    pub transition: WorkflowTransition,
+    /// The new state of the revision
+    pub state: WorkflowState,
+    /// Actions of the transition that remain to be run, in execution order
+    pub actions: Vec<TransitionAction>,
+}
+
+/// Event emitted by a transition action
+#[derive(Debug, Clone)]
+pub struct WorkflowEvent {
+    /// Unique identifier for the event
+    pub event_id: Option<i64>,
+    /// Name of the event
+    pub name: String,
+    /// ID of the revision the transition was executed on
+    pub revision_id: i64,
+    /// ID of the executed transition
+    pub transition_id: i64,
+    /// User who executed the transition
+    pub performed_by: String,
+    /// Date the event was emitted
+    pub created_date: SystemTime,
+}
+
+/// Engine executing workflow transitions on revisions
+pub struct WorkflowEngine<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+    /// Whether SignedRelease guards are left to the caller, which verifies the signed release
+    signed_release_deferred: bool,
+}
+
+impl<'a> WorkflowEngine<'a> {
+    /// Create a new WorkflowEngine
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new WorkflowEngine instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager, signed_release_deferred: false }
+    }
+
+    /// Leave the SignedRelease guards to the caller
+    ///
+    /// The signature of a release can only be verified once the release has been committed and
+    /// tagged, after the transition. Without this, the SignedRelease guard fails, so that
+    /// guarded transitions can only be executed through the release path of the part
+    /// management, which verifies the signature before the release is committed.
+    ///
+    /// # Returns
+    ///
+    /// The WorkflowEngine with deferred SignedRelease guards
+    pub fn with_deferred_signed_release(mut self) -> Self {
+        self.signed_release_deferred = true;
+        self
+    }
+
+    /// Assign a workflow to a category
+    ///
+    /// Revisions that are already tracked keep the workflow they were started with.
+    ///
+    /// # Arguments
+    ///
+    /// * `category_id` - The ID of the category
+    /// * `workflow_id` - The ID of the workflow, or None to use the default part workflow
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the workflow was successfully assigned
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workflow has no initial state or could not be assigned
+    pub fn assign_category_workflow(&self, category_id: i64, workflow_id: Option<i64>) -> DatabaseResult<()> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            if let Some(workflow_id) = workflow_id {
+                // Fail early instead of when the first revision is started
+                WorkflowManager::new(self.connection_manager).get_initial_state_in_transaction(workflow_id, tx)
+                    .map_err(|_| DatabaseError::ValidationError(format!("Workflow {} has no initial state", workflow_id)))?;
+            }
+
+            tx.execute(
+                "UPDATE Categories SET workflow_id = ?2 WHERE category_id = ?1",
+                params![category_id, workflow_id],
+            )?;
+            Ok(())
+        })
+    }
+
+    /// Get the workflow that governs new revisions of a part within an existing transaction
+    ///
+    /// The workflow assigned to the part's category is used; otherwise the default part workflow,
+    /// which is created if it does not exist yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `part_id` - The ID of the part
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The workflow of the part
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workflow could not be determined
+    pub fn get_workflow_for_part_in_transaction(&self, part_id: i64, tx: &Transaction) -> DatabaseResult<Workflow> {
+        let workflow_manager = WorkflowManager::new(self.connection_manager);
+
+        let category_workflow_id: Option<i64> = tx.query_row(
+            "SELECT c.workflow_id
+             FROM Parts p
+             JOIN Categories c ON c.name = p.category
+             WHERE p.part_id = ?1",
+            params![part_id],
+            |row| row.get(0),
+        ).optional()?.flatten();
+
+        if let Some(workflow_id) = category_workflow_id {
+            let workflow = workflow_manager.get_workflow_in_transaction(workflow_id, tx)?;
+            return Ok(workflow);
+        }
+
+        if let Some(workflow) = workflow_manager.get_workflow_by_name_in_transaction(DEFAULT_PART_WORKFLOW, tx)? {
+            return Ok(workflow);
+        }
+        let workflow_id = workflow_manager.create_default_part_workflow_in_transaction(tx)?;
+        workflow_manager.get_workflow_in_transaction(workflow_id, tx)
+    }
+
+    /// Put a new revision into the initial state of its part's workflow within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    /// * `performed_by` - The user starting the revision
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The initial state of the revision
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workflow has no initial state or the state could not be stored
+    pub fn start_revision_in_transaction(&self, revision_id: i64, performed_by: &str, tx: &Transaction) -> DatabaseResult<WorkflowState> {
+        let revision = RevisionManager::new(self.connection_manager).get_revision_in_transaction(revision_id, tx)?;
+        let workflow = self.get_workflow_for_part_in_transaction(revision.part_id, tx)?;
+        let workflow_id = workflow.workflow_id.unwrap_or_default();
+        let initial_state = WorkflowManager::new(self.connection_manager).get_initial_state_in_transaction(workflow_id, tx)?;
+
+        self.set_state_in_transaction(&revision, workflow_id, &initial_state, performed_by, tx)?;
+        Ok(initial_state)
+    }
+
+    /// Get the current workflow state of a revision
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    ///
+    /// # Returns
+    ///
+    /// The current workflow state of the revision
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the state could not be determined
+    pub fn get_revision_state(&self, revision_id: i64) -> DatabaseResult<RevisionWorkflowState> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.get_revision_state_in_transaction(revision_id, tx)
+        })
+    }
+
+    /// Get the current workflow state of a revision within an existing transaction
+    ///
+    /// Revisions that were created before they were tracked are placed in the state of their
+    /// part's workflow that has the same name as their status.
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The current workflow state of the revision
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the state could not be determined
+    pub fn get_revision_state_in_transaction(&self, revision_id: i64, tx: &Transaction) -> DatabaseResult<RevisionWorkflowState> {
+        let workflow_manager = WorkflowManager::new(self.connection_manager);
+
+        let tracked: Option<(i64, i64, String, i64)> = tx.query_row(
+            "SELECT workflow_id, state_id, updated_by, updated_date
+             FROM RevisionWorkflowStates
+             WHERE revision_id = ?1",
+            params![revision_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        ).optional()?;
+
+        if let Some((workflow_id, state_id, updated_by, updated_date)) = tracked {
+            return Ok(RevisionWorkflowState {
+                revision_id,
+                workflow: workflow_manager.get_workflow_in_transaction(workflow_id, tx)?,
+                state: workflow_manager.get_workflow_state_in_transaction(state_id, tx)?,
+                updated_by,
+                updated_date: UNIX_EPOCH + std::time::Duration::from_secs(updated_date as u64),
+            });
+        }
+
+        // Fall back to the state named after the revision status
+        let revision = RevisionManager::new(self.connection_manager).get_revision_in_transaction(revision_id, tx)?;
+        let workflow = self.get_workflow_for_part_in_transaction(revision.part_id, tx)?;
+        let state = workflow_manager
+            .get_workflow_state_by_name_in_transaction(workflow.workflow_id.unwrap_or_default(), revision.status.to_str(), tx)?
+            .ok_or_else(|| DatabaseError::ValidationError(format!(
+                "Revision {} has status {}, which is not a state of workflow {}",
+                revision_id, revision.status.to_str(), workflow.name
+            )))?;
+
+        Ok(RevisionWorkflowState {
+            revision_id,
+            workflow,
+            state,
+            updated_by: revision.created_by,
+            updated_date: revision.created_date,
+        })
+    }
+
+    /// Get the transitions that can be executed from the current state of a revision
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    ///
+    /// # Returns
+    ///
+    /// A vector of transitions leaving the current state
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the transitions could not be retrieved
+    pub fn get_available_transitions(&self, revision_id: i64) -> DatabaseResult<Vec<WorkflowTransition>> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let current = self.get_revision_state_in_transaction(revision_id, tx)?;
+            WorkflowManager::new(self.connection_manager)
+                .get_transitions_from_state_in_transaction(current.state.state_id.unwrap_or_default(), tx)
+        })
+    }
+
+    /// Execute a workflow transition on a revision
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    /// * `transition_id` - The ID of the transition to execute
+    /// * `performed_by` - The user executing the transition
+    ///
+    /// # Returns
+    ///
+    /// The outcome of the transition
+    ///
+    /// # Errors
+    ///
+    /// Returns a PartManagementError if the transition is not available, its approval requirement is
+    /// not met or one of its guards fails
+    pub fn execute_transition(&self, revision_id: i64, transition_id: i64, performed_by: &str) -> PartManagementResult<TransitionOutcome> {
+        self.connection_manager.transaction::<_, _, PartManagementError>(|tx| {
+            self.execute_transition_in_transaction(revision_id, transition_id, performed_by, tx)
+        })
+    }
+
+    /// Execute a workflow transition on a revision within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    /// * `transition_id` - The ID of the transition to execute
+    /// * `performed_by` - The user executing the transition
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The outcome of the transition
+    ///
+    /// # Errors
+    ///
+    /// Returns a PartManagementError if the transition is not available, its approval requirement is
+    /// not met or one of its guards fails
+    pub fn execute_transition_in_transaction(&self, revision_id: i64, transition_id: i64, performed_by: &str, tx: &Transaction) -> PartManagementResult<TransitionOutcome> {
+        let current = self.get_revision_state_in_transaction(revision_id, tx)?;
+        let transition = self.find_available_transition_in_transaction(&current, transition_id, tx)?;
+
+        self.apply_transition_in_transaction(&current, &transition, performed_by, tx)
+    }
+
+    /// Check the guards of a transition without executing it
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    /// * `transition_id` - The ID of the transition
+    ///
+    /// # Returns
+    ///
+    /// The reasons the guards of the transition do not pass; empty if they all pass
+    ///
+    /// # Errors
+    ///
+    /// Returns a PartManagementError if the transition is not available from the current state
+    pub fn check_transition(&self, revision_id: i64, transition_id: i64) -> PartManagementResult<Vec<GuardFailure>> {
+        self.connection_manager.transaction::<_, _, PartManagementError>(|tx| {
+            let current = self.get_revision_state_in_transaction(revision_id, tx)?;
+            let transition = self.find_available_transition_in_transaction(&current, transition_id, tx)?;
+            Ok(self.check_guards_in_transaction(revision_id, &transition, tx)?)
+        })
+    }
+
+    /// Check the guards of a transition for a revision within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    /// * `transition` - The transition
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The reasons the guards of the transition do not pass; empty if they all pass
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the guards could not be evaluated
+    pub fn check_guards_in_transaction(&self, revision_id: i64, transition: &WorkflowTransition, tx: &Transaction) -> DatabaseResult<Vec<GuardFailure>> {
+        let revision = RevisionManager::new(self.connection_manager).get_revision_in_transaction(revision_id, tx)?;
+        let guards = WorkflowManager::new(self.connection_manager)
+            .get_transition_guards_in_transaction(transition.transition_id.unwrap_or_default(), tx)?;
+
+        let mut failures = Vec::new();
+        for guard in &guards {
+            failures.extend(self.check_guard_in_transaction(&revision, guard, tx)?);
+        }
+        Ok(failures)
+    }
+
+    /// Record an event emitted by a transition action within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the event
+    /// * `revision_id` - The ID of the revision
+    /// * `transition_id` - The ID of the executed transition
+    /// * `performed_by` - The user who executed the transition
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The ID of the recorded event
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the event could not be recorded
+    pub fn record_event_in_transaction(&self, name: &str, revision_id: i64, transition_id: i64, performed_by: &str, tx: &Transaction) -> DatabaseResult<i64> {
+        tx.execute(
+            "INSERT INTO WorkflowEvents (name, revision_id, transition_id, performed_by, created_date)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                name,
+                revision_id,
+                transition_id,
+                performed_by,
+                SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64,
+            ],
+        )?;
+        Ok(tx.last_insert_rowid())
+    }
+
+    /// Get the events emitted for a revision, oldest first
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    ///
+    /// # Returns
+    ///
+    /// A vector of events of the revision
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the events could not be retrieved
+    pub fn get_events_for_revision(&self, revision_id: i64) -> DatabaseResult<Vec<WorkflowEvent>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT event_id, name, revision_id, transition_id, performed_by, created_date
+                 FROM WorkflowEvents
+                 WHERE revision_id = ?1
+                 ORDER BY event_id",
+            )?;
+            let events = stmt.query_map(params![revision_id], |row| self.row_to_event(row))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(events)
+        })
+    }
+
+    /// Find a transition leaving the current state of a revision
+    fn find_available_transition_in_transaction(&self, current: &RevisionWorkflowState, transition_id: i64, tx: &Transaction) -> PartManagementResult<WorkflowTransition> {
+        WorkflowManager::new(self.connection_manager)
+            .get_transitions_from_state_in_transaction(current.state.state_id.unwrap_or_default(), tx)?
+            .into_iter()
+            .find(|transition| transition.transition_id == Some(transition_id))
+            .ok_or_else(|| PartManagementError::InvalidStateTransition(format!(
+                "Transition {} is not available from state {}", transition_id, current.state.name
+            )))
+    }
+
+    /// Move a revision to the state with a given name within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `revision_id` - The ID of the revision
+    /// * `state_name` - The name of the target state
+    /// * `performed_by` - The user executing the transition
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The outcome of the transition
+    ///
+    /// # Errors
+    ///
+    /// Returns a PartManagementError if the workflow has no transition from the current state to
+    /// the target state, its approval requirement is not met or one of its guards fails
+    pub fn transition_to_state_in_transaction(&self, revision_id: i64, state_name: &str, performed_by: &str, tx: &Transaction) -> PartManagementResult<TransitionOutcome> {
+        let workflow_manager = WorkflowManager::new(self.connection_manager);
+        let current = self.get_revision_state_in_transaction(revision_id, tx)?;
+
+        let target = workflow_manager
+            .get_workflow_state_by_name_in_transaction(current.workflow.workflow_id.unwrap_or_default(), state_name, tx)?;
+        let transition = target.and_then(|target| {
+            workflow_manager
+                .get_transitions_from_state_in_transaction(current.state.state_id.unwrap_or_default(), tx)
+                .map(|transitions| transitions.into_iter().find(|transition| Some(transition.to_state_id) == target.state_id))
+                .transpose()
+        }).transpose()?;
+
+        let transition = transition.ok_or_else(|| PartManagementError::InvalidStateTransition(format!(
+            "Workflow {} has no transition from {} to {}", current.workflow.name, current.state.name, state_name
+        )))?;
+
+        self.apply_transition_in_transaction(&current, &transition, performed_by, tx)
+    }
+
+    /// Check the requirements of a transition and move the revision to its target state
+    fn apply_transition_in_transaction(
+        &self,
+        current: &RevisionWorkflowState,
+        transition: &WorkflowTransition,
+        performed_by: &str,
+        tx: &Transaction,
+    ) -> PartManagementResult<TransitionOutcome> {
+        let revision_id = current.revision_id;
+
+        // Releasing always requires an approved review, whether or not the workflow marks the transition
+        let workflow_manager = WorkflowManager::new(self.connection_manager);
+        let target = workflow_manager.get_workflow_state_in_transaction(transition.to_state_id, tx)?;
+        let releases = RevisionStatus::from_str(&target.name) == Some(RevisionStatus::Released);
+        if transition.requires_approval || releases {
+            let evaluation = ApprovalPolicyManager::new(self.connection_manager)
+                .evaluate_in_transaction(revision_id, transition.transition_id, tx)?;
+            if evaluation.decision != ApprovalDecision::Approved {
+                let mut message = format!(
+                    "Transition {} requires the revision to be approved ({} of {} approvals",
+                    transition.name, evaluation.approvals, evaluation.quorum
+                );
+                if !evaluation.missing_groups.is_empty() {
+                    message.push_str(&format!("; missing approval from {}", evaluation.missing_groups.join(", ")));
+                }
+                message.push(')');
+                return Err(PartManagementError::ApprovalRequired(message));
+            }
+        }
+
+        let failures = self.check_guards_in_transaction(revision_id, transition, tx)?;
+        if !failures.is_empty() {
+            return Err(PartManagementError::GuardsFailed(failures));
+        }
+
+        let revision = RevisionManager::new(self.connection_manager).get_revision_in_transaction(revision_id, tx)?;
+        self.set_state_in_transaction(&revision, transition.workflow_id, &target, performed_by, tx)?;
+
+        Ok(TransitionOutcome {
+            transition: transition.clone(),
+            state: target,
+            actions: workflow_manager.get_transition_actions_in_transaction(transition.transition_id.unwrap_or_default(), tx)?,
+        })
+    }
+
+    /// Evaluate a single guard for a revision
+    fn check_guard_in_transaction(&self, revision: &Revision, guard: &TransitionGuard, tx: &Transaction) -> DatabaseResult<Vec<GuardFailure>> {
+        let failure = |part_id: Option<i64>, message: String| GuardFailure {
+            guard_id: guard.guard_id.unwrap_or_default(),
+            guard_type: guard.guard_type,
+            part_id,
+            message,
+        };
+        let revision_id = revision.revision_id.unwrap_or_default();
+
+        match guard.guard_type {
+            TransitionGuardType::ChildrenReleased => {
+                let mut stmt = tx.prepare(
+                    "SELECT DISTINCT r.child_part_id
+                     FROM Relationships r
+                     WHERE r.parent_part_id = ?1 AND r.type = 'Assembly'
+                     AND NOT EXISTS (
+                         SELECT 1 FROM Revisions v
+                         WHERE v.part_id = r.child_part_id AND v.status = 'Released'
+                     )
+                     ORDER BY r.child_part_id",
+                )?;
+                let unreleased = stmt.query_map(params![revision.part_id], |row| row.get::<_, i64>(0))?
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let part_manager = PartManager::new(self.connection_manager);
+                let mut failures = Vec::new();
+                for child_part_id in unreleased {
+                    let part_number = part_manager.get_part_in_transaction(child_part_id, tx)?
+                        .display_part_number_in_transaction(tx);
+                    failures.push(failure(Some(child_part_id), format!("Child part {} has no released revision", part_number)));
+                }
+                Ok(failures)
+            }
+            TransitionGuardType::FileAttached => {
+                let Some(file_type) = guard.parameter.as_deref().map(str::trim).filter(|t| !t.is_empty()) else {
+                    return Ok(vec![failure(None, "File guard has no file type".to_string())]);
+                };
+                let count: i64 = tx.query_row(
+                    "SELECT COUNT(*) FROM Files
+                     WHERE type = ?1 AND (revision_id = ?2 OR part_id = ?3)",
+                    params![file_type, revision_id, revision.part_id],
+                    |row| row.get(0),
+                )?;
+                if count == 0 {
+                    Ok(vec![failure(None, format!("No {} file is attached", file_type))])
+                } else {
+                    Ok(Vec::new())
+                }
+            }
+            TransitionGuardType::PropertiesPresent => {
+                let mut failures = Vec::new();
+                let keys = guard.parameter.as_deref().unwrap_or_default()
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|key| !key.is_empty());
+                for key in keys {
+                    let present: bool = tx.query_row(
+                        "SELECT EXISTS (
+                             SELECT 1 FROM Properties
+                             WHERE key = ?1 AND (revision_id = ?2 OR part_id = ?3)
+                             AND value IS NOT NULL AND value <> ''
+                         )",
+                        params![key, revision_id, revision.part_id],
+                        |row| row.get(0),
+                    )?;
+                    if !present {
+                        failures.push(failure(None, format!("Required property {} is missing", key)));
+                    }
+                }
+                Ok(failures)
+            }
+            TransitionGuardType::NoOpenDeviations => {
+                let open = DeviationManager::new(self.connection_manager)
+                    .get_open_deviations_for_revision_in_transaction(revision_id, tx)?;
+                Ok(open
+                    .into_iter()
+                    .map(|deviation| failure(None, format!(
+                        "{} {} is still {}", deviation.deviation_type.to_str(), deviation.deviation_number,
+                        if deviation.status == DeviationStatus::Pending { "awaiting approval" } else { "active" }
+                    )))
+                    .collect())
+            }
+            TransitionGuardType::SignedRelease => {
+                // The signature can only be verified once the release has been committed and
+                // tagged, which happens after the transition
+                if self.signed_release_deferred {
+                    Ok(Vec::new())
+                } else {
+                    Ok(vec![failure(None, "The release must be signed; release the revision to execute this transition".to_string())])
+                }
+            }
+        }
+    }
+
+    /// Store the workflow state of a revision and keep the revision status in sync
+    ///
+    /// States named like a revision status (Draft, In Review, Released, Obsolete) also set the
+    /// revision status; other states leave it unchanged.
+    fn set_state_in_transaction(
+        &self,
+        revision: &Revision,
+        workflow_id: i64,
+        state: &WorkflowState,
+        performed_by: &str,
+        tx: &Transaction,
+    ) -> DatabaseResult<()> {
+        let revision_id = revision.revision_id.unwrap_or_default();
+
+        tx.execute(
+            "INSERT OR REPLACE INTO RevisionWorkflowStates (revision_id, workflow_id, state_id, updated_by, updated_date)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                revision_id,
+                workflow_id,
+                state.state_id,
+                performed_by,
+                SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64,
+            ],
+        )?;
+
+        if let Some(status) = RevisionStatus::from_str(&state.name) {
+            if status != revision.status {
+                RevisionManager::new(self.connection_manager)
+                    .with_actor(performed_by)
+                    .update_status_in_transaction(revision_id, status, tx)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Convert a database row to a WorkflowEvent
+    fn row_to_event(&self, row: &Row) -> SqliteResult<WorkflowEvent> {
+        let created_secs: i64 = row.get(5)?;
+        Ok(WorkflowEvent {
+            event_id: Some(row.get(0)?),
+            name: row.get(1)?,
+            revision_id: row.get(2)?,
+            transition_id: row.get(3)?,
+            performed_by: row.get(4)?,
+            created_date: UNIX_EPOCH + std::time::Duration::from_secs(created_secs as u64),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::DatabaseManager;
+    use crate::database::part::{Part, PartManager};
+    use crate::database::approval::{Approval, ApprovalStatus, ApprovalManager};
+    use crate::database::category::CategoryManager;
+    use crate::database::relationship::{Relationship, RelationshipType, RelationshipManager};
+    use crate::database::file::{File, FileType, FileManager};
+    use crate::database::property::{Property, PropertyType, PropertyManager};
+    use std::path::PathBuf;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_custom_category_workflow() {
+        // Create a temporary directory for the test database
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+
+        // Create a new database manager and initialize the schema
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let connection_manager = db_manager.connection_manager();
+
+        let workflow_manager = WorkflowManager::new(connection_manager);
+        let engine = WorkflowEngine::new(connection_manager);
+
+        // Define a workflow: Draft -> Prototype -> Released, where release is not marked as requiring approval
+        let workflow_id = workflow_manager.create_workflow(&Workflow::new("Prototype Workflow".to_string(), None, true)).unwrap();
+        let draft = workflow_manager.create_workflow_state(&WorkflowState::new(workflow_id, "Draft".to_string(), None, true, false)).unwrap();
+        let prototype = workflow_manager.create_workflow_state(&WorkflowState::new(workflow_id, "Prototype".to_string(), None, false, false)).unwrap();
+        let released = workflow_manager.create_workflow_state(&WorkflowState::new(workflow_id, "Released".to_string(), None, false, true)).unwrap();
+        let build = workflow_manager.create_workflow_transition(&WorkflowTransition::new(
+            workflow_id, draft, prototype, "Build Prototype".to_string(), None, false,
+        )).unwrap();
+        let release = workflow_manager.create_workflow_transition(&WorkflowTransition::new(
+            workflow_id, prototype, released, "Release".to_string(), None, false,
+        )).unwrap();
+
+        // Assign it to the Mechanical category
+        let category_manager = CategoryManager::new(connection_manager);
+        let category = category_manager.get_category_by_code("ME").unwrap();
+        engine.assign_category_workflow(category.category_id.unwrap(), Some(workflow_id)).unwrap();
+
+        // Create a mechanical part with a tracked revision
+        let part = Part::new(10001, "Mechanical".to_string(), "Bracket".to_string(), "Bracket".to_string(), None);
+        PartManager::new(connection_manager).create_part(&part).unwrap();
+        let revision_manager = RevisionManager::new(connection_manager);
+        let revision_id = revision_manager.create_revision(&Revision::new(
+            10001, "1".to_string(), RevisionStatus::Draft, "alice".to_string(), None,
+        )).unwrap();
+        connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            engine.start_revision_in_transaction(revision_id, "alice", tx)
+        }).unwrap();
+
+        // Only the transition out of Draft is available
+        let transitions = engine.get_available_transitions(revision_id).unwrap();
+        assert_eq!(transitions.len(), 1);
+        assert!(engine.execute_transition(revision_id, release, "alice").is_err());
+
+        // Prototype is not a revision status, so the status stays Draft
+        let outcome = engine.execute_transition(revision_id, build, "alice").unwrap();
+        assert_eq!(outcome.state.name, "Prototype");
+        assert_eq!(revision_manager.get_revision(revision_id).unwrap().status, RevisionStatus::Draft);
+
+        // Releasing requires approval all the same
+        assert!(matches!(
+            engine.execute_transition(revision_id, release, "alice"),
+            Err(PartManagementError::ApprovalRequired(_))
+        ));
+        ApprovalManager::new(connection_manager).create_approval(&Approval::new(
+            revision_id, "bob".to_string(), ApprovalStatus::Approved, None,
+        )).unwrap();
+        engine.execute_transition(revision_id, release, "bob").unwrap();
+
+        let current = engine.get_revision_state(revision_id).unwrap();
+        assert_eq!(current.state.name, "Released");
+        assert_eq!(current.updated_by, "bob");
+        assert_eq!(revision_manager.get_revision(revision_id).unwrap().status, RevisionStatus::Released);
+    }
+
+    #[test]
+    fn test_transition_guards() {
+        // Create a temporary directory for the test database
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+
+        // Create a new database manager and initialize the schema
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let connection_manager = db_manager.connection_manager();
+
+        let workflow_manager = WorkflowManager::new(connection_manager);
+        let engine = WorkflowEngine::new(connection_manager);
+        let revision_manager = RevisionManager::new(connection_manager);
+
+        // Create an assembly with one unreleased child
+        let part_manager = PartManager::new(connection_manager);
+        part_manager.create_part(&Part::new(10001, "Electronic".to_string(), "PCA".to_string(), "Controller".to_string(), None)).unwrap();
+        part_manager.create_part(&Part::new(10002, "Electronic".to_string(), "Resistor".to_string(), "R 10k".to_string(), None)).unwrap();
+        RelationshipManager::new(connection_manager).create_relationship(&Relationship::new(10001, 10002, RelationshipType::Assembly, 2)).unwrap();
+        let child_revision_id = revision_manager.create_revision(&Revision::new(
+            10002, "1".to_string(), RevisionStatus::Draft, "alice".to_string(), None,
+        )).unwrap();
+        let revision_id = revision_manager.create_revision(&Revision::new(
+            10001, "1".to_string(), RevisionStatus::Draft, "alice".to_string(), None,
+        )).unwrap();
+
+        // Guard the submission of the default workflow
+        workflow_manager.create_default_part_workflow().unwrap();
+        connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            engine.start_revision_in_transaction(revision_id, "alice", tx)
+        }).unwrap();
+        let submit = engine.get_available_transitions(revision_id).unwrap()
+            .into_iter()
+            .find(|transition| transition.name == "Submit for Review")
+            .and_then(|transition| transition.transition_id)
+            .unwrap();
+        for (guard_type, parameter) in [
+            (TransitionGuardType::ChildrenReleased, None),
+            (TransitionGuardType::FileAttached, Some("Manufacturing")),
+            (TransitionGuardType::PropertiesPresent, Some("Resistance, Tolerance")),
+        ] {
+            workflow_manager.create_transition_guard(&TransitionGuard::new(submit, guard_type, parameter.map(str::to_string))).unwrap();
+        }
+
+        // Every failing guard is reported
+        let failures = engine.check_transition(revision_id, submit).unwrap();
+        assert_eq!(failures.len(), 4);
+        assert_eq!(failures[0].guard_type, TransitionGuardType::ChildrenReleased);
+        assert_eq!(failures[0].part_id, Some(10002));
+        assert!(matches!(
+            engine.execute_transition(revision_id, submit, "alice"),
+            Err(PartManagementError::GuardsFailed(failures)) if failures.len() == 4
+        ));
+
+        // Satisfy the guards
+        revision_manager.update_status(child_revision_id, RevisionStatus::Released).unwrap();
+        FileManager::new(connection_manager).create_file(&File::new_revision_file(
+            revision_id, PathBuf::from("manufacturing/gerbers.zip"), FileType::Manufacturing, None,
+        )).unwrap();
+        let property_manager = PropertyManager::new(connection_manager);
+        property_manager.create_property(&Property::new_part_property(10001, "Resistance".to_string(), Some("10k".to_string()), PropertyType::String)).unwrap();
+        property_manager.create_property(&Property::new_part_property(10001, "Tolerance".to_string(), Some("1%".to_string()), PropertyType::String)).unwrap();
+
+        assert!(engine.check_transition(revision_id, submit).unwrap().is_empty());
+        let outcome = engine.execute_transition(revision_id, submit, "alice").unwrap();
+        assert_eq!(outcome.state.name, "In Review");
+        assert!(outcome.actions.is_empty());
+
+        // A signed release cannot be checked by the engine, so only the release path may defer it
+        let release = engine.get_available_transitions(revision_id).unwrap()
+            .into_iter()
+            .find(|transition| transition.name == "Approve")
+            .and_then(|transition| transition.transition_id)
+            .unwrap();
+        workflow_manager.create_transition_guard(&TransitionGuard::new(release, TransitionGuardType::SignedRelease, None)).unwrap();
+        let failures = engine.check_transition(revision_id, release).unwrap();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].guard_type, TransitionGuardType::SignedRelease);
+        assert!(WorkflowEngine::new(connection_manager).with_deferred_signed_release()
+            .check_transition(revision_id, release).unwrap().is_empty());
+    }
+}
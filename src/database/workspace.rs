@@ -0,0 +1,601 @@
+//! Workspace module for Implexa
+//!
+//! This module provides functionality for managing workspaces in the database. A workspace is a
+//! named collection of parts a user is working on. Each member either follows the latest
+//! revision of its part or is pinned to a specific revision. The members of a workspace, and
+//! optionally their BOM trees, determine the part directories of its sparse checkout.
+
+use rusqlite::{Transaction, params, Row, Result as SqliteResult};
+use std::collections::BTreeSet;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::database::schema::{DatabaseResult, DatabaseError};
+use crate::database::connection_manager::ConnectionManager;
+use crate::database::part::PartManager;
+use crate::database::relationship::RelationshipManager;
+use crate::database::revision::RevisionManager;
+
+/// Represents a workspace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workspace {
+    /// Unique identifier for the workspace
+    pub workspace_id: Option<i64>,
+    /// Name of the workspace
+    pub name: String,
+    /// Description of the workspace
+    pub description: Option<String>,
+    /// Whether the sparse checkout includes the BOM trees of the member parts
+    pub include_bom: bool,
+    /// User who created the workspace
+    pub created_by: String,
+    /// Creation date
+    pub created_date: SystemTime,
+    /// Date of the last change to the workspace or its members
+    pub updated_date: SystemTime,
+}
+
+impl Workspace {
+    /// Create a new workspace
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the workspace
+    /// * `description` - Description of the workspace
+    /// * `created_by` - User who created the workspace
+    ///
+    /// # Returns
+    ///
+    /// A new Workspace instance
+    pub fn new(name: String, description: Option<String>, created_by: String) -> Self {
+        let now = SystemTime::now();
+        Self {
+            workspace_id: None,
+            name,
+            description,
+            include_bom: false,
+            created_by,
+            created_date: now,
+            updated_date: now,
+        }
+    }
+}
+
+/// Represents a part in a workspace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkspaceMember {
+    /// ID of the workspace
+    pub workspace_id: i64,
+    /// ID of the part
+    pub part_id: i64,
+    /// ID of the pinned revision; None to follow the latest revision of the part
+    pub revision_id: Option<i64>,
+    /// Date the part was added to the workspace
+    pub added_date: SystemTime,
+}
+
+/// Manager for workspace operations
+pub struct WorkspaceManager<'a> {
+    /// Connection manager for the SQLite database
+    connection_manager: &'a ConnectionManager,
+}
+
+impl<'a> WorkspaceManager<'a> {
+    /// Create a new WorkspaceManager
+    ///
+    /// # Arguments
+    ///
+    /// * `connection_manager` - Connection manager for the SQLite database
+    ///
+    /// # Returns
+    ///
+    /// A new WorkspaceManager instance
+    pub fn new(connection_manager: &'a ConnectionManager) -> Self {
+        Self { connection_manager }
+    }
+
+    /// Create a new workspace in the database
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace` - The workspace to create
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created workspace
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workspace could not be created
+    pub fn create_workspace(&self, workspace: &Workspace) -> DatabaseResult<i64> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.create_workspace_in_transaction(workspace, tx)
+        })
+    }
+
+    /// Create a new workspace in the database within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace` - The workspace to create
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The ID of the newly created workspace
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workspace could not be created
+    pub fn create_workspace_in_transaction(&self, workspace: &Workspace, tx: &Transaction) -> DatabaseResult<i64> {
+        if workspace.name.trim().is_empty() {
+            return Err(DatabaseError::ValidationError("Workspace name must not be empty".to_string()));
+        }
+        tx.execute(
+            "INSERT INTO Workspaces (name, description, include_bom, created_by, created_date, updated_date)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                workspace.name,
+                workspace.description,
+                workspace.include_bom,
+                workspace.created_by,
+                to_secs(workspace.created_date),
+                to_secs(workspace.updated_date),
+            ],
+        )?;
+        Ok(tx.last_insert_rowid())
+    }
+
+    /// Get a workspace by ID
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - The ID of the workspace
+    ///
+    /// # Returns
+    ///
+    /// The workspace
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workspace could not be retrieved
+    pub fn get_workspace(&self, workspace_id: i64) -> DatabaseResult<Workspace> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.get_workspace_in_transaction(workspace_id, tx)
+        })
+    }
+
+    /// Get a workspace by ID within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - The ID of the workspace
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The workspace
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workspace could not be retrieved
+    pub fn get_workspace_in_transaction(&self, workspace_id: i64, tx: &Transaction) -> DatabaseResult<Workspace> {
+        let workspace = tx.query_row(
+            "SELECT workspace_id, name, description, include_bom, created_by, created_date, updated_date
+             FROM Workspaces WHERE workspace_id = ?1",
+            params![workspace_id],
+            |row| self.row_to_workspace(row),
+        )?;
+        Ok(workspace)
+    }
+
+    /// Get all workspaces
+    ///
+    /// # Returns
+    ///
+    /// A vector of all workspaces, ordered by name
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workspaces could not be retrieved
+    pub fn get_all_workspaces(&self) -> DatabaseResult<Vec<Workspace>> {
+        self.connection_manager.execute::<_, _, DatabaseError>(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT workspace_id, name, description, include_bom, created_by, created_date, updated_date
+                 FROM Workspaces ORDER BY name",
+            )?;
+            let workspaces = stmt
+                .query_map([], |row| self.row_to_workspace(row))?
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(workspaces)
+        })
+    }
+
+    /// Update the name, description and BOM setting of a workspace
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace` - The workspace with the updated fields
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the workspace was successfully updated
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workspace does not exist or could not be updated
+    pub fn update_workspace(&self, workspace: &Workspace) -> DatabaseResult<()> {
+        let workspace_id = workspace.workspace_id
+            .ok_or_else(|| DatabaseError::ValidationError("Workspace has no ID".to_string()))?;
+        if workspace.name.trim().is_empty() {
+            return Err(DatabaseError::ValidationError("Workspace name must not be empty".to_string()));
+        }
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let updated = tx.execute(
+                "UPDATE Workspaces SET name = ?1, description = ?2, include_bom = ?3, updated_date = ?4 WHERE workspace_id = ?5",
+                params![workspace.name, workspace.description, workspace.include_bom, to_secs(SystemTime::now()), workspace_id],
+            )?;
+            if updated == 0 {
+                return Err(DatabaseError::Sqlite(rusqlite::Error::QueryReturnedNoRows));
+            }
+            Ok(())
+        })
+    }
+
+    /// Delete a workspace and its memberships
+    ///
+    /// The parts themselves are not affected.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - The ID of the workspace to delete
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the workspace was successfully deleted
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workspace does not exist or could not be deleted
+    pub fn delete_workspace(&self, workspace_id: i64) -> DatabaseResult<()> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            tx.execute("DELETE FROM WorkspaceParts WHERE workspace_id = ?1", params![workspace_id])?;
+            let deleted = tx.execute("DELETE FROM Workspaces WHERE workspace_id = ?1", params![workspace_id])?;
+            if deleted == 0 {
+                return Err(DatabaseError::Sqlite(rusqlite::Error::QueryReturnedNoRows));
+            }
+            Ok(())
+        })
+    }
+
+    /// Add a part to a workspace
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - The ID of the workspace
+    /// * `part_id` - The ID of the part
+    /// * `revision_id` - The ID of a revision of the part to pin, or None to follow the latest revision
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the part was added
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workspace or part does not exist, the part is already in
+    /// the workspace, or the revision does not belong to the part
+    pub fn add_part(&self, workspace_id: i64, part_id: i64, revision_id: Option<i64>) -> DatabaseResult<()> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.get_workspace_in_transaction(workspace_id, tx)?;
+            let part_exists: bool = tx.query_row(
+                "SELECT EXISTS(SELECT 1 FROM Parts WHERE part_id = ?1)",
+                params![part_id],
+                |row| row.get(0),
+            )?;
+            if !part_exists {
+                return Err(DatabaseError::ValidationError(format!("Part {} does not exist", part_id)));
+            }
+            let member_exists: bool = tx.query_row(
+                "SELECT EXISTS(SELECT 1 FROM WorkspaceParts WHERE workspace_id = ?1 AND part_id = ?2)",
+                params![workspace_id, part_id],
+                |row| row.get(0),
+            )?;
+            if member_exists {
+                return Err(DatabaseError::ValidationError(format!("Part {} is already in workspace {}", part_id, workspace_id)));
+            }
+            self.check_revision_in_transaction(part_id, revision_id, tx)?;
+
+            let now = to_secs(SystemTime::now());
+            tx.execute(
+                "INSERT INTO WorkspaceParts (workspace_id, part_id, revision_id, added_date) VALUES (?1, ?2, ?3, ?4)",
+                params![workspace_id, part_id, revision_id, now],
+            )?;
+            self.touch_in_transaction(workspace_id, tx)
+        })
+    }
+
+    /// Pin a part in a workspace to a revision, or let it follow the latest revision
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - The ID of the workspace
+    /// * `part_id` - The ID of the part
+    /// * `revision_id` - The ID of a revision of the part to pin, or None to follow the latest revision
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the membership was updated
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the part is not in the workspace or the revision does not
+    /// belong to the part
+    pub fn set_part_revision(&self, workspace_id: i64, part_id: i64, revision_id: Option<i64>) -> DatabaseResult<()> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.check_revision_in_transaction(part_id, revision_id, tx)?;
+            let updated = tx.execute(
+                "UPDATE WorkspaceParts SET revision_id = ?1 WHERE workspace_id = ?2 AND part_id = ?3",
+                params![revision_id, workspace_id, part_id],
+            )?;
+            if updated == 0 {
+                return Err(DatabaseError::ValidationError(format!("Part {} is not in workspace {}", part_id, workspace_id)));
+            }
+            self.touch_in_transaction(workspace_id, tx)
+        })
+    }
+
+    /// Remove a part from a workspace
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - The ID of the workspace
+    /// * `part_id` - The ID of the part
+    ///
+    /// # Returns
+    ///
+    /// Ok(()) if the part was removed
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the part is not in the workspace
+    pub fn remove_part(&self, workspace_id: i64, part_id: i64) -> DatabaseResult<()> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            let deleted = tx.execute(
+                "DELETE FROM WorkspaceParts WHERE workspace_id = ?1 AND part_id = ?2",
+                params![workspace_id, part_id],
+            )?;
+            if deleted == 0 {
+                return Err(DatabaseError::ValidationError(format!("Part {} is not in workspace {}", part_id, workspace_id)));
+            }
+            self.touch_in_transaction(workspace_id, tx)
+        })
+    }
+
+    /// Get the members of a workspace
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - The ID of the workspace
+    ///
+    /// # Returns
+    ///
+    /// The members of the workspace, in the order they were added
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the members could not be retrieved
+    pub fn get_members(&self, workspace_id: i64) -> DatabaseResult<Vec<WorkspaceMember>> {
+        self.connection_manager.transaction::<_, _, DatabaseError>(|tx| {
+            self.get_members_in_transaction(workspace_id, tx)
+        })
+    }
+
+    /// Get the members of a workspace within an existing transaction
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - The ID of the workspace
+    /// * `tx` - Transaction to use for database operations
+    ///
+    /// # Returns
+    ///
+    /// The members of the workspace, in the order they were added
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the members could not be retrieved
+    pub fn get_members_in_transaction(&self, workspace_id: i64, tx: &Transaction) -> DatabaseResult<Vec<WorkspaceMember>> {
+        let mut stmt = tx.prepare(
+            "SELECT workspace_id, part_id, revision_id, added_date FROM WorkspaceParts
+             WHERE workspace_id = ?1 ORDER BY added_date, part_id",
+        )?;
+        let members = stmt
+            .query_map(params![workspace_id], |row| self.row_to_member(row))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(members)
+    }
+
+    /// Get the sparse checkout patterns of a workspace
+    ///
+    /// Every member part contributes its part directory. When the workspace includes BOM trees,
+    /// the directories of all parts in the multi-level BOMs of the members are added as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `workspace_id` - The ID of the workspace
+    ///
+    /// # Returns
+    ///
+    /// The part directories relative to the repository root, sorted and without duplicates
+    ///
+    /// # Errors
+    ///
+    /// Returns a DatabaseError if the workspace does not exist, its members could not be
+    /// retrieved, or a BOM contains a circular reference
+    pub fn get_sparse_checkout_patterns(&self, workspace_id: i64) -> DatabaseResult<Vec<String>> {
+        let workspace = self.get_workspace(workspace_id)?;
+        let mut part_ids = BTreeSet::new();
+        for member in self.get_members(workspace_id)? {
+            part_ids.insert(member.part_id);
+            if workspace.include_bom {
+                for line in RelationshipManager::new(self.connection_manager).explode_bom(member.part_id)? {
+                    part_ids.insert(line.part_id);
+                }
+            }
+        }
+
+        let part_manager = PartManager::new(self.connection_manager);
+        let mut patterns = BTreeSet::new();
+        for part_id in part_ids {
+            let part = part_manager.get_part(part_id)?;
+            patterns.insert(format!("parts/{}", part.display_part_number(self.connection_manager)));
+        }
+        Ok(patterns.into_iter().collect())
+    }
+
+    /// Check that a revision to pin belongs to the part
+    fn check_revision_in_transaction(&self, part_id: i64, revision_id: Option<i64>, tx: &Transaction) -> DatabaseResult<()> {
+        if let Some(revision_id) = revision_id {
+            let revision = RevisionManager::new(self.connection_manager).get_revision_in_transaction(revision_id, tx)?;
+            if revision.part_id != part_id {
+                return Err(DatabaseError::ValidationError(format!("Revision {} does not belong to part {}", revision_id, part_id)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Record a change to the members of a workspace
+    fn touch_in_transaction(&self, workspace_id: i64, tx: &Transaction) -> DatabaseResult<()> {
+        tx.execute(
+            "UPDATE Workspaces SET updated_date = ?1 WHERE workspace_id = ?2",
+            params![to_secs(SystemTime::now()), workspace_id],
+        )?;
+        Ok(())
+    }
+
+    /// Convert a database row to a Workspace
+    fn row_to_workspace(&self, row: &Row) -> SqliteResult<Workspace> {
+        Ok(Workspace {
+            workspace_id: Some(row.get(0)?),
+            name: row.get(1)?,
+            description: row.get(2)?,
+            include_bom: row.get(3)?,
+            created_by: row.get(4)?,
+            created_date: from_secs(row.get(5)?),
+            updated_date: from_secs(row.get(6)?),
+        })
+    }
+
+    /// Convert a database row to a WorkspaceMember
+    fn row_to_member(&self, row: &Row) -> SqliteResult<WorkspaceMember> {
+        Ok(WorkspaceMember {
+            workspace_id: row.get(0)?,
+            part_id: row.get(1)?,
+            revision_id: row.get(2)?,
+            added_date: from_secs(row.get(3)?),
+        })
+    }
+}
+
+/// Convert a date to seconds since UNIX epoch
+fn to_secs(date: SystemTime) -> i64 {
+    date.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs() as i64
+}
+
+/// Convert seconds since UNIX epoch to a date
+fn from_secs(secs: i64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs.max(0) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema::DatabaseManager;
+    use crate::database::part::Part;
+    use crate::database::revision::{Revision, RevisionStatus};
+    use crate::database::relationship::{Relationship, RelationshipType};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_workspace_persistence_and_membership() {
+        // Create a temporary database
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let connection_manager = db_manager.connection_manager();
+
+        let part_manager = PartManager::new(connection_manager);
+        let revision_manager = RevisionManager::new(connection_manager);
+        part_manager.create_part(&Part::new(100001, "Electronic".to_string(), "Resistor".to_string(), "10K Resistor".to_string(), None)).unwrap();
+        part_manager.create_part(&Part::new(100002, "Electronic".to_string(), "Capacitor".to_string(), "100nF Capacitor".to_string(), None)).unwrap();
+        let revision_id = revision_manager.create_revision(&Revision::new(100001, "1".to_string(), RevisionStatus::Released, "alice".to_string(), None)).unwrap();
+        let other_revision_id = revision_manager.create_revision(&Revision::new(100002, "1".to_string(), RevisionStatus::Draft, "alice".to_string(), None)).unwrap();
+
+        let workspace_manager = WorkspaceManager::new(connection_manager);
+        let workspace_id = workspace_manager.create_workspace(&Workspace::new("Power supply".to_string(), None, "alice".to_string())).unwrap();
+        assert!(workspace_manager.create_workspace(&Workspace::new(" ".to_string(), None, "alice".to_string())).is_err());
+
+        // Members reference parts and optionally pin a revision of the part
+        workspace_manager.add_part(workspace_id, 100001, Some(revision_id)).unwrap();
+        workspace_manager.add_part(workspace_id, 100002, None).unwrap();
+        assert!(workspace_manager.add_part(workspace_id, 100002, None).is_err());
+        assert!(workspace_manager.add_part(workspace_id, 100003, None).is_err());
+        assert!(workspace_manager.set_part_revision(workspace_id, 100001, Some(other_revision_id)).is_err());
+        workspace_manager.set_part_revision(workspace_id, 100001, None).unwrap();
+
+        let members = workspace_manager.get_members(workspace_id).unwrap();
+        assert_eq!(members.len(), 2);
+        assert!(members.iter().all(|member| member.revision_id.is_none()));
+
+        let mut workspace = workspace_manager.get_workspace(workspace_id).unwrap();
+        workspace.description = Some("Rev 2 power board".to_string());
+        workspace_manager.update_workspace(&workspace).unwrap();
+        assert_eq!(workspace_manager.get_all_workspaces().unwrap()[0].description.as_deref(), Some("Rev 2 power board"));
+
+        workspace_manager.remove_part(workspace_id, 100002).unwrap();
+        assert!(workspace_manager.remove_part(workspace_id, 100002).is_err());
+
+        // Deleting the workspace removes its memberships but not the parts
+        workspace_manager.delete_workspace(workspace_id).unwrap();
+        assert!(workspace_manager.get_workspace(workspace_id).is_err());
+        assert!(workspace_manager.get_members(workspace_id).unwrap().is_empty());
+        assert!(part_manager.get_part(100001).is_ok());
+    }
+
+    #[test]
+    fn test_sparse_checkout_patterns() {
+        // Create a temporary database
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db_manager = DatabaseManager::new(&db_path).unwrap();
+        db_manager.initialize_schema().unwrap();
+        let connection_manager = db_manager.connection_manager();
+
+        // An assembly with a sub-assembly that contains a resistor
+        let part_manager = PartManager::new(connection_manager);
+        for (part_id, name) in [(100001, "Power Board"), (100002, "Regulator Module"), (100003, "10K Resistor")] {
+            part_manager.create_part(&Part::new(part_id, "Electronic".to_string(), "Assembly".to_string(), name.to_string(), None)).unwrap();
+        }
+        let relationship_manager = RelationshipManager::new(connection_manager);
+        relationship_manager.create_relationship(&Relationship::new(100001, 100002, RelationshipType::Assembly, 1)).unwrap();
+        relationship_manager.create_relationship(&Relationship::new(100002, 100003, RelationshipType::Assembly, 2)).unwrap();
+        let directory = |part_id: i64| format!("parts/{}", part_manager.get_part(part_id).unwrap().display_part_number(connection_manager));
+
+        let workspace_manager = WorkspaceManager::new(connection_manager);
+        let mut workspace = Workspace::new("Power board".to_string(), None, "alice".to_string());
+        let workspace_id = workspace_manager.create_workspace(&workspace).unwrap();
+        assert!(workspace_manager.get_sparse_checkout_patterns(workspace_id).unwrap().is_empty());
+
+        // Only the member directories without the BOM trees
+        workspace_manager.add_part(workspace_id, 100001, None).unwrap();
+        assert_eq!(workspace_manager.get_sparse_checkout_patterns(workspace_id).unwrap(), vec![directory(100001)]);
+
+        // The full BOM tree once the workspace includes it
+        workspace.workspace_id = Some(workspace_id);
+        workspace.include_bom = true;
+        workspace_manager.update_workspace(&workspace).unwrap();
+        workspace_manager.add_part(workspace_id, 100003, None).unwrap();
+        let mut expected = vec![directory(100001), directory(100002), directory(100003)];
+        expected.sort();
+        assert_eq!(workspace_manager.get_sparse_checkout_patterns(workspace_id).unwrap(), expected);
+    }
+}
@@ -11,12 +11,23 @@ pub mod hook;
 pub mod conflict;
 pub mod auth;
 pub mod directory;
+pub mod worktree;
+pub mod branching;
+pub mod release;
+pub mod signing;
+pub mod trailer;
 
 use std::path::{Path, PathBuf};
 use git2::{Repository, Oid, Branch, Commit};
 use thiserror::Error;
 use std::io;
 
+/// Reference the notes with the metadata of commits are kept under
+pub const NOTES_REF: &str = "refs/notes/implexa";
+
+/// Trailer that carried the metadata of commits before it moved to notes
+const LEGACY_METADATA_TRAILER: &str = "PLM-Metadata";
+
 /// Configuration for the Git Backend Manager
 #[derive(Debug, Clone)]
 pub struct GitBackendConfig {
@@ -30,6 +41,16 @@ pub struct GitBackendConfig {
     pub git_executable: PathBuf,
     /// Path to the Git-LFS executable
     pub git_lfs_executable: PathBuf,
+    /// Directory the worktrees of part branches are created in; defaults to a directory inside
+    /// the git directory of the repository
+    pub worktree_directory: Option<PathBuf>,
+    /// Which branches parts are drafted, reviewed and released on; the default branch is the
+    /// mainline
+    pub branching_mode: branching::BranchingMode,
+    /// Templates of the names of part and change order branches
+    pub branch_templates: branching::BranchTemplates,
+    /// Template of the names of the annotated tags of released revisions
+    pub release_tag_template: String,
 }
 
 impl Default for GitBackendConfig {
@@ -49,6 +70,10 @@ impl Default for GitBackendConfig {
             ],
             git_executable: "git".into(),
             git_lfs_executable: "git-lfs".into(),
+            worktree_directory: None,
+            branching_mode: branching::BranchingMode::PerPart,
+            branch_templates: branching::BranchTemplates::default(),
+            release_tag_template: "{part}/rev-{revision}".to_string(),
         }
     }
 }
@@ -204,6 +229,8 @@ pub struct AuthConfig {
     pub ssh_key_path: Option<PathBuf>,
     /// Whether to use the SSH agent
     pub use_ssh_agent: bool,
+    /// Key used to sign release merges and tags; releases are not signed without one
+    pub signing: Option<signing::SigningConfig>,
 }
 
 impl Default for AuthConfig {
@@ -212,14 +239,17 @@ impl Default for AuthConfig {
             use_credential_helper: true,
             ssh_key_path: None,
             use_ssh_agent: true,
+            signing: None,
         }
     }
 }
 
 /// Metadata for a commit
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Metadata {
-    /// Key-value pairs of metadata
+    /// Trailers of the commit message naming the part, revision and change order
+    pub trailers: trailer::CommitTrailers,
+    /// Key-value pairs of metadata kept in the notes of the commit
     pub properties: std::collections::HashMap<String, String>,
 }
 
@@ -260,6 +290,8 @@ pub struct GitBackendManager {
 impl GitBackendManager {
     /// Creates a new GitBackendManager with the specified configuration
     pub fn new(config: GitBackendConfig, auth_config: AuthConfig) -> Result<Self> {
+        config.branch_templates.validate()?;
+        branching::validate_template("release tag", &config.release_tag_template, "refs/tags/")?;
         let auth_provider = auth::AuthProvider::new(auth_config)?;
         
         Ok(Self {
@@ -346,7 +378,7 @@ impl GitBackendManager {
     
     /// Gets the operation handler for the specified repository
     pub fn operation_handler<'a>(&'a self, repo: &'a Repository) -> operation::OperationHandler<'a> {
-        operation::OperationHandler::new(repo, &self.config)
+        operation::OperationHandler::new(repo, &self.config, self.auth_provider.signing_config())
     }
     
     /// Gets the LFS manager for the specified repository
@@ -364,6 +396,60 @@ impl GitBackendManager {
         conflict::ConflictResolver::new(repo, &self.config)
     }
     
+    /// Gets the worktree manager for the specified repository
+    pub fn worktree_manager<'a>(&'a self, repo: &'a Repository) -> worktree::WorktreeManager<'a> {
+        worktree::WorktreeManager::new(repo, &self.config)
+    }
+    
+    /// Gets the branching strategy that names the branches of parts and change orders
+    pub fn branching_strategy(&self) -> branching::BranchingStrategy<'_> {
+        branching::BranchingStrategy::new(self.config.branching_mode, &self.config.default_branch, &self.config.branch_templates)
+    }
+    
+    /// Gets the name of the release tag of a revision
+    pub fn release_tag_name(&self, context: &branching::BranchContext) -> String {
+        branching::render_template(&self.config.release_tag_template, context)
+    }
+    
+    /// Creates the annotated release tag of a revision at the specified commit
+    pub fn create_release_tag(
+        &self,
+        repo: &Repository,
+        name: &str,
+        target: Oid,
+        tagger: &git2::Signature,
+        release: &release::ReleaseTag,
+    ) -> Result<Oid> {
+        self.operation_handler(repo).create_annotated_tag(name, target, tagger, &release.message())
+    }
+    
+    /// Gets the release metadata of a release tag
+    ///
+    /// Returns None if the tag does not exist or is not a release tag.
+    pub fn get_release_tag(&self, repo: &Repository, name: &str) -> Result<Option<release::ReleaseTag>> {
+        let reference = match repo.find_reference(&format!("refs/tags/{}", name)) {
+            Ok(reference) => reference,
+            Err(_) => return Ok(None),
+        };
+        let tag = match reference.peel_to_tag() {
+            Ok(tag) => tag,
+            Err(_) => return Ok(None),
+        };
+        Ok(tag.message().and_then(release::ReleaseTag::parse))
+    }
+    
+    /// Verifies the signature of a commit
+    pub fn verify_commit_signature(&self, repo: &Repository, commit: Oid) -> Result<signing::SignatureStatus> {
+        signing::verify_commit(repo, commit, self.auth_provider.signing_config())
+    }
+    
+    /// Verifies the signature of an annotated tag
+    ///
+    /// Returns None if the tag does not exist.
+    pub fn verify_tag_signature(&self, repo: &Repository, name: &str) -> Result<Option<signing::SignatureStatus>> {
+        signing::verify_tag(repo, name, self.auth_provider.signing_config())
+    }
+    
     /// Gets the auth provider
     pub fn auth_provider(&self) -> &auth::AuthProvider {
         &self.auth_provider
@@ -384,6 +470,13 @@ impl GitBackendManager {
         Ok(branch)
     }
     
+    /// Creates a new branch with the specified name at the tip of another branch
+    pub fn create_branch_from<'a>(&self, repo: &'a Repository, name: &str, source: &str) -> Result<Branch<'a>> {
+        let commit = repo.find_branch(source, git2::BranchType::Local)?.get().peel_to_commit()?;
+        let branch = repo.branch(name, &commit, false)?;
+        Ok(branch)
+    }
+    
     /// Switches to the specified branch
     pub fn checkout_branch(&self, repo: &Repository, name: &str) -> Result<()> {
         self.operation_handler(repo).checkout_branch(name)
@@ -395,45 +488,95 @@ impl GitBackendManager {
     }
     
     /// Commits changes with metadata
+    ///
+    /// The trailers are appended to the commit message and the properties are stored in the
+    /// notes of the commit. The commit and its notes are authored by the specified user, so no
+    /// user needs to be configured in the repository.
     pub fn commit_with_metadata(
         &self,
         repo: &Repository,
         message: &str,
         files: &[&Path],
         metadata: &Metadata,
+        author: &git2::Signature,
     ) -> Result<Oid> {
-        // Convert metadata to a JSON string
-        let metadata_json = serde_json::to_string(&metadata.properties)
-            .map_err(|e| GitBackendError::Other(format!("Failed to serialize metadata: {}", e)))?;
+        // Perform the commit with the trailers at the end of the message
+        let commit_oid = self.operation_handler(repo).commit_as(&metadata.trailers.apply(message), files, author)?;
         
-        // Create a commit message with metadata
-        let full_message = format!("{}\n\nPLM-Metadata: {}", message, metadata_json);
+        // Keep the properties in the notes of the commit
+        if !metadata.properties.is_empty() {
+            self.add_commit_notes(repo, commit_oid, &metadata.properties, author)?;
+        }
         
-        // Perform the commit
-        self.operation_handler(repo).commit(&full_message, files)
+        Ok(commit_oid)
     }
     
     /// Gets metadata for the specified commit
     pub fn get_commit_metadata(
         &self,
-        _repo: &Repository,
+        repo: &Repository,
         commit: &Commit,
     ) -> Result<Metadata> {
         // Get the commit message
         let message = commit.message()
             .ok_or_else(|| GitBackendError::Other("Commit message is not valid UTF-8".to_string()))?;
         
-        // Extract metadata from the commit message
-        let mut properties = std::collections::HashMap::new();
+        // Extract the trailers from the commit message
+        let mut trailers = trailer::CommitTrailers::from_message(message);
+        let legacy = trailers.other.iter()
+            .position(|trailer| trailer.has_token(LEGACY_METADATA_TRAILER))
+            .map(|position| trailers.other.remove(position));
         
-        if let Some(metadata_start) = message.find("PLM-Metadata: ") {
-            let metadata_json = &message[metadata_start + 14..];
-            if let Ok(parsed) = serde_json::from_str::<std::collections::HashMap<String, String>>(metadata_json) {
-                properties = parsed;
+        // Commits made before the notes carry their properties as JSON in a trailer
+        let mut properties = self.get_commit_notes(repo, commit.id())?;
+        if properties.is_empty() {
+            if let Some(legacy) = legacy {
+                properties = serde_json::from_str(&legacy.value).unwrap_or_default();
             }
         }
         
-        Ok(Metadata { properties })
+        Ok(Metadata { trailers, properties })
+    }
+    
+    /// Adds properties to the notes of a commit
+    ///
+    /// Notes can be added after the commit was made without rewriting history. Properties that
+    /// are already in the notes are overwritten. The notes are authored by the specified user.
+    pub fn add_commit_notes(
+        &self,
+        repo: &Repository,
+        commit: Oid,
+        properties: &std::collections::HashMap<String, String>,
+        author: &git2::Signature,
+    ) -> Result<Oid> {
+        let mut notes: std::collections::BTreeMap<String, String> = self.get_commit_notes(repo, commit)?
+            .into_iter()
+            .collect();
+        notes.extend(properties.iter().map(|(key, value)| (key.clone(), value.clone())));
+        let notes_json = serde_json::to_string_pretty(&notes)
+            .map_err(|e| GitBackendError::Other(format!("Failed to serialize metadata: {}", e)))?;
+        
+        repo.note(author, author, Some(NOTES_REF), commit, &notes_json, true)
+            .map_err(GitBackendError::GitError)
+    }
+    
+    /// Gets the properties in the notes of a commit
+    ///
+    /// Returns no properties if the commit has no notes.
+    pub fn get_commit_notes(
+        &self,
+        repo: &Repository,
+        commit: Oid,
+    ) -> Result<std::collections::HashMap<String, String>> {
+        let note = match repo.find_note(Some(NOTES_REF), commit) {
+            Ok(note) => note,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(std::collections::HashMap::new()),
+            Err(e) => return Err(GitBackendError::GitError(e)),
+        };
+        let message = note.message()
+            .ok_or_else(|| GitBackendError::Other("Commit notes are not valid UTF-8".to_string()))?;
+        serde_json::from_str(message)
+            .map_err(|e| GitBackendError::Other(format!("Failed to parse commit notes: {}", e)))
     }
     
     /// Gets the status of the repository for display in the UI
@@ -442,7 +585,7 @@ impl GitBackendManager {
         let info = repo_manager.get_info()?;
         
         let statuses = repo.statuses(None)
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         let mut modified_files = Vec::new();
         let mut untracked_files = Vec::new();
@@ -459,20 +602,18 @@ impl GitBackendManager {
         
         let mut branches = Vec::new();
         repo.branches(None)
-            .map_err(|e| GitBackendError::GitError(e))?
+            .map_err(GitBackendError::GitError)?
             .for_each(|branch_result| {
                 if let Ok((branch, _)) = branch_result {
-                    if let Ok(name) = branch.name() {
-                        if let Some(name) = name {
-                            branches.push(name.to_string());
-                        }
+                    if let Ok(Some(name)) = branch.name() {
+                        branches.push(name.to_string());
                     }
                 }
             });
         
         let mut tags = Vec::new();
         repo.tag_names(None)
-            .map_err(|e| GitBackendError::GitError(e))?
+            .map_err(GitBackendError::GitError)?
             .iter()
             .for_each(|tag| {
                 if let Some(tag) = tag {
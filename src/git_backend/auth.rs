@@ -7,6 +7,7 @@
 use std::path::PathBuf;
 use git2::{Cred, CredentialType};
 use crate::git_backend::{GitBackendError, Result, Credentials, AuthConfig};
+use crate::git_backend::signing::SigningConfig;
 
 /// Auth Provider
 pub struct AuthProvider {
@@ -88,6 +89,11 @@ impl AuthProvider {
         Ok(())
     }
     
+    /// Gets the key commits and tags are signed with, if any
+    pub fn signing_config(&self) -> Option<&SigningConfig> {
+        self.config.signing.as_ref()
+    }
+    
     /// Gets the git2 credentials for the specified URL
     pub(crate) fn get_git2_credentials(
         &self,
@@ -102,29 +108,30 @@ impl AuthProvider {
                 // If we don't have credentials, try to use the default authentication methods
                 
                 // Check if we can use SSH key authentication
-                if allowed_types.contains(CredentialType::SSH_KEY) && self.config.ssh_key_path.is_some() {
-                    let ssh_key_path = self.config.ssh_key_path.as_ref().unwrap();
-                    let public_key = ssh_key_path.with_extension("pub");
-                    
-                    return Ok(Cred::ssh_key(
-                        username_from_url.unwrap_or("git"),
-                        Some(&public_key),
-                        ssh_key_path,
-                        None,
-                    ).map_err(|e| GitBackendError::AuthError(format!("Failed to create SSH key credentials: {}", e)))?);
+                if allowed_types.contains(CredentialType::SSH_KEY) {
+                    if let Some(ssh_key_path) = &self.config.ssh_key_path {
+                        let public_key = ssh_key_path.with_extension("pub");
+                        
+                        return Cred::ssh_key(
+                            username_from_url.unwrap_or("git"),
+                            Some(&public_key),
+                            ssh_key_path,
+                            None,
+                        ).map_err(|e| GitBackendError::AuthError(format!("Failed to create SSH key credentials: {}", e)));
+                    }
                 }
                 
                 // Check if we can use SSH agent authentication
                 if allowed_types.contains(CredentialType::SSH_KEY) && self.config.use_ssh_agent {
-                    return Ok(Cred::ssh_key_from_agent(
+                    return Cred::ssh_key_from_agent(
                         username_from_url.unwrap_or("git"),
-                    ).map_err(|e| GitBackendError::AuthError(format!("Failed to create SSH agent credentials: {}", e)))?);
+                    ).map_err(|e| GitBackendError::AuthError(format!("Failed to create SSH agent credentials: {}", e)));
                 }
                 
                 // Check if we can use default credentials
                 if allowed_types.contains(CredentialType::DEFAULT) {
-                    return Ok(Cred::default()
-                        .map_err(|e| GitBackendError::AuthError(format!("Failed to create default credentials: {}", e)))?);
+                    return Cred::default()
+                        .map_err(|e| GitBackendError::AuthError(format!("Failed to create default credentials: {}", e)));
                 }
                 
                 // No credentials available
@@ -0,0 +1,262 @@
+//! Branching Strategy
+//!
+//! This module decides which branches parts are drafted, reviewed and released on. Branch names
+//! are rendered from templates with placeholders for the part number, revision, user and change
+//! order, and the branching mode decides which of those branches exist at all.
+
+use serde::{Serialize, Deserialize};
+use crate::git_backend::{GitBackendError, Result};
+
+/// Placeholders that can be used in branch and tag name templates
+pub const BRANCH_TEMPLATE_PLACEHOLDERS: [&str; 4] = ["{part}", "{revision}", "{user}", "{eco}"];
+
+/// Branching mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BranchingMode {
+    /// All work is committed directly on the mainline
+    TrunkOnly,
+    /// Parts are drafted and reviewed on branches of their own (default)
+    PerPart,
+    /// Parts are drafted on branches of their own and reviewed on the branch of their change
+    /// order; revisions reviewed outside a change order are reviewed on their draft branch
+    PerChangeOrder,
+}
+
+impl BranchingMode {
+    /// Converts a string to a BranchingMode
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(mode: &str) -> Option<Self> {
+        match mode {
+            "TrunkOnly" => Some(Self::TrunkOnly),
+            "PerPart" => Some(Self::PerPart),
+            "PerChangeOrder" => Some(Self::PerChangeOrder),
+            _ => None,
+        }
+    }
+    
+    /// Converts a BranchingMode to a string
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::TrunkOnly => "TrunkOnly",
+            Self::PerPart => "PerPart",
+            Self::PerChangeOrder => "PerChangeOrder",
+        }
+    }
+}
+
+/// Templates of branch names
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BranchTemplates {
+    /// Draft branch of the first revision of a part
+    pub draft: String,
+    /// Draft branch of later revisions of a part
+    pub revision_draft: String,
+    /// Review branch of a part
+    pub review: String,
+    /// Branch collecting the changes of a change order
+    pub change_order: String,
+}
+
+impl Default for BranchTemplates {
+    fn default() -> Self {
+        Self {
+            draft: "part/{part}/draft".to_string(),
+            revision_draft: "part/{part}/v{revision}/draft".to_string(),
+            review: "part/{part}/review".to_string(),
+            change_order: "eco/{eco}".to_string(),
+        }
+    }
+}
+
+impl BranchTemplates {
+    /// Validates the templates
+    ///
+    /// Every template must only use known placeholders and render to a valid branch name.
+    pub fn validate(&self) -> Result<()> {
+        for (kind, template) in [
+            ("draft branch", &self.draft),
+            ("revision draft branch", &self.revision_draft),
+            ("review branch", &self.review),
+            ("change order branch", &self.change_order),
+        ] {
+            validate_template(kind, template, "refs/heads/")?;
+        }
+        Ok(())
+    }
+}
+
+/// Values substituted into branch name templates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchContext<'a> {
+    /// Display part number of the part
+    pub part_number: &'a str,
+    /// Version of the revision
+    pub revision: &'a str,
+    /// Username of the user working on the branch
+    pub user: &'a str,
+    /// Number of the change order, if any
+    pub change_order: Option<&'a str>,
+}
+
+impl<'a> BranchContext<'a> {
+    /// Creates a new BranchContext without a change order
+    pub fn new(part_number: &'a str, revision: &'a str, user: &'a str) -> Self {
+        Self {
+            part_number,
+            revision,
+            user,
+            change_order: None,
+        }
+    }
+    
+    /// Sets the change order number
+    pub fn with_change_order(mut self, change_order: &'a str) -> Self {
+        self.change_order = Some(change_order);
+        self
+    }
+}
+
+/// Branching strategy of a repository
+#[derive(Debug, Clone, Copy)]
+pub struct BranchingStrategy<'a> {
+    /// Branching mode
+    mode: BranchingMode,
+    /// Name of the mainline branch
+    mainline: &'a str,
+    /// Templates of branch names
+    templates: &'a BranchTemplates,
+}
+
+impl<'a> BranchingStrategy<'a> {
+    /// Creates a new BranchingStrategy
+    pub fn new(mode: BranchingMode, mainline: &'a str, templates: &'a BranchTemplates) -> Self {
+        Self { mode, mainline, templates }
+    }
+    
+    /// Gets the branching mode
+    pub fn mode(&self) -> BranchingMode {
+        self.mode
+    }
+    
+    /// Gets the name of the mainline branch released revisions are merged into
+    pub fn mainline(&self) -> &'a str {
+        self.mainline
+    }
+    
+    /// Checks whether a branch is the mainline
+    pub fn is_mainline(&self, branch: &str) -> bool {
+        branch == self.mainline
+    }
+    
+    /// Gets the draft branch of a revision
+    ///
+    /// The first revision of a part is drafted on the draft branch of the part, later revisions
+    /// on a draft branch of their own. In trunk-only mode this is the mainline.
+    pub fn draft_branch(&self, context: &BranchContext, initial: bool) -> String {
+        match self.mode {
+            BranchingMode::TrunkOnly => self.mainline.to_string(),
+            _ if initial => render_template(&self.templates.draft, context),
+            _ => render_template(&self.templates.revision_draft, context),
+        }
+    }
+    
+    /// Gets the branches a revision may be drafted on, most specific first
+    pub fn draft_branch_candidates(&self, context: &BranchContext) -> Vec<String> {
+        match self.mode {
+            BranchingMode::TrunkOnly => vec![self.mainline.to_string()],
+            _ => vec![
+                render_template(&self.templates.revision_draft, context),
+                render_template(&self.templates.draft, context),
+            ],
+        }
+    }
+    
+    /// Gets the review branch of a part
+    ///
+    /// Returns None if revisions are reviewed on their draft branch.
+    pub fn review_branch(&self, context: &BranchContext) -> Option<String> {
+        match self.mode {
+            BranchingMode::PerPart => Some(render_template(&self.templates.review, context)),
+            BranchingMode::TrunkOnly | BranchingMode::PerChangeOrder => None,
+        }
+    }
+    
+    /// Gets the branch collecting the changes of a change order
+    ///
+    /// In trunk-only mode this is the mainline.
+    pub fn change_order_branch(&self, context: &BranchContext) -> String {
+        match self.mode {
+            BranchingMode::TrunkOnly => self.mainline.to_string(),
+            _ => render_template(&self.templates.change_order, context),
+        }
+    }
+}
+
+/// Validates a name template
+///
+/// The template must only use known placeholders and render to a valid reference name below
+/// `prefix`.
+pub fn validate_template(kind: &str, template: &str, prefix: &str) -> Result<()> {
+    let mut rest = template.to_string();
+    for placeholder in BRANCH_TEMPLATE_PLACEHOLDERS {
+        rest = rest.replace(placeholder, "");
+    }
+    if rest.contains('{') || rest.contains('}') {
+        return Err(GitBackendError::OperationError(
+            format!("The {} template {} contains an unknown placeholder", kind, template),
+        ));
+    }
+    let sample = BranchContext::new("EL-RES-100001", "1", "user").with_change_order("ECO-0001");
+    if !git2::Reference::is_valid_name(&format!("{}{}", prefix, render_template(template, &sample))) {
+        return Err(GitBackendError::OperationError(
+            format!("The {} template {} does not produce a valid name", kind, template),
+        ));
+    }
+    Ok(())
+}
+
+/// Renders a name template
+pub fn render_template(template: &str, context: &BranchContext) -> String {
+    template
+        .replace("{part}", context.part_number)
+        .replace("{revision}", context.revision)
+        .replace("{user}", context.user)
+        .replace("{eco}", context.change_order.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_branch_names() {
+        let templates = BranchTemplates::default();
+        templates.validate().unwrap();
+        let context = BranchContext::new("EL-RES-100001", "2", "alice").with_change_order("ECO-0007");
+        
+        let per_part = BranchingStrategy::new(BranchingMode::PerPart, "main", &templates);
+        assert_eq!(per_part.draft_branch(&context, true), "part/EL-RES-100001/draft");
+        assert_eq!(per_part.draft_branch(&context, false), "part/EL-RES-100001/v2/draft");
+        assert_eq!(per_part.review_branch(&context).as_deref(), Some("part/EL-RES-100001/review"));
+        assert_eq!(per_part.change_order_branch(&context), "eco/ECO-0007");
+        
+        let per_change_order = BranchingStrategy::new(BranchingMode::PerChangeOrder, "main", &templates);
+        assert_eq!(per_change_order.review_branch(&context), None);
+        
+        let trunk = BranchingStrategy::new(BranchingMode::TrunkOnly, "trunk", &templates);
+        assert_eq!(trunk.draft_branch(&context, false), "trunk");
+        assert_eq!(trunk.draft_branch_candidates(&context), vec!["trunk".to_string()]);
+        assert_eq!(trunk.change_order_branch(&context), "trunk");
+        assert!(trunk.is_mainline("trunk"));
+        
+        // Templates can name branches after the user and reject unknown placeholders
+        let custom = BranchTemplates {
+            draft: "users/{user}/{part}".to_string(),
+            ..BranchTemplates::default()
+        };
+        let strategy = BranchingStrategy::new(BranchingMode::PerPart, "main", &custom);
+        assert_eq!(strategy.draft_branch(&context, true), "users/alice/EL-RES-100001");
+        assert!(BranchTemplates { review: "review/{pn}".to_string(), ..BranchTemplates::default() }.validate().is_err());
+        assert!(BranchTemplates { review: "review/{part}..x".to_string(), ..BranchTemplates::default() }.validate().is_err());
+    }
+}
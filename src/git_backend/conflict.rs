@@ -26,7 +26,7 @@ impl<'a> ConflictResolver<'a> {
     /// Checks if there are any conflicts in the repository
     pub fn has_conflicts(&self) -> Result<bool> {
         let index = self.repo.index()
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         Ok(index.has_conflicts())
     }
@@ -34,7 +34,7 @@ impl<'a> ConflictResolver<'a> {
     /// Gets the list of conflicted files
     pub fn get_conflicted_files(&self) -> Result<Vec<PathBuf>> {
         let index = self.repo.index()
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         if !index.has_conflicts() {
             return Ok(Vec::new());
@@ -54,14 +54,12 @@ impl<'a> ConflictResolver<'a> {
                 let mut has_conflict = false;
                 
                 if let Ok(conflicts) = index.conflicts() {
-                    for conflict in conflicts {
-                        if let Ok(conflict) = conflict {
-                            if let Some(our_entry) = conflict.our {
-                                let our_path = String::from_utf8_lossy(&our_entry.path).to_string();
-                                if our_path == path_str {
-                                    has_conflict = true;
-                                    break;
-                                }
+                    for conflict in conflicts.flatten() {
+                        if let Some(our_entry) = conflict.our {
+                            let our_path = String::from_utf8_lossy(&our_entry.path).to_string();
+                            if our_path == path_str {
+                                has_conflict = true;
+                                break;
                             }
                         }
                     }
@@ -96,7 +94,7 @@ impl<'a> ConflictResolver<'a> {
         
         // Get the index
         let mut index = self.repo.index()
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         // Get the conflict entries
         let file_str = file.to_str().unwrap();
@@ -104,24 +102,22 @@ impl<'a> ConflictResolver<'a> {
         let mut found_conflict = false;
         
         if let Ok(conflicts) = index.conflicts() {
-            for conflict in conflicts {
-                if let Ok(conflict_data) = conflict {
-                    // Check if any of the conflict entries match our file
-                    let matches = if let Some(our_entry) = &conflict_data.our {
-                        String::from_utf8_lossy(&our_entry.path).to_string() == file_str
-                    } else if let Some(their_entry) = &conflict_data.their {
-                        String::from_utf8_lossy(&their_entry.path).to_string() == file_str
-                    } else if let Some(ancestor_entry) = &conflict_data.ancestor {
-                        String::from_utf8_lossy(&ancestor_entry.path).to_string() == file_str
-                    } else {
-                        false
-                    };
-                    
-                    if matches {
-                        conflict_entries = (conflict_data.our, conflict_data.ancestor, conflict_data.their);
-                        found_conflict = true;
-                        break;
-                    }
+            for conflict_data in conflicts.flatten() {
+                // Check if any of the conflict entries match our file
+                let matches = if let Some(our_entry) = &conflict_data.our {
+                    String::from_utf8_lossy(&our_entry.path) == file_str
+                } else if let Some(their_entry) = &conflict_data.their {
+                    String::from_utf8_lossy(&their_entry.path) == file_str
+                } else if let Some(ancestor_entry) = &conflict_data.ancestor {
+                    String::from_utf8_lossy(&ancestor_entry.path) == file_str
+                } else {
+                    false
+                };
+                
+                if matches {
+                    conflict_entries = (conflict_data.our, conflict_data.ancestor, conflict_data.their);
+                    found_conflict = true;
+                    break;
                 }
             }
         }
@@ -140,7 +136,7 @@ impl<'a> ConflictResolver<'a> {
                     // Our version doesn't exist, so remove the file
                     if file_path.exists() {
                         fs::remove_file(&file_path)
-                            .map_err(|e| GitBackendError::IoError(e))?;
+                            .map_err(GitBackendError::IoError)?;
                     }
                 }
             },
@@ -152,7 +148,7 @@ impl<'a> ConflictResolver<'a> {
                     // Their version doesn't exist, so remove the file
                     if file_path.exists() {
                         fs::remove_file(&file_path)
-                            .map_err(|e| GitBackendError::IoError(e))?;
+                            .map_err(GitBackendError::IoError)?;
                     }
                 }
             },
@@ -164,11 +160,11 @@ impl<'a> ConflictResolver<'a> {
         
         // Mark the conflict as resolved
         index.add_path(file)
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         // Write the index
         index.write()
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         Ok(())
     }
@@ -189,7 +185,7 @@ impl<'a> ConflictResolver<'a> {
             .current_dir(repo_path)
             .args(["merge", "--abort"])
             .output()
-            .map_err(|e| GitBackendError::IoError(e))?;
+            .map_err(GitBackendError::IoError)?;
         
         if !output.status.success() {
             let error = String::from_utf8_lossy(&output.stderr);
@@ -214,7 +210,7 @@ impl<'a> ConflictResolver<'a> {
         
         // Get the object from the repository
         let object = self.repo.find_object(entry.id, None)
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         // Get the blob
         let blob = object.as_blob()
@@ -222,7 +218,7 @@ impl<'a> ConflictResolver<'a> {
         
         // Write the blob content to the file
         fs::write(&file_path, blob.content())
-            .map_err(|e| GitBackendError::IoError(e))?;
+            .map_err(GitBackendError::IoError)?;
         
         Ok(())
     }
@@ -242,7 +238,7 @@ impl<'a> ConflictResolver<'a> {
         
         // Read the current file content (with conflict markers)
         let content = fs::read_to_string(&file_path)
-            .map_err(|e| GitBackendError::IoError(e))?;
+            .map_err(GitBackendError::IoError)?;
         
         // Parse the conflict markers
         let mut result = String::new();
@@ -284,7 +280,7 @@ impl<'a> ConflictResolver<'a> {
         
         // Write the merged content back to the file
         fs::write(&file_path, result)
-            .map_err(|e| GitBackendError::IoError(e))?;
+            .map_err(GitBackendError::IoError)?;
         
         Ok(())
     }
@@ -313,7 +309,7 @@ impl<'a> ConflictResolver<'a> {
         
         // Read the current file content (with conflict markers)
         let content = fs::read_to_string(&file_path)
-            .map_err(|e| GitBackendError::IoError(e))?;
+            .map_err(GitBackendError::IoError)?;
         
         // Parse the BOM file and resolve conflicts
         let resolved_content = match strategy {
@@ -330,17 +326,17 @@ impl<'a> ConflictResolver<'a> {
         
         // Write the resolved content back to the file
         fs::write(&file_path, resolved_content)
-            .map_err(|e| GitBackendError::IoError(e))?;
+            .map_err(GitBackendError::IoError)?;
         
         // Mark the conflict as resolved
         let mut index = self.repo.index()
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         index.add_path(file)
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         index.write()
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         Ok(())
     }
@@ -415,7 +411,7 @@ impl<'a> ConflictResolver<'a> {
             
             if in_conflict {
                 // We're in the "ours" section, ignore
-            } else if !theirs.is_empty() || in_conflict == false {
+            } else if !theirs.is_empty() || !in_conflict {
                 // We're in the "theirs" section or outside a conflict
                 theirs.push_str(line);
                 theirs.push('\n');
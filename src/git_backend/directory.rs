@@ -118,7 +118,7 @@ impl<'a> DirectoryTemplateManager<'a> {
         }
         
         let content = fs::read_to_string(&path)
-            .map_err(|e| GitBackendError::IoError(e))?;
+            .map_err(GitBackendError::IoError)?;
         
         serde_json::from_str(&content)
             .map_err(|e| GitBackendError::RepositoryError(
@@ -132,14 +132,14 @@ impl<'a> DirectoryTemplateManager<'a> {
         let template_dir = self.template_dir();
         if !template_dir.exists() {
             fs::create_dir_all(&template_dir)
-                .map_err(|e| GitBackendError::IoError(e))?;
+                .map_err(GitBackendError::IoError)?;
         }
 
         // Create the custom template directory if it doesn't exist
         let custom_dir = template_dir.join("custom");
         if !custom_dir.exists() {
             fs::create_dir_all(&custom_dir)
-                .map_err(|e| GitBackendError::IoError(e))?;
+                .map_err(GitBackendError::IoError)?;
         }
 
         // Create the minimal template if it doesn't exist
@@ -151,7 +151,7 @@ impl<'a> DirectoryTemplateManager<'a> {
                     format!("Failed to serialize minimal template: {}", e)
                 ))?;
             fs::write(&minimal_path, content)
-                .map_err(|e| GitBackendError::IoError(e))?;
+                .map_err(GitBackendError::IoError)?;
         }
 
         // Create the standard template if it doesn't exist
@@ -163,7 +163,7 @@ impl<'a> DirectoryTemplateManager<'a> {
                     format!("Failed to serialize standard template: {}", e)
                 ))?;
             fs::write(&standard_path, content)
-                .map_err(|e| GitBackendError::IoError(e))?;
+                .map_err(GitBackendError::IoError)?;
         }
 
         // Create the extended template if it doesn't exist
@@ -175,7 +175,7 @@ impl<'a> DirectoryTemplateManager<'a> {
                     format!("Failed to serialize extended template: {}", e)
                 ))?;
             fs::write(&extended_path, content)
-                .map_err(|e| GitBackendError::IoError(e))?;
+                .map_err(GitBackendError::IoError)?;
         }
 
         Ok(())
@@ -601,13 +601,13 @@ impl<'a> DirectoryTemplateManager<'a> {
         let custom_dir = self.template_dir().join("custom");
         if custom_dir.exists() {
             let entries = fs::read_dir(&custom_dir)
-                .map_err(|e| GitBackendError::IoError(e))?;
+                .map_err(GitBackendError::IoError)?;
             
             for entry in entries {
-                let entry = entry.map_err(|e| GitBackendError::IoError(e))?;
+                let entry = entry.map_err(GitBackendError::IoError)?;
                 let path = entry.path();
                 
-                if path.is_file() && path.extension().map_or(false, |ext| ext == "json") {
+                if path.is_file() && path.extension().is_some_and(|ext| ext == "json") {
                     if let Some(stem) = path.file_stem() {
                         if let Some(name) = stem.to_str() {
                             templates.push(format!("custom/{}", name));
@@ -638,7 +638,7 @@ impl<'a> DirectoryTemplateManager<'a> {
         
         if !part_dir.exists() {
             fs::create_dir_all(&part_dir)
-                .map_err(|e| GitBackendError::IoError(e))?;
+                .map_err(GitBackendError::IoError)?;
         }
         
         // Create directories from the template
@@ -660,7 +660,7 @@ impl<'a> DirectoryTemplateManager<'a> {
         
         if !dir_path.exists() {
             fs::create_dir_all(&dir_path)
-                .map_err(|e| GitBackendError::IoError(e))?;
+                .map_err(GitBackendError::IoError)?;
         }
         
         // Create subdirectories
@@ -679,7 +679,7 @@ impl<'a> DirectoryTemplateManager<'a> {
         if let Some(parent) = file_path.parent() {
             if !parent.exists() {
                 fs::create_dir_all(parent)
-                    .map_err(|e| GitBackendError::IoError(e))?;
+                    .map_err(GitBackendError::IoError)?;
             }
         }
         
@@ -694,16 +694,16 @@ impl<'a> DirectoryTemplateManager<'a> {
             
             if template_file.exists() {
                 fs::copy(&template_file, &file_path)
-                    .map_err(|e| GitBackendError::IoError(e))?;
+                    .map_err(GitBackendError::IoError)?;
             } else {
                 // If the template file doesn't exist, create an empty file
                 fs::write(&file_path, "")
-                    .map_err(|e| GitBackendError::IoError(e))?;
+                    .map_err(GitBackendError::IoError)?;
             }
         } else {
             // If there's no template file, create an empty file
             fs::write(&file_path, "")
-                .map_err(|e| GitBackendError::IoError(e))?;
+                .map_err(GitBackendError::IoError)?;
         }
         
         Ok(())
@@ -728,7 +728,7 @@ impl<'a> DirectoryTemplateManager<'a> {
         let custom_dir = self.template_dir().join("custom");
         if !custom_dir.exists() {
             fs::create_dir_all(&custom_dir)
-                .map_err(|e| GitBackendError::IoError(e))?;
+                .map_err(GitBackendError::IoError)?;
         }
         
         let template_path = custom_dir.join(format!("{}.json", name));
@@ -738,7 +738,7 @@ impl<'a> DirectoryTemplateManager<'a> {
             ))?;
         
         fs::write(&template_path, content)
-            .map_err(|e| GitBackendError::IoError(e))?;
+            .map_err(GitBackendError::IoError)?;
         
         Ok(())
     }
@@ -749,7 +749,7 @@ impl<'a> DirectoryTemplateManager<'a> {
         
         if !templates_dir.exists() {
             fs::create_dir_all(&templates_dir)
-                .map_err(|e| GitBackendError::IoError(e))?;
+                .map_err(GitBackendError::IoError)?;
         }
         
         // Create minimal README template
@@ -771,7 +771,7 @@ Brief description of the part.
 How to use this part.
 "#;
             fs::write(&minimal_path, content)
-                .map_err(|e| GitBackendError::IoError(e))?;
+                .map_err(GitBackendError::IoError)?;
         }
         
         // Create standard README template
@@ -809,7 +809,7 @@ Notes for manufacturing this part.
 - Related part 2
 "#;
             fs::write(&standard_path, content)
-                .map_err(|e| GitBackendError::IoError(e))?;
+                .map_err(GitBackendError::IoError)?;
         }
         
         // Create extended README template
@@ -884,7 +884,7 @@ Compliance information for this part, including:
 | 1.0.0    |      | Initial release |
 "#;
             fs::write(&extended_path, content)
-                .map_err(|e| GitBackendError::IoError(e))?;
+                .map_err(GitBackendError::IoError)?;
         }
         
         Ok(())
@@ -34,7 +34,7 @@ impl<'a> HookManager<'a> {
         // Create the hook directory if it doesn't exist
         if let Some(parent) = hook_path.parent() {
             fs::create_dir_all(parent)
-                .map_err(|e| GitBackendError::IoError(e))?;
+                .map_err(GitBackendError::IoError)?;
         }
         
         // Write the hook script
@@ -44,7 +44,7 @@ impl<'a> HookManager<'a> {
             .truncate(true)
             .custom_flags(0o755) // Set executable permission
             .open(&hook_path)
-            .map_err(|e| GitBackendError::IoError(e))?;
+            .map_err(GitBackendError::IoError)?;
         
         // Add shebang if not present
         let script_with_shebang = if !script.starts_with("#!") {
@@ -55,7 +55,7 @@ impl<'a> HookManager<'a> {
         
         // Write the script
         file.write_all(script_with_shebang.as_bytes())
-            .map_err(|e| GitBackendError::IoError(e))?;
+            .map_err(GitBackendError::IoError)?;
         
         Ok(())
     }
@@ -68,7 +68,7 @@ impl<'a> HookManager<'a> {
         // Remove the hook if it exists
         if hook_path.exists() {
             fs::remove_file(&hook_path)
-                .map_err(|e| GitBackendError::IoError(e))?;
+                .map_err(GitBackendError::IoError)?;
         }
         
         Ok(())
@@ -82,7 +82,7 @@ impl<'a> HookManager<'a> {
         // Read the hook script
         if hook_path.exists() {
             fs::read_to_string(&hook_path)
-                .map_err(|e| GitBackendError::IoError(e))
+                .map_err(GitBackendError::IoError)
         } else {
             Ok(String::new())
         }
@@ -164,7 +164,7 @@ impl<'a> HookManager<'a> {
         if let Some(description) = &workflow.description {
             script.push_str(&format!("# Description: {}\n\n", description));
         } else {
-            script.push_str("\n");
+            script.push('\n');
         }
         
         // Add workflow steps
@@ -33,7 +33,7 @@ impl<'a> LfsManager<'a> {
             .current_dir(repo_path)
             .arg("install")
             .output()
-            .map_err(|e| GitBackendError::IoError(e))?;
+            .map_err(GitBackendError::IoError)?;
         
         if !output.status.success() {
             let error = String::from_utf8_lossy(&output.stderr);
@@ -59,7 +59,7 @@ impl<'a> LfsManager<'a> {
                 .arg("track")
                 .arg(pattern)
                 .output()
-                .map_err(|e| GitBackendError::IoError(e))?;
+                .map_err(GitBackendError::IoError)?;
             
             if !output.status.success() {
                 let error = String::from_utf8_lossy(&output.stderr);
@@ -113,7 +113,7 @@ impl<'a> LfsManager<'a> {
             .current_dir(repo_path)
             .arg("pull")
             .output()
-            .map_err(|e| GitBackendError::IoError(e))?;
+            .map_err(GitBackendError::IoError)?;
         
         if !output.status.success() {
             let error = String::from_utf8_lossy(&output.stderr);
@@ -136,7 +136,7 @@ impl<'a> LfsManager<'a> {
         }
         
         let content = std::fs::read_to_string(&gitattributes_path)
-            .map_err(|e| GitBackendError::IoError(e))?;
+            .map_err(GitBackendError::IoError)?;
         
         Ok(content.contains("filter=lfs diff=lfs merge=lfs"))
     }
@@ -152,7 +152,7 @@ impl<'a> LfsManager<'a> {
             .current_dir(repo_path)
             .arg("track")
             .output()
-            .map_err(|e| GitBackendError::IoError(e))?;
+            .map_err(GitBackendError::IoError)?;
         
         if !output.status.success() {
             let error = String::from_utf8_lossy(&output.stderr);
@@ -188,7 +188,7 @@ impl<'a> LfsManager<'a> {
             .current_dir(repo_path)
             .args(["ls-files", "--size"])
             .output()
-            .map_err(|e| GitBackendError::IoError(e))?;
+            .map_err(GitBackendError::IoError)?;
         
         if !output.status.success() {
             let error = String::from_utf8_lossy(&output.stderr);
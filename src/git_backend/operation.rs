@@ -5,8 +5,9 @@
 //! and maintains operation history and audit trail.
 
 use std::path::{Path, PathBuf};
-use git2::{Repository, Oid, Branch, Commit, BranchType, MergeOptions};
+use git2::{Repository, Oid, Branch, Commit, BranchType, MergeOptions, Signature};
 use crate::git_backend::{GitBackendConfig, GitBackendError, Result, MergeResult};
+use crate::git_backend::signing::{self, SigningConfig};
 
 /// Operation Handler
 pub struct OperationHandler<'a> {
@@ -15,19 +16,30 @@ pub struct OperationHandler<'a> {
     /// The Git Backend configuration
     #[allow(dead_code)]
     config: &'a GitBackendConfig,
+    /// Key commits and annotated tags are signed with, if any
+    signing: Option<&'a SigningConfig>,
 }
 
 impl<'a> OperationHandler<'a> {
     /// Creates a new OperationHandler
-    pub fn new(repo: &'a Repository, config: &'a GitBackendConfig) -> Self {
-        Self { repo, config }
+    pub fn new(repo: &'a Repository, config: &'a GitBackendConfig, signing: Option<&'a SigningConfig>) -> Self {
+        Self { repo, config, signing }
     }
     
     /// Creates a new commit with the specified message and changes
     pub fn commit(&self, message: &str, files: &[&Path]) -> Result<Oid> {
+        // Get the signature
+        let signature = self.repo.signature()
+            .map_err(GitBackendError::GitError)?;
+        
+        self.commit_as(message, files, &signature)
+    }
+    
+    /// Creates a new commit with the specified message and changes, authored by the specified user
+    pub fn commit_as(&self, message: &str, files: &[&Path], author: &Signature) -> Result<Oid> {
         // Get the repository index
         let mut index = self.repo.index()
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         // Add the specified files to the index
         for file in files {
@@ -49,61 +61,45 @@ impl<'a> OperationHandler<'a> {
         
         // Write the index to the repository
         let index_oid = index.write_tree()
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         // Get the tree object
         let tree = self.repo.find_tree(index_oid)
-            .map_err(|e| GitBackendError::GitError(e))?;
-        
-        // Get the signature
-        let signature = self.repo.signature()
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         // Get the parent commit
         let parent_commit = match self.repo.head() {
             Ok(head) => {
                 let head_commit = head.peel_to_commit()
-                    .map_err(|e| GitBackendError::GitError(e))?;
+                    .map_err(GitBackendError::GitError)?;
                 Some(head_commit)
             },
             Err(_) => None,
         };
         
-        // Create the commit
-        let commit_oid = match parent_commit {
-            Some(parent) => {
-                self.repo.commit(
-                    Some("HEAD"),
-                    &signature,
-                    &signature,
-                    message,
-                    &tree,
-                    &[&parent],
-                )
-            },
-            None => {
-                self.repo.commit(
-                    Some("HEAD"),
-                    &signature,
-                    &signature,
-                    message,
-                    &tree,
-                    &[],
-                )
-            },
-        }.map_err(|e| GitBackendError::GitError(e))?;
+        // Create the commit, signed if a signing key is configured
+        let parents: Vec<&Commit> = parent_commit.iter().collect();
+        let commit_oid = signing::create_commit(
+            self.repo,
+            self.signing,
+            Some("HEAD"),
+            author,
+            message,
+            &tree,
+            &parents,
+        )?;
         
         Ok(commit_oid)
     }
     
     /// Creates a new branch with the specified name
-    pub fn create_branch(&self, name: &str) -> Result<Branch> {
+    pub fn create_branch(&self, name: &str) -> Result<Branch<'_>> {
         // Get the HEAD commit
         let head = self.repo.head()
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         let head_commit = head.peel_to_commit()
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         // Create the branch
         let branch = self.repo.branch(name, &head_commit, false)
@@ -125,7 +121,7 @@ impl<'a> OperationHandler<'a> {
         
         // Get the branch commit
         let obj = self.repo.revparse_single(branch_ref)
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         // Create a checkout builder
         let mut checkout_builder = git2::build::CheckoutBuilder::new();
@@ -133,11 +129,11 @@ impl<'a> OperationHandler<'a> {
         
         // Checkout the branch
         self.repo.checkout_tree(&obj, Some(&mut checkout_builder))
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         // Set the HEAD to the branch
         self.repo.set_head(branch_ref)
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         Ok(())
     }
@@ -150,18 +146,18 @@ impl<'a> OperationHandler<'a> {
         
         // Get the annotated commit for the branch
         let branch_commit = branch.get().peel_to_commit()
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         let annotated_commit = self.repo.find_annotated_commit(branch_commit.id())
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         // Get the current branch
         let head = self.repo.head()
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         // Analyze the merge
         let analysis = self.repo.merge_analysis(&[&annotated_commit])
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         // Check if the merge is up-to-date
         if analysis.0.is_up_to_date() {
@@ -173,22 +169,22 @@ impl<'a> OperationHandler<'a> {
             });
         }
         
-        // Check if the merge is fast-forward
-        if analysis.0.is_fast_forward() {
+        // Check if the merge is fast-forward; signed merges always create a merge commit
+        if analysis.0.is_fast_forward() && self.signing.is_none() {
             // Get the reference to update
             let mut reference = head;
             
             // Fast-forward the reference
             reference.set_target(branch_commit.id(), "Fast-forward merge")
-                .map_err(|e| GitBackendError::GitError(e))?;
+                .map_err(GitBackendError::GitError)?;
             
             // Update the working directory
             let mut checkout_builder = git2::build::CheckoutBuilder::new();
             checkout_builder.force();
             
             self.repo.checkout_tree(&self.repo.find_object(branch_commit.id(), None)
-                .map_err(|e| GitBackendError::GitError(e))?, Some(&mut checkout_builder))
-                .map_err(|e| GitBackendError::GitError(e))?;
+                .map_err(GitBackendError::GitError)?, Some(&mut checkout_builder))
+                .map_err(GitBackendError::GitError)?;
             
             return Ok(MergeResult {
                 success: true,
@@ -199,7 +195,7 @@ impl<'a> OperationHandler<'a> {
         }
         
         // Normal merge
-        if analysis.0.is_normal() {
+        if analysis.0.is_normal() || analysis.0.is_fast_forward() {
             // Start the merge
             let mut merge_options = MergeOptions::new();
             merge_options.fail_on_conflict(false);
@@ -208,18 +204,18 @@ impl<'a> OperationHandler<'a> {
             checkout_builder.force();
             
             self.repo.merge(&[&annotated_commit], Some(&mut merge_options), Some(&mut checkout_builder))
-                .map_err(|e| GitBackendError::GitError(e))?;
+                .map_err(GitBackendError::GitError)?;
             
             // Check for conflicts
             let has_conflicts = self.repo.index()
-                .map_err(|e| GitBackendError::GitError(e))?
+                .map_err(GitBackendError::GitError)?
                 .has_conflicts();
             
             if has_conflicts {
                 // Get the list of conflicted files
                 let mut conflicted_files = Vec::new();
                 let index = self.repo.index()
-                    .map_err(|e| GitBackendError::GitError(e))?;
+                    .map_err(GitBackendError::GitError)?;
                 
                 for entry in index.iter() {
                     // Check if this path has conflicts using the conflicts iterator
@@ -227,14 +223,12 @@ impl<'a> OperationHandler<'a> {
                     let mut has_conflict = false;
                     
                     if let Ok(conflicts) = index.conflicts() {
-                        for conflict in conflicts {
-                            if let Ok(conflict) = conflict {
-                                if let Some(our_entry) = conflict.our {
-                                    let our_path = String::from_utf8_lossy(&our_entry.path).to_string();
-                                    if our_path == path_str {
-                                        has_conflict = true;
-                                        break;
-                                    }
+                        for conflict in conflicts.flatten() {
+                            if let Some(our_entry) = conflict.our {
+                                let our_path = String::from_utf8_lossy(&our_entry.path).to_string();
+                                if our_path == path_str {
+                                    has_conflict = true;
+                                    break;
                                 }
                             }
                         }
@@ -255,33 +249,34 @@ impl<'a> OperationHandler<'a> {
             
             // Create the merge commit
             let index_oid = self.repo.index()
-                .map_err(|e| GitBackendError::GitError(e))?
+                .map_err(GitBackendError::GitError)?
                 .write_tree()
-                .map_err(|e| GitBackendError::GitError(e))?;
+                .map_err(GitBackendError::GitError)?;
             
             let tree = self.repo.find_tree(index_oid)
-                .map_err(|e| GitBackendError::GitError(e))?;
+                .map_err(GitBackendError::GitError)?;
             
             let signature = self.repo.signature()
-                .map_err(|e| GitBackendError::GitError(e))?;
+                .map_err(GitBackendError::GitError)?;
             
             let head_commit = head.peel_to_commit()
-                .map_err(|e| GitBackendError::GitError(e))?;
+                .map_err(GitBackendError::GitError)?;
             
             let message = format!("Merge branch '{}' into {}", name, head.shorthand().unwrap_or("HEAD"));
             
-            let commit_oid = self.repo.commit(
+            let commit_oid = signing::create_commit(
+                self.repo,
+                self.signing,
                 Some("HEAD"),
                 &signature,
-                &signature,
                 &message,
                 &tree,
                 &[&head_commit, &branch_commit],
-            ).map_err(|e| GitBackendError::GitError(e))?;
+            )?;
             
             // Clean up the merge state
             self.repo.cleanup_state()
-                .map_err(|e| GitBackendError::GitError(e))?;
+                .map_err(GitBackendError::GitError)?;
             
             return Ok(MergeResult {
                 success: true,
@@ -298,14 +293,14 @@ impl<'a> OperationHandler<'a> {
     pub fn create_tag(&self, name: &str, message: &str) -> Result<Oid> {
         // Get the HEAD commit
         let head = self.repo.head()
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         let head_commit = head.peel_to_commit()
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         // Get the signature
         let signature = self.repo.signature()
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         // Create the tag
         let tag_oid = self.repo.tag(
@@ -319,30 +314,47 @@ impl<'a> OperationHandler<'a> {
         Ok(tag_oid)
     }
     
+    /// Creates a new annotated tag on the specified commit
+    ///
+    /// The tag is signed if a signing key is configured.
+    pub fn create_annotated_tag(&self, name: &str, target: Oid, tagger: &Signature, message: &str) -> Result<Oid> {
+        if let Some(signing) = self.signing {
+            return signing::create_signed_tag(self.repo, signing, name, target, tagger, message);
+        }
+        
+        let target = self.repo.find_object(target, None)
+            .map_err(GitBackendError::GitError)?;
+        
+        let tag_oid = self.repo.tag(name, &target, tagger, message, false)
+            .map_err(|e| GitBackendError::OperationError(format!("Failed to create tag {}: {}", name, e)))?;
+        
+        Ok(tag_oid)
+    }
+    
     /// Gets the commit history for the specified reference
-    pub fn get_history(&self, reference: &str) -> Result<Vec<Commit>> {
+    pub fn get_history(&self, reference: &str) -> Result<Vec<Commit<'_>>> {
         // Get the reference
         let obj = self.repo.revparse_single(reference)
             .map_err(|e| GitBackendError::OperationError(format!("Failed to find reference {}: {}", reference, e)))?;
         
         // Get the commit
         let commit = obj.peel_to_commit()
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         // Create a revwalk
         let mut revwalk = self.repo.revwalk()
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         // Configure the revwalk
         revwalk.push(commit.id())
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         // Collect the commits
         let mut commits = Vec::new();
         for oid in revwalk {
-            let oid = oid.map_err(|e| GitBackendError::GitError(e))?;
+            let oid = oid.map_err(GitBackendError::GitError)?;
             let commit = self.repo.find_commit(oid)
-                .map_err(|e| GitBackendError::GitError(e))?;
+                .map_err(GitBackendError::GitError)?;
             commits.push(commit);
         }
         
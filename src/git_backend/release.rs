@@ -0,0 +1,106 @@
+//! Release Tags
+//!
+//! This module describes the annotated tags that mark released part revisions. The tag message
+//! starts with a summary line followed by the release metadata as "Key: value" lines, so that the
+//! metadata can be read back from the repository without the database.
+
+use chrono::{DateTime, Utc};
+
+/// Release metadata carried by the annotated tag of a released revision
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseTag {
+    /// Display part number of the released part
+    pub part_number: String,
+    /// Version of the released revision
+    pub revision: String,
+    /// Username of the user who released the revision
+    pub released_by: String,
+    /// Date of the release
+    pub release_date: DateTime<Utc>,
+    /// Usernames of the users who approved the revision
+    pub approvers: Vec<String>,
+    /// Number of the change order the revision was released with, if any
+    pub change_order: Option<String>,
+}
+
+impl ReleaseTag {
+    /// Renders the tag message
+    pub fn message(&self) -> String {
+        let mut message = format!(
+            "Release {} revision {}\n\nPart: {}\nRevision: {}\nReleased-By: {}\nRelease-Date: {}\n",
+            self.part_number,
+            self.revision,
+            self.part_number,
+            self.revision,
+            self.released_by,
+            self.release_date.to_rfc3339(),
+        );
+        for approver in &self.approvers {
+            message.push_str(&format!("Approved-By: {}\n", approver));
+        }
+        if let Some(change_order) = &self.change_order {
+            message.push_str(&format!("Change-Order: {}\n", change_order));
+        }
+        message
+    }
+    
+    /// Parses the release metadata from a tag message
+    ///
+    /// Returns None if the message does not carry release metadata.
+    pub fn parse(message: &str) -> Option<Self> {
+        let mut part_number = None;
+        let mut revision = None;
+        let mut released_by = None;
+        let mut release_date = None;
+        let mut approvers = Vec::new();
+        let mut change_order = None;
+        
+        for line in message.lines().skip_while(|line| !line.trim().is_empty()) {
+            let Some((key, value)) = line.split_once(": ") else {
+                continue;
+            };
+            let value = value.trim().to_string();
+            match key {
+                "Part" => part_number = Some(value),
+                "Revision" => revision = Some(value),
+                "Released-By" => released_by = Some(value),
+                "Release-Date" => release_date = DateTime::parse_from_rfc3339(&value).ok().map(|date| date.with_timezone(&Utc)),
+                "Approved-By" => approvers.push(value),
+                "Change-Order" => change_order = Some(value),
+                _ => {}
+            }
+        }
+        
+        Some(Self {
+            part_number: part_number?,
+            revision: revision?,
+            released_by: released_by?,
+            release_date: release_date?,
+            approvers,
+            change_order,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    
+    #[test]
+    fn test_release_tag_message() {
+        let tag = ReleaseTag {
+            part_number: "EL-RES-100042".to_string(),
+            revision: "B".to_string(),
+            released_by: "alice".to_string(),
+            release_date: Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap(),
+            approvers: vec!["bob".to_string(), "carol".to_string()],
+            change_order: Some("ECO-00007".to_string()),
+        };
+        let message = tag.message();
+        assert!(message.starts_with("Release EL-RES-100042 revision B\n\n"));
+        assert!(message.contains("Approved-By: carol\n"));
+        assert_eq!(ReleaseTag::parse(&message), Some(tag));
+        assert_eq!(ReleaseTag::parse("Release of something else"), None);
+    }
+}
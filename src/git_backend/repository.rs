@@ -6,6 +6,9 @@
 use git2::Repository;
 use crate::git_backend::{GitBackendConfig, GitBackendError, Result, RepositoryInfo, RepositorySettings};
 
+/// Directories that are checked out in every sparse checkout, as the application needs them
+pub const SPARSE_CHECKOUT_REQUIRED_DIRECTORIES: [&str; 3] = ["config", "scripts", "templates"];
+
 /// Repository Manager
 pub struct RepositoryManager<'a> {
     /// The Git repository
@@ -60,7 +63,7 @@ impl<'a> RepositoryManager<'a> {
             let dir_path = repo_path.join(dir);
             if !dir_path.exists() {
                 std::fs::create_dir_all(&dir_path)
-                    .map_err(|e| GitBackendError::IoError(e))?;
+                    .map_err(GitBackendError::IoError)?;
             }
         }
         
@@ -87,7 +90,7 @@ impl<'a> RepositoryManager<'a> {
 *.bin filter=lfs diff=lfs merge=lfs -text
 "#;
             std::fs::write(&gitattributes_path, gitattributes_content)
-                .map_err(|e| GitBackendError::IoError(e))?;
+                .map_err(GitBackendError::IoError)?;
         }
         
         // Create a .gitignore file
@@ -127,7 +130,7 @@ vendor/
 .env.local
 "#;
             std::fs::write(&gitignore_path, gitignore_content)
-                .map_err(|e| GitBackendError::IoError(e))?;
+                .map_err(GitBackendError::IoError)?;
         }
         
         // Create directory templates
@@ -139,10 +142,14 @@ vendor/
     }
     
     /// Configures sparse checkout for the repository
+    ///
+    /// Sparse checkout runs in cone mode, so patterns are directories relative to the repository
+    /// root. Files at the root and the directories in `SPARSE_CHECKOUT_REQUIRED_DIRECTORIES` are
+    /// always checked out.
     pub fn configure_sparse_checkout(&self, patterns: &[&str]) -> Result<()> {
         // Check if the repository is already initialized
         if self.repo.is_empty()
-            .map_err(|e| GitBackendError::GitError(e))? {
+            .map_err(GitBackendError::GitError)? {
             return Err(GitBackendError::RepositoryError(
                 "Cannot configure sparse checkout on an empty repository".to_string()
             ));
@@ -155,10 +162,10 @@ vendor/
         // Enable sparse checkout
         let mut command = std::process::Command::new(&self.config.git_executable);
         command.current_dir(repo_path)
-            .args(["sparse-checkout", "init"]);
+            .args(["sparse-checkout", "init", "--cone"]);
         
         let output = command.output()
-            .map_err(|e| GitBackendError::IoError(e))?;
+            .map_err(GitBackendError::IoError)?;
         
         if !output.status.success() {
             let error = String::from_utf8_lossy(&output.stderr);
@@ -173,12 +180,12 @@ vendor/
             .arg("sparse-checkout")
             .arg("set");
         
-        for pattern in patterns {
+        for pattern in SPARSE_CHECKOUT_REQUIRED_DIRECTORIES.iter().chain(patterns) {
             command.arg(pattern);
         }
         
         let output = command.output()
-            .map_err(|e| GitBackendError::IoError(e))?;
+            .map_err(GitBackendError::IoError)?;
         
         if !output.status.success() {
             let error = String::from_utf8_lossy(&output.stderr);
@@ -190,6 +197,28 @@ vendor/
         Ok(())
     }
     
+    /// Disables sparse checkout and restores the full working tree
+    pub fn disable_sparse_checkout(&self) -> Result<()> {
+        // Get the repository path
+        let repo_path = self.repo.path().parent()
+            .ok_or_else(|| GitBackendError::RepositoryError("Repository path has no parent".to_string()))?;
+        
+        let output = std::process::Command::new(&self.config.git_executable)
+            .current_dir(repo_path)
+            .args(["sparse-checkout", "disable"])
+            .output()
+            .map_err(GitBackendError::IoError)?;
+        
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(GitBackendError::RepositoryError(
+                format!("Failed to disable sparse checkout: {}", error)
+            ));
+        }
+        
+        Ok(())
+    }
+    
     /// Creates a part directory with the specified template
     pub fn create_part_directory(
         &self,
@@ -215,7 +244,7 @@ vendor/
         
         // Get the current branch
         let head = self.repo.head()
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         let current_branch = if head.is_branch() {
             head.shorthand().unwrap_or("HEAD").to_string()
@@ -225,7 +254,7 @@ vendor/
         
         // Check if the repository has uncommitted changes
         let statuses = self.repo.statuses(None)
-            .map_err(|e| GitBackendError::GitError(e))?;
+            .map_err(GitBackendError::GitError)?;
         
         let has_changes = statuses.iter().any(|entry| {
             entry.status().is_wt_modified() ||
@@ -259,7 +288,7 @@ vendor/
         }
         
         let content = std::fs::read_to_string(&gitattributes_path)
-            .map_err(|e| GitBackendError::IoError(e))?;
+            .map_err(GitBackendError::IoError)?;
         
         Ok(content.contains("filter=lfs diff=lfs merge=lfs"))
     }
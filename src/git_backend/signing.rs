@@ -0,0 +1,540 @@
+//! Commit and Tag Signing
+//!
+//! This module signs commits and tags with the SSH or GPG key configured in the authentication
+//! configuration and verifies the signatures of existing commits and tags. Like git itself,
+//! signing and verification are delegated to `ssh-keygen` and `gpg`.
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use git2::{ObjectType, Oid, Repository, Signature};
+use serde::{Serialize, Deserialize};
+use crate::git_backend::{GitBackendError, Result};
+
+/// Namespace of SSH signatures on git objects
+const SSH_NAMESPACE: &str = "git";
+
+/// Armor header of SSH signatures
+const SSH_SIGNATURE_HEADER: &str = "-----BEGIN SSH SIGNATURE-----";
+
+/// Armor header of GPG signatures
+const GPG_SIGNATURE_HEADER: &str = "-----BEGIN PGP SIGNATURE-----";
+
+/// Counter that keeps the names of temporary signature files unique within the process
+static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Format of commit and tag signatures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SigningFormat {
+    /// SSH signatures made with `ssh-keygen -Y sign`
+    Ssh,
+    /// OpenPGP signatures made with `gpg`
+    Gpg,
+}
+
+impl SigningFormat {
+    /// Converts a string to a SigningFormat
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(format: &str) -> Option<Self> {
+        match format {
+            "ssh" => Some(Self::Ssh),
+            "gpg" | "openpgp" => Some(Self::Gpg),
+            _ => None,
+        }
+    }
+    
+    /// Converts a SigningFormat to a string
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Self::Ssh => "ssh",
+            Self::Gpg => "gpg",
+        }
+    }
+    
+    /// Detects the format of an armored signature
+    pub fn detect(signature: &str) -> Option<Self> {
+        if signature.contains(SSH_SIGNATURE_HEADER) {
+            Some(Self::Ssh)
+        } else if signature.contains(GPG_SIGNATURE_HEADER) {
+            Some(Self::Gpg)
+        } else {
+            None
+        }
+    }
+}
+
+/// Configuration for signing commits and tags
+#[derive(Debug, Clone)]
+pub struct SigningConfig {
+    /// Signature format
+    pub format: SigningFormat,
+    /// Signing key: the path of an SSH key, or a GPG key ID
+    pub key: String,
+    /// Program used to sign and verify; defaults to `ssh-keygen` or `gpg`
+    pub program: Option<PathBuf>,
+    /// SSH allowed signers file that maps signers to their public keys; without it no SSH
+    /// signature is trusted
+    pub allowed_signers: Option<PathBuf>,
+}
+
+impl SigningConfig {
+    /// Creates a new SigningConfig for an SSH key
+    pub fn ssh(key_path: impl Into<PathBuf>) -> Self {
+        Self {
+            format: SigningFormat::Ssh,
+            key: key_path.into().to_string_lossy().into_owned(),
+            program: None,
+            allowed_signers: None,
+        }
+    }
+    
+    /// Creates a new SigningConfig for a GPG key
+    pub fn gpg(key_id: &str) -> Self {
+        Self {
+            format: SigningFormat::Gpg,
+            key: key_id.to_string(),
+            program: None,
+            allowed_signers: None,
+        }
+    }
+    
+    /// Gets the program used for a signature format
+    fn program(&self, format: SigningFormat) -> PathBuf {
+        match (&self.program, format) {
+            (Some(program), _) if format == self.format => program.clone(),
+            (_, SigningFormat::Ssh) => "ssh-keygen".into(),
+            (_, SigningFormat::Gpg) => "gpg".into(),
+        }
+    }
+}
+
+/// Result of verifying the signature of a commit or tag
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SignatureStatus {
+    /// The object is not signed
+    Unsigned,
+    /// The signature is valid
+    Valid {
+        /// Signature format
+        format: SigningFormat,
+        /// Identity of the signer, if it could be established
+        signer: Option<String>,
+    },
+    /// The signature matches the content, but the signing key is not trusted
+    Untrusted {
+        /// Signature format
+        format: SigningFormat,
+        /// Identity the signer claims, if any
+        signer: Option<String>,
+        /// Reason the signing key is not trusted
+        reason: String,
+    },
+    /// The signature could not be verified
+    Invalid {
+        /// Signature format, if it could be recognized
+        format: Option<SigningFormat>,
+        /// Reason the signature is not valid
+        reason: String,
+    },
+}
+
+impl SignatureStatus {
+    /// Checks whether the signature is valid and made with a trusted key
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Self::Valid { .. })
+    }
+}
+
+/// Signer and verifier of commits and tags
+pub struct CommitSigner<'a> {
+    /// The signing configuration
+    config: &'a SigningConfig,
+}
+
+impl<'a> CommitSigner<'a> {
+    /// Creates a new CommitSigner
+    pub fn new(config: &'a SigningConfig) -> Self {
+        Self { config }
+    }
+    
+    /// Signs a payload, returning the armored signature
+    pub fn sign(&self, payload: &str) -> Result<String> {
+        let program = self.config.program(self.config.format);
+        let mut command = Command::new(&program);
+        match self.config.format {
+            SigningFormat::Ssh => command.args(["-Y", "sign", "-n", SSH_NAMESPACE, "-f", &self.config.key]),
+            SigningFormat::Gpg => command.args(["--status-fd=2", "-bsau", &self.config.key]),
+        };
+        
+        let output = run(&mut command, payload)
+            .map_err(|e| GitBackendError::AuthError(format!("Failed to run {}: {}", program.display(), e)))?;
+        let signature = String::from_utf8_lossy(&output.stdout).into_owned();
+        if !output.status.success() || SigningFormat::detect(&signature).is_none() {
+            return Err(GitBackendError::AuthError(format!(
+                "Failed to sign with {} key {}: {}",
+                self.config.format.to_str(),
+                self.config.key,
+                String::from_utf8_lossy(&output.stderr).trim(),
+            )));
+        }
+        Ok(signature)
+    }
+    
+    /// Verifies the signature of a payload
+    pub fn verify(&self, payload: &str, signature: &str) -> SignatureStatus {
+        verify_signature(Some(self.config), payload, signature)
+    }
+}
+
+/// Verifies the signature of a payload
+///
+/// The format is detected from the signature. SSH signatures are only trusted if their key is
+/// listed in the configured allowed signers file, and GPG signatures only if their key is fully
+/// or ultimately trusted in the keyring; other signatures that match the content are reported
+/// as untrusted.
+pub fn verify_signature(config: Option<&SigningConfig>, payload: &str, signature: &str) -> SignatureStatus {
+    let Some(format) = SigningFormat::detect(signature) else {
+        return SignatureStatus::Invalid { format: None, reason: "Unknown signature format".to_string() };
+    };
+    let invalid = |reason: String| SignatureStatus::Invalid { format: Some(format), reason };
+    let program = config.map(|config| config.program(format)).unwrap_or_else(|| match format {
+        SigningFormat::Ssh => "ssh-keygen".into(),
+        SigningFormat::Gpg => "gpg".into(),
+    });
+    
+    let signature_file = match TempFile::new(signature) {
+        Ok(file) => file,
+        Err(e) => return invalid(format!("Failed to write signature: {}", e)),
+    };
+    let signature_path = signature_file.path.to_string_lossy().into_owned();
+    
+    match format {
+        SigningFormat::Ssh => {
+            let allowed_signers = config.and_then(|config| config.allowed_signers.as_ref());
+            let Some(allowed_signers) = allowed_signers else {
+                // Without allowed signers only the signature itself can be checked, and no key is trusted
+                let mut command = Command::new(&program);
+                command.args(["-Y", "check-novalidate", "-n", SSH_NAMESPACE, "-s", &signature_path]);
+                return match run(&mut command, payload) {
+                    Ok(output) if output.status.success() => SignatureStatus::Untrusted {
+                        format,
+                        signer: None,
+                        reason: "No allowed signers are configured to trust the signing key".to_string(),
+                    },
+                    Ok(output) => invalid(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+                    Err(e) => invalid(format!("Failed to run {}: {}", program.display(), e)),
+                };
+            };
+            let allowed_signers = allowed_signers.to_string_lossy().into_owned();
+            
+            let mut command = Command::new(&program);
+            command.args(["-Y", "find-principals", "-f", &allowed_signers, "-s", &signature_path]);
+            let principal = match run(&mut command, "") {
+                Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string(),
+                Ok(_) => return invalid("The signing key is not an allowed signer".to_string()),
+                Err(e) => return invalid(format!("Failed to run {}: {}", program.display(), e)),
+            };
+            
+            let mut command = Command::new(&program);
+            command.args(["-Y", "verify", "-n", SSH_NAMESPACE, "-f", &allowed_signers, "-I", &principal, "-s", &signature_path]);
+            match run(&mut command, payload) {
+                Ok(output) if output.status.success() => SignatureStatus::Valid { format, signer: Some(principal) },
+                Ok(output) => invalid(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+                Err(e) => invalid(format!("Failed to run {}: {}", program.display(), e)),
+            }
+        }
+        SigningFormat::Gpg => {
+            let mut command = Command::new(&program);
+            command.args(["--status-fd=1", "--verify", &signature_path, "-"]);
+            let output = match run(&mut command, payload) {
+                Ok(output) => output,
+                Err(e) => return invalid(format!("Failed to run {}: {}", program.display(), e)),
+            };
+            let status = String::from_utf8_lossy(&output.stdout);
+            let good_signature = status.lines().find_map(|line| line.strip_prefix("[GNUPG:] GOODSIG "));
+            let trusted = status.lines().any(|line| {
+                line.starts_with("[GNUPG:] TRUST_FULLY") || line.starts_with("[GNUPG:] TRUST_ULTIMATE")
+            });
+            match good_signature {
+                Some(good_signature) if output.status.success() => {
+                    // GOODSIG is followed by the key ID and the user ID of the signer
+                    let signer = good_signature.split_once(' ').map(|(_, user_id)| user_id.to_string());
+                    if trusted {
+                        SignatureStatus::Valid { format, signer }
+                    } else {
+                        SignatureStatus::Untrusted {
+                            format,
+                            signer,
+                            reason: "The signing key is not trusted in the keyring".to_string(),
+                        }
+                    }
+                }
+                _ => invalid(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+            }
+        }
+    }
+}
+
+/// Creates a commit, signing it if a signing configuration is given
+///
+/// The signature is used as both author and committer. Updates `update_ref` to the new commit
+/// like `Repository::commit`.
+pub fn create_commit(
+    repo: &Repository,
+    signing: Option<&SigningConfig>,
+    update_ref: Option<&str>,
+    signature: &Signature,
+    message: &str,
+    tree: &git2::Tree,
+    parents: &[&git2::Commit],
+) -> Result<Oid> {
+    let Some(signing) = signing else {
+        return repo.commit(update_ref, signature, signature, message, tree, parents)
+            .map_err(GitBackendError::GitError);
+    };
+    
+    let buffer = repo.commit_create_buffer(signature, signature, message, tree, parents)
+        .map_err(GitBackendError::GitError)?;
+    let content = buffer.as_str()
+        .ok_or_else(|| GitBackendError::OperationError("Commit content is not valid UTF-8".to_string()))?;
+    let armored = CommitSigner::new(signing).sign(content)?;
+    let commit_oid = repo.commit_signed(content, &armored, None)
+        .map_err(GitBackendError::GitError)?;
+    
+    if let Some(update_ref) = update_ref {
+        // Move the branch HEAD points to, which may not have been born yet
+        let reference = repo.find_reference(update_ref)
+            .map_err(GitBackendError::GitError)?;
+        let branch = reference.symbolic_target().unwrap_or(update_ref).to_string();
+        repo.reference(
+            &branch,
+            commit_oid,
+            true,
+            &format!("commit: {}", message.lines().next().unwrap_or_default()),
+        ).map_err(GitBackendError::GitError)?;
+    }
+    
+    Ok(commit_oid)
+}
+
+/// Creates an annotated tag signed with the configured key
+///
+/// Returns the ID of the tag object.
+pub fn create_signed_tag(
+    repo: &Repository,
+    signing: &SigningConfig,
+    name: &str,
+    target: Oid,
+    tagger: &Signature,
+    message: &str,
+) -> Result<Oid> {
+    let object = repo.find_object(target, None)
+        .map_err(GitBackendError::GitError)?;
+    let object_type = object.kind().and_then(|kind| match kind {
+        ObjectType::Commit => Some("commit"),
+        ObjectType::Tree => Some("tree"),
+        ObjectType::Blob => Some("blob"),
+        ObjectType::Tag => Some("tag"),
+        ObjectType::Any => None,
+    }).ok_or_else(|| GitBackendError::OperationError(format!("Cannot tag object {}", target)))?;
+    
+    let mut message = message.to_string();
+    if !message.ends_with('\n') {
+        message.push('\n');
+    }
+    let payload = format!(
+        "object {}\ntype {}\ntag {}\ntagger {}\n\n{}",
+        target,
+        object_type,
+        name,
+        format_signature(tagger),
+        message,
+    );
+    let signature = CommitSigner::new(signing).sign(&payload)?;
+    
+    let tag_oid = repo.odb()
+        .and_then(|odb| odb.write(ObjectType::Tag, format!("{}{}", payload, signature).as_bytes()))
+        .map_err(GitBackendError::GitError)?;
+    repo.reference(&format!("refs/tags/{}", name), tag_oid, false, &format!("tag: {}", name))
+        .map_err(|e| GitBackendError::OperationError(format!("Failed to create tag {}: {}", name, e)))?;
+    
+    Ok(tag_oid)
+}
+
+/// Verifies the signature of a commit
+pub fn verify_commit(repo: &Repository, commit: Oid, signing: Option<&SigningConfig>) -> Result<SignatureStatus> {
+    let (signature, signed_data) = match repo.extract_signature(&commit, None) {
+        Ok(extracted) => extracted,
+        Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(SignatureStatus::Unsigned),
+        Err(e) => return Err(GitBackendError::GitError(e)),
+    };
+    let signature = String::from_utf8_lossy(&signature).into_owned();
+    let signed_data = String::from_utf8_lossy(&signed_data).into_owned();
+    Ok(verify_signature(signing, &signed_data, &signature))
+}
+
+/// Verifies the signature of an annotated tag
+///
+/// Returns None if the tag does not exist; lightweight tags are reported as unsigned.
+pub fn verify_tag(repo: &Repository, name: &str, signing: Option<&SigningConfig>) -> Result<Option<SignatureStatus>> {
+    let reference = match repo.find_reference(&format!("refs/tags/{}", name)) {
+        Ok(reference) => reference,
+        Err(_) => return Ok(None),
+    };
+    let Some(target) = reference.target() else {
+        return Ok(Some(SignatureStatus::Unsigned));
+    };
+    let odb = repo.odb()
+        .map_err(GitBackendError::GitError)?;
+    let object = odb.read(target)
+        .map_err(GitBackendError::GitError)?;
+    if object.kind() != ObjectType::Tag {
+        return Ok(Some(SignatureStatus::Unsigned));
+    }
+    
+    // The signature is appended to the tag message
+    let content = String::from_utf8_lossy(object.data()).into_owned();
+    let start = [SSH_SIGNATURE_HEADER, GPG_SIGNATURE_HEADER]
+        .iter()
+        .filter_map(|header| content.rfind(header))
+        .max();
+    Ok(Some(match start {
+        Some(start) => verify_signature(signing, &content[..start], &content[start..]),
+        None => SignatureStatus::Unsigned,
+    }))
+}
+
+/// Formats a signature as it appears in commit and tag headers
+fn format_signature(signature: &Signature) -> String {
+    let when = signature.when();
+    let offset = when.offset_minutes();
+    format!(
+        "{} <{}> {} {}{:02}{:02}",
+        signature.name().unwrap_or_default(),
+        signature.email().unwrap_or_default(),
+        when.seconds(),
+        if offset < 0 { '-' } else { '+' },
+        offset.abs() / 60,
+        offset.abs() % 60,
+    )
+}
+
+/// Runs a command with the payload on its standard input
+fn run(command: &mut Command, input: &str) -> std::io::Result<std::process::Output> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes())?;
+    }
+    child.wait_with_output()
+}
+
+/// Temporary file holding a signature for verification, removed when dropped
+struct TempFile {
+    /// Path of the file
+    path: PathBuf,
+}
+
+impl TempFile {
+    /// Writes the contents to a new temporary file
+    fn new(contents: &str) -> std::io::Result<Self> {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos();
+        let path = std::env::temp_dir().join(format!(
+            "implexa-signature-{}-{}-{}",
+            std::process::id(),
+            nanos,
+            TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed),
+        ));
+        std::fs::write(&path, contents)?;
+        Ok(Self { path })
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    
+    #[test]
+    fn test_ssh_signed_commit_and_tag() {
+        // Generate an SSH key; skip the test where ssh-keygen is not installed
+        let key_dir = tempdir().unwrap();
+        let key_path = key_dir.path().join("id_ed25519");
+        if !generate_ssh_key(&key_path, "release@example.com") {
+            eprintln!("Skipping test_ssh_signed_commit_and_tag: ssh-keygen is not available");
+            return;
+        }
+        let public_key = std::fs::read_to_string(key_path.with_extension("pub")).unwrap();
+        let allowed_signers = key_dir.path().join("allowed_signers");
+        std::fs::write(&allowed_signers, format!("release@example.com {}", public_key)).unwrap();
+        let mut signing = SigningConfig::ssh(&key_path);
+        signing.allowed_signers = Some(allowed_signers);
+        
+        let repo_dir = tempdir().unwrap();
+        let repo = Repository::init(repo_dir.path()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let tree = repo.find_tree(repo.index().unwrap().write_tree().unwrap()).unwrap();
+        
+        // An unsigned commit, then a signed commit on top of it
+        let unsigned = create_commit(&repo, None, Some("HEAD"), &sig, "Initial commit", &tree, &[]).unwrap();
+        assert_eq!(verify_commit(&repo, unsigned, Some(&signing)).unwrap(), SignatureStatus::Unsigned);
+        let parent = repo.find_commit(unsigned).unwrap();
+        let signed = create_commit(&repo, Some(&signing), Some("HEAD"), &sig, "Release", &tree, &[&parent]).unwrap();
+        assert_eq!(repo.head().unwrap().target(), Some(signed));
+        assert_eq!(
+            verify_commit(&repo, signed, Some(&signing)).unwrap(),
+            SignatureStatus::Valid { format: SigningFormat::Ssh, signer: Some("release@example.com".to_string()) },
+        );
+        
+        // A signed annotated tag
+        create_signed_tag(&repo, &signing, "EL-RES-100001/rev-1", signed, &sig, "Release EL-RES-100001 revision 1").unwrap();
+        let tag = repo.revparse_single("refs/tags/EL-RES-100001/rev-1").unwrap().peel_to_tag().unwrap();
+        assert_eq!(tag.target_id(), signed);
+        assert!(tag.message().unwrap().starts_with("Release EL-RES-100001 revision 1\n"));
+        assert!(verify_tag(&repo, "EL-RES-100001/rev-1", Some(&signing)).unwrap().unwrap().is_valid());
+        assert_eq!(verify_tag(&repo, "missing", Some(&signing)).unwrap(), None);
+        
+        // Tampered content no longer verifies
+        assert!(!CommitSigner::new(&signing).verify("tampered", &CommitSigner::new(&signing).sign("original").unwrap()).is_valid());
+        
+        // A signature made with a key that is not an allowed signer is rejected
+        let other_key_path = key_dir.path().join("id_other");
+        assert!(generate_ssh_key(&other_key_path, "mallory@example.com"));
+        let other_signature = CommitSigner::new(&SigningConfig::ssh(&other_key_path)).sign("release").unwrap();
+        assert!(matches!(
+            CommitSigner::new(&signing).verify("release", &other_signature),
+            SignatureStatus::Invalid { format: Some(SigningFormat::Ssh), .. }
+        ));
+        
+        // Without allowed signers no key is trusted, even if the signature matches the content
+        let status = verify_signature(Some(&SigningConfig::ssh(&other_key_path)), "release", &other_signature);
+        assert!(matches!(status, SignatureStatus::Untrusted { format: SigningFormat::Ssh, .. }));
+        assert!(!status.is_valid());
+        assert!(!verify_signature(None, "release", &other_signature).is_valid());
+    }
+    
+    /// Generate an unencrypted Ed25519 SSH key, returning false if ssh-keygen is not available
+    fn generate_ssh_key(key_path: &std::path::Path, comment: &str) -> bool {
+        let generated = Command::new("ssh-keygen")
+            .args(["-q", "-t", "ed25519", "-N", "", "-C", comment, "-f"])
+            .arg(key_path)
+            .output();
+        matches!(generated, Ok(ref output) if output.status.success())
+    }
+}
@@ -0,0 +1,281 @@
+//! Commit Trailers
+//!
+//! This module reads and writes the trailers at the end of commit messages, the "Token: value"
+//! lines such as `Signed-off-by:`. Trailers are found with the same rules as
+//! `git interpret-trailers`, so messages written by git and by Implexa are read alike.
+
+/// Trailer naming the part a commit belongs to
+pub const PART_TRAILER: &str = "Part";
+
+/// Trailer naming the revision a commit belongs to
+pub const REVISION_TRAILER: &str = "Revision";
+
+/// Trailer naming the change order a commit belongs to
+pub const CHANGE_ORDER_TRAILER: &str = "ECO";
+
+/// Trailer certifying the origin of a commit
+pub const SIGNED_OFF_BY_TRAILER: &str = "Signed-off-by";
+
+/// Prefixes of trailer lines generated by git itself
+const GIT_GENERATED_PREFIXES: [&str; 2] = ["Signed-off-by: ", "(cherry picked from commit "];
+
+/// A trailer of a commit message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trailer {
+    /// Token of the trailer, e.g. `Signed-off-by`
+    pub token: String,
+    /// Value of the trailer; folded lines are joined with a space
+    pub value: String,
+}
+
+impl Trailer {
+    /// Creates a new Trailer
+    pub fn new(token: &str, value: &str) -> Self {
+        Self {
+            token: token.to_string(),
+            value: value.to_string(),
+        }
+    }
+    
+    /// Checks whether the trailer has the specified token; tokens are not case-sensitive
+    pub fn has_token(&self, token: &str) -> bool {
+        self.token.eq_ignore_ascii_case(token)
+    }
+}
+
+/// The PLM trailers of a commit message
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommitTrailers {
+    /// Display part number of the part
+    pub part: Option<String>,
+    /// Version of the revision
+    pub revision: Option<String>,
+    /// Number of the change order
+    pub change_order: Option<String>,
+    /// Identities that signed off the commit, as `Name <email>`
+    pub signed_off_by: Vec<String>,
+    /// Other trailers, in the order they appear in the message
+    pub other: Vec<Trailer>,
+}
+
+impl CommitTrailers {
+    /// Reads the trailers of a commit message
+    ///
+    /// If a trailer that names the part, revision or change order appears more than once, the
+    /// last one wins.
+    pub fn from_message(message: &str) -> Self {
+        let mut trailers = Self::default();
+        for trailer in parse_trailers(message) {
+            if trailer.has_token(PART_TRAILER) {
+                trailers.part = Some(trailer.value);
+            } else if trailer.has_token(REVISION_TRAILER) {
+                trailers.revision = Some(trailer.value);
+            } else if trailer.has_token(CHANGE_ORDER_TRAILER) {
+                trailers.change_order = Some(trailer.value);
+            } else if trailer.has_token(SIGNED_OFF_BY_TRAILER) {
+                trailers.signed_off_by.push(trailer.value);
+            } else {
+                trailers.other.push(trailer);
+            }
+        }
+        trailers
+    }
+    
+    /// Checks whether there are no trailers
+    pub fn is_empty(&self) -> bool {
+        self.part.is_none()
+            && self.revision.is_none()
+            && self.change_order.is_none()
+            && self.signed_off_by.is_empty()
+            && self.other.is_empty()
+    }
+    
+    /// Gets the trailers in the order they are written
+    pub fn to_trailers(&self) -> Vec<Trailer> {
+        let mut trailers = Vec::new();
+        for (token, value) in [
+            (PART_TRAILER, &self.part),
+            (REVISION_TRAILER, &self.revision),
+            (CHANGE_ORDER_TRAILER, &self.change_order),
+        ] {
+            if let Some(value) = value {
+                trailers.push(Trailer::new(token, value));
+            }
+        }
+        trailers.extend(self.other.iter().cloned());
+        trailers.extend(self.signed_off_by.iter().map(|identity| Trailer::new(SIGNED_OFF_BY_TRAILER, identity)));
+        trailers
+    }
+    
+    /// Appends the trailers to a commit message
+    pub fn apply(&self, message: &str) -> String {
+        append_trailers(message, &self.to_trailers())
+    }
+}
+
+/// Parses the trailers of a commit message
+///
+/// The trailers are the last paragraph of the message, unless that paragraph is the title. The
+/// paragraph counts as trailers if all of its lines are trailers, or if it contains a trailer
+/// generated by git and at least a quarter of its lines are trailers. Lines starting with
+/// whitespace continue the previous trailer. Comment lines and everything after a `---` divider
+/// are ignored.
+pub fn parse_trailers(message: &str) -> Vec<Trailer> {
+    let lines = message_lines(message);
+    let Some(block) = trailer_block(&lines) else {
+        return Vec::new();
+    };
+    
+    let mut trailers: Vec<Trailer> = Vec::new();
+    let mut continues_trailer = false;
+    for line in block {
+        if line.starts_with([' ', '\t']) {
+            if continues_trailer {
+                if let Some(trailer) = trailers.last_mut() {
+                    trailer.value = format!("{} {}", trailer.value, line.trim()).trim().to_string();
+                }
+            }
+            continue;
+        }
+        continues_trailer = false;
+        if let Some((token, value)) = split_trailer(line) {
+            trailers.push(Trailer::new(token, value.trim()));
+            continues_trailer = true;
+        }
+    }
+    trailers
+}
+
+/// Appends trailers to a commit message
+///
+/// The trailers are added to the trailers the message already has, skipping those that are
+/// already present, or become a new paragraph at the end of the message.
+pub fn append_trailers(message: &str, trailers: &[Trailer]) -> String {
+    let existing = parse_trailers(message);
+    let new_trailers: Vec<&Trailer> = trailers
+        .iter()
+        .filter(|trailer| !existing.iter().any(|present| present.has_token(&trailer.token) && present.value == trailer.value))
+        .collect();
+    
+    let mut result = message.trim_end().to_string();
+    if new_trailers.is_empty() {
+        result.push('\n');
+        return result;
+    }
+    
+    // Start a new paragraph unless the message already ends with trailers
+    let has_trailers = trailer_block(&message_lines(message)).is_some();
+    result.push_str(if has_trailers { "\n" } else { "\n\n" });
+    for trailer in new_trailers {
+        let value = trailer.value.split_whitespace().collect::<Vec<_>>().join(" ");
+        result.push_str(&format!("{}: {}\n", trailer.token, value));
+    }
+    result
+}
+
+/// Gets the lines of a message that may hold trailers
+///
+/// Comment lines, everything after a `---` divider and trailing blank lines are left out.
+fn message_lines(message: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = message
+        .lines()
+        .take_while(|line| !is_divider(line))
+        .filter(|line| !line.starts_with('#'))
+        .collect();
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+/// Finds the trailer block at the end of the lines of a message
+fn trailer_block<'a, 'b>(lines: &'b [&'a str]) -> Option<&'b [&'a str]> {
+    // The title paragraph never holds trailers
+    let title_end = lines.iter().position(|line| line.trim().is_empty())?;
+    let start = lines[title_end..]
+        .iter()
+        .rposition(|line| line.trim().is_empty())
+        .map(|position| title_end + position + 1)?;
+    let block = &lines[start..];
+    
+    let mut trailer_lines = 0;
+    let mut other_lines = 0;
+    let mut git_generated = false;
+    for line in block {
+        if line.starts_with([' ', '\t']) {
+            continue;
+        }
+        if GIT_GENERATED_PREFIXES.iter().any(|prefix| line.starts_with(prefix)) {
+            git_generated = true;
+            trailer_lines += 1;
+        } else if split_trailer(line).is_some() {
+            trailer_lines += 1;
+        } else {
+            other_lines += 1;
+        }
+    }
+    
+    let is_block = (trailer_lines > 0 && other_lines == 0) || (git_generated && trailer_lines * 3 >= other_lines);
+    is_block.then_some(block)
+}
+
+/// Splits a trailer line into its token and value
+///
+/// The token is made of letters, digits and dashes and may be followed by whitespace before
+/// the `:` separator.
+fn split_trailer(line: &str) -> Option<(&str, &str)> {
+    let (token, value) = line.split_once(':')?;
+    let token = token.trim_end();
+    if token.is_empty() || !token.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return None;
+    }
+    Some((token, value))
+}
+
+/// Checks whether a line is the `---` divider before a patch
+fn is_divider(line: &str) -> bool {
+    line.strip_prefix("---").is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_commit_trailers() {
+        let trailers = CommitTrailers {
+            part: Some("EL-RES-100001".to_string()),
+            revision: Some("2".to_string()),
+            change_order: Some("ECO-0007".to_string()),
+            signed_off_by: vec!["Alice <alice@example.com>".to_string()],
+            other: Vec::new(),
+        };
+        let message = trailers.apply("Regenerate README\n\nThe resistance changed.\n");
+        assert_eq!(
+            message,
+            "Regenerate README\n\nThe resistance changed.\n\nPart: EL-RES-100001\nRevision: 2\nECO: ECO-0007\nSigned-off-by: Alice <alice@example.com>\n",
+        );
+        assert_eq!(CommitTrailers::from_message(&message), trailers);
+        
+        // Existing trailers are extended rather than repeated
+        let signed_off = append_trailers(&message, &[
+            Trailer::new("Signed-off-by", "Alice <alice@example.com>"),
+            Trailer::new("Signed-off-by", "Bob <bob@example.com>"),
+        ]);
+        assert!(signed_off.ends_with("ECO-0007\nSigned-off-by: Alice <alice@example.com>\nSigned-off-by: Bob <bob@example.com>\n"));
+        
+        // Text after the trailers no longer breaks them, and prose with colons is not a trailer
+        assert!(parse_trailers("Part: EL-RES-100001").is_empty());
+        assert!(parse_trailers("Update\n\nNote: the value changed and\nwas measured again").is_empty());
+        assert_eq!(
+            parse_trailers("Update\n\nPart: EL-RES-100001\nReviewed-by: Bob\n  <bob@example.com>\n# comment\n---\nPart: other"),
+            vec![Trailer::new("Part", "EL-RES-100001"), Trailer::new("Reviewed-by", "Bob <bob@example.com>")],
+        );
+        
+        // A trailer generated by git makes a mostly free-form paragraph count
+        assert_eq!(
+            parse_trailers("Fix\n\nSome text\nmore text\nSigned-off-by: Bob <bob@example.com>"),
+            vec![Trailer::new("Signed-off-by", "Bob <bob@example.com>")],
+        );
+    }
+}
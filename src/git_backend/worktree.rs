@@ -0,0 +1,144 @@
+//! Worktree Manager
+//!
+//! This module handles the git worktrees that draft and review branches are worked on in.
+//! Every branch gets its own working directory under a managed directory, so that work on one
+//! part never checks out files over uncommitted work on another.
+
+use std::path::PathBuf;
+use git2::{BranchType, Repository, WorktreeAddOptions, WorktreePruneOptions};
+use crate::git_backend::{GitBackendConfig, GitBackendError, Result};
+
+/// Name of the managed worktree directory inside the git directory of the repository
+const DEFAULT_WORKTREE_DIRECTORY: &str = "implexa-worktrees";
+
+/// Worktree Manager
+pub struct WorktreeManager<'a> {
+    /// The Git repository
+    repo: &'a Repository,
+    /// The Git Backend configuration
+    config: &'a GitBackendConfig,
+}
+
+impl<'a> WorktreeManager<'a> {
+    /// Creates a new WorktreeManager
+    pub fn new(repo: &'a Repository, config: &'a GitBackendConfig) -> Self {
+        Self { repo, config }
+    }
+    
+    /// Gets the directory the worktrees are created in
+    pub fn worktree_root(&self) -> PathBuf {
+        self.config.worktree_directory.clone()
+            .unwrap_or_else(|| self.repo.path().join(DEFAULT_WORKTREE_DIRECTORY))
+    }
+    
+    /// Gets the worktree name of a branch
+    ///
+    /// Worktree names cannot contain path separators, so every character other than ASCII
+    /// letters, digits, '-', '_' and '.' is replaced with '-'.
+    pub fn worktree_name(branch: &str) -> String {
+        branch.chars()
+            .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') { c } else { '-' })
+            .collect()
+    }
+    
+    /// Gets the working directory of a branch, if it has a worktree
+    pub fn worktree_path(&self, branch: &str) -> Option<PathBuf> {
+        self.repo.find_worktree(&Self::worktree_name(branch))
+            .ok()
+            .filter(|worktree| worktree.validate().is_ok())
+            .map(|worktree| worktree.path().to_path_buf())
+    }
+    
+    /// Opens the worktree of a branch, creating it if it does not exist
+    ///
+    /// Returns the working directory of the branch.
+    pub fn open_worktree(&self, branch: &str) -> Result<PathBuf> {
+        if let Some(path) = self.worktree_path(branch) {
+            return Ok(path);
+        }
+        
+        // Clean up a worktree whose directory was removed by hand
+        let name = Self::worktree_name(branch);
+        if let Ok(stale) = self.repo.find_worktree(&name) {
+            stale.prune(Some(WorktreePruneOptions::new().valid(true)))
+                .map_err(GitBackendError::GitError)?;
+        }
+        
+        let branch_ref = self.repo.find_branch(branch, BranchType::Local)
+            .map_err(|e| GitBackendError::OperationError(format!("Failed to find branch {}: {}", branch, e)))?
+            .into_reference();
+        
+        let root = self.worktree_root();
+        std::fs::create_dir_all(&root)
+            .map_err(GitBackendError::IoError)?;
+        let path = root.join(&name);
+        
+        let mut options = WorktreeAddOptions::new();
+        options.reference(Some(&branch_ref));
+        let worktree = self.repo.worktree(&name, &path, Some(&options))
+            .map_err(|e| GitBackendError::OperationError(format!("Failed to create worktree for {}: {}", branch, e)))?;
+        
+        Ok(worktree.path().to_path_buf())
+    }
+    
+    /// Opens the repository of a branch's worktree, creating the worktree if it does not exist
+    pub fn open_worktree_repository(&self, branch: &str) -> Result<Repository> {
+        let path = self.open_worktree(branch)?;
+        Repository::open(&path).map_err(GitBackendError::GitError)
+    }
+    
+    /// Removes the worktree of a branch along with its working directory
+    ///
+    /// The branch itself is kept. Does nothing if the branch has no worktree.
+    pub fn remove_worktree(&self, branch: &str) -> Result<()> {
+        let worktree = match self.repo.find_worktree(&Self::worktree_name(branch)) {
+            Ok(worktree) => worktree,
+            Err(_) => return Ok(()),
+        };
+        
+        worktree.prune(Some(WorktreePruneOptions::new().valid(true).locked(true).working_tree(true)))
+            .map_err(GitBackendError::GitError)?;
+        
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    
+    #[test]
+    fn test_worktree_lifecycle() {
+        // Create a repository with an initial commit on main and a part branch
+        let temp_dir = tempdir().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let sig = git2::Signature::now("Test User", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let commit_id = repo.commit(Some("refs/heads/main"), &sig, &sig, "Initial commit", &tree, &[]).unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+        repo.branch("part/EL-RES-100001/draft", &repo.find_commit(commit_id).unwrap(), false).unwrap();
+        
+        let config = GitBackendConfig::default();
+        let manager = WorktreeManager::new(&repo, &config);
+        let branch = "part/EL-RES-100001/draft";
+        assert_eq!(WorktreeManager::worktree_name(branch), "part-EL-RES-100001-draft");
+        assert!(manager.worktree_path(branch).is_none());
+        
+        // The branch is checked out in its own directory; the main working tree stays on main
+        let path = manager.open_worktree(branch).unwrap();
+        assert!(path.starts_with(manager.worktree_root()));
+        assert_eq!(manager.open_worktree(branch).unwrap(), path);
+        let worktree_repo = manager.open_worktree_repository(branch).unwrap();
+        assert_eq!(worktree_repo.head().unwrap().shorthand(), Some(branch));
+        assert_eq!(repo.head().unwrap().shorthand(), Some("main"));
+        
+        // Removing the worktree keeps the branch
+        manager.remove_worktree(branch).unwrap();
+        assert!(manager.worktree_path(branch).is_none());
+        assert!(!path.exists());
+        assert!(repo.find_branch(branch, BranchType::Local).is_ok());
+        manager.remove_worktree(branch).unwrap();
+    }
+}
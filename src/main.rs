@@ -15,6 +15,14 @@ use implexa::commands::approval;
 use implexa::commands::file;
 use implexa::commands::manufacturer_part;
 use implexa::commands::property;
+use implexa::commands::supplier;
+use implexa::commands::inventory;
+use implexa::commands::serialized_unit;
+use implexa::commands::user;
+use implexa::commands::change_order;
+use implexa::commands::deviation;
+use implexa::commands::change_impact;
+use implexa::commands::audit;
 
 
 // Import only the necessary state and initialization functions from the library crate
@@ -38,6 +46,22 @@ use implexa::commands::relationship::RelationshipState;
 use implexa::commands::relationship::init_relationship_state;
 use implexa::commands::revision::RevisionState;
 use implexa::commands::revision::init_revision_state;
+use implexa::commands::supplier::SupplierState;
+use implexa::commands::supplier::init_supplier_state;
+use implexa::commands::inventory::InventoryState;
+use implexa::commands::inventory::init_inventory_state;
+use implexa::commands::serialized_unit::SerializedUnitState;
+use implexa::commands::serialized_unit::init_serialized_unit_state;
+use implexa::commands::user::UserState;
+use implexa::commands::user::init_user_state;
+use implexa::commands::change_order::ChangeOrderState;
+use implexa::commands::change_order::init_change_order_state;
+use implexa::commands::deviation::DeviationState;
+use implexa::commands::deviation::init_deviation_state;
+use implexa::commands::change_impact::ChangeImpactState;
+use implexa::commands::change_impact::init_change_impact_state;
+use implexa::commands::audit::AuditState;
+use implexa::commands::audit::init_audit_state;
 // Define a simple state struct for our application
 struct AppState {
     counter: Mutex<i32>,
@@ -145,6 +169,31 @@ async fn delete_part(
     parts::delete_part(part_id, db_state).await
 }
 
+#[tauri::command]
+async fn execute_revision_transition(
+    revision_id: i64,
+    transition_id: i64,
+    db_state: tauri::State<'_, DatabaseState>,
+) -> Result<String, String> {
+    parts::execute_revision_transition(revision_id, transition_id, db_state).await
+}
+
+#[tauri::command]
+async fn get_part_working_path(
+    revision_id: i64,
+    db_state: tauri::State<'_, DatabaseState>,
+) -> Result<String, String> {
+    parts::get_part_working_path(revision_id, db_state).await
+}
+
+#[tauri::command]
+async fn verify_release_signatures(
+    revision_id: i64,
+    db_state: tauri::State<'_, DatabaseState>,
+) -> Result<parts::ReleaseVerificationDto, String> {
+    parts::verify_release_signatures(revision_id, db_state).await
+}
+
 // Workspace command wrappers
 #[tauri::command]
 async fn get_workspaces(
@@ -155,7 +204,7 @@ async fn get_workspaces(
 
 #[tauri::command]
 async fn get_workspace(
-    workspace_id: String,
+    workspace_id: i64,
     workspace_state: tauri::State<'_, WorkspaceState>,
 ) -> Result<workspace::WorkspaceDto, String> {
     workspace::get_workspace(workspace_id, workspace_state).await
@@ -165,13 +214,14 @@ async fn get_workspace(
 async fn create_workspace(
     workspace_data: workspace::WorkspaceCreationData,
     workspace_state: tauri::State<'_, WorkspaceState>,
+    db_state: tauri::State<'_, DatabaseState>,
 ) -> Result<workspace::WorkspaceDto, String> {
-    workspace::create_workspace(workspace_data, workspace_state).await
+    workspace::create_workspace(workspace_data, workspace_state, db_state).await
 }
 
 #[tauri::command]
 async fn update_workspace(
-    workspace_id: String,
+    workspace_id: i64,
     workspace_data: workspace::WorkspaceUpdateData,
     workspace_state: tauri::State<'_, WorkspaceState>,
 ) -> Result<workspace::WorkspaceDto, String> {
@@ -180,7 +230,7 @@ async fn update_workspace(
 
 #[tauri::command]
 async fn delete_workspace(
-    workspace_id: String,
+    workspace_id: i64,
     workspace_state: tauri::State<'_, WorkspaceState>,
 ) -> Result<(), String> {
     workspace::delete_workspace(workspace_id, workspace_state).await
@@ -188,22 +238,49 @@ async fn delete_workspace(
 
 #[tauri::command]
 async fn add_part_to_workspace(
-    workspace_id: String,
+    workspace_id: i64,
     part_id: i64,
+    revision_id: Option<i64>,
     workspace_state: tauri::State<'_, WorkspaceState>,
 ) -> Result<(), String> {
-    workspace::add_part_to_workspace(workspace_id, part_id, workspace_state).await
+    workspace::add_part_to_workspace(workspace_id, part_id, revision_id, workspace_state).await
+}
+
+#[tauri::command]
+async fn set_workspace_part_revision(
+    workspace_id: i64,
+    part_id: i64,
+    revision_id: Option<i64>,
+    workspace_state: tauri::State<'_, WorkspaceState>,
+) -> Result<(), String> {
+    workspace::set_workspace_part_revision(workspace_id, part_id, revision_id, workspace_state).await
 }
 
 #[tauri::command]
 async fn remove_part_from_workspace(
-    workspace_id: String,
+    workspace_id: i64,
     part_id: i64,
     workspace_state: tauri::State<'_, WorkspaceState>,
 ) -> Result<(), String> {
     workspace::remove_part_from_workspace(workspace_id, part_id, workspace_state).await
 }
 
+#[tauri::command]
+async fn activate_workspace(
+    workspace_id: i64,
+    workspace_state: tauri::State<'_, WorkspaceState>,
+    git_state: tauri::State<'_, GitBackendState>,
+) -> Result<Vec<String>, String> {
+    workspace::activate_workspace(workspace_id, workspace_state, git_state).await
+}
+
+#[tauri::command]
+async fn deactivate_workspace(
+    git_state: tauri::State<'_, GitBackendState>,
+) -> Result<(), String> {
+    workspace::deactivate_workspace(git_state).await
+}
+
 // Workflow command wrappers
 #[tauri::command]
 async fn get_workflows(
@@ -283,6 +360,112 @@ async fn create_default_part_workflow(
     workflow::create_default_part_workflow(workflow_state).await
 }
 
+#[tauri::command]
+async fn assign_category_workflow(
+    category_id: i64,
+    workflow_id: Option<i64>,
+    workflow_state: tauri::State<'_, WorkflowState>,
+) -> Result<(), String> {
+    workflow::assign_category_workflow(category_id, workflow_id, workflow_state).await
+}
+
+#[tauri::command]
+async fn get_revision_workflow_state(
+    revision_id: i64,
+    workflow_state: tauri::State<'_, WorkflowState>,
+) -> Result<workflow::RevisionWorkflowStateDto, String> {
+    workflow::get_revision_workflow_state(revision_id, workflow_state).await
+}
+
+#[tauri::command]
+async fn get_available_revision_transitions(
+    revision_id: i64,
+    workflow_state: tauri::State<'_, WorkflowState>,
+) -> Result<Vec<workflow::WorkflowTransitionDto>, String> {
+    workflow::get_available_revision_transitions(revision_id, workflow_state).await
+}
+
+#[tauri::command]
+async fn get_transition_guards(
+    transition_id: i64,
+    workflow_state: tauri::State<'_, WorkflowState>,
+) -> Result<Vec<workflow::TransitionGuardDto>, String> {
+    workflow::get_transition_guards(transition_id, workflow_state).await
+}
+
+#[tauri::command]
+async fn create_transition_guard(
+    guard_data: workflow::TransitionGuardCreationData,
+    workflow_state: tauri::State<'_, WorkflowState>,
+) -> Result<i64, String> {
+    workflow::create_transition_guard(guard_data, workflow_state).await
+}
+
+#[tauri::command]
+async fn delete_transition_guard(
+    guard_id: i64,
+    workflow_state: tauri::State<'_, WorkflowState>,
+) -> Result<(), String> {
+    workflow::delete_transition_guard(guard_id, workflow_state).await
+}
+
+#[tauri::command]
+async fn get_transition_actions(
+    transition_id: i64,
+    workflow_state: tauri::State<'_, WorkflowState>,
+) -> Result<Vec<workflow::TransitionActionDto>, String> {
+    workflow::get_transition_actions(transition_id, workflow_state).await
+}
+
+#[tauri::command]
+async fn create_transition_action(
+    action_data: workflow::TransitionActionCreationData,
+    workflow_state: tauri::State<'_, WorkflowState>,
+) -> Result<i64, String> {
+    workflow::create_transition_action(action_data, workflow_state).await
+}
+
+#[tauri::command]
+async fn delete_transition_action(
+    action_id: i64,
+    workflow_state: tauri::State<'_, WorkflowState>,
+) -> Result<(), String> {
+    workflow::delete_transition_action(action_id, workflow_state).await
+}
+
+#[tauri::command]
+async fn check_revision_transition(
+    revision_id: i64,
+    transition_id: i64,
+    workflow_state: tauri::State<'_, WorkflowState>,
+) -> Result<Vec<workflow::GuardFailureDto>, String> {
+    workflow::check_revision_transition(revision_id, transition_id, workflow_state).await
+}
+
+#[tauri::command]
+async fn get_revision_workflow_events(
+    revision_id: i64,
+    workflow_state: tauri::State<'_, WorkflowState>,
+) -> Result<Vec<workflow::WorkflowEventDto>, String> {
+    workflow::get_revision_workflow_events(revision_id, workflow_state).await
+}
+
+#[tauri::command]
+async fn load_workflow_files(
+    path: String,
+    workflow_state: tauri::State<'_, WorkflowState>,
+) -> Result<Vec<i64>, String> {
+    workflow::load_workflow_files(path, workflow_state).await
+}
+
+#[tauri::command]
+async fn export_workflow_files(
+    path: String,
+    workflow_state: tauri::State<'_, WorkflowState>,
+) -> Result<Vec<String>, String> {
+    workflow::export_workflow_files(path, workflow_state).await
+}
+
 // Revision command wrappers
 #[tauri::command]
 async fn get_revision(
@@ -350,6 +533,32 @@ async fn delete_revision(
     revision::delete_revision(revision_id, revision_state).await
 }
 
+#[tauri::command]
+async fn get_revision_effectivity(
+    revision_id: i64,
+    revision_state: tauri::State<'_, RevisionState>,
+) -> Result<Option<revision::EffectivityDto>, String> {
+    revision::get_revision_effectivity(revision_id, revision_state).await
+}
+
+#[tauri::command]
+async fn set_revision_effectivity(
+    effectivity: revision::EffectivityDto,
+    revision_state: tauri::State<'_, RevisionState>,
+) -> Result<(), String> {
+    revision::set_revision_effectivity(effectivity, revision_state).await
+}
+
+#[tauri::command]
+async fn get_effective_revision(
+    part_id: i64,
+    build_date: Option<i64>,
+    serial_number: Option<String>,
+    revision_state: tauri::State<'_, RevisionState>,
+) -> Result<Option<revision::RevisionDto>, String> {
+    revision::get_effective_revision(part_id, build_date, serial_number, revision_state).await
+}
+
 // Relationship command wrappers
 #[tauri::command]
 async fn get_relationship(
@@ -488,6 +697,130 @@ async fn submit_for_approval(
     approval::submit_for_approval(revision_id, approvers, approval_state).await
 }
 
+#[tauri::command]
+async fn get_approval_policies(
+    approval_state: tauri::State<'_, ApprovalState>,
+) -> Result<Vec<approval::ApprovalPolicyDto>, String> {
+    approval::get_approval_policies(approval_state).await
+}
+
+#[tauri::command]
+async fn create_approval_policy(
+    policy_data: approval::ApprovalPolicyCreationData,
+    approval_state: tauri::State<'_, ApprovalState>,
+) -> Result<approval::ApprovalPolicyDto, String> {
+    approval::create_approval_policy(policy_data, approval_state).await
+}
+
+#[tauri::command]
+async fn delete_approval_policy(
+    policy_id: i64,
+    approval_state: tauri::State<'_, ApprovalState>,
+) -> Result<(), String> {
+    approval::delete_approval_policy(policy_id, approval_state).await
+}
+
+#[tauri::command]
+async fn get_approval_policy_groups(
+    policy_id: i64,
+    approval_state: tauri::State<'_, ApprovalState>,
+) -> Result<Vec<approval::ApprovalPolicyGroupDto>, String> {
+    approval::get_approval_policy_groups(policy_id, approval_state).await
+}
+
+#[tauri::command]
+async fn set_approval_policy_group(
+    policy_id: i64,
+    group_id: i64,
+    min_approvals: i64,
+    approval_state: tauri::State<'_, ApprovalState>,
+) -> Result<(), String> {
+    approval::set_approval_policy_group(policy_id, group_id, min_approvals, approval_state).await
+}
+
+#[tauri::command]
+async fn remove_approval_policy_group(
+    policy_id: i64,
+    group_id: i64,
+    approval_state: tauri::State<'_, ApprovalState>,
+) -> Result<(), String> {
+    approval::remove_approval_policy_group(policy_id, group_id, approval_state).await
+}
+
+#[tauri::command]
+async fn evaluate_revision_approval(
+    revision_id: i64,
+    approval_state: tauri::State<'_, ApprovalState>,
+) -> Result<approval::ApprovalEvaluationDto, String> {
+    approval::evaluate_revision_approval(revision_id, approval_state).await
+}
+
+#[tauri::command]
+async fn get_revision_signatures(
+    revision_id: i64,
+    approval_state: tauri::State<'_, ApprovalState>,
+) -> Result<Vec<approval::SignatureDto>, String> {
+    approval::get_revision_signatures(revision_id, approval_state).await
+}
+
+#[tauri::command]
+async fn verify_revision_signatures(
+    revision_id: i64,
+    approval_state: tauri::State<'_, ApprovalState>,
+) -> Result<Vec<approval::SignatureVerificationDto>, String> {
+    approval::verify_revision_signatures(revision_id, approval_state).await
+}
+
+#[tauri::command]
+async fn get_groups(
+    approval_state: tauri::State<'_, ApprovalState>,
+) -> Result<Vec<approval::GroupDto>, String> {
+    approval::get_groups(approval_state).await
+}
+
+#[tauri::command]
+async fn create_group(
+    name: String,
+    description: Option<String>,
+    approval_state: tauri::State<'_, ApprovalState>,
+) -> Result<approval::GroupDto, String> {
+    approval::create_group(name, description, approval_state).await
+}
+
+#[tauri::command]
+async fn delete_group(
+    group_id: i64,
+    approval_state: tauri::State<'_, ApprovalState>,
+) -> Result<(), String> {
+    approval::delete_group(group_id, approval_state).await
+}
+
+#[tauri::command]
+async fn get_group_members(
+    group_id: i64,
+    approval_state: tauri::State<'_, ApprovalState>,
+) -> Result<Vec<String>, String> {
+    approval::get_group_members(group_id, approval_state).await
+}
+
+#[tauri::command]
+async fn add_group_member(
+    group_id: i64,
+    member: String,
+    approval_state: tauri::State<'_, ApprovalState>,
+) -> Result<(), String> {
+    approval::add_group_member(group_id, member, approval_state).await
+}
+
+#[tauri::command]
+async fn remove_group_member(
+    group_id: i64,
+    member: String,
+    approval_state: tauri::State<'_, ApprovalState>,
+) -> Result<(), String> {
+    approval::remove_group_member(group_id, member, approval_state).await
+}
+
 // Manufacturer Part command wrappers
 #[tauri::command]
 async fn get_manufacturer_part(
@@ -547,6 +880,524 @@ async fn search_manufacturer_parts(
     manufacturer_part::search_manufacturer_parts(search_term, manufacturer_part_state).await
 }
 
+#[tauri::command]
+async fn get_obsolescence_report(
+    as_of: Option<String>,
+    manufacturer_part_state: tauri::State<'_, ManufacturerPartState>,
+) -> Result<manufacturer_part::ObsolescenceReportDto, String> {
+    manufacturer_part::get_obsolescence_report(as_of, manufacturer_part_state).await
+}
+
+// Supplier command wrappers
+#[tauri::command]
+async fn get_suppliers(
+    supplier_state: tauri::State<'_, SupplierState>,
+) -> Result<Vec<supplier::SupplierDto>, String> {
+    supplier::get_suppliers(supplier_state).await
+}
+
+#[tauri::command]
+async fn get_supplier_offers(
+    mpn_id: i64,
+    supplier_state: tauri::State<'_, SupplierState>,
+) -> Result<Vec<supplier::SupplierOfferDto>, String> {
+    supplier::get_supplier_offers(mpn_id, supplier_state).await
+}
+
+#[tauri::command]
+async fn create_supplier_offer(
+    offer_data: supplier::SupplierOfferCreationData,
+    supplier_state: tauri::State<'_, SupplierState>,
+) -> Result<supplier::SupplierOfferDto, String> {
+    supplier::create_supplier_offer(offer_data, supplier_state).await
+}
+
+#[tauri::command]
+async fn delete_supplier_offer(
+    offer_id: i64,
+    supplier_state: tauri::State<'_, SupplierState>,
+) -> Result<(), String> {
+    supplier::delete_supplier_offer(offer_id, supplier_state).await
+}
+
+#[tauri::command]
+async fn import_supplier_offers_csv(
+    csv_content: String,
+    supplier_name: Option<String>,
+    supplier_state: tauri::State<'_, SupplierState>,
+) -> Result<supplier::OfferImportResultDto, String> {
+    supplier::import_supplier_offers_csv(csv_content, supplier_name, supplier_state).await
+}
+
+#[tauri::command]
+async fn get_bom_cost_rollup(
+    part_id: i64,
+    build_quantity: i64,
+    currency: String,
+    supplier_state: tauri::State<'_, SupplierState>,
+) -> Result<supplier::CostRollupDto, String> {
+    supplier::get_bom_cost_rollup(part_id, build_quantity, currency, supplier_state).await
+}
+
+// Inventory command wrappers
+#[tauri::command]
+async fn get_stock_locations(
+    inventory_state: tauri::State<'_, InventoryState>,
+) -> Result<Vec<inventory::StockLocationDto>, String> {
+    inventory::get_stock_locations(inventory_state).await
+}
+
+#[tauri::command]
+async fn create_stock_location(
+    name: String,
+    description: Option<String>,
+    inventory_state: tauri::State<'_, InventoryState>,
+) -> Result<inventory::StockLocationDto, String> {
+    inventory::create_stock_location(name, description, inventory_state).await
+}
+
+#[tauri::command]
+async fn get_part_stock(
+    part_id: i64,
+    inventory_state: tauri::State<'_, InventoryState>,
+) -> Result<Vec<inventory::StockRecordDto>, String> {
+    inventory::get_part_stock(part_id, inventory_state).await
+}
+
+#[tauri::command]
+async fn get_part_stock_level(
+    part_id: i64,
+    inventory_state: tauri::State<'_, InventoryState>,
+) -> Result<inventory::StockLevelDto, String> {
+    inventory::get_part_stock_level(part_id, inventory_state).await
+}
+
+#[tauri::command]
+async fn get_location_stock(
+    location_id: i64,
+    inventory_state: tauri::State<'_, InventoryState>,
+) -> Result<Vec<inventory::StockRecordDto>, String> {
+    inventory::get_location_stock(location_id, inventory_state).await
+}
+
+#[tauri::command]
+async fn record_inventory_transaction(
+    transaction_data: inventory::InventoryTransactionData,
+    inventory_state: tauri::State<'_, InventoryState>,
+) -> Result<i64, String> {
+    inventory::record_inventory_transaction(transaction_data, inventory_state).await
+}
+
+#[tauri::command]
+async fn get_part_inventory_transactions(
+    part_id: i64,
+    inventory_state: tauri::State<'_, InventoryState>,
+) -> Result<Vec<inventory::InventoryTransactionDto>, String> {
+    inventory::get_part_inventory_transactions(part_id, inventory_state).await
+}
+
+#[tauri::command]
+async fn reserve_stock(
+    part_id: i64,
+    location_id: i64,
+    quantity: i64,
+    lot_number: Option<String>,
+    inventory_state: tauri::State<'_, InventoryState>,
+) -> Result<(), String> {
+    inventory::reserve_stock(part_id, location_id, quantity, lot_number, inventory_state).await
+}
+
+#[tauri::command]
+async fn release_stock_reservation(
+    part_id: i64,
+    location_id: i64,
+    quantity: i64,
+    lot_number: Option<String>,
+    inventory_state: tauri::State<'_, InventoryState>,
+) -> Result<(), String> {
+    inventory::release_stock_reservation(part_id, location_id, quantity, lot_number, inventory_state).await
+}
+
+#[tauri::command]
+async fn get_clear_to_build(
+    revision_id: i64,
+    build_quantity: i64,
+    inventory_state: tauri::State<'_, InventoryState>,
+) -> Result<inventory::ClearToBuildReportDto, String> {
+    inventory::get_clear_to_build(revision_id, build_quantity, inventory_state).await
+}
+
+// Serialized unit command wrappers
+#[tauri::command]
+async fn create_serialized_unit(
+    unit_data: serialized_unit::SerializedUnitCreationData,
+    serialized_unit_state: tauri::State<'_, SerializedUnitState>,
+) -> Result<serialized_unit::SerializedUnitDto, String> {
+    serialized_unit::create_serialized_unit(unit_data, serialized_unit_state).await
+}
+
+#[tauri::command]
+async fn get_serialized_unit(
+    serial_number: String,
+    serialized_unit_state: tauri::State<'_, SerializedUnitState>,
+) -> Result<serialized_unit::SerializedUnitDto, String> {
+    serialized_unit::get_serialized_unit(serial_number, serialized_unit_state).await
+}
+
+#[tauri::command]
+async fn get_unit_as_built(
+    serial_number: String,
+    serialized_unit_state: tauri::State<'_, SerializedUnitState>,
+) -> Result<Vec<serialized_unit::AsBuiltComponentDto>, String> {
+    serialized_unit::get_unit_as_built(serial_number, serialized_unit_state).await
+}
+
+#[tauri::command]
+async fn add_as_built_component(
+    serial_number: String,
+    component_data: serialized_unit::AsBuiltComponentData,
+    serialized_unit_state: tauri::State<'_, SerializedUnitState>,
+) -> Result<i64, String> {
+    serialized_unit::add_as_built_component(serial_number, component_data, serialized_unit_state).await
+}
+
+#[tauri::command]
+async fn get_revision_units(
+    revision_id: i64,
+    serialized_unit_state: tauri::State<'_, SerializedUnitState>,
+) -> Result<Vec<serialized_unit::SerializedUnitDto>, String> {
+    serialized_unit::get_revision_units(revision_id, serialized_unit_state).await
+}
+
+#[tauri::command]
+async fn get_units_containing_lot(
+    lot_number: String,
+    part_id: Option<i64>,
+    serialized_unit_state: tauri::State<'_, SerializedUnitState>,
+) -> Result<Vec<serialized_unit::SerializedUnitDto>, String> {
+    serialized_unit::get_units_containing_lot(lot_number, part_id, serialized_unit_state).await
+}
+
+// User command wrappers
+#[tauri::command]
+async fn get_users(
+    user_state: tauri::State<'_, UserState>,
+) -> Result<Vec<user::UserDto>, String> {
+    user::get_users(user_state).await
+}
+
+#[tauri::command]
+async fn create_user(
+    user_data: user::UserCreationData,
+    user_state: tauri::State<'_, UserState>,
+    db_state: tauri::State<'_, DatabaseState>,
+) -> Result<user::UserDto, String> {
+    user::create_user(user_data, user_state, db_state).await
+}
+
+#[tauri::command]
+async fn update_user(
+    user_id: i64,
+    update_data: user::UserUpdateData,
+    user_state: tauri::State<'_, UserState>,
+    db_state: tauri::State<'_, DatabaseState>,
+) -> Result<user::UserDto, String> {
+    user::update_user(user_id, update_data, user_state, db_state).await
+}
+
+#[tauri::command]
+async fn delete_user(
+    user_id: i64,
+    user_state: tauri::State<'_, UserState>,
+    db_state: tauri::State<'_, DatabaseState>,
+) -> Result<(), String> {
+    user::delete_user(user_id, user_state, db_state).await
+}
+
+#[tauri::command]
+async fn get_current_user(
+    db_state: tauri::State<'_, DatabaseState>,
+) -> Result<user::UserDto, String> {
+    user::get_current_user(db_state).await
+}
+
+#[tauri::command]
+async fn use_git_identity(
+    repo_path: Option<String>,
+    user_state: tauri::State<'_, UserState>,
+    db_state: tauri::State<'_, DatabaseState>,
+) -> Result<user::UserDto, String> {
+    user::use_git_identity(repo_path, user_state, db_state).await
+}
+
+#[tauri::command]
+async fn get_permissions(
+    user_state: tauri::State<'_, UserState>,
+) -> Result<Vec<user::PermissionDto>, String> {
+    user::get_permissions(user_state).await
+}
+
+#[tauri::command]
+async fn grant_permission(
+    permission_data: user::PermissionCreationData,
+    user_state: tauri::State<'_, UserState>,
+    db_state: tauri::State<'_, DatabaseState>,
+) -> Result<user::PermissionDto, String> {
+    user::grant_permission(permission_data, user_state, db_state).await
+}
+
+#[tauri::command]
+async fn revoke_permission(
+    permission_id: i64,
+    user_state: tauri::State<'_, UserState>,
+    db_state: tauri::State<'_, DatabaseState>,
+) -> Result<(), String> {
+    user::revoke_permission(permission_id, user_state, db_state).await
+}
+
+// Change order command wrappers
+#[tauri::command]
+async fn get_change_orders(
+    change_order_state: tauri::State<'_, ChangeOrderState>,
+) -> Result<Vec<change_order::ChangeOrderDto>, String> {
+    change_order::get_change_orders(change_order_state).await
+}
+
+#[tauri::command]
+async fn get_change_order(
+    eco_id: i64,
+    change_order_state: tauri::State<'_, ChangeOrderState>,
+) -> Result<change_order::ChangeOrderDto, String> {
+    change_order::get_change_order(eco_id, change_order_state).await
+}
+
+#[tauri::command]
+async fn create_change_order(
+    change_order_data: change_order::ChangeOrderData,
+    change_order_state: tauri::State<'_, ChangeOrderState>,
+    db_state: tauri::State<'_, DatabaseState>,
+) -> Result<change_order::ChangeOrderDto, String> {
+    change_order::create_change_order(change_order_data, change_order_state, db_state).await
+}
+
+#[tauri::command]
+async fn update_change_order(
+    eco_id: i64,
+    change_order_data: change_order::ChangeOrderData,
+    change_order_state: tauri::State<'_, ChangeOrderState>,
+) -> Result<change_order::ChangeOrderDto, String> {
+    change_order::update_change_order(eco_id, change_order_data, change_order_state).await
+}
+
+#[tauri::command]
+async fn get_change_order_revisions(
+    eco_id: i64,
+    change_order_state: tauri::State<'_, ChangeOrderState>,
+) -> Result<Vec<revision::RevisionDto>, String> {
+    change_order::get_change_order_revisions(eco_id, change_order_state).await
+}
+
+#[tauri::command]
+async fn add_revision_to_change_order(
+    eco_id: i64,
+    revision_id: i64,
+    change_order_state: tauri::State<'_, ChangeOrderState>,
+) -> Result<(), String> {
+    change_order::add_revision_to_change_order(eco_id, revision_id, change_order_state).await
+}
+
+#[tauri::command]
+async fn remove_revision_from_change_order(
+    eco_id: i64,
+    revision_id: i64,
+    change_order_state: tauri::State<'_, ChangeOrderState>,
+) -> Result<(), String> {
+    change_order::remove_revision_from_change_order(eco_id, revision_id, change_order_state).await
+}
+
+#[tauri::command]
+async fn evaluate_change_order(
+    eco_id: i64,
+    change_order_state: tauri::State<'_, ChangeOrderState>,
+) -> Result<String, String> {
+    change_order::evaluate_change_order(eco_id, change_order_state).await
+}
+
+#[tauri::command]
+async fn submit_change_order(
+    eco_id: i64,
+    reviewers: Vec<String>,
+    db_state: tauri::State<'_, DatabaseState>,
+) -> Result<(), String> {
+    change_order::submit_change_order(eco_id, reviewers, db_state).await
+}
+
+#[tauri::command]
+async fn approve_change_order(
+    eco_id: i64,
+    comments: Option<String>,
+    db_state: tauri::State<'_, DatabaseState>,
+) -> Result<(), String> {
+    change_order::approve_change_order(eco_id, comments, db_state).await
+}
+
+#[tauri::command]
+async fn reject_change_order(
+    eco_id: i64,
+    comments: Option<String>,
+    db_state: tauri::State<'_, DatabaseState>,
+) -> Result<(), String> {
+    change_order::reject_change_order(eco_id, comments, db_state).await
+}
+
+#[tauri::command]
+async fn release_change_order(
+    eco_id: i64,
+    db_state: tauri::State<'_, DatabaseState>,
+) -> Result<String, String> {
+    change_order::release_change_order(eco_id, db_state).await
+}
+
+#[tauri::command]
+async fn cancel_change_order(
+    eco_id: i64,
+    db_state: tauri::State<'_, DatabaseState>,
+) -> Result<(), String> {
+    change_order::cancel_change_order(eco_id, db_state).await
+}
+
+// Deviation command wrappers
+#[tauri::command]
+async fn get_deviations(
+    deviation_state: tauri::State<'_, DeviationState>,
+) -> Result<Vec<deviation::DeviationDto>, String> {
+    deviation::get_deviations(deviation_state).await
+}
+
+#[tauri::command]
+async fn create_deviation(
+    deviation_data: deviation::DeviationCreationData,
+    deviation_state: tauri::State<'_, DeviationState>,
+    db_state: tauri::State<'_, DatabaseState>,
+) -> Result<deviation::DeviationDto, String> {
+    deviation::create_deviation(deviation_data, deviation_state, db_state).await
+}
+
+#[tauri::command]
+async fn get_deviation_items(
+    deviation_id: i64,
+    deviation_state: tauri::State<'_, DeviationState>,
+) -> Result<Vec<deviation::DeviationItemDto>, String> {
+    deviation::get_deviation_items(deviation_id, deviation_state).await
+}
+
+#[tauri::command]
+async fn add_deviation_item(
+    deviation_id: i64,
+    part_id: i64,
+    revision_id: Option<i64>,
+    deviation_state: tauri::State<'_, DeviationState>,
+) -> Result<(), String> {
+    deviation::add_deviation_item(deviation_id, part_id, revision_id, deviation_state).await
+}
+
+#[tauri::command]
+async fn remove_deviation_item(
+    deviation_id: i64,
+    part_id: i64,
+    revision_id: Option<i64>,
+    deviation_state: tauri::State<'_, DeviationState>,
+) -> Result<(), String> {
+    deviation::remove_deviation_item(deviation_id, part_id, revision_id, deviation_state).await
+}
+
+#[tauri::command]
+async fn decide_deviation(
+    deviation_id: i64,
+    approved: bool,
+    deviation_state: tauri::State<'_, DeviationState>,
+    db_state: tauri::State<'_, DatabaseState>,
+) -> Result<deviation::DeviationDto, String> {
+    deviation::decide_deviation(deviation_id, approved, deviation_state, db_state).await
+}
+
+#[tauri::command]
+async fn close_deviation(
+    deviation_id: i64,
+    deviation_state: tauri::State<'_, DeviationState>,
+) -> Result<(), String> {
+    deviation::close_deviation(deviation_id, deviation_state).await
+}
+
+#[tauri::command]
+async fn record_deviation_usage(
+    deviation_id: i64,
+    quantity: i64,
+    deviation_state: tauri::State<'_, DeviationState>,
+) -> Result<deviation::DeviationDto, String> {
+    deviation::record_deviation_usage(deviation_id, quantity, deviation_state).await
+}
+
+#[tauri::command]
+async fn export_bom_csv(
+    revision_id: i64,
+    file_path: String,
+    deviation_state: tauri::State<'_, DeviationState>,
+) -> Result<(), String> {
+    deviation::export_bom_csv(revision_id, file_path, deviation_state).await
+}
+
+// Change impact command wrappers
+#[tauri::command]
+async fn get_change_flags(
+    revision_id: i64,
+    change_impact_state: tauri::State<'_, ChangeImpactState>,
+) -> Result<change_impact::ChangeFlagsDto, String> {
+    change_impact::get_change_flags(revision_id, change_impact_state).await
+}
+
+#[tauri::command]
+async fn set_change_flags(
+    revision_id: i64,
+    flags: change_impact::ChangeFlagsDto,
+    change_impact_state: tauri::State<'_, ChangeImpactState>,
+) -> Result<(), String> {
+    change_impact::set_change_flags(revision_id, flags, change_impact_state).await
+}
+
+#[tauri::command]
+async fn analyze_change_impact(
+    revision_id: i64,
+    change_impact_state: tauri::State<'_, ChangeImpactState>,
+    db_state: tauri::State<'_, DatabaseState>,
+) -> Result<change_impact::ChangeImpactReportDto, String> {
+    change_impact::analyze_change_impact(revision_id, change_impact_state, db_state).await
+}
+
+#[tauri::command]
+async fn get_review_impact_report(
+    revision_id: i64,
+    change_impact_state: tauri::State<'_, ChangeImpactState>,
+) -> Result<Option<change_impact::ChangeImpactReportDto>, String> {
+    change_impact::get_review_impact_report(revision_id, change_impact_state).await
+}
+
+// Audit command wrappers
+#[tauri::command]
+async fn get_audit_log(
+    filter: Option<audit::AuditFilterDto>,
+    audit_state: tauri::State<'_, AuditState>,
+) -> Result<Vec<audit::AuditEntryDto>, String> {
+    audit::get_audit_log(filter, audit_state).await
+}
+
+#[tauri::command]
+async fn verify_audit_log(
+    audit_state: tauri::State<'_, AuditState>,
+) -> Result<audit::AuditVerificationDto, String> {
+    audit::verify_audit_log(audit_state).await
+}
+
 // Property command wrappers
 #[tauri::command]
 async fn get_part_properties(
@@ -711,7 +1562,7 @@ fn main() {
             app.manage(db_state);
             
             // Initialize the workspace state
-            app.manage(init_workspace_state());
+            app.manage(init_workspace_state(connection_manager.clone()));
             
             // Initialize the workflow state
             app.manage(init_workflow_state(connection_manager.clone()));
@@ -734,6 +1585,30 @@ fn main() {
             // Initialize the revision state
             app.manage(init_revision_state(connection_manager.clone()));
             
+            // Initialize the supplier state
+            app.manage(init_supplier_state(connection_manager.clone()));
+            
+            // Initialize the inventory state
+            app.manage(init_inventory_state(connection_manager.clone()));
+            
+            // Initialize the serialized unit state
+            app.manage(init_serialized_unit_state(connection_manager.clone()));
+            
+            // Initialize the user state
+            app.manage(init_user_state(connection_manager.clone()));
+            
+            // Initialize the change order state
+            app.manage(init_change_order_state(connection_manager.clone()));
+            
+            // Initialize the deviation state
+            app.manage(init_deviation_state(connection_manager.clone()));
+            
+            // Initialize the change impact state
+            app.manage(init_change_impact_state(connection_manager.clone()));
+            
+            // Initialize the audit state
+            app.manage(init_audit_state(connection_manager.clone()));
+            
             println!("Application states initialized successfully");
             
             // Log that the application has started
@@ -760,6 +1635,9 @@ fn main() {
             update_part,
             change_part_status,
             delete_part,
+            execute_revision_transition,
+            get_part_working_path,
+            verify_release_signatures,
             
             // Workspace commands
             get_workspaces,
@@ -768,7 +1646,10 @@ fn main() {
             update_workspace,
             delete_workspace,
             add_part_to_workspace,
+            set_workspace_part_revision,
             remove_part_from_workspace,
+            activate_workspace,
+            deactivate_workspace,
             
             // Workflow commands
             get_workflows,
@@ -781,6 +1662,19 @@ fn main() {
             get_workflow_states,
             get_initial_state,
             create_default_part_workflow,
+            assign_category_workflow,
+            get_revision_workflow_state,
+            get_available_revision_transitions,
+            get_transition_guards,
+            create_transition_guard,
+            delete_transition_guard,
+            get_transition_actions,
+            create_transition_action,
+            delete_transition_action,
+            check_revision_transition,
+            get_revision_workflow_events,
+            load_workflow_files,
+            export_workflow_files,
             
             // Revision commands
             get_revision,
@@ -791,6 +1685,9 @@ fn main() {
             update_revision,
             update_revision_status,
             delete_revision,
+            get_revision_effectivity,
+            set_revision_effectivity,
+            get_effective_revision,
             
             // Relationship commands
             get_relationship,
@@ -810,6 +1707,21 @@ fn main() {
             delete_approval,
             is_revision_approved,
             submit_for_approval,
+            get_approval_policies,
+            create_approval_policy,
+            delete_approval_policy,
+            get_approval_policy_groups,
+            set_approval_policy_group,
+            remove_approval_policy_group,
+            evaluate_revision_approval,
+            get_revision_signatures,
+            verify_revision_signatures,
+            get_groups,
+            create_group,
+            delete_group,
+            get_group_members,
+            add_group_member,
+            remove_group_member,
             
             // Manufacturer Part commands
             get_manufacturer_part,
@@ -819,6 +1731,78 @@ fn main() {
             update_manufacturer_part,
             delete_manufacturer_part,
             search_manufacturer_parts,
+            get_obsolescence_report,
+            
+            // Supplier commands
+            get_suppliers,
+            get_supplier_offers,
+            create_supplier_offer,
+            delete_supplier_offer,
+            import_supplier_offers_csv,
+            get_bom_cost_rollup,
+            
+            // Inventory commands
+            get_stock_locations,
+            create_stock_location,
+            get_part_stock,
+            get_part_stock_level,
+            get_location_stock,
+            record_inventory_transaction,
+            get_part_inventory_transactions,
+            reserve_stock,
+            release_stock_reservation,
+            get_clear_to_build,
+            
+            // Serialized unit commands
+            create_serialized_unit,
+            get_serialized_unit,
+            get_unit_as_built,
+            add_as_built_component,
+            get_revision_units,
+            get_units_containing_lot,
+            
+            // User commands
+            get_users,
+            create_user,
+            update_user,
+            delete_user,
+            get_current_user,
+            use_git_identity,
+            get_permissions,
+            grant_permission,
+            revoke_permission,
+            get_change_orders,
+            get_change_order,
+            create_change_order,
+            update_change_order,
+            get_change_order_revisions,
+            add_revision_to_change_order,
+            remove_revision_from_change_order,
+            evaluate_change_order,
+            submit_change_order,
+            approve_change_order,
+            reject_change_order,
+            release_change_order,
+            cancel_change_order,
+            get_deviations,
+            create_deviation,
+            get_deviation_items,
+            add_deviation_item,
+            remove_deviation_item,
+            decide_deviation,
+            close_deviation,
+            record_deviation_usage,
+            export_bom_csv,
+            
+            // Change impact commands
+            get_change_flags,
+            set_change_flags,
+            analyze_change_impact,
+            get_review_impact_report,
+            
+            // Audit commands
+            get_audit_log,
+            verify_audit_log,
             
             // Property commands
             get_property,
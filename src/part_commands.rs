@@ -215,9 +215,19 @@ pub fn init_database_state() -> DatabaseState {
     let git_manager = GitBackendManager::new(git_config, auth_config)
         .expect("Failed to create git backend manager");
     
-    // Create a default user for system operations
+    // The current user is the stored user of the git identity of this machine, or a system user
+    // without one; new identities are designers until an administrator grants them more
     use implexa::database::part_management::{User, UserRole};
-    let system_user = User::new("system".to_string(), UserRole::Admin);
+    use implexa::database::user::UserManager;
+    let system_user = match git2::Config::open_default()
+        .ok()
+        .and_then(|config| User::from_git_config(&config, UserRole::Designer))
+    {
+        Some(identity) => UserManager::new(&connection_manager)
+            .resolve_git_identity(&identity.username, identity.email.as_deref())
+            .unwrap_or(identity),
+        None => User::new("system".to_string(), UserRole::Designer),
+    };
     
     // Create the part manager with 'static lifetime
     let static_connection_manager: &'static ConnectionManager = Box::leak(Box::new(connection_manager.clone()));
@@ -139,7 +139,7 @@ pub async fn get_latest_released_revision(
     // since there's no direct method for this in the RevisionManager
     let revision = revision_state.connection_manager.execute::<_, _, DatabaseError>(|conn| {
         conn.query_row(
-            "SELECT revision_id, part_id, version, status, created_date, created_by, commit_hash
+            "SELECT revision_id, part_id, version, status, created_date, created_by, commit_hash, approved_commit_hash
              FROM Revisions
              WHERE part_id = ?1 AND status = 'Released'
              ORDER BY created_date DESC
@@ -163,6 +163,7 @@ pub async fn get_latest_released_revision(
                     created_date,
                     created_by: row.get(5)?,
                     commit_hash: row.get(6)?,
+                    approved_commit_hash: row.get(7)?,
                 })
             },
         )